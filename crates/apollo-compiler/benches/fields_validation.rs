@@ -114,11 +114,70 @@ fn bench_many_types(c: &mut Criterion) {
     });
 }
 
+fn bench_many_fragment_spreads(c: &mut Criterion) {
+    let schema = Schema::parse_and_validate(
+        "
+        type Query { user: User }
+        type User { id: ID! name: String friends: [User] }
+    ",
+        "schema.graphql",
+    )
+    .unwrap();
+    // Every fragment spreads the same nested fragments,
+    // so their fields are merged together in many places.
+    let leaves: String = (0..10).map(|i| format!("...Leaf{i} ")).collect();
+    let fragments: String = (0..100)
+        .map(|i| format!("fragment F{i} on User {{ id name friends {{ {leaves} }} }}\n"))
+        .chain(
+            (0..10)
+                .map(|i| format!("fragment Leaf{i} on User {{ id name friends {{ id name }} }}\n")),
+        )
+        .collect();
+    let spreads: String = (0..100).map(|i| format!("...F{i} ")).collect();
+    let query = format!("{{ user {{ {spreads} friends {{ {spreads} }} }} }}\n{fragments}");
+
+    c.bench_function("many_fragment_spreads", move |b| {
+        b.iter(|| {
+            let doc =
+                ExecutableDocument::parse_and_validate(&schema, &query, "query.graphql").unwrap();
+            black_box(doc);
+        });
+    });
+}
+
+fn bench_many_repeated_fragment_spreads(c: &mut Criterion) {
+    let schema = Schema::parse_and_validate(
+        "
+        type Query { user: User }
+        type User { id: ID! name: String friends: [User] }
+    ",
+        "schema.graphql",
+    )
+    .unwrap();
+    let query = format!(
+        "
+        query {{ user {{ {spreads} }} }}
+        fragment Friend on User {{ id friends {{ id name }} }}
+    ",
+        spreads = "friends { ...Friend } ".repeat(1_000)
+    );
+
+    c.bench_function("many_repeated_fragment_spreads", move |b| {
+        b.iter(|| {
+            let doc =
+                ExecutableDocument::parse_and_validate(&schema, &query, "query.graphql").unwrap();
+            black_box(doc);
+        });
+    });
+}
+
 criterion_group!(
     fields,
     bench_many_same_field,
     bench_many_same_nested_field,
     bench_many_arguments,
     bench_many_types,
+    bench_many_fragment_spreads,
+    bench_many_repeated_fragment_spreads,
 );
 criterion_main!(fields);
@@ -47,5 +47,67 @@ fn bench_big_schema_many_fragments(c: &mut Criterion) {
     });
 }
 
-criterion_group!(fragments, bench_big_schema_many_fragments,);
+fn bench_large_union(c: &mut Criterion) {
+    const NUM_MEMBERS: usize = 1_000;
+
+    let mut sdl = String::new();
+    let mut members = Vec::new();
+    for m in 0..NUM_MEMBERS {
+        _ = writeln!(&mut sdl, r#"type Member{m} {{ field: Int! }}"#);
+        members.push(format!("Member{m}"));
+    }
+    _ = writeln!(&mut sdl, "union Big = {}", members.join(" | "));
+    _ = writeln!(&mut sdl, "type Query {{ big: Big }}");
+
+    let schema = Schema::parse_and_validate(sdl, "schema.graphql").unwrap();
+    let mut selection = String::new();
+    for m in 0..NUM_MEMBERS {
+        _ = writeln!(&mut selection, "  ... on Member{m} {{ field }}");
+    }
+    let query = format!("query {{ big {{\n{selection}  __typename\n}} }}");
+
+    c.bench_function("large_union", move |b| {
+        b.iter(|| {
+            let doc =
+                ExecutableDocument::parse_and_validate(&schema, &query, "query.graphql").unwrap();
+            black_box(doc);
+        });
+    });
+}
+
+fn bench_many_distinct_fragments(c: &mut Criterion) {
+    const NUM_FRAGMENTS: usize = 4_000;
+
+    let schema = Schema::parse_and_validate(
+        "type Query { user: User }
+        type User { id: ID! name: String friends: [User] }",
+        "schema.graphql",
+    )
+    .unwrap();
+    let mut selection = String::new();
+    let mut fragments = String::new();
+    for f in 0..NUM_FRAGMENTS {
+        _ = writeln!(&mut selection, "  friends {{ ...frag{f} }}");
+        _ = writeln!(
+            &mut fragments,
+            "fragment frag{f} on User {{ id friends {{ id name }} }}"
+        );
+    }
+    let query = format!("query {{ user {{\n{selection}}} }}\n{fragments}");
+
+    c.bench_function("many_distinct_fragments", move |b| {
+        b.iter(|| {
+            let doc =
+                ExecutableDocument::parse_and_validate(&schema, &query, "query.graphql").unwrap();
+            black_box(doc);
+        });
+    });
+}
+
+criterion_group!(
+    fragments,
+    bench_big_schema_many_fragments,
+    bench_large_union,
+    bench_many_distinct_fragments,
+);
 criterion_main!(fragments);
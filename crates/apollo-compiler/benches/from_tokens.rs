@@ -0,0 +1,33 @@
+use apollo_compiler::parser::Parser;
+use criterion::*;
+
+fn parse_ast(skip_syntax_tree: bool, schema: &str, query: &str) {
+    let mut parser = Parser::new().skip_syntax_tree(skip_syntax_tree);
+    let schema = parser.parse_ast(schema, "schema.graphql").unwrap();
+    let doc = parser.parse_ast(query, "query.graphql").unwrap();
+    black_box((schema, doc));
+}
+
+fn bench_parse_ast(c: &mut Criterion, name: &str, schema: &'static str, query: &'static str) {
+    c.bench_function(&format!("{name} parse_ast with syntax tree"), move |b| {
+        b.iter(|| parse_ast(false, schema, query))
+    });
+    c.bench_function(&format!("{name} parse_ast from tokens"), move |b| {
+        b.iter(|| parse_ast(true, schema, query))
+    });
+}
+
+fn bench_simple_query(c: &mut Criterion) {
+    let query = include_str!("testdata/simple_query.graphql");
+    let schema = include_str!("testdata/simple_schema.graphql");
+    bench_parse_ast(c, "simple_query", schema, query);
+}
+
+fn bench_supergraph(c: &mut Criterion) {
+    let schema = include_str!("testdata/supergraph.graphql");
+    let query = include_str!("testdata/supergraph_query.graphql");
+    bench_parse_ast(c, "supergraph", schema, query);
+}
+
+criterion_group!(benches, bench_supergraph, bench_simple_query);
+criterion_main!(benches);
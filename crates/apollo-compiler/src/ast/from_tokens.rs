@@ -0,0 +1,814 @@
+//! Building an AST directly from lexer tokens, without a concrete syntax tree.
+//!
+//! This only handles syntactically valid documents. On any lexical or syntax error,
+//! or when a limit is reached, parsing returns `None` and the caller parses again
+//! through `apollo_parser` to get the same diagnostics as it would have otherwise.
+//! For valid documents the result, including source locations, is the same as [`from_cst`].
+//!
+//! [`from_cst`]: super::from_cst
+
+use crate::ast;
+use crate::parser::FileId;
+use crate::parser::SourceSpan;
+use crate::Name;
+use crate::Node;
+use apollo_parser::Lexer;
+use apollo_parser::LimitTracker;
+use apollo_parser::Token;
+use apollo_parser::TokenKind;
+use rowan::TextRange;
+use rowan::TextSize;
+
+pub(crate) struct ParsedDocument {
+    pub(crate) definitions: Vec<ast::Definition>,
+    pub(crate) recursion_reached: usize,
+    pub(crate) tokens_reached: usize,
+}
+
+pub(crate) fn parse_document(
+    source_text: &str,
+    file_id: FileId,
+    recursion_limit: Option<usize>,
+    token_limit: Option<usize>,
+) -> Option<ParsedDocument> {
+    let mut lexer = Lexer::new(source_text);
+    if let Some(limit) = token_limit {
+        lexer = lexer.with_limit(limit)
+    }
+    let mut parser = TokenParser {
+        lexer,
+        file_id,
+        // An end of file token, replaced by the first call to `bump` below
+        current: Lexer::new("").next()?.ok()?,
+        previous_end: 0,
+        tokens: 0,
+        // Same default as `apollo_parser`, so both parse paths reject the same documents
+        recursion: LimitTracker::new(
+            recursion_limit.unwrap_or(apollo_parser::Parser::DEFAULT_RECURSION_LIMIT),
+        ),
+    };
+    parser.bump()?;
+    let mut definitions = Vec::new();
+    while !parser.at(TokenKind::Eof) {
+        definitions.push(parser.definition()?);
+    }
+    Some(ParsedDocument {
+        definitions,
+        recursion_reached: parser.recursion.high,
+        tokens_reached: parser.tokens,
+    })
+}
+
+struct TokenParser<'a> {
+    lexer: Lexer<'a>,
+    file_id: FileId,
+    /// The next significant token
+    current: Token<'a>,
+    /// End offset of the last significant token consumed,
+    /// which is where a node that ends there ends.
+    previous_end: usize,
+    /// Number of tokens lexed so far, including ignored tokens
+    tokens: usize,
+    recursion: LimitTracker,
+}
+
+impl<'a> TokenParser<'a> {
+    /// Consume the current token and move to the next significant one.
+    /// Returns `None` on a lexer error.
+    fn bump(&mut self) -> Option<Token<'a>> {
+        loop {
+            let token = self.lexer.next()?.ok()?;
+            self.tokens += 1;
+            if !matches!(
+                token.kind(),
+                TokenKind::Whitespace | TokenKind::Comment | TokenKind::Comma
+            ) {
+                let previous = std::mem::replace(&mut self.current, token);
+                self.previous_end = previous.index() + previous.data().len();
+                return Some(previous);
+            }
+        }
+    }
+
+    fn at(&self, kind: TokenKind) -> bool {
+        self.current.kind() == kind
+    }
+
+    fn at_keyword(&self, keyword: &str) -> bool {
+        self.at(TokenKind::Name) && self.current.data() == keyword
+    }
+
+    fn eat(&mut self, kind: TokenKind) -> Option<bool> {
+        if self.at(kind) {
+            self.bump()?;
+            Some(true)
+        } else {
+            Some(false)
+        }
+    }
+
+    fn expect(&mut self, kind: TokenKind) -> Option<Token<'a>> {
+        if self.at(kind) {
+            self.bump()
+        } else {
+            None
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Option<()> {
+        if self.at_keyword(keyword) {
+            self.bump()?;
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    fn start(&self) -> usize {
+        self.current.index()
+    }
+
+    /// Location from `start` to the end of the last consumed token
+    fn span(&self, start: usize) -> Option<SourceSpan> {
+        Some(SourceSpan {
+            file_id: self.file_id,
+            text_range: TextRange::new(
+                TextSize::try_from(start).ok()?,
+                TextSize::try_from(self.previous_end).ok()?,
+            ),
+        })
+    }
+
+    fn node<T>(&self, start: usize, node: T) -> Option<Node<T>> {
+        Some(Node::new_parsed(node, self.span(start)?))
+    }
+
+    /// Called where `apollo_parser` checks its recursion limit: for each selection set,
+    /// list type, list item, and object field value
+    fn enter(&mut self) -> Option<()> {
+        (!self.recursion.check_and_increment()).then_some(())
+    }
+
+    fn leave(&mut self) {
+        self.recursion.decrement()
+    }
+
+    /// Parse `open item+ close`
+    fn list<T>(
+        &mut self,
+        open: TokenKind,
+        close: TokenKind,
+        mut item: impl FnMut(&mut Self) -> Option<T>,
+    ) -> Option<Vec<T>> {
+        self.expect(open)?;
+        let mut items = vec![item(self)?];
+        while !self.eat(close)? {
+            items.push(item(self)?)
+        }
+        Some(items)
+    }
+
+    fn name(&mut self) -> Option<Name> {
+        let start = self.start();
+        let token = self.expect(TokenKind::Name)?;
+        Some(
+            Name::new(token.data())
+                .ok()?
+                .with_location(self.span(start)?),
+        )
+    }
+
+    fn description(&mut self) -> Option<Option<Node<str>>> {
+        if !self.at(TokenKind::StringValue) {
+            return Some(None);
+        }
+        let start = self.start();
+        let value = self.bump()?.string_value()?;
+        Some(Some(Node::new_str_parsed(&value, self.span(start)?)))
+    }
+
+    fn definition(&mut self) -> Option<ast::Definition> {
+        use ast::Definition as D;
+
+        let start = self.start();
+        let description = self.description()?;
+        if self.at(TokenKind::LCurly) && description.is_none() {
+            let def = self.operation_definition()?;
+            return Some(D::OperationDefinition(self.node(start, def)?));
+        }
+        if !self.at(TokenKind::Name) {
+            return None;
+        }
+        Some(match (self.current.data(), description) {
+            ("query" | "mutation" | "subscription", None) => {
+                let def = self.operation_definition()?;
+                D::OperationDefinition(self.node(start, def)?)
+            }
+            ("fragment", None) => {
+                let def = self.fragment_definition()?;
+                D::FragmentDefinition(self.node(start, def)?)
+            }
+            ("extend", None) => self.extension(start)?,
+            ("directive", description) => {
+                let def = self.directive_definition(description)?;
+                D::DirectiveDefinition(self.node(start, def)?)
+            }
+            ("schema", description) => {
+                self.bump()?;
+                let def = ast::SchemaDefinition {
+                    description,
+                    directives: self.directives(true)?,
+                    root_operations: self.root_operations()?,
+                };
+                D::SchemaDefinition(self.node(start, def)?)
+            }
+            ("scalar", description) => {
+                self.bump()?;
+                let def = ast::ScalarTypeDefinition {
+                    description,
+                    name: self.name()?,
+                    directives: self.directives(true)?,
+                };
+                D::ScalarTypeDefinition(self.node(start, def)?)
+            }
+            ("type", description) => {
+                self.bump()?;
+                let def = ast::ObjectTypeDefinition {
+                    description,
+                    name: self.name()?,
+                    implements_interfaces: self.implements_interfaces()?,
+                    directives: self.directives(true)?,
+                    fields: self.fields_definition()?,
+                };
+                D::ObjectTypeDefinition(self.node(start, def)?)
+            }
+            ("interface", description) => {
+                self.bump()?;
+                let def = ast::InterfaceTypeDefinition {
+                    description,
+                    name: self.name()?,
+                    implements_interfaces: self.implements_interfaces()?,
+                    directives: self.directives(true)?,
+                    fields: self.fields_definition()?,
+                };
+                D::InterfaceTypeDefinition(self.node(start, def)?)
+            }
+            ("union", description) => {
+                self.bump()?;
+                let def = ast::UnionTypeDefinition {
+                    description,
+                    name: self.name()?,
+                    directives: self.directives(true)?,
+                    members: self.union_members()?,
+                };
+                D::UnionTypeDefinition(self.node(start, def)?)
+            }
+            ("enum", description) => {
+                self.bump()?;
+                let def = ast::EnumTypeDefinition {
+                    description,
+                    name: self.name()?,
+                    directives: self.directives(true)?,
+                    values: self.enum_values_definition()?,
+                };
+                D::EnumTypeDefinition(self.node(start, def)?)
+            }
+            ("input", description) => {
+                self.bump()?;
+                let def = ast::InputObjectTypeDefinition {
+                    description,
+                    name: self.name()?,
+                    directives: self.directives(true)?,
+                    fields: self.input_fields_definition()?,
+                };
+                D::InputObjectTypeDefinition(self.node(start, def)?)
+            }
+            _ => return None,
+        })
+    }
+
+    fn extension(&mut self, start: usize) -> Option<ast::Definition> {
+        use ast::Definition as D;
+
+        self.expect_keyword("extend")?;
+        if !self.at(TokenKind::Name) {
+            return None;
+        }
+        // Each kind of extension must extend something
+        Some(match self.current.data() {
+            "schema" => {
+                self.bump()?;
+                let def = ast::SchemaExtension {
+                    directives: self.directives(true)?,
+                    root_operations: if self.at(TokenKind::LCurly) {
+                        self.root_operations()?
+                    } else {
+                        Vec::new()
+                    },
+                };
+                if def.directives.is_empty() && def.root_operations.is_empty() {
+                    return None;
+                }
+                D::SchemaExtension(self.node(start, def)?)
+            }
+            "scalar" => {
+                self.bump()?;
+                let def = ast::ScalarTypeExtension {
+                    name: self.name()?,
+                    directives: self.directives(true)?,
+                };
+                if def.directives.is_empty() {
+                    return None;
+                }
+                D::ScalarTypeExtension(self.node(start, def)?)
+            }
+            "type" => {
+                self.bump()?;
+                let def = ast::ObjectTypeExtension {
+                    name: self.name()?,
+                    implements_interfaces: self.implements_interfaces()?,
+                    directives: self.directives(true)?,
+                    fields: self.fields_definition()?,
+                };
+                if def.implements_interfaces.is_empty()
+                    && def.directives.is_empty()
+                    && def.fields.is_empty()
+                {
+                    return None;
+                }
+                D::ObjectTypeExtension(self.node(start, def)?)
+            }
+            "interface" => {
+                self.bump()?;
+                let def = ast::InterfaceTypeExtension {
+                    name: self.name()?,
+                    implements_interfaces: self.implements_interfaces()?,
+                    directives: self.directives(true)?,
+                    fields: self.fields_definition()?,
+                };
+                if def.implements_interfaces.is_empty()
+                    && def.directives.is_empty()
+                    && def.fields.is_empty()
+                {
+                    return None;
+                }
+                D::InterfaceTypeExtension(self.node(start, def)?)
+            }
+            "union" => {
+                self.bump()?;
+                let def = ast::UnionTypeExtension {
+                    name: self.name()?,
+                    directives: self.directives(true)?,
+                    members: self.union_members()?,
+                };
+                if def.directives.is_empty() && def.members.is_empty() {
+                    return None;
+                }
+                D::UnionTypeExtension(self.node(start, def)?)
+            }
+            "enum" => {
+                self.bump()?;
+                let def = ast::EnumTypeExtension {
+                    name: self.name()?,
+                    directives: self.directives(true)?,
+                    values: self.enum_values_definition()?,
+                };
+                if def.directives.is_empty() && def.values.is_empty() {
+                    return None;
+                }
+                D::EnumTypeExtension(self.node(start, def)?)
+            }
+            "input" => {
+                self.bump()?;
+                let def = ast::InputObjectTypeExtension {
+                    name: self.name()?,
+                    directives: self.directives(true)?,
+                    fields: self.input_fields_definition()?,
+                };
+                if def.directives.is_empty() && def.fields.is_empty() {
+                    return None;
+                }
+                D::InputObjectTypeExtension(self.node(start, def)?)
+            }
+            _ => return None,
+        })
+    }
+
+    fn operation_definition(&mut self) -> Option<ast::OperationDefinition> {
+        if self.at(TokenKind::LCurly) {
+            return Some(ast::OperationDefinition {
+                operation_type: ast::OperationType::Query,
+                name: None,
+                variables: Vec::new(),
+                directives: ast::DirectiveList::new(),
+                selection_set: self.selection_set()?,
+            });
+        }
+        let operation_type = self.operation_type()?;
+        let name = if self.at(TokenKind::Name) {
+            Some(self.name()?)
+        } else {
+            None
+        };
+        let variables = if self.at(TokenKind::LParen) {
+            self.list(
+                TokenKind::LParen,
+                TokenKind::RParen,
+                Self::variable_definition,
+            )?
+        } else {
+            Vec::new()
+        };
+        Some(ast::OperationDefinition {
+            operation_type,
+            name,
+            variables,
+            directives: self.directives(false)?,
+            selection_set: self.selection_set()?,
+        })
+    }
+
+    fn operation_type(&mut self) -> Option<ast::OperationType> {
+        let operation_type = match self.current.data() {
+            "query" => ast::OperationType::Query,
+            "mutation" => ast::OperationType::Mutation,
+            "subscription" => ast::OperationType::Subscription,
+            _ => return None,
+        };
+        self.expect(TokenKind::Name)?;
+        Some(operation_type)
+    }
+
+    fn variable_definition(&mut self) -> Option<Node<ast::VariableDefinition>> {
+        let start = self.start();
+        self.expect(TokenKind::Dollar)?;
+        let name = self.name()?;
+        self.expect(TokenKind::Colon)?;
+        let def = ast::VariableDefinition {
+            name,
+            ty: self.type_node()?,
+            default_value: self.default_value()?,
+            directives: self.directives(true)?,
+        };
+        self.node(start, def)
+    }
+
+    fn fragment_definition(&mut self) -> Option<ast::FragmentDefinition> {
+        self.expect_keyword("fragment")?;
+        if self.at_keyword("on") {
+            return None;
+        }
+        let name = self.name()?;
+        self.expect_keyword("on")?;
+        Some(ast::FragmentDefinition {
+            name,
+            type_condition: self.name()?,
+            directives: self.directives(false)?,
+            selection_set: self.selection_set()?,
+        })
+    }
+
+    fn selection_set(&mut self) -> Option<Vec<ast::Selection>> {
+        self.expect(TokenKind::LCurly)?;
+        self.enter()?;
+        let mut selections = vec![self.selection()?];
+        while !self.at(TokenKind::RCurly) {
+            selections.push(self.selection()?)
+        }
+        self.leave();
+        self.expect(TokenKind::RCurly)?;
+        Some(selections)
+    }
+
+    fn selection(&mut self) -> Option<ast::Selection> {
+        let start = self.start();
+        if self.eat(TokenKind::Spread)? {
+            if self.at(TokenKind::Name) && !self.at_keyword("on") {
+                let spread = ast::FragmentSpread {
+                    fragment_name: self.name()?,
+                    directives: self.directives(false)?,
+                };
+                return Some(ast::Selection::FragmentSpread(self.node(start, spread)?));
+            }
+            let type_condition = if self.at_keyword("on") {
+                self.bump()?;
+                Some(self.name()?)
+            } else {
+                None
+            };
+            let inline = ast::InlineFragment {
+                type_condition,
+                directives: self.directives(false)?,
+                selection_set: self.selection_set()?,
+            };
+            return Some(ast::Selection::InlineFragment(self.node(start, inline)?));
+        }
+        let mut name = self.name()?;
+        let alias = if self.eat(TokenKind::Colon)? {
+            Some(std::mem::replace(&mut name, self.name()?))
+        } else {
+            None
+        };
+        let field = ast::Field {
+            alias,
+            name,
+            arguments: self.arguments(false)?,
+            directives: self.directives(false)?,
+            selection_set: if self.at(TokenKind::LCurly) {
+                self.selection_set()?
+            } else {
+                Vec::new()
+            },
+        };
+        Some(ast::Selection::Field(self.node(start, field)?))
+    }
+
+    fn arguments(&mut self, constant: bool) -> Option<Vec<Node<ast::Argument>>> {
+        if !self.at(TokenKind::LParen) {
+            return Some(Vec::new());
+        }
+        self.list(TokenKind::LParen, TokenKind::RParen, |p| {
+            let start = p.start();
+            let name = p.name()?;
+            p.expect(TokenKind::Colon)?;
+            let value = p.value_node(constant)?;
+            p.node(start, ast::Argument { name, value })
+        })
+    }
+
+    fn directives(&mut self, constant: bool) -> Option<ast::DirectiveList> {
+        let mut directives = ast::DirectiveList::new();
+        while self.at(TokenKind::At) {
+            let start = self.start();
+            self.bump()?;
+            let directive = ast::Directive {
+                name: self.name()?,
+                arguments: self.arguments(constant)?,
+            };
+            directives.push(self.node(start, directive)?);
+        }
+        Some(directives)
+    }
+
+    fn default_value(&mut self) -> Option<Option<Node<ast::Value>>> {
+        Some(if self.eat(TokenKind::Eq)? {
+            Some(self.value_node(true)?)
+        } else {
+            None
+        })
+    }
+
+    fn value_node(&mut self, constant: bool) -> Option<Node<ast::Value>> {
+        let start = self.start();
+        let value = self.value(constant)?;
+        self.node(start, value)
+    }
+
+    fn value(&mut self, constant: bool) -> Option<ast::Value> {
+        Some(match self.current.kind() {
+            TokenKind::Dollar if !constant => {
+                self.bump()?;
+                ast::Value::Variable(self.name()?)
+            }
+            TokenKind::Int => ast::Value::Int(ast::IntValue::new_parsed(self.bump()?.data())),
+            TokenKind::Float => ast::Value::Float(ast::FloatValue::new_parsed(self.bump()?.data())),
+            TokenKind::StringValue => ast::Value::String(self.bump()?.string_value()?),
+            TokenKind::Name => match self.current.data() {
+                "true" => {
+                    self.bump()?;
+                    ast::Value::Boolean(true)
+                }
+                "false" => {
+                    self.bump()?;
+                    ast::Value::Boolean(false)
+                }
+                "null" => {
+                    self.bump()?;
+                    ast::Value::Null
+                }
+                _ => ast::Value::Enum(self.name()?),
+            },
+            TokenKind::LBracket => {
+                self.bump()?;
+                let mut values = Vec::new();
+                while !self.eat(TokenKind::RBracket)? {
+                    self.enter()?;
+                    values.push(self.value_node(constant)?);
+                    self.leave();
+                }
+                ast::Value::List(values)
+            }
+            TokenKind::LCurly => {
+                self.bump()?;
+                let mut fields = Vec::new();
+                while !self.eat(TokenKind::RCurly)? {
+                    // Like in `from_cst`, the location of a field value
+                    // is that of the whole `name: value` pair
+                    let start = self.start();
+                    let name = self.name()?;
+                    self.expect(TokenKind::Colon)?;
+                    self.enter()?;
+                    let value = self.value(constant)?;
+                    self.leave();
+                    fields.push((name, self.node(start, value)?));
+                }
+                ast::Value::Object(fields)
+            }
+            _ => return None,
+        })
+    }
+
+    fn type_node(&mut self) -> Option<Node<ast::Type>> {
+        let start = self.start();
+        let ty = self.ty()?;
+        self.node(start, ty)
+    }
+
+    fn ty(&mut self) -> Option<ast::Type> {
+        if self.eat(TokenKind::LBracket)? {
+            self.enter()?;
+            let item = self.ty()?;
+            self.leave();
+            self.expect(TokenKind::RBracket)?;
+            Some(if self.eat(TokenKind::Bang)? {
+                ast::Type::NonNullList(Box::new(item))
+            } else {
+                ast::Type::List(Box::new(item))
+            })
+        } else {
+            let name = self.name()?;
+            Some(if self.eat(TokenKind::Bang)? {
+                ast::Type::NonNullNamed(name)
+            } else {
+                ast::Type::Named(name)
+            })
+        }
+    }
+
+    fn root_operations(&mut self) -> Option<Vec<Node<(ast::OperationType, ast::NamedType)>>> {
+        self.list(TokenKind::LCurly, TokenKind::RCurly, |p| {
+            let start = p.start();
+            let operation_type = p.operation_type()?;
+            p.expect(TokenKind::Colon)?;
+            let name = p.name()?;
+            p.node(start, (operation_type, name))
+        })
+    }
+
+    fn implements_interfaces(&mut self) -> Option<Vec<ast::NamedType>> {
+        let mut interfaces = Vec::new();
+        if self.at_keyword("implements") {
+            self.bump()?;
+            self.eat(TokenKind::Amp)?;
+            interfaces.push(self.name()?);
+            while self.eat(TokenKind::Amp)? {
+                interfaces.push(self.name()?)
+            }
+        }
+        Some(interfaces)
+    }
+
+    fn union_members(&mut self) -> Option<Vec<ast::NamedType>> {
+        let mut members = Vec::new();
+        if self.eat(TokenKind::Eq)? {
+            self.eat(TokenKind::Pipe)?;
+            members.push(self.name()?);
+            while self.eat(TokenKind::Pipe)? {
+                members.push(self.name()?)
+            }
+        }
+        Some(members)
+    }
+
+    fn fields_definition(&mut self) -> Option<Vec<Node<ast::FieldDefinition>>> {
+        if !self.at(TokenKind::LCurly) {
+            return Some(Vec::new());
+        }
+        self.list(TokenKind::LCurly, TokenKind::RCurly, |p| {
+            let start = p.start();
+            let description = p.description()?;
+            let name = p.name()?;
+            let arguments = p.arguments_definition()?;
+            p.expect(TokenKind::Colon)?;
+            let def = ast::FieldDefinition {
+                description,
+                name,
+                arguments,
+                ty: p.ty()?,
+                directives: p.directives(true)?,
+            };
+            p.node(start, def)
+        })
+    }
+
+    fn arguments_definition(&mut self) -> Option<Vec<Node<ast::InputValueDefinition>>> {
+        if !self.at(TokenKind::LParen) {
+            return Some(Vec::new());
+        }
+        self.list(
+            TokenKind::LParen,
+            TokenKind::RParen,
+            Self::input_value_definition,
+        )
+    }
+
+    fn input_fields_definition(&mut self) -> Option<Vec<Node<ast::InputValueDefinition>>> {
+        if !self.at(TokenKind::LCurly) {
+            return Some(Vec::new());
+        }
+        self.list(
+            TokenKind::LCurly,
+            TokenKind::RCurly,
+            Self::input_value_definition,
+        )
+    }
+
+    fn input_value_definition(&mut self) -> Option<Node<ast::InputValueDefinition>> {
+        let start = self.start();
+        let description = self.description()?;
+        let name = self.name()?;
+        self.expect(TokenKind::Colon)?;
+        let def = ast::InputValueDefinition {
+            description,
+            name,
+            ty: self.type_node()?,
+            default_value: self.default_value()?,
+            directives: self.directives(true)?,
+        };
+        self.node(start, def)
+    }
+
+    fn enum_values_definition(&mut self) -> Option<Vec<Node<ast::EnumValueDefinition>>> {
+        if !self.at(TokenKind::LCurly) {
+            return Some(Vec::new());
+        }
+        self.list(TokenKind::LCurly, TokenKind::RCurly, |p| {
+            let start = p.start();
+            let description = p.description()?;
+            if ["true", "false", "null"].contains(&p.current.data()) {
+                return None;
+            }
+            let def = ast::EnumValueDefinition {
+                description,
+                value: p.name()?,
+                directives: p.directives(true)?,
+            };
+            p.node(start, def)
+        })
+    }
+
+    fn directive_definition(
+        &mut self,
+        description: Option<Node<str>>,
+    ) -> Option<ast::DirectiveDefinition> {
+        self.expect_keyword("directive")?;
+        self.expect(TokenKind::At)?;
+        let name = self.name()?;
+        let arguments = self.arguments_definition()?;
+        let repeatable = self.at_keyword("repeatable");
+        if repeatable {
+            self.bump()?;
+        }
+        self.expect_keyword("on")?;
+        self.eat(TokenKind::Pipe)?;
+        let mut locations = vec![self.directive_location()?];
+        while self.eat(TokenKind::Pipe)? {
+            locations.push(self.directive_location()?)
+        }
+        Some(ast::DirectiveDefinition {
+            description,
+            name,
+            arguments,
+            repeatable,
+            locations,
+        })
+    }
+
+    fn directive_location(&mut self) -> Option<ast::DirectiveLocation> {
+        use ast::DirectiveLocation as L;
+
+        let location = match self.expect(TokenKind::Name)?.data() {
+            "QUERY" => L::Query,
+            "MUTATION" => L::Mutation,
+            "SUBSCRIPTION" => L::Subscription,
+            "FIELD" => L::Field,
+            "FRAGMENT_DEFINITION" => L::FragmentDefinition,
+            "FRAGMENT_SPREAD" => L::FragmentSpread,
+            "INLINE_FRAGMENT" => L::InlineFragment,
+            "VARIABLE_DEFINITION" => L::VariableDefinition,
+            "SCHEMA" => L::Schema,
+            "SCALAR" => L::Scalar,
+            "OBJECT" => L::Object,
+            "FIELD_DEFINITION" => L::FieldDefinition,
+            "ARGUMENT_DEFINITION" => L::ArgumentDefinition,
+            "INTERFACE" => L::Interface,
+            "UNION" => L::Union,
+            "ENUM" => L::Enum,
+            "ENUM_VALUE" => L::EnumValue,
+            "INPUT_OBJECT" => L::InputObject,
+            "INPUT_FIELD_DEFINITION" => L::InputFieldDefinition,
+            _ => return None,
+        };
+        Some(location)
+    }
+}
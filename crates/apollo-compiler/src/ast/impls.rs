@@ -5,6 +5,7 @@ use crate::parser::SourceSpan;
 use crate::schema::SchemaBuilder;
 use crate::validation::DiagnosticList;
 use crate::validation::Valid;
+use crate::validation::ValidationConfig;
 use crate::validation::WithErrors;
 use crate::ExecutableDocument;
 use crate::Schema;
@@ -38,6 +39,24 @@ impl Document {
     }
 
     /// Validate as an executable document, as much as possible without a schema
+    ///
+    /// This is for tools that check operations before a schema is available.
+    /// Rules that only depend on the document itself are checked:
+    ///
+    /// * Operation and fragment names are unique, and an anonymous operation is alone
+    /// * Fragments are defined, used, and do not form cycles
+    /// * Variables are unique, defined, and used
+    /// * Arguments are unique
+    /// * Subscriptions select a single root field, which is not an introspection field
+    /// * Built-in directives (`@skip`, `@include`, …) are used in valid locations,
+    ///   with valid arguments, and at most once per location
+    ///
+    /// Rules that need type information from a schema are skipped:
+    /// whether fields, arguments, types, and other directives exist,
+    /// whether values and variables have the expected types,
+    /// whether fragments can apply to their parent type, leaf field selections,
+    /// and whether fields with the same response name can be merged.
+    /// Directives that are not built-in are assumed to be defined and repeatable.
     pub fn validate_standalone_executable(&self) -> Result<(), DiagnosticList> {
         let mut errors = DiagnosticList::new(self.sources.clone());
         let type_system_definitions_are_errors = true;
@@ -90,6 +109,46 @@ impl Document {
         errors.into_valid_result(document)
     }
 
+    /// Build and validate an executable document from this AST against each of `schemas`,
+    /// such as variants of a graph deployed to different environments.
+    ///
+    /// The document is parsed once, then built and validated separately for each schema
+    /// since names in it resolve to different definitions.
+    /// Returns one list of diagnostics per schema, in the same order as `schemas`.
+    /// An empty list means the document is valid against that schema.
+    ///
+    /// ```rust
+    /// use apollo_compiler::ast;
+    /// use apollo_compiler::validation::ValidationConfig;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let staging =
+    ///     Schema::parse_and_validate("type Query { a: Int, b: Int }", "staging.graphql").unwrap();
+    /// let prod = Schema::parse_and_validate("type Query { a: Int }", "prod.graphql").unwrap();
+    /// let document = ast::Document::parse("{ a b }", "query.graphql").unwrap();
+    /// let results =
+    ///     document.validate_executable_against(&[&staging, &prod], &ValidationConfig::new());
+    /// assert!(results[0].is_empty());
+    /// assert_eq!(results[1].len(), 1);
+    /// ```
+    pub fn validate_executable_against(
+        &self,
+        schemas: &[&Valid<Schema>],
+        config: &ValidationConfig,
+    ) -> Vec<DiagnosticList> {
+        schemas
+            .iter()
+            .map(|schema| {
+                let mut build_errors = DiagnosticList::new(self.sources.clone());
+                let document = self.to_executable_inner(schema, &mut build_errors);
+                build_errors.apply_config(config);
+                let mut errors = document.validation_diagnostics(schema, config);
+                errors.merge(build_errors);
+                errors
+            })
+            .collect()
+    }
+
     pub(crate) fn to_executable_inner(
         &self,
         schema: &Valid<Schema>,
@@ -460,6 +519,14 @@ impl DirectiveDefinition {
         self.arguments.iter().find(|argument| argument.name == name)
     }
 
+    /// Returns the location of the argument definitions, including parentheses.
+    ///
+    /// Returns `None` if there are no arguments,
+    /// or if the directive definition was not parsed from a source file in `sources`.
+    pub fn arguments_location(&self, sources: &SourceMap) -> Option<SourceSpan> {
+        arguments_location(&self.name, &self.arguments, sources)
+    }
+
     serialize_method!();
 }
 
@@ -621,6 +688,14 @@ impl Directive {
             .find_map(|arg| (arg.name == name).then_some(&arg.value))
     }
 
+    /// Returns the location of the arguments list, including parentheses.
+    ///
+    /// Returns `None` if there are no arguments,
+    /// or if the directive was not parsed from a source file in `sources`.
+    pub fn arguments_location(&self, sources: &SourceMap) -> Option<SourceSpan> {
+        arguments_location(&self.name, &self.arguments, sources)
+    }
+
     serialize_method!();
 }
 
@@ -671,6 +746,22 @@ impl DirectiveLocation {
             DirectiveLocation::InputFieldDefinition => "INPUT_FIELD_DEFINITION",
         }
     }
+
+    /// Whether directives can be applied at this location in executable documents,
+    /// as opposed to type system documents
+    pub fn is_executable_location(self) -> bool {
+        matches!(
+            self,
+            DirectiveLocation::Query
+                | DirectiveLocation::Mutation
+                | DirectiveLocation::Subscription
+                | DirectiveLocation::Field
+                | DirectiveLocation::FragmentDefinition
+                | DirectiveLocation::FragmentSpread
+                | DirectiveLocation::InlineFragment
+                | DirectiveLocation::VariableDefinition
+        )
+    }
 }
 
 impl fmt::Debug for DirectiveLocation {
@@ -832,6 +923,14 @@ impl FieldDefinition {
         self.arguments.iter().find(|argument| argument.name == name)
     }
 
+    /// Returns the location of the argument definitions, including parentheses.
+    ///
+    /// Returns `None` if there are no arguments,
+    /// or if the field definition was not parsed from a source file in `sources`.
+    pub fn arguments_location(&self, sources: &SourceMap) -> Option<SourceSpan> {
+        arguments_location(&self.name, &self.arguments, sources)
+    }
+
     serialize_method!();
 }
 
@@ -904,6 +1003,15 @@ impl Field {
         self.alias.as_ref().unwrap_or(&self.name)
     }
 
+    /// Returns the location of the arguments list, including parentheses.
+    /// For example, to replace all arguments of a field with a text edit.
+    ///
+    /// Returns `None` if there are no arguments,
+    /// or if the field was not parsed from a source file in `sources`.
+    pub fn arguments_location(&self, sources: &SourceMap) -> Option<SourceSpan> {
+        arguments_location(&self.name, &self.arguments, sources)
+    }
+
     serialize_method!();
 }
 
@@ -911,6 +1019,48 @@ impl FragmentSpread {
     serialize_method!();
 }
 
+/// Finds the parentheses around `arguments` in source text, given the name that precedes them.
+/// Only ignored tokens can appear between the name and `(`, and between the last argument and `)`.
+fn arguments_location<T>(
+    name: &Name,
+    arguments: &[Node<T>],
+    sources: &SourceMap,
+) -> Option<SourceSpan> {
+    let name = name.location()?;
+    let last = arguments.last()?.location()?;
+    let text = sources.get(&name.file_id())?.source_text();
+    let start = skip_ignored_tokens(text, name.end_offset())?;
+    let end = skip_ignored_tokens(text, last.end_offset())?;
+    if text.as_bytes()[start] != b'(' || text.as_bytes()[end] != b')' {
+        return None;
+    }
+    Some(SourceSpan {
+        file_id: name.file_id(),
+        text_range: rowan::TextRange::new(
+            u32::try_from(start).ok()?.into(),
+            u32::try_from(end + 1).ok()?.into(),
+        ),
+    })
+}
+
+/// Returns the offset of the first token at or after `offset` that is not
+/// [ignored](https://spec.graphql.org/October2021/#sec-Language.Source-Text.Ignored-Tokens).
+fn skip_ignored_tokens(text: &str, mut offset: usize) -> Option<usize> {
+    let bytes = text.as_bytes();
+    loop {
+        match *bytes.get(offset)? {
+            b' ' | b'\t' | b'\n' | b'\r' | b',' => offset += 1,
+            b'#' => {
+                while !matches!(bytes.get(offset)?, b'\n' | b'\r') {
+                    offset += 1
+                }
+            }
+            _ if text[offset..].starts_with('\u{FEFF}') => offset += '\u{FEFF}'.len_utf8(),
+            _ => return Some(offset),
+        }
+    }
+}
+
 impl InlineFragment {
     serialize_method!();
 }
@@ -960,6 +1110,14 @@ impl Value {
         }
     }
 
+    pub fn to_i64(&self) -> Option<i64> {
+        if let Value::Int(value) = self {
+            value.try_to_i64().ok()
+        } else {
+            None
+        }
+    }
+
     pub fn to_bool(&self) -> Option<bool> {
         if let Value::Boolean(value) = *self {
             Some(value)
@@ -1011,7 +1169,7 @@ impl IntValue {
         Self(text.into())
     }
 
-    fn valid_syntax(text: &str) -> bool {
+    pub(crate) fn valid_syntax(text: &str) -> bool {
         match text.strip_prefix('-').unwrap_or(text).as_bytes() {
             [b'0'..=b'9'] => true,
             [b'1'..=b'9', rest @ ..] => rest.iter().all(|b| b.is_ascii_digit()),
@@ -1032,6 +1190,27 @@ impl IntValue {
         self.0.parse()
     }
 
+    /// Converts to `i64`, returning an error on overflow
+    ///
+    /// GraphQL `Int` is 32-bit, but custom scalars such as `BigInt` or `Long`
+    /// may accept larger integer literals.
+    pub fn try_to_i64(&self) -> Result<i64, std::num::ParseIntError> {
+        self.0.parse()
+    }
+
+    /// Converts to `i128`, returning an error on overflow
+    pub fn try_to_i128(&self) -> Result<i128, std::num::ParseIntError> {
+        self.0.parse()
+    }
+
+    /// Converts to an arbitrary-precision decimal, without loss of precision
+    #[cfg(feature = "bigdecimal")]
+    pub fn try_to_big_decimal(
+        &self,
+    ) -> Result<bigdecimal::BigDecimal, bigdecimal::ParseBigDecimalError> {
+        self.0.parse()
+    }
+
     /// Converts to a finite `f64`, returning an error on overflow to infinity
     ///
     /// An `IntValue` signals integer syntax was used, but is also valid in contexts
@@ -1054,7 +1233,7 @@ impl FloatValue {
         Self(text.into())
     }
 
-    fn valid_syntax(text: &str) -> bool {
+    pub(crate) fn valid_syntax(text: &str) -> bool {
         if let Some((mantissa, exponent)) = text.split_once(['e', 'E']) {
             let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
             if !exponent.bytes().all(|b| b.is_ascii_digit()) {
@@ -1089,6 +1268,16 @@ impl FloatValue {
     pub fn try_to_f64(&self) -> Result<f64, FloatOverflowError> {
         try_to_f64(&self.0)
     }
+
+    /// Converts to an arbitrary-precision decimal, without the rounding of [`Self::try_to_f64`].
+    ///
+    /// Returns an error if the exponent is too large to be represented.
+    #[cfg(feature = "bigdecimal")]
+    pub fn try_to_big_decimal(
+        &self,
+    ) -> Result<bigdecimal::BigDecimal, bigdecimal::ParseBigDecimalError> {
+        self.0.parse()
+    }
 }
 
 fn try_to_f64(text: &str) -> Result<f64, FloatOverflowError> {
@@ -37,6 +37,7 @@ use crate::Name;
 use crate::Node;
 
 pub(crate) mod from_cst;
+pub(crate) mod from_tokens;
 pub(crate) mod impls;
 pub(crate) mod serialize;
 
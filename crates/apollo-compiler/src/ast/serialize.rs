@@ -14,6 +14,9 @@ pub struct Serialize<'a, T> {
 pub(crate) struct Config<'a> {
     indent_prefix: Option<&'a str>,
     initial_indent_level: usize,
+    descriptions: bool,
+    pub(crate) sorted: bool,
+    pub(crate) built_ins: bool,
 }
 
 pub(crate) struct State<'config, 'fmt, 'fmt2> {
@@ -44,6 +47,35 @@ impl<'a, T> Serialize<'a, T> {
         self.config.initial_indent_level = initial_indent_level;
         self
     }
+
+    /// Omit descriptions.
+    ///
+    /// By default descriptions are printed, as block strings when indentation is enabled.
+    pub fn no_descriptions(mut self) -> Self {
+        self.config.descriptions = false;
+        self
+    }
+}
+
+impl Serialize<'_, crate::Schema> {
+    /// Print definitions in alphabetical order instead of schema order,
+    /// so that the output does not depend on the order of the input.
+    ///
+    /// The `schema` definition comes first, followed by directive definitions then types.
+    /// Within each definition, fields, arguments, enum values, and input fields are sorted too.
+    /// Extensions of a type are printed right after its definition.
+    pub fn sorted(mut self) -> Self {
+        self.config.sorted = true;
+        self
+    }
+
+    /// Also print definitions of built-in directives, built-in scalars, and introspection types.
+    ///
+    /// By default they are omitted since every schema contains them implicitly.
+    pub fn include_built_ins(mut self) -> Self {
+        self.config.built_ins = true;
+        self
+    }
 }
 
 impl Default for Config<'_> {
@@ -51,6 +83,9 @@ impl Default for Config<'_> {
         Self {
             indent_prefix: Some("  "),
             initial_indent_level: 0,
+            descriptions: true,
+            sorted: false,
+            built_ins: false,
         }
     }
 }
@@ -120,6 +155,10 @@ impl<'config, 'fmt, 'fmt2> State<'config, 'fmt, 'fmt2> {
         Ok(())
     }
 
+    pub(crate) fn config(&self) -> &Config<'config> {
+        &self.config
+    }
+
     pub(crate) fn newlines_enabled(&self) -> bool {
         self.config.indent_prefix.is_some()
     }
@@ -279,10 +318,9 @@ fn serialize_arguments_definition(
                 arg.serialize_impl(state)
             })
         };
-        if arguments
-            .iter()
-            .any(|arg| arg.description.is_some() || !arg.directives.is_empty())
-        {
+        if arguments.iter().any(|arg| {
+            (arg.description.is_some() && state.config.descriptions) || !arg.directives.is_empty()
+        }) {
             serialize_arguments(state)?
         } else {
             state.on_single_line(serialize_arguments)?
@@ -942,6 +980,9 @@ fn can_be_block_string(value: &str) -> bool {
 }
 
 fn serialize_description(state: &mut State, description: &Option<Node<str>>) -> fmt::Result {
+    if !state.config.descriptions {
+        return Ok(());
+    }
     if let Some(description) = description {
         let is_description = true;
         serialize_string_value(state, is_description, description)?;
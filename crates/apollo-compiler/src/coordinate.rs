@@ -180,6 +180,9 @@ pub enum SchemaLookupError<'coord, 'schema> {
     /// The requested type does not exist in the schema.
     #[error("type `{0}` does not exist")]
     MissingType(&'coord NamedType),
+    /// The requested directive is not defined in the schema.
+    #[error("directive `@{0}` is not defined")]
+    MissingDirective(&'coord Name),
     /// The requested field or enum value does not exist on its type.
     #[error("type does not have attribute `{0}`")]
     MissingAttribute(&'coord Name),
@@ -455,7 +458,7 @@ impl DirectiveCoordinate {
         schema
             .directive_definitions
             .get(directive)
-            .ok_or(SchemaLookupError::MissingType(directive))
+            .ok_or(SchemaLookupError::MissingDirective(directive))
     }
 
     /// Look up this directive in a schema.
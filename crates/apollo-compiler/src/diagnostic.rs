@@ -113,6 +113,9 @@ pub struct CliReport<'s> {
     sources: &'s SourceMap,
     colors: ColorGenerator,
     report: ariadne::ReportBuilder<'static, AriadneSpan>,
+    /// Labels added so far, kept for structured output
+    pub(crate) labels: Vec<(SourceSpan, String)>,
+    pub(crate) help: Option<String>,
 }
 
 /// Indicate when to use ANSI colors for printing.
@@ -171,9 +174,12 @@ impl<T: ToCliReport> ToCliReport for &T {
 type AriadneSpan = (FileId, Range<usize>);
 
 /// Translate a SourceSpan into an ariadne span type.
-fn to_span(location: SourceSpan) -> Option<AriadneSpan> {
-    let start = location.offset();
-    let end = location.end_offset();
+fn to_span(sources: &SourceMap, location: SourceSpan) -> Option<AriadneSpan> {
+    let padding = sources
+        .get(&location.file_id)
+        .map_or(0, |source| source.display_padding());
+    let start = location.offset() + padding;
+    let end = location.end_offset() + padding;
     Some((location.file_id, start..end))
 }
 
@@ -203,11 +209,38 @@ impl<'s> CliReport<'s> {
         sources: &'s SourceMap,
         main_location: Option<SourceSpan>,
         color: Color,
+    ) -> Self {
+        Self::builder_with_kind(sources, main_location, color, ReportKind::Error)
+    }
+
+    /// Like [`builder`][Self::builder], for a report labeled as a warning
+    pub(crate) fn warning_builder(
+        sources: &'s SourceMap,
+        main_location: Option<SourceSpan>,
+        color: Color,
+    ) -> Self {
+        Self::builder_with_kind(sources, main_location, color, ReportKind::Warning)
+    }
+
+    /// Like [`builder`][Self::builder], for a report labeled as advice
+    pub(crate) fn advice_builder(
+        sources: &'s SourceMap,
+        main_location: Option<SourceSpan>,
+        color: Color,
+    ) -> Self {
+        Self::builder_with_kind(sources, main_location, color, ReportKind::Advice)
+    }
+
+    fn builder_with_kind(
+        sources: &'s SourceMap,
+        main_location: Option<SourceSpan>,
+        color: Color,
+        kind: ReportKind<'static>,
     ) -> Self {
         let (file_id, range) = main_location
-            .and_then(to_span)
+            .and_then(|location| to_span(sources, location))
             .unwrap_or((FileId::NONE, 0..0));
-        let report = ariadne::Report::build(ReportKind::Error, file_id, range.start);
+        let report = ariadne::Report::build(kind, file_id, range.start);
         let enable_color = match color {
             Color::Never => false,
             // Rely on ariadne's `auto-color` feature, which uses `concolor` to enable colors
@@ -221,6 +254,8 @@ impl<'s> CliReport<'s> {
             sources,
             colors: ColorGenerator::new(),
             report: report.with_config(config),
+            labels: Vec::new(),
+            help: None,
         }
     }
 
@@ -231,7 +266,9 @@ impl<'s> CliReport<'s> {
 
     /// Set the help message for the report, usually a suggestion on how to fix the error.
     pub fn with_help(&mut self, help: impl ToString) {
-        self.report.set_help(help);
+        let help = help.to_string();
+        self.report.set_help(&help);
+        self.help = Some(help);
     }
 
     /// Set a note for the report, providing additional information that isn't related to a
@@ -242,12 +279,16 @@ impl<'s> CliReport<'s> {
 
     /// Add a label at a given location. If the location is `None`, the message is discarded.
     pub fn with_label_opt(&mut self, location: Option<SourceSpan>, message: impl ToString) {
-        if let Some(span) = location.and_then(to_span) {
-            self.report.add_label(
-                ariadne::Label::new(span)
-                    .with_message(message)
-                    .with_color(self.colors.next()),
-            );
+        if let Some(location) = location {
+            let message = message.to_string();
+            if let Some(span) = to_span(self.sources, location) {
+                self.report.add_label(
+                    ariadne::Label::new(span)
+                        .with_message(&message)
+                        .with_color(self.colors.next()),
+                );
+            }
+            self.labels.push((location, message));
         }
     }
 
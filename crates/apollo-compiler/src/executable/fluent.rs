@@ -0,0 +1,306 @@
+//! Fluent builders for defining operations and fragments in code
+
+use crate::ast;
+use crate::executable::Directive;
+use crate::executable::ExecutableDocument;
+use crate::executable::Name;
+use crate::executable::NamedType;
+use crate::executable::OperationType;
+use crate::executable::Type;
+use crate::executable::Value;
+use crate::validation::Valid;
+use crate::validation::WithErrors;
+use crate::Node;
+use crate::Schema;
+
+/// Builds an executable document in code, as returned by [`ExecutableDocument::builder`]
+#[derive(Debug, Clone, Default)]
+pub struct ExecutableDocumentBuilder {
+    document: ast::Document,
+}
+
+/// Builds an operation for [`ExecutableDocumentBuilder::operation`]
+#[derive(Debug, Clone, Default)]
+pub struct OperationBuilder {
+    variables: Vec<Node<ast::VariableDefinition>>,
+    directives: ast::DirectiveList,
+    selection_set: Vec<ast::Selection>,
+}
+
+/// Builds a fragment definition for [`ExecutableDocumentBuilder::fragment`]
+#[derive(Debug, Clone, Default)]
+pub struct FragmentBuilder {
+    directives: ast::DirectiveList,
+    selection_set: Vec<ast::Selection>,
+}
+
+/// Builds a field selection for [`field_with`][OperationBuilder::field_with]
+#[derive(Debug, Clone, Default)]
+pub struct FieldBuilder {
+    alias: Option<Name>,
+    arguments: Vec<Node<ast::Argument>>,
+    directives: ast::DirectiveList,
+    selection_set: Vec<ast::Selection>,
+}
+
+/// Builds an inline fragment for [`inline_fragment`][OperationBuilder::inline_fragment]
+#[derive(Debug, Clone, Default)]
+pub struct InlineFragmentBuilder {
+    directives: ast::DirectiveList,
+    selection_set: Vec<ast::Selection>,
+}
+
+impl ExecutableDocument {
+    /// Returns a builder for defining operations and fragments in code.
+    ///
+    /// Names, types, and values are taken as compiler types
+    /// (see the [`name!`][crate::name!] and [`ty!`][crate::ty!] macros)
+    /// so that no GraphQL syntax needs to be written by string concatenation.
+    /// [`build`][ExecutableDocumentBuilder::build] resolves fields against a schema
+    /// and validates the document.
+    ///
+    /// ```rust
+    /// use apollo_compiler::ast::OperationType;
+    /// use apollo_compiler::ast::Value;
+    /// use apollo_compiler::name;
+    /// use apollo_compiler::ty;
+    /// use apollo_compiler::ExecutableDocument;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse_and_validate(
+    ///     r#"
+    ///     type Query { hero(episode: Episode): Character }
+    ///     interface Character { name: String friends: [Character] }
+    ///     type Droid implements Character { name: String friends: [Character] function: String }
+    ///     enum Episode { NEWHOPE EMPIRE JEDI }
+    ///     "#,
+    ///     "schema.graphql",
+    /// )
+    /// .unwrap();
+    /// let document = ExecutableDocument::builder()
+    ///     .operation(OperationType::Query, Some(name!("Hero")), |op| {
+    ///         op.variable(name!("episode"), ty!(Episode))
+    ///             .field_with(name!("hero"), |field| {
+    ///                 field
+    ///                     .argument(name!("episode"), Value::Variable(name!("episode")))
+    ///                     .fragment_spread(name!("CharacterName"))
+    ///                     .inline_fragment(Some(name!("Droid")), |fragment| {
+    ///                         fragment.field(name!("function"))
+    ///                     })
+    ///             })
+    ///     })
+    ///     .fragment(name!("CharacterName"), name!("Character"), |fragment| {
+    ///         fragment.field(name!("name"))
+    ///     })
+    ///     .build(&schema)
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     document.to_string(),
+    ///     r#"query Hero($episode: Episode) {
+    ///   hero(episode: $episode) {
+    ///     ...CharacterName
+    ///     ... on Droid {
+    ///       function
+    ///     }
+    ///   }
+    /// }
+    ///
+    /// fragment CharacterName on Character {
+    ///   name
+    /// }
+    /// "#
+    /// );
+    /// ```
+    pub fn builder() -> ExecutableDocumentBuilder {
+        ExecutableDocumentBuilder::default()
+    }
+}
+
+impl ExecutableDocumentBuilder {
+    /// Add an operation of the given type, with variables and selections added by the `build` callback
+    pub fn operation(
+        mut self,
+        operation_type: OperationType,
+        name: Option<Name>,
+        build: impl FnOnce(OperationBuilder) -> OperationBuilder,
+    ) -> Self {
+        let operation = build(OperationBuilder::default());
+        self.document
+            .definitions
+            .push(ast::Definition::OperationDefinition(Node::new(
+                ast::OperationDefinition {
+                    operation_type,
+                    name,
+                    variables: operation.variables,
+                    directives: operation.directives,
+                    selection_set: operation.selection_set,
+                },
+            )));
+        self
+    }
+
+    /// Add a fragment definition, with selections added by the `build` callback
+    pub fn fragment(
+        mut self,
+        name: Name,
+        type_condition: NamedType,
+        build: impl FnOnce(FragmentBuilder) -> FragmentBuilder,
+    ) -> Self {
+        let fragment = build(FragmentBuilder::default());
+        self.document
+            .definitions
+            .push(ast::Definition::FragmentDefinition(Node::new(
+                ast::FragmentDefinition {
+                    name,
+                    type_condition,
+                    directives: fragment.directives,
+                    selection_set: fragment.selection_set,
+                },
+            )));
+        self
+    }
+
+    /// Returns the AST of the document built so far, for example to print it
+    pub fn to_ast(&self) -> &ast::Document {
+        &self.document
+    }
+
+    /// Returns the AST of the document
+    pub fn into_ast(self) -> ast::Document {
+        self.document
+    }
+
+    /// Resolve fields against `schema` and validate the document.
+    ///
+    /// Like when parsing, selections of fields or type conditions not defined in the schema
+    /// are reported as errors without a source location.
+    pub fn build(
+        self,
+        schema: &Valid<Schema>,
+    ) -> Result<Valid<ExecutableDocument>, WithErrors<ExecutableDocument>> {
+        self.document.to_executable_validate(schema)
+    }
+}
+
+/// Methods for adding selections, shared by builders that have a selection set
+macro_rules! selection_methods {
+    () => {
+        /// Select a field without arguments or subselections
+        pub fn field(self, name: Name) -> Self {
+            self.field_with(name, |field| field)
+        }
+
+        /// Select a field with an alias, arguments, directives,
+        /// or subselections added by the `build` callback
+        pub fn field_with(
+            mut self,
+            name: Name,
+            build: impl FnOnce(FieldBuilder) -> FieldBuilder,
+        ) -> Self {
+            let field = build(FieldBuilder::default());
+            self.selection_set
+                .push(ast::Selection::Field(Node::new(ast::Field {
+                    alias: field.alias,
+                    name,
+                    arguments: field.arguments,
+                    directives: field.directives,
+                    selection_set: field.selection_set,
+                })));
+            self
+        }
+
+        /// Add an inline fragment, with selections added by the `build` callback
+        pub fn inline_fragment(
+            mut self,
+            type_condition: Option<NamedType>,
+            build: impl FnOnce(InlineFragmentBuilder) -> InlineFragmentBuilder,
+        ) -> Self {
+            let fragment = build(InlineFragmentBuilder::default());
+            self.selection_set
+                .push(ast::Selection::InlineFragment(Node::new(
+                    ast::InlineFragment {
+                        type_condition,
+                        directives: fragment.directives,
+                        selection_set: fragment.selection_set,
+                    },
+                )));
+            self
+        }
+
+        /// Add a spread of the named fragment
+        pub fn fragment_spread(mut self, fragment_name: Name) -> Self {
+            self.selection_set
+                .push(ast::Selection::FragmentSpread(Node::new(
+                    ast::FragmentSpread {
+                        fragment_name,
+                        directives: Default::default(),
+                    },
+                )));
+            self
+        }
+
+        /// Apply a directive
+        pub fn directive(mut self, directive: impl Into<Node<Directive>>) -> Self {
+            self.directives.push(directive.into());
+            self
+        }
+    };
+}
+
+impl OperationBuilder {
+    /// Declare a variable of the given type
+    pub fn variable(mut self, name: Name, ty: Type) -> Self {
+        self.variables.push(Node::new(ast::VariableDefinition {
+            name,
+            ty: Node::new(ty),
+            default_value: None,
+            directives: Default::default(),
+        }));
+        self
+    }
+
+    /// Declare a variable of the given type with a default value
+    pub fn variable_with_default(
+        mut self,
+        name: Name,
+        ty: Type,
+        default_value: impl Into<Node<Value>>,
+    ) -> Self {
+        self.variables.push(Node::new(ast::VariableDefinition {
+            name,
+            ty: Node::new(ty),
+            default_value: Some(default_value.into()),
+            directives: Default::default(),
+        }));
+        self
+    }
+
+    selection_methods!();
+}
+
+impl FragmentBuilder {
+    selection_methods!();
+}
+
+impl FieldBuilder {
+    /// Set the alias of the field, used as the response key instead of its name
+    pub fn alias(mut self, alias: Name) -> Self {
+        self.alias = Some(alias);
+        self
+    }
+
+    /// Add an argument
+    pub fn argument(mut self, name: Name, value: impl Into<Node<Value>>) -> Self {
+        self.arguments.push(Node::new(ast::Argument {
+            name,
+            value: value.into(),
+        }));
+        self
+    }
+
+    selection_methods!();
+}
+
+impl InlineFragmentBuilder {
+    selection_methods!();
+}
@@ -1,5 +1,6 @@
 use super::*;
 use crate::ty;
+use crate::validation::suggestion::suggestions;
 
 pub(crate) struct BuildErrors<'a> {
     pub(crate) errors: &'a mut DiagnosticList,
@@ -241,13 +242,23 @@ impl SelectionSet {
                                 ),
                             }
                         }
-                        Err(schema::FieldLookupError::NoSuchField(type_name, _)) => {
+                        Err(schema::FieldLookupError::NoSuchField(type_name, type_def)) => {
+                            let fields = match type_def {
+                                schema::ExtendedType::Object(ty) => Some(&ty.fields),
+                                schema::ExtendedType::Interface(ty) => Some(&ty.fields),
+                                _ => None,
+                            };
                             errors.errors.push(
                                 ast.name.location(),
                                 BuildError::UndefinedField {
                                     type_name: type_name.clone(),
                                     field_name: ast.name.clone(),
                                     path: errors.path.clone(),
+                                    suggestions: suggestions(
+                                        &ast.name,
+                                        fields.into_iter().flat_map(|fields| fields.keys()),
+                                    )
+                                    .into(),
                                 },
                             )
                         }
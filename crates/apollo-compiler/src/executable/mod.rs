@@ -2,6 +2,7 @@
 //! which can contain operations and fragments.
 
 use crate::ast;
+use crate::collections::HashSet;
 use crate::collections::IndexMap;
 use crate::coordinate::FieldArgumentCoordinate;
 use crate::coordinate::TypeAttributeCoordinate;
@@ -9,9 +10,13 @@ use crate::parser::Parser;
 use crate::parser::SourceMap;
 use crate::parser::SourceSpan;
 use crate::schema;
+use crate::validation::diagnostics::DiagnosticData;
 use crate::validation::DiagnosticList;
+use crate::validation::LintConfig;
 use crate::validation::Valid;
+use crate::validation::ValidationConfig;
 use crate::validation::WithErrors;
+use crate::validation::WithWarnings;
 use crate::Node;
 use crate::Schema;
 use indexmap::map::Entry;
@@ -19,10 +24,21 @@ use std::fmt;
 use std::path::Path;
 use std::sync::Arc;
 
+mod fluent;
 pub(crate) mod from_ast;
 mod serialize;
+mod usage;
 pub(crate) mod validation;
 
+pub use self::fluent::ExecutableDocumentBuilder;
+pub use self::fluent::FieldBuilder;
+pub use self::fluent::FragmentBuilder;
+pub use self::fluent::InlineFragmentBuilder;
+pub use self::fluent::OperationBuilder;
+pub use self::usage::field_usage;
+pub use self::usage::FieldUsageReport;
+pub use self::usage::OperationRef;
+pub use self::usage::Usage;
 pub use crate::ast::Argument;
 pub use crate::ast::Directive;
 pub use crate::ast::DirectiveList;
@@ -69,6 +85,28 @@ pub struct FieldSet {
     pub selection_set: SelectionSet,
 }
 
+/// Named fragments without operations, such as a library of fragments shared between
+/// many client documents.
+///
+/// A library is validated against a schema once, by itself.
+/// Its fragments are not required to be used.
+/// Executable documents then spread library fragments without defining them,
+/// and [`ExecutableDocument::validate_with_library`] links in the fragments they use.
+#[derive(Debug, Clone, Default)]
+pub struct FragmentLibrary {
+    /// If this library was originally parsed from source files,
+    /// this map contains one entry for each file and its ID.
+    ///
+    /// The library may have been modified since.
+    pub sources: SourceMap,
+
+    pub fragments: FragmentMap,
+
+    /// Fragments that do not use variables, computed during validation.
+    /// Linking does not validate them again.
+    context_free: HashSet<Name>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Operation {
     pub operation_type: OperationType,
@@ -132,6 +170,9 @@ pub(crate) enum BuildError {
         describe: &'static str,
     },
 
+    #[error("a fragment library must not contain operations")]
+    OperationInFragmentLibrary { name: Option<Name> },
+
     #[error("anonymous operation cannot be selected when the document contains other operations")]
     AmbiguousAnonymousOperation,
 
@@ -180,6 +221,8 @@ pub(crate) enum BuildError {
         type_name: NamedType,
         field_name: Name,
         path: SelectionPath,
+        /// Fields of the type with a similar name
+        suggestions: Box<[Name]>,
     },
 
     // Validation errors
@@ -316,17 +359,253 @@ impl ExecutableDocument {
         errors.into_valid_result(doc)
     }
 
+    /// Validate this document against `schema`.
+    ///
+    /// The result only depends on this document and the schema.
+    /// A tool that keeps many documents only needs to revalidate the one that changed,
+    /// or all of them if the schema changed, as done by [`DocumentSet`][crate::validation::DocumentSet].
     pub fn validate(self, schema: &Valid<Schema>) -> Result<Valid<Self>, WithErrors<Self>> {
+        self.validate_with_config(schema, &ValidationConfig::default())
+            .map(WithWarnings::into_valid)
+    }
+
+    /// Like [`validate`][Self::validate], but does not report diagnostics
+    /// from rules turned off in `config`.
+    ///
+    /// Only errors fail validation.
+    /// [Warnings][crate::validation::Severity::Warning] enabled by `config`, if any,
+    /// are returned alongside the valid result.
+    pub fn validate_with_config(
+        self,
+        schema: &Valid<Schema>,
+        config: &ValidationConfig,
+    ) -> Result<WithWarnings<Self>, WithErrors<Self>> {
+        let errors = self.validation_diagnostics(schema, config);
+        errors.into_valid_result_with_warnings(self)
+    }
+
+    /// Returns all diagnostics of [`validate_with_config`][Self::validate_with_config],
+    /// including [suppressed][DiagnosticList::suppressed] ones, even if the document is valid.
+    pub fn validation_diagnostics(
+        &self,
+        schema: &Valid<Schema>,
+        config: &ValidationConfig,
+    ) -> DiagnosticList {
         let mut sources = IndexMap::clone(&schema.sources);
         sources.extend(self.sources.iter().map(|(k, v)| (*k, v.clone())));
         let mut errors = DiagnosticList::new(Arc::new(sources));
-        validation::validate_executable_document(&mut errors, schema, &self);
+        validation::validate_executable_document_with_config(&mut errors, schema, self, config);
+        errors.apply_config(config);
+        errors
+    }
+
+    /// Check style rules that are not part of validation,
+    /// such as `@include(if: true)` having no effect, as configured by `config`.
+    ///
+    /// Returns diagnostics with [`Severity::Lint`][crate::validation::Severity::Lint].
+    /// Linting is separate from validation: run [`validate`][Self::validate] as well.
+    ///
+    /// ```rust
+    /// use apollo_compiler::validation::LintConfig;
+    /// use apollo_compiler::ExecutableDocument;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    /// let input = "{ a @skip(if: false) }";
+    /// let document = ExecutableDocument::parse(&schema, input, "query.graphql").unwrap();
+    /// let lints = document.lint(&LintConfig::new());
+    /// assert_eq!(lints.len(), 1);
+    /// assert!(lints.to_string().contains("`@skip(if: false)` has no effect"));
+    /// ```
+    pub fn lint(&self, config: &LintConfig) -> DiagnosticList {
+        let mut diagnostics = DiagnosticList::new(self.sources.clone());
+        crate::validation::lint::lint_executable_document(&mut diagnostics, self, config);
+        diagnostics
+    }
+
+    /// Validate only the operation that a request with `operation_name` executes,
+    /// as selected by [`OperationMap::get`], and the fragments it uses
+    /// directly or through other fragments.
+    ///
+    /// Other operations and fragments of this document are not validated,
+    /// so a request pipeline does not pay for definitions that a request does not use.
+    /// The returned document contains only the validated definitions.
+    ///
+    /// ```rust
+    /// use apollo_compiler::ExecutableDocument;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    /// let input = "query A { ...F } query B($unused: Int) { a } fragment F on Query { a }";
+    /// let document = ExecutableDocument::parse(&schema, input, "query.graphql").unwrap();
+    /// // Operation `B` defines a variable that it does not use
+    /// let valid = document.validate_operation(&schema, Some("A")).unwrap();
+    /// assert_eq!(valid.operations.len(), 1);
+    /// assert!(document.validate_operation(&schema, Some("B")).is_err());
+    /// ```
+    pub fn validate_operation(
+        &self,
+        schema: &Valid<Schema>,
+        operation_name: Option<&str>,
+    ) -> Result<Valid<Self>, WithErrors<Self>> {
+        let operation = match self.operations.get(operation_name) {
+            Ok(operation) => operation,
+            Err(_) => {
+                let mut errors = DiagnosticList::new(self.sources.clone());
+                errors.push(
+                    None,
+                    DiagnosticData::OperationNotFound {
+                        name: operation_name.map(ToOwned::to_owned),
+                        count: self.operations.len(),
+                    },
+                );
+                let partial = Self {
+                    sources: self.sources.clone(),
+                    ..Default::default()
+                };
+                return Err(WithErrors { partial, errors });
+            }
+        };
+        let subset = validation::operation_subset(self, operation);
+        let errors = subset.validation_diagnostics(schema, &ValidationConfig::default());
+        errors.into_valid_result(subset)
+    }
+
+    /// Link fragments from a validated library into this document, then validate it.
+    ///
+    /// Library fragments that this document spreads, directly or through other library
+    /// fragments, are added to [`fragments`][Self::fragments].
+    /// Fragments defined in this document must not have the same name as a library fragment.
+    ///
+    /// Library fragments that do not use variables are not validated again.
+    /// Those that do are validated against the variables of each operation that uses them.
+    pub fn validate_with_library(
+        mut self,
+        schema: &Valid<Schema>,
+        library: &Valid<FragmentLibrary>,
+    ) -> Result<Valid<Self>, WithErrors<Self>> {
+        let mut sources = IndexMap::clone(&schema.sources);
+        sources.extend(library.sources.iter().map(|(k, v)| (*k, v.clone())));
+        sources.extend(self.sources.iter().map(|(k, v)| (*k, v.clone())));
+        let mut errors = DiagnosticList::new(Arc::new(sources));
+        let prevalidated = validation::link_fragment_library(&mut errors, &mut self, library);
+        validation::validate_linked_executable_document(&mut errors, schema, &self, &prevalidated);
         errors.into_valid_result(self)
     }
 
     serialize_method!();
 }
 
+impl FragmentLibrary {
+    /// Create an empty library, to be filled programatically
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a fragment library with the default configuration.
+    ///
+    /// `path` is the filesystem path (or arbitrary string) used in diagnostics
+    /// to identify this source file to users.
+    ///
+    /// Create a [`Parser`] to use different parser configuration.
+    pub fn parse(
+        schema: &Valid<Schema>,
+        source_text: impl Into<String>,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, WithErrors<Self>> {
+        Parser::new().parse_fragment_library(schema, source_text, path)
+    }
+
+    /// Parse another source file and add its fragments to this library,
+    /// for projects where shared fragments are split across several files.
+    ///
+    /// Fragments can spread fragments defined in any file of the library,
+    /// and fragment names must be unique across all of them.
+    /// [Validate][Self::validate] the library after all files are added.
+    ///
+    /// ```rust
+    /// use apollo_compiler::executable::FragmentLibrary;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse_and_validate("type Query { a: Int, b: Int }", "schema.graphql")
+    ///     .unwrap();
+    /// let mut library =
+    ///     FragmentLibrary::parse(&schema, "fragment A on Query { a ...B }", "a.graphql").unwrap();
+    /// library.parse_into(&schema, "fragment B on Query { b }", "b.graphql").unwrap();
+    /// library.validate(&schema).unwrap();
+    /// ```
+    pub fn parse_into(
+        &mut self,
+        schema: &Valid<Schema>,
+        source_text: impl Into<String>,
+        path: impl AsRef<Path>,
+    ) -> Result<(), DiagnosticList> {
+        let (other, mut errors) =
+            Parser::new().parse_fragment_library_inner(schema, source_text, path);
+        Arc::make_mut(&mut self.sources).extend(other.sources.iter().map(|(k, v)| (*k, v.clone())));
+        for (name, fragment) in other.fragments {
+            if let Some(previous) = self.fragments.get(&name) {
+                errors.push(
+                    fragment.name.location(),
+                    BuildError::FragmentNameCollision {
+                        name_at_previous_location: previous.name.clone(),
+                    },
+                );
+            } else {
+                self.fragments.insert(name, fragment);
+            }
+        }
+        // Collisions point to fragments in previously added files
+        Arc::make_mut(&mut errors.sources)
+            .extend(self.sources.iter().map(|(k, v)| (*k, v.clone())));
+        errors.into_result()
+    }
+
+    /// [`parse`][Self::parse] then [`validate`][Self::validate],
+    /// to get a `Valid<FragmentLibrary>` when mutating it isn’t needed.
+    pub fn parse_and_validate(
+        schema: &Valid<Schema>,
+        source_text: impl Into<String>,
+        path: impl AsRef<Path>,
+    ) -> Result<Valid<Self>, WithErrors<Self>> {
+        let (library, mut errors) =
+            Parser::new().parse_fragment_library_inner(schema, source_text, path);
+        Arc::make_mut(&mut errors.sources)
+            .extend(schema.sources.iter().map(|(k, v)| (*k, v.clone())));
+        library.validate_inner(schema, errors)
+    }
+
+    /// Validate the fragments of this library against a schema,
+    /// without requiring them to be used by an operation.
+    ///
+    /// Variables used in library fragments are checked when linking the library
+    /// into a document with [`ExecutableDocument::validate_with_library`].
+    pub fn validate(self, schema: &Valid<Schema>) -> Result<Valid<Self>, WithErrors<Self>> {
+        let mut sources = IndexMap::clone(&schema.sources);
+        sources.extend(self.sources.iter().map(|(k, v)| (*k, v.clone())));
+        self.validate_inner(schema, DiagnosticList::new(Arc::new(sources)))
+    }
+
+    fn validate_inner(
+        mut self,
+        schema: &Valid<Schema>,
+        mut errors: DiagnosticList,
+    ) -> Result<Valid<Self>, WithErrors<Self>> {
+        validation::validate_fragment_library(&mut errors, schema, &self);
+        self.context_free = validation::context_free_fragments(&self);
+        errors.into_valid_result(self)
+    }
+}
+
+impl Eq for FragmentLibrary {}
+
+/// `sources` are ignored for comparison
+impl PartialEq for FragmentLibrary {
+    fn eq(&self, other: &Self) -> bool {
+        self.fragments == other.fragments
+    }
+}
+
 impl Eq for ExecutableDocument {}
 
 /// `sources` and `build_errors` are ignored for comparison
@@ -0,0 +1,349 @@
+use crate::ast;
+use crate::collections::HashMap;
+use crate::collections::HashSet;
+use crate::collections::IndexMap;
+use crate::collections::IndexSet;
+use crate::coordinate::DirectiveArgumentCoordinate;
+use crate::coordinate::FieldArgumentCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::executable::DirectiveList;
+use crate::executable::Field;
+use crate::executable::Operation;
+use crate::executable::Selection;
+use crate::executable::SelectionSet;
+use crate::schema::ExtendedType;
+use crate::schema::Implementers;
+use crate::schema::NamedType;
+use crate::schema::Type;
+use crate::validation::Valid;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Schema;
+use std::fmt;
+
+/// Which schema fields, arguments, and enum values a corpus of operations uses,
+/// as returned by [`field_usage`]
+///
+/// Keys are in the order they were first used.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FieldUsageReport {
+    /// Usage of fields of object types, interface types, and input object types.
+    ///
+    /// A field selected on an interface type is also used on each type that implements it,
+    /// since any of them may resolve it.
+    /// Input object fields are used when they appear in literal values.
+    pub fields: IndexMap<TypeAttributeCoordinate, Usage>,
+    /// Usage of field arguments and directive arguments
+    pub arguments: IndexMap<SchemaCoordinate, Usage>,
+    /// Usage of enum values that appear in literal values
+    pub enum_values: IndexMap<TypeAttributeCoordinate, Usage>,
+}
+
+/// How often a schema element is used, in a [`FieldUsageReport`]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Usage {
+    /// How many times the element appears across all operations,
+    /// including in the fragments they use
+    pub count: usize,
+    /// The operations that use the element
+    pub operations: IndexSet<OperationRef>,
+}
+
+/// Identifies an operation in a [`FieldUsageReport`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OperationRef {
+    /// The identifier given for the document that contains the operation,
+    /// such as a file path or a client name
+    pub document: String,
+    /// The name of the operation, or `None` for an anonymous operation
+    pub operation: Option<Name>,
+}
+
+/// Returns which schema fields, arguments, and enum values are used by the operations
+/// in `documents`, how many times, and by which operations.
+///
+/// Each document is given with an identifier for its [`OperationRef`]s,
+/// such as a file path or a client name.
+/// Fields selected in fragments count as used by every operation that spreads them.
+/// A fragment spread more than once in an operation is counted once for that operation.
+/// Values given as variables are not known, so only enum values and input object fields
+/// in literal values, including variable default values, are counted.
+///
+/// Unused fields are candidates for deprecation and removal:
+///
+/// ```rust
+/// use apollo_compiler::coordinate::TypeAttributeCoordinate;
+/// use apollo_compiler::executable;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(
+///     "type Query { me: User } type User { id: ID! name: String legacyName: String }",
+///     "schema.graphql",
+/// )
+/// .unwrap();
+/// let document = ExecutableDocument::parse_and_validate(
+///     &schema,
+///     "query GetMe { me { ...UserFields } } fragment UserFields on User { id name }",
+///     "operations.graphql",
+/// )
+/// .unwrap();
+/// let report = executable::field_usage(&schema, [("web", &document)]);
+/// let coordinate: TypeAttributeCoordinate = "User.name".parse().unwrap();
+/// let name = &report.fields[&coordinate];
+/// assert_eq!(name.count, 1);
+/// assert_eq!(name.operations[0].to_string(), "web#GetMe");
+///
+/// let unused: Vec<_> = report.unused_fields(&schema).map(|c| c.to_string()).collect();
+/// assert_eq!(unused, ["User.legacyName"]);
+/// ```
+pub fn field_usage<'a>(
+    schema: &Valid<Schema>,
+    documents: impl IntoIterator<Item = (&'a str, &'a Valid<ExecutableDocument>)>,
+) -> FieldUsageReport {
+    let mut report = FieldUsageReport::default();
+    for (document_id, document) in documents {
+        report.add_document(schema, document_id, document)
+    }
+    report
+}
+
+impl FieldUsageReport {
+    /// Returns an empty report
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add usage by the operations of one more document, like [`field_usage`] does
+    pub fn add_document(
+        &mut self,
+        schema: &Valid<Schema>,
+        document_id: &str,
+        document: &Valid<ExecutableDocument>,
+    ) {
+        let implementers = schema.implementers_map();
+        for operation in document.operations.iter() {
+            let mut collector = Collector {
+                schema,
+                document,
+                implementers: &implementers,
+                report: self,
+                operation: OperationRef {
+                    document: document_id.to_owned(),
+                    operation: operation.name.clone(),
+                },
+                fragments_seen: HashSet::default(),
+            };
+            collector.operation(operation)
+        }
+    }
+
+    /// Returns coordinates of fields of object, interface, and input object types in `schema`
+    /// that are not used by any operation in this report
+    pub fn unused_fields<'a>(
+        &'a self,
+        schema: &'a Schema,
+    ) -> impl Iterator<Item = TypeAttributeCoordinate> + 'a {
+        schema
+            .types
+            .values()
+            .filter(|ty| !ty.is_built_in())
+            .flat_map(|ty| {
+                let names: Vec<&Name> = match ty {
+                    ExtendedType::Object(def) => def.fields.keys().collect(),
+                    ExtendedType::Interface(def) => def.fields.keys().collect(),
+                    ExtendedType::InputObject(def) => def.fields.keys().collect(),
+                    _ => Vec::new(),
+                };
+                names.into_iter().map(|name| TypeAttributeCoordinate {
+                    ty: ty.name().clone(),
+                    attribute: name.clone(),
+                })
+            })
+            .filter(|coordinate| !self.fields.contains_key(coordinate))
+    }
+}
+
+impl Usage {
+    fn add(&mut self, operation: &OperationRef) {
+        self.count += 1;
+        if !self.operations.contains(operation) {
+            self.operations.insert(operation.clone());
+        }
+    }
+}
+
+impl fmt::Display for OperationRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.operation {
+            Some(name) => write!(f, "{}#{name}", self.document),
+            None => write!(f, "{}", self.document),
+        }
+    }
+}
+
+struct Collector<'a> {
+    schema: &'a Schema,
+    document: &'a ExecutableDocument,
+    implementers: &'a HashMap<Name, Implementers>,
+    report: &'a mut FieldUsageReport,
+    operation: OperationRef,
+    fragments_seen: HashSet<Name>,
+}
+
+impl Collector<'_> {
+    fn operation(&mut self, operation: &Operation) {
+        self.directives(&operation.directives);
+        for variable in &operation.variables {
+            self.directives(&variable.directives);
+            if let Some(default_value) = &variable.default_value {
+                self.value(default_value, &variable.ty)
+            }
+        }
+        self.selection_set(&operation.selection_set)
+    }
+
+    fn selection_set(&mut self, selection_set: &SelectionSet) {
+        for selection in &selection_set.selections {
+            match selection {
+                Selection::Field(field) => self.field(&selection_set.ty, field),
+                Selection::InlineFragment(inline) => {
+                    self.directives(&inline.directives);
+                    self.selection_set(&inline.selection_set)
+                }
+                Selection::FragmentSpread(spread) => {
+                    self.directives(&spread.directives);
+                    if !self.fragments_seen.insert(spread.fragment_name.clone()) {
+                        continue;
+                    }
+                    if let Some(fragment) = self.document.fragments.get(&spread.fragment_name) {
+                        self.directives(&fragment.directives);
+                        self.selection_set(&fragment.selection_set)
+                    }
+                }
+            }
+        }
+    }
+
+    fn field(&mut self, parent_type: &NamedType, field: &Field) {
+        let is_schema_field = self
+            .schema
+            .types
+            .get(parent_type)
+            .is_some_and(|ty| !ty.is_built_in())
+            && !field.name.starts_with("__");
+        if is_schema_field {
+            // The field can be resolved by the parent type or, for an interface,
+            // by any of the types that implement it
+            let mut types = vec![parent_type.clone()];
+            if let Some(implementers) = self.implementers.get(parent_type) {
+                types.extend(implementers.iter().cloned())
+            }
+            for ty in types {
+                let coordinate = TypeAttributeCoordinate {
+                    ty: ty.clone(),
+                    attribute: field.name.clone(),
+                };
+                self.report
+                    .fields
+                    .entry(coordinate)
+                    .or_default()
+                    .add(&self.operation);
+                for argument in &field.arguments {
+                    let coordinate = FieldArgumentCoordinate {
+                        ty: ty.clone(),
+                        field: field.name.clone(),
+                        argument: argument.name.clone(),
+                    };
+                    self.report
+                        .arguments
+                        .entry(coordinate.into())
+                        .or_default()
+                        .add(&self.operation);
+                }
+            }
+            for argument in &field.arguments {
+                if let Some(definition) = field.definition.argument_by_name(&argument.name) {
+                    self.value(&argument.value, &definition.ty)
+                }
+            }
+        }
+        self.directives(&field.directives);
+        self.selection_set(&field.selection_set)
+    }
+
+    fn directives(&mut self, directives: &DirectiveList) {
+        for directive in directives {
+            let Some(definition) = self.schema.directive_definitions.get(&directive.name) else {
+                continue;
+            };
+            for argument in &directive.arguments {
+                let coordinate = DirectiveArgumentCoordinate {
+                    directive: directive.name.clone(),
+                    argument: argument.name.clone(),
+                };
+                self.report
+                    .arguments
+                    .entry(coordinate.into())
+                    .or_default()
+                    .add(&self.operation);
+                if let Some(argument_definition) = definition.argument_by_name(&argument.name) {
+                    self.value(&argument.value, &argument_definition.ty)
+                }
+            }
+        }
+    }
+
+    /// Record enum values and input object fields in a literal value of type `ty`
+    fn value(&mut self, value: &ast::Value, ty: &Type) {
+        match value {
+            ast::Value::Enum(value) => {
+                let enum_name = ty.inner_named_type();
+                if self.schema.get_enum(enum_name).is_some() {
+                    let coordinate = TypeAttributeCoordinate {
+                        ty: enum_name.clone(),
+                        attribute: value.clone(),
+                    };
+                    self.report
+                        .enum_values
+                        .entry(coordinate)
+                        .or_default()
+                        .add(&self.operation);
+                }
+            }
+            ast::Value::List(items) => {
+                for item in items {
+                    self.value(item, ty.item_type())
+                }
+            }
+            ast::Value::Object(fields) => {
+                let input_name = ty.inner_named_type();
+                let Some(input_object) = self.schema.get_input_object(input_name) else {
+                    return;
+                };
+                for (name, value) in fields {
+                    let Some(definition) = input_object.fields.get(name) else {
+                        continue;
+                    };
+                    let coordinate = TypeAttributeCoordinate {
+                        ty: input_name.clone(),
+                        attribute: name.clone(),
+                    };
+                    self.report
+                        .fields
+                        .entry(coordinate)
+                        .or_default()
+                        .add(&self.operation);
+                    self.value(value, &definition.ty)
+                }
+            }
+            ast::Value::Null
+            | ast::Value::Variable(_)
+            | ast::Value::String(_)
+            | ast::Value::Float(_)
+            | ast::Value::Int(_)
+            | ast::Value::Boolean(_) => {}
+        }
+    }
+}
@@ -1,51 +1,293 @@
+use super::BuildError;
 use super::FieldSet;
-use crate::validation::fragment::validate_fragment_used;
+use super::FragmentLibrary;
+use super::Operation;
+use super::Selection;
+use super::SelectionSet;
+use crate::collections::HashSet;
+use crate::validation::allowlist::validate_allowlisted_document;
+use crate::validation::custom_directive::validate_custom_directives_in_document;
+use crate::validation::custom_scalar::validate_custom_scalar_values;
+use crate::validation::directive::validate_repeated_directives_in_document;
+use crate::validation::fragment::validate_fragments_used;
+use crate::validation::one_of::validate_one_of_values;
 use crate::validation::operation::validate_operation_definitions;
+use crate::validation::progress::Progress;
+use crate::validation::progress::ValidationPhase;
 use crate::validation::selection::FieldsInSetCanMerge;
+use crate::validation::unreachable::validate_unreachable_selections;
+use crate::validation::variable::fragment_uses_variables;
 use crate::validation::DiagnosticList;
 use crate::validation::ExecutableValidationContext;
+use crate::validation::SpecVersion;
 use crate::validation::Valid;
+use crate::validation::ValidationConfig;
+use crate::validation::ValidationRule;
 use crate::ExecutableDocument;
+use crate::Name;
+use crate::Node;
 use crate::Schema;
+use std::sync::Arc;
 
 pub(crate) fn validate_executable_document(
     errors: &mut DiagnosticList,
     schema: &Schema,
     document: &ExecutableDocument,
 ) {
-    validate_with_or_without_schema(errors, Some(schema), document);
-    validate_with_schema(errors, schema, document);
+    validate_executable_document_with_config(errors, schema, document, &Default::default())
+}
+
+pub(crate) fn validate_executable_document_with_config(
+    errors: &mut DiagnosticList,
+    schema: &Schema,
+    document: &ExecutableDocument,
+    config: &ValidationConfig,
+) {
+    if let Some(allowlist) = config.checked_operation_allowlist() {
+        validate_allowlisted_document(errors, document, allowlist);
+    }
+    let draft = config.validation_spec_version() >= SpecVersion::Draft;
+    let additional_rules = config.has_scalar_validators()
+        || config.has_directive_validators()
+        || draft
+        || config.repeated_directive_warnings_enabled()
+        || config.unreachable_selection_warnings_enabled();
+    let definitions = document.operations.len() + document.fragments.len();
+    let merging = document.operations.len();
+    let total = definitions + merging + if additional_rules { definitions } else { 0 };
+    let mut progress = Progress::new(config, total);
+
+    let context = ExecutableValidationContext::new(Some(schema)).with_config(config);
+    if progress.start(errors, ValidationPhase::Operations, definitions) {
+        validate_with_or_without_schema(errors, &context, document);
+    }
+    // Later rules, such as field merging, can take as much work as the budget that was exceeded
+    if !context.budget_exceeded() && progress.start(errors, ValidationPhase::FieldMerging, merging)
+    {
+        validate_with_schema(errors, schema, document, config);
+        if additional_rules && progress.start(errors, ValidationPhase::AdditionalRules, definitions)
+        {
+            if config.has_scalar_validators()
+                && config.any_rule_enabled(&[ValidationRule::InvalidScalarValue])
+            {
+                validate_custom_scalar_values(errors, schema, document, config);
+            }
+            if config.has_directive_validators()
+                && config.any_rule_enabled(&[ValidationRule::InvalidDirectiveApplication])
+            {
+                validate_custom_directives_in_document(errors, schema, document, config);
+            }
+            if draft
+                && config.any_rule_enabled(&[
+                    ValidationRule::OneOfFieldCount,
+                    ValidationRule::NullOneOfField,
+                    ValidationRule::NullableVariableInOneOf,
+                ])
+            {
+                validate_one_of_values(errors, schema, document);
+            }
+            if config.repeated_directive_warnings_enabled()
+                && config.any_rule_enabled(&[ValidationRule::RepeatedDirectiveArguments])
+            {
+                validate_repeated_directives_in_document(errors, schema, document);
+            }
+            if config.unreachable_selection_warnings_enabled()
+                && config.any_rule_enabled(&[ValidationRule::UnreachableSelection])
+            {
+                validate_unreachable_selections(errors, schema, document);
+            }
+        }
+    }
+    errors.apply_unknown_directive_config(config);
 }
 
 pub(crate) fn validate_standalone_executable(
     errors: &mut DiagnosticList,
     document: &ExecutableDocument,
 ) {
-    validate_with_or_without_schema(errors, None, document);
+    let context = ExecutableValidationContext::new(None);
+    validate_with_or_without_schema(errors, &context, document);
 }
 
-fn validate_with_schema(
+/// Validate a document that had a fragment library linked into it by
+/// [`link_fragment_library`]. The definitions of `prevalidated_fragments` are not revisited.
+pub(crate) fn validate_linked_executable_document(
     errors: &mut DiagnosticList,
     schema: &Schema,
     document: &ExecutableDocument,
+    prevalidated_fragments: &HashSet<Name>,
 ) {
+    let context = ExecutableValidationContext::new(Some(schema))
+        .with_prevalidated_fragments(prevalidated_fragments);
+    validate_with_or_without_schema(errors, &context, document);
+    validate_with_schema(errors, schema, document, &Default::default());
+}
+
+pub(crate) fn validate_fragment_library(
+    errors: &mut DiagnosticList,
+    schema: &Schema,
+    library: &FragmentLibrary,
+) {
+    let document = ExecutableDocument {
+        sources: library.sources.clone(),
+        operations: Default::default(),
+        fragments: library.fragments.clone(),
+    };
+    crate::validation::fragment::validate_fragment_library(errors, schema, &document);
+
     let alloc = typed_arena::Arena::new();
-    let mut fields_in_set_can_merge = FieldsInSetCanMerge::new(&alloc, schema, document);
+    let mut fields_in_set_can_merge = FieldsInSetCanMerge::new(&alloc, schema, &document);
+    for fragment in document.fragments.values() {
+        fields_in_set_can_merge.validate_fragment(fragment, errors);
+    }
+}
+
+/// Returns the names of library fragments that do not use variables.
+///
+/// Their validity does not depend on the operation they are used in,
+/// so linking does not need to validate them again.
+pub(crate) fn context_free_fragments(library: &FragmentLibrary) -> HashSet<Name> {
+    let document = ExecutableDocument {
+        sources: library.sources.clone(),
+        operations: Default::default(),
+        fragments: library.fragments.clone(),
+    };
+    document
+        .fragments
+        .values()
+        .filter(|fragment| !fragment_uses_variables(&document, fragment))
+        .map(|fragment| fragment.name.clone())
+        .collect()
+}
+
+/// Copy the library fragments that `document` refers to, directly or through other
+/// library fragments, into `document`.
+///
+/// Returns the names of linked fragments that do not need to be validated again.
+pub(crate) fn link_fragment_library(
+    errors: &mut DiagnosticList,
+    document: &mut ExecutableDocument,
+    library: &FragmentLibrary,
+) -> HashSet<Name> {
+    for (name, fragment) in &document.fragments {
+        if let Some((library_name, _)) = library.fragments.get_key_value(name) {
+            errors.push(
+                fragment.name.location(),
+                BuildError::FragmentNameCollision {
+                    name_at_previous_location: library_name.clone(),
+                },
+            )
+        }
+    }
+
+    let mut pending = Vec::new();
     for operation in document.operations.iter() {
-        crate::validation::operation::validate_subscription(document, operation, errors);
-        fields_in_set_can_merge.validate_operation(operation, errors);
+        collect_fragment_spreads(&operation.selection_set, &mut pending);
+    }
+    for fragment in document.fragments.values() {
+        collect_fragment_spreads(&fragment.selection_set, &mut pending);
+    }
+
+    let mut prevalidated = HashSet::default();
+    while let Some(name) = pending.pop() {
+        if document.fragments.contains_key(&name) {
+            continue;
+        }
+        if let Some(fragment) = library.fragments.get(&name) {
+            collect_fragment_spreads(&fragment.selection_set, &mut pending);
+            document.fragments.insert(name.clone(), fragment.clone());
+            if library.context_free.contains(&name) {
+                prevalidated.insert(name);
+            }
+        }
+    }
+
+    Arc::make_mut(&mut document.sources)
+        .extend(library.sources.iter().map(|(k, v)| (*k, v.clone())));
+    prevalidated
+}
+
+/// Returns a document with only `operation` and the fragments it uses,
+/// directly or through other fragments, in their original order.
+pub(crate) fn operation_subset(
+    document: &ExecutableDocument,
+    operation: &Node<Operation>,
+) -> ExecutableDocument {
+    let mut used = HashSet::default();
+    let mut pending = Vec::new();
+    collect_fragment_spreads(&operation.selection_set, &mut pending);
+    while let Some(name) = pending.pop() {
+        if used.contains(&name) {
+            continue;
+        }
+        if let Some(fragment) = document.fragments.get(&name) {
+            collect_fragment_spreads(&fragment.selection_set, &mut pending);
+        }
+        used.insert(name);
+    }
+    let mut subset = ExecutableDocument {
+        sources: document.sources.clone(),
+        operations: Default::default(),
+        fragments: document
+            .fragments
+            .iter()
+            .filter(|(name, _)| used.contains(*name))
+            .map(|(name, fragment)| (name.clone(), fragment.clone()))
+            .collect(),
+    };
+    subset.operations.insert(operation.clone());
+    subset
+}
+
+fn collect_fragment_spreads(selection_set: &SelectionSet, names: &mut Vec<Name>) {
+    for selection in &selection_set.selections {
+        match selection {
+            Selection::Field(field) => collect_fragment_spreads(&field.selection_set, names),
+            Selection::FragmentSpread(spread) => names.push(spread.fragment_name.clone()),
+            Selection::InlineFragment(inline) => {
+                collect_fragment_spreads(&inline.selection_set, names)
+            }
+        }
+    }
+}
+
+fn validate_with_schema(
+    errors: &mut DiagnosticList,
+    schema: &Schema,
+    document: &ExecutableDocument,
+    config: &ValidationConfig,
+) {
+    if config.any_rule_enabled(&[
+        ValidationRule::ConflictingFieldType,
+        ValidationRule::ConflictingFieldName,
+        ValidationRule::ConflictingFieldArgument,
+    ]) {
+        let alloc = typed_arena::Arena::new();
+        let mut fields_in_set_can_merge = FieldsInSetCanMerge::new(&alloc, schema, document)
+            .with_limits(config.validation_limits());
+        for operation in document.operations.iter() {
+            fields_in_set_can_merge.validate_operation(operation, errors);
+        }
+    }
+    if config.any_rule_enabled(&[
+        ValidationRule::UniqueIncrementalLabel,
+        ValidationRule::VariableIncrementalLabel,
+        ValidationRule::NegativeInitialCount,
+        ValidationRule::DeferOnSubscriptionRoot,
+        ValidationRule::StreamOnNonListField,
+    ]) {
+        crate::validation::incremental::validate_incremental_directives(errors, schema, document);
     }
 }
 
-pub(crate) fn validate_with_or_without_schema(
+fn validate_with_or_without_schema(
     errors: &mut DiagnosticList,
-    schema: Option<&Schema>,
+    context: &ExecutableValidationContext<'_>,
     document: &ExecutableDocument,
 ) {
-    let context = ExecutableValidationContext::new(schema);
-    validate_operation_definitions(errors, document, &context);
-    for def in document.fragments.values() {
-        validate_fragment_used(errors, document, def);
+    validate_operation_definitions(errors, document, context);
+    if context.is_rule_enabled(ValidationRule::UnusedFragment) {
+        validate_fragments_used(errors, document);
     }
 }
 
@@ -0,0 +1,245 @@
+use crate::ast;
+use crate::ast::NamedType;
+use crate::collections::HashMap;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::executable::Field;
+use crate::executable::Operation;
+use crate::executable::Selection;
+use crate::executable::SelectionSet;
+use crate::validation::Valid;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+
+/// Weights used by [`estimate_cost`].
+///
+/// The weight of a field is, in order of precedence:
+///
+/// * The weight configured with [`field_weight`][Self::field_weight]
+/// * With [`cost_directives`][Self::cost_directives], a `@cost(weight:)` directive on the field definition
+/// * The weight configured with [`type_weight`][Self::type_weight] for the type of the field
+/// * With [`cost_directives`][Self::cost_directives], a `@cost(weight:)` directive on that type
+/// * 1 for object, interface, and union types, 0 for scalar and enum types
+#[derive(Debug, Clone)]
+pub struct CostConfig {
+    field_weights: HashMap<TypeAttributeCoordinate, f64>,
+    type_weights: HashMap<NamedType, f64>,
+    default_list_size: f64,
+    cost_directives: bool,
+}
+
+/// The result of [`estimate_cost`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CostEstimate {
+    /// The estimated cost of the whole operation
+    pub total: f64,
+    /// The cost of each field selected by the operation, in document order
+    pub fields: Vec<FieldCost>,
+}
+
+/// The cost of one field in a [`CostEstimate`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldCost {
+    /// Response keys from the root of the operation to this field
+    pub path: Vec<Name>,
+    /// The definition of this field in the schema
+    pub coordinate: TypeAttributeCoordinate,
+    /// The cost of this field including its subselections,
+    /// multiplied by the expected size if it returns a list
+    pub cost: f64,
+}
+
+impl Default for CostConfig {
+    fn default() -> Self {
+        Self {
+            field_weights: Default::default(),
+            type_weights: Default::default(),
+            default_list_size: 10.0,
+            cost_directives: false,
+        }
+    }
+}
+
+impl CostConfig {
+    /// Returns the default configuration
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the weight of the field with the given coordinate, such as `Query.users`
+    pub fn field_weight(mut self, coordinate: TypeAttributeCoordinate, weight: f64) -> Self {
+        self.field_weights.insert(coordinate, weight);
+        self
+    }
+
+    /// Set the weight of fields that return the given type
+    pub fn type_weight(mut self, type_name: NamedType, weight: f64) -> Self {
+        self.type_weights.insert(type_name, weight);
+        self
+    }
+
+    /// Set how many items are expected in lists whose size is not otherwise known.
+    /// Defaults to 10.
+    pub fn default_list_size(mut self, size: f64) -> Self {
+        self.default_list_size = size;
+        self
+    }
+
+    /// Read weights and list sizes from `@cost` and `@listSize` directives in the schema,
+    /// as in the [IBM GraphQL Cost Directive specification](https://ibm.github.io/graphql-specs/cost-spec.html).
+    ///
+    /// `@listSize(slicingArguments:)` is only used with literal argument values.
+    /// When a slicing argument is given as a variable, `assumedSize` is used instead.
+    pub fn cost_directives(mut self, enable: bool) -> Self {
+        self.cost_directives = enable;
+        self
+    }
+}
+
+/// Estimate how expensive it is to execute an operation, without executing it.
+///
+/// The cost of a field is its weight plus the cost of its subselections.
+/// For a field that returns a list, that is multiplied by the expected size of the list.
+/// Fragments on different types of an abstract type are all counted,
+/// so this is an upper bound for union and interface fields.
+///
+/// Servers can reject operations over a cost budget before execution starts,
+/// or use the total for rate limiting.
+pub fn estimate_cost(
+    schema: &Valid<Schema>,
+    document: &Valid<ExecutableDocument>,
+    operation: &Operation,
+    config: &CostConfig,
+) -> CostEstimate {
+    let mut estimator = Estimator {
+        schema,
+        document,
+        config,
+        fields: Vec::new(),
+    };
+    let total = estimator.selection_set_cost(&operation.selection_set, &mut Vec::new());
+    CostEstimate {
+        total,
+        fields: estimator.fields,
+    }
+}
+
+struct Estimator<'a> {
+    schema: &'a Schema,
+    document: &'a ExecutableDocument,
+    config: &'a CostConfig,
+    fields: Vec<FieldCost>,
+}
+
+impl Estimator<'_> {
+    fn selection_set_cost(&mut self, selection_set: &SelectionSet, path: &mut Vec<Name>) -> f64 {
+        let mut cost = 0.0;
+        for selection in &selection_set.selections {
+            cost += match selection {
+                Selection::Field(field) => self.field_cost(&selection_set.ty, field, path),
+                Selection::InlineFragment(inline) => {
+                    self.selection_set_cost(&inline.selection_set, path)
+                }
+                Selection::FragmentSpread(spread) => {
+                    // Validation ensures that `Valid<ExecutableDocument>` does not contain fragment cycles
+                    match self.document.fragments.get(&spread.fragment_name) {
+                        Some(def) => self.selection_set_cost(&def.selection_set, path),
+                        None => 0.0,
+                    }
+                }
+            };
+        }
+        cost
+    }
+
+    fn field_cost(&mut self, parent_type: &NamedType, field: &Field, path: &mut Vec<Name>) -> f64 {
+        path.push(field.response_key().clone());
+        let coordinate = TypeAttributeCoordinate {
+            ty: parent_type.clone(),
+            attribute: field.name.clone(),
+        };
+        // Reserve a slot so that fields are listed before their subselections
+        let index = self.fields.len();
+        self.fields.push(FieldCost {
+            path: path.clone(),
+            coordinate: coordinate.clone(),
+            cost: 0.0,
+        });
+
+        let weight = self.field_weight(&coordinate, field);
+        let subselections = self.selection_set_cost(&field.selection_set, path);
+        let mut cost = weight + subselections;
+        if field.definition.ty.is_list() {
+            cost *= self.list_size(field);
+        }
+
+        self.fields[index].cost = cost;
+        path.pop();
+        cost
+    }
+
+    fn field_weight(&self, coordinate: &TypeAttributeCoordinate, field: &Field) -> f64 {
+        if let Some(&weight) = self.config.field_weights.get(coordinate) {
+            return weight;
+        }
+        if let Some(weight) = self.cost_directive(field.definition.directives.get("cost")) {
+            return weight;
+        }
+        let ty = field.definition.ty.inner_named_type();
+        if let Some(&weight) = self.config.type_weights.get(ty) {
+            return weight;
+        }
+        let Some(definition) = self.schema.types.get(ty) else {
+            return 0.0;
+        };
+        let directive = definition.directives().get("cost").map(|d| &d.node);
+        if let Some(weight) = self.cost_directive(directive) {
+            weight
+        } else if definition.is_leaf() {
+            0.0
+        } else {
+            1.0
+        }
+    }
+
+    /// Returns the `weight` argument of a `@cost` directive,
+    /// which the cost specification defines as a string
+    fn cost_directive(&self, directive: Option<&Node<ast::Directive>>) -> Option<f64> {
+        if !self.config.cost_directives {
+            return None;
+        }
+        let weight = directive?.argument_by_name("weight")?;
+        weight
+            .as_str()
+            .and_then(|weight| weight.parse().ok())
+            .or_else(|| weight.to_f64())
+    }
+
+    fn list_size(&self, field: &Field) -> f64 {
+        let list_size = self
+            .config
+            .cost_directives
+            .then(|| field.definition.directives.get("listSize"))
+            .flatten();
+        let Some(list_size) = list_size else {
+            return self.config.default_list_size;
+        };
+        let slicing_arguments = list_size
+            .argument_by_name("slicingArguments")
+            .and_then(|value| value.as_list())
+            .unwrap_or_default();
+        let sliced = slicing_arguments
+            .iter()
+            .filter_map(|name| field.argument_by_name(name.as_str()?))
+            .filter_map(|argument| argument.value.to_f64())
+            .reduce(f64::max);
+        sliced
+            .or_else(|| {
+                list_size
+                    .argument_by_name("assumedSize")
+                    .and_then(|value| value.to_f64())
+            })
+            .unwrap_or(self.config.default_list_size)
+    }
+}
@@ -9,12 +9,16 @@ use crate::execution::GraphQLError;
 use crate::execution::JsonMap;
 use crate::execution::JsonValue;
 use crate::execution::Response;
+use crate::execution::ResponseData;
 use crate::parser::SourceMap;
 use crate::parser::SourceSpan;
 use crate::schema::ExtendedType;
 use crate::schema::FieldDefinition;
+use crate::validation::value::coerce_valid_value;
 use crate::validation::SuspectedValidationBug;
 use crate::validation::Valid;
+use crate::validation::ValuePath;
+use crate::validation::ValuePathElement;
 use crate::ExecutableDocument;
 use crate::Node;
 use crate::Schema;
@@ -26,12 +30,22 @@ pub enum InputCoercionError {
     ValueError {
         message: String,
         location: Option<SourceSpan>,
+        /// For errors in the value of a variable, the path to the invalid (possibly nested) value
+        path: Option<ValuePath>,
     },
 }
 
+/// Every error found by [`coerce_variable_values`], in the order of variable definitions
+#[derive(Debug, Clone)]
+pub struct InputCoercionErrors {
+    pub errors: Vec<InputCoercionError>,
+}
+
 /// Coerce the values of variables from a GraphQL request to the types expected by the operation.
 ///
-/// If type coercion fails, a request error is returned and the request must not be executed.
+/// If type coercion fails, request errors are returned and the request must not be executed.
+/// Coercion continues after an error, so that every invalid value is reported,
+/// with the path to it from its variable.
 ///
 /// This is [CoerceVariableValues()](https://spec.graphql.org/October2021/#CoerceVariableValues())
 /// in the GraphQL specification.
@@ -39,22 +53,47 @@ pub fn coerce_variable_values(
     schema: &Valid<Schema>,
     operation: &Operation,
     values: &JsonMap,
-) -> Result<Valid<JsonMap>, InputCoercionError> {
+) -> Result<Valid<JsonMap>, InputCoercionErrors> {
     let mut coerced_values = JsonMap::new();
+    let mut coercion = VariableCoercion {
+        schema,
+        errors: Vec::new(),
+        path: Vec::new(),
+        // Nested values have no location of their own, point to the variable definition instead
+        location: None,
+    };
     for variable_def in &operation.variables {
         let name = variable_def.name.as_str();
+        coercion.location = variable_def.location();
+        coercion.path = vec![ValuePathElement::Variable(variable_def.name.clone())];
         if let Some((key, value)) = values.get_key_value(name) {
-            let value =
-                coerce_variable_value(schema, "variable", "", "", name, &variable_def.ty, value)?;
-            coerced_values.insert(key.clone(), value);
+            if let Some(value) = coercion.coerce("variable", "", "", name, &variable_def.ty, value)
+            {
+                coerced_values.insert(key.clone(), value);
+            }
         } else if let Some(default) = &variable_def.default_value {
-            let value = graphql_value_to_json("variable default value", "", "", name, default)?;
-            coerced_values.insert(name, value);
+            // Coerce the default too, so that list wrapping
+            // and defaults of nested input object fields are applied
+            let value = match graphql_value_to_json("variable default value", "", "", name, default)
+            {
+                Ok(value) => coercion.coerce(
+                    "variable default value",
+                    "",
+                    "",
+                    name,
+                    &variable_def.ty,
+                    &value,
+                ),
+                Err(error) => {
+                    coercion.push(error);
+                    None
+                }
+            };
+            if let Some(value) = value {
+                coerced_values.insert(name, value);
+            }
         } else if variable_def.ty.is_non_null() {
-            return Err(InputCoercionError::ValueError {
-                message: format!("missing value for non-null variable '{name}'"),
-                location: variable_def.location(),
-            });
+            coercion.error(format!("missing value for non-null variable '{name}'"));
         } else {
             // Nullable variable with no provided value nor explicit default.
             // Spec says nothing for this case, but for the similar case in input objects:
@@ -63,157 +102,226 @@ pub fn coerce_variable_values(
             // > versus having not provided a value
         }
     }
-    Ok(Valid(coerced_values))
+    if coercion.errors.is_empty() {
+        Ok(Valid(coerced_values))
+    } else {
+        Err(InputCoercionErrors {
+            errors: coercion.errors,
+        })
+    }
 }
 
-#[allow(clippy::too_many_arguments)] // yes it’s not a nice API but it’s internal
-fn coerce_variable_value(
-    schema: &Valid<Schema>,
-    kind: &str,
-    parent: &str,
-    sep: &str,
-    name: &str,
-    ty: &Type,
-    value: &JsonValue,
-) -> Result<JsonValue, InputCoercionError> {
-    if value.is_null() {
-        if ty.is_non_null() {
-            return Err(InputCoercionError::ValueError {
-                message: format!("null value for {kind} {parent}{sep}{name} of non-null type {ty}"),
-                location: None,
-            });
-        } else {
-            return Ok(JsonValue::Null);
-        }
+struct VariableCoercion<'a> {
+    schema: &'a Valid<Schema>,
+    errors: Vec<InputCoercionError>,
+    /// Path from the variable being coerced to the current value
+    path: Vec<ValuePathElement>,
+    /// Location of the definition of the variable being coerced
+    location: Option<SourceSpan>,
+}
+
+impl VariableCoercion<'_> {
+    fn error(&mut self, message: String) {
+        self.errors.push(InputCoercionError::ValueError {
+            message,
+            location: self.location,
+            path: Some(self.path.iter().cloned().collect()),
+        })
     }
-    let ty_name = match ty {
-        Type::List(inner) | Type::NonNullList(inner) => {
-            // https://spec.graphql.org/October2021/#sec-List.Input-Coercion
-            return value
-                .as_array()
-                .map(Vec::as_slice)
-                // If not an array, treat the value as an array of size one:
-                .unwrap_or(std::slice::from_ref(value))
-                .iter()
-                .map(|item| coerce_variable_value(schema, kind, parent, sep, name, inner, item))
-                .collect();
+
+    fn push(&mut self, error: InputCoercionError) {
+        match error {
+            InputCoercionError::ValueError { message, .. } => self.error(message),
+            error => self.errors.push(error),
         }
-        Type::Named(ty_name) | Type::NonNullNamed(ty_name) => ty_name,
-    };
-    let Some(ty_def) = schema.types.get(ty_name) else {
-        Err(SuspectedValidationBug {
-            message: format!("Undefined type {ty_name} for {kind} {parent}{sep}{name}"),
-            location: ty_name.location(),
-        })?
-    };
-    match ty_def {
-        ExtendedType::Object(_) | ExtendedType::Interface(_) | ExtendedType::Union(_) => {
-            Err(SuspectedValidationBug {
-                message: format!("Non-input type {ty_name} for {kind} {parent}{sep}{name}."),
-                location: ty_name.location(),
-            })?
+    }
+
+    /// Returns `None` if coercion failed, after recording errors
+    fn coerce(
+        &mut self,
+        kind: &str,
+        parent: &str,
+        sep: &str,
+        name: &str,
+        ty: &Type,
+        value: &JsonValue,
+    ) -> Option<JsonValue> {
+        if value.is_null() {
+            if ty.is_non_null() {
+                self.error(format!(
+                    "null value for {kind} {parent}{sep}{name} of non-null type {ty}"
+                ));
+                return None;
+            } else {
+                return Some(JsonValue::Null);
+            }
         }
-        ExtendedType::Scalar(_) => match ty_name.as_str() {
-            "Int" => {
-                // https://spec.graphql.org/October2021/#sec-Int.Input-Coercion
-                if value
-                    .as_i64()
-                    .is_some_and(|value| i32::try_from(value).is_ok())
-                {
-                    return Ok(value.clone());
+        let ty_name = match ty {
+            Type::List(inner) | Type::NonNullList(inner) => {
+                // https://spec.graphql.org/October2021/#sec-List.Input-Coercion
+                let Some(items) = value.as_array() else {
+                    // If not an array, treat the value as an array of size one:
+                    return self
+                        .coerce(kind, parent, sep, name, inner, value)
+                        .map(|item| vec![item].into());
+                };
+                let mut coerced = Vec::with_capacity(items.len());
+                let mut valid = true;
+                for (index, item) in items.iter().enumerate() {
+                    self.path.push(ValuePathElement::Index(index));
+                    match self.coerce(kind, parent, sep, name, inner, item) {
+                        Some(item) => coerced.push(item),
+                        None => valid = false,
+                    }
+                    self.path.pop();
                 }
+                return valid.then(|| coerced.into());
             }
-            "Float" => {
-                // https://spec.graphql.org/October2021/#sec-Float.Input-Coercion
-                if value.is_f64() {
-                    return Ok(value.clone());
+            Type::Named(ty_name) | Type::NonNullNamed(ty_name) => ty_name,
+        };
+        let Some(ty_def) = self.schema.types.get(ty_name) else {
+            self.push(
+                SuspectedValidationBug {
+                    message: format!("Undefined type {ty_name} for {kind} {parent}{sep}{name}"),
+                    location: ty_name.location(),
                 }
+                .into(),
+            );
+            return None;
+        };
+        match ty_def {
+            ExtendedType::Object(_) | ExtendedType::Interface(_) | ExtendedType::Union(_) => {
+                self.push(
+                    SuspectedValidationBug {
+                        message: format!(
+                            "Non-input type {ty_name} for {kind} {parent}{sep}{name}."
+                        ),
+                        location: ty_name.location(),
+                    }
+                    .into(),
+                );
+                return None;
             }
-            "String" => {
-                // https://spec.graphql.org/October2021/#sec-String.Input-Coercion
-                if value.is_string() {
-                    return Ok(value.clone());
+            ExtendedType::Scalar(_) => match ty_name.as_str() {
+                "Int" => {
+                    // https://spec.graphql.org/October2021/#sec-Int.Input-Coercion
+                    if value
+                        .as_i64()
+                        .is_some_and(|value| i32::try_from(value).is_ok())
+                    {
+                        return Some(value.clone());
+                    }
                 }
-            }
-            "Boolean" => {
-                // https://spec.graphql.org/October2021/#sec-Boolean.Input-Coercion
-                if value.is_boolean() {
-                    return Ok(value.clone());
+                "Float" => {
+                    // https://spec.graphql.org/October2021/#sec-Float.Input-Coercion
+                    // Integer input values are accepted too
+                    if value.is_number() {
+                        return Some(value.clone());
+                    }
                 }
-            }
-            "ID" => {
-                // https://spec.graphql.org/October2021/#sec-ID.Input-Coercion
-                if value.is_string() || value.is_i64() {
-                    return Ok(value.clone());
+                "String" => {
+                    // https://spec.graphql.org/October2021/#sec-String.Input-Coercion
+                    if value.is_string() {
+                        return Some(value.clone());
+                    }
                 }
-            }
-            _ => {
-                // Custom scalar
-                // TODO: have a hook for coercion of custom scalars?
-                return Ok(value.clone());
-            }
-        },
-        ExtendedType::Enum(ty_def) => {
-            // https://spec.graphql.org/October2021/#sec-Enums.Input-Coercion
-            if let Some(str) = value.as_str() {
-                if ty_def.values.keys().any(|value_name| value_name == str) {
-                    return Ok(value.clone());
+                "Boolean" => {
+                    // https://spec.graphql.org/October2021/#sec-Boolean.Input-Coercion
+                    if value.is_boolean() {
+                        return Some(value.clone());
+                    }
                 }
-            }
-        }
-        ExtendedType::InputObject(ty_def) => {
-            // https://spec.graphql.org/October2021/#sec-Input-Objects.Input-Coercion
-            if let Some(object) = value.as_object() {
-                if let Some(key) = object
-                    .keys()
-                    .find(|key| !ty_def.fields.contains_key(key.as_str()))
-                {
-                    return Err(InputCoercionError::ValueError {
-                        message: format!(
-                            "Input object has key {} not in type {ty_name}",
-                            key.as_str()
-                        ),
-                        location: None,
-                    });
+                "ID" => {
+                    // https://spec.graphql.org/October2021/#sec-ID.Input-Coercion
+                    if value.is_string() {
+                        return Some(value.clone());
+                    }
+                    // Integer input values of any size are coerced to their string representation
+                    if let JsonValue::Number(number) = value {
+                        if number.is_i64() || number.is_u64() {
+                            return Some(number.to_string().as_str().into());
+                        }
+                    }
                 }
-                let mut object = object.clone();
-                for (field_name, field_def) in &ty_def.fields {
-                    if let Some(field_value) = object.get_mut(field_name.as_str()) {
-                        *field_value = coerce_variable_value(
-                            schema,
-                            "input field",
-                            ty_name,
-                            ".",
-                            field_name,
-                            &field_def.ty,
-                            field_value,
-                        )?
-                    } else if let Some(default) = &field_def.default_value {
-                        let default = graphql_value_to_json(
-                            "input field",
-                            ty_name,
-                            ".",
-                            field_name,
-                            default,
-                        )?;
-                        object.insert(field_name.as_str(), default);
-                    } else if field_def.ty.is_non_null() {
-                        return Err(InputCoercionError::ValueError {
-                            message: format!("Missing value for non-null input object field {ty_name}.{field_name}"),
-                            location: None,
-                        });
-                    } else {
-                        // Field not required
+                _ => {
+                    // Custom scalar
+                    // TODO: have a hook for coercion of custom scalars?
+                    return Some(value.clone());
+                }
+            },
+            ExtendedType::Enum(ty_def) => {
+                // https://spec.graphql.org/October2021/#sec-Enums.Input-Coercion
+                if let Some(str) = value.as_str() {
+                    if ty_def.values.keys().any(|value_name| value_name == str) {
+                        return Some(value.clone());
                     }
                 }
-                return Ok(object.into());
+            }
+            ExtendedType::InputObject(ty_def) => {
+                // https://spec.graphql.org/October2021/#sec-Input-Objects.Input-Coercion
+                if let Some(object) = value.as_object() {
+                    let mut valid = true;
+                    for key in object.keys() {
+                        if !ty_def.fields.contains_key(key.as_str()) {
+                            self.error(format!(
+                                "Input object has key {} not in type {ty_name}",
+                                key.as_str()
+                            ));
+                            valid = false;
+                        }
+                    }
+                    let mut object = object.clone();
+                    for (field_name, field_def) in &ty_def.fields {
+                        self.path.push(ValuePathElement::Field(field_name.clone()));
+                        if let Some(field_value) = object.get_mut(field_name.as_str()) {
+                            match self.coerce(
+                                "input field",
+                                ty_name,
+                                ".",
+                                field_name,
+                                &field_def.ty,
+                                field_value,
+                            ) {
+                                Some(coerced) => *field_value = coerced,
+                                None => valid = false,
+                            }
+                        } else if let Some(default) = &field_def.default_value {
+                            match default_value_to_json(
+                                self.schema,
+                                "input field",
+                                ty_name,
+                                ".",
+                                field_name,
+                                &field_def.ty,
+                                default,
+                            ) {
+                                Ok(default) => {
+                                    object.insert(field_name.as_str(), default);
+                                }
+                                Err(error) => {
+                                    self.push(error);
+                                    valid = false
+                                }
+                            }
+                        } else if field_def.ty.is_non_null() {
+                            self.error(format!(
+                                "Missing value for non-null input object field {ty_name}.{field_name}"
+                            ));
+                            valid = false
+                        } else {
+                            // Field not required
+                        }
+                        self.path.pop();
+                    }
+                    return valid.then(|| object.into());
+                }
             }
         }
+        self.error(format!(
+            "Could not coerce {kind} {parent}{sep}{name}: {value} to type {ty_name}"
+        ));
+        None
     }
-    Err(InputCoercionError::ValueError {
-        message: format!("Could not coerce {kind} {parent}{sep}{name}: {value} to type {ty_name}"),
-        location: None,
-    })
 }
 
 fn graphql_value_to_json(
@@ -242,12 +350,14 @@ fn graphql_value_to_json(
             InputCoercionError::ValueError {
                 message: format!("IntValue overflow in {kind} {parent}{sep}{name}"),
                 location: value.location(),
+                path: None,
             }
         })?)),
         Value::Float(f) => Ok(JsonValue::Number(f.as_str().parse().map_err(|_| {
             InputCoercionError::ValueError {
                 message: format!("FloatValue overflow in {kind} {parent}{sep}{name}"),
                 location: value.location(),
+                path: None,
             }
         })?)),
         Value::List(value) => value
@@ -266,6 +376,22 @@ fn graphql_value_to_json(
     }
 }
 
+/// Convert a default value to JSON,
+/// filling in defaults of nested input object fields that it does not have
+#[allow(clippy::too_many_arguments)]
+fn default_value_to_json(
+    schema: &Schema,
+    kind: &str,
+    parent: &str,
+    sep: &str,
+    name: &str,
+    ty: &Type,
+    default: &Node<Value>,
+) -> Result<JsonValue, InputCoercionError> {
+    let default = coerce_valid_value(schema, ty, default);
+    graphql_value_to_json(kind, parent, sep, name, &default)
+}
+
 /// <https://spec.graphql.org/October2021/#sec-Coercing-Field-Arguments>
 pub(crate) fn coerce_argument_values(
     schema: &Schema,
@@ -322,8 +448,9 @@ pub(crate) fn coerce_argument_values(
             }
         }
         if let Some(default) = &arg_def.default_value {
-            let value =
-                graphql_value_to_json("argument", "", "", arg_name, default).map_err(|err| {
+            let ty = &arg_def.ty;
+            let value = default_value_to_json(schema, "argument", "", "", arg_name, ty, default)
+                .map_err(|err| {
                     errors.push(err.into_field_error(path, &document.sources));
                     PropagateNull
                 })?;
@@ -468,12 +595,19 @@ fn coerce_argument_value(
                         )?;
                         coerced_object.insert(field_name.as_str(), coerced_value);
                     } else if let Some(default) = &field_def.default_value {
-                        let default =
-                            graphql_value_to_json("input field", ty_name, ".", field_name, default)
-                                .map_err(|err| {
-                                    errors.push(err.into_field_error(path, &document.sources));
-                                    PropagateNull
-                                })?;
+                        let default = default_value_to_json(
+                            schema,
+                            "input field",
+                            ty_name,
+                            ".",
+                            field_name,
+                            &field_def.ty,
+                            default,
+                        )
+                        .map_err(|err| {
+                            errors.push(err.into_field_error(path, &document.sources));
+                            PropagateNull
+                        })?;
                         coerced_object.insert(field_name.as_str(), default);
                     } else if field_def.ty.is_non_null() {
                         errors.push(GraphQLError::field_error(
@@ -520,7 +654,17 @@ impl InputCoercionError {
     pub fn into_graphql_error(self, sources: &SourceMap) -> GraphQLError {
         match self {
             Self::SuspectedValidationBug(s) => s.into_graphql_error(sources),
-            Self::ValueError { message, location } => GraphQLError::new(message, location, sources),
+            Self::ValueError {
+                message,
+                location,
+                path,
+            } => {
+                let message = match path {
+                    Some(path) if path.is_nested() => format!("{message} (at {path})"),
+                    _ => message,
+                };
+                GraphQLError::new(message, location, sources)
+            }
         }
     }
 
@@ -539,9 +683,31 @@ impl InputCoercionError {
     ) -> GraphQLError {
         match self {
             Self::SuspectedValidationBug(s) => s.into_field_error(sources, path),
-            Self::ValueError { message, location } => {
-                GraphQLError::field_error(message, path, location, sources)
-            }
+            Self::ValueError {
+                message, location, ..
+            } => GraphQLError::field_error(message, path, location, sources),
+        }
+    }
+}
+
+impl InputCoercionErrors {
+    /// Convert into JSON-serializable errors as represented in a GraphQL response
+    pub fn into_graphql_errors(self, sources: &SourceMap) -> Vec<GraphQLError> {
+        self.errors
+            .into_iter()
+            .map(|error| error.into_graphql_error(sources))
+            .collect()
+    }
+
+    /// Convert into a response with these errors as [request errors]
+    /// that prevented execution from starting.
+    ///
+    /// [request errors]: https://spec.graphql.org/October2021/#sec-Errors.Request-errors
+    pub fn into_response(self, sources: &SourceMap) -> Response {
+        Response {
+            errors: self.into_graphql_errors(sources),
+            data: ResponseData::Absent,
+            extensions: JsonMap::new(),
         }
     }
 }
@@ -1,18 +1,21 @@
 use crate::collections::HashMap;
 use crate::executable::Operation;
 use crate::executable::OperationType;
+use crate::execution::coerce_variable_values;
 use crate::execution::engine::execute_selection_set;
 use crate::execution::engine::ExecutionMode;
 use crate::execution::resolver::ResolvedValue;
 use crate::execution::GraphQLError;
 use crate::execution::JsonMap;
 use crate::execution::Response;
+use crate::execution::ResponseData;
 use crate::execution::SchemaIntrospectionError;
 use crate::execution::SchemaIntrospectionSplit;
 use crate::schema;
 use crate::schema::Implementers;
 use crate::schema::Name;
 use crate::validation::Valid;
+use crate::validation::WithErrors;
 use crate::ExecutableDocument;
 use crate::Node;
 use crate::Schema;
@@ -98,6 +101,115 @@ impl SchemaIntrospectionQuery {
     }
 }
 
+/// Parse, validate, and execute a GraphQL request that only selects introspection meta-fields,
+/// answering it entirely from `schema`.
+///
+/// This handles a whole request for servers that have no resolvers of their own,
+/// such as mock servers or gateways that answer introspection locally:
+///
+/// * Parse and validation errors, an unknown or ambiguous `operation_name`,
+///   and invalid `variable_values` are returned as [request errors].
+/// * `__schema` and `__type` fields are executed as with [`SchemaIntrospectionQuery`],
+///   including aliases, fragments, directives like `@include`,
+///   and `includeDeprecated` arguments.
+/// * `__typename` can be selected at the root of a query alongside them.
+/// * Any other root field is a request error,
+///   since answering it would require a resolver.
+///
+/// ```rust
+/// use apollo_compiler::execution::execute_introspection_request;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+/// let query = r#"{ __typename q: __type(name: "Query") { fields { name } } }"#;
+/// let response = execute_introspection_request(&schema, query, None, &Default::default());
+/// assert!(response.errors.is_empty());
+/// let response = serde_json::to_value(&response).unwrap();
+/// assert_eq!(response["data"]["__typename"], "Query");
+/// assert_eq!(response["data"]["q"]["fields"][0]["name"], "a");
+/// ```
+///
+/// [request errors]: https://spec.graphql.org/October2021/#sec-Errors.Request-errors
+pub fn execute_introspection_request(
+    schema: &Valid<Schema>,
+    query: &str,
+    operation_name: Option<&str>,
+    variable_values: &JsonMap,
+) -> Response {
+    let document = match ExecutableDocument::parse_and_validate(schema, query, "request.graphql") {
+        Ok(document) => document,
+        Err(WithErrors { errors, .. }) => {
+            return Response {
+                errors: errors.iter().map(|error| error.to_json()).collect(),
+                data: ResponseData::Absent,
+                extensions: Default::default(),
+            }
+        }
+    };
+    let Ok(operation) = document.operations.get(operation_name) else {
+        let message = match operation_name {
+            Some(name) => format!("unknown operation named `{name}`"),
+            None => {
+                "an operation name is required when a document has multiple operations".to_owned()
+            }
+        };
+        return Response::from_request_error(GraphQLError::new(message, None, &document.sources));
+    };
+    let variable_values = match coerce_variable_values(schema, operation, variable_values) {
+        Ok(variable_values) => variable_values,
+        Err(error) => return error.into_response(&document.sources),
+    };
+    SchemaIntrospectionQuery::split_and_execute(
+        schema,
+        &document,
+        operation,
+        &variable_values,
+        |other_fields_document| {
+            // Either the original document or one with introspection fields removed,
+            // with the same operation names
+            let operation = other_fields_document
+                .operations
+                .get(operation_name)
+                .unwrap();
+            let unsupported = if operation.operation_type != OperationType::Query {
+                Some((
+                    format!(
+                        "a {} cannot be answered from the schema alone",
+                        operation.operation_type.name()
+                    ),
+                    operation.location(),
+                ))
+            } else {
+                operation
+                    .root_fields(other_fields_document)
+                    .find(|field| field.name != "__typename")
+                    .map(|field| {
+                        (
+                            format!(
+                                "field `{}` cannot be answered from the schema alone",
+                                field.name
+                            ),
+                            field.location(),
+                        )
+                    })
+            };
+            if let Some((message, location)) = unsupported {
+                return Response::from_request_error(GraphQLError::new(
+                    message,
+                    location,
+                    &other_fields_document.sources,
+                ));
+            }
+            execute_introspection_only_query(
+                schema,
+                other_fields_document,
+                operation,
+                &variable_values,
+            )
+        },
+    )
+}
+
 /// Execute a query whose [root fields][Operation::root_fields] are all intropsection meta-fields:
 /// `__schema`, `__type`, or `__typename`.
 ///
@@ -6,21 +6,32 @@
 
 #[macro_use]
 mod resolver;
+mod cost;
 mod engine;
 mod input_coercion;
 mod introspection_execute;
 mod introspection_max_depth;
 mod introspection_split;
+mod null_propagation;
 mod response;
 mod result_coercion;
 
+pub use self::cost::estimate_cost;
+pub use self::cost::CostConfig;
+pub use self::cost::CostEstimate;
+pub use self::cost::FieldCost;
 pub use self::input_coercion::coerce_variable_values;
 pub use self::input_coercion::InputCoercionError;
+pub use self::input_coercion::InputCoercionErrors;
 pub use self::introspection_execute::execute_introspection_only_query;
+pub use self::introspection_execute::execute_introspection_request;
 pub use self::introspection_execute::SchemaIntrospectionQuery;
 pub use self::introspection_max_depth::check_introspection_max_depth;
 pub use self::introspection_split::SchemaIntrospectionError;
 pub use self::introspection_split::SchemaIntrospectionSplit;
+pub use self::null_propagation::analyze_null_propagation;
+pub use self::null_propagation::NullPropagation;
+pub use self::null_propagation::NullPropagationReport;
 pub use self::response::GraphQLError;
 pub use self::response::Response;
 pub use self::response::ResponseData;
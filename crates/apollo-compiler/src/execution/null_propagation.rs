@@ -0,0 +1,171 @@
+use crate::ast::Type;
+use crate::collections::HashSet;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::executable::Field;
+use crate::executable::Operation;
+use crate::executable::Selection;
+use crate::executable::SelectionSet;
+use crate::parser::SourceSpan;
+use crate::validation::diagnostics::DiagnosticData;
+use crate::validation::DiagnosticList;
+use crate::validation::Valid;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Node;
+
+/// The result of [`analyze_null_propagation`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct NullPropagationReport {
+    /// Each field with a non-null type selected by the operation, in document order
+    pub fields: Vec<NullPropagation>,
+}
+
+/// Where an error in one non-null field propagates to, in a [`NullPropagationReport`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct NullPropagation {
+    /// Response keys from the root of the operation to this field
+    pub path: Vec<Name>,
+    /// The definition of this field in the schema
+    pub coordinate: TypeAttributeCoordinate,
+    /// The first selection of this field in the document
+    pub location: Option<SourceSpan>,
+    /// Response keys of the nearest ancestor field that can be null.
+    /// When this field has an error, that field or one of its list items is set to `null`.
+    ///
+    /// `None` if no ancestor can be null, so the entire `data` of the response is `null`.
+    pub nulled: Option<Vec<Name>>,
+}
+
+impl NullPropagation {
+    /// Returns whether an error in this field sets more than its parent object to `null`:
+    /// the null propagates through another non-null field, or up to the response `data`.
+    pub fn is_fragile(&self) -> bool {
+        match &self.nulled {
+            Some(nulled) => nulled.len() + 1 < self.path.len(),
+            None => true,
+        }
+    }
+}
+
+impl NullPropagationReport {
+    /// Returns a warning for each [fragile][NullPropagation::is_fragile] field
+    pub fn warnings(&self, document: &ExecutableDocument) -> DiagnosticList {
+        let mut diagnostics = DiagnosticList::new(document.sources.clone());
+        let join = |path: &[Name]| {
+            path.iter()
+                .map(|key| key.as_str())
+                .collect::<Vec<_>>()
+                .join(".")
+        };
+        for field in self.fields.iter().filter(|field| field.is_fragile()) {
+            diagnostics.push(
+                field.location,
+                DiagnosticData::NullPropagation {
+                    path: join(&field.path),
+                    nulled: field.nulled.as_deref().map(join),
+                },
+            );
+        }
+        diagnostics
+    }
+}
+
+/// Find where errors in non-null fields of an operation propagate to.
+///
+/// When a field with a non-null type has an error, [its parent object becomes `null`][spec],
+/// and so on up to the nearest field (or list item) that can be null.
+/// A single error deep in a chain of non-null fields can discard a large part of the response.
+/// Use [`NullPropagationReport::warnings`] to report fields where that goes
+/// beyond the parent object.
+///
+/// Fragments on different types of an abstract type are all included.
+///
+/// [spec]: https://spec.graphql.org/October2021/#sec-Handling-Field-Errors
+pub fn analyze_null_propagation(
+    document: &Valid<ExecutableDocument>,
+    operation: &Operation,
+) -> NullPropagationReport {
+    let mut analyzer = Analyzer {
+        document,
+        seen: HashSet::default(),
+        fields: Vec::new(),
+    };
+    analyzer.selection_set(&operation.selection_set, &mut Vec::new(), None);
+    NullPropagationReport {
+        fields: analyzer.fields,
+    }
+}
+
+struct Analyzer<'a> {
+    document: &'a ExecutableDocument,
+    /// Paths already reported, since fields with the same response key are merged
+    seen: HashSet<Vec<Name>>,
+    fields: Vec<NullPropagation>,
+}
+
+impl Analyzer<'_> {
+    /// `nullable_ancestor` is the length of the path to the nearest ancestor
+    /// that can be null, if any.
+    fn selection_set(
+        &mut self,
+        selection_set: &SelectionSet,
+        path: &mut Vec<Name>,
+        nullable_ancestor: Option<usize>,
+    ) {
+        for selection in &selection_set.selections {
+            match selection {
+                Selection::Field(field) => {
+                    self.field(&selection_set.ty, field, path, nullable_ancestor)
+                }
+                Selection::InlineFragment(inline) => {
+                    self.selection_set(&inline.selection_set, path, nullable_ancestor)
+                }
+                Selection::FragmentSpread(spread) => {
+                    // Validation ensures that `Valid<ExecutableDocument>` does not contain fragment cycles
+                    if let Some(def) = self.document.fragments.get(&spread.fragment_name) {
+                        self.selection_set(&def.selection_set, path, nullable_ancestor)
+                    }
+                }
+            }
+        }
+    }
+
+    fn field(
+        &mut self,
+        parent_type: &Name,
+        field: &Node<Field>,
+        path: &mut Vec<Name>,
+        nullable_ancestor: Option<usize>,
+    ) {
+        path.push(field.response_key().clone());
+        let ty = &field.definition.ty;
+        if ty.is_non_null() && self.seen.insert(path.clone()) {
+            self.fields.push(NullPropagation {
+                path: path.clone(),
+                coordinate: TypeAttributeCoordinate {
+                    ty: parent_type.clone(),
+                    attribute: field.name.clone(),
+                },
+                location: field.location(),
+                nulled: nullable_ancestor.map(|len| path[..len].to_vec()),
+            });
+        }
+        // A null object in this field's value stops here
+        // unless the field and all its list items are non-null
+        let nullable_ancestor = if is_non_null_with_items(ty) {
+            nullable_ancestor
+        } else {
+            Some(path.len())
+        };
+        self.selection_set(&field.selection_set, path, nullable_ancestor);
+        path.pop();
+    }
+}
+
+fn is_non_null_with_items(ty: &Type) -> bool {
+    match ty {
+        Type::NonNullNamed(_) => true,
+        Type::NonNullList(inner) => is_non_null_with_items(inner),
+        Type::Named(_) | Type::List(_) => false,
+    }
+}
@@ -26,12 +26,18 @@ use std::sync::Arc;
 use std::sync::OnceLock;
 
 /// Configuration for parsing an input string as GraphQL syntax
-#[derive(Default, Debug, Clone)]
+#[derive(Debug, Clone)]
 pub struct Parser {
     recursion_limit: Option<usize>,
     token_limit: Option<usize>,
     recursion_reached: usize,
     tokens_reached: usize,
+    anchor: Option<SourceAnchor>,
+    line_terminators: LineTerminators,
+    skip_syntax_tree: bool,
+    lenient: bool,
+    /// Deviations from the grammar accepted in lenient mode, for all inputs parsed so far
+    warnings: DiagnosticList,
 }
 
 /// Records for validation information about a file that was parsed
@@ -40,6 +46,63 @@ pub struct SourceFile {
     pub(crate) path: PathBuf,
     pub(crate) source_text: String,
     pub(crate) source: OnceLock<ariadne::Source>,
+    pub(crate) line_index: OnceLock<LineIndex>,
+    pub(crate) anchor: Option<SourceAnchor>,
+    pub(crate) line_terminators: LineTerminators,
+}
+
+/// The start offsets of lines in a source text,
+/// for converting byte offsets to line numbers.
+///
+/// Which characters terminate a line is configured with [`LineTerminators`].
+/// By default, as specified for GraphQL, lines are terminated by LF (`\n`), CR (`\r`),
+/// or CRLF (`\r\n`) which counts as a single line terminator.
+/// Other Unicode line separators do not start a new line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+    /// Byte offset of the start of each line. The first is always 0.
+    line_starts: Vec<usize>,
+    /// Length of the source text in bytes
+    len: usize,
+}
+
+/// Which characters end a line, for computing line and column numbers
+/// in diagnostics and in [`SourceFile::line_index`].
+///
+/// Parsing itself is not affected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum LineTerminators {
+    /// LF, CR, and CRLF, as specified for GraphQL
+    #[default]
+    GraphQL,
+    /// Only LF, including as part of CRLF.
+    /// A lone CR does not start a new line,
+    /// matching editors and tools that split lines on `\n`.
+    LineFeed,
+}
+
+/// Where parsed source text starts within a larger host file,
+/// for GraphQL embedded in another language.
+///
+/// For example, with an operation extracted from this JavaScript file:
+///
+/// ```js
+/// const query = gql`
+///   { hello }
+/// `;
+/// ```
+///
+/// the anchor of the template contents is at byte offset 18, line 1, column 19.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct SourceAnchor {
+    /// Offset from the start of the host file to the start of the source text, in UTF-8 bytes
+    pub offset: usize,
+    /// Line and column numbers in the host file of the start of the source text.
+    ///
+    /// The column is added to columns on the first line of the source text as-is,
+    /// so it should be counted in the [`PositionEncoding`] that columns will be requested in.
+    pub line_column: LineColumn,
 }
 
 /// A map of source files relevant to a given document
@@ -67,6 +130,19 @@ pub struct SourceSpan {
     pub(crate) text_range: TextRange,
 }
 
+/// The unit used to count columns in a [`LineColumn`]
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum PositionEncoding {
+    /// UTF-8 code units (bytes), like byte offsets into a Rust `str`
+    Utf8,
+    /// UTF-16 code units, as in JavaScript strings and by default in the Language Server Protocol
+    Utf16,
+    /// Unicode Scalar Values, like [`str::chars`].
+    /// This is the encoding used by [`SourceSpan::line_column`] and in diagnostics.
+    #[default]
+    Scalar,
+}
+
 /// A line number and column number within a GraphQL document.
 #[derive(Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -74,7 +150,7 @@ pub struct LineColumn {
     /// The line number for this location, starting at 1 for the first line.
     pub line: usize,
     /// The column number for this location, starting at 1 and counting characters (Unicode Scalar
-    /// Values) like [`str::chars`].
+    /// Values) like [`str::chars`], unless a different [`PositionEncoding`] was requested.
     pub column: usize,
 }
 
@@ -98,6 +174,22 @@ pub fn parse_mixed_validate(
     Parser::new().parse_mixed_validate(source_text, path)
 }
 
+impl Default for Parser {
+    fn default() -> Self {
+        Self {
+            recursion_limit: None,
+            token_limit: None,
+            recursion_reached: 0,
+            tokens_reached: 0,
+            anchor: None,
+            line_terminators: LineTerminators::GraphQL,
+            skip_syntax_tree: false,
+            lenient: false,
+            warnings: DiagnosticList::new(Default::default()),
+        }
+    }
+}
+
 impl Parser {
     pub fn new() -> Self {
         Self::default()
@@ -109,6 +201,59 @@ impl Parser {
         self
     }
 
+    /// Configure where source text passed to this parser starts within a host file.
+    ///
+    /// Spans in the parsed documents stay relative to the source text,
+    /// but line and column numbers in diagnostics and in [`SourceSpan::line_column`]
+    /// refer to the host file, and [`SourceSpan::host_range`] returns host file offsets.
+    pub fn anchor(mut self, anchor: SourceAnchor) -> Self {
+        self.anchor = Some(anchor);
+        self
+    }
+
+    /// Configure which characters end a line when computing line and column numbers
+    /// for source text passed to this parser. Defaults to [`LineTerminators::GraphQL`].
+    pub fn line_terminators(mut self, value: LineTerminators) -> Self {
+        self.line_terminators = value;
+        self
+    }
+
+    /// Configure whether to build documents directly from tokens,
+    /// without first constructing an [`apollo_parser`] concrete syntax tree.
+    ///
+    /// This makes parsing faster and uses less memory, which helps services that parse
+    /// many documents only to validate them. Input with syntax errors, or that reaches a limit,
+    /// is parsed again through the syntax tree so that diagnostics are the same either way.
+    ///
+    /// This applies to [`Parser::parse_ast`], schemas, and executable documents.
+    /// Disabled by default.
+    pub fn skip_syntax_tree(mut self, value: bool) -> Self {
+        self.skip_syntax_tree = value;
+        self
+    }
+
+    /// Configure whether to accept known deviations from the GraphQL grammar,
+    /// such as empty `{}` type bodies, as [warnings][Self::warnings] instead of syntax errors.
+    ///
+    /// See [`apollo_parser::Parser::lenient`] for the deviations that are accepted.
+    /// Disabled by default.
+    ///
+    /// ```rust
+    /// use apollo_compiler::parser::Parser;
+    ///
+    /// let input = "type Query {}";
+    /// assert!(Parser::new().parse_schema(input, "schema.graphql").is_err());
+    ///
+    /// let mut parser = Parser::new().lenient(true);
+    /// let schema = parser.parse_schema(input, "schema.graphql").unwrap();
+    /// assert!(schema.get_object("Query").unwrap().fields.is_empty());
+    /// assert_eq!(parser.warnings().len(), 1);
+    /// ```
+    pub fn lenient(mut self, value: bool) -> Self {
+        self.lenient = value;
+        self
+    }
+
     /// Configure the limit on the number of tokens to parse.
     /// If an input document is too big, parsing will be aborted.
     /// By default, there is no limit.
@@ -138,8 +283,25 @@ impl Parser {
         file_id: FileId,
         errors: &mut DiagnosticList,
     ) -> Document {
+        let source_text = source_text.into();
+        if self.skip_syntax_tree {
+            if let Some(parsed) = ast::from_tokens::parse_document(
+                &source_text,
+                file_id,
+                self.recursion_limit,
+                self.token_limit,
+            ) {
+                self.recursion_reached = parsed.recursion_reached;
+                self.tokens_reached = parsed.tokens_reached;
+                self.add_source_file(source_text, path.as_ref().to_owned(), file_id, errors);
+                return Document {
+                    sources: errors.sources.clone(),
+                    definitions: parsed.definitions,
+                };
+            }
+        }
         let tree = self.parse_common(
-            source_text.into(),
+            source_text,
             path.as_ref().to_owned(),
             file_id,
             errors,
@@ -164,15 +326,11 @@ impl Parser {
         if let Some(value) = self.token_limit {
             parser = parser.token_limit(value)
         }
+        parser = parser.lenient(self.lenient);
         let tree = parse(parser);
         self.recursion_reached = tree.recursion_limit().high;
         self.tokens_reached = tree.token_limit().high;
-        let source_file = Arc::new(SourceFile {
-            path,
-            source_text,
-            source: OnceLock::new(),
-        });
-        Arc::make_mut(&mut errors.sources).insert(file_id, source_file);
+        self.add_source_file(source_text, path, file_id, errors);
         for parser_error in tree.errors() {
             // Silently skip parse errors at index beyond 4 GiB.
             // Rowan in apollo-parser might complain about files that large
@@ -198,9 +356,48 @@ impl Parser {
             };
             errors.push(location, details)
         }
+        if tree.warnings().len() > 0 {
+            let sources = Arc::make_mut(&mut self.warnings.sources);
+            sources.insert(file_id, errors.sources[&file_id].clone());
+        }
+        for warning in tree.warnings() {
+            let (Ok(index), Ok(len)) =
+                (warning.index().try_into(), warning.data().len().try_into())
+            else {
+                continue;
+            };
+            let location = Some(SourceSpan {
+                file_id,
+                text_range: rowan::TextRange::at(index, len),
+            });
+            self.warnings.push_warning(
+                location,
+                Details::SyntaxWarning {
+                    message: warning.message().to_owned(),
+                },
+            )
+        }
         tree
     }
 
+    fn add_source_file(
+        &self,
+        source_text: String,
+        path: PathBuf,
+        file_id: FileId,
+        errors: &mut DiagnosticList,
+    ) {
+        let source_file = Arc::new(SourceFile {
+            path,
+            source_text,
+            source: OnceLock::new(),
+            line_index: OnceLock::new(),
+            anchor: self.anchor,
+            line_terminators: self.line_terminators,
+        });
+        Arc::make_mut(&mut errors.sources).insert(file_id, source_file);
+    }
+
     /// Parse the given source text as the sole input file of a schema.
     ///
     /// `path` is the filesystem path (or arbitrary string) used in diagnostics
@@ -263,6 +460,43 @@ impl Parser {
         (document, errors)
     }
 
+    /// Parse the given source text into a fragment library, with the given schema.
+    ///
+    /// The source text must only contain fragment definitions.
+    ///
+    /// `path` is the filesystem path (or arbitrary string) used in diagnostics
+    /// to identify this source file to users.
+    pub fn parse_fragment_library(
+        &mut self,
+        schema: &Valid<Schema>,
+        source_text: impl Into<String>,
+        path: impl AsRef<Path>,
+    ) -> Result<executable::FragmentLibrary, WithErrors<executable::FragmentLibrary>> {
+        let (library, errors) = self.parse_fragment_library_inner(schema, source_text, path);
+        errors.into_result_with(library)
+    }
+
+    pub(crate) fn parse_fragment_library_inner(
+        &mut self,
+        schema: &Valid<Schema>,
+        source_text: impl Into<String>,
+        path: impl AsRef<Path>,
+    ) -> (executable::FragmentLibrary, DiagnosticList) {
+        let (document, mut errors) = self.parse_executable_inner(schema, source_text, path);
+        for operation in document.operations.iter() {
+            errors.push(
+                operation.location(),
+                executable::BuildError::OperationInFragmentLibrary {
+                    name: operation.name.clone(),
+                },
+            )
+        }
+        let mut library = executable::FragmentLibrary::new();
+        library.sources = document.sources;
+        library.fragments = document.fragments;
+        (library, errors)
+    }
+
     /// Parse a schema and executable document from the given source text
     /// containing a mixture of type system definitions and executable definitions,
     /// and validate them.
@@ -389,6 +623,13 @@ impl Parser {
     pub fn tokens_reached(&self) -> usize {
         self.tokens_reached
     }
+
+    /// Returns the deviations from the GraphQL grammar that were accepted
+    /// in [lenient][Self::lenient] mode, as [warnings][crate::validation::Severity::Warning],
+    /// for every input parsed with this parser so far.
+    pub fn warnings(&self) -> &DiagnosticList {
+        &self.warnings
+    }
 }
 
 impl SourceFile {
@@ -402,20 +643,85 @@ impl SourceFile {
         &self.source_text
     }
 
+    /// Where the source text starts within a host file, if it was parsed with
+    /// [`Parser::anchor`].
+    pub fn anchor(&self) -> Option<SourceAnchor> {
+        self.anchor
+    }
+
+    /// Which characters end a line in this source file, as configured with
+    /// [`Parser::line_terminators`].
+    pub fn line_terminators(&self) -> LineTerminators {
+        self.line_terminators
+    }
+
     pub(crate) fn ariadne(&self) -> &ariadne::Source {
         self.source.get_or_init(|| {
             // FIXME This string copy is not ideal, but changing to a reference counted string affects
             // public API
-            ariadne::Source::from(self.source_text.clone())
+            let mut text = "\n".repeat(self.display_padding());
+            text.reserve(self.source_text.len());
+            // ariadne splits lines on more characters than `LineIndex` does.
+            // Replace the others with characters of the same length in both bytes and `char`s,
+            // so that reports show the same line numbers as `line_column` without shifting spans.
+            let mut chars = self.source_text.chars().peekable();
+            while let Some(c) = chars.next() {
+                text.push(match c {
+                    '\r' if self.line_terminators == LineTerminators::LineFeed
+                        && chars.peek() != Some(&'\n') =>
+                    {
+                        ' '
+                    }
+                    '\x0B' | '\x0C' => ' ',
+                    '\u{0085}' => '\u{00A0}',
+                    '\u{2028}' | '\u{2029}' => '\u{2007}',
+                    _ => c,
+                })
+            }
+            ariadne::Source::from(text)
         })
     }
 
+    /// Number of blank lines before the source text in [`Self::ariadne`],
+    /// so that CLI reports show line numbers of the host file for anchored sources.
+    /// Spans shown in reports are shifted by the same number of bytes.
+    pub(crate) fn display_padding(&self) -> usize {
+        self.anchor
+            .map_or(0, |anchor| anchor.line_column.line.saturating_sub(1))
+    }
+
+    /// The line index of the source text, computed on first use
+    pub fn line_index(&self) -> &LineIndex {
+        self.line_index
+            .get_or_init(|| LineIndex::with_terminators(&self.source_text, self.line_terminators))
+    }
+
     pub(crate) fn get_line_column(&self, index: usize) -> Option<LineColumn> {
-        let (_, zero_indexed_line, zero_indexed_column) = self.ariadne().get_byte_line(index)?;
-        Some(LineColumn {
-            line: zero_indexed_line + 1,
-            column: zero_indexed_column + 1,
-        })
+        self.line_column(index, PositionEncoding::Scalar)
+    }
+
+    /// The line and column numbers of a byte offset in the source text,
+    /// with columns counted in the given encoding.
+    ///
+    /// Only the text between the start of the line and `offset` is scanned.
+    /// Returns `None` if the offset is past the end of the text
+    /// or not on a character boundary.
+    pub fn line_column(&self, offset: usize, encoding: PositionEncoding) -> Option<LineColumn> {
+        let line = self.line_index().line_of(offset)?;
+        let line_start = self.line_index().line_start(line)?;
+        let prefix = self.source_text.get(line_start..offset)?;
+        let mut line_column = LineColumn {
+            line,
+            column: encoding.len(prefix) + 1,
+        };
+        if let Some(anchor) = self.anchor {
+            // Only the first line shares its start with a line of the host file
+            if line == 1 {
+                line_column.column += anchor.line_column.column.saturating_sub(1);
+            }
+            line_column.line += anchor.line_column.line.saturating_sub(1);
+        }
+        Some(line_column)
     }
 }
 
@@ -425,9 +731,18 @@ impl std::fmt::Debug for SourceFile {
             path,
             source_text,
             source: _, // Skipped: it’s a cache and would make debugging other things noisy
+            line_index: _, // Skipped: same
+            anchor,
+            line_terminators,
         } = self;
         let mut debug_struct = f.debug_struct("SourceFile");
         debug_struct.field("path", path);
+        if let Some(anchor) = anchor {
+            debug_struct.field("anchor", anchor);
+        }
+        if *line_terminators != LineTerminators::default() {
+            debug_struct.field("line_terminators", line_terminators);
+        }
         if path != std::path::Path::new("built_in.graphql") {
             debug_struct.field("source_text", source_text);
         } else {
@@ -440,6 +755,71 @@ impl std::fmt::Debug for SourceFile {
     }
 }
 
+impl PositionEncoding {
+    /// The length of `text` in this encoding
+    pub fn len(self, text: &str) -> usize {
+        match self {
+            PositionEncoding::Utf8 => text.len(),
+            PositionEncoding::Utf16 => text.chars().map(char::len_utf16).sum(),
+            PositionEncoding::Scalar => text.chars().count(),
+        }
+    }
+}
+
+impl LineIndex {
+    /// Find the start of every line in `source_text`, with GraphQL line terminators
+    pub fn new(source_text: &str) -> Self {
+        Self::with_terminators(source_text, LineTerminators::GraphQL)
+    }
+
+    /// Find the start of every line in `source_text`, with the given line terminators
+    pub fn with_terminators(source_text: &str, terminators: LineTerminators) -> Self {
+        let mut line_starts = vec![0];
+        let bytes = source_text.as_bytes();
+        for (index, &byte) in bytes.iter().enumerate() {
+            match byte {
+                b'\n' => line_starts.push(index + 1),
+                // For CRLF, the line starts after the LF instead
+                b'\r'
+                    if terminators == LineTerminators::GraphQL
+                        && bytes.get(index + 1) != Some(&b'\n') =>
+                {
+                    line_starts.push(index + 1)
+                }
+                _ => {}
+            }
+        }
+        Self {
+            line_starts,
+            len: source_text.len(),
+        }
+    }
+
+    /// The number of lines, at least 1.
+    ///
+    /// A line terminator at the very end of the text is followed by an empty last line.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// The byte offset where the given line starts, with lines numbered from 1
+    pub fn line_start(&self, line: usize) -> Option<usize> {
+        self.line_starts.get(line.checked_sub(1)?).copied()
+    }
+
+    /// The line number, starting at 1, containing the given byte offset.
+    ///
+    /// An offset in the middle of a line terminator belongs to the line it terminates.
+    /// Returns `None` if the offset is past the end of the text.
+    pub fn line_of(&self, offset: usize) -> Option<usize> {
+        if offset > self.len {
+            return None;
+        }
+        // `line_starts[0]` is 0, so this is at least 1
+        Some(self.line_starts.partition_point(|&start| start <= offset))
+    }
+}
+
 impl std::fmt::Debug for FileId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.id.fmt(f)
@@ -560,6 +940,14 @@ impl SourceSpan {
         self.text_range.len().into()
     }
 
+    /// Returns the range from [`Self::offset`] to [`Self::end_offset`], in UTF-8 bytes.
+    ///
+    /// Together with [`Self::file_id`], this identifies the exact source text of a node,
+    /// for example to replace it with a text edit.
+    pub fn range(&self) -> Range<usize> {
+        self.offset()..self.end_offset()
+    }
+
     /// Best effort at making a location with the given start and end
     pub fn recompose(start_of: Option<Self>, end_of: Option<Self>) -> Option<Self> {
         match (start_of, end_of) {
@@ -578,6 +966,18 @@ impl SourceSpan {
         }
     }
 
+    /// The range from [`Self::offset`] to [`Self::end_offset`], shifted by the offset of the
+    /// source file's [`SourceAnchor`] if it has one.
+    ///
+    /// For GraphQL embedded in another language, this is the range within the host file.
+    pub fn host_range(&self, sources: &SourceMap) -> Range<usize> {
+        let base = sources
+            .get(&self.file_id)
+            .and_then(|source| source.anchor)
+            .map_or(0, |anchor| anchor.offset);
+        base + self.offset()..base + self.end_offset()
+    }
+
     /// The line and column numbers of [`Self::offset`]
     pub fn line_column(&self, sources: &SourceMap) -> Option<LineColumn> {
         let source = sources.get(&self.file_id)?;
@@ -587,9 +987,20 @@ impl SourceSpan {
     /// The line and column numbers of the range from [`Self::offset`] to [`Self::end_offset`]
     /// inclusive.
     pub fn line_column_range(&self, sources: &SourceMap) -> Option<Range<LineColumn>> {
+        self.line_column_range_with_encoding(sources, PositionEncoding::Scalar)
+    }
+
+    /// Like [`Self::line_column_range`], with columns counted in the given encoding.
+    ///
+    /// For example, the Language Server Protocol uses [`PositionEncoding::Utf16`] by default.
+    pub fn line_column_range_with_encoding(
+        &self,
+        sources: &SourceMap,
+        encoding: PositionEncoding,
+    ) -> Option<Range<LineColumn>> {
         let source = sources.get(&self.file_id)?;
-        let start = source.get_line_column(self.offset())?;
-        let end = source.get_line_column(self.end_offset())?;
+        let start = source.line_column(self.offset(), encoding)?;
+        let end = source.line_column(self.end_offset(), encoding)?;
         Some(Range { start, end })
     }
 }
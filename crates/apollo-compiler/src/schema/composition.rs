@@ -0,0 +1,995 @@
+use crate::ast;
+use crate::ast::Type;
+use crate::collections::HashMap;
+use crate::collections::HashSet;
+use crate::collections::IndexMap;
+use crate::collections::IndexSet;
+use crate::coordinate::FieldArgumentCoordinate;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::schema::Component;
+use crate::schema::ComponentName;
+use crate::schema::ComponentOrigin;
+use crate::schema::DirectiveList;
+use crate::schema::EnumType;
+use crate::schema::ExtendedType;
+use crate::schema::FieldDefinition;
+use crate::schema::InputObjectType;
+use crate::schema::InputValueDefinition;
+use crate::schema::InterfaceType;
+use crate::schema::NamedType;
+use crate::schema::ObjectType;
+use crate::schema::ScalarType;
+use crate::schema::UnionType;
+use crate::validation::DiagnosticList;
+use crate::validation::Valid;
+use crate::validation::ValidationConfig;
+use crate::validation::WithErrors;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+use std::sync::Arc;
+
+/// Types defined by the federation and link specifications, not part of the API schema
+const FEDERATION_TYPES: &[&str] = &["_Any", "_Entity", "_Service", "_FieldSet", "FieldSet"];
+
+/// Root fields added to subgraphs by federation libraries
+const FEDERATION_ROOT_FIELDS: &[&str] = &["_service", "_entities"];
+
+/// Compose subgraph schemas into the API schema that a federated graph exposes to clients.
+///
+/// This is a best-effort preview for local tooling, not a full implementation of
+/// [Federation composition](https://www.apollographql.com/docs/federation/federated-schemas/composition):
+///
+/// * Types with the same name are merged. Object types, interfaces, unions, and enums
+///   have the union of their fields, members, and values in all subgraphs.
+///   Input object types and field arguments have only the fields and arguments
+///   that every subgraph defines.
+/// * Field types must have the same named type in every subgraph.
+///   An output field is nullable if it is nullable in any subgraph,
+///   and an input field or argument is non-null if it is non-null in any subgraph.
+/// * In subgraphs that `@link` to Federation 2, an object type field resolved by
+///   multiple subgraphs must be `@shareable` or part of a `@key` in each of them.
+/// * A field of an entity that only some subgraphs resolve must be reachable:
+///   one of those subgraphs must have a `@key` on the type that is not `resolvable: false`.
+/// * Root operation types are renamed to `Query`, `Mutation`, and `Subscription`.
+/// * The result is an API schema: elements marked `@inaccessible`, fields marked `@external`,
+///   federation types, directives, and `_service` and `_entities` root fields are removed.
+///   Only `@deprecated`, `@specifiedBy`, and `@oneOf` directive applications are kept.
+///   Executable directive definitions are kept if every subgraph defines them.
+///
+/// Federation directives are recognized by their default names, like `@key`,
+/// or with the `federation__` prefix, like `@federation__key`.
+///
+/// If subgraphs cannot be composed, or the composed schema is not valid,
+/// returns errors with the partial composed schema.
+///
+/// ```rust
+/// use apollo_compiler::schema;
+/// use apollo_compiler::Schema;
+///
+/// let accounts = Schema::parse_and_validate(
+///     r#"
+///     directive @key(fields: String!) repeatable on OBJECT
+///     type Query { me: User }
+///     type User @key(fields: "id") { id: ID! name: String }
+///     "#,
+///     "accounts.graphql",
+/// )
+/// .unwrap();
+/// let reviews = Schema::parse_and_validate(
+///     r#"
+///     directive @key(fields: String!) repeatable on OBJECT
+///     type Query { topReviews: [Review] }
+///     type Review { body: String author: User }
+///     type User @key(fields: "id") { id: ID! reviews: [Review] }
+///     "#,
+///     "reviews.graphql",
+/// )
+/// .unwrap();
+/// let composed = schema::compose([("accounts", &accounts), ("reviews", &reviews)]).unwrap();
+/// let user = composed.get_object("User").unwrap();
+/// let fields: Vec<_> = user.fields.keys().map(|name| name.as_str()).collect();
+/// assert_eq!(fields, ["id", "name", "reviews"]);
+/// assert!(composed.directive_definitions.get("key").is_none());
+/// ```
+pub fn compose<'a>(
+    subgraphs: impl IntoIterator<Item = (&'a str, &'a Valid<Schema>)>,
+) -> Result<Valid<Schema>, WithErrors<Schema>> {
+    let subgraphs: Vec<_> = subgraphs
+        .into_iter()
+        .map(|(name, schema)| SubgraphInfo::new(name, schema))
+        .collect();
+    let mut sources = IndexMap::default();
+    for subgraph in &subgraphs {
+        sources.extend(subgraph.schema.sources.iter().map(|(k, v)| (*k, v.clone())));
+    }
+    let sources = Arc::new(sources);
+    let mut composer = Composer {
+        subgraphs: &subgraphs,
+        errors: DiagnosticList::new(sources.clone()),
+        schema: Schema::new(),
+    };
+    composer.compose_types();
+    composer.compose_directive_definitions();
+    composer.compose_root_operations();
+
+    let Composer {
+        mut errors,
+        mut schema,
+        ..
+    } = composer;
+    schema.sources = sources;
+    if errors.is_empty() {
+        errors.merge(schema.validation_diagnostics(&ValidationConfig::default()));
+    }
+    errors.into_valid_result(schema)
+}
+
+/// Inconsistencies between subgraph schemas found by [`compose`]
+#[derive(thiserror::Error, Debug, Clone)]
+pub(crate) enum CompositionError {
+    #[error(
+        "`{type_name}` is {kind} in subgraph `{subgraph}` \
+         but {other_kind} in subgraph `{other_subgraph}`"
+    )]
+    TypeKindMismatch {
+        type_name: NamedType,
+        subgraph: String,
+        kind: &'static str,
+        other_subgraph: String,
+        other_kind: &'static str,
+    },
+
+    #[error(
+        "field `{coordinate}` has type `{ty}` in subgraph `{subgraph}` \
+         but `{other_ty}` in subgraph `{other_subgraph}`"
+    )]
+    FieldTypeMismatch {
+        coordinate: TypeAttributeCoordinate,
+        subgraph: String,
+        ty: Type,
+        other_subgraph: String,
+        other_ty: Type,
+    },
+
+    #[error(
+        "argument `{coordinate}` has type `{ty}` in subgraph `{subgraph}` \
+         but `{other_ty}` in subgraph `{other_subgraph}`"
+    )]
+    ArgumentTypeMismatch {
+        coordinate: FieldArgumentCoordinate,
+        subgraph: String,
+        ty: Type,
+        other_subgraph: String,
+        other_ty: Type,
+    },
+
+    #[error(
+        "field `{coordinate}` is resolved by subgraphs {}, but is not `@shareable` in subgraph `{subgraph}`",
+        quoted(.subgraphs)
+    )]
+    NonShareableField {
+        coordinate: TypeAttributeCoordinate,
+        subgraph: String,
+        subgraphs: Vec<String>,
+    },
+
+    #[error(
+        "field `{coordinate}` can only be resolved by subgraphs {}, \
+         which have no resolvable `@key` on `{}`",
+        quoted(.subgraphs),
+        .coordinate.ty
+    )]
+    UnresolvableField {
+        coordinate: TypeAttributeCoordinate,
+        subgraphs: Vec<String>,
+    },
+}
+
+fn quoted(subgraphs: &[String]) -> String {
+    subgraphs
+        .iter()
+        .map(|name| format!("`{name}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+struct SubgraphInfo<'a> {
+    name: &'a str,
+    schema: &'a Schema,
+    /// Whether the subgraph links to Federation 2, which requires `@shareable`
+    federation_2: bool,
+    /// Names of root operation types that are renamed to their default name
+    root_renames: HashMap<Name, Name>,
+}
+
+impl<'a> SubgraphInfo<'a> {
+    fn new(name: &'a str, schema: &'a Schema) -> Self {
+        let federation_2 = schema
+            .schema_definition
+            .directives
+            .get_all("link")
+            .filter_map(|link| link.argument_by_name("url")?.as_str())
+            .any(|url| url.contains("specs.apollo.dev/federation/v2"));
+        let definition = &schema.schema_definition;
+        let root_renames = [
+            (&definition.query, "Query"),
+            (&definition.mutation, "Mutation"),
+            (&definition.subscription, "Subscription"),
+        ]
+        .into_iter()
+        .filter_map(|(root, default)| {
+            let root = root.as_ref()?;
+            (root.name != default).then(|| (root.name.clone(), Name::new_unchecked(default)))
+        })
+        .collect();
+        Self {
+            name,
+            schema,
+            federation_2,
+            root_renames,
+        }
+    }
+
+    fn composed_name<'n>(&'n self, name: &'n Name) -> &'n Name {
+        self.root_renames.get(name).unwrap_or(name)
+    }
+
+    fn is_root_type(&self, composed_name: &str) -> bool {
+        let definition = &self.schema.schema_definition;
+        [
+            &definition.query,
+            &definition.mutation,
+            &definition.subscription,
+        ]
+        .into_iter()
+        .flatten()
+        .any(|root| self.composed_name(&root.name) == composed_name)
+    }
+}
+
+/// Returns whether `name` or `federation__name` is applied
+fn has_federation_directive<'a>(
+    mut names: impl Iterator<Item = &'a Name>,
+    directive: &str,
+) -> bool {
+    names.any(|name| name == directive || name.strip_prefix("federation__") == Some(directive))
+}
+
+fn is_federation_directive_name(name: &str) -> bool {
+    const DIRECTIVES: &[&str] = &[
+        "key",
+        "extends",
+        "external",
+        "requires",
+        "provides",
+        "shareable",
+        "inaccessible",
+        "override",
+        "tag",
+        "composeDirective",
+        "interfaceObject",
+        "authenticated",
+        "requiresScopes",
+        "policy",
+        "link",
+    ];
+    name.starts_with("federation__") || name.starts_with("link__") || DIRECTIVES.contains(&name)
+}
+
+fn is_federation_type_name(name: &str) -> bool {
+    name.starts_with("federation__")
+        || name.starts_with("link__")
+        || FEDERATION_TYPES.contains(&name)
+}
+
+/// Names of the top-level fields of the `@key` directives of a type
+/// that are not marked `resolvable: false`, and whether there is any such key
+fn resolvable_keys(directives: &DirectiveList) -> (bool, IndexSet<String>) {
+    let mut resolvable = false;
+    let mut fields = IndexSet::default();
+    for key in directives
+        .iter()
+        .filter(|directive| has_federation_directive([&directive.name].into_iter(), "key"))
+    {
+        if key
+            .argument_by_name("resolvable")
+            .and_then(|value| value.to_bool())
+            != Some(false)
+        {
+            resolvable = true;
+        }
+        if let Some(field_set) = key
+            .argument_by_name("fields")
+            .and_then(|value| value.as_str())
+        {
+            fields.extend(top_level_fields(field_set));
+        }
+    }
+    (resolvable, fields)
+}
+
+/// Returns the names selected at the top level of a field set, like `id` and `org` in `id org { id }`
+fn top_level_fields(field_set: &str) -> Vec<String> {
+    let mut depth = 0;
+    let mut names = Vec::new();
+    let mut current = String::new();
+    for char in field_set.chars().chain([' ']) {
+        if char.is_ascii_alphanumeric() || char == '_' {
+            current.push(char);
+            continue;
+        }
+        if !current.is_empty() && depth == 0 {
+            names.push(std::mem::take(&mut current));
+        }
+        current.clear();
+        match char {
+            '{' | '(' => depth += 1,
+            '}' | ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    names
+}
+
+/// Keep only directives that are part of the API schema
+fn api_directives(directives: &ast::DirectiveList) -> ast::DirectiveList {
+    ast::DirectiveList(
+        directives
+            .iter()
+            .filter(|directive| directive.name == "deprecated")
+            .cloned()
+            .collect(),
+    )
+}
+
+fn is_inaccessible(directives: &ast::DirectiveList) -> bool {
+    has_federation_directive(
+        directives.iter().map(|directive| &directive.name),
+        "inaccessible",
+    )
+}
+
+/// Merge two types with the same named type and list structure.
+///
+/// The result is nullable where either type is nullable for output types,
+/// and non-null where either type is non-null for input types.
+fn merge_types(a: &Type, b: &Type, input: bool) -> Option<Type> {
+    let non_null = if input {
+        a.is_non_null() || b.is_non_null()
+    } else {
+        a.is_non_null() && b.is_non_null()
+    };
+    match (a, b) {
+        (
+            Type::Named(a_name) | Type::NonNullNamed(a_name),
+            Type::Named(b_name) | Type::NonNullNamed(b_name),
+        ) if a_name == b_name => Some(if non_null {
+            Type::NonNullNamed(a_name.clone())
+        } else {
+            Type::Named(a_name.clone())
+        }),
+        (
+            Type::List(a_item) | Type::NonNullList(a_item),
+            Type::List(b_item) | Type::NonNullList(b_item),
+        ) => {
+            let item = Box::new(merge_types(a_item, b_item, input)?);
+            Some(if non_null {
+                Type::NonNullList(item)
+            } else {
+                Type::List(item)
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Rename references to root operation types in a field or argument type
+fn rename_type(ty: &Type, subgraph: &SubgraphInfo<'_>) -> Type {
+    match ty {
+        Type::Named(name) => Type::Named(subgraph.composed_name(name).clone()),
+        Type::NonNullNamed(name) => Type::NonNullNamed(subgraph.composed_name(name).clone()),
+        Type::List(item) => Type::List(Box::new(rename_type(item, subgraph))),
+        Type::NonNullList(item) => Type::NonNullList(Box::new(rename_type(item, subgraph))),
+    }
+}
+
+fn component<T>(node: Node<T>) -> Component<T> {
+    Component {
+        origin: ComponentOrigin::Definition,
+        node,
+    }
+}
+
+struct Composer<'a> {
+    subgraphs: &'a [SubgraphInfo<'a>],
+    errors: DiagnosticList,
+    schema: Schema,
+}
+
+impl<'a> Composer<'a> {
+    fn compose_types(&mut self) {
+        let mut definitions: IndexMap<Name, Vec<(&'a SubgraphInfo<'a>, &'a ExtendedType)>> =
+            IndexMap::default();
+        for subgraph in self.subgraphs {
+            for (name, ty) in &subgraph.schema.types {
+                if ty.is_built_in() || is_federation_type_name(name) {
+                    continue;
+                }
+                definitions
+                    .entry(subgraph.composed_name(name).clone())
+                    .or_default()
+                    .push((subgraph, ty));
+            }
+        }
+        for (name, definitions) in definitions {
+            let (first_subgraph, first) = definitions[0];
+            let mut same_kind = Vec::with_capacity(definitions.len());
+            for (subgraph, ty) in definitions {
+                if std::mem::discriminant(ty) == std::mem::discriminant(first) {
+                    same_kind.push((subgraph, ty));
+                } else {
+                    self.errors.push(
+                        ty.location(),
+                        CompositionError::TypeKindMismatch {
+                            type_name: name.clone(),
+                            subgraph: first_subgraph.name.to_owned(),
+                            kind: first.describe(),
+                            other_subgraph: subgraph.name.to_owned(),
+                            other_kind: ty.describe(),
+                        },
+                    )
+                }
+            }
+            if same_kind.iter().any(|(_, ty)| is_inaccessible_type(ty)) {
+                continue;
+            }
+            let composed = match first {
+                ExtendedType::Scalar(_) => self.compose_scalar(&name, &same_kind),
+                ExtendedType::Object(_) => self.compose_object(&name, &same_kind),
+                ExtendedType::Interface(_) => self.compose_interface(&name, &same_kind),
+                ExtendedType::Union(_) => self.compose_union(&name, &same_kind),
+                ExtendedType::Enum(_) => self.compose_enum(&name, &same_kind),
+                ExtendedType::InputObject(_) => self.compose_input_object(&name, &same_kind),
+            };
+            self.schema.types.insert(name, composed);
+        }
+    }
+
+    fn compose_scalar(
+        &mut self,
+        name: &Name,
+        definitions: &[(&'a SubgraphInfo<'a>, &'a ExtendedType)],
+    ) -> ExtendedType {
+        let scalars = definitions.iter().filter_map(|(_, ty)| match ty {
+            ExtendedType::Scalar(scalar) => Some(scalar),
+            _ => None,
+        });
+        let mut composed = ScalarType {
+            description: None,
+            name: name.clone(),
+            directives: Default::default(),
+        };
+        for scalar in scalars {
+            composed.description = composed.description.or(scalar.description.clone());
+            if composed.directives.is_empty() {
+                composed.directives = DirectiveList(
+                    scalar
+                        .directives
+                        .iter()
+                        .filter(|directive| directive.name == "specifiedBy")
+                        .map(|directive| component(directive.node.clone()))
+                        .collect(),
+                );
+            }
+        }
+        ExtendedType::Scalar(Node::new_opt_location(
+            composed,
+            first_location(definitions),
+        ))
+    }
+
+    fn compose_object(
+        &mut self,
+        name: &Name,
+        definitions: &[(&'a SubgraphInfo<'a>, &'a ExtendedType)],
+    ) -> ExtendedType {
+        let objects: Vec<_> = definitions
+            .iter()
+            .filter_map(|(subgraph, ty)| match ty {
+                ExtendedType::Object(object) => Some((*subgraph, object)),
+                _ => None,
+            })
+            .collect();
+        let fields: Vec<_> = objects
+            .iter()
+            .map(|(subgraph, object)| (*subgraph, &object.fields))
+            .collect();
+        let fields = self.compose_fields(name, &fields);
+        self.check_shareable_and_resolvable(name, &objects);
+        let mut composed = ObjectType {
+            description: first_description(objects.iter().map(|(_, ty)| &ty.description)),
+            name: name.clone(),
+            implements_interfaces: Default::default(),
+            directives: Default::default(),
+            fields,
+        };
+        for (subgraph, object) in &objects {
+            composed.implements_interfaces.extend(
+                object
+                    .implements_interfaces
+                    .iter()
+                    .map(|interface| subgraph.composed_name(&interface.name).into()),
+            )
+        }
+        ExtendedType::Object(Node::new_opt_location(
+            composed,
+            first_location(definitions),
+        ))
+    }
+
+    fn compose_interface(
+        &mut self,
+        name: &Name,
+        definitions: &[(&'a SubgraphInfo<'a>, &'a ExtendedType)],
+    ) -> ExtendedType {
+        let interfaces: Vec<_> = definitions
+            .iter()
+            .filter_map(|(subgraph, ty)| match ty {
+                ExtendedType::Interface(interface) => Some((*subgraph, interface)),
+                _ => None,
+            })
+            .collect();
+        let fields: Vec<_> = interfaces
+            .iter()
+            .map(|(subgraph, interface)| (*subgraph, &interface.fields))
+            .collect();
+        let mut composed = InterfaceType {
+            description: first_description(interfaces.iter().map(|(_, ty)| &ty.description)),
+            name: name.clone(),
+            implements_interfaces: Default::default(),
+            directives: Default::default(),
+            fields: self.compose_fields(name, &fields),
+        };
+        for (subgraph, interface) in &interfaces {
+            composed.implements_interfaces.extend(
+                interface
+                    .implements_interfaces
+                    .iter()
+                    .map(|interface| subgraph.composed_name(&interface.name).into()),
+            )
+        }
+        ExtendedType::Interface(Node::new_opt_location(
+            composed,
+            first_location(definitions),
+        ))
+    }
+
+    fn compose_union(
+        &mut self,
+        name: &Name,
+        definitions: &[(&'a SubgraphInfo<'a>, &'a ExtendedType)],
+    ) -> ExtendedType {
+        let mut composed = UnionType {
+            description: None,
+            name: name.clone(),
+            directives: Default::default(),
+            members: Default::default(),
+        };
+        for (subgraph, ty) in definitions {
+            if let ExtendedType::Union(union_) = ty {
+                composed.description = composed.description.or(union_.description.clone());
+                composed.members.extend(
+                    union_
+                        .members
+                        .iter()
+                        .map(|member| ComponentName::from(subgraph.composed_name(&member.name))),
+                )
+            }
+        }
+        ExtendedType::Union(Node::new_opt_location(
+            composed,
+            first_location(definitions),
+        ))
+    }
+
+    fn compose_enum(
+        &mut self,
+        name: &Name,
+        definitions: &[(&'a SubgraphInfo<'a>, &'a ExtendedType)],
+    ) -> ExtendedType {
+        let mut composed = EnumType {
+            description: None,
+            name: name.clone(),
+            directives: Default::default(),
+            values: Default::default(),
+        };
+        let mut inaccessible = HashSet::default();
+        for (_, ty) in definitions {
+            if let ExtendedType::Enum(enum_) = ty {
+                composed.description = composed.description.or(enum_.description.clone());
+                for (value_name, value) in &enum_.values {
+                    if is_inaccessible(&value.directives) {
+                        inaccessible.insert(value_name.clone());
+                    }
+                    composed
+                        .values
+                        .entry(value_name.clone())
+                        .or_insert_with(|| {
+                            let directives = api_directives(&value.directives);
+                            let mut value = value.node.clone();
+                            value.make_mut().directives = directives;
+                            component(value)
+                        });
+                }
+            }
+        }
+        composed
+            .values
+            .retain(|value_name, _| !inaccessible.contains(value_name));
+        ExtendedType::Enum(Node::new_opt_location(
+            composed,
+            first_location(definitions),
+        ))
+    }
+
+    fn compose_input_object(
+        &mut self,
+        name: &Name,
+        definitions: &[(&'a SubgraphInfo<'a>, &'a ExtendedType)],
+    ) -> ExtendedType {
+        let input_objects: Vec<_> = definitions
+            .iter()
+            .filter_map(|(subgraph, ty)| match ty {
+                ExtendedType::InputObject(input_object) => Some((*subgraph, input_object)),
+                _ => None,
+            })
+            .collect();
+        let mut composed = InputObjectType {
+            description: first_description(input_objects.iter().map(|(_, ty)| &ty.description)),
+            name: name.clone(),
+            directives: Default::default(),
+            fields: Default::default(),
+        };
+        if input_objects
+            .iter()
+            .any(|(_, input_object)| input_object.directives.has("oneOf"))
+        {
+            composed.directives = DirectiveList(vec![component(Node::new(ast::Directive {
+                name: Name::new_unchecked("oneOf"),
+                arguments: Vec::new(),
+            }))]);
+        }
+        let (first_subgraph, first) = input_objects[0];
+        // Only fields defined by every subgraph
+        'fields: for (field_name, first_field) in &first.fields {
+            let coordinate = TypeAttributeCoordinate {
+                ty: name.clone(),
+                attribute: field_name.clone(),
+            };
+            let mut ty = rename_type(&first_field.ty, first_subgraph);
+            let mut inaccessible = is_inaccessible(&first_field.directives);
+            for (subgraph, input_object) in &input_objects[1..] {
+                let Some(field) = input_object.fields.get(field_name) else {
+                    continue 'fields;
+                };
+                inaccessible |= is_inaccessible(&field.directives);
+                let other_ty = rename_type(&field.ty, subgraph);
+                match merge_types(&ty, &other_ty, true) {
+                    Some(merged) => ty = merged,
+                    None => self.errors.push(
+                        field.location(),
+                        CompositionError::FieldTypeMismatch {
+                            coordinate: coordinate.clone(),
+                            subgraph: first_subgraph.name.to_owned(),
+                            ty: ty.clone(),
+                            other_subgraph: subgraph.name.to_owned(),
+                            other_ty,
+                        },
+                    ),
+                }
+            }
+            if inaccessible {
+                continue;
+            }
+            let mut field = first_field.node.clone();
+            let field_mut = field.make_mut();
+            field_mut.ty = Node::new(ty);
+            field_mut.directives = api_directives(&field_mut.directives);
+            composed.fields.insert(field_name.clone(), component(field));
+        }
+        ExtendedType::InputObject(Node::new_opt_location(
+            composed,
+            first_location(definitions),
+        ))
+    }
+
+    /// Merge fields of object types or interfaces.
+    /// Fields marked `@external` do not contribute to the composed type.
+    fn compose_fields(
+        &mut self,
+        type_name: &Name,
+        definitions: &[(
+            &'a SubgraphInfo<'a>,
+            &'a IndexMap<Name, Component<FieldDefinition>>,
+        )],
+    ) -> IndexMap<Name, Component<FieldDefinition>> {
+        let mut by_name: IndexMap<
+            &Name,
+            Vec<(&'a SubgraphInfo<'a>, &'a Component<FieldDefinition>)>,
+        > = IndexMap::default();
+        for (subgraph, fields) in definitions {
+            for (field_name, field) in *fields {
+                by_name
+                    .entry(field_name)
+                    .or_default()
+                    .push((*subgraph, field));
+            }
+        }
+        let mut composed = IndexMap::default();
+        for (field_name, fields) in by_name {
+            if FEDERATION_ROOT_FIELDS.contains(&field_name.as_str())
+                || fields
+                    .iter()
+                    .any(|(_, field)| is_inaccessible(&field.directives))
+            {
+                continue;
+            }
+            let resolved: Vec<_> = fields
+                .iter()
+                .filter(|(_, field)| !is_external(field))
+                .copied()
+                .collect();
+            // Fields that are `@external` in every subgraph are not resolved by any
+            let Some(&(first_subgraph, first)) = resolved.first() else {
+                continue;
+            };
+            let coordinate = TypeAttributeCoordinate {
+                ty: type_name.clone(),
+                attribute: field_name.clone(),
+            };
+            let mut ty = rename_type(&first.ty, first_subgraph);
+            for &(subgraph, field) in &fields {
+                let other_ty = rename_type(&field.ty, subgraph);
+                match merge_types(&ty, &other_ty, false) {
+                    Some(merged) => ty = merged,
+                    None => self.errors.push(
+                        field.location(),
+                        CompositionError::FieldTypeMismatch {
+                            coordinate: coordinate.clone(),
+                            subgraph: first_subgraph.name.to_owned(),
+                            ty: ty.clone(),
+                            other_subgraph: subgraph.name.to_owned(),
+                            other_ty,
+                        },
+                    ),
+                }
+            }
+            let arguments = self.compose_arguments(&coordinate, &resolved);
+            let mut field = first.node.clone();
+            let field_mut = field.make_mut();
+            field_mut.ty = ty;
+            field_mut.arguments = arguments;
+            field_mut.directives = api_directives(&field_mut.directives);
+            if !field_mut.directives.has("deprecated") {
+                if let Some(deprecated) = fields
+                    .iter()
+                    .find_map(|(_, field)| field.directives.get("deprecated"))
+                {
+                    field_mut.directives.push(deprecated.clone());
+                }
+            }
+            composed.insert(field_name.clone(), component(field));
+        }
+        composed
+    }
+
+    /// Only arguments defined by every subgraph that resolves the field are kept
+    fn compose_arguments(
+        &mut self,
+        field: &TypeAttributeCoordinate,
+        definitions: &[(&'a SubgraphInfo<'a>, &'a Component<FieldDefinition>)],
+    ) -> Vec<Node<InputValueDefinition>> {
+        let Some(&(first_subgraph, first)) = definitions.first() else {
+            return Vec::new();
+        };
+        let mut composed = Vec::new();
+        'arguments: for first_arg in &first.arguments {
+            let coordinate = FieldArgumentCoordinate {
+                ty: field.ty.clone(),
+                field: field.attribute.clone(),
+                argument: first_arg.name.clone(),
+            };
+            let mut ty = rename_type(&first_arg.ty, first_subgraph);
+            let mut inaccessible = is_inaccessible(&first_arg.directives);
+            for &(subgraph, field) in &definitions[1..] {
+                let Some(arg) = field.argument_by_name(&first_arg.name) else {
+                    continue 'arguments;
+                };
+                inaccessible |= is_inaccessible(&arg.directives);
+                let other_ty = rename_type(&arg.ty, subgraph);
+                match merge_types(&ty, &other_ty, true) {
+                    Some(merged) => ty = merged,
+                    None => self.errors.push(
+                        arg.location(),
+                        CompositionError::ArgumentTypeMismatch {
+                            coordinate: coordinate.clone(),
+                            subgraph: first_subgraph.name.to_owned(),
+                            ty: ty.clone(),
+                            other_subgraph: subgraph.name.to_owned(),
+                            other_ty,
+                        },
+                    ),
+                }
+            }
+            if inaccessible {
+                continue;
+            }
+            let mut arg = first_arg.clone();
+            let arg_mut = arg.make_mut();
+            arg_mut.ty = Node::new(ty);
+            arg_mut.directives = api_directives(&arg_mut.directives);
+            composed.push(arg);
+        }
+        composed
+    }
+
+    fn check_shareable_and_resolvable(
+        &mut self,
+        type_name: &Name,
+        objects: &[(&'a SubgraphInfo<'a>, &'a Node<ObjectType>)],
+    ) {
+        if objects.len() < 2 {
+            return;
+        }
+        let is_root = objects
+            .iter()
+            .any(|(subgraph, _)| subgraph.is_root_type(type_name));
+        let keys: Vec<_> = objects
+            .iter()
+            .map(|(_, object)| resolvable_keys(&object.directives))
+            .collect();
+        let mut field_names = IndexSet::default();
+        for (_, object) in objects {
+            field_names.extend(object.fields.keys());
+        }
+        for field_name in field_names {
+            let coordinate = TypeAttributeCoordinate {
+                ty: type_name.clone(),
+                attribute: field_name.clone(),
+            };
+            let resolving: Vec<_> = objects
+                .iter()
+                .zip(&keys)
+                .filter_map(|((subgraph, object), keys)| {
+                    let field = object.fields.get(field_name)?;
+                    (!is_external(field)).then_some((*subgraph, object, field, keys))
+                })
+                .collect();
+            if resolving.len() > 1 {
+                let names: Vec<_> = resolving
+                    .iter()
+                    .map(|(subgraph, ..)| subgraph.name.to_owned())
+                    .collect();
+                for (subgraph, object, field, (_, key_fields)) in &resolving {
+                    let shareable = !subgraph.federation_2
+                        || has_federation_directive(
+                            field.directives.iter().map(|directive| &directive.name),
+                            "shareable",
+                        )
+                        || has_federation_directive(
+                            object.directives.iter().map(|directive| &directive.name),
+                            "shareable",
+                        )
+                        || key_fields.contains(field_name.as_str());
+                    if !shareable {
+                        self.errors.push(
+                            field.location(),
+                            CompositionError::NonShareableField {
+                                coordinate: coordinate.clone(),
+                                subgraph: subgraph.name.to_owned(),
+                                subgraphs: names.clone(),
+                            },
+                        )
+                    }
+                }
+            }
+            // Root fields are always reachable, and fields that every subgraph resolves
+            // do not require jumping to another subgraph
+            if is_root || resolving.is_empty() || resolving.len() == objects.len() {
+                continue;
+            }
+            if !resolving
+                .iter()
+                .any(|(_, _, _, (resolvable, _))| *resolvable)
+            {
+                let (_, _, field, _) = resolving[0];
+                self.errors.push(
+                    field.location(),
+                    CompositionError::UnresolvableField {
+                        coordinate,
+                        subgraphs: resolving
+                            .iter()
+                            .map(|(subgraph, ..)| subgraph.name.to_owned())
+                            .collect(),
+                    },
+                )
+            }
+        }
+    }
+
+    /// Keep executable directive definitions that every subgraph defines
+    fn compose_directive_definitions(&mut self) {
+        let Some(first) = self.subgraphs.first() else {
+            return;
+        };
+        for (name, definition) in &first.schema.directive_definitions {
+            if definition.is_built_in()
+                || is_federation_directive_name(name)
+                || !definition
+                    .locations
+                    .iter()
+                    .all(|location| location.is_executable_location())
+                || !self.subgraphs[1..]
+                    .iter()
+                    .all(|subgraph| subgraph.schema.directive_definitions.contains_key(name))
+            {
+                continue;
+            }
+            self.schema
+                .directive_definitions
+                .insert(name.clone(), definition.clone());
+        }
+    }
+
+    fn compose_root_operations(&mut self) {
+        let definition = self.schema.schema_definition.make_mut();
+        for (root, name) in [
+            (&mut definition.query, "Query"),
+            (&mut definition.mutation, "Mutation"),
+            (&mut definition.subscription, "Subscription"),
+        ] {
+            let is_root = self
+                .subgraphs
+                .iter()
+                .any(|subgraph| subgraph.is_root_type(name));
+            if is_root && self.schema.types.contains_key(name) {
+                *root = Some(Name::new_unchecked(name).into())
+            }
+        }
+    }
+}
+
+fn is_external(field: &FieldDefinition) -> bool {
+    has_federation_directive(
+        field.directives.iter().map(|directive| &directive.name),
+        "external",
+    )
+}
+
+fn is_inaccessible_type(ty: &ExtendedType) -> bool {
+    let directives = match ty {
+        ExtendedType::Scalar(ty) => &ty.directives,
+        ExtendedType::Object(ty) => &ty.directives,
+        ExtendedType::Interface(ty) => &ty.directives,
+        ExtendedType::Union(ty) => &ty.directives,
+        ExtendedType::Enum(ty) => &ty.directives,
+        ExtendedType::InputObject(ty) => &ty.directives,
+    };
+    has_federation_directive(
+        directives.iter().map(|directive| &directive.name),
+        "inaccessible",
+    )
+}
+
+fn first_description<'b>(
+    descriptions: impl Iterator<Item = &'b Option<Node<str>>>,
+) -> Option<Node<str>> {
+    descriptions.flatten().next().cloned()
+}
+
+fn first_location(
+    definitions: &[(&SubgraphInfo<'_>, &ExtendedType)],
+) -> Option<crate::parser::SourceSpan> {
+    definitions.first().and_then(|(_, ty)| ty.location())
+}
@@ -0,0 +1,200 @@
+use crate::ast;
+use crate::coordinate::DirectiveArgumentCoordinate;
+use crate::coordinate::FieldArgumentCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::parser::SourceSpan;
+use crate::schema::ExtendedType;
+use crate::schema::FieldDefinition;
+use crate::Node;
+use crate::Schema;
+
+/// An element of a schema marked `@deprecated`, as returned by [`Schema::deprecations`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deprecation {
+    pub coordinate: SchemaCoordinate,
+    pub kind: DeprecationKind,
+    /// The `reason` argument of `@deprecated`, or its default value `"No longer supported"`
+    pub reason: String,
+    /// The location of the definition of the deprecated element
+    pub location: Option<SourceSpan>,
+}
+
+/// What kind of element a [`Deprecation`] is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeprecationKind {
+    /// A field of an object or interface type
+    Field,
+    /// An argument of a field or directive definition
+    Argument,
+    /// A field of an input object type
+    InputField,
+    /// A value of an enum type
+    EnumValue,
+}
+
+impl Schema {
+    /// Returns every field, argument, input field, and enum value marked `@deprecated`,
+    /// in schema definition order, with its coordinate, reason, and location.
+    ///
+    /// Built-in types and directives are not included.
+    ///
+    /// ```rust
+    /// use apollo_compiler::schema::DeprecationKind;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse_and_validate(
+    ///     r#"
+    ///     type Query {
+    ///       user(id: ID!, legacyId: Int @deprecated): User
+    ///     }
+    ///     type User {
+    ///       name: String
+    ///       fullName: String @deprecated(reason: "Use `name`")
+    ///     }
+    ///     "#,
+    ///     "schema.graphql",
+    /// )
+    /// .unwrap();
+    /// let deprecations: Vec<_> = schema
+    ///     .deprecations()
+    ///     .into_iter()
+    ///     .map(|deprecation| format!("{}: {}", deprecation.coordinate, deprecation.reason))
+    ///     .collect();
+    /// assert_eq!(
+    ///     deprecations,
+    ///     [
+    ///         "Query.user(legacyId:): No longer supported",
+    ///         "User.fullName: Use `name`",
+    ///     ]
+    /// );
+    /// ```
+    pub fn deprecations(&self) -> Vec<Deprecation> {
+        let mut deprecations = Vec::new();
+        for ty in self.types.values() {
+            if ty.is_built_in() {
+                continue;
+            }
+            match ty {
+                ExtendedType::Object(def) => {
+                    add_fields(&mut deprecations, ty, def.fields.values().map(|f| &f.node))
+                }
+                ExtendedType::Interface(def) => {
+                    add_fields(&mut deprecations, ty, def.fields.values().map(|f| &f.node))
+                }
+                ExtendedType::InputObject(def) => {
+                    for field in def.fields.values() {
+                        let coordinate = TypeAttributeCoordinate {
+                            ty: def.name.clone(),
+                            attribute: field.name.clone(),
+                        };
+                        add(
+                            &mut deprecations,
+                            coordinate.into(),
+                            DeprecationKind::InputField,
+                            &field.directives,
+                            field.location(),
+                        )
+                    }
+                }
+                ExtendedType::Enum(def) => {
+                    for value in def.values.values() {
+                        let coordinate = TypeAttributeCoordinate {
+                            ty: def.name.clone(),
+                            attribute: value.value.clone(),
+                        };
+                        add(
+                            &mut deprecations,
+                            coordinate.into(),
+                            DeprecationKind::EnumValue,
+                            &value.directives,
+                            value.location(),
+                        )
+                    }
+                }
+                ExtendedType::Scalar(_) | ExtendedType::Union(_) => {}
+            }
+        }
+        for (name, definition) in &self.directive_definitions {
+            if definition.is_built_in() {
+                continue;
+            }
+            for argument in &definition.arguments {
+                let coordinate = DirectiveArgumentCoordinate {
+                    directive: name.clone(),
+                    argument: argument.name.clone(),
+                };
+                add(
+                    &mut deprecations,
+                    coordinate.into(),
+                    DeprecationKind::Argument,
+                    &argument.directives,
+                    argument.location(),
+                )
+            }
+        }
+        deprecations
+    }
+}
+
+fn add_fields<'a>(
+    deprecations: &mut Vec<Deprecation>,
+    ty: &ExtendedType,
+    fields: impl Iterator<Item = &'a Node<FieldDefinition>>,
+) {
+    for field in fields {
+        let coordinate = TypeAttributeCoordinate {
+            ty: ty.name().clone(),
+            attribute: field.name.clone(),
+        };
+        add(
+            deprecations,
+            coordinate.into(),
+            DeprecationKind::Field,
+            &field.directives,
+            field.location(),
+        );
+        for argument in &field.arguments {
+            let coordinate = FieldArgumentCoordinate {
+                ty: ty.name().clone(),
+                field: field.name.clone(),
+                argument: argument.name.clone(),
+            };
+            add(
+                deprecations,
+                coordinate.into(),
+                DeprecationKind::Argument,
+                &argument.directives,
+                argument.location(),
+            )
+        }
+    }
+}
+
+fn add(
+    deprecations: &mut Vec<Deprecation>,
+    coordinate: SchemaCoordinate,
+    kind: DeprecationKind,
+    directives: &ast::DirectiveList,
+    location: Option<SourceSpan>,
+) {
+    if let Some(reason) = deprecation_reason(directives) {
+        deprecations.push(Deprecation {
+            coordinate,
+            kind,
+            reason,
+            location,
+        })
+    }
+}
+
+/// Returns the reason given by a `@deprecated` directive in `directives`, if there is one.
+fn deprecation_reason(directives: &ast::DirectiveList) -> Option<String> {
+    let directive = directives.get("deprecated")?;
+    let reason = directive
+        .argument_by_name("reason")
+        .and_then(|value| value.as_str())
+        // The default value of the `reason` argument
+        .unwrap_or("No longer supported");
+    Some(reason.to_owned())
+}
@@ -0,0 +1,801 @@
+use crate::ast;
+use crate::ast::DirectiveLocation;
+use crate::ast::OperationType;
+use crate::ast::Type;
+use crate::ast::Value;
+use crate::collections::IndexMap;
+use crate::collections::IndexSet;
+use crate::coordinate::DirectiveArgumentCoordinate;
+use crate::coordinate::FieldArgumentCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::schema::Component;
+use crate::schema::ComponentName;
+use crate::schema::DirectiveDefinition;
+use crate::schema::ExtendedType;
+use crate::schema::FieldDefinition;
+use crate::schema::InputValueDefinition;
+use crate::schema::NamedType;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+use std::fmt;
+
+/// Returns the changes from schema `old` to schema `new`,
+/// each classified by how it affects existing clients.
+///
+/// This is the core of schema checks: a new version of a schema can be rejected
+/// when it has [breaking][Criticality::Breaking] changes.
+/// Classification is conservative: for example, changing the type of a field
+/// from an interface to an object type that implements it is reported as breaking.
+/// Descriptions and directive applications other than `@deprecated` are not compared.
+///
+/// ```rust
+/// use apollo_compiler::schema;
+/// use apollo_compiler::schema::Criticality;
+/// use apollo_compiler::Schema;
+///
+/// let old = Schema::parse_and_validate("type Query { a: Int b: String }", "old.graphql").unwrap();
+/// let new = Schema::parse_and_validate("type Query { a: Int! c: Int }", "new.graphql").unwrap();
+/// let diff = schema::diff(&old, &new);
+/// let changes: Vec<_> = diff
+///     .changes
+///     .iter()
+///     .map(|change| (change.criticality, change.to_string()))
+///     .collect();
+/// assert_eq!(
+///     changes,
+///     [
+///         (Criticality::Safe, "field `Query.a` changed type from `Int` to `Int!`".to_owned()),
+///         (Criticality::Breaking, "field `Query.b` was removed".to_owned()),
+///         (Criticality::Safe, "field `Query.c` was added".to_owned()),
+///     ]
+/// );
+/// assert!(diff.has_breaking_changes());
+/// ```
+pub fn diff(old: &Schema, new: &Schema) -> SchemaDiff {
+    let mut differ = Differ {
+        changes: Vec::new(),
+    };
+    differ.diff_root_operations(old, new);
+    differ.diff_types(old, new);
+    differ.diff_directive_definitions(old, new);
+    SchemaDiff {
+        changes: differ.changes,
+    }
+}
+
+/// The result of [`diff`]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SchemaDiff {
+    /// Changes in the order of definitions in the old schema,
+    /// followed by definitions added in the new schema
+    pub changes: Vec<SchemaChange>,
+}
+
+/// A change between two versions of a schema, as returned by [`diff`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SchemaChange {
+    pub kind: SchemaChangeKind,
+    pub criticality: Criticality,
+}
+
+/// How a [`SchemaChange`] affects clients of the old schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Criticality {
+    /// Existing operations keep working with the same results
+    Safe,
+    /// Existing operations keep validating, but their results may change,
+    /// such as by returning an enum value that clients do not expect
+    Dangerous,
+    /// Existing operations may fail to validate or execute
+    Breaking,
+}
+
+/// What changed in a [`SchemaChange`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SchemaChangeKind {
+    /// A root operation type was added, removed, or changed to another type
+    RootOperationTypeChanged {
+        operation_type: OperationType,
+        old_type: Option<NamedType>,
+        new_type: Option<NamedType>,
+    },
+    TypeAdded {
+        name: NamedType,
+    },
+    TypeRemoved {
+        name: NamedType,
+    },
+    /// A type was redefined as a different kind of type, such as an object type as an interface
+    TypeKindChanged {
+        name: NamedType,
+        old_kind: &'static str,
+        new_kind: &'static str,
+    },
+    /// A field was added to an object type, interface, or input object type
+    FieldAdded {
+        coordinate: TypeAttributeCoordinate,
+    },
+    /// A field was removed from an object type, interface, or input object type
+    FieldRemoved {
+        coordinate: TypeAttributeCoordinate,
+    },
+    FieldTypeChanged {
+        coordinate: TypeAttributeCoordinate,
+        old_type: Type,
+        new_type: Type,
+    },
+    /// An argument was added to a field or directive definition
+    ArgumentAdded {
+        coordinate: SchemaCoordinate,
+        ty: Type,
+        default_value: Option<Node<Value>>,
+    },
+    /// An argument was removed from a field or directive definition
+    ArgumentRemoved {
+        coordinate: SchemaCoordinate,
+    },
+    ArgumentTypeChanged {
+        coordinate: SchemaCoordinate,
+        old_type: Type,
+        new_type: Type,
+    },
+    /// The default value of an argument or input object field was added, removed, or changed
+    DefaultValueChanged {
+        coordinate: SchemaCoordinate,
+        old_value: Option<Node<Value>>,
+        new_value: Option<Node<Value>>,
+    },
+    EnumValueAdded {
+        coordinate: TypeAttributeCoordinate,
+    },
+    EnumValueRemoved {
+        coordinate: TypeAttributeCoordinate,
+    },
+    UnionMemberAdded {
+        union_name: NamedType,
+        member: NamedType,
+    },
+    UnionMemberRemoved {
+        union_name: NamedType,
+        member: NamedType,
+    },
+    /// An object type or interface now implements `interface`
+    InterfaceImplementationAdded {
+        type_name: NamedType,
+        interface: NamedType,
+    },
+    /// An object type or interface no longer implements `interface`
+    InterfaceImplementationRemoved {
+        type_name: NamedType,
+        interface: NamedType,
+    },
+    /// A field, argument, input object field, or enum value was marked `@deprecated`
+    DeprecationAdded {
+        coordinate: SchemaCoordinate,
+        reason: Option<String>,
+    },
+    /// A field, argument, input object field, or enum value is no longer `@deprecated`
+    DeprecationRemoved {
+        coordinate: SchemaCoordinate,
+    },
+    DirectiveAdded {
+        name: Name,
+    },
+    DirectiveRemoved {
+        name: Name,
+    },
+    DirectiveLocationAdded {
+        name: Name,
+        location: DirectiveLocation,
+    },
+    DirectiveLocationRemoved {
+        name: Name,
+        location: DirectiveLocation,
+    },
+    /// A directive definition became repeatable, or is no longer repeatable
+    DirectiveRepeatableChanged {
+        name: Name,
+        repeatable: bool,
+    },
+}
+
+impl SchemaDiff {
+    /// Returns whether the schemas have no differences that `diff` compares
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns whether any change is [breaking][Criticality::Breaking]
+    pub fn has_breaking_changes(&self) -> bool {
+        self.changes
+            .iter()
+            .any(|change| change.criticality == Criticality::Breaking)
+    }
+
+    /// Returns the changes with the given criticality
+    pub fn with_criticality(
+        &self,
+        criticality: Criticality,
+    ) -> impl Iterator<Item = &SchemaChange> + '_ {
+        self.changes
+            .iter()
+            .filter(move |change| change.criticality == criticality)
+    }
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.kind.fmt(f)
+    }
+}
+
+impl fmt::Display for SchemaChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RootOperationTypeChanged {
+                operation_type,
+                old_type,
+                new_type,
+            } => match (old_type, new_type) {
+                (None, Some(new)) => {
+                    write!(f, "{operation_type} root operation type `{new}` was added")
+                }
+                (Some(old), None) => write!(
+                    f,
+                    "{operation_type} root operation type `{old}` was removed"
+                ),
+                (Some(old), Some(new)) => write!(
+                    f,
+                    "{operation_type} root operation type changed from `{old}` to `{new}`"
+                ),
+                (None, None) => write!(f, "{operation_type} root operation type changed"),
+            },
+            Self::TypeAdded { name } => write!(f, "type `{name}` was added"),
+            Self::TypeRemoved { name } => write!(f, "type `{name}` was removed"),
+            Self::TypeKindChanged {
+                name,
+                old_kind,
+                new_kind,
+            } => write!(f, "type `{name}` changed from {old_kind} to {new_kind}"),
+            Self::FieldAdded { coordinate } => write!(f, "field `{coordinate}` was added"),
+            Self::FieldRemoved { coordinate } => write!(f, "field `{coordinate}` was removed"),
+            Self::FieldTypeChanged {
+                coordinate,
+                old_type,
+                new_type,
+            } => write!(
+                f,
+                "field `{coordinate}` changed type from `{old_type}` to `{new_type}`"
+            ),
+            Self::ArgumentAdded { coordinate, .. } => {
+                write!(f, "argument `{coordinate}` was added")
+            }
+            Self::ArgumentRemoved { coordinate } => {
+                write!(f, "argument `{coordinate}` was removed")
+            }
+            Self::ArgumentTypeChanged {
+                coordinate,
+                old_type,
+                new_type,
+            } => write!(
+                f,
+                "argument `{coordinate}` changed type from `{old_type}` to `{new_type}`"
+            ),
+            Self::DefaultValueChanged {
+                coordinate,
+                old_value,
+                new_value,
+            } => match (old_value, new_value) {
+                (None, Some(new)) => write!(f, "default value `{new}` was added to `{coordinate}`"),
+                (Some(old), None) => {
+                    write!(f, "default value `{old}` was removed from `{coordinate}`")
+                }
+                (Some(old), Some(new)) => write!(
+                    f,
+                    "default value of `{coordinate}` changed from `{old}` to `{new}`"
+                ),
+                (None, None) => write!(f, "default value of `{coordinate}` changed"),
+            },
+            Self::EnumValueAdded { coordinate } => {
+                write!(f, "enum value `{coordinate}` was added")
+            }
+            Self::EnumValueRemoved { coordinate } => {
+                write!(f, "enum value `{coordinate}` was removed")
+            }
+            Self::UnionMemberAdded { union_name, member } => {
+                write!(f, "`{member}` was added to union `{union_name}`")
+            }
+            Self::UnionMemberRemoved { union_name, member } => {
+                write!(f, "`{member}` was removed from union `{union_name}`")
+            }
+            Self::InterfaceImplementationAdded {
+                type_name,
+                interface,
+            } => write!(f, "`{type_name}` now implements `{interface}`"),
+            Self::InterfaceImplementationRemoved {
+                type_name,
+                interface,
+            } => write!(f, "`{type_name}` no longer implements `{interface}`"),
+            Self::DeprecationAdded { coordinate, .. } => {
+                write!(f, "`{coordinate}` was deprecated")
+            }
+            Self::DeprecationRemoved { coordinate } => {
+                write!(f, "`{coordinate}` is no longer deprecated")
+            }
+            Self::DirectiveAdded { name } => write!(f, "directive `@{name}` was added"),
+            Self::DirectiveRemoved { name } => write!(f, "directive `@{name}` was removed"),
+            Self::DirectiveLocationAdded { name, location } => {
+                write!(f, "location {location} was added to directive `@{name}`")
+            }
+            Self::DirectiveLocationRemoved { name, location } => {
+                write!(
+                    f,
+                    "location {location} was removed from directive `@{name}`"
+                )
+            }
+            Self::DirectiveRepeatableChanged { name, repeatable } => {
+                if *repeatable {
+                    write!(f, "directive `@{name}` is now repeatable")
+                } else {
+                    write!(f, "directive `@{name}` is no longer repeatable")
+                }
+            }
+        }
+    }
+}
+
+struct Differ {
+    changes: Vec<SchemaChange>,
+}
+
+impl Differ {
+    fn push(&mut self, criticality: Criticality, kind: SchemaChangeKind) {
+        self.changes.push(SchemaChange { kind, criticality })
+    }
+
+    fn diff_root_operations(&mut self, old: &Schema, new: &Schema) {
+        let old_def = &old.schema_definition;
+        let new_def = &new.schema_definition;
+        for (operation_type, old_type, new_type) in [
+            (OperationType::Query, &old_def.query, &new_def.query),
+            (
+                OperationType::Mutation,
+                &old_def.mutation,
+                &new_def.mutation,
+            ),
+            (
+                OperationType::Subscription,
+                &old_def.subscription,
+                &new_def.subscription,
+            ),
+        ] {
+            let old_type = old_type.as_ref().map(|name| name.name.clone());
+            let new_type = new_type.as_ref().map(|name| name.name.clone());
+            if old_type == new_type {
+                continue;
+            }
+            let criticality = if old_type.is_none() {
+                Criticality::Safe
+            } else {
+                Criticality::Breaking
+            };
+            self.push(
+                criticality,
+                SchemaChangeKind::RootOperationTypeChanged {
+                    operation_type,
+                    old_type,
+                    new_type,
+                },
+            )
+        }
+    }
+
+    fn diff_types(&mut self, old: &Schema, new: &Schema) {
+        for (name, old_ty) in &old.types {
+            match new.types.get(name) {
+                None => self.push(
+                    Criticality::Breaking,
+                    SchemaChangeKind::TypeRemoved { name: name.clone() },
+                ),
+                Some(new_ty) => self.diff_type(name, old_ty, new_ty),
+            }
+        }
+        for name in new.types.keys() {
+            if !old.types.contains_key(name) {
+                self.push(
+                    Criticality::Safe,
+                    SchemaChangeKind::TypeAdded { name: name.clone() },
+                )
+            }
+        }
+    }
+
+    fn diff_type(&mut self, name: &NamedType, old: &ExtendedType, new: &ExtendedType) {
+        match (old, new) {
+            (ExtendedType::Scalar(_), ExtendedType::Scalar(_)) => {}
+            (ExtendedType::Object(old), ExtendedType::Object(new)) => {
+                self.diff_implements(name, &old.implements_interfaces, &new.implements_interfaces);
+                self.diff_fields(name, &old.fields, &new.fields);
+            }
+            (ExtendedType::Interface(old), ExtendedType::Interface(new)) => {
+                self.diff_implements(name, &old.implements_interfaces, &new.implements_interfaces);
+                self.diff_fields(name, &old.fields, &new.fields);
+            }
+            (ExtendedType::Union(old), ExtendedType::Union(new)) => {
+                for member in old.members.difference(&new.members) {
+                    self.push(
+                        Criticality::Breaking,
+                        SchemaChangeKind::UnionMemberRemoved {
+                            union_name: name.clone(),
+                            member: member.name.clone(),
+                        },
+                    )
+                }
+                for member in new.members.difference(&old.members) {
+                    self.push(
+                        Criticality::Dangerous,
+                        SchemaChangeKind::UnionMemberAdded {
+                            union_name: name.clone(),
+                            member: member.name.clone(),
+                        },
+                    )
+                }
+            }
+            (ExtendedType::Enum(old), ExtendedType::Enum(new)) => {
+                for (value, old_def) in &old.values {
+                    let coordinate = attribute(name, value);
+                    match new.values.get(value) {
+                        None => self.push(
+                            Criticality::Breaking,
+                            SchemaChangeKind::EnumValueRemoved { coordinate },
+                        ),
+                        Some(new_def) => self.diff_deprecation(
+                            coordinate.into(),
+                            &old_def.directives,
+                            &new_def.directives,
+                        ),
+                    }
+                }
+                for value in new.values.keys() {
+                    if !old.values.contains_key(value) {
+                        self.push(
+                            Criticality::Dangerous,
+                            SchemaChangeKind::EnumValueAdded {
+                                coordinate: attribute(name, value),
+                            },
+                        )
+                    }
+                }
+            }
+            (ExtendedType::InputObject(old), ExtendedType::InputObject(new)) => {
+                for (field_name, old_field) in &old.fields {
+                    let coordinate = attribute(name, field_name);
+                    match new.fields.get(field_name) {
+                        None => self.push(
+                            Criticality::Breaking,
+                            SchemaChangeKind::FieldRemoved { coordinate },
+                        ),
+                        Some(new_field) => {
+                            if old_field.ty != new_field.ty {
+                                self.push(
+                                    input_type_change_criticality(&old_field.ty, &new_field.ty),
+                                    SchemaChangeKind::FieldTypeChanged {
+                                        coordinate: coordinate.clone(),
+                                        old_type: (*old_field.ty).clone(),
+                                        new_type: (*new_field.ty).clone(),
+                                    },
+                                )
+                            }
+                            self.diff_input_value(coordinate.into(), old_field, new_field)
+                        }
+                    }
+                }
+                for (field_name, new_field) in &new.fields {
+                    if !old.fields.contains_key(field_name) {
+                        self.push(
+                            added_input_criticality(new_field),
+                            SchemaChangeKind::FieldAdded {
+                                coordinate: attribute(name, field_name),
+                            },
+                        )
+                    }
+                }
+            }
+            _ => self.push(
+                Criticality::Breaking,
+                SchemaChangeKind::TypeKindChanged {
+                    name: name.clone(),
+                    old_kind: old.describe(),
+                    new_kind: new.describe(),
+                },
+            ),
+        }
+    }
+
+    fn diff_implements(
+        &mut self,
+        name: &NamedType,
+        old: &IndexSet<ComponentName>,
+        new: &IndexSet<ComponentName>,
+    ) {
+        for interface in old.difference(new) {
+            self.push(
+                Criticality::Breaking,
+                SchemaChangeKind::InterfaceImplementationRemoved {
+                    type_name: name.clone(),
+                    interface: interface.name.clone(),
+                },
+            )
+        }
+        for interface in new.difference(old) {
+            self.push(
+                Criticality::Dangerous,
+                SchemaChangeKind::InterfaceImplementationAdded {
+                    type_name: name.clone(),
+                    interface: interface.name.clone(),
+                },
+            )
+        }
+    }
+
+    fn diff_fields(
+        &mut self,
+        name: &NamedType,
+        old: &IndexMap<Name, Component<FieldDefinition>>,
+        new: &IndexMap<Name, Component<FieldDefinition>>,
+    ) {
+        for (field_name, old_field) in old {
+            let coordinate = attribute(name, field_name);
+            let Some(new_field) = new.get(field_name) else {
+                self.push(
+                    Criticality::Breaking,
+                    SchemaChangeKind::FieldRemoved { coordinate },
+                );
+                continue;
+            };
+            if old_field.ty != new_field.ty {
+                let criticality = if is_safe_output_type_change(&old_field.ty, &new_field.ty) {
+                    Criticality::Safe
+                } else {
+                    Criticality::Breaking
+                };
+                self.push(
+                    criticality,
+                    SchemaChangeKind::FieldTypeChanged {
+                        coordinate: coordinate.clone(),
+                        old_type: old_field.ty.clone(),
+                        new_type: new_field.ty.clone(),
+                    },
+                )
+            }
+            self.diff_arguments(&old_field.arguments, &new_field.arguments, |argument| {
+                FieldArgumentCoordinate {
+                    ty: name.clone(),
+                    field: field_name.clone(),
+                    argument: argument.clone(),
+                }
+                .into()
+            });
+            self.diff_deprecation(
+                coordinate.into(),
+                &old_field.directives,
+                &new_field.directives,
+            );
+        }
+        for field_name in new.keys() {
+            if !old.contains_key(field_name) {
+                self.push(
+                    Criticality::Safe,
+                    SchemaChangeKind::FieldAdded {
+                        coordinate: attribute(name, field_name),
+                    },
+                )
+            }
+        }
+    }
+
+    fn diff_arguments(
+        &mut self,
+        old: &[Node<InputValueDefinition>],
+        new: &[Node<InputValueDefinition>],
+        coordinate: impl Fn(&Name) -> SchemaCoordinate,
+    ) {
+        for old_arg in old {
+            let Some(new_arg) = new.iter().find(|arg| arg.name == old_arg.name) else {
+                self.push(
+                    Criticality::Breaking,
+                    SchemaChangeKind::ArgumentRemoved {
+                        coordinate: coordinate(&old_arg.name),
+                    },
+                );
+                continue;
+            };
+            if old_arg.ty != new_arg.ty {
+                self.push(
+                    input_type_change_criticality(&old_arg.ty, &new_arg.ty),
+                    SchemaChangeKind::ArgumentTypeChanged {
+                        coordinate: coordinate(&old_arg.name),
+                        old_type: (*old_arg.ty).clone(),
+                        new_type: (*new_arg.ty).clone(),
+                    },
+                )
+            }
+            self.diff_input_value(coordinate(&old_arg.name), old_arg, new_arg)
+        }
+        for new_arg in new {
+            if !old.iter().any(|arg| arg.name == new_arg.name) {
+                self.push(
+                    added_input_criticality(new_arg),
+                    SchemaChangeKind::ArgumentAdded {
+                        coordinate: coordinate(&new_arg.name),
+                        ty: (*new_arg.ty).clone(),
+                        default_value: new_arg.default_value.clone(),
+                    },
+                )
+            }
+        }
+    }
+
+    /// Compare the default value and deprecation of an argument or input object field
+    fn diff_input_value(
+        &mut self,
+        coordinate: SchemaCoordinate,
+        old: &InputValueDefinition,
+        new: &InputValueDefinition,
+    ) {
+        if old.default_value != new.default_value {
+            // Removing the default value of a required input breaks operations that omit it
+            let criticality = if new.default_value.is_none() && new.ty.is_non_null() {
+                Criticality::Breaking
+            } else {
+                Criticality::Dangerous
+            };
+            self.push(
+                criticality,
+                SchemaChangeKind::DefaultValueChanged {
+                    coordinate: coordinate.clone(),
+                    old_value: old.default_value.clone(),
+                    new_value: new.default_value.clone(),
+                },
+            )
+        }
+        self.diff_deprecation(coordinate, &old.directives, &new.directives)
+    }
+
+    fn diff_deprecation(
+        &mut self,
+        coordinate: SchemaCoordinate,
+        old: &ast::DirectiveList,
+        new: &ast::DirectiveList,
+    ) {
+        match (old.get("deprecated"), new.get("deprecated")) {
+            (None, Some(deprecated)) => self.push(
+                Criticality::Safe,
+                SchemaChangeKind::DeprecationAdded {
+                    coordinate,
+                    reason: deprecated
+                        .argument_by_name("reason")
+                        .and_then(|value| value.as_str())
+                        .map(ToOwned::to_owned),
+                },
+            ),
+            (Some(_), None) => self.push(
+                Criticality::Safe,
+                SchemaChangeKind::DeprecationRemoved { coordinate },
+            ),
+            _ => {}
+        }
+    }
+
+    fn diff_directive_definitions(&mut self, old: &Schema, new: &Schema) {
+        for (name, old_def) in &old.directive_definitions {
+            match new.directive_definitions.get(name) {
+                None => self.push(
+                    Criticality::Breaking,
+                    SchemaChangeKind::DirectiveRemoved { name: name.clone() },
+                ),
+                Some(new_def) => self.diff_directive_definition(name, old_def, new_def),
+            }
+        }
+        for name in new.directive_definitions.keys() {
+            if !old.directive_definitions.contains_key(name) {
+                self.push(
+                    Criticality::Safe,
+                    SchemaChangeKind::DirectiveAdded { name: name.clone() },
+                )
+            }
+        }
+    }
+
+    fn diff_directive_definition(
+        &mut self,
+        name: &Name,
+        old: &DirectiveDefinition,
+        new: &DirectiveDefinition,
+    ) {
+        for &location in &old.locations {
+            if !new.locations.contains(&location) {
+                self.push(
+                    Criticality::Breaking,
+                    SchemaChangeKind::DirectiveLocationRemoved {
+                        name: name.clone(),
+                        location,
+                    },
+                )
+            }
+        }
+        for &location in &new.locations {
+            if !old.locations.contains(&location) {
+                self.push(
+                    Criticality::Safe,
+                    SchemaChangeKind::DirectiveLocationAdded {
+                        name: name.clone(),
+                        location,
+                    },
+                )
+            }
+        }
+        if old.repeatable != new.repeatable {
+            let criticality = if new.repeatable {
+                Criticality::Safe
+            } else {
+                Criticality::Breaking
+            };
+            self.push(
+                criticality,
+                SchemaChangeKind::DirectiveRepeatableChanged {
+                    name: name.clone(),
+                    repeatable: new.repeatable,
+                },
+            )
+        }
+        self.diff_arguments(&old.arguments, &new.arguments, |argument| {
+            DirectiveArgumentCoordinate {
+                directive: name.clone(),
+                argument: argument.clone(),
+            }
+            .into()
+        });
+    }
+}
+
+fn attribute(ty: &NamedType, attribute: &Name) -> TypeAttributeCoordinate {
+    TypeAttributeCoordinate {
+        ty: ty.clone(),
+        attribute: attribute.clone(),
+    }
+}
+
+/// Adding a required argument or input object field breaks operations that do not provide it
+fn added_input_criticality(def: &InputValueDefinition) -> Criticality {
+    if def.is_required() {
+        Criticality::Breaking
+    } else {
+        Criticality::Safe
+    }
+}
+
+/// Accepting more values in an argument or input object field is safe
+fn input_type_change_criticality(old: &Type, new: &Type) -> Criticality {
+    if is_safe_output_type_change(new, old) {
+        Criticality::Safe
+    } else {
+        Criticality::Breaking
+    }
+}
+
+/// Returns whether every value of type `new` is a value of type `old`,
+/// so clients expecting `old` can handle results of type `new`
+fn is_safe_output_type_change(old: &Type, new: &Type) -> bool {
+    match (old, new) {
+        (Type::Named(old), Type::Named(new) | Type::NonNullNamed(new))
+        | (Type::NonNullNamed(old), Type::NonNullNamed(new)) => old == new,
+        (Type::List(old), Type::List(new) | Type::NonNullList(new))
+        | (Type::NonNullList(old), Type::NonNullList(new)) => is_safe_output_type_change(old, new),
+        _ => false,
+    }
+}
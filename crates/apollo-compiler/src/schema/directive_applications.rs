@@ -0,0 +1,212 @@
+use crate::ast;
+use crate::coordinate::DirectiveArgumentCoordinate;
+use crate::coordinate::FieldArgumentCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::coordinate::TypeCoordinate;
+use crate::schema::Directive;
+use crate::schema::ExtendedType;
+use crate::schema::InputValueDefinition;
+use crate::schema::Value;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+
+/// An application of a directive somewhere in a schema,
+/// as returned by [`Schema::directive_applications`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedDirective<'schema> {
+    /// The element the directive is applied to,
+    /// or `None` for the `schema` definition and its extensions
+    pub coordinate: Option<SchemaCoordinate>,
+    /// The directive as written in the schema
+    pub directive: &'schema Node<Directive>,
+    /// Arguments in the order of the directive definition,
+    /// including default values of arguments that are not specified.
+    /// If the directive is not defined, only arguments as written.
+    pub arguments: Vec<(Name, Node<Value>)>,
+}
+
+impl AppliedDirective<'_> {
+    /// Returns the value of the argument `name`, or its default value
+    pub fn argument(&self, name: &str) -> Option<&Node<Value>> {
+        self.arguments
+            .iter()
+            .find_map(|(arg_name, value)| (arg_name == name).then_some(value))
+    }
+}
+
+impl Schema {
+    /// Returns every application of the directive named `directive_name` in this schema,
+    /// with the coordinate of the element it is applied to and its arguments.
+    ///
+    /// Applications are looked for on the `schema` definition, types, fields,
+    /// field arguments, input fields, enum values, and arguments of directive definitions,
+    /// in schema definition order. Built-in types and directives are not included.
+    ///
+    /// ```rust
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse_and_validate(
+    ///     r#"
+    ///     directive @tag(name: String!, public: Boolean = false) repeatable on OBJECT | FIELD_DEFINITION | ARGUMENT_DEFINITION
+    ///     type Query @tag(name: "root") {
+    ///       user(id: ID! @tag(name: "lookup", public: true)): User
+    ///     }
+    ///     type User {
+    ///       name: String @tag(name: "pii") @tag(name: "profile")
+    ///     }
+    ///     "#,
+    ///     "schema.graphql",
+    /// )
+    /// .unwrap();
+    /// let tags: Vec<_> = schema
+    ///     .directive_applications("tag")
+    ///     .iter()
+    ///     .map(|application| {
+    ///         format!(
+    ///             "{} {} {}",
+    ///             application.coordinate.as_ref().unwrap(),
+    ///             application.argument("name").unwrap(),
+    ///             application.argument("public").unwrap(),
+    ///         )
+    ///     })
+    ///     .collect();
+    /// assert_eq!(
+    ///     tags,
+    ///     [
+    ///         r#"Query "root" false"#,
+    ///         r#"Query.user(id:) "lookup" true"#,
+    ///         r#"User.name "pii" false"#,
+    ///         r#"User.name "profile" false"#,
+    ///     ]
+    /// );
+    /// ```
+    pub fn directive_applications(&self, directive_name: &str) -> Vec<AppliedDirective<'_>> {
+        let mut collector = Collector {
+            schema: self,
+            directive_name,
+            applications: Vec::new(),
+        };
+        collector.add(
+            None,
+            self.schema_definition.directives.iter().map(|d| &d.node),
+        );
+        for ty in self.types.values() {
+            if ty.is_built_in() {
+                continue;
+            }
+            let type_coordinate = TypeCoordinate {
+                ty: ty.name().clone(),
+            };
+            collector.add(
+                Some(type_coordinate.into()),
+                ty.directives().iter().map(|d| &d.node),
+            );
+            match ty {
+                ExtendedType::Object(def) => {
+                    collector.add_fields(&def.name, def.fields.values().map(|f| &f.node))
+                }
+                ExtendedType::Interface(def) => {
+                    collector.add_fields(&def.name, def.fields.values().map(|f| &f.node))
+                }
+                ExtendedType::InputObject(def) => {
+                    for field in def.fields.values() {
+                        let coordinate = TypeAttributeCoordinate {
+                            ty: def.name.clone(),
+                            attribute: field.name.clone(),
+                        };
+                        collector.add(Some(coordinate.into()), &field.directives)
+                    }
+                }
+                ExtendedType::Enum(def) => {
+                    for value in def.values.values() {
+                        let coordinate = TypeAttributeCoordinate {
+                            ty: def.name.clone(),
+                            attribute: value.value.clone(),
+                        };
+                        collector.add(Some(coordinate.into()), &value.directives)
+                    }
+                }
+                ExtendedType::Scalar(_) | ExtendedType::Union(_) => {}
+            }
+        }
+        for (name, definition) in &self.directive_definitions {
+            if definition.is_built_in() {
+                continue;
+            }
+            for argument in &definition.arguments {
+                let coordinate = DirectiveArgumentCoordinate {
+                    directive: name.clone(),
+                    argument: argument.name.clone(),
+                };
+                collector.add(Some(coordinate.into()), &argument.directives)
+            }
+        }
+        collector.applications
+    }
+}
+
+struct Collector<'schema, 'name> {
+    schema: &'schema Schema,
+    directive_name: &'name str,
+    applications: Vec<AppliedDirective<'schema>>,
+}
+
+impl<'schema> Collector<'schema, '_> {
+    fn add_fields(
+        &mut self,
+        type_name: &Name,
+        fields: impl Iterator<Item = &'schema Node<ast::FieldDefinition>>,
+    ) {
+        for field in fields {
+            let coordinate = TypeAttributeCoordinate {
+                ty: type_name.clone(),
+                attribute: field.name.clone(),
+            };
+            self.add(Some(coordinate.into()), &field.directives);
+            for argument in &field.arguments {
+                let coordinate = FieldArgumentCoordinate {
+                    ty: type_name.clone(),
+                    field: field.name.clone(),
+                    argument: argument.name.clone(),
+                };
+                self.add(Some(coordinate.into()), &argument.directives)
+            }
+        }
+    }
+
+    fn add(
+        &mut self,
+        coordinate: Option<SchemaCoordinate>,
+        directives: impl IntoIterator<Item = &'schema Node<Directive>>,
+    ) {
+        for directive in directives {
+            if directive.name != self.directive_name {
+                continue;
+            }
+            let arguments = match self.schema.directive_definitions.get(&directive.name) {
+                Some(definition) => definition
+                    .arguments
+                    .iter()
+                    .filter_map(|argument_def: &Node<InputValueDefinition>| {
+                        let value = directive
+                            .argument_by_name(&argument_def.name)
+                            .or(argument_def.default_value.as_ref())?;
+                        Some((argument_def.name.clone(), value.clone()))
+                    })
+                    .collect(),
+                None => directive
+                    .arguments
+                    .iter()
+                    .map(|argument| (argument.name.clone(), argument.value.clone()))
+                    .collect(),
+            };
+            self.applications.push(AppliedDirective {
+                coordinate: coordinate.clone(),
+                directive,
+                arguments,
+            })
+        }
+    }
+}
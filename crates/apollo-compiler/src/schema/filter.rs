@@ -0,0 +1,508 @@
+use crate::collections::IndexMap;
+use crate::collections::IndexSet;
+use crate::coordinate::DirectiveArgumentCoordinate;
+use crate::coordinate::DirectiveCoordinate;
+use crate::coordinate::FieldArgumentCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::coordinate::TypeCoordinate;
+use crate::schema::Component;
+use crate::schema::ComponentName;
+use crate::schema::Directive;
+use crate::schema::DirectiveDefinition;
+use crate::schema::ExtendedType;
+use crate::schema::FieldDefinition;
+use crate::schema::InputValueDefinition;
+use crate::schema::NamedType;
+use crate::schema::Value;
+use crate::schema::VisitorMut;
+use crate::validation::Valid;
+use crate::Name;
+use crate::Schema;
+use std::fmt;
+
+/// Returns a copy of `schema` without the elements that have the directive
+/// named `directive_name` applied, and a report of everything that was removed.
+///
+/// Types, fields, arguments, input fields, and enum values can be annotated.
+/// Removing them can leave dangling references, which are fixed by removing more elements:
+///
+/// * Fields, arguments, and input fields whose type was removed
+/// * Arguments and input fields whose default value uses a removed enum value or input field
+/// * Fields, input object types, and directive definitions with a removed required argument
+///   or input field, since a value could no longer be provided for it
+/// * Fields and arguments of an interface when they are removed from a type
+///   that implements it, so that they are not reachable through the interface either
+/// * Object, interface, and input object types left without fields,
+///   enum types left without values, and union types left without members
+///
+/// Removed types are also removed from union members, implemented interfaces,
+/// and root operations. Applications of removed directive definitions are removed,
+/// as are arguments of applications that were removed from the definition
+/// or whose value uses a removed enum value or input field.
+///
+/// The result should be validated before use:
+/// for example, it is not valid if the `query` root operation type was removed.
+/// This is how public API schemas are derived from a schema with internal parts.
+///
+/// ```rust
+/// use apollo_compiler::schema;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(
+///     r#"
+///     directive @internal on OBJECT | FIELD_DEFINITION
+///     type Query { me: User, audit: [AuditEntry] }
+///     type User { name: String, ssn: String @internal }
+///     type AuditEntry @internal { user: User }
+///     "#,
+///     "schema.graphql",
+/// )
+/// .unwrap();
+/// let (filtered, report) = schema::remove_annotated(&schema, "internal");
+/// let annotated: Vec<_> = report.annotated.iter().map(|c| c.to_string()).collect();
+/// assert_eq!(annotated, ["User.ssn", "AuditEntry"]);
+/// assert_eq!(
+///     report.cascaded[0].to_string(),
+///     "Query.audit was removed because AuditEntry was removed"
+/// );
+/// assert!(!filtered.types.contains_key("AuditEntry"));
+/// assert!(filtered.validate().is_ok());
+/// ```
+pub fn remove_annotated(schema: &Valid<Schema>, directive_name: &str) -> (Schema, RemovalReport) {
+    let mut removals = Removals {
+        schema,
+        directive_name,
+        removed: IndexMap::default(),
+        changed: true,
+    };
+    while removals.changed {
+        removals.changed = false;
+        removals.find_removals();
+    }
+
+    let mut filtered = Schema::clone(schema);
+    filtered
+        .types
+        .retain(|name, _| !removals.is_type_removed(name));
+    filtered
+        .directive_definitions
+        .retain(|name, _| !removals.is_removed(&directive_coordinate(name)));
+    let definition = filtered.schema_definition.make_mut();
+    for root in [
+        &mut definition.query,
+        &mut definition.mutation,
+        &mut definition.subscription,
+    ] {
+        if root
+            .as_ref()
+            .is_some_and(|root| removals.is_type_removed(&root.name))
+        {
+            *root = None
+        }
+    }
+    filtered.visit_mut(&mut Remover {
+        removals: &removals,
+    });
+
+    let mut report = RemovalReport::default();
+    for (coordinate, cause) in removals.removed {
+        match cause {
+            None => report.annotated.push(coordinate),
+            Some(cause) => report.cascaded.push(CascadedRemoval { coordinate, cause }),
+        }
+    }
+    (filtered, report)
+}
+
+/// What was removed by [`remove_annotated`]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RemovalReport {
+    /// Elements that had the directive applied
+    pub annotated: Vec<SchemaCoordinate>,
+    /// Elements that were removed to fix references to other removed elements
+    pub cascaded: Vec<CascadedRemoval>,
+}
+
+/// An element removed by [`remove_annotated`] because of the removal of another element
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CascadedRemoval {
+    /// The removed element
+    pub coordinate: SchemaCoordinate,
+    /// The element whose removal caused this one,
+    /// which is itself either annotated or cascaded
+    pub cause: SchemaCoordinate,
+}
+
+impl RemovalReport {
+    /// Returns whether nothing was removed
+    pub fn is_empty(&self) -> bool {
+        self.annotated.is_empty() && self.cascaded.is_empty()
+    }
+}
+
+impl fmt::Display for CascadedRemoval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} was removed because {} was removed",
+            self.coordinate, self.cause
+        )
+    }
+}
+
+struct Removals<'a> {
+    schema: &'a Schema,
+    directive_name: &'a str,
+    /// Removed elements, with `None` for annotated elements or the cause of a cascaded removal
+    removed: IndexMap<SchemaCoordinate, Option<SchemaCoordinate>>,
+    changed: bool,
+}
+
+impl Removals<'_> {
+    fn is_removed(&self, coordinate: &SchemaCoordinate) -> bool {
+        self.removed.contains_key(coordinate)
+    }
+
+    fn is_type_removed(&self, name: &NamedType) -> bool {
+        self.is_removed(&type_coordinate(name))
+    }
+
+    fn remove(&mut self, coordinate: SchemaCoordinate, cause: Option<SchemaCoordinate>) {
+        if !self.removed.contains_key(&coordinate) {
+            self.removed.insert(coordinate, cause);
+            self.changed = true;
+        }
+    }
+
+    /// One pass over the schema, finding removals caused by those found so far
+    fn find_removals(&mut self) {
+        for definition in self.schema.directive_definitions.values() {
+            if definition.is_built_in() {
+                continue;
+            }
+            let coordinate = directive_coordinate(&definition.name);
+            for argument in &definition.arguments {
+                if self.is_removed(&coordinate) {
+                    break;
+                }
+                let argument_coordinate = DirectiveArgumentCoordinate {
+                    directive: definition.name.clone(),
+                    argument: argument.name.clone(),
+                };
+                self.check_input_value(argument_coordinate.into(), argument, &coordinate)
+            }
+        }
+        for ty in self.schema.types.values() {
+            if ty.is_built_in() {
+                continue;
+            }
+            let coordinate = type_coordinate(ty.name());
+            if self.is_removed(&coordinate) {
+                continue;
+            }
+            if ty.directives().has(self.directive_name) {
+                self.remove(coordinate, None);
+                continue;
+            }
+            let all_removed = match ty {
+                ExtendedType::Scalar(_) => continue,
+                ExtendedType::Object(def) => {
+                    self.check_fields(&def.name, &def.fields);
+                    self.check_interfaces(&def.name, &def.implements_interfaces, &def.fields);
+                    self.all_removed(def.fields.keys().map(|f| attribute(&def.name, f)))
+                }
+                ExtendedType::Interface(def) => {
+                    self.check_fields(&def.name, &def.fields);
+                    self.check_interfaces(&def.name, &def.implements_interfaces, &def.fields);
+                    self.all_removed(def.fields.keys().map(|f| attribute(&def.name, f)))
+                }
+                ExtendedType::Union(def) => {
+                    self.all_removed(def.members.iter().map(|m| type_coordinate(&m.name)))
+                }
+                ExtendedType::Enum(def) => {
+                    for value in def.values.values() {
+                        let value_coordinate = attribute(&def.name, &value.value);
+                        if value.directives.has(self.directive_name) {
+                            self.remove(value_coordinate, None)
+                        }
+                    }
+                    self.all_removed(def.values.keys().map(|v| attribute(&def.name, v)))
+                }
+                ExtendedType::InputObject(def) => {
+                    for field in def.fields.values() {
+                        if self.is_removed(&coordinate) {
+                            break;
+                        }
+                        let field_coordinate = attribute(&def.name, &field.name);
+                        self.check_input_value(field_coordinate, field, &coordinate)
+                    }
+                    self.all_removed(def.fields.keys().map(|f| attribute(&def.name, f)))
+                }
+            };
+            if let Some(last_removed) = all_removed {
+                self.remove(coordinate, Some(last_removed))
+            }
+        }
+    }
+
+    /// Returns the last of `coordinates` if all of them are removed
+    fn all_removed(
+        &self,
+        coordinates: impl Iterator<Item = SchemaCoordinate>,
+    ) -> Option<SchemaCoordinate> {
+        let mut last = None;
+        for coordinate in coordinates {
+            if !self.is_removed(&coordinate) {
+                return None;
+            }
+            last = Some(coordinate)
+        }
+        last
+    }
+
+    fn check_fields(
+        &mut self,
+        type_name: &Name,
+        fields: &IndexMap<Name, Component<FieldDefinition>>,
+    ) {
+        for field in fields.values() {
+            let coordinate = attribute(type_name, &field.name);
+            if self.is_removed(&coordinate) {
+                continue;
+            }
+            if field.directives.has(self.directive_name) {
+                self.remove(coordinate, None);
+                continue;
+            }
+            let field_type = field.ty.inner_named_type();
+            if self.is_type_removed(field_type) {
+                self.remove(coordinate, Some(type_coordinate(field_type)));
+                continue;
+            }
+            for argument in &field.arguments {
+                if self.is_removed(&coordinate) {
+                    break;
+                }
+                let argument_coordinate = field_argument(type_name, &field.name, &argument.name);
+                self.check_input_value(argument_coordinate, argument, &coordinate)
+            }
+        }
+    }
+
+    /// Removes fields and arguments of implemented interfaces
+    /// when they are removed from the implementing type
+    fn check_interfaces(
+        &mut self,
+        type_name: &Name,
+        interfaces: &IndexSet<ComponentName>,
+        fields: &IndexMap<Name, Component<FieldDefinition>>,
+    ) {
+        for interface in interfaces {
+            if self.is_type_removed(&interface.name) {
+                continue;
+            }
+            let Some(interface_def) = self.schema.get_interface(&interface.name) else {
+                continue;
+            };
+            for field in fields.values() {
+                let Some(interface_field) = interface_def.fields.get(&field.name) else {
+                    continue;
+                };
+                let coordinate = attribute(type_name, &field.name);
+                if self.is_removed(&coordinate) {
+                    self.remove(attribute(&interface.name, &field.name), Some(coordinate));
+                    continue;
+                }
+                for argument in &field.arguments {
+                    let argument_coordinate =
+                        field_argument(type_name, &field.name, &argument.name);
+                    if self.is_removed(&argument_coordinate)
+                        && interface_field.argument_by_name(&argument.name).is_some()
+                    {
+                        let interface_argument =
+                            field_argument(&interface.name, &field.name, &argument.name);
+                        self.remove(interface_argument, Some(argument_coordinate))
+                    }
+                }
+            }
+        }
+    }
+
+    /// Checks an argument or input field, removing `parent` too if it is required
+    fn check_input_value(
+        &mut self,
+        coordinate: SchemaCoordinate,
+        definition: &InputValueDefinition,
+        parent: &SchemaCoordinate,
+    ) {
+        if self.is_removed(&coordinate) {
+            return;
+        }
+        let ty = definition.ty.inner_named_type();
+        let cause = if definition.directives.has(self.directive_name) {
+            None
+        } else if self.is_type_removed(ty) {
+            Some(type_coordinate(ty))
+        } else if let Some(cause) = definition
+            .default_value
+            .as_ref()
+            .and_then(|value| self.removed_in_value(ty, value))
+        {
+            Some(cause)
+        } else {
+            return;
+        };
+        self.remove(coordinate.clone(), cause);
+        if definition.is_required() {
+            self.remove(parent.clone(), Some(coordinate))
+        }
+    }
+
+    /// Returns a removed enum value or input field used in `value` of type `ty`
+    fn removed_in_value(&self, ty: &NamedType, value: &Value) -> Option<SchemaCoordinate> {
+        match value {
+            Value::Enum(name) => {
+                let coordinate = attribute(ty, name);
+                self.is_removed(&coordinate).then_some(coordinate)
+            }
+            Value::List(items) => items
+                .iter()
+                .find_map(|item| self.removed_in_value(ty, item)),
+            Value::Object(fields) => {
+                let input_object = self.schema.get_input_object(ty)?;
+                fields.iter().find_map(|(name, value)| {
+                    let coordinate = attribute(ty, name);
+                    if self.is_removed(&coordinate) {
+                        return Some(coordinate);
+                    }
+                    let field = input_object.fields.get(name)?;
+                    self.removed_in_value(field.ty.inner_named_type(), value)
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Removes from the elements of a schema references to removed elements
+struct Remover<'a> {
+    removals: &'a Removals<'a>,
+}
+
+impl VisitorMut for Remover<'_> {
+    fn visit_type(&mut self, ty: &mut ExtendedType) {
+        let removals = self.removals;
+        match ty {
+            ExtendedType::Scalar(_) => {}
+            ExtendedType::Object(def) => {
+                let def = def.make_mut();
+                let type_name = def.name.clone();
+                def.fields
+                    .retain(|name, _| !removals.is_removed(&attribute(&type_name, name)));
+                def.implements_interfaces
+                    .retain(|interface| !removals.is_type_removed(&interface.name));
+            }
+            ExtendedType::Interface(def) => {
+                let def = def.make_mut();
+                let type_name = def.name.clone();
+                def.fields
+                    .retain(|name, _| !removals.is_removed(&attribute(&type_name, name)));
+                def.implements_interfaces
+                    .retain(|interface| !removals.is_type_removed(&interface.name));
+            }
+            ExtendedType::Union(def) => {
+                def.make_mut()
+                    .members
+                    .retain(|member| !removals.is_type_removed(&member.name));
+            }
+            ExtendedType::Enum(def) => {
+                let def = def.make_mut();
+                let type_name = def.name.clone();
+                def.values
+                    .retain(|name, _| !removals.is_removed(&attribute(&type_name, name)));
+            }
+            ExtendedType::InputObject(def) => {
+                let def = def.make_mut();
+                let type_name = def.name.clone();
+                def.fields
+                    .retain(|name, _| !removals.is_removed(&attribute(&type_name, name)));
+            }
+        }
+    }
+
+    fn visit_field(&mut self, coordinate: &TypeAttributeCoordinate, field: &mut FieldDefinition) {
+        field.arguments.retain(|argument| {
+            let argument = field_argument(&coordinate.ty, &coordinate.attribute, &argument.name);
+            !self.removals.is_removed(&argument)
+        });
+    }
+
+    fn visit_directive_definition(&mut self, definition: &mut DirectiveDefinition) {
+        let name = definition.name.clone();
+        definition.arguments.retain(|argument| {
+            let argument = DirectiveArgumentCoordinate {
+                directive: name.clone(),
+                argument: argument.name.clone(),
+            };
+            !self.removals.is_removed(&argument.into())
+        });
+    }
+
+    fn visit_directive(&mut self, directive: &mut Directive) -> bool {
+        let removals = self.removals;
+        if removals.is_removed(&directive_coordinate(&directive.name)) {
+            return false;
+        }
+        let Some(definition) = removals.schema.directive_definitions.get(&directive.name) else {
+            return true;
+        };
+        let mut keep_directive = true;
+        directive.arguments.retain(|argument| {
+            let Some(argument_def) = definition.argument_by_name(&argument.name) else {
+                return true;
+            };
+            let coordinate = DirectiveArgumentCoordinate {
+                directive: definition.name.clone(),
+                argument: argument.name.clone(),
+            };
+            let keep = !removals.is_removed(&coordinate.into())
+                && removals
+                    .removed_in_value(argument_def.ty.inner_named_type(), &argument.value)
+                    .is_none();
+            if !keep && argument_def.is_required() {
+                keep_directive = false
+            }
+            keep
+        });
+        keep_directive
+    }
+}
+
+fn type_coordinate(name: &NamedType) -> SchemaCoordinate {
+    TypeCoordinate { ty: name.clone() }.into()
+}
+
+fn attribute(ty: &NamedType, attribute: &Name) -> SchemaCoordinate {
+    TypeAttributeCoordinate {
+        ty: ty.clone(),
+        attribute: attribute.clone(),
+    }
+    .into()
+}
+
+fn field_argument(ty: &NamedType, field: &Name, argument: &Name) -> SchemaCoordinate {
+    FieldArgumentCoordinate {
+        ty: ty.clone(),
+        field: field.clone(),
+        argument: argument.clone(),
+    }
+    .into()
+}
+
+fn directive_coordinate(name: &Name) -> SchemaCoordinate {
+    DirectiveCoordinate {
+        directive: name.clone(),
+    }
+    .into()
+}
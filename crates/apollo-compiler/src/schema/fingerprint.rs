@@ -0,0 +1,70 @@
+use crate::validation::ContentHash;
+use crate::Schema;
+
+/// Options for [`Schema::fingerprint_with_options`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FingerprintOptions {
+    descriptions: bool,
+}
+
+impl Default for FingerprintOptions {
+    fn default() -> Self {
+        Self { descriptions: true }
+    }
+}
+
+impl FingerprintOptions {
+    /// Returns the default options, which include descriptions
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether changing a description changes the fingerprint.
+    /// Defaults to true.
+    pub fn descriptions(mut self, enable: bool) -> Self {
+        self.descriptions = enable;
+        self
+    }
+}
+
+impl Schema {
+    /// Returns a hash of the contents of this schema that is stable across process runs,
+    /// so services can cheaply detect that a schema actually changed, or key caches by it.
+    ///
+    /// The fingerprint does not depend on source locations or formatting,
+    /// on the order of definitions, fields, arguments, or enum values,
+    /// or on whether components are in type extensions or in the definition they extend.
+    /// It may change with the version of apollo-compiler.
+    ///
+    /// ```rust
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse("type Query { a: Int, b: String }", "a.graphql").unwrap();
+    /// let reordered = Schema::parse(
+    ///     "type Query {
+    ///         b: String
+    ///     }
+    ///     extend type Query { a: Int }",
+    ///     "b.graphql",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(schema.fingerprint(), reordered.fingerprint());
+    ///
+    /// let changed = Schema::parse("type Query { a: Int, b: ID }", "c.graphql").unwrap();
+    /// assert_ne!(schema.fingerprint(), changed.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> ContentHash {
+        self.fingerprint_with_options(&FingerprintOptions::default())
+    }
+
+    /// Like [`fingerprint`][Self::fingerprint], with configuration of what is ignored
+    pub fn fingerprint_with_options(&self, options: &FingerprintOptions) -> ContentHash {
+        let (folded, _provenance) = self.fold_extensions();
+        let serialize = folded.serialize().sorted().no_indent();
+        if options.descriptions {
+            ContentHash::of_serialized(serialize)
+        } else {
+            ContentHash::of_serialized(serialize.no_descriptions())
+        }
+    }
+}
@@ -0,0 +1,395 @@
+//! Fluent methods of [`SchemaBuilder`] for defining types in code
+
+use crate::ast;
+use crate::schema::SchemaBuilder;
+use std::fmt::Write;
+
+impl SchemaBuilder {
+    /// Define an object type, with fields added by the `build` callback.
+    ///
+    /// Like other definitions added by fluent methods, this is written as SDL
+    /// to a synthetic source file named after the definition, like `builder:Query`,
+    /// then parsed as if passed to [`parse`][Self::parse].
+    /// Names and types are parsed from strings, so invalid syntax and references to
+    /// undefined types are reported by [`build`][Self::build]
+    /// or [validation][crate::Schema::validate] with locations in that source file.
+    ///
+    /// ```rust
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::builder()
+    ///     .interface("Character", |ty| ty.field("name", "String!"))
+    ///     .object("Query", |ty| {
+    ///         ty.field_with("hero", "Character", |field| {
+    ///             field
+    ///                 .description("The hero of a film, or of the whole saga")
+    ///                 .argument_with("episode", "Episode", |arg| arg.default_value("NEWHOPE"))
+    ///         })
+    ///     })
+    ///     .object("Droid", |ty| {
+    ///         ty.implements("Character")
+    ///             .field("name", "String!")
+    ///             .field_with("function", "String", |field| field.deprecated("Ask them"))
+    ///     })
+    ///     .enum_type("Episode", |ty| ty.value("NEWHOPE").value("EMPIRE").value("JEDI"))
+    ///     .build()
+    ///     .unwrap()
+    ///     .validate()
+    ///     .unwrap();
+    /// let hero = schema.type_field("Query", "hero").unwrap();
+    /// assert_eq!(hero.ty.inner_named_type(), "Character");
+    /// assert_eq!(hero.arguments[0].default_value.as_ref().unwrap().to_string(), "NEWHOPE");
+    /// ```
+    pub fn object(
+        self,
+        name: &str,
+        build: impl FnOnce(ObjectTypeBuilder) -> ObjectTypeBuilder,
+    ) -> Self {
+        let ty = build(ObjectTypeBuilder::default());
+        self.add_synthetic(name, ty.to_sdl("type", name))
+    }
+
+    /// Define an interface type, with fields added by the `build` callback.
+    /// See [`object`][Self::object].
+    pub fn interface(
+        self,
+        name: &str,
+        build: impl FnOnce(ObjectTypeBuilder) -> ObjectTypeBuilder,
+    ) -> Self {
+        let ty = build(ObjectTypeBuilder::default());
+        self.add_synthetic(name, ty.to_sdl("interface", name))
+    }
+
+    /// Define an input object type, with fields added by the `build` callback.
+    /// See [`object`][Self::object].
+    pub fn input_object(
+        self,
+        name: &str,
+        build: impl FnOnce(InputObjectTypeBuilder) -> InputObjectTypeBuilder,
+    ) -> Self {
+        let ty = build(InputObjectTypeBuilder::default());
+        let mut sdl = String::new();
+        write_description(&mut sdl, "", &ty.description);
+        writeln!(sdl, "input {name} {{").unwrap();
+        for field in &ty.fields {
+            field.write(&mut sdl, "  ");
+            sdl.push('\n');
+        }
+        sdl.push('}');
+        self.add_synthetic(name, sdl)
+    }
+
+    /// Define an enum type, with values added by the `build` callback.
+    /// See [`object`][Self::object].
+    pub fn enum_type(
+        self,
+        name: &str,
+        build: impl FnOnce(EnumTypeBuilder) -> EnumTypeBuilder,
+    ) -> Self {
+        let ty = build(EnumTypeBuilder::default());
+        let mut sdl = String::new();
+        write_description(&mut sdl, "", &ty.description);
+        writeln!(sdl, "enum {name} {{").unwrap();
+        for value in &ty.values {
+            write_description(&mut sdl, "  ", &value.description);
+            write!(sdl, "  {}", value.name).unwrap();
+            write_deprecated(&mut sdl, &value.deprecated);
+            sdl.push('\n');
+        }
+        sdl.push('}');
+        self.add_synthetic(name, sdl)
+    }
+
+    /// Define a union type with the given member types.
+    /// See [`object`][Self::object].
+    pub fn union<'a>(self, name: &str, members: impl IntoIterator<Item = &'a str>) -> Self {
+        let members: Vec<_> = members.into_iter().collect();
+        let sdl = format!("union {name} = {}", members.join(" | "));
+        self.add_synthetic(name, sdl)
+    }
+
+    /// Define a custom scalar type.
+    /// See [`object`][Self::object].
+    pub fn scalar(self, name: &str) -> Self {
+        let sdl = format!("scalar {name}");
+        self.add_synthetic(name, sdl)
+    }
+
+    fn add_synthetic(self, name: &str, sdl: String) -> Self {
+        self.parse(sdl, format!("builder:{name}"))
+    }
+}
+
+/// Builds an object or interface type for [`SchemaBuilder::object`]
+/// or [`SchemaBuilder::interface`]
+#[derive(Debug, Clone, Default)]
+pub struct ObjectTypeBuilder {
+    description: Option<String>,
+    implements: Vec<String>,
+    fields: Vec<FieldBuilder>,
+}
+
+/// Builds a field definition for [`ObjectTypeBuilder::field_with`]
+#[derive(Debug, Clone)]
+pub struct FieldBuilder {
+    name: String,
+    ty: String,
+    description: Option<String>,
+    arguments: Vec<InputValueBuilder>,
+    deprecated: Option<Option<String>>,
+}
+
+/// Builds an input object type for [`SchemaBuilder::input_object`]
+#[derive(Debug, Clone, Default)]
+pub struct InputObjectTypeBuilder {
+    description: Option<String>,
+    fields: Vec<InputValueBuilder>,
+}
+
+/// Builds an argument or input field definition
+/// for [`FieldBuilder::argument_with`] or [`InputObjectTypeBuilder::field_with`]
+#[derive(Debug, Clone)]
+pub struct InputValueBuilder {
+    name: String,
+    ty: String,
+    description: Option<String>,
+    default_value: Option<String>,
+    deprecated: Option<Option<String>>,
+}
+
+/// Builds an enum type for [`SchemaBuilder::enum_type`]
+#[derive(Debug, Clone, Default)]
+pub struct EnumTypeBuilder {
+    description: Option<String>,
+    values: Vec<EnumValueBuilder>,
+}
+
+/// Builds an enum value definition for [`EnumTypeBuilder::value_with`]
+#[derive(Debug, Clone)]
+pub struct EnumValueBuilder {
+    name: String,
+    description: Option<String>,
+    deprecated: Option<Option<String>>,
+}
+
+impl ObjectTypeBuilder {
+    /// Set the description of the type
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_owned());
+        self
+    }
+
+    /// Add an interface implemented by the type
+    pub fn implements(mut self, interface: &str) -> Self {
+        self.implements.push(interface.to_owned());
+        self
+    }
+
+    /// Add a field with the given name and type, like `"[String!]"`
+    pub fn field(self, name: &str, ty: &str) -> Self {
+        self.field_with(name, ty, |field| field)
+    }
+
+    /// Add a field with arguments, a description, or a deprecation set by the `build` callback
+    pub fn field_with(
+        mut self,
+        name: &str,
+        ty: &str,
+        build: impl FnOnce(FieldBuilder) -> FieldBuilder,
+    ) -> Self {
+        self.fields.push(build(FieldBuilder {
+            name: name.to_owned(),
+            ty: ty.to_owned(),
+            description: None,
+            arguments: Vec::new(),
+            deprecated: None,
+        }));
+        self
+    }
+
+    fn to_sdl(&self, keyword: &str, name: &str) -> String {
+        let mut sdl = String::new();
+        write_description(&mut sdl, "", &self.description);
+        write!(sdl, "{keyword} {name}").unwrap();
+        if !self.implements.is_empty() {
+            write!(sdl, " implements {}", self.implements.join(" & ")).unwrap();
+        }
+        sdl.push_str(" {\n");
+        for field in &self.fields {
+            write_description(&mut sdl, "  ", &field.description);
+            write!(sdl, "  {}", field.name).unwrap();
+            if !field.arguments.is_empty() {
+                sdl.push_str("(\n");
+                for argument in &field.arguments {
+                    argument.write(&mut sdl, "    ");
+                    sdl.push('\n');
+                }
+                sdl.push_str("  )");
+            }
+            write!(sdl, ": {}", field.ty).unwrap();
+            write_deprecated(&mut sdl, &field.deprecated);
+            sdl.push('\n');
+        }
+        sdl.push('}');
+        sdl
+    }
+}
+
+impl FieldBuilder {
+    /// Set the description of the field
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_owned());
+        self
+    }
+
+    /// Add an argument with the given name and type
+    pub fn argument(self, name: &str, ty: &str) -> Self {
+        self.argument_with(name, ty, |argument| argument)
+    }
+
+    /// Add an argument with a default value, a description, or a deprecation
+    /// set by the `build` callback
+    pub fn argument_with(
+        mut self,
+        name: &str,
+        ty: &str,
+        build: impl FnOnce(InputValueBuilder) -> InputValueBuilder,
+    ) -> Self {
+        self.arguments.push(build(InputValueBuilder::new(name, ty)));
+        self
+    }
+
+    /// Mark the field `@deprecated` with the given reason
+    pub fn deprecated(mut self, reason: &str) -> Self {
+        self.deprecated = Some(Some(reason.to_owned()));
+        self
+    }
+}
+
+impl InputObjectTypeBuilder {
+    /// Set the description of the type
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_owned());
+        self
+    }
+
+    /// Add a field with the given name and type
+    pub fn field(self, name: &str, ty: &str) -> Self {
+        self.field_with(name, ty, |field| field)
+    }
+
+    /// Add a field with a default value, a description, or a deprecation
+    /// set by the `build` callback
+    pub fn field_with(
+        mut self,
+        name: &str,
+        ty: &str,
+        build: impl FnOnce(InputValueBuilder) -> InputValueBuilder,
+    ) -> Self {
+        self.fields.push(build(InputValueBuilder::new(name, ty)));
+        self
+    }
+}
+
+impl InputValueBuilder {
+    fn new(name: &str, ty: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            ty: ty.to_owned(),
+            description: None,
+            default_value: None,
+            deprecated: None,
+        }
+    }
+
+    /// Set the description of the argument or input field
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_owned());
+        self
+    }
+
+    /// Set the default value, written in GraphQL syntax like `"text"`, `[1, 2]`, or `RED`
+    pub fn default_value(mut self, value: &str) -> Self {
+        self.default_value = Some(value.to_owned());
+        self
+    }
+
+    /// Mark the argument or input field `@deprecated` with the given reason
+    pub fn deprecated(mut self, reason: &str) -> Self {
+        self.deprecated = Some(Some(reason.to_owned()));
+        self
+    }
+
+    fn write(&self, sdl: &mut String, indent: &str) {
+        write_description(sdl, indent, &self.description);
+        write!(sdl, "{indent}{}: {}", self.name, self.ty).unwrap();
+        if let Some(default_value) = &self.default_value {
+            write!(sdl, " = {default_value}").unwrap();
+        }
+        write_deprecated(sdl, &self.deprecated);
+    }
+}
+
+impl EnumTypeBuilder {
+    /// Set the description of the type
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_owned());
+        self
+    }
+
+    /// Add a value with the given name
+    pub fn value(self, name: &str) -> Self {
+        self.value_with(name, |value| value)
+    }
+
+    /// Add a value with a description or a deprecation set by the `build` callback
+    pub fn value_with(
+        mut self,
+        name: &str,
+        build: impl FnOnce(EnumValueBuilder) -> EnumValueBuilder,
+    ) -> Self {
+        self.values.push(build(EnumValueBuilder {
+            name: name.to_owned(),
+            description: None,
+            deprecated: None,
+        }));
+        self
+    }
+}
+
+impl EnumValueBuilder {
+    /// Set the description of the value
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_owned());
+        self
+    }
+
+    /// Mark the value `@deprecated` with the given reason
+    pub fn deprecated(mut self, reason: &str) -> Self {
+        self.deprecated = Some(Some(reason.to_owned()));
+        self
+    }
+}
+
+fn write_description(sdl: &mut String, indent: &str, description: &Option<String>) {
+    if let Some(description) = description {
+        let value = ast::Value::String(description.clone());
+        writeln!(sdl, "{indent}{}", value.serialize().no_indent()).unwrap();
+    }
+}
+
+fn write_deprecated(sdl: &mut String, deprecated: &Option<Option<String>>) {
+    match deprecated {
+        None => {}
+        Some(None) => sdl.push_str(" @deprecated"),
+        Some(Some(reason)) => {
+            let reason = ast::Value::String(reason.clone());
+            write!(
+                sdl,
+                " @deprecated(reason: {})",
+                reason.serialize().no_indent()
+            )
+            .unwrap();
+        }
+    }
+}
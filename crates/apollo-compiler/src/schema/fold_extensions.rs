@@ -0,0 +1,229 @@
+use crate::ast::OperationType;
+use crate::collections::IndexMap;
+use crate::collections::IndexSet;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::schema::Component;
+use crate::schema::ComponentName;
+use crate::schema::ComponentOrigin;
+use crate::schema::DirectiveList;
+use crate::schema::ExtendedType;
+use crate::schema::ExtensionId;
+use crate::schema::NamedType;
+use crate::Name;
+use crate::Schema;
+
+/// Where [`Schema::fold_extensions`] recorded that components came from extensions
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ExtensionProvenance {
+    /// Components that were contributed by a `schema` extension or type extension,
+    /// with the ID of that extension, in schema order
+    pub components: Vec<(FoldedComponent, ExtensionId)>,
+}
+
+/// A component that was moved from an extension into its definition
+/// by [`Schema::fold_extensions`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FoldedComponent {
+    /// A root operation of the `schema` definition
+    RootOperation(OperationType),
+    /// A directive applied to the `schema` definition
+    SchemaDirective(Name),
+    /// A directive applied to a type
+    TypeDirective { ty: NamedType, directive: Name },
+    /// An interface implemented by an object or interface type
+    ImplementedInterface { ty: NamedType, interface: NamedType },
+    /// A member of a union type
+    UnionMember { ty: NamedType, member: NamedType },
+    /// A field of an object, interface, or input object type, or a value of an enum type
+    TypeAttribute(TypeAttributeCoordinate),
+}
+
+impl Schema {
+    /// Returns a copy of this schema where components added by `extend schema`
+    /// and `extend type` (or other kinds of type extensions) are part of their definition,
+    /// and a record of which extension each such component came from.
+    ///
+    /// The returned schema prints as SDL without any extension,
+    /// for consumers such as some code generators that do not support them.
+    /// Extensions of built-in scalars are kept,
+    /// since definitions of built-in types are not printed.
+    ///
+    /// ```rust
+    /// use apollo_compiler::schema::FoldedComponent;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse_and_validate(
+    ///     r#"
+    ///     type Query { a: Int }
+    ///     extend type Query { b: Int }
+    ///     "#,
+    ///     "schema.graphql",
+    /// )
+    /// .unwrap();
+    /// let (folded, provenance) = schema.fold_extensions();
+    /// assert_eq!(folded.to_string(), "type Query {\n  a: Int\n  b: Int\n}\n");
+    ///
+    /// let (component, extension) = &provenance.components[0];
+    /// let FoldedComponent::TypeAttribute(coordinate) = component else {
+    ///     panic!()
+    /// };
+    /// assert_eq!(coordinate.to_string(), "Query.b");
+    /// let line = extension.location().unwrap().line_column(&schema.sources).unwrap().line;
+    /// assert_eq!(line, 3);
+    /// ```
+    pub fn fold_extensions(&self) -> (Schema, ExtensionProvenance) {
+        let mut schema = self.clone();
+        let mut provenance = ExtensionProvenance::default();
+        let components = &mut provenance.components;
+
+        if !schema.schema_definition.extensions().is_empty() {
+            let definition = schema.schema_definition.make_mut();
+            fold_directives(components, &mut definition.directives, |directive| {
+                FoldedComponent::SchemaDirective(directive)
+            });
+            for (operation_type, root) in [
+                (OperationType::Query, &mut definition.query),
+                (OperationType::Mutation, &mut definition.mutation),
+                (OperationType::Subscription, &mut definition.subscription),
+            ] {
+                if let Some(root) = root {
+                    if let Some(id) = take_extension(&mut root.origin) {
+                        components.push((FoldedComponent::RootOperation(operation_type), id))
+                    }
+                }
+            }
+        }
+
+        for (type_name, ty) in &mut schema.types {
+            if ty.is_built_in() {
+                continue;
+            }
+            let type_directive = |directive| FoldedComponent::TypeDirective {
+                ty: type_name.clone(),
+                directive,
+            };
+            match ty {
+                ExtendedType::Scalar(def) => {
+                    if def.extensions().is_empty() {
+                        continue;
+                    }
+                    let def = def.make_mut();
+                    fold_directives(components, &mut def.directives, type_directive);
+                }
+                ExtendedType::Object(def) => {
+                    if def.extensions().is_empty() {
+                        continue;
+                    }
+                    let def = def.make_mut();
+                    fold_directives(components, &mut def.directives, type_directive);
+                    fold_names(components, &mut def.implements_interfaces, |interface| {
+                        FoldedComponent::ImplementedInterface {
+                            ty: type_name.clone(),
+                            interface,
+                        }
+                    });
+                    fold_attributes(components, type_name, &mut def.fields);
+                }
+                ExtendedType::Interface(def) => {
+                    if def.extensions().is_empty() {
+                        continue;
+                    }
+                    let def = def.make_mut();
+                    fold_directives(components, &mut def.directives, type_directive);
+                    fold_names(components, &mut def.implements_interfaces, |interface| {
+                        FoldedComponent::ImplementedInterface {
+                            ty: type_name.clone(),
+                            interface,
+                        }
+                    });
+                    fold_attributes(components, type_name, &mut def.fields);
+                }
+                ExtendedType::Union(def) => {
+                    if def.extensions().is_empty() {
+                        continue;
+                    }
+                    let def = def.make_mut();
+                    fold_directives(components, &mut def.directives, type_directive);
+                    fold_names(components, &mut def.members, |member| {
+                        FoldedComponent::UnionMember {
+                            ty: type_name.clone(),
+                            member,
+                        }
+                    });
+                }
+                ExtendedType::Enum(def) => {
+                    if def.extensions().is_empty() {
+                        continue;
+                    }
+                    let def = def.make_mut();
+                    fold_directives(components, &mut def.directives, type_directive);
+                    fold_attributes(components, type_name, &mut def.values);
+                }
+                ExtendedType::InputObject(def) => {
+                    if def.extensions().is_empty() {
+                        continue;
+                    }
+                    let def = def.make_mut();
+                    fold_directives(components, &mut def.directives, type_directive);
+                    fold_attributes(components, type_name, &mut def.fields);
+                }
+            }
+        }
+        (schema, provenance)
+    }
+}
+
+/// Mark a component as coming from a definition,
+/// returning the extension it previously came from if any
+fn take_extension(origin: &mut ComponentOrigin) -> Option<ExtensionId> {
+    match std::mem::replace(origin, ComponentOrigin::Definition) {
+        ComponentOrigin::Definition => None,
+        ComponentOrigin::Extension(id) => Some(id),
+    }
+}
+
+fn fold_directives(
+    components: &mut Vec<(FoldedComponent, ExtensionId)>,
+    directives: &mut DirectiveList,
+    component: impl Fn(Name) -> FoldedComponent,
+) {
+    for directive in directives.iter_mut() {
+        if let Some(id) = take_extension(&mut directive.origin) {
+            components.push((component(directive.name.clone()), id))
+        }
+    }
+}
+
+fn fold_names(
+    components: &mut Vec<(FoldedComponent, ExtensionId)>,
+    names: &mut IndexSet<ComponentName>,
+    component: impl Fn(Name) -> FoldedComponent,
+) {
+    // Origins are not part of the hash, but set elements cannot be mutated in place
+    *names = std::mem::take(names)
+        .into_iter()
+        .map(|mut name| {
+            if let Some(id) = take_extension(&mut name.origin) {
+                components.push((component(name.name.clone()), id))
+            }
+            name
+        })
+        .collect();
+}
+
+fn fold_attributes<T>(
+    components: &mut Vec<(FoldedComponent, ExtensionId)>,
+    type_name: &NamedType,
+    attributes: &mut IndexMap<Name, Component<T>>,
+) {
+    for (name, attribute) in attributes {
+        if let Some(id) = take_extension(&mut attribute.origin) {
+            let coordinate = TypeAttributeCoordinate {
+                ty: type_name.clone(),
+                attribute: name.clone(),
+            };
+            components.push((FoldedComponent::TypeAttribute(coordinate), id))
+        }
+    }
+}
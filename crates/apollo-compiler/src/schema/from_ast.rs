@@ -7,12 +7,53 @@ use std::sync::Arc;
 #[derive(Clone)]
 pub struct SchemaBuilder {
     adopt_orphan_extensions: bool,
+    duplicate_definitions: DuplicateDefinitions,
+    built_in_scalar_redefinitions: BuiltInScalarRedefinitions,
     schema: Schema,
     schema_definition: SchemaDefinitionStatus,
     orphan_type_extensions: IndexMap<Name, Vec<ast::Definition>>,
     pub(crate) errors: DiagnosticList,
 }
 
+/// How [`SchemaBuilder`] handles a type or directive definition
+/// with the same name as an earlier one, set with
+/// [`SchemaBuilder::duplicate_definitions`].
+///
+/// Policies other than `Error` still report a [warning][crate::validation::Severity::Warning]
+/// for each duplicate they resolve.
+/// The schema definition and built-in scalars are not affected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateDefinitions {
+    /// Report an error. This is required by the GraphQL specification.
+    #[default]
+    Error,
+    /// Keep the first definition and ignore later ones
+    FirstWins,
+    /// Replace earlier definitions with the last one.
+    ///
+    /// Extensions applied to a replaced type definition are discarded with it.
+    LastWins,
+    /// Keep the first definition if later ones are identical to it, ignoring source locations.
+    /// Different definitions are an error.
+    MergeIfIdentical,
+}
+
+/// How [`SchemaBuilder`] handles a definition of a built-in scalar such as `String`,
+/// set with [`SchemaBuilder::built_in_scalar_redefinitions`].
+///
+/// A redefinition is identical if it has no directives.
+/// Descriptions are ignored. Other redefinitions are always an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BuiltInScalarRedefinitions {
+    /// Report an error. This is required by the GraphQL specification.
+    #[default]
+    Error,
+    /// Ignore identical redefinitions, with a [warning][crate::validation::Severity::Warning]
+    WarnIfIdentical,
+    /// Silently ignore identical redefinitions
+    AllowIfIdentical,
+}
+
 #[derive(Clone)]
 enum SchemaDefinitionStatus {
     Found,
@@ -36,6 +77,8 @@ impl SchemaBuilder {
             .get_or_init(|| {
                 let mut builder = SchemaBuilder {
                     adopt_orphan_extensions: false,
+                    duplicate_definitions: DuplicateDefinitions::Error,
+                    built_in_scalar_redefinitions: BuiltInScalarRedefinitions::Error,
                     schema: Schema {
                         sources: Default::default(),
                         schema_definition: Node::new(SchemaDefinition {
@@ -47,6 +90,7 @@ impl SchemaBuilder {
                         }),
                         directive_definitions: IndexMap::with_hasher(Default::default()),
                         types: IndexMap::with_hasher(Default::default()),
+                        reverse_indexes_cache: Default::default(),
                     },
                     schema_definition: SchemaDefinitionStatus::NoneSoFar {
                         orphan_extensions: Vec::new(),
@@ -67,6 +111,21 @@ impl SchemaBuilder {
             .clone()
     }
 
+    /// Returns a builder that adds definitions to an already-built schema.
+    ///
+    /// The schema definition is considered found, so that schema extensions extend it.
+    pub(crate) fn from_schema(schema: Schema) -> Self {
+        SchemaBuilder {
+            adopt_orphan_extensions: false,
+            duplicate_definitions: DuplicateDefinitions::Error,
+            built_in_scalar_redefinitions: BuiltInScalarRedefinitions::Error,
+            schema_definition: SchemaDefinitionStatus::Found,
+            orphan_type_extensions: IndexMap::with_hasher(Default::default()),
+            errors: DiagnosticList::new(schema.sources.clone()),
+            schema,
+        }
+    }
+
     /// Configure the builder so that “orphan” schema extensions and type extensions
     /// (without a corresponding definition) are “adopted”:
     /// accepted as if extending an empty definition instead of being rejected as errors.
@@ -75,6 +134,52 @@ impl SchemaBuilder {
         self
     }
 
+    /// Configure how a type or directive definition that repeats the name of an earlier one
+    /// is handled, for example when layering several input files.
+    /// By default this is an error.
+    ///
+    /// ```rust
+    /// use apollo_compiler::schema::DuplicateDefinitions;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let result = Schema::builder()
+    ///     .duplicate_definitions(DuplicateDefinitions::LastWins)
+    ///     .parse("type Query { a: Int }", "base.graphql")
+    ///     .parse("type Query { b: Int }", "override.graphql")
+    ///     .build();
+    /// // The duplicate is reported as a warning
+    /// let with_warnings = result.unwrap_err();
+    /// assert!(!with_warnings.errors.has_errors());
+    /// let query = with_warnings.partial.get_object("Query").unwrap();
+    /// assert!(query.fields.contains_key("b"));
+    /// ```
+    pub fn duplicate_definitions(mut self, policy: DuplicateDefinitions) -> Self {
+        self.duplicate_definitions = policy;
+        self
+    }
+
+    /// Configure how a definition of a built-in scalar, such as `scalar String`, is handled.
+    /// By default this is an error.
+    ///
+    /// Some tools produce SDL that includes built-in scalars,
+    /// so this can accept their identical redefinitions.
+    ///
+    /// ```rust
+    /// use apollo_compiler::schema::BuiltInScalarRedefinitions;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::builder()
+    ///     .built_in_scalar_redefinitions(BuiltInScalarRedefinitions::AllowIfIdentical)
+    ///     .parse("scalar String type Query { a: String }", "schema.graphql")
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(schema.types["String"].is_built_in());
+    /// ```
+    pub fn built_in_scalar_redefinitions(mut self, policy: BuiltInScalarRedefinitions) -> Self {
+        self.built_in_scalar_redefinitions = policy;
+        self
+    }
+
     /// Parse an input file with the default configuration as an additional input for this schema.
     ///
     /// Create a [`Parser`] to use different parser configuration.
@@ -121,18 +226,61 @@ impl SchemaBuilder {
                             );
                             entry.insert(extended_def.into());
                         }
-                        Entry::Occupied(entry) => {
-                            let previous = entry.get();
-                            if $is_scalar && previous.is_built_in() {
-                                self.errors.push(
-                                    $def.location(),
-                                    BuildError::BuiltInScalarTypeRedefinition,
+                        Entry::Occupied(mut entry) => {
+                            let previous = entry.get_mut();
+                            let previous_location = previous.name().location();
+                            let kept = match self.duplicate_definitions {
+                                _ if $is_scalar && previous.is_built_in() => {
+                                    let identical = $def.directives.is_empty();
+                                    match self.built_in_scalar_redefinitions {
+                                        BuiltInScalarRedefinitions::AllowIfIdentical
+                                            if identical => {}
+                                        BuiltInScalarRedefinitions::WarnIfIdentical
+                                            if identical =>
+                                        {
+                                            self.errors.push_warning(
+                                                $def.location(),
+                                                BuildError::BuiltInScalarTypeRedefinition,
+                                            )
+                                        }
+                                        _ => self.errors.push(
+                                            $def.location(),
+                                            BuildError::BuiltInScalarTypeRedefinition,
+                                        ),
+                                    }
+                                    continue;
+                                }
+                                DuplicateDefinitions::Error => None,
+                                DuplicateDefinitions::FirstWins => Some("first"),
+                                DuplicateDefinitions::LastWins => {
+                                    *previous =
+                                        $Type::from_ast(&mut self.errors, $def, Vec::new()).into();
+                                    Some("last")
+                                }
+                                DuplicateDefinitions::MergeIfIdentical => {
+                                    // Errors within an identical definition
+                                    // were already reported for the first one
+                                    let mut ignored = DiagnosticList::new(Default::default());
+                                    let new: ExtendedType =
+                                        $Type::from_ast(&mut ignored, $def, Vec::new()).into();
+                                    (new == *previous).then_some("first")
+                                }
+                            };
+                            if let Some(kept) = kept {
+                                self.errors.push_warning(
+                                    $def.name.location(),
+                                    BuildError::DuplicateDefinitionResolved {
+                                        previous_location,
+                                        name: $def.name.clone(),
+                                        directive: false,
+                                        kept,
+                                    },
                                 )
                             } else {
                                 self.errors.push(
                                     $def.name.location(),
                                     BuildError::TypeDefinitionCollision {
-                                        previous_location: previous.name().location(),
+                                        previous_location,
                                         name: $def.name.clone(),
                                     },
                                 )
@@ -193,13 +341,37 @@ impl SchemaBuilder {
                                 // so the new definition won’t be considered built-in.)
                                 *previous = def.clone()
                             } else {
-                                self.errors.push(
-                                    def.name.location(),
-                                    BuildError::DirectiveDefinitionCollision {
-                                        previous_location: previous.name.location(),
-                                        name: def.name.clone(),
-                                    },
-                                )
+                                let previous_location = previous.name.location();
+                                let kept = match self.duplicate_definitions {
+                                    DuplicateDefinitions::Error => None,
+                                    DuplicateDefinitions::FirstWins => Some("first"),
+                                    DuplicateDefinitions::LastWins => {
+                                        *previous = def.clone();
+                                        Some("last")
+                                    }
+                                    DuplicateDefinitions::MergeIfIdentical => {
+                                        (*previous == *def).then_some("first")
+                                    }
+                                };
+                                if let Some(kept) = kept {
+                                    self.errors.push_warning(
+                                        def.name.location(),
+                                        BuildError::DuplicateDefinitionResolved {
+                                            previous_location,
+                                            name: def.name.clone(),
+                                            directive: true,
+                                            kept,
+                                        },
+                                    )
+                                } else {
+                                    self.errors.push(
+                                        def.name.location(),
+                                        BuildError::DirectiveDefinitionCollision {
+                                            previous_location,
+                                            name: def.name.clone(),
+                                        },
+                                    )
+                                }
                             }
                         }
                     }
@@ -262,6 +434,8 @@ impl SchemaBuilder {
     pub(crate) fn build_inner(self) -> (Schema, DiagnosticList) {
         let SchemaBuilder {
             adopt_orphan_extensions,
+            duplicate_definitions: _,
+            built_in_scalar_redefinitions: _,
             mut schema,
             schema_definition,
             orphan_type_extensions,
@@ -530,6 +704,7 @@ impl ObjectType {
         definition: &Node<ast::ObjectTypeDefinition>,
         extensions: Vec<ast::Definition>,
     ) -> Node<Self> {
+        let mut collisions = Self::name_collisions(&definition.name);
         let mut ty = Self {
             description: definition.description.clone(),
             name: definition.name.clone(),
@@ -558,29 +733,46 @@ impl ObjectType {
                     .fields
                     .iter()
                     .map(|field| (&field.name, field.to_component(ComponentOrigin::Definition))),
-                |prev_key, dup_value| {
-                    errors.push(
-                        dup_value.location(),
-                        BuildError::ObjectFieldNameCollision {
-                            name_at_previous_location: prev_key.clone(),
-                            type_name: definition.name.clone(),
-                        },
-                    )
-                },
+                |prev_key, dup_value| collisions.record(prev_key, dup_value.location()),
             ),
         };
         for def in &extensions {
             if let ast::Definition::ObjectTypeExtension(ext) = def {
-                ty.extend_ast(errors, ext)
+                ty.extend_ast_inner(errors, &mut collisions, ext)
             }
         }
+        collisions.report(errors);
         definition.same_location(ty)
     }
 
+    fn name_collisions(type_name: &Name) -> NameCollisions {
+        NameCollisions::new(
+            type_name,
+            |name_at_previous_location, type_name, redefined_definitions| {
+                BuildError::ObjectFieldNameCollision {
+                    name_at_previous_location,
+                    type_name,
+                    redefined_definitions,
+                }
+            },
+        )
+    }
+
     fn extend_ast(
         &mut self,
         errors: &mut DiagnosticList,
         extension: &Node<ast::ObjectTypeExtension>,
+    ) {
+        let mut collisions = Self::name_collisions(&extension.name);
+        self.extend_ast_inner(errors, &mut collisions, extension);
+        collisions.report(errors);
+    }
+
+    fn extend_ast_inner(
+        &mut self,
+        errors: &mut DiagnosticList,
+        collisions: &mut NameCollisions,
+        extension: &Node<ast::ObjectTypeExtension>,
     ) {
         let origin = ComponentOrigin::Extension(ExtensionId::new(extension));
         self.directives.extend(
@@ -611,15 +803,7 @@ impl ObjectType {
                 .fields
                 .iter()
                 .map(|field| (&field.name, field.to_component(origin.clone()))),
-            |prev_key, dup_value| {
-                errors.push(
-                    dup_value.location(),
-                    BuildError::ObjectFieldNameCollision {
-                        name_at_previous_location: prev_key.clone(),
-                        type_name: extension.name.clone(),
-                    },
-                )
-            },
+            |prev_key, dup_value| collisions.record(prev_key, dup_value.location()),
         );
     }
 }
@@ -630,6 +814,7 @@ impl InterfaceType {
         definition: &Node<ast::InterfaceTypeDefinition>,
         extensions: Vec<ast::Definition>,
     ) -> Node<Self> {
+        let mut collisions = Self::name_collisions(&definition.name);
         let mut ty = Self {
             description: definition.description.clone(),
             name: definition.name.clone(),
@@ -658,29 +843,46 @@ impl InterfaceType {
                     .fields
                     .iter()
                     .map(|field| (&field.name, field.to_component(ComponentOrigin::Definition))),
-                |prev_key, dup_value| {
-                    errors.push(
-                        dup_value.location(),
-                        BuildError::InterfaceFieldNameCollision {
-                            name_at_previous_location: prev_key.clone(),
-                            type_name: definition.name.clone(),
-                        },
-                    )
-                },
+                |prev_key, dup_value| collisions.record(prev_key, dup_value.location()),
             ),
         };
         for def in &extensions {
             if let ast::Definition::InterfaceTypeExtension(ext) = def {
-                ty.extend_ast(errors, ext)
+                ty.extend_ast_inner(errors, &mut collisions, ext)
             }
         }
+        collisions.report(errors);
         definition.same_location(ty)
     }
 
+    fn name_collisions(type_name: &Name) -> NameCollisions {
+        NameCollisions::new(
+            type_name,
+            |name_at_previous_location, type_name, redefined_definitions| {
+                BuildError::InterfaceFieldNameCollision {
+                    name_at_previous_location,
+                    type_name,
+                    redefined_definitions,
+                }
+            },
+        )
+    }
+
     fn extend_ast(
         &mut self,
         errors: &mut DiagnosticList,
         extension: &Node<ast::InterfaceTypeExtension>,
+    ) {
+        let mut collisions = Self::name_collisions(&extension.name);
+        self.extend_ast_inner(errors, &mut collisions, extension);
+        collisions.report(errors);
+    }
+
+    fn extend_ast_inner(
+        &mut self,
+        errors: &mut DiagnosticList,
+        collisions: &mut NameCollisions,
+        extension: &Node<ast::InterfaceTypeExtension>,
     ) {
         let origin = ComponentOrigin::Extension(ExtensionId::new(extension));
         self.directives.extend(
@@ -711,15 +913,7 @@ impl InterfaceType {
                 .fields
                 .iter()
                 .map(|field| (&field.name, field.to_component(origin.clone()))),
-            |prev_key, dup_value| {
-                errors.push(
-                    dup_value.location(),
-                    BuildError::InterfaceFieldNameCollision {
-                        name_at_previous_location: prev_key.clone(),
-                        type_name: extension.name.clone(),
-                    },
-                )
-            },
+            |prev_key, dup_value| collisions.record(prev_key, dup_value.location()),
         );
     }
 }
@@ -799,6 +993,7 @@ impl EnumType {
         definition: &Node<ast::EnumTypeDefinition>,
         extensions: Vec<ast::Definition>,
     ) -> Node<Self> {
+        let mut collisions = Self::name_collisions(&definition.name);
         let mut ty = Self {
             description: definition.description.clone(),
             name: definition.name.clone(),
@@ -814,29 +1009,45 @@ impl EnumType {
                         value_def.to_component(ComponentOrigin::Definition),
                     )
                 }),
-                |prev_key, dup_value| {
-                    errors.push(
-                        dup_value.location(),
-                        BuildError::EnumValueNameCollision {
-                            name_at_previous_location: prev_key.clone(),
-                            type_name: definition.name.clone(),
-                        },
-                    )
-                },
+                |prev_key, dup_value| collisions.record(prev_key, dup_value.location()),
             ),
         };
         for def in &extensions {
             if let ast::Definition::EnumTypeExtension(ext) = def {
-                ty.extend_ast(errors, ext)
+                ty.extend_ast_inner(&mut collisions, ext)
             }
         }
+        collisions.report(errors);
         definition.same_location(ty)
     }
 
+    fn name_collisions(type_name: &Name) -> NameCollisions {
+        NameCollisions::new(
+            type_name,
+            |name_at_previous_location, type_name, redefined_definitions| {
+                BuildError::EnumValueNameCollision {
+                    name_at_previous_location,
+                    type_name,
+                    redefined_definitions,
+                }
+            },
+        )
+    }
+
     fn extend_ast(
         &mut self,
         errors: &mut DiagnosticList,
         extension: &Node<ast::EnumTypeExtension>,
+    ) {
+        let mut collisions = Self::name_collisions(&extension.name);
+        self.extend_ast_inner(&mut collisions, extension);
+        collisions.report(errors);
+    }
+
+    fn extend_ast_inner(
+        &mut self,
+        collisions: &mut NameCollisions,
+        extension: &Node<ast::EnumTypeExtension>,
     ) {
         let origin = ComponentOrigin::Extension(ExtensionId::new(extension));
         self.directives.extend(
@@ -851,15 +1062,7 @@ impl EnumType {
                 .values
                 .iter()
                 .map(|value_def| (&value_def.value, value_def.to_component(origin.clone()))),
-            |prev_key, dup_value| {
-                errors.push(
-                    dup_value.location(),
-                    BuildError::EnumValueNameCollision {
-                        name_at_previous_location: prev_key.clone(),
-                        type_name: extension.name.clone(),
-                    },
-                )
-            },
+            |prev_key, dup_value| collisions.record(prev_key, dup_value.location()),
         )
     }
 }
@@ -870,6 +1073,7 @@ impl InputObjectType {
         definition: &Node<ast::InputObjectTypeDefinition>,
         extensions: Vec<ast::Definition>,
     ) -> Node<Self> {
+        let mut collisions = Self::name_collisions(&definition.name);
         let mut ty = Self {
             description: definition.description.clone(),
             name: definition.name.clone(),
@@ -883,29 +1087,45 @@ impl InputObjectType {
                     .fields
                     .iter()
                     .map(|field| (&field.name, field.to_component(ComponentOrigin::Definition))),
-                |prev_key, dup_value| {
-                    errors.push(
-                        dup_value.location(),
-                        BuildError::InputFieldNameCollision {
-                            name_at_previous_location: prev_key.clone(),
-                            type_name: definition.name.clone(),
-                        },
-                    )
-                },
+                |prev_key, dup_value| collisions.record(prev_key, dup_value.location()),
             ),
         };
         for def in &extensions {
             if let ast::Definition::InputObjectTypeExtension(ext) = def {
-                ty.extend_ast(errors, ext)
+                ty.extend_ast_inner(&mut collisions, ext)
             }
         }
+        collisions.report(errors);
         definition.same_location(ty)
     }
 
+    fn name_collisions(type_name: &Name) -> NameCollisions {
+        NameCollisions::new(
+            type_name,
+            |name_at_previous_location, type_name, redefined_definitions| {
+                BuildError::InputFieldNameCollision {
+                    name_at_previous_location,
+                    type_name,
+                    redefined_definitions,
+                }
+            },
+        )
+    }
+
     fn extend_ast(
         &mut self,
         errors: &mut DiagnosticList,
         extension: &Node<ast::InputObjectTypeExtension>,
+    ) {
+        let mut collisions = Self::name_collisions(&extension.name);
+        self.extend_ast_inner(&mut collisions, extension);
+        collisions.report(errors);
+    }
+
+    fn extend_ast_inner(
+        &mut self,
+        collisions: &mut NameCollisions,
+        extension: &Node<ast::InputObjectTypeExtension>,
     ) {
         let origin = ComponentOrigin::Extension(ExtensionId::new(extension));
         self.directives.extend(
@@ -920,19 +1140,50 @@ impl InputObjectType {
                 .fields
                 .iter()
                 .map(|field| (&field.name, field.to_component(origin.clone()))),
-            |prev_key, dup_value| {
-                errors.push(
-                    dup_value.location(),
-                    BuildError::InputFieldNameCollision {
-                        name_at_previous_location: prev_key.clone(),
-                        type_name: extension.name.clone(),
-                    },
-                )
-            },
+            |prev_key, dup_value| collisions.record(prev_key, dup_value.location()),
         )
     }
 }
 
+/// Field or value names defined more than once in a type and its extensions,
+/// reported as one diagnostic per name with every later definition,
+/// including those in extensions added separately
+struct NameCollisions {
+    type_name: Name,
+    error: fn(Name, Name, Vec<Option<SourceSpan>>) -> BuildError,
+    redefinitions: IndexMap<Name, Vec<Option<SourceSpan>>>,
+}
+
+impl NameCollisions {
+    /// `error` is called with the name at its previous location, the type name,
+    /// and the locations of later definitions
+    fn new(type_name: &Name, error: fn(Name, Name, Vec<Option<SourceSpan>>) -> BuildError) -> Self {
+        Self {
+            type_name: type_name.clone(),
+            error,
+            redefinitions: Default::default(),
+        }
+    }
+
+    /// Record a definition at `location` that was not inserted because of `previous`
+    fn record(&mut self, previous: &Name, location: Option<SourceSpan>) {
+        self.redefinitions
+            .entry(previous.clone())
+            .or_default()
+            .push(location)
+    }
+
+    fn report(self, errors: &mut DiagnosticList) {
+        for (name, redefined_definitions) in self.redefinitions {
+            errors.push_name_collision((self.error)(
+                name,
+                self.type_name.clone(),
+                redefined_definitions,
+            ))
+        }
+    }
+}
+
 /// Like `IndexMap::extend`, but does not replace a value if an equivalent key is already in the map.
 ///
 /// On collision, calls `duplicate` with the previous key and the value not inserted
@@ -0,0 +1,404 @@
+use crate::schema::validation::validate_schema;
+use crate::validation::Valid;
+use crate::validation::WithErrors;
+use crate::Schema;
+use serde_json::Value;
+use std::fmt::Write;
+
+/// Directives defined by [`Schema::builder`]
+const BUILT_IN_DIRECTIVES: &[&str] = &["skip", "include", "deprecated", "specifiedBy"];
+
+/// Scalars defined by [`Schema::builder`]
+const BUILT_IN_SCALARS: &[&str] = &["Int", "Float", "String", "Boolean", "ID"];
+
+/// A malformed introspection response found by [`Schema::from_introspection_response`]
+#[derive(thiserror::Error, Debug, Clone)]
+pub(crate) enum IntrospectionResponseError {
+    #[error("introspection response has no `__schema` object")]
+    MissingSchema,
+
+    #[error("expected {expected} at `{path}` in introspection response")]
+    UnexpectedValue {
+        path: String,
+        expected: &'static str,
+    },
+
+    #[error("unknown type kind `{kind}` at `{path}` in introspection response")]
+    UnknownTypeKind { path: String, kind: String },
+}
+
+impl Schema {
+    /// Build a schema from the result of an
+    /// [introspection query](https://spec.graphql.org/October2021/#sec-Introspection),
+    /// for services that do not expose their schema as SDL.
+    ///
+    /// `response` is either a full GraphQL response with a `data` key,
+    /// or the `data` object with a `__schema` key.
+    /// It should select the fields of the usual `IntrospectionQuery` used by GraphQL tooling:
+    /// types with their fields, arguments, input fields, enum values, interfaces,
+    /// and possible types, directives, and deprecations.
+    /// `specifiedByURL`, `isRepeatable`, and `isOneOf` are used if selected.
+    ///
+    /// Built-in scalars, built-in directives, and introspection types are not read
+    /// from the response: the schema has the definitions from [`Schema::builder`].
+    /// Introspection does not expose directive applications other than deprecations,
+    /// so the schema has none other than `@deprecated`, `@specifiedBy`, and `@oneOf`.
+    ///
+    /// Diagnostics refer to SDL generated from the response,
+    /// in a source file named `introspection.graphql`.
+    /// Malformed parts of the response are reported and skipped.
+    ///
+    /// Requires the `serde_json` feature.
+    ///
+    /// ```rust
+    /// use apollo_compiler::Schema;
+    ///
+    /// let response = serde_json::json!({
+    ///     "data": {
+    ///         "__schema": {
+    ///             "queryType": { "name": "Query" },
+    ///             "types": [{
+    ///                 "kind": "OBJECT",
+    ///                 "name": "Query",
+    ///                 "fields": [{
+    ///                     "name": "hello",
+    ///                     "args": [],
+    ///                     "type": { "kind": "SCALAR", "name": "String" },
+    ///                     "isDeprecated": false
+    ///                 }],
+    ///                 "interfaces": []
+    ///             }],
+    ///             "directives": []
+    ///         }
+    ///     }
+    /// });
+    /// let schema = Schema::from_introspection_response(&response).unwrap();
+    /// assert_eq!(schema.to_string(), "type Query {\n  hello: String\n}\n");
+    /// ```
+    pub fn from_introspection_response(response: &Value) -> Result<Valid<Self>, WithErrors<Self>> {
+        let mut writer = SdlWriter::default();
+        match response
+            .get("data")
+            .unwrap_or(response)
+            .get("__schema")
+            .filter(|schema| schema.is_object())
+        {
+            Some(schema) => writer.schema(schema),
+            None => writer
+                .errors
+                .push(IntrospectionResponseError::MissingSchema),
+        }
+        let mut builder = Schema::builder().parse(writer.sdl, "introspection.graphql");
+        for error in writer.errors {
+            builder.errors.push(None, error)
+        }
+        let (schema, mut errors) = builder.build_inner();
+        validate_schema(&mut errors, &schema);
+        errors.into_valid_result(schema)
+    }
+}
+
+/// Writes SDL for the definitions of an introspection response.
+///
+/// Strings from the response are written as JSON strings,
+/// which are also valid GraphQL string values.
+/// Default values are already formatted as GraphQL values by the server.
+#[derive(Default)]
+struct SdlWriter {
+    sdl: String,
+    errors: Vec<IntrospectionResponseError>,
+}
+
+impl SdlWriter {
+    fn schema(&mut self, schema: &Value) {
+        let mut root_operations = String::new();
+        for (key, operation) in [
+            ("queryType", "query"),
+            ("mutationType", "mutation"),
+            ("subscriptionType", "subscription"),
+        ] {
+            if let Some(root) = schema.get(key).filter(|root| !root.is_null()) {
+                if let Some(name) = self.name(root, key) {
+                    write!(root_operations, " {operation}: {name}").unwrap();
+                }
+            }
+        }
+        if !root_operations.is_empty() {
+            self.description(schema, "");
+            writeln!(self.sdl, "schema {{{root_operations} }}\n").unwrap();
+        }
+
+        let mut has_one_of = false;
+        for (index, ty) in self.array(schema, "types", "types").iter().enumerate() {
+            let path = format!("types[{index}]");
+            has_one_of |= ty.get("isOneOf").and_then(Value::as_bool) == Some(true);
+            self.type_definition(ty, &path);
+        }
+        let directives = self.array(schema, "directives", "directives");
+        let defines_one_of = directives
+            .iter()
+            .any(|directive| directive.get("name").and_then(Value::as_str) == Some("oneOf"));
+        if has_one_of && !defines_one_of {
+            self.sdl.push_str("directive @oneOf on INPUT_OBJECT\n\n");
+        }
+        for (index, directive) in directives.iter().enumerate() {
+            self.directive_definition(directive, &format!("directives[{index}]"));
+        }
+    }
+
+    fn type_definition(&mut self, ty: &Value, path: &str) {
+        let (Some(kind), Some(name)) = (self.str(ty, "kind", path), self.name(ty, path)) else {
+            return;
+        };
+        if name.starts_with("__") || BUILT_IN_SCALARS.contains(&name) {
+            return;
+        }
+        self.description(ty, "");
+        match kind {
+            "SCALAR" => {
+                write!(self.sdl, "scalar {name}").unwrap();
+                if let Some(url) = ty.get("specifiedByURL").and_then(Value::as_str) {
+                    write!(self.sdl, " @specifiedBy(url: {})", quote(url)).unwrap();
+                }
+                self.sdl.push('\n');
+            }
+            "OBJECT" | "INTERFACE" => {
+                let keyword = if kind == "OBJECT" {
+                    "type"
+                } else {
+                    "interface"
+                };
+                write!(self.sdl, "{keyword} {name}").unwrap();
+                let interfaces = self.type_names(ty, "interfaces", path);
+                if !interfaces.is_empty() {
+                    write!(self.sdl, " implements {}", interfaces.join(" & ")).unwrap();
+                }
+                self.sdl.push_str(" {\n");
+                let path = format!("{path}.fields");
+                for (index, field) in self.array(ty, "fields", &path).iter().enumerate() {
+                    self.field_definition(field, &format!("{path}[{index}]"));
+                }
+                self.sdl.push_str("}\n");
+            }
+            "UNION" => {
+                let members = self.type_names(ty, "possibleTypes", path);
+                writeln!(self.sdl, "union {name} = {}", members.join(" | ")).unwrap();
+            }
+            "ENUM" => {
+                writeln!(self.sdl, "enum {name} {{").unwrap();
+                let path = format!("{path}.enumValues");
+                for (index, value) in self.array(ty, "enumValues", &path).iter().enumerate() {
+                    let path = format!("{path}[{index}]");
+                    let Some(value_name) = self.name(value, &path) else {
+                        continue;
+                    };
+                    self.description(value, "  ");
+                    write!(self.sdl, "  {value_name}").unwrap();
+                    self.deprecation(value);
+                    self.sdl.push('\n');
+                }
+                self.sdl.push_str("}\n");
+            }
+            "INPUT_OBJECT" => {
+                write!(self.sdl, "input {name}").unwrap();
+                if ty.get("isOneOf").and_then(Value::as_bool) == Some(true) {
+                    self.sdl.push_str(" @oneOf");
+                }
+                self.sdl.push_str(" {\n");
+                let path = format!("{path}.inputFields");
+                for (index, field) in self.array(ty, "inputFields", &path).iter().enumerate() {
+                    let path = format!("{path}[{index}]");
+                    if let Some(field) = self.input_value_definition(field, &path, "  ") {
+                        writeln!(self.sdl, "{field}").unwrap();
+                    }
+                }
+                self.sdl.push_str("}\n");
+            }
+            _ => self
+                .errors
+                .push(IntrospectionResponseError::UnknownTypeKind {
+                    path: format!("{path}.kind"),
+                    kind: kind.to_owned(),
+                }),
+        }
+        self.sdl.push('\n');
+    }
+
+    fn field_definition(&mut self, field: &Value, path: &str) {
+        let Some(name) = self.name(field, path) else {
+            return;
+        };
+        let Some(ty) = self.type_ref(field.get("type"), &format!("{path}.type")) else {
+            return;
+        };
+        let arguments = self.arguments(field, path);
+        self.description(field, "  ");
+        write!(self.sdl, "  {name}{arguments}: {ty}").unwrap();
+        self.deprecation(field);
+        self.sdl.push('\n');
+    }
+
+    fn directive_definition(&mut self, directive: &Value, path: &str) {
+        let Some(name) = self.name(directive, path) else {
+            return;
+        };
+        if BUILT_IN_DIRECTIVES.contains(&name) {
+            return;
+        }
+        let arguments = self.arguments(directive, path);
+        let locations_path = format!("{path}.locations");
+        let locations: Vec<_> = self
+            .array(directive, "locations", &locations_path)
+            .iter()
+            .enumerate()
+            .filter_map(|(index, location)| {
+                let location = location.as_str();
+                if location.is_none() {
+                    self.errors
+                        .push(IntrospectionResponseError::UnexpectedValue {
+                            path: format!("{locations_path}[{index}]"),
+                            expected: "a string",
+                        })
+                }
+                location
+            })
+            .collect();
+        self.description(directive, "");
+        write!(self.sdl, "directive @{name}{arguments}").unwrap();
+        if directive.get("isRepeatable").and_then(Value::as_bool) == Some(true) {
+            self.sdl.push_str(" repeatable");
+        }
+        writeln!(self.sdl, " on {}\n", locations.join(" | ")).unwrap();
+    }
+
+    /// Returns arguments formatted with parentheses, or an empty string
+    fn arguments(&mut self, parent: &Value, path: &str) -> String {
+        let path = format!("{path}.args");
+        let arguments: Vec<_> = self
+            .array(parent, "args", &path)
+            .iter()
+            .enumerate()
+            .filter_map(|(index, arg)| {
+                self.input_value_definition(arg, &format!("{path}[{index}]"), "    ")
+            })
+            .collect();
+        if arguments.is_empty() {
+            String::new()
+        } else {
+            format!("(\n{}\n  )", arguments.join("\n"))
+        }
+    }
+
+    fn input_value_definition(
+        &mut self,
+        input_value: &Value,
+        path: &str,
+        indent: &str,
+    ) -> Option<String> {
+        let name = self.name(input_value, path)?;
+        let ty = self.type_ref(input_value.get("type"), &format!("{path}.type"))?;
+        let mut definition = String::new();
+        if let Some(description) = input_value.get("description").and_then(Value::as_str) {
+            writeln!(definition, "{indent}{}", quote(description)).unwrap();
+        }
+        write!(definition, "{indent}{name}: {ty}").unwrap();
+        if let Some(default_value) = input_value.get("defaultValue").and_then(Value::as_str) {
+            write!(definition, " = {default_value}").unwrap();
+        }
+        if input_value.get("isDeprecated").and_then(Value::as_bool) == Some(true) {
+            definition.push_str(&deprecated(input_value));
+        }
+        Some(definition)
+    }
+
+    /// Formats a possibly-nested `__Type` reference like `[String!]`
+    fn type_ref(&mut self, ty: Option<&Value>, path: &str) -> Option<String> {
+        let Some(ty) = ty.filter(|ty| ty.is_object()) else {
+            self.errors
+                .push(IntrospectionResponseError::UnexpectedValue {
+                    path: path.to_owned(),
+                    expected: "a type reference",
+                });
+            return None;
+        };
+        let of_type_path = format!("{path}.ofType");
+        match self.str(ty, "kind", path)? {
+            "NON_NULL" => Some(format!(
+                "{}!",
+                self.type_ref(ty.get("ofType"), &of_type_path)?
+            )),
+            "LIST" => Some(format!(
+                "[{}]",
+                self.type_ref(ty.get("ofType"), &of_type_path)?
+            )),
+            _ => self.name(ty, path).map(ToOwned::to_owned),
+        }
+    }
+
+    /// Returns the names of a list of type references, like `interfaces`
+    fn type_names<'v>(&mut self, ty: &'v Value, key: &str, path: &str) -> Vec<&'v str> {
+        let path = format!("{path}.{key}");
+        self.array(ty, key, &path)
+            .iter()
+            .enumerate()
+            .filter_map(|(index, ty)| self.name(ty, &format!("{path}[{index}]")))
+            .collect()
+    }
+
+    fn description(&mut self, element: &Value, indent: &str) {
+        if let Some(description) = element.get("description").and_then(Value::as_str) {
+            writeln!(self.sdl, "{indent}{}", quote(description)).unwrap();
+        }
+    }
+
+    fn deprecation(&mut self, element: &Value) {
+        if element.get("isDeprecated").and_then(Value::as_bool) == Some(true) {
+            self.sdl.push_str(&deprecated(element));
+        }
+    }
+
+    fn name<'v>(&mut self, element: &'v Value, path: &str) -> Option<&'v str> {
+        self.str(element, "name", path)
+    }
+
+    fn str<'v>(&mut self, element: &'v Value, key: &str, path: &str) -> Option<&'v str> {
+        let value = element.get(key).and_then(Value::as_str);
+        if value.is_none() {
+            self.errors
+                .push(IntrospectionResponseError::UnexpectedValue {
+                    path: format!("{path}.{key}"),
+                    expected: "a string",
+                })
+        }
+        value
+    }
+
+    /// Returns the array at `key`, or an empty slice if it is null or missing.
+    /// Reports other values.
+    fn array<'v>(&mut self, element: &'v Value, key: &str, path: &str) -> &'v [Value] {
+        match element.get(key) {
+            Some(Value::Array(array)) => array,
+            None | Some(Value::Null) => &[],
+            Some(_) => {
+                self.errors
+                    .push(IntrospectionResponseError::UnexpectedValue {
+                        path: path.to_owned(),
+                        expected: "a list",
+                    });
+                &[]
+            }
+        }
+    }
+}
+
+fn deprecated(element: &Value) -> String {
+    match element.get("deprecationReason").and_then(Value::as_str) {
+        Some(reason) => format!(" @deprecated(reason: {})", quote(reason)),
+        None => " @deprecated".to_owned(),
+    }
+}
+
+/// JSON string escapes are a subset of GraphQL string escapes
+fn quote(string: &str) -> String {
+    Value::from(string).to_string()
+}
@@ -0,0 +1,232 @@
+use crate::collections::HashMap;
+use crate::collections::HashSet;
+use crate::collections::IndexSet;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::schema::ExtendedType;
+use crate::schema::Implementers;
+use crate::schema::NamedType;
+use crate::Schema;
+use std::sync::Arc;
+use std::sync::OnceLock;
+
+/// Reverse lookups between the types of a schema, as returned by [`Schema::reverse_indexes`]
+#[derive(Debug)]
+pub struct ReverseIndexes {
+    implementers: HashMap<NamedType, Implementers>,
+    unions: HashMap<NamedType, IndexSet<NamedType>>,
+    union_members: HashMap<NamedType, IndexSet<NamedType>>,
+    fields: HashMap<NamedType, Vec<TypeAttributeCoordinate>>,
+    possible_types: HashMap<NamedType, Arc<IndexSet<NamedType>>>,
+}
+
+/// Lazily-computed [`ReverseIndexes`] stored in a [`Schema`]
+#[derive(Default, Clone)]
+pub(crate) struct ReverseIndexesCache(OnceLock<Arc<ReverseIndexes>>);
+
+impl Schema {
+    /// Returns reverse lookups between the types of this schema:
+    /// the types that implement an interface, the unions that contain a type,
+    /// and the fields that return a type.
+    ///
+    /// They are computed on first use and cached in the schema, so later calls are cheap.
+    /// After adding, removing, or mutating types, call
+    /// [`invalidate_reverse_indexes`][Self::invalidate_reverse_indexes]
+    /// or the cached indexes will still describe the previous types.
+    /// [`validate`][Self::validate] does so before validating.
+    ///
+    /// ```rust
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse_and_validate(
+    ///     r#"
+    ///     type Query { search: [Result] featured: Book }
+    ///     interface Node { id: ID! }
+    ///     type Book implements Node { id: ID! }
+    ///     type Author implements Node { id: ID! favorite: Book }
+    ///     union Result = Book | Author
+    ///     "#,
+    ///     "schema.graphql",
+    /// )
+    /// .unwrap();
+    /// let indexes = schema.reverse_indexes();
+    /// let implementers = indexes.implementers_of("Node").unwrap();
+    /// assert_eq!(implementers.objects.len(), 2);
+    /// assert!(indexes.unions_containing("Book").any(|union_| union_ == "Result"));
+    /// let fields: Vec<_> = indexes
+    ///     .fields_returning("Book")
+    ///     .iter()
+    ///     .map(|coordinate| coordinate.to_string())
+    ///     .collect();
+    /// assert_eq!(fields, ["Query.featured", "Author.favorite"]);
+    /// ```
+    pub fn reverse_indexes(&self) -> Arc<ReverseIndexes> {
+        self.reverse_indexes_cache
+            .0
+            .get_or_init(|| Arc::new(ReverseIndexes::new(self)))
+            .clone()
+    }
+
+    /// Drops the cached [`reverse_indexes`][Self::reverse_indexes] and
+    /// [`possible_types`][Self::possible_types],
+    /// so that they are recomputed from the current types on next use.
+    ///
+    /// Call this after changing [`types`][Self::types] of a schema
+    /// whose reverse indexes may have been computed already.
+    pub fn invalidate_reverse_indexes(&mut self) {
+        self.reverse_indexes_cache = Default::default();
+    }
+
+    /// Returns the object types that can be the runtime type of a value of the given type.
+    ///
+    /// For a union type, these are its members.
+    /// For an interface type, these are the object types that implement it,
+    /// directly or through another interface.
+    /// For an object type, this is a set containing only that type.
+    /// Otherwise the set is empty.
+    ///
+    /// Sets are precomputed for every type together with [`reverse_indexes`][Self::reverse_indexes]
+    /// and cached the same way, so repeated calls are cheap.
+    ///
+    /// Spec: <https://spec.graphql.org/October2021/#GetPossibleTypes()>
+    ///
+    /// ```rust
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse_and_validate(
+    ///     r#"
+    ///     type Query { node: Node }
+    ///     interface Node { id: ID! }
+    ///     interface Resource implements Node { id: ID! url: String }
+    ///     type Image implements Resource & Node { id: ID! url: String }
+    ///     type User implements Node { id: ID! }
+    ///     union SearchResult = Image | User
+    ///     "#,
+    ///     "schema.graphql",
+    /// )
+    /// .unwrap();
+    /// let possible: Vec<_> = schema.possible_types("Node").iter().cloned().collect();
+    /// assert_eq!(possible, ["Image", "User"]);
+    /// assert_eq!(schema.possible_types("Resource").len(), 1);
+    /// assert_eq!(schema.possible_types("SearchResult").len(), 2);
+    /// assert!(schema.possible_types("String").is_empty());
+    /// ```
+    pub fn possible_types(&self, ty: &str) -> Arc<IndexSet<NamedType>> {
+        self.reverse_indexes()
+            .possible_types
+            .get(ty)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+impl ReverseIndexes {
+    fn new(schema: &Schema) -> Self {
+        let mut unions = HashMap::<NamedType, IndexSet<NamedType>>::default();
+        let mut union_members = HashMap::<NamedType, IndexSet<NamedType>>::default();
+        let mut fields = HashMap::<NamedType, Vec<TypeAttributeCoordinate>>::default();
+        for (type_name, ty) in &schema.types {
+            let type_fields = match ty {
+                ExtendedType::Object(def) => &def.fields,
+                ExtendedType::Interface(def) => &def.fields,
+                ExtendedType::Union(def) => {
+                    for member in &def.members {
+                        unions
+                            .entry(member.name.clone())
+                            .or_default()
+                            .insert(type_name.clone());
+                    }
+                    union_members.insert(
+                        type_name.clone(),
+                        def.members
+                            .iter()
+                            .map(|member| member.name.clone())
+                            .collect(),
+                    );
+                    continue;
+                }
+                ExtendedType::Scalar(_) | ExtendedType::Enum(_) | ExtendedType::InputObject(_) => {
+                    continue
+                }
+            };
+            for (field_name, field) in type_fields {
+                fields
+                    .entry(field.ty.inner_named_type().clone())
+                    .or_default()
+                    .push(TypeAttributeCoordinate {
+                        ty: type_name.clone(),
+                        attribute: field_name.clone(),
+                    })
+            }
+        }
+        let implementers = schema.implementers_map();
+        let possible_types = schema
+            .types
+            .iter()
+            .filter_map(|(name, ty)| {
+                let possible: IndexSet<NamedType> = match ty {
+                    ExtendedType::Object(_) => [name.clone()].into_iter().collect(),
+                    ExtendedType::Interface(_) => implementing_objects(&implementers, name),
+                    ExtendedType::Union(def) => def
+                        .members
+                        .iter()
+                        .map(|member| member.name.clone())
+                        .collect(),
+                    ExtendedType::Scalar(_)
+                    | ExtendedType::Enum(_)
+                    | ExtendedType::InputObject(_) => return None,
+                };
+                Some((name.clone(), Arc::new(possible)))
+            })
+            .collect();
+        Self {
+            implementers,
+            unions,
+            union_members,
+            fields,
+            possible_types,
+        }
+    }
+
+    /// Returns the object and interface types that implement the given interface,
+    /// or `None` if there are none
+    pub fn implementers_of(&self, interface: &str) -> Option<&Implementers> {
+        self.implementers.get(interface)
+    }
+
+    /// Returns the union types that have the given type as a member
+    pub fn unions_containing(&self, ty: &str) -> impl Iterator<Item = &NamedType> {
+        self.unions.get(ty).into_iter().flatten()
+    }
+
+    /// Returns the members of the given union type, or `None` if it is not a union type
+    pub(crate) fn union_members(&self, union_: &str) -> Option<&IndexSet<NamedType>> {
+        self.union_members.get(union_)
+    }
+
+    /// Returns the fields of object and interface types whose type is the given type,
+    /// possibly wrapped in list or non-null types, in schema definition order
+    pub fn fields_returning(&self, ty: &str) -> &[TypeAttributeCoordinate] {
+        self.fields.get(ty).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Object types implementing `interface` directly or through other interfaces
+fn implementing_objects(
+    implementers: &HashMap<NamedType, Implementers>,
+    interface: &NamedType,
+) -> IndexSet<NamedType> {
+    let mut objects = IndexSet::default();
+    let mut visited = HashSet::default();
+    let mut stack = vec![interface];
+    while let Some(interface) = stack.pop() {
+        // Interfaces can only implement each other in a cycle in an invalid schema
+        if !visited.insert(interface) {
+            continue;
+        }
+        if let Some(implementers) = implementers.get(interface) {
+            objects.extend(implementers.objects.iter().cloned());
+            stack.extend(&implementers.interfaces);
+        }
+    }
+    objects
+}
@@ -0,0 +1,459 @@
+use crate::ast::Value;
+use crate::collections::HashMap;
+use crate::name;
+use crate::parser::SourceSpan;
+use crate::schema::Component;
+use crate::schema::Directive;
+use crate::validation::DiagnosticList;
+use crate::validation::WithErrors;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+use std::fmt;
+
+/// The identity of the link specification, whose `@link` directive links other specifications
+const LINK_IDENTITY: &str = "https://specs.apollo.dev/link";
+
+/// The specifications linked to a schema by `@link` directives on its `schema` definition,
+/// resolved according to the [core schema spec](https://specs.apollo.dev/link/v1.0).
+///
+/// Elements of a linked specification are referred to in the schema either by an imported name,
+/// like `@key` for `import: ["@key"]`, or by a name prefixed with the namespace of the link,
+/// like `@federation__key`.
+/// The namespace is the `as:` argument of `@link`, or the name of the specification in its URL.
+///
+/// ```rust
+/// use apollo_compiler::schema::Links;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse(
+///     r#"
+///     extend schema
+///       @link(url: "https://specs.apollo.dev/federation/v2.3", import: ["@key", { name: "@shareable", as: "@shared" }])
+///     type Query { me: User }
+///     type User @key(fields: "id") { id: ID! name: String @federation__tag(name: "pii") }
+///     "#,
+///     "subgraph.graphql",
+/// )
+/// .unwrap();
+/// let links = Links::from_schema(&schema).unwrap();
+/// let federation = links.by_identity("https://specs.apollo.dev/federation").unwrap();
+/// assert_eq!(federation.version.unwrap().to_string(), "v2.3");
+/// assert_eq!(federation.directive_name_in_schema("shareable").unwrap(), "shared");
+/// assert_eq!(federation.directive_name_in_schema("tag").unwrap(), "federation__tag");
+///
+/// let (link, element) = links.resolve_directive("federation__tag").unwrap();
+/// assert_eq!(link.identity, "https://specs.apollo.dev/federation");
+/// assert_eq!(element, "tag");
+/// assert_eq!(links.resolve_directive("shared").unwrap().1, "shareable");
+/// assert!(links.resolve_directive("shareable").is_none());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Links {
+    links: Vec<Link>,
+}
+
+/// A specification linked by one `@link` directive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    /// The `url:` argument
+    pub url: String,
+    /// The URL without its version, which identifies the specification across versions,
+    /// like `https://specs.apollo.dev/federation`
+    pub identity: String,
+    /// The name of the specification from its URL, like `federation`
+    pub name: Option<Name>,
+    /// The version of the specification from its URL, like `v2.3`
+    pub version: Option<LinkVersion>,
+    /// The prefix of names of elements that are not imported: the `as:` argument or [`name`][Self::name]
+    pub namespace: Option<Name>,
+    /// Elements of the specification imported by the `import:` argument
+    pub imports: Vec<Import>,
+    /// The `for:` argument, like `SECURITY` or `EXECUTION`
+    pub purpose: Option<Name>,
+    /// The location of the `@link` directive
+    pub location: Option<SourceSpan>,
+}
+
+/// The version of a linked specification, like `v2.3`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LinkVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+/// An element of a linked specification imported with its own name or an alias
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Import {
+    /// The name of the element in the specification, without `@`
+    pub element: Name,
+    /// The `as:` name of the element in the schema, without `@`
+    pub alias: Option<Name>,
+    /// Whether this imports a directive (`"@name"`) rather than a type (`"Name"`)
+    pub is_directive: bool,
+}
+
+/// A malformed `@link` directive
+#[derive(thiserror::Error, Debug, Clone)]
+pub(crate) enum LinkError {
+    #[error("`@{directive}` must have a `url` string argument")]
+    MissingUrl { directive: Name },
+
+    #[error("`{url}` is not a valid link URL: {reason}")]
+    InvalidUrl { url: String, reason: &'static str },
+
+    #[error("`{value}` is not a valid namespace for the link to `{url}`")]
+    InvalidNamespace { url: String, value: String },
+
+    #[error("invalid import in the link to `{url}`: {reason}")]
+    InvalidImport { url: String, reason: String },
+
+    #[error("`{name}` refers to elements of multiple linked specifications")]
+    NameCollision { name: String },
+}
+
+impl Links {
+    /// Resolve the `@link` directives applied to the `schema` definition of `schema`.
+    ///
+    /// The link directive itself is `@link`, or the name it was imported as
+    /// if the schema links the link specification with an `as:` argument.
+    /// The schema does not need to be valid, since subgraph schemas often use `@link`
+    /// without defining it.
+    ///
+    /// If this returns errors, the partial result leaves out malformed links and imports.
+    pub fn from_schema(schema: &Schema) -> Result<Self, WithErrors<Self>> {
+        let mut errors = DiagnosticList::new(schema.sources.clone());
+        let directives = &schema.schema_definition.directives;
+        let link_directive = directives
+            .iter()
+            .find_map(|directive| {
+                let url = directive.argument_by_name("url")?.as_str()?;
+                let (identity, _, _) = parse_url(url).ok()?;
+                let namespace = string_argument(directive, "as").unwrap_or("link");
+                (identity == LINK_IDENTITY && directive.name == namespace)
+                    .then(|| directive.name.clone())
+            })
+            .unwrap_or_else(|| name!("link"));
+
+        let mut links = Self::default();
+        for directive in directives.get_all(&link_directive) {
+            if let Some(link) = Link::from_directive(&mut errors, directive) {
+                links.links.push(link)
+            }
+        }
+        links.check_collisions(&mut errors);
+        errors.into_result_with(links)
+    }
+
+    /// Returns all links, in the order of `@link` directives
+    pub fn iter(&self) -> impl Iterator<Item = &Link> {
+        self.links.iter()
+    }
+
+    /// Returns the link to the specification with the given identity,
+    /// like `https://specs.apollo.dev/federation`
+    pub fn by_identity(&self, identity: &str) -> Option<&Link> {
+        self.links.iter().find(|link| link.identity == identity)
+    }
+
+    /// Returns which linked directive a directive name used in the schema (without `@`) refers to:
+    /// the link and the name of the directive in the linked specification.
+    ///
+    /// For example with the default namespace, both `@federation__key`,
+    /// and `@key` if imported, refer to the `key` directive of the federation specification.
+    pub fn resolve_directive(&self, name_in_schema: &str) -> Option<(&Link, Name)> {
+        self.resolve(name_in_schema, true)
+    }
+
+    /// Returns which linked type a type name used in the schema refers to:
+    /// the link and the name of the type in the linked specification.
+    pub fn resolve_type(&self, name_in_schema: &str) -> Option<(&Link, Name)> {
+        self.resolve(name_in_schema, false)
+    }
+
+    fn resolve(&self, name_in_schema: &str, is_directive: bool) -> Option<(&Link, Name)> {
+        for link in &self.links {
+            if let Some(import) = link.import(name_in_schema, is_directive) {
+                return Some((link, import.element.clone()));
+            }
+        }
+        for link in &self.links {
+            let Some(namespace) = &link.namespace else {
+                continue;
+            };
+            if is_directive && name_in_schema == namespace.as_str() {
+                // The directive named like the specification, like `@link` itself
+                if let Some(name) = &link.name {
+                    return Some((link, name.clone()));
+                }
+            }
+            if let Some(element) = name_in_schema
+                .strip_prefix(namespace.as_str())
+                .and_then(|rest| rest.strip_prefix("__"))
+            {
+                if let Ok(element) = Name::new(element) {
+                    return Some((link, element));
+                }
+            }
+        }
+        None
+    }
+
+    fn check_collisions(&mut self, errors: &mut DiagnosticList) {
+        let mut seen = HashMap::<String, usize>::default();
+        let mut collisions = Vec::new();
+        for (index, link) in self.links.iter().enumerate() {
+            let names = link
+                .imports
+                .iter()
+                .map(|import| {
+                    let prefix = if import.is_directive { "@" } else { "" };
+                    format!("{prefix}{}", import.name_in_schema())
+                })
+                .chain(
+                    link.namespace
+                        .iter()
+                        .map(|namespace| format!("{namespace}__")),
+                );
+            for name in names {
+                match seen.get(&name) {
+                    Some(&other) if other != index => collisions.push((index, name)),
+                    _ => {
+                        seen.insert(name, index);
+                    }
+                }
+            }
+        }
+        for (index, name) in collisions {
+            errors.push(
+                self.links[index].location,
+                LinkError::NameCollision { name },
+            )
+        }
+    }
+}
+
+impl Link {
+    fn from_directive(
+        errors: &mut DiagnosticList,
+        directive: &Component<Directive>,
+    ) -> Option<Self> {
+        let location = directive.location();
+        let Some(url) = string_argument(directive, "url") else {
+            errors.push(
+                location,
+                LinkError::MissingUrl {
+                    directive: directive.name.clone(),
+                },
+            );
+            return None;
+        };
+        let (identity, name, version) = match parse_url(url) {
+            Ok(parsed) => parsed,
+            Err(reason) => {
+                errors.push(
+                    location,
+                    LinkError::InvalidUrl {
+                        url: url.to_owned(),
+                        reason,
+                    },
+                );
+                return None;
+            }
+        };
+        let namespace = match string_argument(directive, "as") {
+            Some(value) => match Name::new(value) {
+                Ok(namespace) if !value.contains("__") => Some(namespace),
+                _ => {
+                    errors.push(
+                        location,
+                        LinkError::InvalidNamespace {
+                            url: url.to_owned(),
+                            value: value.to_owned(),
+                        },
+                    );
+                    return None;
+                }
+            },
+            None => name.clone(),
+        };
+        let mut imports = Vec::new();
+        if let Some(value) = directive.argument_by_name("import") {
+            let items = value.as_list().unwrap_or(std::slice::from_ref(value));
+            for item in items {
+                match parse_import(item) {
+                    Ok(import) => imports.push(import),
+                    Err(reason) => errors.push(
+                        item.location().or(location),
+                        LinkError::InvalidImport {
+                            url: url.to_owned(),
+                            reason,
+                        },
+                    ),
+                }
+            }
+        }
+        let purpose = match directive.argument_by_name("for").map(|value| &**value) {
+            Some(Value::Enum(purpose)) => Some(purpose.clone()),
+            _ => None,
+        };
+        Some(Self {
+            url: url.to_owned(),
+            identity,
+            name,
+            version,
+            namespace,
+            imports,
+            purpose,
+            location,
+        })
+    }
+
+    /// Returns the name used in the schema (without `@`) for the given directive of this specification,
+    /// or `None` if it cannot be referred to because the link has no namespace
+    pub fn directive_name_in_schema(&self, element: &str) -> Option<Name> {
+        if let Some(import) = self
+            .imports
+            .iter()
+            .find(|import| import.is_directive && import.element == element)
+        {
+            return Some(import.name_in_schema().clone());
+        }
+        let namespace = self.namespace.as_ref()?;
+        if self.name.as_ref().is_some_and(|name| name == element) {
+            return Some(namespace.clone());
+        }
+        Name::new(&format!("{namespace}__{element}")).ok()
+    }
+
+    /// Returns the name used in the schema for the given type of this specification,
+    /// or `None` if it cannot be referred to because the link has no namespace
+    pub fn type_name_in_schema(&self, element: &str) -> Option<Name> {
+        if let Some(import) = self
+            .imports
+            .iter()
+            .find(|import| !import.is_directive && import.element == element)
+        {
+            return Some(import.name_in_schema().clone());
+        }
+        let namespace = self.namespace.as_ref()?;
+        Name::new(&format!("{namespace}__{element}")).ok()
+    }
+
+    fn import(&self, name_in_schema: &str, is_directive: bool) -> Option<&Import> {
+        self.imports.iter().find(|import| {
+            import.is_directive == is_directive && import.name_in_schema() == name_in_schema
+        })
+    }
+}
+
+impl Import {
+    /// Returns the name of the imported element in the schema: its alias if any, or its own name
+    pub fn name_in_schema(&self) -> &Name {
+        self.alias.as_ref().unwrap_or(&self.element)
+    }
+}
+
+impl fmt::Display for LinkVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v{}.{}", self.major, self.minor)
+    }
+}
+
+fn string_argument<'a>(directive: &'a Directive, name: &str) -> Option<&'a str> {
+    directive.argument_by_name(name)?.as_str()
+}
+
+/// Split a link URL into its identity, the name of the specification, and its version
+fn parse_url(url: &str) -> Result<(String, Option<Name>, Option<LinkVersion>), &'static str> {
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return Err("expected an absolute URL");
+    };
+    let valid_scheme = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    if !valid_scheme {
+        return Err("invalid scheme");
+    }
+    // The query string and fragment are not part of the identity
+    let rest = rest.split(['?', '#']).next().unwrap_or_default();
+    let mut segments = rest.split('/');
+    let host = segments.next().unwrap_or_default();
+    if host.is_empty() {
+        return Err("missing host");
+    }
+    let mut path: Vec<&str> = segments.filter(|segment| !segment.is_empty()).collect();
+    let version = match path.last() {
+        Some(last)
+            if last.starts_with('v') && last[1..].starts_with(|c: char| c.is_ascii_digit()) =>
+        {
+            let version = parse_version(&last[1..]).ok_or("invalid version")?;
+            path.pop();
+            Some(version)
+        }
+        _ => None,
+    };
+    let name = path.last().and_then(|name| Name::new(name).ok());
+    let mut identity = format!("{scheme}://{host}");
+    for segment in path {
+        identity.push('/');
+        identity.push_str(segment);
+    }
+    Ok((identity, name, version))
+}
+
+fn parse_version(version: &str) -> Option<LinkVersion> {
+    let (major, minor) = version.split_once('.')?;
+    let number = |digits: &str| {
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        digits.parse().ok()
+    };
+    Some(LinkVersion {
+        major: number(major)?,
+        minor: number(minor)?,
+    })
+}
+
+/// Parse an element of the `import:` argument: `"@name"`, `"Name"`, or `{ name: "@name", as: "@alias" }`
+fn parse_import(value: &Node<Value>) -> Result<Import, String> {
+    let (name, alias) = match &**value {
+        Value::String(name) => (name.as_str(), None),
+        Value::Object(fields) => {
+            let field = |key: &str| {
+                fields
+                    .iter()
+                    .find(|(name, _)| name == key)
+                    .map(|(_, value)| value.as_str().ok_or(format!("`{key}` must be a string")))
+                    .transpose()
+            };
+            let name = field("name")?.ok_or("missing `name`")?;
+            (name, field("as")?)
+        }
+        _ => return Err(format!("expected a string or an object, found `{value}`")),
+    };
+    let is_directive = name.starts_with('@');
+    let element = parse_element_name(name)?;
+    let alias = match alias {
+        Some(alias) if alias.starts_with('@') != is_directive => {
+            let expected = if is_directive { "must" } else { "must not" };
+            return Err(format!(
+                "`{alias}`, the alias of `{name}`, {expected} start with `@`"
+            ));
+        }
+        Some(alias) => Some(parse_element_name(alias)?),
+        None => None,
+    };
+    Ok(Import {
+        element,
+        alias,
+        is_directive,
+    })
+}
+
+fn parse_element_name(name: &str) -> Result<Name, String> {
+    Name::new(name.strip_prefix('@').unwrap_or(name))
+        .map_err(|_| format!("`{name}` is not a valid name"))
+}
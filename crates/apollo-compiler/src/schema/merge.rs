@@ -0,0 +1,411 @@
+use crate::ast;
+use crate::ast::OperationType;
+use crate::collections::IndexMap;
+use crate::coordinate::FieldArgumentCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::schema::Component;
+use crate::schema::DirectiveDefinition;
+use crate::schema::DirectiveList;
+use crate::schema::ExtendedType;
+use crate::schema::FieldDefinition;
+use crate::schema::InputValueDefinition;
+use crate::schema::NamedType;
+use crate::schema::Type;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+use std::fmt;
+use std::sync::Arc;
+
+/// The result of [`Schema::merge`] when the schemas define the same name incompatibly
+#[derive(Debug, Clone)]
+pub struct MergeConflicts {
+    /// The merged schema. For each conflict, it has the definition from the first schema.
+    pub partial: Schema,
+    /// Conflicts in the order of the second schema’s definitions
+    pub conflicts: Vec<MergeConflict>,
+}
+
+/// A name defined incompatibly by both schemas given to [`Schema::merge`]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum MergeConflict {
+    #[error(
+        "the {operation_type} root operation type is `{ty}` in the first schema \
+         but `{other_ty}` in the second schema"
+    )]
+    RootOperation {
+        operation_type: OperationType,
+        ty: NamedType,
+        other_ty: NamedType,
+    },
+
+    /// Directive definitions with the same name have different arguments, locations,
+    /// or repeatability
+    #[error("directive `@{name}` is defined differently in the two schemas")]
+    DirectiveDefinition { name: Name },
+
+    #[error("`{name}` is {kind} in the first schema but {other_kind} in the second schema")]
+    TypeKind {
+        name: NamedType,
+        kind: &'static str,
+        other_kind: &'static str,
+    },
+
+    #[error(
+        "field `{coordinate}` has type `{ty}` in the first schema \
+         but `{other_ty}` in the second schema"
+    )]
+    FieldType {
+        coordinate: TypeAttributeCoordinate,
+        ty: Type,
+        other_ty: Type,
+    },
+
+    /// An argument of a field or directive defined by both schemas
+    /// is missing from one of them, or has a different type or default value.
+    /// Also used for input object fields.
+    #[error("`{coordinate}` is not defined the same way in the two schemas")]
+    Argument { coordinate: SchemaCoordinate },
+}
+
+impl Schema {
+    /// Merge the definitions of `other` into a copy of this schema,
+    /// as if their source files were parsed into the same schema.
+    ///
+    /// * Types and directive definitions defined by only one schema are kept.
+    /// * Types defined by both have the union of their fields, implemented interfaces,
+    ///   union members, enum values, input fields, and directive applications.
+    ///   A field or input field defined by both must have the same type and arguments.
+    /// * Directive definitions defined by both must have the same arguments, locations,
+    ///   and repeatability.
+    /// * Root operations must have the same type if both schemas define them.
+    /// * Descriptions come from this schema, or from `other` if this schema has none.
+    ///
+    /// The schemas don’t need to be valid on their own, for example if one of them
+    /// refers to types defined by the other. The result is not validated.
+    ///
+    /// If the same name is defined incompatibly, returns every conflict
+    /// together with a merged schema that keeps this schema’s definition in each case.
+    ///
+    /// ```rust
+    /// use apollo_compiler::Schema;
+    ///
+    /// let users = Schema::parse("type Query { me: User } type User { id: ID! }", "users.graphql")
+    ///     .unwrap();
+    /// let posts = Schema::parse(
+    ///     "type Query { posts: [Post] } type Post { author: User } type User { id: ID! posts: [Post] }",
+    ///     "posts.graphql",
+    /// )
+    /// .unwrap();
+    /// let merged = users.merge(&posts).unwrap().validate().unwrap();
+    /// let user = merged.get_object("User").unwrap();
+    /// let fields: Vec<_> = user.fields.keys().map(|name| name.as_str()).collect();
+    /// assert_eq!(fields, ["id", "posts"]);
+    ///
+    /// let conflicting = Schema::parse("type User { id: String }", "other.graphql").unwrap();
+    /// let conflicts = users.merge(&conflicting).unwrap_err().conflicts;
+    /// assert_eq!(
+    ///     conflicts[0].to_string(),
+    ///     "field `User.id` has type `ID!` in the first schema but `String` in the second schema"
+    /// );
+    /// ```
+    pub fn merge(&self, other: &Schema) -> Result<Schema, MergeConflicts> {
+        let mut merger = Merger {
+            schema: self.clone(),
+            conflicts: Vec::new(),
+        };
+        Arc::make_mut(&mut merger.schema.sources)
+            .extend(other.sources.iter().map(|(id, file)| (*id, file.clone())));
+        merger.merge_schema_definition(other);
+        for (name, def) in &other.directive_definitions {
+            merger.merge_directive_definition(name, def)
+        }
+        for (name, ty) in &other.types {
+            merger.merge_type(name, ty)
+        }
+        let Merger { schema, conflicts } = merger;
+        if conflicts.is_empty() {
+            Ok(schema)
+        } else {
+            Err(MergeConflicts {
+                partial: schema,
+                conflicts,
+            })
+        }
+    }
+}
+
+struct Merger {
+    schema: Schema,
+    conflicts: Vec<MergeConflict>,
+}
+
+impl Merger {
+    fn merge_schema_definition(&mut self, other: &Schema) {
+        let other = &other.schema_definition;
+        let merged = self.schema.schema_definition.make_mut();
+        merge_description(&mut merged.description, &other.description);
+        merge_directives(&mut merged.directives, &other.directives);
+        for (operation_type, merged_root, other_root) in [
+            (OperationType::Query, &mut merged.query, &other.query),
+            (
+                OperationType::Mutation,
+                &mut merged.mutation,
+                &other.mutation,
+            ),
+            (
+                OperationType::Subscription,
+                &mut merged.subscription,
+                &other.subscription,
+            ),
+        ] {
+            let Some(other_root) = other_root else {
+                continue;
+            };
+            match merged_root {
+                Some(root) if root.name != other_root.name => {
+                    self.conflicts.push(MergeConflict::RootOperation {
+                        operation_type,
+                        ty: root.name.clone(),
+                        other_ty: other_root.name.clone(),
+                    })
+                }
+                Some(_) => {}
+                None => *merged_root = Some(other_root.clone()),
+            }
+        }
+    }
+
+    fn merge_directive_definition(&mut self, name: &Name, other: &Node<DirectiveDefinition>) {
+        let Some(def) = self.schema.directive_definitions.get_mut(name) else {
+            self.schema
+                .directive_definitions
+                .insert(name.clone(), other.clone());
+            return;
+        };
+        if **def == **other {
+            return;
+        }
+        let compatible = def.repeatable == other.repeatable
+            && def.locations.len() == other.locations.len()
+            && def
+                .locations
+                .iter()
+                .all(|location| other.locations.contains(location))
+            && arguments_compatible(&def.arguments, &other.arguments, |_| true);
+        if compatible {
+            merge_description(&mut def.make_mut().description, &other.description)
+        } else {
+            self.conflicts
+                .push(MergeConflict::DirectiveDefinition { name: name.clone() })
+        }
+    }
+
+    fn merge_type(&mut self, name: &NamedType, other: &ExtendedType) {
+        let Some(ty) = self.schema.types.get_mut(name) else {
+            self.schema.types.insert(name.clone(), other.clone());
+            return;
+        };
+        if ty.is_built_in() && other.is_built_in() {
+            return;
+        }
+        let conflicts = &mut self.conflicts;
+        match (ty, other) {
+            (ExtendedType::Scalar(def), ExtendedType::Scalar(other)) => {
+                let def = def.make_mut();
+                merge_description(&mut def.description, &other.description);
+                merge_directives(&mut def.directives, &other.directives);
+            }
+            (ExtendedType::Object(def), ExtendedType::Object(other)) => {
+                let def = def.make_mut();
+                merge_description(&mut def.description, &other.description);
+                merge_directives(&mut def.directives, &other.directives);
+                def.implements_interfaces
+                    .extend(other.implements_interfaces.iter().cloned());
+                merge_fields(conflicts, name, &mut def.fields, &other.fields);
+            }
+            (ExtendedType::Interface(def), ExtendedType::Interface(other)) => {
+                let def = def.make_mut();
+                merge_description(&mut def.description, &other.description);
+                merge_directives(&mut def.directives, &other.directives);
+                def.implements_interfaces
+                    .extend(other.implements_interfaces.iter().cloned());
+                merge_fields(conflicts, name, &mut def.fields, &other.fields);
+            }
+            (ExtendedType::Union(def), ExtendedType::Union(other)) => {
+                let def = def.make_mut();
+                merge_description(&mut def.description, &other.description);
+                merge_directives(&mut def.directives, &other.directives);
+                def.members.extend(other.members.iter().cloned());
+            }
+            (ExtendedType::Enum(def), ExtendedType::Enum(other)) => {
+                let def = def.make_mut();
+                merge_description(&mut def.description, &other.description);
+                merge_directives(&mut def.directives, &other.directives);
+                for (value_name, other_value) in &other.values {
+                    match def.values.get_mut(value_name) {
+                        Some(value) => {
+                            let value = value.make_mut();
+                            merge_description(&mut value.description, &other_value.description);
+                            merge_ast_directives(&mut value.directives, &other_value.directives);
+                        }
+                        None => {
+                            def.values.insert(value_name.clone(), other_value.clone());
+                        }
+                    }
+                }
+            }
+            (ExtendedType::InputObject(def), ExtendedType::InputObject(other)) => {
+                let def = def.make_mut();
+                merge_description(&mut def.description, &other.description);
+                merge_directives(&mut def.directives, &other.directives);
+                for (field_name, other_field) in &other.fields {
+                    match def.fields.get_mut(field_name) {
+                        Some(field) if input_value_compatible(field, other_field) => {
+                            let field = field.make_mut();
+                            merge_description(&mut field.description, &other_field.description);
+                            merge_ast_directives(&mut field.directives, &other_field.directives);
+                        }
+                        Some(_) => conflicts.push(MergeConflict::Argument {
+                            coordinate: TypeAttributeCoordinate {
+                                ty: name.clone(),
+                                attribute: field_name.clone(),
+                            }
+                            .into(),
+                        }),
+                        None => {
+                            def.fields.insert(field_name.clone(), other_field.clone());
+                        }
+                    }
+                }
+            }
+            (ty, other) => conflicts.push(MergeConflict::TypeKind {
+                name: name.clone(),
+                kind: ty.describe(),
+                other_kind: other.describe(),
+            }),
+        }
+    }
+}
+
+fn merge_fields(
+    conflicts: &mut Vec<MergeConflict>,
+    type_name: &NamedType,
+    fields: &mut IndexMap<Name, Component<FieldDefinition>>,
+    other_fields: &IndexMap<Name, Component<FieldDefinition>>,
+) {
+    for (field_name, other_field) in other_fields {
+        let Some(field) = fields.get_mut(field_name) else {
+            fields.insert(field_name.clone(), other_field.clone());
+            continue;
+        };
+        if field.ty != other_field.ty {
+            conflicts.push(MergeConflict::FieldType {
+                coordinate: TypeAttributeCoordinate {
+                    ty: type_name.clone(),
+                    attribute: field_name.clone(),
+                },
+                ty: field.ty.clone(),
+                other_ty: other_field.ty.clone(),
+            });
+            continue;
+        }
+        let mut incompatible = Vec::new();
+        arguments_compatible(&field.arguments, &other_field.arguments, |argument| {
+            incompatible.push(argument.clone());
+            false
+        });
+        if incompatible.is_empty() {
+            let field = field.make_mut();
+            merge_description(&mut field.description, &other_field.description);
+            merge_ast_directives(&mut field.directives, &other_field.directives);
+        }
+        conflicts.extend(incompatible.into_iter().map(|argument| {
+            MergeConflict::Argument {
+                coordinate: FieldArgumentCoordinate {
+                    ty: type_name.clone(),
+                    field: field_name.clone(),
+                    argument,
+                }
+                .into(),
+            }
+        }))
+    }
+}
+
+/// Returns whether both lists define the same arguments with the same types and default values,
+/// calling `on_incompatible` with the name of each argument that differs until it returns false.
+fn arguments_compatible(
+    arguments: &[Node<InputValueDefinition>],
+    other_arguments: &[Node<InputValueDefinition>],
+    mut on_incompatible: impl FnMut(&Name) -> bool,
+) -> bool {
+    let mut compatible = true;
+    let find = |arguments: &'_ [Node<InputValueDefinition>], name: &Name| {
+        arguments
+            .iter()
+            .find(|argument| argument.name == *name)
+            .cloned()
+    };
+    let names = arguments
+        .iter()
+        .chain(other_arguments)
+        .map(|argument| &argument.name);
+    let mut seen = Vec::new();
+    for name in names {
+        if seen.contains(&name) {
+            continue;
+        }
+        seen.push(name);
+        let same = match (find(arguments, name), find(other_arguments, name)) {
+            (Some(argument), Some(other)) => input_value_compatible(&argument, &other),
+            _ => false,
+        };
+        if !same {
+            compatible = false;
+            if !on_incompatible(name) {
+                break;
+            }
+        }
+    }
+    compatible
+}
+
+fn input_value_compatible(value: &InputValueDefinition, other: &InputValueDefinition) -> bool {
+    value.ty == other.ty && value.default_value == other.default_value
+}
+
+fn merge_description(description: &mut Option<Node<str>>, other: &Option<Node<str>>) {
+    if description.is_none() {
+        description.clone_from(other)
+    }
+}
+
+fn merge_directives(directives: &mut DirectiveList, other: &DirectiveList) {
+    for directive in other.iter() {
+        if !directives.contains(directive) {
+            directives.push(directive.clone())
+        }
+    }
+}
+
+fn merge_ast_directives(directives: &mut ast::DirectiveList, other: &ast::DirectiveList) {
+    for directive in other.iter() {
+        if !directives.contains(directive) {
+            directives.push(directive.clone())
+        }
+    }
+}
+
+impl fmt::Display for MergeConflicts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for conflict in &self.conflicts {
+            writeln!(f, "{conflict}")?
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for MergeConflicts {}
@@ -4,29 +4,125 @@ use crate::ast;
 use crate::collections::HashMap;
 use crate::collections::IndexMap;
 use crate::collections::IndexSet;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::SchemaCoordinateLookup;
+use crate::coordinate::SchemaLookupError;
 use crate::name;
 use crate::parser::FileId;
 use crate::parser::Parser;
 use crate::parser::SourceSpan;
 use crate::ty;
+use crate::validation::custom_directive::validate_custom_directives_in_schema;
+use crate::validation::deprecation::validate_deprecated_required_inputs;
+use crate::validation::directive::validate_repeated_directives_in_schema;
+use crate::validation::federation::validate_federation_field_sets;
+use crate::validation::one_of::validate_one_of_definitions;
+use crate::validation::progress::Progress;
+use crate::validation::progress::ValidationPhase;
+use crate::validation::scalar::validate_specified_by_urls;
 use crate::validation::DiagnosticList;
+use crate::validation::LintConfig;
+use crate::validation::SpecVersion;
 use crate::validation::Valid;
+use crate::validation::ValidationConfig;
+use crate::validation::ValidationRule;
 use crate::validation::WithErrors;
+use crate::validation::WithWarnings;
 pub use crate::Name;
 use crate::Node;
 use std::path::Path;
 use std::sync::OnceLock;
 
 mod component;
+mod composition;
+mod deprecations;
+mod diff;
+mod directive_applications;
+mod filter;
+mod fingerprint;
+mod fluent;
+mod fold_extensions;
 mod from_ast;
+#[cfg(feature = "serde_json")]
+mod from_introspection;
+mod indexes;
+mod link;
+mod merge;
+mod prune;
+mod references;
+mod rename;
+mod semantic_eq;
 mod serialize;
+mod slice;
+#[cfg(feature = "snapshot")]
+mod snapshot;
+mod supergraph;
+#[cfg(feature = "serde_json")]
+mod to_introspection;
 pub(crate) mod validation;
+mod visit;
 
 pub use self::component::Component;
 pub use self::component::ComponentName;
 pub use self::component::ComponentOrigin;
 pub use self::component::ExtensionId;
+pub use self::composition::compose;
+pub(crate) use self::composition::CompositionError;
+pub use self::deprecations::Deprecation;
+pub use self::deprecations::DeprecationKind;
+pub use self::diff::diff;
+pub use self::diff::Criticality;
+pub use self::diff::SchemaChange;
+pub use self::diff::SchemaChangeKind;
+pub use self::diff::SchemaDiff;
+pub use self::directive_applications::AppliedDirective;
+pub use self::filter::remove_annotated;
+pub use self::filter::CascadedRemoval;
+pub use self::filter::RemovalReport;
+pub use self::fingerprint::FingerprintOptions;
+pub use self::fluent::EnumTypeBuilder;
+pub use self::fluent::EnumValueBuilder;
+pub use self::fluent::FieldBuilder;
+pub use self::fluent::InputObjectTypeBuilder;
+pub use self::fluent::InputValueBuilder;
+pub use self::fluent::ObjectTypeBuilder;
+pub use self::fold_extensions::ExtensionProvenance;
+pub use self::fold_extensions::FoldedComponent;
+pub use self::from_ast::BuiltInScalarRedefinitions;
+pub use self::from_ast::DuplicateDefinitions;
 pub use self::from_ast::SchemaBuilder;
+#[cfg(feature = "serde_json")]
+pub(crate) use self::from_introspection::IntrospectionResponseError;
+pub use self::indexes::ReverseIndexes;
+use self::indexes::ReverseIndexesCache;
+pub use self::link::Import;
+pub use self::link::Link;
+pub(crate) use self::link::LinkError;
+pub use self::link::LinkVersion;
+pub use self::link::Links;
+pub use self::merge::MergeConflict;
+pub use self::merge::MergeConflicts;
+pub use self::prune::prune;
+pub use self::prune::PruneReport;
+pub use self::references::TypeReference;
+pub use self::references::TypeReferenceGraph;
+pub use self::references::TypeReferenceKind;
+pub use self::rename::rename_types;
+pub use self::rename::RenameTypesError;
+pub use self::rename::TypeRenames;
+pub use self::semantic_eq::SemanticDifference;
+pub use self::semantic_eq::SemanticDifferenceKind;
+pub use self::semantic_eq::SemanticEqOptions;
+pub use self::slice::slice_for_operations;
+#[cfg(feature = "snapshot")]
+pub use self::snapshot::SnapshotError;
+pub use self::supergraph::Subgraph;
+pub use self::supergraph::Supergraph;
+pub(crate) use self::supergraph::SupergraphError;
+#[cfg(feature = "serde_json")]
+pub use self::to_introspection::IntrospectionOptions;
+pub use self::visit::Visitor;
+pub use self::visit::VisitorMut;
 pub use crate::ast::Directive;
 pub use crate::ast::DirectiveDefinition;
 pub use crate::ast::DirectiveLocation;
@@ -54,6 +150,8 @@ pub struct Schema {
     /// Definitions and extensions of built-in scalars, introspection types,
     /// and explicit types
     pub types: IndexMap<NamedType, ExtendedType>,
+
+    reverse_indexes_cache: ReverseIndexesCache,
 }
 
 /// The `schema` definition and its extensions, defining root operations
@@ -206,6 +304,20 @@ pub(crate) enum BuildError {
         name: Name,
     },
 
+    #[error(
+        "the {} `{}{name}` is defined multiple times in the schema, keeping the {kept} definition",
+        if *directive { "directive" } else { "type" },
+        if *directive { "@" } else { "" }
+    )]
+    DuplicateDefinitionResolved {
+        previous_location: Option<SourceSpan>,
+        name: Name,
+        /// Whether this is a directive definition rather than a type definition
+        directive: bool,
+        /// `first` or `last`
+        kept: &'static str,
+    },
+
     #[error("built-in scalar definitions must be omitted")]
     BuiltInScalarTypeRedefinition,
 
@@ -254,6 +366,8 @@ pub(crate) enum BuildError {
     ObjectFieldNameCollision {
         name_at_previous_location: Name,
         type_name: Name,
+        /// Every later definition, in source order
+        redefined_definitions: Vec<Option<SourceSpan>>,
     },
 
     #[error(
@@ -263,6 +377,8 @@ pub(crate) enum BuildError {
     InterfaceFieldNameCollision {
         name_at_previous_location: Name,
         type_name: Name,
+        /// Every later definition, in source order
+        redefined_definitions: Vec<Option<SourceSpan>>,
     },
 
     #[error(
@@ -272,6 +388,8 @@ pub(crate) enum BuildError {
     EnumValueNameCollision {
         name_at_previous_location: Name,
         type_name: Name,
+        /// Every later definition, in source order
+        redefined_definitions: Vec<Option<SourceSpan>>,
     },
 
     #[error(
@@ -290,9 +408,43 @@ pub(crate) enum BuildError {
     InputFieldNameCollision {
         name_at_previous_location: Name,
         type_name: Name,
+        /// Every later definition, in source order
+        redefined_definitions: Vec<Option<SourceSpan>>,
     },
 }
 
+impl BuildError {
+    /// For a field or enum value defined more than once in a type and its extensions,
+    /// returns the name at its first definition, the type name, and the later definitions
+    pub(crate) fn name_collision_mut(
+        &mut self,
+    ) -> Option<(&Name, &Name, &mut Vec<Option<SourceSpan>>)> {
+        match self {
+            Self::ObjectFieldNameCollision {
+                name_at_previous_location,
+                type_name,
+                redefined_definitions,
+            }
+            | Self::InterfaceFieldNameCollision {
+                name_at_previous_location,
+                type_name,
+                redefined_definitions,
+            }
+            | Self::EnumValueNameCollision {
+                name_at_previous_location,
+                type_name,
+                redefined_definitions,
+            }
+            | Self::InputFieldNameCollision {
+                name_at_previous_location,
+                type_name,
+                redefined_definitions,
+            } => Some((name_at_previous_location, type_name, redefined_definitions)),
+            _ => None,
+        }
+    }
+}
+
 /// Could not find the requested field definition
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FieldLookupError<'schema> {
@@ -347,9 +499,136 @@ impl Schema {
     }
 
     pub fn validate(self) -> Result<Valid<Self>, WithErrors<Self>> {
+        self.validate_with_config(&ValidationConfig::default())
+            .map(WithWarnings::into_valid)
+    }
+
+    /// Like [`validate`][Self::validate], but does not report diagnostics
+    /// from rules turned off in `config`.
+    ///
+    /// Only errors fail validation.
+    /// [Warnings][crate::validation::Severity::Warning] enabled by `config`, if any,
+    /// are returned alongside the valid result.
+    pub fn validate_with_config(
+        mut self,
+        config: &ValidationConfig,
+    ) -> Result<WithWarnings<Self>, WithErrors<Self>> {
+        self.invalidate_reverse_indexes();
+        let errors = self.validation_diagnostics(config);
+        errors.into_valid_result_with_warnings(self)
+    }
+
+    /// Returns all diagnostics of [`validate_with_config`][Self::validate_with_config],
+    /// including [suppressed][DiagnosticList::suppressed] ones, even if the schema is valid.
+    pub fn validation_diagnostics(&self, config: &ValidationConfig) -> DiagnosticList {
         let mut errors = DiagnosticList::new(self.sources.clone());
-        validation::validate_schema(&mut errors, &self);
-        errors.into_valid_result(self)
+        let draft = config.validation_spec_version() >= SpecVersion::Draft;
+        let specified_by_url_check = config.specified_by_url_check();
+        let additional_rules = draft
+            || specified_by_url_check.is_some()
+            || config.repeated_directive_warnings_enabled()
+            || config.federation_field_sets_enabled()
+            || config.has_directive_validators();
+        // Additional rules walk the schema again
+        let steps = validation::schema_validation_steps(self);
+        let total = if additional_rules { 2 * steps } else { steps };
+        let mut progress = Progress::new(config, total);
+        let limits = config.validation_limits();
+        validation::validate_schema_with_progress(&mut errors, self, limits, &mut progress);
+        if additional_rules && progress.start(&mut errors, ValidationPhase::AdditionalRules, steps)
+        {
+            if draft
+                && config.any_rule_enabled(&[
+                    ValidationRule::NonNullOneOfField,
+                    ValidationRule::OneOfFieldDefault,
+                ])
+            {
+                validate_one_of_definitions(&mut errors, self);
+            }
+            if draft && config.any_rule_enabled(&[ValidationRule::DeprecatedRequiredInput]) {
+                validate_deprecated_required_inputs(&mut errors, self);
+            }
+            if let Some(check) = specified_by_url_check
+                .filter(|_| config.any_rule_enabled(&[ValidationRule::InvalidSpecifiedByUrl]))
+            {
+                validate_specified_by_urls(&mut errors, self, check);
+            }
+            if config.repeated_directive_warnings_enabled()
+                && config.any_rule_enabled(&[ValidationRule::RepeatedDirectiveArguments])
+            {
+                validate_repeated_directives_in_schema(&mut errors, self);
+            }
+            if config.federation_field_sets_enabled() {
+                validate_federation_field_sets(&mut errors, self);
+            }
+            if config.has_directive_validators()
+                && config.any_rule_enabled(&[ValidationRule::InvalidDirectiveApplication])
+            {
+                validate_custom_directives_in_schema(&mut errors, self, config);
+            }
+        }
+        errors.apply_config(config);
+        errors
+    }
+
+    /// Check the schema against style conventions that the specification does not require,
+    /// such as `PascalCase` type names, as configured by `config`.
+    ///
+    /// Returns diagnostics with [`Severity::Lint`][crate::validation::Severity::Lint].
+    /// Linting is separate from validation: run [`validate`][Self::validate] as well.
+    ///
+    /// ```rust
+    /// use apollo_compiler::validation::LintConfig;
+    /// use apollo_compiler::validation::LintRule;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse("type Query { user_name: String }", "schema.graphql").unwrap();
+    /// let config = LintConfig::new().disable_rule(LintRule::DescriptionRequired);
+    /// let lints = schema.lint(&config);
+    /// assert_eq!(lints.len(), 1);
+    /// assert!(lints.to_string().contains("rename to `userName`"));
+    /// ```
+    pub fn lint(&self, config: &LintConfig) -> DiagnosticList {
+        let mut diagnostics = DiagnosticList::new(self.sources.clone());
+        crate::validation::lint::lint_schema(&mut diagnostics, self, config);
+        diagnostics
+    }
+
+    /// Add type system definitions and extensions from `document` to a copy of `schema`,
+    /// then validate only what they add or change.
+    ///
+    /// This is for extending a schema at runtime without validating it again as a whole.
+    /// Diagnostics are limited to the new definitions, the types and directives they extend,
+    /// and types that implement an extended interface.
+    /// The returned schema includes the new definitions.
+    ///
+    /// ```rust
+    /// use apollo_compiler::ast;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    /// let added = ast::Document::parse("extend type Query { b: B }", "b.graphql").unwrap();
+    /// let errors = Schema::validate_added_definitions(&schema, &added).unwrap_err().errors;
+    /// assert!(errors.to_string().contains("cannot find type `B`"));
+    ///
+    /// let added = ast::Document::parse("extend type Query { b: Int }", "b.graphql").unwrap();
+    /// let schema = Schema::validate_added_definitions(&schema, &added).unwrap();
+    /// assert!(schema.type_field("Query", "b").is_ok());
+    /// ```
+    pub fn validate_added_definitions(
+        schema: &Valid<Self>,
+        document: &ast::Document,
+    ) -> Result<Valid<Self>, WithErrors<Self>> {
+        let mut builder = SchemaBuilder::from_schema(schema.as_ref().clone());
+        builder.add_ast_document(document, true);
+        let (schema, mut errors) = builder.build_inner();
+        validation::validate_affected_definitions(
+            &mut errors,
+            &schema,
+            document,
+            &Default::default(),
+        );
+        errors.into_valid_result(schema)
     }
 
     /// Returns the type with the given name, if it is a scalar type
@@ -456,6 +735,53 @@ impl Schema {
         Err(FieldLookupError::NoSuchField(ty_def_name, ty_def))
     }
 
+    /// Returns the definition of the type, field, input field, enum value, argument,
+    /// or directive that a schema coordinate points to.
+    ///
+    /// Tooling configured by coordinates can parse them once with [`str::parse`],
+    /// then look them up in each schema.
+    /// This is [`SchemaCoordinate::lookup`] with the schema first.
+    ///
+    /// ```rust
+    /// use apollo_compiler::coordinate::SchemaCoordinate;
+    /// use apollo_compiler::coordinate::SchemaCoordinateLookup;
+    /// use apollo_compiler::coordinate::SchemaLookupError;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse_and_validate(
+    ///     r#"
+    ///     directive @auth(role: String) on FIELD_DEFINITION
+    ///     type Query { hero(episode: Episode): String @auth(role: "fan") }
+    ///     enum Episode { NEWHOPE EMPIRE JEDI }
+    ///     "#,
+    ///     "schema.graphql",
+    /// )
+    /// .unwrap();
+    ///
+    /// let coordinate: SchemaCoordinate = "Episode.EMPIRE".parse().unwrap();
+    /// let Ok(SchemaCoordinateLookup::EnumValue(value)) = schema.element_at(&coordinate) else {
+    ///     panic!("expected an enum value")
+    /// };
+    /// assert_eq!(value.value, "EMPIRE");
+    ///
+    /// let coordinate: SchemaCoordinate = "@auth(role:)".parse().unwrap();
+    /// let Ok(SchemaCoordinateLookup::Argument(argument)) = schema.element_at(&coordinate) else {
+    ///     panic!("expected an argument")
+    /// };
+    /// assert_eq!(argument.ty.to_string(), "String");
+    ///
+    /// let coordinate: SchemaCoordinate = "@cached".parse().unwrap();
+    /// let error = schema.element_at(&coordinate).unwrap_err();
+    /// assert!(matches!(error, SchemaLookupError::MissingDirective(_)));
+    /// assert_eq!(error.to_string(), "directive `@cached` is not defined");
+    /// ```
+    pub fn element_at<'coord>(
+        &self,
+        coordinate: &'coord SchemaCoordinate,
+    ) -> Result<SchemaCoordinateLookup<'_>, SchemaLookupError<'coord, '_>> {
+        coordinate.lookup(self)
+    }
+
     /// Returns a map of interface names to names of types that implement that interface
     ///
     /// `Schema` only stores the inverse relationship
@@ -946,6 +1272,7 @@ impl PartialEq for Schema {
             schema_definition,
             directive_definitions,
             types,
+            reverse_indexes_cache: _, // ignored
         } = self;
         *schema_definition == other.schema_definition
             && *directive_definitions == other.directive_definitions
@@ -1041,6 +1368,7 @@ impl std::fmt::Debug for Schema {
             schema_definition,
             directive_definitions,
             types,
+            reverse_indexes_cache: _,
         } = self;
         f.debug_struct("Schema")
             .field("sources", sources)
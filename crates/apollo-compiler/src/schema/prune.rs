@@ -0,0 +1,211 @@
+use crate::collections::HashMap;
+use crate::collections::IndexSet;
+use crate::schema::ExtendedType;
+use crate::schema::Implementers;
+use crate::schema::NamedType;
+use crate::validation::Valid;
+use crate::Name;
+use crate::Schema;
+
+/// Returns a copy of `schema` without the types and directive definitions
+/// that are not reachable from its root operation types, and a report of what was removed.
+///
+/// A type is reachable if it is a root operation type, or is referenced
+/// by the fields, arguments, interfaces, or members of a reachable type
+/// or by the arguments of a kept directive definition.
+/// Types that implement a reachable interface are also reachable,
+/// since they are its possible types.
+/// Type names in `keep` are kept even if not referenced, together with what they reach,
+/// for types that are intentionally orphaned such as entities resolved by reference.
+///
+/// Directive definitions are kept if they have an executable location like `FIELD`,
+/// since clients can use them, or if they are applied to the schema definition
+/// or somewhere in a kept type.
+/// Built-in types and directives are always kept.
+///
+/// Since everything a kept definition refers to is also kept, the result is still valid.
+/// This can generate a public API schema from an internal one, after removing internal fields.
+///
+/// ```rust
+/// use apollo_compiler::schema;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(
+///     r#"
+///     directive @cached on FIELD
+///     directive @internal on OBJECT
+///     type Query { user: User }
+///     type User { name: String }
+///     type AuditLog @internal { entries: [String] }
+///     type Product { upc: ID! }
+///     "#,
+///     "schema.graphql",
+/// )
+/// .unwrap();
+/// let (pruned, report) = schema::prune(&schema, ["Product"]);
+/// assert_eq!(report.removed_types, ["AuditLog"]);
+/// assert_eq!(report.removed_directives, ["internal"]);
+/// assert!(pruned.types.contains_key("Product"));
+/// assert!(pruned.directive_definitions.contains_key("cached"));
+/// ```
+pub fn prune<'a>(
+    schema: &Valid<Schema>,
+    keep: impl IntoIterator<Item = &'a str>,
+) -> (Valid<Schema>, PruneReport) {
+    let mut reachable = Reachable {
+        schema,
+        implementers: schema.implementers_map(),
+        types: IndexSet::default(),
+        directives: IndexSet::default(),
+        queue: Vec::new(),
+    };
+    for (_, root) in schema.schema_definition.iter_root_operations() {
+        reachable.add_type(&root.name);
+    }
+    for name in keep {
+        if let Some(ty) = schema.types.get(name) {
+            reachable.add_type(ty.name());
+        }
+    }
+    reachable.add_directives(
+        schema
+            .schema_definition
+            .directives
+            .iter()
+            .map(|directive| &directive.name),
+    );
+    for (name, definition) in &schema.directive_definitions {
+        if definition.is_built_in()
+            || definition
+                .locations
+                .iter()
+                .any(|location| location.is_executable_location())
+        {
+            reachable.add_directive(name);
+        }
+    }
+    while let Some(name) = reachable.queue.pop() {
+        reachable.walk_type(&name);
+    }
+
+    let mut report = PruneReport::default();
+    let mut pruned = Schema::clone(schema);
+    pruned.types.retain(|name, ty| {
+        let keep = ty.is_built_in() || reachable.types.contains(name);
+        if !keep {
+            report.removed_types.push(name.clone());
+        }
+        keep
+    });
+    pruned.directive_definitions.retain(|name, _| {
+        let keep = reachable.directives.contains(name);
+        if !keep {
+            report.removed_directives.push(name.clone());
+        }
+        keep
+    });
+    (Valid::assume_valid(pruned), report)
+}
+
+/// What was removed by [`prune`]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PruneReport {
+    /// Names of removed types, in the order they were defined
+    pub removed_types: Vec<NamedType>,
+    /// Names of removed directive definitions, in the order they were defined
+    pub removed_directives: Vec<Name>,
+}
+
+impl PruneReport {
+    /// Returns whether nothing was removed
+    pub fn is_empty(&self) -> bool {
+        self.removed_types.is_empty() && self.removed_directives.is_empty()
+    }
+}
+
+struct Reachable<'a> {
+    schema: &'a Schema,
+    implementers: HashMap<Name, Implementers>,
+    types: IndexSet<NamedType>,
+    directives: IndexSet<Name>,
+    /// Reachable types that have not been walked yet
+    queue: Vec<NamedType>,
+}
+
+impl Reachable<'_> {
+    fn add_type(&mut self, name: &NamedType) {
+        if self.types.insert(name.clone()) {
+            self.queue.push(name.clone());
+        }
+    }
+
+    fn add_directive(&mut self, name: &Name) {
+        if !self.directives.insert(name.clone()) {
+            return;
+        }
+        let Some(definition) = self.schema.directive_definitions.get(name) else {
+            return;
+        };
+        for argument in &definition.arguments {
+            self.add_type(argument.ty.inner_named_type());
+            self.add_directives(argument.directives.iter().map(|directive| &directive.name));
+        }
+    }
+
+    fn add_directives<'b>(&mut self, names: impl IntoIterator<Item = &'b Name>) {
+        for name in names {
+            self.add_directive(name)
+        }
+    }
+
+    fn walk_type(&mut self, name: &NamedType) {
+        let Some(ty) = self.schema.types.get(name) else {
+            return;
+        };
+        if ty.is_built_in() {
+            return;
+        }
+        self.add_directives(ty.directives().iter().map(|directive| &directive.name));
+        let (interfaces, fields) = match ty {
+            ExtendedType::Object(def) => (&def.implements_interfaces, &def.fields),
+            ExtendedType::Interface(def) => (&def.implements_interfaces, &def.fields),
+            ExtendedType::Union(def) => {
+                for member in &def.members {
+                    self.add_type(&member.name);
+                }
+                return;
+            }
+            ExtendedType::Enum(def) => {
+                for value in def.values.values() {
+                    self.add_directives(value.directives.iter().map(|directive| &directive.name));
+                }
+                return;
+            }
+            ExtendedType::InputObject(def) => {
+                for field in def.fields.values() {
+                    self.add_type(field.ty.inner_named_type());
+                    self.add_directives(field.directives.iter().map(|directive| &directive.name));
+                }
+                return;
+            }
+            ExtendedType::Scalar(_) => return,
+        };
+        for interface in interfaces {
+            self.add_type(&interface.name);
+        }
+        for field in fields.values() {
+            self.add_type(field.ty.inner_named_type());
+            self.add_directives(field.directives.iter().map(|directive| &directive.name));
+            for argument in &field.arguments {
+                self.add_type(argument.ty.inner_named_type());
+                self.add_directives(argument.directives.iter().map(|directive| &directive.name));
+            }
+        }
+        if let Some(implementers) = self.implementers.get(name) {
+            let implementers: Vec<_> = implementers.iter().cloned().collect();
+            for implementer in &implementers {
+                self.add_type(implementer);
+            }
+        }
+    }
+}
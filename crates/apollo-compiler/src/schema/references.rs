@@ -0,0 +1,274 @@
+use crate::collections::IndexMap;
+use crate::coordinate::DirectiveArgumentCoordinate;
+use crate::coordinate::FieldArgumentCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::coordinate::TypeCoordinate;
+use crate::schema::ExtendedType;
+use crate::schema::NamedType;
+use crate::Name;
+use crate::Schema;
+use std::fmt;
+
+/// Which schema elements refer to which types, as returned by [`Schema::type_references`]
+///
+/// References made by built-in types and directives are not included,
+/// but references to built-in scalars are.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TypeReferenceGraph {
+    references: Vec<TypeReference>,
+    /// Indices in `references`, by referenced type
+    by_target: IndexMap<NamedType, Vec<usize>>,
+    /// Indices in `references`, by the type definition that contains the source
+    by_source_type: IndexMap<NamedType, Vec<usize>>,
+    /// Indices in `references`, by the directive definition that contains the source
+    by_source_directive: IndexMap<Name, Vec<usize>>,
+}
+
+/// A reference to a type in a [`TypeReferenceGraph`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TypeReference {
+    /// The element that refers to the type.
+    /// For union members and implemented interfaces, this is the union or implementing type.
+    pub source: SchemaCoordinate,
+    /// The referenced type
+    pub target: NamedType,
+    pub kind: TypeReferenceKind,
+}
+
+/// How a [`TypeReference`] refers to its target
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TypeReferenceKind {
+    /// The type of a field of an object or interface type
+    FieldType,
+    /// The type of a field argument
+    ArgumentType,
+    /// The type of a field of an input object type
+    InputFieldType,
+    /// A member type of a union type
+    UnionMember,
+    /// An interface implemented by an object or interface type
+    ImplementedInterface,
+    /// The type of a directive argument
+    DirectiveArgumentType,
+}
+
+impl Schema {
+    /// Returns a graph of which schema elements refer to which types,
+    /// such as field return types, argument types, union members, and implemented interfaces.
+    ///
+    /// Reverse lookups answer impact analysis questions
+    /// like "what breaks if `Money` is removed?":
+    ///
+    /// ```rust
+    /// use apollo_compiler::schema::TypeReferenceKind;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse_and_validate(
+    ///     r#"
+    ///     type Query { product: Product }
+    ///     type Product { price(currency: Currency): Money discounts: [Money!] }
+    ///     type Money { amount: Float currency: Currency }
+    ///     enum Currency { EUR USD }
+    ///     "#,
+    ///     "schema.graphql",
+    /// )
+    /// .unwrap();
+    /// let graph = schema.type_references();
+    /// let sources: Vec<_> = graph
+    ///     .references_to("Money")
+    ///     .map(|reference| reference.source.to_string())
+    ///     .collect();
+    /// assert_eq!(sources, ["Product.price", "Product.discounts"]);
+    ///
+    /// let currency: Vec<_> = graph
+    ///     .references_to("Currency")
+    ///     .map(|reference| (reference.source.to_string(), reference.kind))
+    ///     .collect();
+    /// assert_eq!(
+    ///     currency,
+    ///     [
+    ///         ("Product.price(currency:)".to_owned(), TypeReferenceKind::ArgumentType),
+    ///         ("Money.currency".to_owned(), TypeReferenceKind::FieldType),
+    ///     ]
+    /// );
+    /// ```
+    pub fn type_references(&self) -> TypeReferenceGraph {
+        let mut graph = TypeReferenceGraph::default();
+        for (type_name, ty) in &self.types {
+            if ty.is_built_in() {
+                continue;
+            }
+            let mut add = |source: SchemaCoordinate, target: &NamedType, kind| {
+                graph.add(
+                    TypeReference {
+                        source,
+                        target: target.clone(),
+                        kind,
+                    },
+                    Some(type_name),
+                    None,
+                )
+            };
+            let type_coordinate = || {
+                SchemaCoordinate::Type(TypeCoordinate {
+                    ty: type_name.clone(),
+                })
+            };
+            let (interfaces, fields) = match ty {
+                ExtendedType::Object(def) => (&def.implements_interfaces, &def.fields),
+                ExtendedType::Interface(def) => (&def.implements_interfaces, &def.fields),
+                ExtendedType::Union(def) => {
+                    for member in &def.members {
+                        add(
+                            type_coordinate(),
+                            &member.name,
+                            TypeReferenceKind::UnionMember,
+                        )
+                    }
+                    continue;
+                }
+                ExtendedType::InputObject(def) => {
+                    for (field_name, field) in &def.fields {
+                        let source = TypeAttributeCoordinate {
+                            ty: type_name.clone(),
+                            attribute: field_name.clone(),
+                        };
+                        add(
+                            source.into(),
+                            field.ty.inner_named_type(),
+                            TypeReferenceKind::InputFieldType,
+                        )
+                    }
+                    continue;
+                }
+                ExtendedType::Scalar(_) | ExtendedType::Enum(_) => continue,
+            };
+            for interface in interfaces {
+                add(
+                    type_coordinate(),
+                    &interface.name,
+                    TypeReferenceKind::ImplementedInterface,
+                )
+            }
+            for (field_name, field) in fields {
+                let source = TypeAttributeCoordinate {
+                    ty: type_name.clone(),
+                    attribute: field_name.clone(),
+                };
+                add(
+                    source.into(),
+                    field.ty.inner_named_type(),
+                    TypeReferenceKind::FieldType,
+                );
+                for argument in &field.arguments {
+                    let source = FieldArgumentCoordinate {
+                        ty: type_name.clone(),
+                        field: field_name.clone(),
+                        argument: argument.name.clone(),
+                    };
+                    add(
+                        source.into(),
+                        argument.ty.inner_named_type(),
+                        TypeReferenceKind::ArgumentType,
+                    )
+                }
+            }
+        }
+        for (directive_name, definition) in &self.directive_definitions {
+            if definition.is_built_in() {
+                continue;
+            }
+            for argument in &definition.arguments {
+                let source = DirectiveArgumentCoordinate {
+                    directive: directive_name.clone(),
+                    argument: argument.name.clone(),
+                };
+                graph.add(
+                    TypeReference {
+                        source: source.into(),
+                        target: argument.ty.inner_named_type().clone(),
+                        kind: TypeReferenceKind::DirectiveArgumentType,
+                    },
+                    None,
+                    Some(directive_name),
+                )
+            }
+        }
+        graph
+    }
+}
+
+impl TypeReferenceGraph {
+    fn add(
+        &mut self,
+        reference: TypeReference,
+        source_type: Option<&NamedType>,
+        source_directive: Option<&Name>,
+    ) {
+        let index = self.references.len();
+        self.by_target
+            .entry(reference.target.clone())
+            .or_default()
+            .push(index);
+        if let Some(name) = source_type {
+            self.by_source_type
+                .entry(name.clone())
+                .or_default()
+                .push(index);
+        }
+        if let Some(name) = source_directive {
+            self.by_source_directive
+                .entry(name.clone())
+                .or_default()
+                .push(index);
+        }
+        self.references.push(reference)
+    }
+
+    fn lookup<'a>(
+        &'a self,
+        indices: Option<&'a Vec<usize>>,
+    ) -> impl Iterator<Item = &'a TypeReference> + 'a {
+        indices
+            .into_iter()
+            .flatten()
+            .map(|&index| &self.references[index])
+    }
+
+    /// Returns an iterator of all references, in schema definition order
+    pub fn iter(&self) -> impl Iterator<Item = &TypeReference> {
+        self.references.iter()
+    }
+
+    /// Returns an iterator of references to the type with the given name
+    pub fn references_to(&self, ty: &str) -> impl Iterator<Item = &TypeReference> {
+        self.lookup(self.by_target.get(ty))
+    }
+
+    /// Returns an iterator of references made by the definition of the given type,
+    /// including by its fields and their arguments
+    pub fn references_from_type(&self, ty: &str) -> impl Iterator<Item = &TypeReference> {
+        self.lookup(self.by_source_type.get(ty))
+    }
+
+    /// Returns an iterator of references made by the arguments
+    /// of the directive definition with the given name
+    pub fn references_from_directive(
+        &self,
+        directive: &str,
+    ) -> impl Iterator<Item = &TypeReference> {
+        self.lookup(self.by_source_directive.get(directive))
+    }
+
+    /// Returns whether any schema element refers to the type with the given name
+    pub fn is_referenced(&self, ty: &str) -> bool {
+        self.by_target.contains_key(ty)
+    }
+}
+
+impl fmt::Display for TypeReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} -> {}", self.source, self.target)
+    }
+}
@@ -0,0 +1,260 @@
+use crate::collections::HashMap;
+use crate::collections::IndexMap;
+use crate::collections::IndexSet;
+use crate::schema::ComponentName;
+use crate::schema::ExtendedType;
+use crate::schema::InputValueDefinition;
+use crate::schema::NamedType;
+use crate::schema::Type;
+use crate::validation::Valid;
+use crate::Node;
+use crate::Schema;
+
+/// The types renamed by [`rename_types`], as a mapping from old to new names
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TypeRenames {
+    renames: IndexMap<NamedType, NamedType>,
+}
+
+/// A renaming that [`rename_types`] cannot apply
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum RenameTypesError {
+    /// The new name of a type starts with `__`, which is reserved for introspection
+    #[error("cannot rename `{ty}` to `{new_name}`: names starting with `__` are reserved")]
+    ReservedName { ty: NamedType, new_name: NamedType },
+    /// Two types would have the same name after renaming
+    #[error("cannot rename `{ty}` to `{new_name}`: type `{other}` would have the same name")]
+    Conflict {
+        ty: NamedType,
+        new_name: NamedType,
+        other: NamedType,
+    },
+}
+
+/// Returns a copy of `schema` where types are renamed by `rename`, and the mapping that was applied.
+///
+/// `rename` is called with the name of each type that is not built-in,
+/// and returns the new name of that type or `None` to keep it.
+/// References to renamed types are rewritten everywhere:
+/// in root operations, field and argument types, input fields, implemented interfaces,
+/// union members, and directive argument types.
+/// Descriptions, directive applications, and the order of definitions are unchanged.
+///
+/// This is the core of schema stitching setups, where types of each schema are prefixed
+/// before merging to avoid name collisions.
+/// Renaming fails instead of merging types if two of them would have the same name.
+///
+/// ```rust
+/// use apollo_compiler::ast::OperationType;
+/// use apollo_compiler::schema;
+/// use apollo_compiler::Name;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(
+///     r#"
+///     type Query { invoice(id: ID!): Invoice }
+///     type Invoice { id: ID! total: Money }
+///     type Money { amount: Float }
+///     "#,
+///     "billing.graphql",
+/// )
+/// .unwrap();
+/// let prefix = |name: &Name| Name::new(&format!("Billing_{name}")).ok();
+/// let (renamed, renames) = schema::rename_types(&schema, prefix).unwrap();
+/// assert_eq!(renames.get("Invoice").unwrap(), "Billing_Invoice");
+/// let field = renamed.type_field("Billing_Query", "invoice").unwrap();
+/// assert_eq!(field.ty.to_string(), "Billing_Invoice");
+/// let query = renamed.root_operation(OperationType::Query).unwrap();
+/// assert_eq!(query, "Billing_Query");
+/// ```
+pub fn rename_types(
+    schema: &Valid<Schema>,
+    mut rename: impl FnMut(&NamedType) -> Option<NamedType>,
+) -> Result<(Valid<Schema>, TypeRenames), RenameTypesError> {
+    let mut renames = IndexMap::<NamedType, NamedType>::default();
+    for (name, ty) in &schema.types {
+        if ty.is_built_in() {
+            continue;
+        }
+        if let Some(new_name) = rename(name) {
+            if new_name != *name {
+                if new_name.starts_with("__") {
+                    return Err(RenameTypesError::ReservedName {
+                        ty: name.clone(),
+                        new_name,
+                    });
+                }
+                renames.insert(name.clone(), new_name);
+            }
+        }
+    }
+    let renames = TypeRenames { renames };
+
+    let mut names = HashMap::<&NamedType, &NamedType>::default();
+    for name in schema.types.keys() {
+        let new_name = renames.new_name(name);
+        if let Some(other) = names.insert(new_name, name) {
+            // Report the renamed type rather than one that kept its name
+            let (ty, other) = if renames.renames.contains_key(name) {
+                (name, other)
+            } else {
+                (other, name)
+            };
+            return Err(RenameTypesError::Conflict {
+                ty: ty.clone(),
+                new_name: new_name.clone(),
+                other: other.clone(),
+            });
+        }
+    }
+
+    let mut renamed = Schema::clone(schema);
+    if renames.renames.is_empty() {
+        return Ok((Valid::assume_valid(renamed), renames));
+    }
+    let schema_definition = renamed.schema_definition.make_mut();
+    for root in [
+        &mut schema_definition.query,
+        &mut schema_definition.mutation,
+        &mut schema_definition.subscription,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        root.name = renames.new_name(&root.name).clone();
+    }
+    for definition in renamed.directive_definitions.values_mut() {
+        if definition
+            .arguments
+            .iter()
+            .any(|argument| renames.affects(&argument.ty))
+        {
+            renames.rename_arguments(&mut definition.make_mut().arguments)
+        }
+    }
+    renamed.types = std::mem::take(&mut renamed.types)
+        .into_iter()
+        .map(|(name, mut ty)| {
+            renames.rename_in_type(&mut ty);
+            (renames.new_name(&name).clone(), ty)
+        })
+        .collect();
+    // Renaming consistently cannot make a valid schema invalid
+    Ok((Valid::assume_valid(renamed), renames))
+}
+
+impl TypeRenames {
+    /// Returns the new name of the given type, or `None` if it was not renamed
+    pub fn get(&self, old_name: &str) -> Option<&NamedType> {
+        self.renames.get(old_name)
+    }
+
+    /// Returns the original name of the type that now has the given name,
+    /// or `None` if no type was renamed to it
+    pub fn original_name(&self, new_name: &str) -> Option<&NamedType> {
+        self.renames
+            .iter()
+            .find(|(_, renamed)| *renamed == new_name)
+            .map(|(old_name, _)| old_name)
+    }
+
+    /// Returns an iterator of `(old_name, new_name)` pairs, in schema order
+    pub fn iter(&self) -> impl Iterator<Item = (&NamedType, &NamedType)> {
+        self.renames.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.renames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.renames.is_empty()
+    }
+
+    fn new_name<'a>(&'a self, name: &'a NamedType) -> &'a NamedType {
+        self.renames.get(name).unwrap_or(name)
+    }
+
+    fn affects(&self, ty: &Type) -> bool {
+        self.renames.contains_key(ty.inner_named_type())
+    }
+
+    fn rename_type(&self, ty: &Type) -> Type {
+        match ty {
+            Type::Named(name) => Type::Named(self.new_name(name).clone()),
+            Type::NonNullNamed(name) => Type::NonNullNamed(self.new_name(name).clone()),
+            Type::List(inner) => Type::List(Box::new(self.rename_type(inner))),
+            Type::NonNullList(inner) => Type::NonNullList(Box::new(self.rename_type(inner))),
+        }
+    }
+
+    fn rename_arguments(&self, arguments: &mut [Node<InputValueDefinition>]) {
+        for argument in arguments {
+            if self.affects(&argument.ty) {
+                let argument = argument.make_mut();
+                argument.ty = argument.ty.same_location(self.rename_type(&argument.ty));
+            }
+        }
+    }
+
+    fn rename_names(&self, names: &mut IndexSet<ComponentName>) {
+        *names = std::mem::take(names)
+            .into_iter()
+            .map(|name| ComponentName {
+                name: self.new_name(&name.name).clone(),
+                origin: name.origin,
+            })
+            .collect();
+    }
+
+    fn rename_in_type(&self, ty: &mut ExtendedType) {
+        match ty {
+            ExtendedType::Scalar(def) => {
+                if let Some(new_name) = self.get(&def.name) {
+                    def.make_mut().name = new_name.clone()
+                }
+            }
+            ExtendedType::Object(def) => {
+                let def = def.make_mut();
+                def.name = self.new_name(&def.name).clone();
+                self.rename_names(&mut def.implements_interfaces);
+                for field in def.fields.values_mut() {
+                    let field = field.make_mut();
+                    field.ty = self.rename_type(&field.ty);
+                    self.rename_arguments(&mut field.arguments)
+                }
+            }
+            ExtendedType::Interface(def) => {
+                let def = def.make_mut();
+                def.name = self.new_name(&def.name).clone();
+                self.rename_names(&mut def.implements_interfaces);
+                for field in def.fields.values_mut() {
+                    let field = field.make_mut();
+                    field.ty = self.rename_type(&field.ty);
+                    self.rename_arguments(&mut field.arguments)
+                }
+            }
+            ExtendedType::Union(def) => {
+                let def = def.make_mut();
+                def.name = self.new_name(&def.name).clone();
+                self.rename_names(&mut def.members);
+            }
+            ExtendedType::Enum(def) => {
+                if let Some(new_name) = self.get(&def.name) {
+                    def.make_mut().name = new_name.clone()
+                }
+            }
+            ExtendedType::InputObject(def) => {
+                let def = def.make_mut();
+                def.name = self.new_name(&def.name).clone();
+                for field in def.fields.values_mut() {
+                    if self.affects(&field.ty) {
+                        let field = field.make_mut();
+                        field.ty = field.ty.same_location(self.rename_type(&field.ty));
+                    }
+                }
+            }
+        }
+    }
+}
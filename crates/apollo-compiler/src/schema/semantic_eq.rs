@@ -0,0 +1,544 @@
+use crate::ast;
+use crate::ast::OperationType;
+use crate::ast::Value;
+use crate::collections::IndexMap;
+use crate::coordinate::DirectiveArgumentCoordinate;
+use crate::coordinate::DirectiveCoordinate;
+use crate::coordinate::FieldArgumentCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::coordinate::TypeCoordinate;
+use crate::schema::Component;
+use crate::schema::Directive;
+use crate::schema::DirectiveDefinition;
+use crate::schema::DirectiveList;
+use crate::schema::ExtendedType;
+use crate::schema::FieldDefinition;
+use crate::schema::InputValueDefinition;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+use std::fmt;
+
+/// Options for [`Schema::semantic_eq_with_options`] and [`Schema::semantic_differences`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemanticEqOptions {
+    descriptions: bool,
+    max_differences: usize,
+}
+
+impl Default for SemanticEqOptions {
+    fn default() -> Self {
+        Self {
+            descriptions: true,
+            max_differences: 10,
+        }
+    }
+}
+
+impl SemanticEqOptions {
+    /// Returns the default options, which compare descriptions and report up to 10 differences
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether schemas that only differ by descriptions are different.
+    /// Defaults to true.
+    pub fn descriptions(mut self, enable: bool) -> Self {
+        self.descriptions = enable;
+        self
+    }
+
+    /// How many differences [`Schema::semantic_differences`] reports at most.
+    /// Defaults to 10.
+    pub fn max_differences(mut self, max: usize) -> Self {
+        self.max_differences = max;
+        self
+    }
+}
+
+/// A difference between two schemas found by [`Schema::semantic_differences`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemanticDifference {
+    /// The element that differs, or `None` for the `schema` definition
+    pub coordinate: Option<SchemaCoordinate>,
+    pub kind: SemanticDifferenceKind,
+}
+
+/// What differs in a [`SemanticDifference`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SemanticDifferenceKind {
+    /// The element is only in the schema `semantic_differences` is called on
+    OnlyInSelf,
+    /// The element is only in the schema passed to `semantic_differences`
+    OnlyInOther,
+    /// A type is a different kind of type, such as an object type in one schema
+    /// and an interface in the other
+    TypeKind,
+    Description,
+    /// Directive applications, or their arguments
+    Directives,
+    /// The interfaces implemented by an object type or interface
+    Interfaces,
+    UnionMembers,
+    /// The type of a field, argument, or input field
+    Type,
+    DefaultValue,
+    /// Whether a directive definition is repeatable
+    Repeatable,
+    DirectiveLocations,
+    RootOperation(OperationType),
+}
+
+impl Schema {
+    /// Returns whether this schema has the same meaning as `other`.
+    ///
+    /// The comparison ignores source locations and formatting,
+    /// the order of definitions, fields, arguments, input fields, and enum values,
+    /// the order of arguments and input object fields in directive applications and values,
+    /// and whether components are in type extensions or in the definition they extend.
+    /// The order of directive applications on the same element is significant.
+    ///
+    /// ```rust
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse("type Query { a(x: Int, y: Int): Int, b: String }", "a.graphql")
+    ///     .unwrap();
+    /// let reordered = Schema::parse(
+    ///     "type Query {
+    ///         b: String
+    ///     }
+    ///     extend type Query { a(y: Int, x: Int): Int }",
+    ///     "b.graphql",
+    /// )
+    /// .unwrap();
+    /// assert!(schema.semantic_eq(&reordered));
+    ///
+    /// let changed = Schema::parse("type Query { a(x: Int, y: Int): Int, b: ID }", "c.graphql")
+    ///     .unwrap();
+    /// assert!(!schema.semantic_eq(&changed));
+    /// ```
+    pub fn semantic_eq(&self, other: &Schema) -> bool {
+        self.semantic_eq_with_options(other, &SemanticEqOptions::default())
+    }
+
+    /// Like [`semantic_eq`][Self::semantic_eq], with configuration of what is ignored
+    pub fn semantic_eq_with_options(&self, other: &Schema, options: &SemanticEqOptions) -> bool {
+        let options = options.clone().max_differences(1);
+        self.semantic_differences(other, &options).is_empty()
+    }
+
+    /// Returns the first differences between this schema and `other`,
+    /// up to [`max_differences`][SemanticEqOptions::max_differences],
+    /// or an empty vector if the schemas are [semantically equal][Self::semantic_eq].
+    ///
+    /// Differences are reported for the most specific element that differs,
+    /// in the order of the `schema` definition, directive definitions, then types,
+    /// each in the order of this schema followed by elements only in `other`.
+    ///
+    /// ```rust
+    /// use apollo_compiler::schema::SemanticEqOptions;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let old = Schema::parse("type Query { a: Int, b: String }", "old.graphql").unwrap();
+    /// let new = Schema::parse(
+    ///     r#"type Query { "The A" a: Int!, c: Int }"#,
+    ///     "new.graphql",
+    /// )
+    /// .unwrap();
+    /// let options = SemanticEqOptions::new().descriptions(false);
+    /// let differences: Vec<_> = old
+    ///     .semantic_differences(&new, &options)
+    ///     .iter()
+    ///     .map(|difference| difference.to_string())
+    ///     .collect();
+    /// assert_eq!(
+    ///     differences,
+    ///     [
+    ///         "`Query.a` has a different type",
+    ///         "`Query.b` is only in the first schema",
+    ///         "`Query.c` is only in the second schema",
+    ///     ]
+    /// );
+    /// ```
+    pub fn semantic_differences(
+        &self,
+        other: &Schema,
+        options: &SemanticEqOptions,
+    ) -> Vec<SemanticDifference> {
+        let mut comparer = Comparer {
+            options,
+            differences: Vec::new(),
+        };
+        comparer.compare_schemas(self, other);
+        comparer.differences
+    }
+}
+
+struct Comparer<'a> {
+    options: &'a SemanticEqOptions,
+    differences: Vec<SemanticDifference>,
+}
+
+impl Comparer<'_> {
+    fn is_full(&self) -> bool {
+        self.differences.len() >= self.options.max_differences
+    }
+
+    fn push(&mut self, coordinate: Option<SchemaCoordinate>, kind: SemanticDifferenceKind) {
+        if !self.is_full() {
+            self.differences
+                .push(SemanticDifference { coordinate, kind })
+        }
+    }
+
+    fn compare_schemas(&mut self, left: &Schema, right: &Schema) {
+        let (left_def, right_def) = (&left.schema_definition, &right.schema_definition);
+        self.compare_description(
+            None,
+            left_def.description.as_ref(),
+            right_def.description.as_ref(),
+        );
+        self.compare_schema_directives(None, &left_def.directives, &right_def.directives);
+        for (operation_type, left_root, right_root) in [
+            (OperationType::Query, &left_def.query, &right_def.query),
+            (
+                OperationType::Mutation,
+                &left_def.mutation,
+                &right_def.mutation,
+            ),
+            (
+                OperationType::Subscription,
+                &left_def.subscription,
+                &right_def.subscription,
+            ),
+        ] {
+            if left_root.as_ref().map(|root| &root.name)
+                != right_root.as_ref().map(|root| &root.name)
+            {
+                self.push(None, SemanticDifferenceKind::RootOperation(operation_type))
+            }
+        }
+
+        self.compare_maps(
+            &left.directive_definitions,
+            &right.directive_definitions,
+            |name| {
+                DirectiveCoordinate {
+                    directive: name.clone(),
+                }
+                .into()
+            },
+            Self::compare_directive_definitions,
+        );
+        self.compare_maps(
+            &left.types,
+            &right.types,
+            |name| TypeCoordinate { ty: name.clone() }.into(),
+            Self::compare_types,
+        );
+    }
+
+    /// Compare elements with the same name, and report elements missing on either side
+    fn compare_maps<T>(
+        &mut self,
+        left: &IndexMap<Name, T>,
+        right: &IndexMap<Name, T>,
+        coordinate: impl Fn(&Name) -> SchemaCoordinate,
+        mut compare: impl FnMut(&mut Self, SchemaCoordinate, &T, &T),
+    ) {
+        for (name, left_item) in left {
+            if self.is_full() {
+                return;
+            }
+            match right.get(name) {
+                Some(right_item) => compare(self, coordinate(name), left_item, right_item),
+                None => self.push(Some(coordinate(name)), SemanticDifferenceKind::OnlyInSelf),
+            }
+        }
+        for name in right.keys() {
+            if !left.contains_key(name) {
+                self.push(Some(coordinate(name)), SemanticDifferenceKind::OnlyInOther)
+            }
+        }
+    }
+
+    fn compare_directive_definitions(
+        &mut self,
+        coordinate: SchemaCoordinate,
+        left: &Node<DirectiveDefinition>,
+        right: &Node<DirectiveDefinition>,
+    ) {
+        let coordinate = Some(coordinate);
+        self.compare_description(
+            coordinate.clone(),
+            left.description.as_ref(),
+            right.description.as_ref(),
+        );
+        self.compare_arguments(&left.arguments, &right.arguments, |argument| {
+            DirectiveArgumentCoordinate {
+                directive: left.name.clone(),
+                argument: argument.clone(),
+            }
+            .into()
+        });
+        if left.repeatable != right.repeatable {
+            self.push(coordinate.clone(), SemanticDifferenceKind::Repeatable)
+        }
+        if left.locations.len() != right.locations.len()
+            || !left
+                .locations
+                .iter()
+                .all(|location| right.locations.contains(location))
+        {
+            self.push(coordinate, SemanticDifferenceKind::DirectiveLocations)
+        }
+    }
+
+    fn compare_types(
+        &mut self,
+        coordinate: SchemaCoordinate,
+        left: &ExtendedType,
+        right: &ExtendedType,
+    ) {
+        let coordinate = Some(coordinate);
+        if std::mem::discriminant(left) != std::mem::discriminant(right) {
+            self.push(coordinate, SemanticDifferenceKind::TypeKind);
+            return;
+        }
+        self.compare_description(coordinate.clone(), left.description(), right.description());
+        self.compare_schema_directives(coordinate.clone(), left.directives(), right.directives());
+        match (left, right) {
+            (ExtendedType::Object(left), ExtendedType::Object(right)) => {
+                if left.implements_interfaces != right.implements_interfaces {
+                    self.push(coordinate, SemanticDifferenceKind::Interfaces)
+                }
+                self.compare_fields(&left.name, &left.fields, &right.fields)
+            }
+            (ExtendedType::Interface(left), ExtendedType::Interface(right)) => {
+                if left.implements_interfaces != right.implements_interfaces {
+                    self.push(coordinate, SemanticDifferenceKind::Interfaces)
+                }
+                self.compare_fields(&left.name, &left.fields, &right.fields)
+            }
+            (ExtendedType::Union(left), ExtendedType::Union(right))
+                if left.members != right.members =>
+            {
+                self.push(coordinate, SemanticDifferenceKind::UnionMembers)
+            }
+            (ExtendedType::Enum(left), ExtendedType::Enum(right)) => self.compare_maps(
+                &left.values,
+                &right.values,
+                |value| attribute_coordinate(&left.name, value),
+                |this, coordinate, left, right| {
+                    let coordinate = Some(coordinate);
+                    this.compare_description(
+                        coordinate.clone(),
+                        left.description.as_ref(),
+                        right.description.as_ref(),
+                    );
+                    this.compare_directives(coordinate, &left.directives, &right.directives);
+                },
+            ),
+            (ExtendedType::InputObject(left), ExtendedType::InputObject(right)) => self
+                .compare_maps(
+                    &left.fields,
+                    &right.fields,
+                    |field| attribute_coordinate(&left.name, field),
+                    |this, coordinate, left, right| {
+                        this.compare_input_values(coordinate, left, right)
+                    },
+                ),
+            _ => {}
+        }
+    }
+
+    fn compare_fields(
+        &mut self,
+        type_name: &Name,
+        left: &IndexMap<Name, Component<FieldDefinition>>,
+        right: &IndexMap<Name, Component<FieldDefinition>>,
+    ) {
+        self.compare_maps(
+            left,
+            right,
+            |field| attribute_coordinate(type_name, field),
+            |this, coordinate, left, right| {
+                let coordinate = Some(coordinate);
+                this.compare_description(
+                    coordinate.clone(),
+                    left.description.as_ref(),
+                    right.description.as_ref(),
+                );
+                if left.ty != right.ty {
+                    this.push(coordinate.clone(), SemanticDifferenceKind::Type)
+                }
+                this.compare_arguments(&left.arguments, &right.arguments, |argument| {
+                    FieldArgumentCoordinate {
+                        ty: type_name.clone(),
+                        field: left.name.clone(),
+                        argument: argument.clone(),
+                    }
+                    .into()
+                });
+                this.compare_directives(coordinate, &left.directives, &right.directives);
+            },
+        )
+    }
+
+    fn compare_arguments(
+        &mut self,
+        left: &[Node<InputValueDefinition>],
+        right: &[Node<InputValueDefinition>],
+        coordinate: impl Fn(&Name) -> SchemaCoordinate,
+    ) {
+        let by_name = |arguments: &[Node<InputValueDefinition>]| -> IndexMap<Name, Node<_>> {
+            arguments
+                .iter()
+                .map(|argument| (argument.name.clone(), argument.clone()))
+                .collect()
+        };
+        self.compare_maps(
+            &by_name(left),
+            &by_name(right),
+            coordinate,
+            |this, coordinate, left, right| this.compare_input_values(coordinate, left, right),
+        )
+    }
+
+    fn compare_input_values(
+        &mut self,
+        coordinate: SchemaCoordinate,
+        left: &InputValueDefinition,
+        right: &InputValueDefinition,
+    ) {
+        let coordinate = Some(coordinate);
+        self.compare_description(
+            coordinate.clone(),
+            left.description.as_ref(),
+            right.description.as_ref(),
+        );
+        if left.ty != right.ty {
+            self.push(coordinate.clone(), SemanticDifferenceKind::Type)
+        }
+        let default_values_eq = match (&left.default_value, &right.default_value) {
+            (Some(left), Some(right)) => value_eq(left, right),
+            (left, right) => left.is_none() && right.is_none(),
+        };
+        if !default_values_eq {
+            self.push(coordinate.clone(), SemanticDifferenceKind::DefaultValue)
+        }
+        self.compare_directives(coordinate, &left.directives, &right.directives);
+    }
+
+    fn compare_description(
+        &mut self,
+        coordinate: Option<SchemaCoordinate>,
+        left: Option<&Node<str>>,
+        right: Option<&Node<str>>,
+    ) {
+        if self.options.descriptions && left != right {
+            self.push(coordinate, SemanticDifferenceKind::Description)
+        }
+    }
+
+    fn compare_schema_directives(
+        &mut self,
+        coordinate: Option<SchemaCoordinate>,
+        left: &DirectiveList,
+        right: &DirectiveList,
+    ) {
+        let equal = left.len() == right.len()
+            && left
+                .iter()
+                .zip(right.iter())
+                .all(|(left, right)| directive_eq(left, right));
+        if !equal {
+            self.push(coordinate, SemanticDifferenceKind::Directives)
+        }
+    }
+
+    fn compare_directives(
+        &mut self,
+        coordinate: Option<SchemaCoordinate>,
+        left: &ast::DirectiveList,
+        right: &ast::DirectiveList,
+    ) {
+        let equal = left.len() == right.len()
+            && left
+                .iter()
+                .zip(right.iter())
+                .all(|(left, right)| directive_eq(left, right));
+        if !equal {
+            self.push(coordinate, SemanticDifferenceKind::Directives)
+        }
+    }
+}
+
+fn attribute_coordinate(ty: &Name, attribute: &Name) -> SchemaCoordinate {
+    TypeAttributeCoordinate {
+        ty: ty.clone(),
+        attribute: attribute.clone(),
+    }
+    .into()
+}
+
+fn directive_eq(left: &Directive, right: &Directive) -> bool {
+    left.name == right.name
+        && left.arguments.len() == right.arguments.len()
+        && left.arguments.iter().all(|left_arg| {
+            right.arguments.iter().any(|right_arg| {
+                left_arg.name == right_arg.name && value_eq(&left_arg.value, &right_arg.value)
+            })
+        })
+}
+
+/// Like `==`, except that input object fields may be in a different order
+fn value_eq(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::List(left), Value::List(right)) => {
+            left.len() == right.len()
+                && left
+                    .iter()
+                    .zip(right)
+                    .all(|(left, right)| value_eq(left, right))
+        }
+        (Value::Object(left), Value::Object(right)) => {
+            left.len() == right.len()
+                && left.iter().all(|(name, left_value)| {
+                    right.iter().any(|(right_name, right_value)| {
+                        name == right_name && value_eq(left_value, right_value)
+                    })
+                })
+        }
+        _ => left == right,
+    }
+}
+
+impl fmt::Display for SemanticDifference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.coordinate {
+            Some(coordinate) => write!(f, "`{coordinate}`")?,
+            None => f.write_str("the schema definition")?,
+        }
+        match self.kind {
+            SemanticDifferenceKind::OnlyInSelf => f.write_str(" is only in the first schema"),
+            SemanticDifferenceKind::OnlyInOther => f.write_str(" is only in the second schema"),
+            SemanticDifferenceKind::TypeKind => f.write_str(" is a different kind of type"),
+            SemanticDifferenceKind::Description => f.write_str(" has a different description"),
+            SemanticDifferenceKind::Directives => {
+                f.write_str(" has different directive applications")
+            }
+            SemanticDifferenceKind::Interfaces => f.write_str(" implements different interfaces"),
+            SemanticDifferenceKind::UnionMembers => f.write_str(" has different members"),
+            SemanticDifferenceKind::Type => f.write_str(" has a different type"),
+            SemanticDifferenceKind::DefaultValue => f.write_str(" has a different default value"),
+            SemanticDifferenceKind::Repeatable => f.write_str(" is repeatable in only one schema"),
+            SemanticDifferenceKind::DirectiveLocations => f.write_str(" has different locations"),
+            SemanticDifferenceKind::RootOperation(operation_type) => {
+                write!(f, " has a different {operation_type} root operation type")
+            }
+        }
+    }
+}
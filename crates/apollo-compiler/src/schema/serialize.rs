@@ -8,22 +8,97 @@ impl Schema {
     pub(crate) fn serialize_impl(&self, state: &mut State) -> fmt::Result {
         // TODO: avoid allocating temporary AST nodes?
         // it would ~duplicate large parts of ast/serialize.rs
-        top_level(state, self.to_ast(), |state, def| def.serialize_impl(state))
+        let built_ins = state.config().built_ins;
+        if state.config().sorted {
+            let mut definitions: Vec<_> = self.to_ast_impl(built_ins).collect();
+            sort_definitions(&mut definitions);
+            top_level(state, definitions, |state, def| def.serialize_impl(state))
+        } else {
+            top_level(state, self.to_ast_impl(built_ins), |state, def| {
+                def.serialize_impl(state)
+            })
+        }
     }
 
     pub(crate) fn to_ast(&self) -> impl Iterator<Item = ast::Definition> + '_ {
+        self.to_ast_impl(false)
+    }
+
+    /// Converts this schema back to an AST document,
+    /// with separate definitions for the `schema` definition and types and for their extensions.
+    ///
+    /// Each field, directive application, or other component is part of the definition
+    /// or extension that originally contributed it.
+    /// Definitions and extensions parsed from source files are in their original order:
+    /// by source file in the order they were added to the schema, then by position in that file.
+    /// Definitions created programmatically keep their position in schema order.
+    /// Built-in definitions are omitted,
+    /// but the document's `sources` are all of the schema's, including that of built-ins,
+    /// so that the location of any definition or extension can be resolved.
+    ///
+    /// This makes parsing, building a schema, then printing its AST stable,
+    /// as needed for example by formatters.
+    ///
+    /// ```rust
+    /// use apollo_compiler::Schema;
+    ///
+    /// let input = "type Query {
+    ///   a: Int
+    /// }
+    ///
+    /// scalar Date
+    ///
+    /// extend type Query {
+    ///   b: Date
+    /// }
+    /// ";
+    /// let schema = Schema::parse_and_validate(input, "schema.graphql").unwrap();
+    /// assert_eq!(schema.to_document().to_string(), input);
+    /// ```
+    pub fn to_document(&self) -> ast::Document {
+        let mut definitions: Vec<_> = self.to_ast().collect();
+        let source_position = |definition: &ast::Definition| {
+            let location = definition.location()?;
+            let file_index = self.sources.get_index_of(&location.file_id());
+            // Files missing from `sources` go last, in parsing order
+            Some((
+                file_index.unwrap_or(usize::MAX),
+                location.file_id(),
+                location.offset(),
+            ))
+        };
+        // Only reorder definitions that have a source location
+        let slots: Vec<usize> = (0..definitions.len())
+            .filter(|&index| source_position(&definitions[index]).is_some())
+            .collect();
+        let mut located: Vec<_> = slots
+            .iter()
+            .map(|&index| definitions[index].clone())
+            .collect();
+        located.sort_by_key(|definition| source_position(definition));
+        for (index, definition) in slots.into_iter().zip(located) {
+            definitions[index] = definition
+        }
+        ast::Document {
+            sources: self.sources.clone(),
+            definitions,
+        }
+    }
+
+    fn to_ast_impl(&self, built_ins: bool) -> impl Iterator<Item = ast::Definition> + '_ {
         self.schema_definition
             .to_ast(&self.types)
             .chain(
                 self.directive_definitions
                     .values()
-                    .filter(|def| !def.is_built_in())
+                    .filter(move |def| built_ins || !def.is_built_in())
                     .map(|def| ast::Definition::DirectiveDefinition(def.clone())),
             )
-            .chain(self.types.values().flat_map(|def| {
+            .chain(self.types.values().flat_map(move |def| {
                 let mut iter = def.to_ast();
-                // skip the definition of built-in scalars but keep extensions if any
-                if def.is_built_in() {
+                // skip the definition of built-in scalars but keep extensions if any,
+                // and skip the empty definition of a type that only has adopted extensions
+                if (def.is_built_in() && !built_ins) || def.is_only_extensions() {
                     iter.next();
                 }
                 iter
@@ -44,9 +119,14 @@ impl Node<SchemaDefinition> {
             subscription,
         } = &**self;
         let extensions = self.extensions();
+        // Extensions are printed separately. Only skip the definition if what is left
+        // matches the implicit definition, so that parsing the output again
+        // adds the extensions to the same root operations.
+        // This keeps the `extend schema @link(…)` of a subgraph without a `schema` definition.
         let implict = description.is_none()
-            && directives.is_empty()
-            && extensions.is_empty()
+            && directives
+                .iter()
+                .all(|directive| directive.origin.extension_id().is_some())
             && [
                 (query, ast::OperationType::Query),
                 (mutation, ast::OperationType::Mutation),
@@ -60,8 +140,12 @@ impl Node<SchemaDefinition> {
                 let has_object = types
                     .get(&default_type_name).is_some_and(|def| def.is_object());
                 let implicit_root_operation = has_object.then_some(&default_type_name);
-                // What we have
-                let actual_root_operation = root_operation.as_ref().map(|r| &r.name);
+                // What we have, outside of extensions
+                let actual_root_operation = match root_operation {
+                    Some(r) if r.origin.extension_id().is_some() => return true,
+                    Some(r) => Some(&r.name),
+                    None => None,
+                };
                 // Only allow an implicit `schema` definition if they match
                 actual_root_operation == implicit_root_operation
             })
@@ -113,6 +197,44 @@ impl ExtendedType {
         }
     }
 
+    /// Returns whether this type was created by
+    /// [`adopt_orphan_extensions`][crate::schema::SchemaBuilder::adopt_orphan_extensions]
+    /// for extensions without a definition, so that its definition is empty
+    fn is_only_extensions(&self) -> bool {
+        fn all_extensions<'a>(mut origins: impl Iterator<Item = &'a ComponentOrigin>) -> bool {
+            origins.all(|origin| origin.extension_id().is_some())
+        }
+        let directives = self.directives().iter().map(|d| &d.origin);
+        self.location().is_none()
+            && self.description().is_none()
+            && all_extensions(directives)
+            && match self {
+                ExtendedType::Scalar(ty) => !ty.extensions().is_empty(),
+                ExtendedType::Object(ty) => {
+                    !ty.extensions().is_empty()
+                        && all_extensions(ty.implements_interfaces.iter().map(|i| &i.origin))
+                        && all_extensions(ty.fields.values().map(|f| &f.origin))
+                }
+                ExtendedType::Interface(ty) => {
+                    !ty.extensions().is_empty()
+                        && all_extensions(ty.implements_interfaces.iter().map(|i| &i.origin))
+                        && all_extensions(ty.fields.values().map(|f| &f.origin))
+                }
+                ExtendedType::Union(ty) => {
+                    !ty.extensions().is_empty()
+                        && all_extensions(ty.members.iter().map(|m| &m.origin))
+                }
+                ExtendedType::Enum(ty) => {
+                    !ty.extensions().is_empty()
+                        && all_extensions(ty.values.values().map(|v| &v.origin))
+                }
+                ExtendedType::InputObject(ty) => {
+                    !ty.extensions().is_empty()
+                        && all_extensions(ty.fields.values().map(|f| &f.origin))
+                }
+            }
+    }
+
     pub(crate) fn serialize_impl(&self, state: &mut State) -> fmt::Result {
         match self {
             ExtendedType::Scalar(ty) => ty.serialize_impl(state),
@@ -287,6 +409,61 @@ impl InputObjectType {
     }
 }
 
+/// Sort top-level definitions and their contents by name.
+///
+/// The sort is stable so extensions stay after the definition they extend.
+fn sort_definitions(definitions: &mut [ast::Definition]) {
+    definitions.sort_by(|a, b| sort_key(a).cmp(&sort_key(b)));
+    for definition in definitions {
+        match definition {
+            ast::Definition::DirectiveDefinition(def) => {
+                sort_arguments(&mut def.make_mut().arguments)
+            }
+            ast::Definition::ObjectTypeDefinition(def) => sort_fields(&mut def.make_mut().fields),
+            ast::Definition::ObjectTypeExtension(def) => sort_fields(&mut def.make_mut().fields),
+            ast::Definition::InterfaceTypeDefinition(def) => {
+                sort_fields(&mut def.make_mut().fields)
+            }
+            ast::Definition::InterfaceTypeExtension(def) => sort_fields(&mut def.make_mut().fields),
+            ast::Definition::EnumTypeDefinition(def) => {
+                def.make_mut().values.sort_by(|a, b| a.value.cmp(&b.value))
+            }
+            ast::Definition::EnumTypeExtension(def) => {
+                def.make_mut().values.sort_by(|a, b| a.value.cmp(&b.value))
+            }
+            ast::Definition::InputObjectTypeDefinition(def) => {
+                sort_arguments(&mut def.make_mut().fields)
+            }
+            ast::Definition::InputObjectTypeExtension(def) => {
+                sort_arguments(&mut def.make_mut().fields)
+            }
+            _ => {}
+        }
+    }
+}
+
+fn sort_key(definition: &ast::Definition) -> (u8, Option<&Name>) {
+    let group = match definition {
+        ast::Definition::SchemaDefinition(_) | ast::Definition::SchemaExtension(_) => 0,
+        ast::Definition::DirectiveDefinition(_) => 1,
+        _ => 2,
+    };
+    (group, definition.name())
+}
+
+fn sort_fields(fields: &mut [Node<ast::FieldDefinition>]) {
+    fields.sort_by(|a, b| a.name.cmp(&b.name));
+    for field in fields {
+        if field.arguments.len() > 1 {
+            sort_arguments(&mut field.make_mut().arguments)
+        }
+    }
+}
+
+fn sort_arguments(arguments: &mut [Node<ast::InputValueDefinition>]) {
+    arguments.sort_by(|a, b| a.name.cmp(&b.name))
+}
+
 fn components<'a, T: 'a>(
     components: impl IntoIterator<Item = &'a Component<T>>,
     ext: Option<&ExtensionId>,
@@ -0,0 +1,329 @@
+use crate::ast::OperationType;
+use crate::collections::HashSet;
+use crate::collections::IndexMap;
+use crate::collections::IndexSet;
+use crate::executable::Fragment;
+use crate::executable::FragmentMap;
+use crate::executable::Selection;
+use crate::executable::SelectionSet;
+use crate::schema::Directive;
+use crate::schema::ExtendedType;
+use crate::schema::NamedType;
+use crate::validation::Valid;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+
+/// Returns the part of `schema` that the operations of `documents` can touch.
+///
+/// The result contains the root operation types of the operations,
+/// the fields they select with all of their arguments,
+/// the types of these fields, arguments, and variables,
+/// and types named in type conditions of fragments.
+/// Object and interface types keep only selected fields,
+/// and union types keep only members that are otherwise kept.
+/// Enum and input object types are kept whole.
+///
+/// Directive definitions are kept if they are applied in the operations
+/// or to a kept element of the schema. Built-in types and directives are always kept.
+///
+/// So that the result is still valid, a few more fields may be kept:
+/// fields of kept interfaces are kept in kept types that implement them,
+/// and a kept type where no field is selected (for example, only named in a type condition)
+/// keeps its first field of a scalar or enum type, or its first field if there is none.
+/// Similarly, a union type where no member is kept keeps its first member.
+/// The `query` root operation type is always kept.
+///
+/// ```rust
+/// use apollo_compiler::schema;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(
+///     r#"
+///     type Query { user(id: ID!): User, products: [Product] }
+///     type Mutation { deleteUser(id: ID!): Boolean }
+///     type User { name: String, email: String, friends: [User] }
+///     type Product { upc: ID! }
+///     "#,
+///     "schema.graphql",
+/// )
+/// .unwrap();
+/// let document = ExecutableDocument::parse_and_validate(
+///     &schema,
+///     "query($id: ID!) { user(id: $id) { name } }",
+///     "query.graphql",
+/// )
+/// .unwrap();
+/// let sliced = schema::slice_for_operations(&schema, [&document]);
+/// let user = sliced.get_object("User").unwrap();
+/// assert_eq!(user.fields.keys().collect::<Vec<_>>(), ["name"]);
+/// assert!(!sliced.types.contains_key("Product"));
+/// assert!(!sliced.types.contains_key("Mutation"));
+/// assert!(sliced.schema_definition.mutation.is_none());
+/// ```
+pub fn slice_for_operations<'a>(
+    schema: &Valid<Schema>,
+    documents: impl IntoIterator<Item = &'a Valid<ExecutableDocument>>,
+) -> Valid<Schema> {
+    let mut slicer = Slicer {
+        schema,
+        types: IndexSet::default(),
+        fields: IndexMap::default(),
+        directives: IndexSet::default(),
+    };
+    if let Some(query) = &schema.schema_definition.query {
+        slicer.add_type(&query.name);
+    }
+    slicer.add_directives(
+        schema
+            .schema_definition
+            .directives
+            .iter()
+            .map(|directive| &directive.node),
+    );
+    let mut used_mutation = false;
+    let mut used_subscription = false;
+    for document in documents {
+        let mut fragments_walked = HashSet::default();
+        for operation in document.operations.iter() {
+            match operation.operation_type {
+                OperationType::Query => {}
+                OperationType::Mutation => used_mutation = true,
+                OperationType::Subscription => used_subscription = true,
+            }
+            if let Some(root) = schema.root_operation(operation.operation_type) {
+                slicer.add_type(root);
+            }
+            slicer.add_directives(&operation.directives);
+            for variable in &operation.variables {
+                slicer.add_type(variable.ty.inner_named_type());
+                slicer.add_directives(&variable.directives);
+            }
+            slicer.walk_selection_set(
+                &document.fragments,
+                &mut fragments_walked,
+                &operation.selection_set,
+            );
+        }
+    }
+    slicer.complete();
+
+    let mut sliced = Schema::clone(schema);
+    if !used_mutation {
+        sliced.schema_definition.make_mut().mutation = None;
+    }
+    if !used_subscription {
+        sliced.schema_definition.make_mut().subscription = None;
+    }
+    sliced
+        .types
+        .retain(|name, ty| ty.is_built_in() || slicer.types.contains(name));
+    for (name, ty) in &mut sliced.types {
+        if ty.is_built_in() {
+            continue;
+        }
+        let kept_fields = slicer.fields.get(name);
+        let keep_field = |field_name: &Name| kept_fields.is_some_and(|f| f.contains(field_name));
+        match ty {
+            ExtendedType::Object(def) => {
+                let def = def.make_mut();
+                def.fields.retain(|name, _| keep_field(name));
+                def.implements_interfaces
+                    .retain(|interface| slicer.types.contains(&interface.name));
+            }
+            ExtendedType::Interface(def) => {
+                let def = def.make_mut();
+                def.fields.retain(|name, _| keep_field(name));
+                def.implements_interfaces
+                    .retain(|interface| slicer.types.contains(&interface.name));
+            }
+            ExtendedType::Union(def) => {
+                def.make_mut()
+                    .members
+                    .retain(|member| slicer.types.contains(&member.name));
+            }
+            ExtendedType::Scalar(_) | ExtendedType::Enum(_) | ExtendedType::InputObject(_) => {}
+        }
+    }
+    sliced
+        .directive_definitions
+        .retain(|name, def| def.is_built_in() || slicer.directives.contains(name));
+    Valid::assume_valid(sliced)
+}
+
+struct Slicer<'a> {
+    schema: &'a Schema,
+    types: IndexSet<NamedType>,
+    /// Kept fields of kept object and interface types
+    fields: IndexMap<NamedType, IndexSet<Name>>,
+    directives: IndexSet<Name>,
+}
+
+impl Slicer<'_> {
+    fn walk_selection_set(
+        &mut self,
+        fragments: &FragmentMap,
+        fragments_walked: &mut HashSet<Name>,
+        selection_set: &SelectionSet,
+    ) {
+        for selection in &selection_set.selections {
+            match selection {
+                Selection::Field(field) => {
+                    if matches!(field.name.as_str(), "__typename" | "__schema" | "__type") {
+                        // Introspection types are built-in and always kept
+                        continue;
+                    }
+                    self.add_field(&selection_set.ty, &field.name);
+                    self.add_directives(&field.directives);
+                    self.walk_selection_set(fragments, fragments_walked, &field.selection_set)
+                }
+                Selection::FragmentSpread(spread) => {
+                    self.add_directives(&spread.directives);
+                    if fragments_walked.insert(spread.fragment_name.clone()) {
+                        if let Some(fragment) = fragments.get(&spread.fragment_name) {
+                            self.walk_fragment(fragments, fragments_walked, fragment)
+                        }
+                    }
+                }
+                Selection::InlineFragment(inline) => {
+                    self.add_directives(&inline.directives);
+                    self.add_type(&inline.selection_set.ty);
+                    self.walk_selection_set(fragments, fragments_walked, &inline.selection_set)
+                }
+            }
+        }
+    }
+
+    fn walk_fragment(
+        &mut self,
+        fragments: &FragmentMap,
+        fragments_walked: &mut HashSet<Name>,
+        fragment: &Node<Fragment>,
+    ) {
+        self.add_directives(&fragment.directives);
+        self.add_type(&fragment.selection_set.ty);
+        self.walk_selection_set(fragments, fragments_walked, &fragment.selection_set)
+    }
+
+    fn add_type(&mut self, name: &NamedType) {
+        if !self.types.insert(name.clone()) {
+            return;
+        }
+        let Some(ty) = self.schema.types.get(name) else {
+            return;
+        };
+        if ty.is_built_in() {
+            return;
+        }
+        self.add_directives(ty.directives().iter().map(|directive| &directive.node));
+        match ty {
+            ExtendedType::Object(_) | ExtendedType::Interface(_) => {
+                self.fields.entry(name.clone()).or_default();
+            }
+            ExtendedType::Enum(def) => {
+                for value in def.values.values() {
+                    self.add_directives(&value.directives);
+                }
+            }
+            ExtendedType::InputObject(def) => {
+                for field in def.fields.values() {
+                    self.add_type(field.ty.inner_named_type());
+                    self.add_directives(&field.directives);
+                }
+            }
+            ExtendedType::Scalar(_) | ExtendedType::Union(_) => {}
+        }
+    }
+
+    /// Returns whether the field was not already kept
+    fn add_field(&mut self, type_name: &NamedType, field_name: &Name) -> bool {
+        self.add_type(type_name);
+        let Some(fields) = self.fields.get_mut(type_name) else {
+            return false;
+        };
+        if !fields.insert(field_name.clone()) {
+            return false;
+        }
+        let Ok(definition) = self.schema.type_field(type_name, field_name) else {
+            return true;
+        };
+        self.add_type(definition.ty.inner_named_type());
+        self.add_directives(&definition.directives);
+        for argument in &definition.arguments {
+            self.add_type(argument.ty.inner_named_type());
+            self.add_directives(&argument.directives);
+        }
+        true
+    }
+
+    fn add_directives<'b>(&mut self, directives: impl IntoIterator<Item = &'b Node<Directive>>) {
+        for directive in directives {
+            if !self.directives.insert(directive.name.clone()) {
+                continue;
+            }
+            let Some(definition) = self.schema.directive_definitions.get(&directive.name) else {
+                continue;
+            };
+            for argument in &definition.arguments {
+                self.add_type(argument.ty.inner_named_type());
+                self.add_directives(&argument.directives);
+            }
+        }
+    }
+
+    /// Keeps what is needed for the result to be valid, until nothing changes
+    fn complete(&mut self) {
+        let mut changed = true;
+        while changed {
+            changed = false;
+            let types: Vec<_> = self.types.iter().cloned().collect();
+            for name in &types {
+                let Some(ty) = self.schema.types.get(name) else {
+                    continue;
+                };
+                if ty.is_built_in() {
+                    continue;
+                }
+                let (interfaces, fields) = match ty {
+                    ExtendedType::Object(def) => (&def.implements_interfaces, &def.fields),
+                    ExtendedType::Interface(def) => (&def.implements_interfaces, &def.fields),
+                    ExtendedType::Union(def) => {
+                        let has_member = def
+                            .members
+                            .iter()
+                            .any(|member| self.types.contains(&member.name));
+                        if !has_member {
+                            if let Some(member) = def.members.first() {
+                                self.add_type(&member.name);
+                                changed = true;
+                            }
+                        }
+                        continue;
+                    }
+                    _ => continue,
+                };
+                for interface in interfaces {
+                    let Some(interface_fields) = self.fields.get(&interface.name) else {
+                        continue;
+                    };
+                    for field_name in interface_fields.clone() {
+                        changed |= self.add_field(name, &field_name);
+                    }
+                }
+                if self.fields.get(name).is_some_and(|kept| kept.is_empty()) {
+                    let leaf_field = fields.values().find(|field| {
+                        self.schema
+                            .types
+                            .get(field.ty.inner_named_type())
+                            .is_some_and(|ty| ty.is_leaf())
+                    });
+                    if let Some(field) = leaf_field.or_else(|| fields.values().next()) {
+                        changed |= self.add_field(name, &field.name);
+                    }
+                }
+            }
+        }
+    }
+}
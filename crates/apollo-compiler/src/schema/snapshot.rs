@@ -0,0 +1,931 @@
+//! Compact binary (de)serialization of a built schema, for caching
+
+use crate::ast;
+use crate::ast::Value;
+use crate::collections::HashMap;
+use crate::collections::IndexMap;
+use crate::collections::IndexSet;
+use crate::parser::FileId;
+use crate::parser::LineColumn;
+use crate::parser::LineTerminators;
+use crate::parser::SourceAnchor;
+use crate::parser::SourceFile;
+use crate::parser::SourceSpan;
+use crate::schema::Component;
+use crate::schema::ComponentName;
+use crate::schema::ComponentOrigin;
+use crate::schema::DirectiveList;
+use crate::schema::EnumType;
+use crate::schema::ExtendedType;
+use crate::schema::ExtensionId;
+use crate::schema::InputObjectType;
+use crate::schema::InterfaceType;
+use crate::schema::ObjectType;
+use crate::schema::ScalarType;
+use crate::schema::SchemaDefinition;
+use crate::schema::UnionType;
+use crate::validation::Valid;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+use rowan::TextRange;
+use rowan::TextSize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::OnceLock;
+
+/// The first bytes of every snapshot
+const MAGIC: &[u8; 8] = b"GQLSCHEM";
+
+/// Incremented when the binary layout changes
+const FORMAT_VERSION: u32 = 1;
+
+/// The version of apollo-compiler that writes and reads snapshots
+const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// A snapshot given to [`Schema::from_snapshot`] could not be loaded
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SnapshotError {
+    #[error("not a schema snapshot")]
+    NotASnapshot,
+
+    #[error("schema snapshot format version {found} is not supported (expected {expected})")]
+    UnsupportedFormat { found: u32, expected: u32 },
+
+    #[error("schema snapshot was written by apollo-compiler {found}, but this is {expected}")]
+    CompilerVersionMismatch {
+        found: String,
+        expected: &'static str,
+    },
+
+    #[error("schema snapshot is truncated or corrupted")]
+    Malformed,
+}
+
+impl Valid<Schema> {
+    /// Serialize this schema to a compact binary snapshot,
+    /// which [`Schema::from_snapshot`] loads back without parsing or validating.
+    ///
+    /// The snapshot includes source files and locations so that diagnostics
+    /// for documents validated against the loaded schema still point into schema sources.
+    /// It is versioned: it can only be loaded by the same version of apollo-compiler,
+    /// so a cache should be rebuilt when that changes.
+    ///
+    /// Requires the `snapshot` feature.
+    pub fn to_snapshot(&self) -> Vec<u8> {
+        let mut body = Encoder {
+            bytes: Vec::new(),
+            files: self.sources.keys().copied().collect(),
+            extensions: HashMap::default(),
+        };
+        body.schema(self);
+
+        let mut out = Encoder {
+            bytes: Vec::with_capacity(body.bytes.len() + 64),
+            files: IndexSet::default(),
+            extensions: HashMap::default(),
+        };
+        out.bytes.extend_from_slice(MAGIC);
+        out.u32(FORMAT_VERSION);
+        out.str(CRATE_VERSION);
+        out.len(body.files.len());
+        for file_id in &body.files {
+            out.bool(*file_id == FileId::BUILT_IN);
+            match self.sources.get(file_id) {
+                Some(file) => {
+                    out.bool(true);
+                    out.str(&file.path.to_string_lossy());
+                    out.str(&file.source_text);
+                    out.option(file.anchor.as_ref(), |out, anchor| {
+                        out.len(anchor.offset);
+                        out.len(anchor.line_column.line);
+                        out.len(anchor.line_column.column);
+                    });
+                    out.u8(match file.line_terminators {
+                        LineTerminators::GraphQL => 0,
+                        LineTerminators::LineFeed => 1,
+                    });
+                }
+                None => out.bool(false),
+            }
+        }
+        out.bytes.extend_from_slice(&body.bytes);
+        out.bytes
+    }
+}
+
+impl Schema {
+    /// Load a schema from a snapshot created by [`to_snapshot`][Valid::to_snapshot].
+    ///
+    /// The schema is not validated again.
+    /// Snapshots written by a different version of apollo-compiler are rejected.
+    ///
+    /// Requires the `snapshot` feature.
+    ///
+    /// ```rust
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse_and_validate("type Query { hello: String }", "schema.graphql")
+    ///     .unwrap();
+    /// let snapshot = schema.to_snapshot();
+    /// let loaded = Schema::from_snapshot(&snapshot).unwrap();
+    /// assert_eq!(loaded, schema);
+    /// ```
+    pub fn from_snapshot(bytes: &[u8]) -> Result<Valid<Self>, SnapshotError> {
+        let mut decoder = Decoder {
+            bytes,
+            files: Vec::new(),
+            extensions: Vec::new(),
+        };
+        if decoder.take(MAGIC.len()).ok() != Some(MAGIC.as_slice()) {
+            return Err(SnapshotError::NotASnapshot);
+        }
+        let format_version = decoder.u32()?;
+        if format_version != FORMAT_VERSION {
+            return Err(SnapshotError::UnsupportedFormat {
+                found: format_version,
+                expected: FORMAT_VERSION,
+            });
+        }
+        let crate_version = decoder.str()?;
+        if crate_version != CRATE_VERSION {
+            return Err(SnapshotError::CompilerVersionMismatch {
+                found: crate_version.to_owned(),
+                expected: CRATE_VERSION,
+            });
+        }
+
+        let mut sources = IndexMap::default();
+        for _ in 0..decoder.len()? {
+            let file_id = if decoder.bool()? {
+                FileId::BUILT_IN
+            } else {
+                FileId::new()
+            };
+            if decoder.bool()? {
+                let path = PathBuf::from(decoder.str()?);
+                let source_text = decoder.str()?.to_owned();
+                let anchor = decoder.option(|decoder| {
+                    Ok(SourceAnchor {
+                        offset: decoder.len()?,
+                        line_column: LineColumn {
+                            line: decoder.len()?,
+                            column: decoder.len()?,
+                        },
+                    })
+                })?;
+                let line_terminators = match decoder.u8()? {
+                    0 => LineTerminators::GraphQL,
+                    1 => LineTerminators::LineFeed,
+                    _ => return Err(SnapshotError::Malformed),
+                };
+                let file = SourceFile {
+                    path,
+                    source_text,
+                    source: OnceLock::new(),
+                    line_index: OnceLock::new(),
+                    anchor,
+                    line_terminators,
+                };
+                sources.insert(file_id, Arc::new(file));
+            }
+            decoder.files.push(file_id);
+        }
+
+        let schema = decoder.schema(Arc::new(sources))?;
+        if !decoder.bytes.is_empty() {
+            return Err(SnapshotError::Malformed);
+        }
+        Ok(Valid::assume_valid(schema))
+    }
+}
+
+struct Encoder {
+    bytes: Vec<u8>,
+    /// Files referenced by source locations, written as their index in this set
+    files: IndexSet<FileId>,
+    /// Extensions seen so far, written in full the first time then as their index
+    extensions: HashMap<ExtensionId, usize>,
+}
+
+impl Encoder {
+    fn u8(&mut self, value: u8) {
+        self.bytes.push(value)
+    }
+
+    fn bool(&mut self, value: bool) {
+        self.u8(value.into())
+    }
+
+    /// LEB128 variable-length integer
+    fn u64(&mut self, mut value: u64) {
+        loop {
+            let byte = (value & 0x7F) as u8;
+            value >>= 7;
+            if value == 0 {
+                self.u8(byte);
+                break;
+            }
+            self.u8(byte | 0x80)
+        }
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.u64(value.into())
+    }
+
+    fn len(&mut self, value: usize) {
+        self.u64(value as u64)
+    }
+
+    fn str(&mut self, value: &str) {
+        self.len(value.len());
+        self.bytes.extend_from_slice(value.as_bytes())
+    }
+
+    fn option<T>(&mut self, value: Option<T>, f: impl FnOnce(&mut Self, T)) {
+        match value {
+            Some(value) => {
+                self.bool(true);
+                f(self, value)
+            }
+            None => self.bool(false),
+        }
+    }
+
+    fn list<T>(
+        &mut self,
+        values: impl ExactSizeIterator<Item = T>,
+        mut f: impl FnMut(&mut Self, T),
+    ) {
+        self.len(values.len());
+        for value in values {
+            f(self, value)
+        }
+    }
+
+    fn file(&mut self, file_id: FileId) {
+        let (index, _) = self.files.insert_full(file_id);
+        self.len(index)
+    }
+
+    fn location(&mut self, location: Option<SourceSpan>) {
+        self.option(location, |this, location| {
+            this.file(location.file_id);
+            this.u32(location.text_range.start().into());
+            this.u32(location.text_range.len().into());
+        })
+    }
+
+    fn name(&mut self, name: &Name) {
+        self.str(name);
+        self.option(name.location(), |this, location| {
+            this.file(location.file_id);
+            this.u32(location.text_range.start().into());
+        })
+    }
+
+    fn node<T>(&mut self, node: &Node<T>, f: impl FnOnce(&mut Self, &T)) {
+        self.location(node.location());
+        f(self, node)
+    }
+
+    fn description(&mut self, description: &Option<Node<str>>) {
+        self.option(description.as_ref(), |this, description| {
+            this.location(description.location());
+            this.str(description)
+        })
+    }
+
+    fn origin(&mut self, origin: &ComponentOrigin) {
+        match origin {
+            ComponentOrigin::Definition => self.u8(0),
+            ComponentOrigin::Extension(id) => {
+                if let Some(&index) = self.extensions.get(id) {
+                    self.u8(1);
+                    self.len(index)
+                } else {
+                    self.extensions.insert(id.clone(), self.extensions.len());
+                    self.u8(2);
+                    self.location(id.location())
+                }
+            }
+        }
+    }
+
+    fn component<T>(&mut self, component: &Component<T>, f: impl FnOnce(&mut Self, &T)) {
+        self.origin(&component.origin);
+        self.node(&component.node, f)
+    }
+
+    fn component_name(&mut self, name: &ComponentName) {
+        self.origin(&name.origin);
+        self.name(&name.name)
+    }
+
+    fn schema(&mut self, schema: &Schema) {
+        self.node(&schema.schema_definition, |this, def| {
+            this.description(&def.description);
+            this.schema_directives(&def.directives);
+            for root in [&def.query, &def.mutation, &def.subscription] {
+                this.option(root.as_ref(), Self::component_name)
+            }
+        });
+        self.list(schema.directive_definitions.values(), |this, def| {
+            this.node(def, Self::directive_definition)
+        });
+        self.list(schema.types.values(), Self::extended_type)
+    }
+
+    fn directive_definition(&mut self, def: &ast::DirectiveDefinition) {
+        self.description(&def.description);
+        self.name(&def.name);
+        self.list(def.arguments.iter(), |this, arg| {
+            this.node(arg, Self::input_value_definition)
+        });
+        self.bool(def.repeatable);
+        self.list(def.locations.iter(), |this, location| {
+            this.u8(directive_location_tag(*location))
+        })
+    }
+
+    fn extended_type(&mut self, ty: &ExtendedType) {
+        match ty {
+            ExtendedType::Scalar(def) => {
+                self.u8(0);
+                self.node(def, |this, def| {
+                    this.description(&def.description);
+                    this.name(&def.name);
+                    this.schema_directives(&def.directives);
+                })
+            }
+            ExtendedType::Object(def) => {
+                self.u8(1);
+                self.node(def, |this, def| {
+                    this.description(&def.description);
+                    this.name(&def.name);
+                    this.list(def.implements_interfaces.iter(), Self::component_name);
+                    this.schema_directives(&def.directives);
+                    this.list(def.fields.values(), |this, field| {
+                        this.component(field, Self::field_definition)
+                    });
+                })
+            }
+            ExtendedType::Interface(def) => {
+                self.u8(2);
+                self.node(def, |this, def| {
+                    this.description(&def.description);
+                    this.name(&def.name);
+                    this.list(def.implements_interfaces.iter(), Self::component_name);
+                    this.schema_directives(&def.directives);
+                    this.list(def.fields.values(), |this, field| {
+                        this.component(field, Self::field_definition)
+                    });
+                })
+            }
+            ExtendedType::Union(def) => {
+                self.u8(3);
+                self.node(def, |this, def| {
+                    this.description(&def.description);
+                    this.name(&def.name);
+                    this.schema_directives(&def.directives);
+                    this.list(def.members.iter(), Self::component_name);
+                })
+            }
+            ExtendedType::Enum(def) => {
+                self.u8(4);
+                self.node(def, |this, def| {
+                    this.description(&def.description);
+                    this.name(&def.name);
+                    this.schema_directives(&def.directives);
+                    this.list(def.values.values(), |this, value| {
+                        this.component(value, |this, value| {
+                            this.description(&value.description);
+                            this.name(&value.value);
+                            this.directives(&value.directives);
+                        })
+                    });
+                })
+            }
+            ExtendedType::InputObject(def) => {
+                self.u8(5);
+                self.node(def, |this, def| {
+                    this.description(&def.description);
+                    this.name(&def.name);
+                    this.schema_directives(&def.directives);
+                    this.list(def.fields.values(), |this, field| {
+                        this.component(field, Self::input_value_definition)
+                    });
+                })
+            }
+        }
+    }
+
+    fn field_definition(&mut self, def: &ast::FieldDefinition) {
+        self.description(&def.description);
+        self.name(&def.name);
+        self.list(def.arguments.iter(), |this, arg| {
+            this.node(arg, Self::input_value_definition)
+        });
+        self.ty(&def.ty);
+        self.directives(&def.directives)
+    }
+
+    fn input_value_definition(&mut self, def: &ast::InputValueDefinition) {
+        self.description(&def.description);
+        self.name(&def.name);
+        self.node(&def.ty, Self::ty);
+        self.option(def.default_value.as_ref(), |this, value| {
+            this.node(value, Self::value)
+        });
+        self.directives(&def.directives)
+    }
+
+    fn ty(&mut self, ty: &ast::Type) {
+        match ty {
+            ast::Type::Named(name) => {
+                self.u8(0);
+                self.name(name)
+            }
+            ast::Type::NonNullNamed(name) => {
+                self.u8(1);
+                self.name(name)
+            }
+            ast::Type::List(inner) => {
+                self.u8(2);
+                self.ty(inner)
+            }
+            ast::Type::NonNullList(inner) => {
+                self.u8(3);
+                self.ty(inner)
+            }
+        }
+    }
+
+    fn schema_directives(&mut self, directives: &DirectiveList) {
+        self.list(directives.iter(), |this, directive| {
+            this.component(directive, Self::directive)
+        })
+    }
+
+    fn directives(&mut self, directives: &ast::DirectiveList) {
+        self.list(directives.iter(), |this, directive| {
+            this.node(directive, Self::directive)
+        })
+    }
+
+    fn directive(&mut self, directive: &ast::Directive) {
+        self.name(&directive.name);
+        self.list(directive.arguments.iter(), |this, arg| {
+            this.node(arg, |this, arg| {
+                this.name(&arg.name);
+                this.node(&arg.value, Self::value)
+            })
+        })
+    }
+
+    fn value(&mut self, value: &Value) {
+        match value {
+            Value::Null => self.u8(0),
+            Value::Enum(name) => {
+                self.u8(1);
+                self.name(name)
+            }
+            Value::Variable(name) => {
+                self.u8(2);
+                self.name(name)
+            }
+            Value::String(value) => {
+                self.u8(3);
+                self.str(value)
+            }
+            Value::Float(value) => {
+                self.u8(4);
+                self.str(value.as_str())
+            }
+            Value::Int(value) => {
+                self.u8(5);
+                self.str(value.as_str())
+            }
+            Value::Boolean(value) => {
+                self.u8(6);
+                self.bool(*value)
+            }
+            Value::List(items) => {
+                self.u8(7);
+                self.list(items.iter(), |this, item| this.node(item, Self::value))
+            }
+            Value::Object(fields) => {
+                self.u8(8);
+                self.list(fields.iter(), |this, (name, value)| {
+                    this.name(name);
+                    this.node(value, Self::value)
+                })
+            }
+        }
+    }
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    /// File IDs in this process, indexed like `Encoder::files`
+    files: Vec<FileId>,
+    /// Extensions in the order they were first written
+    extensions: Vec<ExtensionId>,
+}
+
+type DecodeResult<T> = Result<T, SnapshotError>;
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, len: usize) -> DecodeResult<&'a [u8]> {
+        if len > self.bytes.len() {
+            return Err(SnapshotError::Malformed);
+        }
+        let bytes: &'a [u8] = self.bytes;
+        let (taken, rest) = bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> DecodeResult<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn bool(&mut self) -> DecodeResult<bool> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(SnapshotError::Malformed),
+        }
+    }
+
+    fn u64(&mut self) -> DecodeResult<u64> {
+        let mut value = 0_u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            value |= u64::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(SnapshotError::Malformed)
+    }
+
+    fn u32(&mut self) -> DecodeResult<u32> {
+        self.u64()?.try_into().map_err(|_| SnapshotError::Malformed)
+    }
+
+    fn len(&mut self) -> DecodeResult<usize> {
+        self.u64()?.try_into().map_err(|_| SnapshotError::Malformed)
+    }
+
+    fn str(&mut self) -> DecodeResult<&'a str> {
+        let len = self.len()?;
+        std::str::from_utf8(self.take(len)?).map_err(|_| SnapshotError::Malformed)
+    }
+
+    fn option<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> DecodeResult<T>,
+    ) -> DecodeResult<Option<T>> {
+        if self.bool()? {
+            f(self).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn list<T>(&mut self, mut f: impl FnMut(&mut Self) -> DecodeResult<T>) -> DecodeResult<Vec<T>> {
+        let len = self.len()?;
+        // Every item takes at least one byte, don’t pre-allocate more than that
+        let mut items = Vec::with_capacity(len.min(self.bytes.len()));
+        for _ in 0..len {
+            items.push(f(self)?)
+        }
+        Ok(items)
+    }
+
+    fn file(&mut self) -> DecodeResult<FileId> {
+        let index = self.len()?;
+        self.files
+            .get(index)
+            .copied()
+            .ok_or(SnapshotError::Malformed)
+    }
+
+    fn span(&mut self, file_id: FileId, start: u32, len: u32) -> DecodeResult<SourceSpan> {
+        let end = start.checked_add(len).ok_or(SnapshotError::Malformed)?;
+        Ok(SourceSpan {
+            file_id,
+            text_range: TextRange::new(TextSize::from(start), TextSize::from(end)),
+        })
+    }
+
+    fn location(&mut self) -> DecodeResult<Option<SourceSpan>> {
+        self.option(|this| {
+            let file_id = this.file()?;
+            let start = this.u32()?;
+            let len = this.u32()?;
+            this.span(file_id, start, len)
+        })
+    }
+
+    fn name(&mut self) -> DecodeResult<Name> {
+        let name = Name::new(self.str()?).map_err(|_| SnapshotError::Malformed)?;
+        let len = name.len() as u32;
+        let location = self.option(|this| {
+            let file_id = this.file()?;
+            let start = this.u32()?;
+            this.span(file_id, start, len)
+        })?;
+        Ok(match location {
+            Some(location) => name.with_location(location),
+            None => name,
+        })
+    }
+
+    fn node<T>(&mut self, f: impl FnOnce(&mut Self) -> DecodeResult<T>) -> DecodeResult<Node<T>> {
+        let location = self.location()?;
+        Ok(Node::new_opt_location(f(self)?, location))
+    }
+
+    fn description(&mut self) -> DecodeResult<Option<Node<str>>> {
+        self.option(|this| {
+            let location = this.location()?;
+            Ok(Node::new_str_opt_location(this.str()?, location))
+        })
+    }
+
+    fn origin(&mut self) -> DecodeResult<ComponentOrigin> {
+        match self.u8()? {
+            0 => Ok(ComponentOrigin::Definition),
+            1 => {
+                let index = self.len()?;
+                let id = self.extensions.get(index).ok_or(SnapshotError::Malformed)?;
+                Ok(ComponentOrigin::Extension(id.clone()))
+            }
+            2 => {
+                let location = self.location()?;
+                let id = ExtensionId::new(&Node::new_opt_location((), location));
+                self.extensions.push(id.clone());
+                Ok(ComponentOrigin::Extension(id))
+            }
+            _ => Err(SnapshotError::Malformed),
+        }
+    }
+
+    fn component<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> DecodeResult<T>,
+    ) -> DecodeResult<Component<T>> {
+        let origin = self.origin()?;
+        let node = self.node(f)?;
+        Ok(Component { origin, node })
+    }
+
+    fn component_name(&mut self) -> DecodeResult<ComponentName> {
+        let origin = self.origin()?;
+        let name = self.name()?;
+        Ok(ComponentName { origin, name })
+    }
+
+    fn schema(&mut self, sources: crate::parser::SourceMap) -> DecodeResult<Schema> {
+        let schema_definition = self.node(|this| {
+            Ok(SchemaDefinition {
+                description: this.description()?,
+                directives: this.schema_directives()?,
+                query: this.option(Self::component_name)?,
+                mutation: this.option(Self::component_name)?,
+                subscription: this.option(Self::component_name)?,
+            })
+        })?;
+        let directive_definitions = self
+            .list(|this| this.node(Self::directive_definition))?
+            .into_iter()
+            .map(|def| (def.name.clone(), def))
+            .collect();
+        let types = self
+            .list(Self::extended_type)?
+            .into_iter()
+            .map(|ty| (ty.name().clone(), ty))
+            .collect();
+        Ok(Schema {
+            sources,
+            schema_definition,
+            directive_definitions,
+            types,
+            reverse_indexes_cache: Default::default(),
+        })
+    }
+
+    fn directive_definition(&mut self) -> DecodeResult<ast::DirectiveDefinition> {
+        Ok(ast::DirectiveDefinition {
+            description: self.description()?,
+            name: self.name()?,
+            arguments: self.list(|this| this.node(Self::input_value_definition))?,
+            repeatable: self.bool()?,
+            locations: self.list(|this| {
+                directive_location_from_tag(this.u8()?).ok_or(SnapshotError::Malformed)
+            })?,
+        })
+    }
+
+    fn extended_type(&mut self) -> DecodeResult<ExtendedType> {
+        Ok(match self.u8()? {
+            0 => ExtendedType::Scalar(self.node(|this| {
+                Ok(ScalarType {
+                    description: this.description()?,
+                    name: this.name()?,
+                    directives: this.schema_directives()?,
+                })
+            })?),
+            1 => ExtendedType::Object(self.node(|this| {
+                Ok(ObjectType {
+                    description: this.description()?,
+                    name: this.name()?,
+                    implements_interfaces: this.list(Self::component_name)?.into_iter().collect(),
+                    directives: this.schema_directives()?,
+                    fields: this.fields()?,
+                })
+            })?),
+            2 => ExtendedType::Interface(self.node(|this| {
+                Ok(InterfaceType {
+                    description: this.description()?,
+                    name: this.name()?,
+                    implements_interfaces: this.list(Self::component_name)?.into_iter().collect(),
+                    directives: this.schema_directives()?,
+                    fields: this.fields()?,
+                })
+            })?),
+            3 => ExtendedType::Union(self.node(|this| {
+                Ok(UnionType {
+                    description: this.description()?,
+                    name: this.name()?,
+                    directives: this.schema_directives()?,
+                    members: this.list(Self::component_name)?.into_iter().collect(),
+                })
+            })?),
+            4 => ExtendedType::Enum(self.node(|this| {
+                Ok(EnumType {
+                    description: this.description()?,
+                    name: this.name()?,
+                    directives: this.schema_directives()?,
+                    values: this
+                        .list(|this| {
+                            this.component(|this| {
+                                Ok(ast::EnumValueDefinition {
+                                    description: this.description()?,
+                                    value: this.name()?,
+                                    directives: this.directives()?,
+                                })
+                            })
+                        })?
+                        .into_iter()
+                        .map(|value| (value.value.clone(), value))
+                        .collect(),
+                })
+            })?),
+            5 => ExtendedType::InputObject(self.node(|this| {
+                Ok(InputObjectType {
+                    description: this.description()?,
+                    name: this.name()?,
+                    directives: this.schema_directives()?,
+                    fields: this
+                        .list(|this| this.component(Self::input_value_definition))?
+                        .into_iter()
+                        .map(|field| (field.name.clone(), field))
+                        .collect(),
+                })
+            })?),
+            _ => return Err(SnapshotError::Malformed),
+        })
+    }
+
+    fn fields(&mut self) -> DecodeResult<IndexMap<Name, Component<ast::FieldDefinition>>> {
+        Ok(self
+            .list(|this| this.component(Self::field_definition))?
+            .into_iter()
+            .map(|field| (field.name.clone(), field))
+            .collect())
+    }
+
+    fn field_definition(&mut self) -> DecodeResult<ast::FieldDefinition> {
+        Ok(ast::FieldDefinition {
+            description: self.description()?,
+            name: self.name()?,
+            arguments: self.list(|this| this.node(Self::input_value_definition))?,
+            ty: self.ty()?,
+            directives: self.directives()?,
+        })
+    }
+
+    fn input_value_definition(&mut self) -> DecodeResult<ast::InputValueDefinition> {
+        Ok(ast::InputValueDefinition {
+            description: self.description()?,
+            name: self.name()?,
+            ty: self.node(Self::ty)?,
+            default_value: self.option(|this| this.node(Self::value))?,
+            directives: self.directives()?,
+        })
+    }
+
+    fn ty(&mut self) -> DecodeResult<ast::Type> {
+        Ok(match self.u8()? {
+            0 => ast::Type::Named(self.name()?),
+            1 => ast::Type::NonNullNamed(self.name()?),
+            2 => ast::Type::List(Box::new(self.ty()?)),
+            3 => ast::Type::NonNullList(Box::new(self.ty()?)),
+            _ => return Err(SnapshotError::Malformed),
+        })
+    }
+
+    fn schema_directives(&mut self) -> DecodeResult<DirectiveList> {
+        Ok(DirectiveList(
+            self.list(|this| this.component(Self::directive))?,
+        ))
+    }
+
+    fn directives(&mut self) -> DecodeResult<ast::DirectiveList> {
+        Ok(ast::DirectiveList(
+            self.list(|this| this.node(Self::directive))?,
+        ))
+    }
+
+    fn directive(&mut self) -> DecodeResult<ast::Directive> {
+        Ok(ast::Directive {
+            name: self.name()?,
+            arguments: self.list(|this| {
+                this.node(|this| {
+                    Ok(ast::Argument {
+                        name: this.name()?,
+                        value: this.node(Self::value)?,
+                    })
+                })
+            })?,
+        })
+    }
+
+    fn value(&mut self) -> DecodeResult<Value> {
+        Ok(match self.u8()? {
+            0 => Value::Null,
+            1 => Value::Enum(self.name()?),
+            2 => Value::Variable(self.name()?),
+            3 => Value::String(self.str()?.to_owned()),
+            4 => {
+                let text = self.str()?;
+                if !ast::FloatValue::valid_syntax(text) {
+                    return Err(SnapshotError::Malformed);
+                }
+                Value::Float(ast::FloatValue::new_parsed(text))
+            }
+            5 => {
+                let text = self.str()?;
+                if !ast::IntValue::valid_syntax(text) {
+                    return Err(SnapshotError::Malformed);
+                }
+                Value::Int(ast::IntValue::new_parsed(text))
+            }
+            6 => Value::Boolean(self.bool()?),
+            7 => Value::List(self.list(|this| this.node(Self::value))?),
+            8 => Value::Object(self.list(|this| {
+                let name = this.name()?;
+                let value = this.node(Self::value)?;
+                Ok((name, value))
+            })?),
+            _ => return Err(SnapshotError::Malformed),
+        })
+    }
+}
+
+const DIRECTIVE_LOCATIONS: [ast::DirectiveLocation; 19] = [
+    ast::DirectiveLocation::Query,
+    ast::DirectiveLocation::Mutation,
+    ast::DirectiveLocation::Subscription,
+    ast::DirectiveLocation::Field,
+    ast::DirectiveLocation::FragmentDefinition,
+    ast::DirectiveLocation::FragmentSpread,
+    ast::DirectiveLocation::InlineFragment,
+    ast::DirectiveLocation::VariableDefinition,
+    ast::DirectiveLocation::Schema,
+    ast::DirectiveLocation::Scalar,
+    ast::DirectiveLocation::Object,
+    ast::DirectiveLocation::FieldDefinition,
+    ast::DirectiveLocation::ArgumentDefinition,
+    ast::DirectiveLocation::Interface,
+    ast::DirectiveLocation::Union,
+    ast::DirectiveLocation::Enum,
+    ast::DirectiveLocation::EnumValue,
+    ast::DirectiveLocation::InputObject,
+    ast::DirectiveLocation::InputFieldDefinition,
+];
+
+fn directive_location_tag(location: ast::DirectiveLocation) -> u8 {
+    DIRECTIVE_LOCATIONS
+        .iter()
+        .position(|&l| l == location)
+        .expect("all directive locations are listed") as u8
+}
+
+fn directive_location_from_tag(tag: u8) -> Option<ast::DirectiveLocation> {
+    DIRECTIVE_LOCATIONS.get(usize::from(tag)).copied()
+}
@@ -0,0 +1,339 @@
+use crate::ast;
+use crate::collections::HashMap;
+use crate::collections::IndexMap;
+use crate::collections::IndexSet;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::schema::Component;
+use crate::schema::ExtendedType;
+use crate::schema::NamedType;
+use crate::validation::DiagnosticList;
+use crate::validation::Valid;
+use crate::validation::WithErrors;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+
+const GRAPH_ENUM: &str = "join__Graph";
+
+/// Which subgraphs provide each part of a supergraph schema,
+/// as recorded by the [join spec](https://specs.apollo.dev/join/v0.3) directives
+/// `@join__graph`, `@join__type`, `@join__field`, `@join__implements`,
+/// `@join__unionMember`, and `@join__enumValue`.
+///
+/// Only the default `join__` prefix is recognized.
+///
+/// ```rust
+/// use apollo_compiler::schema::Supergraph;
+/// use apollo_compiler::Schema;
+///
+/// let sdl = r#"
+///   directive @join__graph(name: String!, url: String!) on ENUM_VALUE
+///   directive @join__type(graph: join__Graph!, key: String) repeatable on OBJECT
+///   directive @join__field(graph: join__Graph) repeatable on FIELD_DEFINITION
+///   enum join__Graph {
+///     ACCOUNTS @join__graph(name: "accounts", url: "http://accounts")
+///     REVIEWS @join__graph(name: "reviews", url: "http://reviews")
+///   }
+///   type Query @join__type(graph: ACCOUNTS) @join__type(graph: REVIEWS) {
+///     me: String @join__field(graph: ACCOUNTS)
+///     reviews: [String] @join__field(graph: REVIEWS)
+///   }
+/// "#;
+/// let schema = Schema::parse_and_validate(sdl, "supergraph.graphql").unwrap();
+/// let supergraph = Supergraph::from_schema(&schema).unwrap();
+/// let owners: Vec<_> = supergraph
+///     .field_subgraphs("Query", "reviews")
+///     .map(|subgraph| subgraph.name.as_str())
+///     .collect();
+/// assert_eq!(owners, ["reviews"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Supergraph {
+    subgraphs: IndexMap<Name, Subgraph>,
+    type_graphs: HashMap<NamedType, IndexSet<Name>>,
+    field_graphs: HashMap<TypeAttributeCoordinate, IndexSet<Name>>,
+}
+
+/// A subgraph of a [`Supergraph`], from a value of the `join__Graph` enum
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subgraph {
+    /// The `join__Graph` enum value that refers to this subgraph in other directives
+    pub enum_value: Name,
+    /// The `name` argument of `@join__graph`
+    pub name: String,
+    /// The `url` argument of `@join__graph`
+    pub url: String,
+}
+
+/// Inconsistent join spec metadata in a supergraph schema
+#[derive(thiserror::Error, Debug, Clone)]
+pub(crate) enum SupergraphError {
+    #[error("supergraph schema must define a `{GRAPH_ENUM}` enum")]
+    MissingGraphEnum,
+
+    #[error("`{GRAPH_ENUM}.{value}` must have a `@join__graph(name:, url:)` directive")]
+    MissingGraphDirective { value: Name },
+
+    #[error("`@{directive}` refers to `{graph}`, which is not a value of `{GRAPH_ENUM}`")]
+    UndefinedGraph { directive: Name, graph: Name },
+
+    #[error(
+        "`@{directive}(graph: {graph})` is used in `{type_name}`, \
+         which has no `@join__type(graph: {graph})`"
+    )]
+    GraphDoesNotOwnType {
+        directive: Name,
+        graph: Name,
+        type_name: NamedType,
+    },
+}
+
+impl Supergraph {
+    /// Read and check the join spec directives of a supergraph schema.
+    ///
+    /// If this returns errors, the partial result leaves out directives
+    /// that refer to subgraphs that are not defined.
+    pub fn from_schema(schema: &Valid<Schema>) -> Result<Self, WithErrors<Self>> {
+        let mut errors = DiagnosticList::new(schema.sources.clone());
+        let mut supergraph = Self {
+            subgraphs: Default::default(),
+            type_graphs: Default::default(),
+            field_graphs: Default::default(),
+        };
+
+        let Some(graph_enum) = schema.get_enum(GRAPH_ENUM) else {
+            errors.push(None, SupergraphError::MissingGraphEnum);
+            return errors.into_result_with(supergraph);
+        };
+        for (value, definition) in &graph_enum.values {
+            let join_graph = definition.directives.get("join__graph");
+            let string_arg = |name| {
+                join_graph
+                    .and_then(|directive| directive.argument_by_name(name))
+                    .and_then(|value| value.as_str())
+            };
+            let (Some(name), Some(url)) = (string_arg("name"), string_arg("url")) else {
+                errors.push(
+                    definition.location(),
+                    SupergraphError::MissingGraphDirective {
+                        value: value.clone(),
+                    },
+                );
+                continue;
+            };
+            supergraph.subgraphs.insert(
+                value.clone(),
+                Subgraph {
+                    enum_value: value.clone(),
+                    name: name.to_owned(),
+                    url: url.to_owned(),
+                },
+            );
+        }
+
+        for (type_name, ty) in &schema.types {
+            let owners: IndexSet<Name> = supergraph
+                .graph_arguments(&mut errors, type_directives(ty, "join__type"))
+                .collect();
+            if owners.is_empty() {
+                // Not part of any subgraph, such as built-in types and `join__Graph` itself
+                continue;
+            }
+
+            for directive in ["join__implements", "join__unionMember"] {
+                let graphs: Vec<_> = supergraph
+                    .graph_arguments(&mut errors, type_directives(ty, directive))
+                    .collect();
+                check_ownership(&mut errors, &owners, type_name, directive, graphs);
+            }
+
+            match ty {
+                ExtendedType::Object(object) => {
+                    supergraph.add_fields(&mut errors, type_name, &owners, &object.fields)
+                }
+                ExtendedType::Interface(interface) => {
+                    supergraph.add_fields(&mut errors, type_name, &owners, &interface.fields)
+                }
+                ExtendedType::InputObject(input_object) => {
+                    supergraph.add_fields(&mut errors, type_name, &owners, &input_object.fields)
+                }
+                ExtendedType::Enum(enum_) => {
+                    for value in enum_.values.values() {
+                        let graphs: Vec<_> = supergraph
+                            .graph_arguments(
+                                &mut errors,
+                                value.directives.get_all("join__enumValue"),
+                            )
+                            .collect();
+                        check_ownership(&mut errors, &owners, type_name, "join__enumValue", graphs);
+                    }
+                }
+                ExtendedType::Scalar(_) | ExtendedType::Union(_) => {}
+            }
+            supergraph.type_graphs.insert(type_name.clone(), owners);
+        }
+
+        errors.into_result_with(supergraph)
+    }
+
+    /// Returns all subgraphs, in the order of the `join__Graph` enum
+    pub fn subgraphs(&self) -> impl Iterator<Item = &Subgraph> {
+        self.subgraphs.values()
+    }
+
+    /// Returns the subgraph for the given `join__Graph` enum value
+    pub fn subgraph(&self, enum_value: &str) -> Option<&Subgraph> {
+        self.subgraphs.get(enum_value)
+    }
+
+    /// Returns the subgraphs that define the given type, from its `@join__type` directives
+    pub fn type_subgraphs(&self, type_name: &str) -> impl Iterator<Item = &Subgraph> {
+        self.resolve(self.type_graphs.get(type_name))
+    }
+
+    /// Returns the subgraphs that can resolve the given field of an object or interface type,
+    /// or that define the given field of an input object type.
+    ///
+    /// These are the subgraphs of its `@join__field` directives not marked `external: true`,
+    /// or the subgraphs of its type if the field has no `@join__field` directive.
+    pub fn field_subgraphs(
+        &self,
+        type_name: &str,
+        field_name: &str,
+    ) -> impl Iterator<Item = &Subgraph> {
+        let coordinate = TypeAttributeCoordinate {
+            ty: NamedType::new_unchecked(type_name),
+            attribute: Name::new_unchecked(field_name),
+        };
+        let graphs = self
+            .field_graphs
+            .get(&coordinate)
+            .or_else(|| self.type_graphs.get(type_name));
+        self.resolve(graphs)
+    }
+
+    fn resolve<'a>(
+        &'a self,
+        graphs: Option<&'a IndexSet<Name>>,
+    ) -> impl Iterator<Item = &'a Subgraph> {
+        graphs
+            .into_iter()
+            .flatten()
+            .filter_map(|graph| self.subgraphs.get(graph))
+    }
+
+    /// Record the subgraphs of fields with `@join__field` directives
+    fn add_fields<T>(
+        &mut self,
+        errors: &mut DiagnosticList,
+        type_name: &NamedType,
+        owners: &IndexSet<Name>,
+        fields: &IndexMap<Name, Component<T>>,
+    ) where
+        T: HasDirectives,
+    {
+        for (field_name, field) in fields {
+            let join_fields: Vec<_> = field.directives().get_all("join__field").collect();
+            if join_fields.is_empty() {
+                continue;
+            }
+            let resolvable = join_fields.iter().copied().filter(|directive| {
+                !directive
+                    .argument_by_name("external")
+                    .is_some_and(|value| matches!(value.as_ref(), ast::Value::Boolean(true)))
+            });
+            let graphs: IndexSet<Name> = self.graph_arguments(errors, resolvable).collect();
+            check_ownership(
+                errors,
+                owners,
+                type_name,
+                "join__field",
+                graphs.iter().cloned(),
+            );
+            // `@join__field` without a `graph` argument does not restrict ownership
+            if !graphs.is_empty() {
+                let coordinate = TypeAttributeCoordinate {
+                    ty: type_name.clone(),
+                    attribute: field_name.clone(),
+                };
+                self.field_graphs.insert(coordinate, graphs);
+            }
+        }
+    }
+
+    /// Returns the `graph` arguments of the given directives that are defined subgraphs,
+    /// and reports the others.
+    fn graph_arguments<'a>(
+        &'a self,
+        errors: &'a mut DiagnosticList,
+        directives: impl IntoIterator<Item = &'a Node<ast::Directive>> + 'a,
+    ) -> impl Iterator<Item = Name> + 'a {
+        directives.into_iter().filter_map(move |directive| {
+            let graph = directive.argument_by_name("graph")?;
+            let ast::Value::Enum(name) = graph.as_ref() else {
+                // Other kinds of values are reported by schema validation
+                return None;
+            };
+            if self.subgraphs.contains_key(name) {
+                // Keep the location of this use, for diagnostics
+                Some(name.clone())
+            } else {
+                errors.push(
+                    graph.location(),
+                    SupergraphError::UndefinedGraph {
+                        directive: directive.name.clone(),
+                        graph: name.clone(),
+                    },
+                );
+                None
+            }
+        })
+    }
+}
+
+fn type_directives<'a>(
+    ty: &'a ExtendedType,
+    name: &'a str,
+) -> impl Iterator<Item = &'a Node<ast::Directive>> {
+    ty.directives()
+        .get_all(name)
+        .map(|component| &component.node)
+}
+
+/// Schema definitions that can have directives, for [`Supergraph::add_fields`]
+trait HasDirectives {
+    fn directives(&self) -> &ast::DirectiveList;
+}
+
+impl HasDirectives for ast::FieldDefinition {
+    fn directives(&self) -> &ast::DirectiveList {
+        &self.directives
+    }
+}
+
+impl HasDirectives for ast::InputValueDefinition {
+    fn directives(&self) -> &ast::DirectiveList {
+        &self.directives
+    }
+}
+
+fn check_ownership(
+    errors: &mut DiagnosticList,
+    owners: &IndexSet<Name>,
+    type_name: &NamedType,
+    directive: &str,
+    graphs: impl IntoIterator<Item = Name>,
+) {
+    for graph in graphs {
+        if !owners.contains(&graph) {
+            errors.push(
+                graph.location(),
+                SupergraphError::GraphDoesNotOwnType {
+                    directive: Name::new_unchecked(directive),
+                    graph,
+                    type_name: type_name.clone(),
+                },
+            );
+        }
+    }
+}
@@ -0,0 +1,303 @@
+use crate::ast;
+use crate::collections::HashMap;
+use crate::schema::ComponentName;
+use crate::schema::ExtendedType;
+use crate::schema::Implementers;
+use crate::schema::InputValueDefinition;
+use crate::schema::Name;
+use crate::schema::Type;
+use crate::Node;
+use crate::Schema;
+use serde_json::json;
+use serde_json::Value;
+
+/// Options for [`Schema::to_introspection_response`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntrospectionOptions {
+    descriptions: bool,
+    include_deprecated: bool,
+}
+
+impl Default for IntrospectionOptions {
+    fn default() -> Self {
+        Self {
+            descriptions: true,
+            include_deprecated: true,
+        }
+    }
+}
+
+impl IntrospectionOptions {
+    /// Returns the default options, which include descriptions and deprecated members
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether to include descriptions. When disabled, every `description` is `null`.
+    /// Defaults to true.
+    pub fn descriptions(mut self, enable: bool) -> Self {
+        self.descriptions = enable;
+        self
+    }
+
+    /// Whether to include deprecated fields, arguments, input fields, and enum values,
+    /// like `includeDeprecated: true` in an introspection query.
+    /// Defaults to true.
+    pub fn include_deprecated(mut self, enable: bool) -> Self {
+        self.include_deprecated = enable;
+        self
+    }
+}
+
+impl Schema {
+    /// Returns the response to an introspection query for this schema,
+    /// without executing a query.
+    ///
+    /// The response has the shape of a full `IntrospectionQuery` as used by GraphQL tooling,
+    /// as `{"data": {"__schema": …}}`.
+    /// It selects every field of the introspection types,
+    /// including `specifiedByURL`, `isRepeatable`, and `isOneOf`,
+    /// with type references nested as deep as needed.
+    /// This is the inverse of [`Schema::from_introspection_response`].
+    ///
+    /// Servers can compute this once and answer introspection with it,
+    /// instead of [executing][crate::execution::SchemaIntrospectionQuery] each query.
+    ///
+    /// Requires the `serde_json` feature.
+    ///
+    /// ```rust
+    /// use apollo_compiler::schema::IntrospectionOptions;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    /// let response = schema.to_introspection_response(&IntrospectionOptions::new());
+    /// assert_eq!(response["data"]["__schema"]["queryType"]["name"], "Query");
+    ///
+    /// let rebuilt = Schema::from_introspection_response(&response).unwrap();
+    /// assert_eq!(rebuilt.to_string(), schema.to_string());
+    /// ```
+    pub fn to_introspection_response(&self, options: &IntrospectionOptions) -> Value {
+        let writer = JsonWriter {
+            schema: self,
+            options,
+            implementers: self.implementers_map(),
+        };
+        json!({ "data": { "__schema": writer.schema() } })
+    }
+}
+
+struct JsonWriter<'a> {
+    schema: &'a Schema,
+    options: &'a IntrospectionOptions,
+    implementers: HashMap<Name, Implementers>,
+}
+
+impl JsonWriter<'_> {
+    fn schema(&self) -> Value {
+        let definition = &self.schema.schema_definition;
+        let root = |name: &Option<ComponentName>| match name {
+            Some(name) => json!({ "name": name.name }),
+            None => Value::Null,
+        };
+        json!({
+            "description": self.description(definition.description.as_ref()),
+            "queryType": root(&definition.query),
+            "mutationType": root(&definition.mutation),
+            "subscriptionType": root(&definition.subscription),
+            "types": self
+                .schema
+                .types
+                .iter()
+                .map(|(name, ty)| self.type_definition(name, ty))
+                .collect::<Vec<_>>(),
+            "directives": self
+                .schema
+                .directive_definitions
+                .values()
+                .map(|directive| self.directive_definition(directive))
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    fn type_definition(&self, name: &Name, ty: &ExtendedType) -> Value {
+        let (fields, interfaces) = match ty {
+            ExtendedType::Object(def) => (Some(&def.fields), Some(&def.implements_interfaces)),
+            ExtendedType::Interface(def) => (Some(&def.fields), Some(&def.implements_interfaces)),
+            _ => (None, None),
+        };
+        let fields = fields.map(|fields| {
+            fields
+                .values()
+                .filter(|field| self.include(&field.directives))
+                .map(|field| {
+                    json!({
+                        "name": field.name,
+                        "description": self.description(field.description.as_ref()),
+                        "args": self.input_values(&field.arguments),
+                        "type": self.type_ref(&field.ty),
+                        "isDeprecated": field.directives.has("deprecated"),
+                        "deprecationReason": deprecation_reason(&field.directives),
+                    })
+                })
+                .collect::<Vec<_>>()
+        });
+        let interfaces = interfaces.map(|interfaces| {
+            interfaces
+                .iter()
+                .map(|name| self.named_type_ref(&name.name))
+                .collect::<Vec<_>>()
+        });
+        let possible_types = match ty {
+            ExtendedType::Interface(_) => Some(
+                self.implementers
+                    .get(name)
+                    .into_iter()
+                    .flat_map(|implementers| &implementers.objects)
+                    .map(|name| self.named_type_ref(name))
+                    .collect::<Vec<_>>(),
+            ),
+            ExtendedType::Union(def) => Some(
+                def.members
+                    .iter()
+                    .map(|member| self.named_type_ref(&member.name))
+                    .collect(),
+            ),
+            _ => None,
+        };
+        let enum_values = match ty {
+            ExtendedType::Enum(def) => Some(
+                def.values
+                    .values()
+                    .filter(|value| self.include(&value.directives))
+                    .map(|value| {
+                        json!({
+                            "name": value.value,
+                            "description": self.description(value.description.as_ref()),
+                            "isDeprecated": value.directives.has("deprecated"),
+                            "deprecationReason": deprecation_reason(&value.directives),
+                        })
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+            _ => None,
+        };
+        let (input_fields, is_one_of) = match ty {
+            ExtendedType::InputObject(def) => (
+                Some(self.input_values(def.fields.values().map(|field| &field.node))),
+                Some(def.directives.has("oneOf")),
+            ),
+            _ => (None, None),
+        };
+        let specified_by_url = match ty {
+            ExtendedType::Scalar(def) => def
+                .directives
+                .get("specifiedBy")
+                .and_then(|directive| directive.argument_by_name("url"))
+                .and_then(|url| url.as_str()),
+            _ => None,
+        };
+        json!({
+            "kind": kind(ty),
+            "name": name,
+            "description": self.description(ty.description()),
+            "fields": fields,
+            "inputFields": input_fields,
+            "interfaces": interfaces,
+            "enumValues": enum_values,
+            "possibleTypes": possible_types,
+            "specifiedByURL": specified_by_url,
+            "isOneOf": is_one_of,
+        })
+    }
+
+    fn directive_definition(&self, directive: &ast::DirectiveDefinition) -> Value {
+        json!({
+            "name": directive.name,
+            "description": self.description(directive.description.as_ref()),
+            "args": self.input_values(&directive.arguments),
+            "locations": directive
+                .locations
+                .iter()
+                .map(|location| location.name())
+                .collect::<Vec<_>>(),
+            "isRepeatable": directive.repeatable,
+        })
+    }
+
+    fn input_values<'b>(
+        &self,
+        input_values: impl IntoIterator<Item = &'b Node<InputValueDefinition>>,
+    ) -> Vec<Value> {
+        input_values
+            .into_iter()
+            .filter(|input_value| self.include(&input_value.directives))
+            .map(|input_value| {
+                json!({
+                    "name": input_value.name,
+                    "description": self.description(input_value.description.as_ref()),
+                    "type": self.type_ref(&input_value.ty),
+                    "defaultValue": input_value
+                        .default_value
+                        .as_ref()
+                        .map(|value| value.serialize().no_indent().to_string()),
+                    "isDeprecated": input_value.directives.has("deprecated"),
+                    "deprecationReason": deprecation_reason(&input_value.directives),
+                })
+            })
+            .collect()
+    }
+
+    fn description<'a>(&self, description: Option<&'a Node<str>>) -> Option<&'a str> {
+        description
+            .filter(|_| self.options.descriptions)
+            .map(|description| description.as_str())
+    }
+
+    fn include(&self, directives: &ast::DirectiveList) -> bool {
+        self.options.include_deprecated || !directives.has("deprecated")
+    }
+
+    /// Nested `__Type` objects with `kind`, `name`, and `ofType`
+    fn type_ref(&self, ty: &Type) -> Value {
+        match ty {
+            Type::Named(name) => self.named_type_ref(name),
+            Type::NonNullNamed(name) => {
+                json!({ "kind": "NON_NULL", "name": null, "ofType": self.named_type_ref(name) })
+            }
+            Type::List(item) => {
+                json!({ "kind": "LIST", "name": null, "ofType": self.type_ref(item) })
+            }
+            Type::NonNullList(item) => json!({
+                "kind": "NON_NULL",
+                "name": null,
+                "ofType": { "kind": "LIST", "name": null, "ofType": self.type_ref(item) },
+            }),
+        }
+    }
+
+    fn named_type_ref(&self, name: &Name) -> Value {
+        json!({
+            "kind": self.schema.types.get(name).map(kind),
+            "name": name,
+            "ofType": null,
+        })
+    }
+}
+
+fn kind(ty: &ExtendedType) -> &'static str {
+    match ty {
+        ExtendedType::Scalar(_) => "SCALAR",
+        ExtendedType::Object(_) => "OBJECT",
+        ExtendedType::Interface(_) => "INTERFACE",
+        ExtendedType::Union(_) => "UNION",
+        ExtendedType::Enum(_) => "ENUM",
+        ExtendedType::InputObject(_) => "INPUT_OBJECT",
+    }
+}
+
+fn deprecation_reason(directives: &ast::DirectiveList) -> Option<&str> {
+    directives
+        .get("deprecated")?
+        .argument_by_name("reason")?
+        .as_str()
+}
@@ -1,21 +1,147 @@
+use crate::ast;
+use crate::collections::IndexSet;
+use crate::schema::ExtendedType;
+use crate::validation::directive::validate_directive_definition;
 use crate::validation::directive::validate_directive_definitions;
+use crate::validation::enum_::validate_enum_definition;
 use crate::validation::enum_::validate_enum_definitions;
+use crate::validation::input_object::validate_input_object_definition;
 use crate::validation::input_object::validate_input_object_definitions;
+use crate::validation::interface::validate_interface_definition;
 use crate::validation::interface::validate_interface_definitions;
+use crate::validation::object::validate_object_type_definition;
 use crate::validation::object::validate_object_type_definitions;
+use crate::validation::progress::Progress;
+use crate::validation::progress::ValidationPhase;
+use crate::validation::scalar::validate_scalar_definition;
 use crate::validation::scalar::validate_scalar_definitions;
 use crate::validation::schema::validate_schema_definition;
+use crate::validation::union_::validate_union_definition;
 use crate::validation::union_::validate_union_definitions;
 use crate::validation::DiagnosticList;
+use crate::validation::ValidationLimits;
+use crate::Name;
 use crate::Schema;
 
 pub(crate) fn validate_schema(errors: &mut DiagnosticList, schema: &Schema) {
-    validate_schema_definition(errors, schema);
-    validate_scalar_definitions(errors, schema);
-    validate_enum_definitions(errors, schema);
-    validate_union_definitions(errors, schema);
-    validate_interface_definitions(errors, schema);
-    validate_directive_definitions(errors, schema);
-    validate_input_object_definitions(errors, schema);
-    validate_object_type_definitions(errors, schema);
+    validate_schema_with_limits(errors, schema, &ValidationLimits::default())
+}
+
+pub(crate) fn validate_schema_with_limits(
+    errors: &mut DiagnosticList,
+    schema: &Schema,
+    limits: &ValidationLimits,
+) {
+    validate_schema_with_progress(errors, schema, limits, &mut Progress::none())
+}
+
+/// Returns the number of progress steps of [`validate_schema_with_progress`]:
+/// one for each definition
+pub(crate) fn schema_validation_steps(schema: &Schema) -> usize {
+    1 + schema.types.len() + schema.directive_definitions.len()
+}
+
+pub(crate) fn validate_schema_with_progress(
+    errors: &mut DiagnosticList,
+    schema: &Schema,
+    limits: &ValidationLimits,
+    progress: &mut Progress<'_>,
+) {
+    let count =
+        |is_kind: fn(&ExtendedType) -> bool| schema.types.values().filter(|ty| is_kind(ty)).count();
+    let scalars = count(ExtendedType::is_scalar);
+    let enums = count(ExtendedType::is_enum);
+    let unions = count(ExtendedType::is_union);
+    let interfaces = count(ExtendedType::is_interface);
+    let directives = schema.directive_definitions.len();
+    let input_objects = count(ExtendedType::is_input_object);
+    let objects = count(ExtendedType::is_object);
+    if progress.start(errors, ValidationPhase::SchemaDefinition, 1) {
+        validate_schema_definition(errors, schema);
+    }
+    if progress.start(errors, ValidationPhase::ScalarTypes, scalars) {
+        validate_scalar_definitions(errors, schema);
+    }
+    if progress.start(errors, ValidationPhase::EnumTypes, enums) {
+        validate_enum_definitions(errors, schema);
+    }
+    if progress.start(errors, ValidationPhase::UnionTypes, unions) {
+        validate_union_definitions(errors, schema);
+    }
+    if progress.start(errors, ValidationPhase::InterfaceTypes, interfaces) {
+        validate_interface_definitions(errors, schema);
+    }
+    if progress.start(errors, ValidationPhase::DirectiveDefinitions, directives) {
+        validate_directive_definitions(errors, schema, limits.type_nesting);
+    }
+    if progress.start(errors, ValidationPhase::InputObjectTypes, input_objects) {
+        validate_input_object_definitions(errors, schema, limits.type_nesting);
+    }
+    if progress.start(errors, ValidationPhase::ObjectTypes, objects) {
+        validate_object_type_definitions(errors, schema);
+    }
+}
+
+/// Validate only the parts of `schema` that the definitions and extensions in `document`
+/// add or change: the types and directives they name, the schema definition if extended,
+/// and types that implement an extended interface.
+pub(crate) fn validate_affected_definitions(
+    errors: &mut DiagnosticList,
+    schema: &Schema,
+    document: &ast::Document,
+    limits: &ValidationLimits,
+) {
+    let mut schema_definition = false;
+    let mut directives = IndexSet::<&Name>::default();
+    let mut types = IndexSet::<&Name>::default();
+    for definition in &document.definitions {
+        match definition {
+            ast::Definition::SchemaDefinition(_) | ast::Definition::SchemaExtension(_) => {
+                schema_definition = true
+            }
+            ast::Definition::DirectiveDefinition(def) => {
+                directives.insert(&def.name);
+            }
+            ast::Definition::OperationDefinition(_) | ast::Definition::FragmentDefinition(_) => {}
+            _ => types.extend(definition.name()),
+        }
+    }
+    let implementers: Vec<&Name> = schema
+        .types
+        .iter()
+        .filter(|(_, ty)| {
+            let implements = match ty {
+                ExtendedType::Object(object) => &object.implements_interfaces,
+                ExtendedType::Interface(interface) => &interface.implements_interfaces,
+                _ => return false,
+            };
+            implements.iter().any(|name| types.contains(&name.name))
+        })
+        .map(|(name, _)| name)
+        .collect();
+    types.extend(implementers);
+
+    if schema_definition {
+        validate_schema_definition(errors, schema);
+    }
+    for name in directives {
+        if let Some(def) = schema.directive_definitions.get(name) {
+            validate_directive_definition(errors, schema, def, limits.type_nesting)
+        }
+    }
+    for name in types {
+        match schema.types.get(name) {
+            Some(ExtendedType::Scalar(def)) => validate_scalar_definition(errors, schema, def),
+            Some(ExtendedType::Object(def)) => validate_object_type_definition(errors, schema, def),
+            Some(ExtendedType::Interface(def)) => {
+                validate_interface_definition(errors, schema, def)
+            }
+            Some(ExtendedType::Union(def)) => validate_union_definition(errors, schema, def),
+            Some(ExtendedType::Enum(def)) => validate_enum_definition(errors, schema, def),
+            Some(ExtendedType::InputObject(def)) => {
+                validate_input_object_definition(errors, schema, def, limits.type_nesting)
+            }
+            None => {}
+        }
+    }
 }
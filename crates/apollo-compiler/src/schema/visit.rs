@@ -0,0 +1,372 @@
+use crate::ast;
+use crate::collections::IndexMap;
+use crate::coordinate::DirectiveArgumentCoordinate;
+use crate::coordinate::FieldArgumentCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::schema::Component;
+use crate::schema::Directive;
+use crate::schema::DirectiveDefinition;
+use crate::schema::DirectiveList;
+use crate::schema::EnumValueDefinition;
+use crate::schema::ExtendedType;
+use crate::schema::FieldDefinition;
+use crate::schema::InputValueDefinition;
+use crate::schema::Name;
+use crate::Node;
+use crate::Schema;
+
+/// Read-only callbacks for the elements of a schema, driven by [`Schema::visit`].
+///
+/// Every method does nothing by default,
+/// so a visitor only needs to implement those for elements it is interested in.
+pub trait Visitor {
+    /// Called for each type definition, before its directives and fields or values
+    fn visit_type(&mut self, _ty: &ExtendedType) {}
+
+    /// Called for each field of an object or interface type, before its directives and arguments
+    fn visit_field(&mut self, _coordinate: &TypeAttributeCoordinate, _field: &FieldDefinition) {}
+
+    /// Called for each argument of a field or directive definition, before its directives.
+    ///
+    /// The coordinate is either a [`FieldArgumentCoordinate`]
+    /// or a [`DirectiveArgumentCoordinate`].
+    fn visit_argument(&mut self, _coordinate: &SchemaCoordinate, _argument: &InputValueDefinition) {
+    }
+
+    /// Called for each field of an input object type, before its directives
+    fn visit_input_field(
+        &mut self,
+        _coordinate: &TypeAttributeCoordinate,
+        _field: &InputValueDefinition,
+    ) {
+    }
+
+    /// Called for each value of an enum type, before its directives
+    fn visit_enum_value(
+        &mut self,
+        _coordinate: &TypeAttributeCoordinate,
+        _value: &EnumValueDefinition,
+    ) {
+    }
+
+    /// Called for each directive definition, before its arguments
+    fn visit_directive_definition(&mut self, _definition: &DirectiveDefinition) {}
+
+    /// Called for each directive application, on the `schema` definition
+    /// or on any of the elements above
+    fn visit_directive(&mut self, _directive: &Directive) {}
+}
+
+/// Mutating callbacks for the elements of a schema, driven by [`Schema::visit_mut`].
+///
+/// Every method does nothing by default,
+/// so a transform only needs to implement those for elements it changes.
+/// Elements are visited after any change made by the callback of their parent element.
+pub trait VisitorMut {
+    /// Called for each type definition, before its directives and fields or values
+    fn visit_type(&mut self, _ty: &mut ExtendedType) {}
+
+    /// Called for each field of an object or interface type, before its directives and arguments
+    fn visit_field(&mut self, _coordinate: &TypeAttributeCoordinate, _field: &mut FieldDefinition) {
+    }
+
+    /// Called for each argument of a field or directive definition, before its directives.
+    ///
+    /// The coordinate is either a [`FieldArgumentCoordinate`]
+    /// or a [`DirectiveArgumentCoordinate`].
+    fn visit_argument(
+        &mut self,
+        _coordinate: &SchemaCoordinate,
+        _argument: &mut InputValueDefinition,
+    ) {
+    }
+
+    /// Called for each field of an input object type, before its directives
+    fn visit_input_field(
+        &mut self,
+        _coordinate: &TypeAttributeCoordinate,
+        _field: &mut InputValueDefinition,
+    ) {
+    }
+
+    /// Called for each value of an enum type, before its directives
+    fn visit_enum_value(
+        &mut self,
+        _coordinate: &TypeAttributeCoordinate,
+        _value: &mut EnumValueDefinition,
+    ) {
+    }
+
+    /// Called for each directive definition, before its arguments
+    fn visit_directive_definition(&mut self, _definition: &mut DirectiveDefinition) {}
+
+    /// Called for each directive application, on the `schema` definition
+    /// or on any of the elements above.
+    ///
+    /// Returns whether to keep the directive application: `false` removes it.
+    fn visit_directive(&mut self, _directive: &mut Directive) -> bool {
+        true
+    }
+}
+
+impl Schema {
+    /// Calls methods of `visitor` for every element of this schema:
+    /// directives applied to the `schema` definition, then types with their
+    /// fields, arguments, enum values, and input fields, then directive definitions,
+    /// each followed by its own directive applications.
+    ///
+    /// Elements are visited in schema definition order.
+    /// Built-in types and directive definitions are not visited.
+    ///
+    /// ```rust
+    /// use apollo_compiler::coordinate::TypeAttributeCoordinate;
+    /// use apollo_compiler::schema::FieldDefinition;
+    /// use apollo_compiler::schema::Visitor;
+    /// use apollo_compiler::Schema;
+    ///
+    /// struct ListFields(Vec<String>);
+    ///
+    /// impl Visitor for ListFields {
+    ///     fn visit_field(&mut self, coordinate: &TypeAttributeCoordinate, field: &FieldDefinition) {
+    ///         self.0.push(format!("{coordinate}: {}", field.ty))
+    ///     }
+    /// }
+    ///
+    /// let schema = Schema::parse_and_validate(
+    ///     "type Query { me: User } type User { name: String! }",
+    ///     "schema.graphql",
+    /// )
+    /// .unwrap();
+    /// let mut visitor = ListFields(Vec::new());
+    /// schema.visit(&mut visitor);
+    /// assert_eq!(visitor.0, ["Query.me: User", "User.name: String!"]);
+    /// ```
+    pub fn visit(&self, visitor: &mut impl Visitor) {
+        visit_directives(visitor, &self.schema_definition.directives);
+        for ty in self.types.values() {
+            if ty.is_built_in() {
+                continue;
+            }
+            visitor.visit_type(ty);
+            match ty {
+                ExtendedType::Scalar(def) => visit_directives(visitor, &def.directives),
+                ExtendedType::Object(def) => {
+                    visit_directives(visitor, &def.directives);
+                    visit_fields(visitor, &def.name, &def.fields)
+                }
+                ExtendedType::Interface(def) => {
+                    visit_directives(visitor, &def.directives);
+                    visit_fields(visitor, &def.name, &def.fields)
+                }
+                ExtendedType::Union(def) => visit_directives(visitor, &def.directives),
+                ExtendedType::Enum(def) => {
+                    visit_directives(visitor, &def.directives);
+                    for value in def.values.values() {
+                        let coordinate = attribute_coordinate(&def.name, &value.value);
+                        visitor.visit_enum_value(&coordinate, value);
+                        visit_ast_directives(visitor, &value.directives)
+                    }
+                }
+                ExtendedType::InputObject(def) => {
+                    visit_directives(visitor, &def.directives);
+                    for field in def.fields.values() {
+                        let coordinate = attribute_coordinate(&def.name, &field.name);
+                        visitor.visit_input_field(&coordinate, field);
+                        visit_ast_directives(visitor, &field.directives)
+                    }
+                }
+            }
+        }
+        for definition in self.directive_definitions.values() {
+            if definition.is_built_in() {
+                continue;
+            }
+            visitor.visit_directive_definition(definition);
+            for argument in &definition.arguments {
+                let coordinate = DirectiveArgumentCoordinate {
+                    directive: definition.name.clone(),
+                    argument: argument.name.clone(),
+                };
+                visitor.visit_argument(&coordinate.into(), argument);
+                visit_ast_directives(visitor, &argument.directives)
+            }
+        }
+    }
+
+    /// Calls methods of `visitor` to transform every element of this schema in place,
+    /// in the same order as [`visit`][Self::visit].
+    ///
+    /// Every visited element is made unique with [`Node::make_mut`],
+    /// copying it if it was shared with another schema.
+    ///
+    /// ```rust
+    /// use apollo_compiler::schema::Directive;
+    /// use apollo_compiler::schema::VisitorMut;
+    /// use apollo_compiler::Schema;
+    ///
+    /// struct DropDirective(&'static str);
+    ///
+    /// impl VisitorMut for DropDirective {
+    ///     fn visit_directive(&mut self, directive: &mut Directive) -> bool {
+    ///         directive.name != self.0
+    ///     }
+    /// }
+    ///
+    /// let mut schema = Schema::parse(
+    ///     r#"
+    ///     directive @internal on OBJECT | FIELD_DEFINITION
+    ///     type Query @internal { me: String @internal @deprecated }
+    ///     "#,
+    ///     "schema.graphql",
+    /// )
+    /// .unwrap();
+    /// schema.visit_mut(&mut DropDirective("internal"));
+    /// let field = schema.type_field("Query", "me").unwrap();
+    /// assert_eq!(field.to_string(), "me: String @deprecated");
+    /// ```
+    pub fn visit_mut(&mut self, visitor: &mut impl VisitorMut) {
+        if !self.schema_definition.directives.is_empty() {
+            let definition = self.schema_definition.make_mut();
+            visit_directives_mut(visitor, &mut definition.directives);
+        }
+        for ty in self.types.values_mut() {
+            if ty.is_built_in() {
+                continue;
+            }
+            visitor.visit_type(ty);
+            match ty {
+                ExtendedType::Scalar(def) => {
+                    visit_directives_mut(visitor, &mut def.make_mut().directives)
+                }
+                ExtendedType::Object(def) => {
+                    let def = def.make_mut();
+                    visit_directives_mut(visitor, &mut def.directives);
+                    visit_fields_mut(visitor, &def.name, &mut def.fields)
+                }
+                ExtendedType::Interface(def) => {
+                    let def = def.make_mut();
+                    visit_directives_mut(visitor, &mut def.directives);
+                    visit_fields_mut(visitor, &def.name, &mut def.fields)
+                }
+                ExtendedType::Union(def) => {
+                    visit_directives_mut(visitor, &mut def.make_mut().directives)
+                }
+                ExtendedType::Enum(def) => {
+                    let def = def.make_mut();
+                    visit_directives_mut(visitor, &mut def.directives);
+                    for value in def.values.values_mut() {
+                        let coordinate = attribute_coordinate(&def.name, &value.value);
+                        let value = value.make_mut();
+                        visitor.visit_enum_value(&coordinate, value);
+                        visit_ast_directives_mut(visitor, &mut value.directives)
+                    }
+                }
+                ExtendedType::InputObject(def) => {
+                    let def = def.make_mut();
+                    visit_directives_mut(visitor, &mut def.directives);
+                    for field in def.fields.values_mut() {
+                        let coordinate = attribute_coordinate(&def.name, &field.name);
+                        let field = field.make_mut();
+                        visitor.visit_input_field(&coordinate, field);
+                        visit_ast_directives_mut(visitor, &mut field.directives)
+                    }
+                }
+            }
+        }
+        for definition in self.directive_definitions.values_mut() {
+            if definition.is_built_in() {
+                continue;
+            }
+            let definition = definition.make_mut();
+            visitor.visit_directive_definition(definition);
+            for argument in &mut definition.arguments {
+                let coordinate = DirectiveArgumentCoordinate {
+                    directive: definition.name.clone(),
+                    argument: argument.name.clone(),
+                };
+                let argument = argument.make_mut();
+                visitor.visit_argument(&coordinate.into(), argument);
+                visit_ast_directives_mut(visitor, &mut argument.directives)
+            }
+        }
+    }
+}
+
+fn attribute_coordinate(ty: &Name, attribute: &Name) -> TypeAttributeCoordinate {
+    TypeAttributeCoordinate {
+        ty: ty.clone(),
+        attribute: attribute.clone(),
+    }
+}
+
+fn field_argument_coordinate(field: &TypeAttributeCoordinate, argument: &Name) -> SchemaCoordinate {
+    FieldArgumentCoordinate {
+        ty: field.ty.clone(),
+        field: field.attribute.clone(),
+        argument: argument.clone(),
+    }
+    .into()
+}
+
+fn visit_fields(
+    visitor: &mut impl Visitor,
+    type_name: &Name,
+    fields: &IndexMap<Name, Component<FieldDefinition>>,
+) {
+    for field in fields.values() {
+        let coordinate = attribute_coordinate(type_name, &field.name);
+        visitor.visit_field(&coordinate, field);
+        visit_ast_directives(visitor, &field.directives);
+        for argument in &field.arguments {
+            visitor.visit_argument(
+                &field_argument_coordinate(&coordinate, &argument.name),
+                argument,
+            );
+            visit_ast_directives(visitor, &argument.directives)
+        }
+    }
+}
+
+fn visit_directives(visitor: &mut impl Visitor, directives: &DirectiveList) {
+    for directive in directives {
+        visitor.visit_directive(directive)
+    }
+}
+
+fn visit_ast_directives(visitor: &mut impl Visitor, directives: &ast::DirectiveList) {
+    for directive in directives {
+        visitor.visit_directive(directive)
+    }
+}
+
+fn visit_fields_mut(
+    visitor: &mut impl VisitorMut,
+    type_name: &Name,
+    fields: &mut IndexMap<Name, Component<FieldDefinition>>,
+) {
+    for field in fields.values_mut() {
+        let coordinate = attribute_coordinate(type_name, &field.name);
+        let field = field.make_mut();
+        visitor.visit_field(&coordinate, field);
+        visit_ast_directives_mut(visitor, &mut field.directives);
+        for argument in &mut field.arguments {
+            let argument_coordinate = field_argument_coordinate(&coordinate, &argument.name);
+            let argument = argument.make_mut();
+            visitor.visit_argument(&argument_coordinate, argument);
+            visit_ast_directives_mut(visitor, &mut argument.directives)
+        }
+    }
+}
+
+fn visit_directives_mut(visitor: &mut impl VisitorMut, directives: &mut DirectiveList) {
+    directives
+        .0
+        .retain_mut(|directive| visitor.visit_directive(directive.make_mut()))
+}
+
+fn visit_ast_directives_mut(visitor: &mut impl VisitorMut, directives: &mut ast::DirectiveList) {
+    directives
+        .0
+        .retain_mut(|directive| visitor.visit_directive(Node::make_mut(directive)))
+}
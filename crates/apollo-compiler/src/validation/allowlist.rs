@@ -0,0 +1,108 @@
+use crate::collections::HashSet;
+use crate::validation::diagnostics::DiagnosticData;
+use crate::validation::ContentHash;
+use crate::validation::DiagnosticList;
+use crate::ExecutableDocument;
+
+/// A set of executable documents that clients are allowed to send,
+/// such as the operations of a persisted query manifest.
+/// Set with [`ValidationConfig::operation_allowlist`][crate::validation::ValidationConfig::operation_allowlist].
+///
+/// Documents are identified by their [`ContentHash`], computed from their serialization.
+/// Differences in whitespace, comments, and commas therefore do not matter,
+/// but the order of definitions, selections, and arguments does.
+/// Like any `ContentHash`, entries are only comparable with hashes computed
+/// by the same version of apollo-compiler.
+///
+/// ```rust
+/// use apollo_compiler::validation::OperationAllowlist;
+/// use apollo_compiler::validation::ValidationConfig;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate("type Query { a: Int, b: Int }", "schema.graphql").unwrap();
+/// let mut allowlist = OperationAllowlist::new();
+/// allowlist.insert_document(&ExecutableDocument::parse(&schema, "{ a }", "a.graphql").unwrap());
+/// let config = ValidationConfig::new().operation_allowlist(allowlist);
+///
+/// let allowed = ExecutableDocument::parse(&schema, "{\n  a\n}", "query.graphql").unwrap();
+/// assert!(allowed.validate_with_config(&schema, &config).is_ok());
+/// let other = ExecutableDocument::parse(&schema, "{ b }", "query.graphql").unwrap();
+/// assert!(other.validate_with_config(&schema, &config).is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OperationAllowlist {
+    hashes: HashSet<ContentHash>,
+}
+
+impl OperationAllowlist {
+    /// Create an empty allowlist, which rejects every document
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow `document`. Returns its hash, which can be stored
+    /// to rebuild the allowlist later with [`insert_hash`][Self::insert_hash].
+    pub fn insert_document(&mut self, document: &ExecutableDocument) -> ContentHash {
+        let hash = ContentHash::of_document(document);
+        self.hashes.insert(hash);
+        hash
+    }
+
+    /// Allow the document that has the hash `hash`
+    pub fn insert_hash(&mut self, hash: ContentHash) {
+        self.hashes.insert(hash);
+    }
+
+    /// Returns whether `document` is allowed
+    pub fn contains_document(&self, document: &ExecutableDocument) -> bool {
+        self.contains_hash(&ContentHash::of_document(document))
+    }
+
+    /// Returns whether the document that has the hash `hash` is allowed
+    pub fn contains_hash(&self, hash: &ContentHash) -> bool {
+        self.hashes.contains(hash)
+    }
+
+    /// Returns the number of allowed documents
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Returns whether no document is allowed
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+}
+
+impl FromIterator<ContentHash> for OperationAllowlist {
+    fn from_iter<T: IntoIterator<Item = ContentHash>>(iter: T) -> Self {
+        Self {
+            hashes: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<ContentHash> for OperationAllowlist {
+    fn extend<T: IntoIterator<Item = ContentHash>>(&mut self, iter: T) {
+        self.hashes.extend(iter)
+    }
+}
+
+/// Report `document` if it is not in `allowlist`
+pub(crate) fn validate_allowlisted_document(
+    diagnostics: &mut DiagnosticList,
+    document: &ExecutableDocument,
+    allowlist: &OperationAllowlist,
+) {
+    let hash = ContentHash::of_document(document);
+    if allowlist.contains_hash(&hash) {
+        return;
+    }
+    let location = document
+        .operations
+        .iter()
+        .next()
+        .and_then(|operation| operation.location());
+    diagnostics.push(location, DiagnosticData::OperationNotAllowed { hash });
+}
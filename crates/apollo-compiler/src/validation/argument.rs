@@ -1,30 +1,35 @@
 use crate::ast;
-use crate::collections::HashMap;
+use crate::collections::IndexMap;
 use crate::validation::diagnostics::DiagnosticData;
 use crate::validation::DiagnosticList;
 use crate::validation::SourceSpan;
 use crate::Node;
 
+/// Reports each argument name provided more than once,
+/// with all of its occurrences in a single diagnostic.
 pub(crate) fn validate_arguments(
     diagnostics: &mut DiagnosticList,
     arguments: &[Node<ast::Argument>],
 ) {
-    let mut seen = HashMap::<_, Option<SourceSpan>>::default();
+    let mut seen = IndexMap::<_, Vec<Option<SourceSpan>>>::default();
 
     for argument in arguments {
-        let name = &argument.name;
-        if let Some(&original_definition) = seen.get(name) {
-            let redefined_definition = argument.location();
-            diagnostics.push(
-                redefined_definition,
-                DiagnosticData::UniqueArgument {
-                    name: name.clone(),
-                    original_definition,
-                    redefined_definition,
-                },
-            );
-        } else {
-            seen.insert(name, argument.location());
+        seen.entry(&argument.name)
+            .or_default()
+            .push(argument.location());
+    }
+    for (name, mut locations) in seen {
+        if locations.len() < 2 {
+            continue;
         }
+        let original_definition = locations.remove(0);
+        diagnostics.push(
+            locations[0],
+            DiagnosticData::UniqueArgument {
+                name: name.clone(),
+                original_definition,
+                redefined_definitions: locations,
+            },
+        );
     }
 }
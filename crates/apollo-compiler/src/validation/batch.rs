@@ -0,0 +1,104 @@
+use crate::collections::IndexMap;
+use crate::executable::FragmentLibrary;
+use crate::validation::Valid;
+use crate::validation::WithErrors;
+use crate::ExecutableDocument;
+use crate::Schema;
+use std::fmt;
+use std::hash::Hash;
+
+/// The result of parsing and validating one manifest entry with [`BatchValidator`]
+pub type BatchResult = Result<Valid<ExecutableDocument>, WithErrors<ExecutableDocument>>;
+
+/// Parses and validates many executable documents against one schema,
+/// such as the operations of a persisted query manifest.
+///
+/// Fragments shared by many operations can be kept in a [`FragmentLibrary`]
+/// set with [`library`][Self::library]: the library is validated once by itself,
+/// and its fragments that do not use variables are not validated again for each operation.
+///
+/// With the `rayon` feature, entries are parsed and validated concurrently.
+/// Results are in the order of the manifest either way.
+///
+/// ```rust
+/// use apollo_compiler::validation::BatchValidator;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+/// let manifest = [("abc123", "{ a }"), ("def456", "{ b }")];
+/// let results = BatchValidator::new(&schema).validate(manifest);
+/// assert!(results["abc123"].is_ok());
+/// assert!(results["def456"].is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct BatchValidator<'a> {
+    schema: &'a Valid<Schema>,
+    library: Option<&'a Valid<FragmentLibrary>>,
+}
+
+impl<'a> BatchValidator<'a> {
+    /// Create a validator for documents against `schema`
+    pub fn new(schema: &'a Valid<Schema>) -> Self {
+        Self {
+            schema,
+            library: None,
+        }
+    }
+
+    /// Link fragments from `library` into each document,
+    /// as with [`ExecutableDocument::validate_with_library`]
+    pub fn library(mut self, library: &'a Valid<FragmentLibrary>) -> Self {
+        self.library = Some(library);
+        self
+    }
+
+    /// Parse and validate each `(id, source_text)` entry of `manifest`.
+    ///
+    /// The id of each entry is used as its path in diagnostics.
+    /// Returns results keyed by id. If an id is repeated, the last entry with it is kept.
+    pub fn validate<K, S>(
+        &self,
+        manifest: impl IntoIterator<Item = (K, S)>,
+    ) -> IndexMap<K, BatchResult>
+    where
+        K: fmt::Display + Hash + Eq + Send,
+        S: Into<String>,
+    {
+        let entries: Vec<(K, String)> = manifest
+            .into_iter()
+            .map(|(id, source_text)| (id, source_text.into()))
+            .collect();
+
+        #[cfg(feature = "rayon")]
+        let results: Vec<(K, BatchResult)> = {
+            use rayon::prelude::*;
+
+            entries
+                .into_par_iter()
+                .map(|(id, source_text)| {
+                    let result = self.validate_one(&id, source_text);
+                    (id, result)
+                })
+                .collect()
+        };
+        #[cfg(not(feature = "rayon"))]
+        let results: Vec<(K, BatchResult)> = entries
+            .into_iter()
+            .map(|(id, source_text)| {
+                let result = self.validate_one(&id, source_text);
+                (id, result)
+            })
+            .collect();
+
+        results.into_iter().collect()
+    }
+
+    fn validate_one(&self, id: &impl fmt::Display, source_text: String) -> BatchResult {
+        let path = id.to_string();
+        let document = ExecutableDocument::parse(self.schema, source_text, path)?;
+        match self.library {
+            Some(library) => document.validate_with_library(self.schema, library),
+            None => document.validate(self.schema),
+        }
+    }
+}
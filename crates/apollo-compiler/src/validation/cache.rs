@@ -0,0 +1,182 @@
+use crate::collections::HashSet;
+use crate::validation::Valid;
+use crate::validation::WithErrors;
+use crate::ExecutableDocument;
+use crate::Schema;
+use sha2::Digest;
+use sha2::Sha256;
+use std::fmt;
+use std::fmt::Write;
+use std::sync::Mutex;
+
+/// A SHA-256 hash of the serialized form of a schema or executable document,
+/// combined with the version of apollo-compiler.
+///
+/// Unlike [`std::hash::Hash`], this is stable across process runs so it can be persisted.
+/// Because it is computed from the serialization, it does not depend on source locations
+/// or on whether the document was parsed or built programmatically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ContentHash([u8; 32]);
+
+/// Identifies the result of validating an executable document against a schema,
+/// for a [`ValidationCacheStore`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ValidationCacheKey {
+    pub schema: ContentHash,
+    pub document: ContentHash,
+}
+
+/// Storage for [`ValidationCache`], such as an in-memory map or an external key-value store.
+///
+/// Only documents that passed validation are recorded:
+/// invalid ones are always validated again so that diagnostics can be returned.
+pub trait ValidationCacheStore {
+    /// Returns whether `key` was previously [inserted][Self::insert]
+    fn contains(&self, key: &ValidationCacheKey) -> bool;
+
+    /// Record that the document and schema identified by `key` are valid together
+    fn insert(&self, key: ValidationCacheKey);
+}
+
+/// An unbounded in-memory [`ValidationCacheStore`]
+#[derive(Debug, Default)]
+pub struct InMemoryValidationCacheStore {
+    keys: Mutex<HashSet<ValidationCacheKey>>,
+}
+
+/// Skips validation of executable documents that were previously found to be valid
+/// against the same schema, as recorded in a [`ValidationCacheStore`].
+///
+/// Documents are identified by a [`ContentHash`], so a document that is parsed again
+/// from the same source text (for example a persisted query) is a cache hit.
+///
+/// ```
+/// use apollo_compiler::validation::InMemoryValidationCacheStore;
+/// use apollo_compiler::validation::ValidationCache;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+/// let cache = ValidationCache::new(&schema, InMemoryValidationCacheStore::default());
+/// for _ in 0..2 {
+///     let document = ExecutableDocument::parse(&schema, "{ a }", "query.graphql").unwrap();
+///     let _valid = cache.validate(document).unwrap();
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ValidationCache<'schema, S> {
+    schema: &'schema Valid<Schema>,
+    schema_hash: ContentHash,
+    store: S,
+}
+
+impl ContentHash {
+    /// Hash the serialization of `schema`
+    pub fn of_schema(schema: &Schema) -> Self {
+        Self::of_serialized(schema.serialize())
+    }
+
+    /// Hash the serialization of `document`
+    pub fn of_document(document: &ExecutableDocument) -> Self {
+        Self::of_serialized(document.serialize())
+    }
+
+    pub(crate) fn of_serialized(serialized: impl fmt::Display) -> Self {
+        let mut hasher = HashWriter(Sha256::new());
+        hasher.0.update(env!("CARGO_PKG_VERSION"));
+        hasher.0.update([0_u8]);
+        write!(hasher, "{serialized}").expect("hashing does not fail");
+        Self(hasher.0.finalize().into())
+    }
+
+    /// Create from bytes previously returned by [`as_bytes`][Self::as_bytes]
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+/// Formats as lowercase hexadecimal
+impl fmt::Display for ContentHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+struct HashWriter(Sha256);
+
+impl fmt::Write for HashWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.update(s);
+        Ok(())
+    }
+}
+
+impl ValidationCacheStore for InMemoryValidationCacheStore {
+    fn contains(&self, key: &ValidationCacheKey) -> bool {
+        self.keys.lock().unwrap().contains(key)
+    }
+
+    fn insert(&self, key: ValidationCacheKey) {
+        self.keys.lock().unwrap().insert(key);
+    }
+}
+
+impl<S: ValidationCacheStore> ValidationCacheStore for &S {
+    fn contains(&self, key: &ValidationCacheKey) -> bool {
+        (**self).contains(key)
+    }
+
+    fn insert(&self, key: ValidationCacheKey) {
+        (**self).insert(key)
+    }
+}
+
+impl<'schema, S: ValidationCacheStore> ValidationCache<'schema, S> {
+    /// Create a cache for documents validated against `schema`.
+    ///
+    /// This hashes the schema once, so a long-lived `ValidationCache` should be reused
+    /// for as long as the schema does not change.
+    pub fn new(schema: &'schema Valid<Schema>, store: S) -> Self {
+        Self {
+            schema,
+            schema_hash: ContentHash::of_schema(schema),
+            store,
+        }
+    }
+
+    /// Returns the schema that documents are validated against
+    pub fn schema(&self) -> &'schema Valid<Schema> {
+        self.schema
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Returns the key that a valid `document` is recorded with in the store
+    pub fn key(&self, document: &ExecutableDocument) -> ValidationCacheKey {
+        ValidationCacheKey {
+            schema: self.schema_hash,
+            document: ContentHash::of_document(document),
+        }
+    }
+
+    /// Like [`ExecutableDocument::validate`], but returns early if the store records
+    /// `document` as valid, and records it if it passes validation.
+    pub fn validate(
+        &self,
+        document: ExecutableDocument,
+    ) -> Result<Valid<ExecutableDocument>, WithErrors<ExecutableDocument>> {
+        let key = self.key(&document);
+        if self.store.contains(&key) {
+            return Ok(Valid::assume_valid(document));
+        }
+        let valid = document.validate(self.schema)?;
+        self.store.insert(key);
+        Ok(valid)
+    }
+}
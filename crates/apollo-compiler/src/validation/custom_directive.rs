@@ -0,0 +1,150 @@
+use crate::ast;
+use crate::validation::diagnostics::DiagnosticData;
+use crate::validation::directive::walk_document_directives;
+use crate::validation::directive::walk_schema_directives;
+use crate::validation::value::coerce_valid_value;
+use crate::validation::DiagnosticList;
+use crate::validation::SourceSpan;
+use crate::validation::ValidationConfig;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+use std::fmt;
+use std::sync::Arc;
+
+/// An application of a directive passed to a validator registered with
+/// [`ValidationConfig::validate_directive`]
+#[derive(Debug)]
+pub struct DirectiveApplication<'a> {
+    directive: &'a Node<ast::Directive>,
+    arguments: Vec<(Name, Node<ast::Value>)>,
+    errors: Vec<(Option<SourceSpan>, String)>,
+}
+
+impl<'a> DirectiveApplication<'a> {
+    /// Returns the directive as written in the schema or document
+    pub fn directive(&self) -> &'a Node<ast::Directive> {
+        self.directive
+    }
+
+    /// Returns the location of the directive application, if it was parsed from a source file
+    pub fn location(&self) -> Option<SourceSpan> {
+        self.directive.location()
+    }
+
+    /// Returns the coerced arguments in the order of the directive definition,
+    /// including default values. Arguments without a value nor a default are omitted.
+    ///
+    /// Default values are located at the directive application.
+    pub fn arguments(&self) -> &[(Name, Node<ast::Value>)] {
+        &self.arguments
+    }
+
+    /// Returns the coerced value of the argument `name`, or its default value
+    pub fn argument(&self, name: &str) -> Option<&Node<ast::Value>> {
+        self.arguments
+            .iter()
+            .find_map(|(arg_name, value)| (arg_name == name).then_some(value))
+    }
+
+    /// Report an error at `location`, or at the directive application if `None`
+    pub fn error(&mut self, location: Option<SourceSpan>, message: impl Into<String>) {
+        self.errors.push((location, message.into()))
+    }
+}
+
+/// A validator registered with [`ValidationConfig::validate_directive`]
+#[derive(Clone)]
+pub(crate) struct DirectiveValidator(
+    pub(crate) Arc<dyn Fn(&mut DirectiveApplication<'_>) + Send + Sync>,
+);
+
+impl fmt::Debug for DirectiveValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DirectiveValidator")
+    }
+}
+
+/// Validators are closures, so compare by identity
+impl PartialEq for DirectiveValidator {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for DirectiveValidator {}
+
+/// Run the [validators registered for directives][ValidationConfig::validate_directive]
+/// on every application of those directives in `schema`
+pub(crate) fn validate_custom_directives_in_schema(
+    diagnostics: &mut DiagnosticList,
+    schema: &Schema,
+    config: &ValidationConfig,
+) {
+    walk_schema_directives(schema, |directives| {
+        validate_custom_directives(diagnostics, schema, directives, config)
+    })
+}
+
+/// Run the [validators registered for directives][ValidationConfig::validate_directive]
+/// on every application of those directives in `document`
+pub(crate) fn validate_custom_directives_in_document(
+    diagnostics: &mut DiagnosticList,
+    schema: &Schema,
+    document: &ExecutableDocument,
+    config: &ValidationConfig,
+) {
+    walk_document_directives(document, |directives| {
+        validate_custom_directives(diagnostics, schema, directives, config)
+    })
+}
+
+fn validate_custom_directives<'dir>(
+    diagnostics: &mut DiagnosticList,
+    schema: &Schema,
+    directives: impl Iterator<Item = &'dir Node<ast::Directive>>,
+    config: &ValidationConfig,
+) {
+    for directive in directives {
+        let Some(validator) = config.directive_validator(&directive.name) else {
+            continue;
+        };
+        // Undefined directives are reported by `validate_directives`
+        let Some(definition) = schema.directive_definitions.get(&directive.name) else {
+            continue;
+        };
+        let arguments = definition
+            .arguments
+            .iter()
+            .filter_map(|argument_def| {
+                if let Some(value) = directive.argument_by_name(&argument_def.name) {
+                    let coerced = coerce_valid_value(schema, &argument_def.ty, value);
+                    return Some((argument_def.name.clone(), coerced));
+                }
+                let default = argument_def.default_value.as_ref()?;
+                let coerced = coerce_valid_value(schema, &argument_def.ty, default);
+                // Errors about a default value belong to the application that uses it,
+                // not to the directive definition
+                let coerced =
+                    Node::new_opt_location(coerced.as_ref().clone(), directive.location());
+                Some((argument_def.name.clone(), coerced))
+            })
+            .collect();
+        let mut application = DirectiveApplication {
+            directive,
+            arguments,
+            errors: Vec::new(),
+        };
+        (validator.0)(&mut application);
+        for (location, message) in application.errors {
+            diagnostics.push(
+                location.or(directive.location()),
+                DiagnosticData::InvalidDirectiveApplication {
+                    directive: directive.name.clone(),
+                    message,
+                },
+            );
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use crate::ast;
+use crate::schema::ExtendedType;
+use crate::validation::diagnostics::DiagnosticData;
+use crate::validation::value::walk_literal_values;
+use crate::validation::DiagnosticList;
+use crate::validation::ValidationConfig;
+use crate::ExecutableDocument;
+use crate::Schema;
+use std::fmt;
+use std::sync::Arc;
+
+/// Run the [validators registered for custom scalars][ValidationConfig::validate_scalar]
+/// on every literal value of such a scalar in `document`:
+/// in arguments of fields and directives, and in default values of variables.
+pub(crate) fn validate_custom_scalar_values(
+    diagnostics: &mut DiagnosticList,
+    schema: &Schema,
+    document: &ExecutableDocument,
+    config: &ValidationConfig,
+) {
+    walk_literal_values(schema, document, &mut |ty, value, path, _variables| {
+        let ExtendedType::Scalar(scalar) = ty else {
+            return;
+        };
+        if scalar.is_built_in() {
+            return;
+        }
+        let Some(validator) = config.scalar_validator(&scalar.name) else {
+            return;
+        };
+        if let Err(message) = validator(value) {
+            diagnostics.push(
+                value.location(),
+                DiagnosticData::InvalidScalarValue {
+                    scalar: scalar.name.clone(),
+                    message,
+                    path: path.iter().cloned().collect(),
+                },
+            );
+        }
+    });
+}
+
+type ValidateScalar = dyn Fn(&ast::Value) -> Result<(), String> + Send + Sync;
+
+/// A validator registered with [`ValidationConfig::validate_scalar`]
+#[derive(Clone)]
+pub(crate) struct ScalarValidator(pub(crate) Arc<ValidateScalar>);
+
+impl fmt::Debug for ScalarValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ScalarValidator")
+    }
+}
+
+/// Validators are closures, so compare by identity
+impl PartialEq for ScalarValidator {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for ScalarValidator {}
@@ -0,0 +1,83 @@
+use crate::ast::InputValueDefinition;
+use crate::coordinate::DirectiveArgumentCoordinate;
+use crate::coordinate::FieldArgumentCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::schema::ExtendedType;
+use crate::validation::diagnostics::DiagnosticData;
+use crate::validation::DiagnosticList;
+use crate::Schema;
+
+/// Required arguments and input fields, with a non-null type and no default value,
+/// must not be deprecated.
+///
+/// Spec: https://spec.graphql.org/draft/#sec--deprecated
+pub(crate) fn validate_deprecated_required_inputs(
+    diagnostics: &mut DiagnosticList,
+    schema: &Schema,
+) {
+    for ty in schema.types.values() {
+        if ty.is_built_in() {
+            continue;
+        }
+        let fields = match ty {
+            ExtendedType::Object(object) => &object.fields,
+            ExtendedType::Interface(interface) => &interface.fields,
+            ExtendedType::InputObject(input_object) => {
+                for (name, field) in &input_object.fields {
+                    let coordinate = TypeAttributeCoordinate {
+                        ty: input_object.name.clone(),
+                        attribute: name.clone(),
+                    };
+                    validate_input_value(diagnostics, "input field", coordinate.into(), field);
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        for field in fields.values() {
+            for argument in &field.arguments {
+                let coordinate = FieldArgumentCoordinate {
+                    ty: ty.name().clone(),
+                    field: field.name.clone(),
+                    argument: argument.name.clone(),
+                };
+                validate_input_value(diagnostics, "argument", coordinate.into(), argument);
+            }
+        }
+    }
+    for directive in schema.directive_definitions.values() {
+        if directive.is_built_in() {
+            continue;
+        }
+        for argument in &directive.arguments {
+            let coordinate = DirectiveArgumentCoordinate {
+                directive: directive.name.clone(),
+                argument: argument.name.clone(),
+            };
+            validate_input_value(diagnostics, "argument", coordinate.into(), argument);
+        }
+    }
+}
+
+fn validate_input_value(
+    diagnostics: &mut DiagnosticList,
+    describe: &'static str,
+    coordinate: SchemaCoordinate,
+    definition: &InputValueDefinition,
+) {
+    if !definition.is_required() {
+        return;
+    }
+    let Some(deprecated) = definition.directives.get("deprecated") else {
+        return;
+    };
+    diagnostics.push(
+        deprecated.location(),
+        DiagnosticData::DeprecatedRequiredInput {
+            describe,
+            coordinate,
+            ty: definition.ty.clone(),
+        },
+    );
+}
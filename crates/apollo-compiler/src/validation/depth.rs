@@ -0,0 +1,286 @@
+use crate::collections::HashMap;
+use crate::collections::HashSet;
+use crate::executable;
+use crate::parser::SourceSpan;
+use crate::validation::diagnostics::DiagnosticData;
+use crate::validation::DiagnosticList;
+use crate::validation::RecursionGuard;
+use crate::validation::RecursionLimitError;
+use crate::validation::RecursionStack;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Node;
+
+/// The most deeply nested field in a selection set, relative to that selection set.
+#[derive(Debug, Clone, Default)]
+struct DeepestField {
+    /// Response keys of the fields leading to the deepest field, including itself
+    path: Vec<Name>,
+    location: Option<SourceSpan>,
+}
+
+/// Report an operation whose fields are nested more than `limit` levels deep.
+///
+/// Fragment spreads and inline fragments do not count as a level,
+/// but the fields inside them do.
+pub(crate) fn validate_operation_depth(
+    diagnostics: &mut DiagnosticList,
+    document: &ExecutableDocument,
+    operation: &executable::Operation,
+    limit: usize,
+    fragment_nesting_limit: usize,
+) {
+    let mut fragments = RecursionStack::new().with_limit(fragment_nesting_limit);
+    let mut cache = HashMap::default();
+    // Hitting the fragment recursion limit is reported by fragment cycle validation
+    let Ok(deepest) = deepest_field(
+        document,
+        &operation.selection_set,
+        &mut fragments.guard(),
+        &mut cache,
+    ) else {
+        return;
+    };
+    let depth = deepest.path.len();
+    if depth > limit {
+        diagnostics.push(
+            deepest.location,
+            DiagnosticData::MaxDepth {
+                depth,
+                limit,
+                path: deepest
+                    .path
+                    .iter()
+                    .map(|key| key.as_str())
+                    .collect::<Vec<_>>()
+                    .join("."),
+            },
+        );
+    }
+}
+
+/// Fragment results are cached by name, since the depth of a fragment
+/// does not depend on where it is spread.
+fn deepest_field(
+    document: &ExecutableDocument,
+    selection_set: &executable::SelectionSet,
+    fragments: &mut RecursionGuard<'_>,
+    cache: &mut HashMap<Name, DeepestField>,
+) -> Result<DeepestField, RecursionLimitError> {
+    let mut deepest = DeepestField::default();
+    for selection in &selection_set.selections {
+        let candidate = match selection {
+            executable::Selection::Field(field) => {
+                let mut inner = deepest_field(document, &field.selection_set, fragments, cache)?;
+                inner.path.insert(0, field.response_key().clone());
+                if inner.location.is_none() {
+                    inner.location = field.location();
+                }
+                inner
+            }
+            executable::Selection::InlineFragment(inline) => {
+                deepest_field(document, &inline.selection_set, fragments, cache)?
+            }
+            executable::Selection::FragmentSpread(spread) => {
+                let name = &spread.fragment_name;
+                // Cycles are reported by fragment cycle validation
+                if fragments.contains(name) {
+                    continue;
+                }
+                if let Some(cached) = cache.get(name) {
+                    cached.clone()
+                } else if let Some(fragment) = document.fragments.get(name) {
+                    let inner = deepest_field(
+                        document,
+                        &fragment.selection_set,
+                        &mut fragments.push(name)?,
+                        cache,
+                    )?;
+                    cache.insert(name.clone(), inner.clone());
+                    inner
+                } else {
+                    continue;
+                }
+            }
+        };
+        if candidate.path.len() > deepest.path.len() {
+            deepest = candidate;
+        }
+    }
+    Ok(deepest)
+}
+
+/// Report `__schema` and `__type` fields of an operation that are nested more than
+/// `depth_limit` levels deep, or that select more than `field_limit` fields in total.
+pub(crate) fn validate_introspection_size(
+    diagnostics: &mut DiagnosticList,
+    document: &ExecutableDocument,
+    operation: &executable::Operation,
+    (depth_limit, field_limit): (Option<usize>, Option<usize>),
+    fragment_nesting_limit: usize,
+) {
+    if depth_limit.is_none() && field_limit.is_none() {
+        return;
+    }
+    let mut fragments = RecursionStack::new().with_limit(fragment_nesting_limit);
+    let mut introspection_fields = Vec::new();
+    let mut visited = HashSet::default();
+    // Hitting the fragment recursion limit is reported by fragment cycle validation
+    if collect_introspection_fields(
+        document,
+        &operation.selection_set,
+        &mut fragments.guard(),
+        &mut visited,
+        &mut introspection_fields,
+    )
+    .is_err()
+    {
+        return;
+    }
+
+    let mut depth_cache = HashMap::default();
+    let mut count_cache = HashMap::default();
+    for field in introspection_fields {
+        if let Some(limit) = depth_limit {
+            let Ok(mut deepest) = deepest_field(
+                document,
+                &field.selection_set,
+                &mut fragments.guard(),
+                &mut depth_cache,
+            ) else {
+                return;
+            };
+            deepest.path.insert(0, field.response_key().clone());
+            let depth = deepest.path.len();
+            if depth > limit {
+                diagnostics.push(
+                    deepest.location.or(field.location()),
+                    DiagnosticData::IntrospectionDepth {
+                        field: field.name.clone(),
+                        depth,
+                        limit,
+                        path: deepest
+                            .path
+                            .iter()
+                            .map(|key| key.as_str())
+                            .collect::<Vec<_>>()
+                            .join("."),
+                    },
+                );
+            }
+        }
+        if let Some(limit) = field_limit {
+            let Ok(count) = count_fields(
+                document,
+                &field.selection_set,
+                &mut fragments.guard(),
+                &mut count_cache,
+            ) else {
+                return;
+            };
+            if count > limit {
+                diagnostics.push(
+                    field.location(),
+                    DiagnosticData::IntrospectionFieldCount {
+                        field: field.name.clone(),
+                        count,
+                        limit,
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// Find `__schema` and `__type` fields selected on the root type, including through fragments.
+/// A fragment spread more than once only contributes its fields once.
+fn collect_introspection_fields<'doc>(
+    document: &'doc ExecutableDocument,
+    selection_set: &'doc executable::SelectionSet,
+    fragments: &mut RecursionGuard<'_>,
+    visited: &mut HashSet<&'doc Name>,
+    found: &mut Vec<&'doc Node<executable::Field>>,
+) -> Result<(), RecursionLimitError> {
+    for selection in &selection_set.selections {
+        match selection {
+            executable::Selection::Field(field) => {
+                if field.name == "__schema" || field.name == "__type" {
+                    found.push(field);
+                }
+            }
+            executable::Selection::InlineFragment(inline) => collect_introspection_fields(
+                document,
+                &inline.selection_set,
+                fragments,
+                visited,
+                found,
+            )?,
+            executable::Selection::FragmentSpread(spread) => {
+                let name = &spread.fragment_name;
+                if fragments.contains(name) || !visited.insert(name) {
+                    continue;
+                }
+                if let Some(fragment) = document.fragments.get(name) {
+                    collect_introspection_fields(
+                        document,
+                        &fragment.selection_set,
+                        &mut fragments.push(name)?,
+                        visited,
+                        found,
+                    )?
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Count fields in a selection set and all nested selection sets,
+/// counting the fields of a fragment again for each spread.
+///
+/// Fragment results are cached by name, since the count of a fragment
+/// does not depend on where it is spread.
+fn count_fields(
+    document: &ExecutableDocument,
+    selection_set: &executable::SelectionSet,
+    fragments: &mut RecursionGuard<'_>,
+    cache: &mut HashMap<Name, usize>,
+) -> Result<usize, RecursionLimitError> {
+    let mut count = 0_usize;
+    for selection in &selection_set.selections {
+        let inner = match selection {
+            executable::Selection::Field(field) => 1_usize.saturating_add(count_fields(
+                document,
+                &field.selection_set,
+                fragments,
+                cache,
+            )?),
+            executable::Selection::InlineFragment(inline) => {
+                count_fields(document, &inline.selection_set, fragments, cache)?
+            }
+            executable::Selection::FragmentSpread(spread) => {
+                let name = &spread.fragment_name;
+                // Cycles are reported by fragment cycle validation
+                if fragments.contains(name) {
+                    continue;
+                }
+                if let Some(cached) = cache.get(name) {
+                    *cached
+                } else if let Some(fragment) = document.fragments.get(name) {
+                    let inner = count_fields(
+                        document,
+                        &fragment.selection_set,
+                        &mut fragments.push(name)?,
+                        cache,
+                    )?;
+                    cache.insert(name.clone(), inner);
+                    inner
+                } else {
+                    continue;
+                }
+            }
+        };
+        count = count.saturating_add(inner);
+    }
+    Ok(count)
+}
@@ -1,11 +1,16 @@
 use crate::ast;
 use crate::ast::DirectiveLocation;
+use crate::ast::NamedType;
 use crate::ast::Type;
 use crate::coordinate::SchemaCoordinate;
 use crate::coordinate::TypeAttributeCoordinate;
 use crate::diagnostic::CliReport;
 use crate::executable;
 use crate::parser::SourceSpan;
+use crate::validation::lint::LintRule;
+use crate::validation::suggestion::report_suggestions;
+use crate::validation::ContentHash;
+use crate::validation::ValuePath;
 use crate::Name;
 use crate::Node;
 use std::fmt;
@@ -19,35 +24,44 @@ pub(crate) enum DiagnosticData {
     UniqueVariable {
         name: Name,
         original_definition: Option<SourceSpan>,
-        redefined_definition: Option<SourceSpan>,
+        /// Every later definition, in source order
+        redefined_definitions: Vec<Option<SourceSpan>>,
     },
     #[error("the argument `{name}` is provided multiple times")]
     UniqueArgument {
         name: Name,
         original_definition: Option<SourceSpan>,
-        redefined_definition: Option<SourceSpan>,
+        /// Every later occurrence, in source order
+        redefined_definitions: Vec<Option<SourceSpan>>,
     },
     #[error("the value `{name}` is defined multiple times")]
     UniqueInputValue {
         name: Name,
         original_definition: Option<SourceSpan>,
-        redefined_definition: Option<SourceSpan>,
+        /// Every later occurrence, in source order
+        redefined_definitions: Vec<Option<SourceSpan>>,
     },
     #[error("the argument `{name}` is not supported by `{coordinate}`")]
     UndefinedArgument {
         name: Name,
         coordinate: SchemaCoordinate,
         definition_location: Option<SourceSpan>,
+        /// Arguments with a similar name
+        suggestions: Vec<Name>,
     },
     #[error("cannot find type `{name}` in this document")]
     UndefinedDefinition {
         /// Name of the type not in scope
         name: Name,
+        /// Types with a similar name
+        suggestions: Vec<Name>,
     },
     #[error("cannot find directive `@{name}` in this document")]
     UndefinedDirective {
         /// Name of the missing directive
         name: Name,
+        /// Directives with a similar name
+        suggestions: Vec<Name>,
     },
     #[error("variable `${name}` is not defined")]
     UndefinedVariable {
@@ -66,6 +80,8 @@ pub(crate) enum DiagnosticData {
         /// Name of the enum
         definition: Name,
         definition_location: Option<SourceSpan>,
+        /// Path to the enum value within its argument
+        path: ValuePath,
     },
     #[error("field `{value}` does not exist on `{definition}`")]
     UndefinedInputValue {
@@ -74,6 +90,8 @@ pub(crate) enum DiagnosticData {
         /// Name of the input object type
         definition: Name,
         definition_location: Option<SourceSpan>,
+        /// Path to the undefined field within its argument
+        path: ValuePath,
     },
     #[error("type `{name}` does not satisfy interface `{interface}`: missing field `{field}`")]
     MissingInterfaceField {
@@ -98,6 +116,8 @@ pub(crate) enum DiagnosticData {
         coordinate: TypeAttributeCoordinate,
         expected_type: Node<Type>,
         definition_location: Option<SourceSpan>,
+        /// Path to the input object value that is missing the field
+        path: ValuePath,
     },
     #[error(
         "interface `{interface}` declares that it implements `{via_interface}`, but to do so it must also implement `{missing_interface}`"
@@ -172,16 +192,22 @@ pub(crate) enum DiagnosticData {
         /// Expected concrete type
         ty: Node<Type>,
         definition_location: Option<SourceSpan>,
+        /// Path to the value within its argument
+        path: ValuePath,
     },
     #[error("int cannot represent non 32-bit signed integer value")]
     IntCoercionError {
         /// The int value that cannot be coerced
         value: String,
+        /// Path to the value within its argument
+        path: ValuePath,
     },
     #[error("float cannot represent non-finite 64-bit floating point value")]
     FloatCoercionError {
         /// The float value that cannot be coerced
         value: String,
+        /// Path to the value within its argument
+        path: ValuePath,
     },
     #[error("non-repeatable directive {name} can only be used once per location")]
     UniqueDirective {
@@ -288,6 +314,272 @@ pub(crate) enum DiagnosticData {
         type_location: Option<SourceSpan>,
         extensions_locations: Vec<Option<SourceSpan>>,
     },
+    #[error("`{coordinate}` is deprecated: {reason}")]
+    DeprecatedUsage {
+        /// The deprecated field, argument, input field, or enum value
+        coordinate: SchemaCoordinate,
+        reason: String,
+        definition_location: Option<SourceSpan>,
+    },
+    #[error(
+        "field `{coordinate}` of abstract type `{ty}` must select `__typename` \
+         or have fragments on all possible types"
+    )]
+    MissingTypename {
+        coordinate: TypeAttributeCoordinate,
+        ty: NamedType,
+        /// Possible types that are not covered by a fragment
+        uncovered_types: Vec<NamedType>,
+    },
+    #[error("fields are nested {depth} levels deep, more than the limit of {limit}")]
+    MaxDepth {
+        depth: usize,
+        limit: usize,
+        /// Response keys leading to the most deeply nested field, separated by `.`
+        path: String,
+    },
+    #[error(
+        "introspection field `{field}` is nested {depth} levels deep, more than the limit of {limit}"
+    )]
+    IntrospectionDepth {
+        /// `__schema` or `__type`
+        field: Name,
+        depth: usize,
+        limit: usize,
+        /// Response keys leading to the most deeply nested field, separated by `.`
+        path: String,
+    },
+    #[error(
+        "introspection field `{field}` selects {count} fields, more than the limit of {limit}"
+    )]
+    IntrospectionFieldCount {
+        /// `__schema` or `__type`
+        field: Name,
+        count: usize,
+        limit: usize,
+    },
+    #[error("selection set has {count} aliased fields, more than the limit of {limit}")]
+    TooManyAliases {
+        count: usize,
+        limit: usize,
+        /// Aliased fields past the limit
+        excess_locations: Vec<Option<SourceSpan>>,
+    },
+    #[error("`{response_key}` is selected {count} times, more than the limit of {limit}")]
+    TooManyRepeatedFields {
+        response_key: Name,
+        count: usize,
+        limit: usize,
+        /// Selections of the field past the limit
+        excess_locations: Vec<Option<SourceSpan>>,
+    },
+    #[error("too many fields to check for merging, more than the limit of {limit}")]
+    FieldMergingBudget { limit: usize },
+    #[error("validation budget exceeded: stopped after {limit} steps")]
+    ValidationBudgetExceeded { limit: usize },
+    #[error("validation was cancelled before it completed")]
+    ValidationCancelled,
+    #[error("the label `{label}` is used by multiple `@defer` or `@stream` directives")]
+    UniqueIncrementalLabel {
+        label: String,
+        original_definition: Option<SourceSpan>,
+        redefined_definition: Option<SourceSpan>,
+    },
+    #[error("the `label` argument of `@{directive}` must not be a variable")]
+    VariableIncrementalLabel { directive: Name },
+    #[error("`initialCount` must not be negative, got {value}")]
+    NegativeInitialCount { value: Node<ast::Value> },
+    #[error("`@defer` cannot be used on the root selection set of a subscription")]
+    DeferOnSubscriptionRoot,
+    #[error("`@stream` cannot be used on field `{field}` of non-list type `{ty}`")]
+    StreamOnNonListField { field: Name, ty: Type },
+    #[error("invalid value for scalar `{scalar}`: {message}")]
+    InvalidScalarValue {
+        scalar: Name,
+        /// Message returned by the validator registered for the scalar
+        message: String,
+        /// Path to the value within its argument or variable
+        path: ValuePath,
+    },
+    #[error("invalid `@{directive}` directive: {message}")]
+    InvalidDirectiveApplication {
+        directive: Name,
+        /// Message reported by the validator registered for the directive
+        message: String,
+    },
+    #[error("field `{coordinate}` of a OneOf input object must be nullable, found `{ty}`")]
+    NonNullOneOfField {
+        coordinate: TypeAttributeCoordinate,
+        ty: Node<Type>,
+    },
+    #[error("required {describe} `{coordinate}` cannot be deprecated")]
+    DeprecatedRequiredInput {
+        /// `argument` or `input field`
+        describe: &'static str,
+        coordinate: SchemaCoordinate,
+        /// The non-null type of the argument or input field
+        ty: Node<Type>,
+    },
+    #[error("field `{coordinate}` of a OneOf input object must not have a default value")]
+    OneOfFieldDefault { coordinate: TypeAttributeCoordinate },
+    #[error("a value of OneOf input object `{ty}` must have exactly one field, found {count}")]
+    OneOfFieldCount {
+        ty: Name,
+        count: usize,
+        /// Path to the input object value
+        path: ValuePath,
+    },
+    #[error("field `{coordinate}` of a OneOf input object value must not be null")]
+    NullOneOfField {
+        coordinate: TypeAttributeCoordinate,
+        /// Path to the input object value
+        path: ValuePath,
+    },
+    #[error("variable `${variable}` of nullable type `{variable_type}` cannot be used for field `{coordinate}` of a OneOf input object")]
+    NullableVariableInOneOf {
+        variable: Name,
+        variable_type: Type,
+        variable_location: Option<SourceSpan>,
+        coordinate: TypeAttributeCoordinate,
+    },
+    #[error("introspection is disabled, but `{field}` is selected")]
+    IntrospectionDisabled { field: Name },
+    #[error("document with hash `{hash}` is not in the operation allowlist")]
+    OperationNotAllowed {
+        /// The [`ContentHash`] of the document
+        hash: ContentHash,
+    },
+    #[error("{}", operation_not_found_message(.name, .count))]
+    OperationNotFound {
+        /// The requested operation name
+        name: Option<String>,
+        /// How many operations the document contains
+        count: usize,
+    },
+    #[error("{} `{name}` should be {}", naming_convention_subject(.rule), naming_convention(.rule))]
+    NamingConvention {
+        rule: LintRule,
+        name: Name,
+        /// The name converted to the convention
+        expected: String,
+    },
+    #[error("{describe_type} `{name}` is missing a description")]
+    MissingDescription {
+        describe_type: &'static str,
+        name: Name,
+    },
+    #[error("{describe} `{name}` differs only by case from `{other}`")]
+    CaseInsensitiveNameCollision {
+        /// `type`, `directive`, `field`, `enum value`, or `argument`
+        describe: &'static str,
+        name: Name,
+        /// The earlier name in the same scope
+        other: Name,
+    },
+    #[error("`@{directive}(if: {value})` has no effect")]
+    RedundantConditionalDirective {
+        /// `include` or `skip`
+        directive: Name,
+        value: bool,
+    },
+    #[error(
+        "`@include(if: ${variable})` and `@skip(if: ${variable})` always exclude this selection"
+    )]
+    ContradictoryConditionalDirectives {
+        variable: Name,
+        /// The `@include` directive. The main location is the `@skip` directive.
+        include: Option<SourceSpan>,
+    },
+    #[error("anonymous {operation_type} should have a name")]
+    UnnamedOperation { operation_type: ast::OperationType },
+    #[error("operation name `{name}` does not match the pattern `{pattern}`")]
+    // Patterns require the `regex` feature
+    #[cfg_attr(not(feature = "regex"), allow(dead_code))]
+    OperationNamePattern { name: Name, pattern: String },
+    #[error(
+        "selection can never be reached: no type matches both `{type_condition}` and {}",
+        enclosing_conditions_message(.enclosing_conditions)
+    )]
+    UnreachableSelection {
+        type_condition: NamedType,
+        /// Type conditions of the fragments enclosing this one since the nearest field,
+        /// from outermost to innermost
+        enclosing_conditions: Vec<(NamedType, Option<SourceSpan>)>,
+    },
+    #[error("directive `@{name}` is applied twice with the same arguments")]
+    RepeatedDirectiveArguments {
+        /// Name of the repeated directive
+        name: Name,
+        /// The first application of the directive with these arguments
+        original_application: Option<SourceSpan>,
+    },
+    #[error("an error in non-null field `{path}` propagates past its parent object")]
+    NullPropagation {
+        /// Response keys of the non-null field, separated by `.`
+        path: String,
+        /// Response keys of the field set to `null`, or `None` for the entire response `data`
+        nulled: Option<String>,
+    },
+    #[error("{describe} are not allowed in the `fields` argument of `@{directive}`")]
+    DisallowedInFieldSet {
+        /// `key`, `requires`, or `provides`
+        directive: Name,
+        /// `aliases`, `directives`, or `arguments`
+        describe: &'static str,
+    },
+    #[error("`@specifiedBy` URL of scalar `{scalar}` is not an absolute URI: {reason}")]
+    InvalidSpecifiedByUrl {
+        scalar: Name,
+        url: String,
+        /// What is wrong with the URL
+        reason: String,
+    },
+}
+
+fn naming_convention_subject(rule: &LintRule) -> &'static str {
+    match rule {
+        LintRule::TypeNamePascalCase => "type name",
+        LintRule::FieldNameCamelCase => "field name",
+        LintRule::EnumValueScreamingSnakeCase => "enum value",
+        LintRule::OperationTypeSuffix => "operation name",
+        LintRule::DescriptionRequired
+        | LintRule::CaseInsensitiveNameCollision
+        | LintRule::RedundantConditionalDirective
+        | LintRule::OperationNameRequired
+        | LintRule::OperationNamePattern => "name",
+    }
+}
+
+fn naming_convention(rule: &LintRule) -> &'static str {
+    match rule {
+        LintRule::TypeNamePascalCase => "PascalCase",
+        LintRule::FieldNameCamelCase => "camelCase",
+        LintRule::EnumValueScreamingSnakeCase => "SCREAMING_SNAKE_CASE",
+        LintRule::DescriptionRequired => "described",
+        LintRule::CaseInsensitiveNameCollision => "distinct ignoring case",
+        LintRule::RedundantConditionalDirective => "meaningful",
+        LintRule::OperationTypeSuffix => "suffixed with its operation type",
+        LintRule::OperationNameRequired => "present",
+        LintRule::OperationNamePattern => "matching the configured pattern",
+    }
+}
+
+fn enclosing_conditions_message(conditions: &[(NamedType, Option<SourceSpan>)]) -> String {
+    let names: Vec<_> = conditions
+        .iter()
+        .map(|(type_condition, _)| format!("`{type_condition}`"))
+        .collect();
+    match names.as_slice() {
+        [name] => format!("the enclosing type condition {name}"),
+        _ => format!("the enclosing type conditions {}", names.join(", ")),
+    }
+}
+
+fn operation_not_found_message(name: &Option<String>, count: &usize) -> String {
+    match name {
+        Some(name) => format!("the document does not contain an operation named `{name}`"),
+        None => format!("an operation name is required, the document contains {count} operations"),
+    }
 }
 
 impl DiagnosticData {
@@ -296,30 +588,34 @@ impl DiagnosticData {
             DiagnosticData::UniqueVariable {
                 name,
                 original_definition,
-                redefined_definition,
+                redefined_definitions,
             } => {
                 report.with_label_opt(
                     *original_definition,
                     format_args!("previous definition of `${name}` here"),
                 );
-                report.with_label_opt(
-                    *redefined_definition,
-                    format_args!("`${name}` defined again here"),
-                );
+                for redefined_definition in redefined_definitions {
+                    report.with_label_opt(
+                        *redefined_definition,
+                        format_args!("`${name}` defined again here"),
+                    );
+                }
             }
             DiagnosticData::UniqueArgument {
                 name,
                 original_definition,
-                redefined_definition,
+                redefined_definitions,
             } => {
                 report.with_label_opt(
                     *original_definition,
                     format_args!("previously provided `{name}` here"),
                 );
-                report.with_label_opt(
-                    *redefined_definition,
-                    format_args!("`{name}` provided again here"),
-                );
+                for redefined_definition in redefined_definitions {
+                    report.with_label_opt(
+                        *redefined_definition,
+                        format_args!("`{name}` provided again here"),
+                    );
+                }
                 report.with_help(format_args!(
                     "`{name}` argument must only be provided once."
                 ));
@@ -327,16 +623,18 @@ impl DiagnosticData {
             DiagnosticData::UniqueInputValue {
                 name,
                 original_definition,
-                redefined_definition,
+                redefined_definitions,
             } => {
                 report.with_label_opt(
                     *original_definition,
                     format_args!("previous definition of `{name}` here"),
                 );
-                report.with_label_opt(
-                    *redefined_definition,
-                    format_args!("`{name}` defined again here"),
-                );
+                for redefined_definition in redefined_definitions {
+                    report.with_label_opt(
+                        *redefined_definition,
+                        format_args!("`{name}` defined again here"),
+                    );
+                }
                 report.with_help(format_args!(
                     "`{name}` must only be defined once in this argument list or input object definition."
                 ));
@@ -344,6 +642,7 @@ impl DiagnosticData {
             DiagnosticData::UndefinedArgument {
                 coordinate,
                 definition_location,
+                suggestions,
                 ..
             } => {
                 report.with_label_opt(main_location, "argument by this name not found");
@@ -351,6 +650,7 @@ impl DiagnosticData {
                     *definition_location,
                     format_args!("{coordinate} defined here"),
                 );
+                report_suggestions(report, "", suggestions);
             }
             DiagnosticData::RequiredArgument {
                 name,
@@ -369,6 +669,7 @@ impl DiagnosticData {
                 coordinate: _,
                 expected_type: _,
                 definition_location,
+                path: _,
             } => {
                 report.with_label_opt(
                     main_location,
@@ -376,11 +677,13 @@ impl DiagnosticData {
                 );
                 report.with_label_opt(*definition_location, "field defined here");
             }
-            DiagnosticData::UndefinedDefinition { .. } => {
+            DiagnosticData::UndefinedDefinition { suggestions, .. } => {
                 report.with_label_opt(main_location, "not found in this scope");
+                report_suggestions(report, "", suggestions);
             }
-            DiagnosticData::UndefinedDirective { .. } => {
+            DiagnosticData::UndefinedDirective { suggestions, .. } => {
                 report.with_label_opt(main_location, "directive not defined");
+                report_suggestions(report, "@", suggestions);
             }
             DiagnosticData::UndefinedVariable { .. } => {
                 report.with_label_opt(main_location, "not found in this scope");
@@ -395,6 +698,7 @@ impl DiagnosticData {
                 value: _,
                 definition,
                 definition_location,
+                path: _,
             } => {
                 report.with_label_opt(
                     main_location,
@@ -406,6 +710,7 @@ impl DiagnosticData {
                 value: _,
                 definition,
                 definition_location,
+                path: _,
             } => {
                 report.with_label_opt(
                     main_location,
@@ -568,6 +873,7 @@ impl DiagnosticData {
                 value,
                 ty,
                 definition_location,
+                path: _,
             } => {
                 report.with_label_opt(
                     main_location,
@@ -714,6 +1020,284 @@ impl DiagnosticData {
                     "input values",
                 );
             }
+            DiagnosticData::DeprecatedUsage {
+                coordinate,
+                definition_location,
+                ..
+            } => {
+                report.with_label_opt(main_location, format_args!("`{coordinate}` used here"));
+                report.with_label_opt(*definition_location, "deprecated here");
+            }
+            DiagnosticData::IntrospectionDepth { depth, path, .. } => {
+                report.with_label_opt(main_location, format_args!("`{path}` is at depth {depth}"));
+            }
+            DiagnosticData::IntrospectionFieldCount { field, count, .. } => {
+                report.with_label_opt(
+                    main_location,
+                    format_args!(
+                        "{count} fields selected in `{field}`, including through fragments"
+                    ),
+                );
+            }
+            DiagnosticData::MaxDepth { depth, path, .. } => {
+                report.with_label_opt(main_location, format_args!("`{path}` is at depth {depth}"));
+            }
+            DiagnosticData::TooManyAliases {
+                excess_locations, ..
+            }
+            | DiagnosticData::TooManyRepeatedFields {
+                excess_locations, ..
+            } => {
+                for &location in excess_locations {
+                    report.with_label_opt(location, "over the limit");
+                }
+            }
+            DiagnosticData::FieldMergingBudget { .. } => {
+                report.with_label_opt(
+                    main_location,
+                    "fields selected here and in nested fragments are over the limit",
+                );
+            }
+            DiagnosticData::ValidationBudgetExceeded { .. } => {
+                report.with_label_opt(main_location, "validation stopped here");
+                report.with_help(
+                    "fragments are validated for each spread, \
+                     so nested fragment spreads can multiply the work",
+                );
+            }
+            DiagnosticData::ValidationCancelled => {
+                report.with_help(
+                    "the progress callback set in `ValidationConfig` stopped validation",
+                );
+            }
+            DiagnosticData::UniqueIncrementalLabel {
+                label,
+                original_definition,
+                redefined_definition,
+            } => {
+                report.with_label_opt(
+                    *original_definition,
+                    format_args!("label `{label}` previously used here"),
+                );
+                report.with_label_opt(
+                    *redefined_definition,
+                    format_args!("`{label}` used again here"),
+                );
+                report.with_help("labels identify incremental payloads, so they must be unique");
+            }
+            DiagnosticData::VariableIncrementalLabel { .. } => {
+                report.with_label_opt(main_location, "use a string literal here");
+            }
+            DiagnosticData::NegativeInitialCount { .. } => {
+                report.with_label_opt(main_location, "use 0 or more");
+            }
+            DiagnosticData::DeferOnSubscriptionRoot => {
+                report.with_label_opt(main_location, "remove this directive");
+                report.with_help("subscription events must each be delivered in a single payload");
+            }
+            DiagnosticData::StreamOnNonListField { .. } => {
+                report.with_label_opt(main_location, "only list fields can be streamed");
+            }
+            DiagnosticData::InvalidScalarValue { message, .. } => {
+                report.with_label_opt(main_location, message);
+            }
+            DiagnosticData::InvalidDirectiveApplication { message, .. } => {
+                report.with_label_opt(main_location, message);
+            }
+            DiagnosticData::NonNullOneOfField { .. } => {
+                report.with_label_opt(main_location, "remove the `!`");
+            }
+            DiagnosticData::DeprecatedRequiredInput { ty, .. } => {
+                report.with_label_opt(main_location, "deprecated here");
+                report.with_label_opt(ty.location(), format_args!("`{ty}` is required"));
+                report.with_help("make the type nullable or add a default value");
+            }
+            DiagnosticData::OneOfFieldDefault { .. } => {
+                report.with_label_opt(main_location, "remove this default value");
+            }
+            DiagnosticData::OneOfFieldCount { ty, .. } => {
+                report.with_label_opt(
+                    main_location,
+                    format_args!("provide exactly one field of `{ty}`"),
+                );
+            }
+            DiagnosticData::NullOneOfField { .. } => {
+                report.with_label_opt(main_location, "provide a non-null value");
+            }
+            DiagnosticData::NullableVariableInOneOf {
+                variable,
+                variable_location,
+                ..
+            } => {
+                report.with_label_opt(
+                    *variable_location,
+                    format_args!("declare `${variable}` with a non-null type here"),
+                );
+                report.with_label_opt(main_location, "variable used here");
+            }
+            DiagnosticData::IntrospectionDisabled { .. } => {
+                report.with_label_opt(main_location, "introspection field selected here");
+            }
+            DiagnosticData::OperationNotAllowed { .. } => {
+                report.with_label_opt(main_location, "this document is not allowed");
+                report
+                    .with_help("only documents registered as persisted operations can be executed");
+            }
+            DiagnosticData::OperationNotFound { .. } => {}
+            DiagnosticData::NamingConvention { expected, .. } => {
+                report.with_label_opt(main_location, format_args!("rename to `{expected}`"));
+            }
+            DiagnosticData::MissingDescription { .. } => {
+                report.with_label_opt(main_location, "add a description before this definition");
+            }
+            DiagnosticData::RedundantConditionalDirective { .. } => {
+                report.with_label_opt(main_location, "remove this directive");
+            }
+            DiagnosticData::UnnamedOperation { .. } => {
+                report.with_label_opt(main_location, "add a name to this operation");
+            }
+            DiagnosticData::OperationNamePattern { .. } => {
+                report.with_label_opt(main_location, "rename this operation");
+            }
+            DiagnosticData::ContradictoryConditionalDirectives { variable, include } => {
+                report.with_label_opt(
+                    *include,
+                    format_args!("included when `${variable}` is true"),
+                );
+                report.with_label_opt(
+                    main_location,
+                    format_args!("skipped when `${variable}` is true"),
+                );
+                report.with_help("remove the selection, or one of the directives");
+            }
+            DiagnosticData::UnreachableSelection {
+                type_condition,
+                enclosing_conditions,
+            } => {
+                for (enclosing, location) in enclosing_conditions {
+                    report.with_label_opt(
+                        *location,
+                        format_args!("only matches types that are `{enclosing}`"),
+                    );
+                }
+                report.with_label_opt(
+                    main_location,
+                    format_args!("no such type is `{type_condition}`"),
+                );
+            }
+            DiagnosticData::CaseInsensitiveNameCollision { other, .. } => {
+                report.with_label_opt(other.location(), format_args!("`{other}` defined here"));
+                report.with_label_opt(main_location, "differs only by case");
+                report.with_help("rename one of them, or remove one if they are the same");
+            }
+            DiagnosticData::RepeatedDirectiveArguments {
+                name,
+                original_application,
+            } => {
+                report.with_label_opt(
+                    *original_application,
+                    format_args!("`@{name}` first applied here"),
+                );
+                report.with_label_opt(main_location, "applied again with the same arguments");
+                report.with_help("remove the repeated application");
+            }
+            DiagnosticData::NullPropagation { nulled, .. } => match nulled {
+                Some(nulled) => report.with_label_opt(
+                    main_location,
+                    format_args!("an error here sets `{nulled}` to null"),
+                ),
+                None => report.with_label_opt(
+                    main_location,
+                    "an error here sets the entire response `data` to null",
+                ),
+            },
+            DiagnosticData::DisallowedInFieldSet { describe, .. } => {
+                report.with_label_opt(main_location, format_args!("remove {describe} here"));
+            }
+            DiagnosticData::InvalidSpecifiedByUrl { url, .. } => {
+                report.with_label_opt(main_location, format_args!("invalid URL `{url}`"));
+                report.with_help("use a full URL including a scheme, such as `https://`");
+            }
+            DiagnosticData::MissingTypename {
+                uncovered_types, ..
+            } => {
+                report.with_label_opt(main_location, "add `__typename` to this selection set");
+                report.with_help(format_args!(
+                    "no fragment selects from {}",
+                    uncovered_types
+                        .iter()
+                        .map(|ty| format!("`{ty}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+        }
+        if let Some(path) = self.value_path().filter(|path| path.is_nested()) {
+            report.with_note(format_args!("input path `{path}`"));
+        }
+    }
+
+    /// Returns whether this diagnostic comes from [`Schema::lint`][crate::Schema::lint]
+    pub(crate) fn is_lint(&self) -> bool {
+        matches!(
+            self,
+            DiagnosticData::NamingConvention { .. }
+                | DiagnosticData::MissingDescription { .. }
+                | DiagnosticData::CaseInsensitiveNameCollision { .. }
+                | DiagnosticData::RedundantConditionalDirective { .. }
+                | DiagnosticData::ContradictoryConditionalDirectives { .. }
+                | DiagnosticData::UnnamedOperation { .. }
+                | DiagnosticData::OperationNamePattern { .. }
+        )
+    }
+
+    /// Returns whether this diagnostic is a warning rather than an error
+    pub(crate) fn is_warning(&self) -> bool {
+        matches!(
+            self,
+            DiagnosticData::DeprecatedUsage { .. }
+                | DiagnosticData::RepeatedDirectiveArguments { .. }
+                | DiagnosticData::UnreachableSelection { .. }
+                | DiagnosticData::NullPropagation { .. }
+        )
+    }
+
+    pub(crate) fn coordinate(&self) -> Option<SchemaCoordinate> {
+        match self {
+            DiagnosticData::UndefinedArgument { coordinate, .. }
+            | DiagnosticData::RequiredArgument { coordinate, .. }
+            | DiagnosticData::DeprecatedUsage { coordinate, .. }
+            | DiagnosticData::DeprecatedRequiredInput { coordinate, .. } => {
+                Some(coordinate.clone())
+            }
+            DiagnosticData::RequiredField { coordinate, .. }
+            | DiagnosticData::MissingSubselection { coordinate, .. }
+            | DiagnosticData::MissingTypename { coordinate, .. } => Some(coordinate.clone().into()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn suggestions(&self) -> &[Name] {
+        match self {
+            DiagnosticData::UndefinedArgument { suggestions, .. }
+            | DiagnosticData::UndefinedDefinition { suggestions, .. }
+            | DiagnosticData::UndefinedDirective { suggestions, .. } => suggestions,
+            _ => &[],
+        }
+    }
+
+    pub(crate) fn value_path(&self) -> Option<&ValuePath> {
+        match self {
+            DiagnosticData::UndefinedEnumValue { path, .. }
+            | DiagnosticData::UndefinedInputValue { path, .. }
+            | DiagnosticData::RequiredField { path, .. }
+            | DiagnosticData::UnsupportedValueType { path, .. }
+            | DiagnosticData::IntCoercionError { path, .. }
+            | DiagnosticData::FloatCoercionError { path, .. }
+            | DiagnosticData::InvalidScalarValue { path, .. }
+            | DiagnosticData::OneOfFieldCount { path, .. }
+            | DiagnosticData::NullOneOfField { path, .. } => Some(path),
+            _ => None,
         }
     }
 
@@ -4,26 +4,36 @@ use crate::collections::HashMap;
 use crate::collections::HashSet;
 use crate::coordinate::DirectiveArgumentCoordinate;
 use crate::coordinate::DirectiveCoordinate;
+use crate::executable;
 use crate::schema;
 use crate::validation::diagnostics::DiagnosticData;
+use crate::validation::suggestion::suggestions;
 use crate::validation::DiagnosticList;
 use crate::validation::RecursionGuard;
 use crate::validation::RecursionStack;
 use crate::validation::SourceSpan;
+use crate::ExecutableDocument;
+use crate::Name;
 use crate::Node;
+use std::sync::OnceLock;
 
 /// This struct just groups functions that are used to find self-referential directives.
 /// The way to use it is to call `FindRecursiveDirective::check`.
 struct FindRecursiveDirective<'s> {
     schema: &'s schema::Schema,
+    /// Input types can refer to themselves, so each type is only visited once
+    visited_types: HashSet<&'s Name>,
 }
 
-impl FindRecursiveDirective<'_> {
+impl<'s> FindRecursiveDirective<'s> {
     fn type_definition(
-        &self,
+        &mut self,
         seen: &mut RecursionGuard<'_>,
-        def: &schema::ExtendedType,
+        def: &'s schema::ExtendedType,
     ) -> Result<(), CycleError<ast::Directive>> {
+        if !self.visited_types.insert(def.name()) {
+            return Ok(());
+        }
         match def {
             schema::ExtendedType::Scalar(scalar_type_definition) => {
                 self.directives(seen, &scalar_type_definition.directives)?;
@@ -55,7 +65,7 @@ impl FindRecursiveDirective<'_> {
     }
 
     fn input_value(
-        &self,
+        &mut self,
         seen: &mut RecursionGuard<'_>,
         input_value: &Node<ast::InputValueDefinition>,
     ) -> Result<(), CycleError<ast::Directive>> {
@@ -72,7 +82,7 @@ impl FindRecursiveDirective<'_> {
     }
 
     fn enum_value(
-        &self,
+        &mut self,
         seen: &mut RecursionGuard<'_>,
         enum_value: &Node<ast::EnumValueDefinition>,
     ) -> Result<(), CycleError<ast::Directive>> {
@@ -84,7 +94,7 @@ impl FindRecursiveDirective<'_> {
     }
 
     fn directives(
-        &self,
+        &mut self,
         seen: &mut RecursionGuard<'_>,
         directives: &[schema::Component<ast::Directive>],
     ) -> Result<(), CycleError<ast::Directive>> {
@@ -95,7 +105,7 @@ impl FindRecursiveDirective<'_> {
     }
 
     fn directive(
-        &self,
+        &mut self,
         seen: &mut RecursionGuard<'_>,
         directive: &Node<ast::Directive>,
     ) -> Result<(), CycleError<ast::Directive>> {
@@ -116,7 +126,7 @@ impl FindRecursiveDirective<'_> {
     }
 
     fn directive_definition(
-        &self,
+        &mut self,
         mut seen: RecursionGuard<'_>,
         def: &Node<ast::DirectiveDefinition>,
     ) -> Result<(), CycleError<ast::Directive>> {
@@ -130,10 +140,15 @@ impl FindRecursiveDirective<'_> {
     fn check(
         schema: &schema::Schema,
         directive_def: &Node<ast::DirectiveDefinition>,
+        limit: usize,
     ) -> Result<(), CycleError<ast::Directive>> {
-        let mut recursion_stack = RecursionStack::with_root(directive_def.name.clone());
-        FindRecursiveDirective { schema }
-            .directive_definition(recursion_stack.guard(), directive_def)
+        let mut recursion_stack =
+            RecursionStack::with_root(directive_def.name.clone()).with_limit(limit);
+        FindRecursiveDirective {
+            schema,
+            visited_types: HashSet::default(),
+        }
+        .directive_definition(recursion_stack.guard(), directive_def)
     }
 }
 
@@ -141,6 +156,7 @@ pub(crate) fn validate_directive_definition(
     diagnostics: &mut DiagnosticList,
     schema: &crate::Schema,
     def: &Node<ast::DirectiveDefinition>,
+    nesting_limit: usize,
 ) {
     super::input_object::validate_argument_definitions(
         diagnostics,
@@ -148,6 +164,9 @@ pub(crate) fn validate_directive_definition(
         &def.arguments,
         ast::DirectiveLocation::ArgumentDefinition,
     );
+    for argument in &def.arguments {
+        super::value::validate_argument_default_value(diagnostics, schema, argument);
+    }
 
     let head_location = SourceSpan::recompose(def.location(), def.name.location());
 
@@ -155,7 +174,7 @@ pub(crate) fn validate_directive_definition(
     // references itself directly.
     //
     // Returns Recursive Definition error.
-    match FindRecursiveDirective::check(schema, def) {
+    match FindRecursiveDirective::check(schema, def, nesting_limit) {
         Ok(_) => {}
         Err(CycleError::Recursed(trace)) => {
             diagnostics.push(
@@ -179,10 +198,12 @@ pub(crate) fn validate_directive_definition(
 pub(crate) fn validate_directive_definitions(
     diagnostics: &mut DiagnosticList,
     schema: &crate::Schema,
+    nesting_limit: usize,
 ) {
-    for directive_definition in schema.directive_definitions.values() {
-        validate_directive_definition(diagnostics, schema, directive_definition);
-    }
+    let definitions: Vec<_> = schema.directive_definitions.values().collect();
+    diagnostics.validate_each(&definitions, |diagnostics, directive_definition| {
+        validate_directive_definition(diagnostics, schema, directive_definition, nesting_limit)
+    });
 }
 
 // TODO(@goto-bus-stop) This is a big function: should probably not be generic over the iterator
@@ -201,8 +222,20 @@ pub(crate) fn validate_directives<'dir>(
 
         let name = &dir.name;
         let loc = dir.location();
-        let directive_definition =
-            schema.and_then(|schema| Some((schema, schema.directive_definitions.get(name)?)));
+        let directive_definition = match schema {
+            Some(schema) => schema
+                .directive_definitions
+                .get(name)
+                .map(|definition| (schema, definition)),
+            // Without a schema, only built-in directives such as `@skip` are known
+            None => {
+                let built_ins = built_in_definitions();
+                built_ins
+                    .directive_definitions
+                    .get(name)
+                    .map(|definition| (built_ins, definition))
+            }
+        };
 
         if let Some(&original_loc) = seen_directives.get(name) {
             let is_repeatable = directive_definition
@@ -275,6 +308,10 @@ pub(crate) fn validate_directives<'dir>(
                             }
                             .into(),
                             definition_location: loc,
+                            suggestions: suggestions(
+                                &argument.name,
+                                directive_definition.arguments.iter().map(|arg| &arg.name),
+                            ),
                         },
                     );
                 }
@@ -308,11 +345,186 @@ pub(crate) fn validate_directives<'dir>(
                     );
                 }
             }
-        } else {
+        } else if let Some(schema) = schema {
             diagnostics.push(
                 loc,
-                DiagnosticData::UndefinedDirective { name: name.clone() },
+                DiagnosticData::UndefinedDirective {
+                    name: name.clone(),
+                    suggestions: suggestions(name, schema.directive_definitions.keys()),
+                },
             )
         }
     }
 }
+
+/// A schema with only built-in definitions, to validate uses of built-in directives
+/// in executable documents validated without a schema
+fn built_in_definitions() -> &'static crate::Schema {
+    static BUILT_IN: OnceLock<crate::Schema> = OnceLock::new();
+    BUILT_IN.get_or_init(crate::Schema::new)
+}
+
+/// Returns whether `name` is a directive defined in every schema, such as `@skip`
+pub(crate) fn is_built_in_directive(name: &str) -> bool {
+    built_in_definitions()
+        .directive_definitions
+        .contains_key(name)
+}
+
+/// Report repeatable directives applied more than once to the same element
+/// with the same arguments, such as `@tag(name: "x") @tag(name: "x")`.
+///
+/// This is valid GraphQL, but usually a copy-paste mistake.
+/// Repeated non-repeatable directives are reported by [`validate_directives`] instead.
+fn validate_repeated_directive_arguments<'dir>(
+    diagnostics: &mut DiagnosticList,
+    schema: &crate::Schema,
+    dirs: impl Iterator<Item = &'dir Node<ast::Directive>>,
+) {
+    let mut seen: Vec<&Node<ast::Directive>> = Vec::new();
+    for dir in dirs {
+        let is_repeatable = schema
+            .directive_definitions
+            .get(&dir.name)
+            .is_some_and(|def| def.repeatable);
+        if !is_repeatable {
+            continue;
+        }
+        let same_arguments = |other: &&&Node<ast::Directive>| {
+            other.name == dir.name
+                && other.arguments.len() == dir.arguments.len()
+                && other
+                    .arguments
+                    .iter()
+                    .all(|arg| dir.arguments.contains(arg))
+        };
+        if let Some(original) = seen.iter().find(same_arguments) {
+            diagnostics.push(
+                dir.location(),
+                DiagnosticData::RepeatedDirectiveArguments {
+                    name: dir.name.clone(),
+                    original_application: original.location(),
+                },
+            );
+        } else {
+            seen.push(dir);
+        }
+    }
+}
+
+/// Run [`validate_repeated_directive_arguments`] on every directive list of a schema
+pub(crate) fn validate_repeated_directives_in_schema(
+    diagnostics: &mut DiagnosticList,
+    schema: &crate::Schema,
+) {
+    walk_schema_directives(schema, |directives| {
+        validate_repeated_directive_arguments(diagnostics, schema, directives)
+    })
+}
+
+/// Run [`validate_repeated_directive_arguments`] on every directive list of an executable document
+pub(crate) fn validate_repeated_directives_in_document(
+    diagnostics: &mut DiagnosticList,
+    schema: &crate::Schema,
+    document: &ExecutableDocument,
+) {
+    walk_document_directives(document, |directives| {
+        validate_repeated_directive_arguments(diagnostics, schema, directives)
+    })
+}
+
+/// Call `visit` with every directive list of a schema, except those of built-in types
+pub(crate) fn walk_schema_directives<'schema>(
+    schema: &'schema crate::Schema,
+    mut visit: impl FnMut(&mut dyn Iterator<Item = &'schema Node<ast::Directive>>),
+) {
+    fn visit_arguments<'schema>(
+        visit: &mut impl FnMut(&mut dyn Iterator<Item = &'schema Node<ast::Directive>>),
+        arguments: &'schema [Node<ast::InputValueDefinition>],
+    ) {
+        for argument in arguments {
+            visit(&mut argument.directives.iter());
+        }
+    }
+
+    visit(
+        &mut schema
+            .schema_definition
+            .directives
+            .iter()
+            .map(|component| &component.node),
+    );
+    for definition in schema.directive_definitions.values() {
+        visit_arguments(&mut visit, &definition.arguments);
+    }
+    for ty in schema.types.values() {
+        if ty.is_built_in() {
+            continue;
+        }
+        visit(&mut ty.directives().iter().map(|component| &component.node));
+        match ty {
+            schema::ExtendedType::Object(object) => {
+                for field in object.fields.values() {
+                    visit(&mut field.directives.iter());
+                    visit_arguments(&mut visit, &field.arguments);
+                }
+            }
+            schema::ExtendedType::Interface(interface) => {
+                for field in interface.fields.values() {
+                    visit(&mut field.directives.iter());
+                    visit_arguments(&mut visit, &field.arguments);
+                }
+            }
+            schema::ExtendedType::Enum(enum_) => {
+                for value in enum_.values.values() {
+                    visit(&mut value.directives.iter());
+                }
+            }
+            schema::ExtendedType::InputObject(input_object) => {
+                for field in input_object.fields.values() {
+                    visit(&mut field.directives.iter());
+                }
+            }
+            schema::ExtendedType::Scalar(_) | schema::ExtendedType::Union(_) => {}
+        }
+    }
+}
+
+/// Call `visit` with every directive list of an executable document
+pub(crate) fn walk_document_directives<'doc>(
+    document: &'doc ExecutableDocument,
+    mut visit: impl FnMut(&mut dyn Iterator<Item = &'doc Node<ast::Directive>>),
+) {
+    fn walk_selection_set<'doc>(
+        visit: &mut impl FnMut(&mut dyn Iterator<Item = &'doc Node<ast::Directive>>),
+        selection_set: &'doc executable::SelectionSet,
+    ) {
+        for selection in &selection_set.selections {
+            match selection {
+                executable::Selection::Field(field) => {
+                    visit(&mut field.directives.iter());
+                    walk_selection_set(visit, &field.selection_set);
+                }
+                executable::Selection::FragmentSpread(spread) => {
+                    visit(&mut spread.directives.iter());
+                }
+                executable::Selection::InlineFragment(inline) => {
+                    visit(&mut inline.directives.iter());
+                    walk_selection_set(visit, &inline.selection_set);
+                }
+            }
+        }
+    }
+
+    for operation in document.operations.iter() {
+        visit(&mut operation.directives.iter());
+        for variable in &operation.variables {
+            visit(&mut variable.directives.iter());
+        }
+        walk_selection_set(&mut visit, &operation.selection_set);
+    }
+    for fragment in document.fragments.values() {
+        visit(&mut fragment.directives.iter());
+        walk_selection_set(&mut visit, &fragment.selection_set);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::collections::IndexMap;
+use crate::validation::DiagnosticList;
+use crate::validation::Valid;
+use crate::validation::ValidationConfig;
+use crate::ExecutableDocument;
+use crate::Schema;
+use std::hash::Hash;
+
+/// Executable documents validated against one schema, such as the open files of an editor,
+/// with the diagnostics of each document memoized separately.
+///
+/// The diagnostics of a document only depend on that document, the schema, and the config:
+///
+/// * [`insert`][Self::insert] or [`remove`][Self::remove] of a document
+///   only discards the memoized diagnostics of that document.
+/// * [`set_schema`][Self::set_schema] and [`set_config`][Self::set_config]
+///   discard the diagnostics of every document.
+///
+/// Documents are validated lazily, the first time their diagnostics are requested after a change.
+///
+/// ```rust
+/// use apollo_compiler::validation::DocumentSet;
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+/// let parse = |source_text| ExecutableDocument::parse(&schema, source_text, "a.graphql").unwrap();
+/// let mut documents = DocumentSet::new(schema.clone());
+/// documents.insert("a.graphql", parse("{ a }"));
+/// documents.insert("b.graphql", parse("query($unused: Int) { a }"));
+/// assert!(documents.diagnostics(&"a.graphql").unwrap().is_empty());
+/// assert_eq!(documents.diagnostics(&"b.graphql").unwrap().len(), 1);
+///
+/// // Only the changed document is validated again
+/// documents.insert("b.graphql", parse("{ a }"));
+/// assert!(documents.is_validated(&"a.graphql"));
+/// assert!(!documents.is_validated(&"b.graphql"));
+/// assert!(documents.diagnostics(&"b.graphql").unwrap().is_empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct DocumentSet<K> {
+    schema: Valid<Schema>,
+    config: ValidationConfig,
+    documents: IndexMap<K, Entry>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    document: ExecutableDocument,
+    /// `None` until validated after the last change
+    diagnostics: Option<DiagnosticList>,
+}
+
+impl<K: Hash + Eq> DocumentSet<K> {
+    /// Create an empty set of documents to validate against `schema`
+    pub fn new(schema: Valid<Schema>) -> Self {
+        Self {
+            schema,
+            config: ValidationConfig::default(),
+            documents: IndexMap::default(),
+        }
+    }
+
+    /// Returns the schema that documents are validated against
+    pub fn schema(&self) -> &Valid<Schema> {
+        &self.schema
+    }
+
+    /// Replace the schema. Every document is validated again.
+    pub fn set_schema(&mut self, schema: Valid<Schema>) {
+        self.schema = schema;
+        self.invalidate_all()
+    }
+
+    /// Replace the configuration that documents are validated with.
+    /// Every document is validated again.
+    pub fn set_config(&mut self, config: ValidationConfig) {
+        self.config = config;
+        self.invalidate_all()
+    }
+
+    /// Add or replace the document with the given key.
+    /// Only that document is validated again.
+    ///
+    /// Returns the previous document with that key, if any.
+    pub fn insert(&mut self, key: K, document: ExecutableDocument) -> Option<ExecutableDocument> {
+        let entry = Entry {
+            document,
+            diagnostics: None,
+        };
+        self.documents
+            .insert(key, entry)
+            .map(|previous| previous.document)
+    }
+
+    /// Remove the document with the given key, and its diagnostics.
+    /// Other documents are not affected.
+    pub fn remove(&mut self, key: &K) -> Option<ExecutableDocument> {
+        self.documents.shift_remove(key).map(|entry| entry.document)
+    }
+
+    /// Returns the document with the given key, if any
+    pub fn get(&self, key: &K) -> Option<&ExecutableDocument> {
+        self.documents.get(key).map(|entry| &entry.document)
+    }
+
+    /// Returns whether the diagnostics of the document with the given key are memoized,
+    /// so that [`diagnostics`][Self::diagnostics] does not validate it again.
+    pub fn is_validated(&self, key: &K) -> bool {
+        self.documents
+            .get(key)
+            .is_some_and(|entry| entry.diagnostics.is_some())
+    }
+
+    /// Returns diagnostics of the document with the given key,
+    /// as with [`ExecutableDocument::validation_diagnostics`].
+    ///
+    /// The document is only validated if it changed since the last call,
+    /// or if the schema or config did.
+    pub fn diagnostics(&mut self, key: &K) -> Option<&DiagnosticList> {
+        let entry = self.documents.get_mut(key)?;
+        Some(entry.diagnostics.get_or_insert_with(|| {
+            entry
+                .document
+                .validation_diagnostics(&self.schema, &self.config)
+        }))
+    }
+
+    /// Returns the diagnostics of every document, in insertion order,
+    /// validating those that changed.
+    pub fn all_diagnostics(&mut self) -> impl Iterator<Item = (&K, &DiagnosticList)> {
+        for entry in self.documents.values_mut() {
+            if entry.diagnostics.is_none() {
+                entry.diagnostics = Some(
+                    entry
+                        .document
+                        .validation_diagnostics(&self.schema, &self.config),
+                )
+            }
+        }
+        self.documents
+            .iter()
+            .filter_map(|(key, entry)| Some((key, entry.diagnostics.as_ref()?)))
+    }
+
+    fn invalidate_all(&mut self) {
+        for entry in self.documents.values_mut() {
+            entry.diagnostics = None
+        }
+    }
+}
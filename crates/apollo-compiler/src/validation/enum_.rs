@@ -6,11 +6,17 @@ use crate::validation::DiagnosticList;
 use crate::Node;
 
 pub(crate) fn validate_enum_definitions(diagnostics: &mut DiagnosticList, schema: &crate::Schema) {
-    for ty in schema.types.values() {
-        if let ExtendedType::Enum(enum_) = ty {
-            validate_enum_definition(diagnostics, schema, enum_);
-        }
-    }
+    let definitions: Vec<_> = schema
+        .types
+        .values()
+        .filter_map(|ty| match ty {
+            ExtendedType::Enum(enum_) => Some(enum_),
+            _ => None,
+        })
+        .collect();
+    diagnostics.validate_each(&definitions, |diagnostics, enum_| {
+        validate_enum_definition(diagnostics, schema, enum_)
+    });
 }
 
 pub(crate) fn validate_enum_definition(
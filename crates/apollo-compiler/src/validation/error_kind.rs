@@ -0,0 +1,224 @@
+use crate::schema::BuildError as SchemaBuildError;
+use crate::validation::ValidationRule;
+use serde::Deserialize;
+use serde::Serialize;
+use std::fmt;
+
+/// Which invariant of a valid schema or document a diagnostic reports as broken,
+/// as returned by [`DiagnosticData::kind`][crate::validation::DiagnosticData::kind].
+///
+/// Kinds group related [rules][crate::validation::DiagnosticData::rule]
+/// so that services can handle or count errors without matching on messages.
+/// New kinds may be added, and rules may move to a more specific kind, in minor releases.
+///
+/// ```rust
+/// use apollo_compiler::validation::ValidationErrorKind;
+/// use apollo_compiler::Schema;
+///
+/// let errors = Schema::parse_and_validate("type Query { a: Missing }", "schema.graphql")
+///     .unwrap_err()
+///     .errors;
+/// let error = errors.iter().next().unwrap();
+/// assert_eq!(error.error.kind(), ValidationErrorKind::UndefinedType);
+/// assert_eq!(error.error.kind().to_string(), "undefinedType");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum ValidationErrorKind {
+    /// The source text is not syntactically valid GraphQL
+    Syntax,
+    /// A limit on size, nesting, or validation work was reached
+    Limit,
+    /// A root operation type is missing, undefined, or not an object type
+    RootOperationType,
+    /// A type is referenced but not defined, including by a type extension
+    UndefinedType,
+    /// A selected field is not defined on its parent type
+    UndefinedField,
+    /// An argument or input object field is not defined
+    UndefinedArgument,
+    /// A directive is applied but not defined
+    UndefinedDirective,
+    /// A fragment is spread but not defined
+    UndefinedFragment,
+    /// A variable is used but not defined by the operation
+    UndefinedVariable,
+    /// Two definitions, arguments, or directives have the same name where names must be unique
+    NameCollision,
+    /// A type is used where a different kind of type is required,
+    /// such as an output type as an argument type
+    WrongTypeKind,
+    /// A definition is not allowed or is incomplete, such as an empty object type
+    InvalidDefinition,
+    /// An object or interface does not correctly implement its interfaces
+    InvalidImplementation,
+    /// Definitions refer to themselves in a way that cannot be resolved
+    Cycle,
+    /// A selection cannot be executed as written,
+    /// such as a missing sub-selection or conflicting fields with the same response key
+    InvalidSelection,
+    /// A value does not have the type expected where it is used
+    InvalidValue,
+    /// A required argument or input object field is missing
+    MissingRequiredValue,
+    /// A directive is applied in a location or way it does not support
+    InvalidDirectiveUsage,
+    /// A variable or fragment is defined but never used
+    Unused,
+    /// A supergraph schema has inconsistent federation metadata
+    Supergraph,
+    /// Subgraph schemas are inconsistent and cannot be composed
+    Composition,
+    /// A style convention is not followed, as checked by lints
+    Lint,
+    /// Any other rule, such as opt-in policies configured with
+    /// [`ValidationConfig`][crate::validation::ValidationConfig]
+    Other,
+}
+
+/// Formats as the serialized name, like `undefinedType`, for use as a log field
+impl fmt::Display for ValidationErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Syntax => "syntax",
+            Self::Limit => "limit",
+            Self::RootOperationType => "rootOperationType",
+            Self::UndefinedType => "undefinedType",
+            Self::UndefinedField => "undefinedField",
+            Self::UndefinedArgument => "undefinedArgument",
+            Self::UndefinedDirective => "undefinedDirective",
+            Self::UndefinedFragment => "undefinedFragment",
+            Self::UndefinedVariable => "undefinedVariable",
+            Self::NameCollision => "nameCollision",
+            Self::WrongTypeKind => "wrongTypeKind",
+            Self::InvalidDefinition => "invalidDefinition",
+            Self::InvalidImplementation => "invalidImplementation",
+            Self::Cycle => "cycle",
+            Self::InvalidSelection => "invalidSelection",
+            Self::InvalidValue => "invalidValue",
+            Self::MissingRequiredValue => "missingRequiredValue",
+            Self::InvalidDirectiveUsage => "invalidDirectiveUsage",
+            Self::Unused => "unused",
+            Self::Supergraph => "supergraph",
+            Self::Composition => "composition",
+            Self::Lint => "lint",
+            Self::Other => "other",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Returns the kind of errors emitted by `rule`
+pub(crate) const fn rule_kind(rule: ValidationRule) -> ValidationErrorKind {
+    use ValidationErrorKind::*;
+    match rule {
+        ValidationRule::MaxDepth
+        | ValidationRule::IntrospectionDepth
+        | ValidationRule::IntrospectionFieldCount
+        | ValidationRule::TooManyAliases
+        | ValidationRule::TooManyRepeatedFields => Limit,
+        ValidationRule::QueryRootOperationType
+        | ValidationRule::RootOperationObjectType
+        | ValidationRule::UndefinedRootOperation => RootOperationType,
+        ValidationRule::UndefinedDefinition
+        | ValidationRule::UndefinedTypeInNamedFragmentTypeCondition
+        | ValidationRule::UndefinedTypeInInlineFragmentTypeCondition => UndefinedType,
+        ValidationRule::UndefinedField => UndefinedField,
+        ValidationRule::UndefinedArgument | ValidationRule::UndefinedInputValue => {
+            UndefinedArgument
+        }
+        ValidationRule::UndefinedDirective => UndefinedDirective,
+        ValidationRule::UndefinedFragment => UndefinedFragment,
+        ValidationRule::UndefinedVariable => UndefinedVariable,
+        ValidationRule::UniqueVariable
+        | ValidationRule::UniqueArgument
+        | ValidationRule::UniqueInputValue
+        | ValidationRule::UniqueIncrementalLabel
+        | ValidationRule::OperationNameCollision
+        | ValidationRule::FragmentNameCollision => NameCollision,
+        ValidationRule::OutputType
+        | ValidationRule::InputType
+        | ValidationRule::VariableInputType
+        | ValidationRule::UnionMemberObjectType
+        | ValidationRule::InvalidFragmentTarget => WrongTypeKind,
+        ValidationRule::EmptyFieldSet
+        | ValidationRule::EmptyValueSet
+        | ValidationRule::EmptyMemberSet
+        | ValidationRule::EmptyInputValueSet
+        | ValidationRule::NonNullOneOfField
+        | ValidationRule::OneOfFieldDefault
+        | ValidationRule::DeprecatedRequiredInput
+        | ValidationRule::TypeSystemDefinition
+        | ValidationRule::OperationInFragmentLibrary
+        | ValidationRule::AmbiguousAnonymousOperation => InvalidDefinition,
+        ValidationRule::MissingInterfaceField | ValidationRule::TransitiveImplementedInterfaces => {
+            InvalidImplementation
+        }
+        ValidationRule::RecursiveDirectiveDefinition
+        | ValidationRule::RecursiveInterfaceDefinition
+        | ValidationRule::RecursiveInputObjectDefinition
+        | ValidationRule::RecursiveFragmentDefinition => Cycle,
+        ValidationRule::MissingSubselection
+        | ValidationRule::SubselectionOnScalarType
+        | ValidationRule::SubselectionOnEnumType
+        | ValidationRule::InvalidFragmentSpread
+        | ValidationRule::UnreachableSelection
+        | ValidationRule::SubscriptionUsesMultipleFields
+        | ValidationRule::SubscriptionUsesIntrospection
+        | ValidationRule::ConflictingFieldType
+        | ValidationRule::ConflictingFieldName
+        | ValidationRule::ConflictingFieldArgument
+        | ValidationRule::DeferOnSubscriptionRoot
+        | ValidationRule::StreamOnNonListField
+        | ValidationRule::DisallowedInFieldSet => InvalidSelection,
+        ValidationRule::UnsupportedValueType
+        | ValidationRule::IntCoercionError
+        | ValidationRule::FloatCoercionError
+        | ValidationRule::UndefinedEnumValue
+        | ValidationRule::InvalidScalarValue
+        | ValidationRule::DisallowedVariableUsage
+        | ValidationRule::OneOfFieldCount
+        | ValidationRule::NullOneOfField
+        | ValidationRule::NullableVariableInOneOf
+        | ValidationRule::NegativeInitialCount
+        | ValidationRule::VariableIncrementalLabel
+        | ValidationRule::InvalidSpecifiedByUrl => InvalidValue,
+        ValidationRule::RequiredArgument | ValidationRule::RequiredField => MissingRequiredValue,
+        ValidationRule::UnsupportedLocation
+        | ValidationRule::UniqueDirective
+        | ValidationRule::InvalidDirectiveApplication
+        | ValidationRule::RepeatedDirectiveArguments => InvalidDirectiveUsage,
+        ValidationRule::UnusedVariable | ValidationRule::UnusedFragment => Unused,
+        ValidationRule::OperationNotFound
+        | ValidationRule::MissingTypename
+        | ValidationRule::IntrospectionDisabled
+        | ValidationRule::OperationNotAllowed
+        | ValidationRule::DeprecatedUsage
+        | ValidationRule::NullPropagation => Other,
+    }
+}
+
+/// Returns the kind of an error found while building a schema from its definitions
+pub(crate) fn schema_build_error_kind(error: &SchemaBuildError) -> ValidationErrorKind {
+    use ValidationErrorKind::*;
+    match error {
+        SchemaBuildError::ExecutableDefinition { .. }
+        | SchemaBuildError::BuiltInScalarTypeRedefinition
+        | SchemaBuildError::OrphanSchemaExtension => InvalidDefinition,
+        SchemaBuildError::OrphanTypeExtension { .. } => UndefinedType,
+        SchemaBuildError::TypeExtensionKindMismatch { .. } => WrongTypeKind,
+        SchemaBuildError::DuplicateRootOperation { .. } => RootOperationType,
+        SchemaBuildError::SchemaDefinitionCollision { .. }
+        | SchemaBuildError::DirectiveDefinitionCollision { .. }
+        | SchemaBuildError::TypeDefinitionCollision { .. }
+        | SchemaBuildError::DuplicateDefinitionResolved { .. }
+        | SchemaBuildError::DuplicateImplementsInterfaceInObject { .. }
+        | SchemaBuildError::DuplicateImplementsInterfaceInInterface { .. }
+        | SchemaBuildError::ObjectFieldNameCollision { .. }
+        | SchemaBuildError::InterfaceFieldNameCollision { .. }
+        | SchemaBuildError::EnumValueNameCollision { .. }
+        | SchemaBuildError::UnionMemberNameCollision { .. }
+        | SchemaBuildError::InputFieldNameCollision { .. } => NameCollision,
+    }
+}
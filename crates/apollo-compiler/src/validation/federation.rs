@@ -0,0 +1,148 @@
+use crate::ast;
+use crate::executable;
+use crate::parser::Parser;
+use crate::parser::SourceMap;
+use crate::parser::SourceSpan;
+use crate::schema::ExtendedType;
+use crate::validation::diagnostics::DiagnosticData;
+use crate::validation::DiagnosticList;
+use crate::validation::Valid;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+use rowan::TextSize;
+
+/// Validate the `fields` argument of Apollo Federation `@key`, `@requires`, and `@provides`
+/// directives as a selection set:
+///
+/// * `@key` fields select from the type with the directive
+/// * `@requires` fields select from the type that has the field with the directive
+/// * `@provides` fields select from the type returned by the field with the directive
+///
+/// Aliases and directives are not allowed in any of them,
+/// and arguments are not allowed in `@key` fields.
+///
+/// Only run when enabled with [`ValidationConfig::federation_field_sets`][crate::validation::ValidationConfig::federation_field_sets].
+pub(crate) fn validate_federation_field_sets(diagnostics: &mut DiagnosticList, schema: &Schema) {
+    for ty in schema.types.values() {
+        if ty.is_built_in() {
+            continue;
+        }
+        let fields = match ty {
+            ExtendedType::Object(object) => &object.fields,
+            ExtendedType::Interface(interface) => &interface.fields,
+            _ => continue,
+        };
+        for key in ty.directives().get_all("key") {
+            validate_field_set_argument(diagnostics, schema, key, ty.name());
+        }
+        for field in fields.values() {
+            for requires in field.directives.get_all("requires") {
+                validate_field_set_argument(diagnostics, schema, requires, ty.name());
+            }
+            for provides in field.directives.get_all("provides") {
+                validate_field_set_argument(
+                    diagnostics,
+                    schema,
+                    provides,
+                    field.ty.inner_named_type(),
+                );
+            }
+        }
+    }
+}
+
+fn validate_field_set_argument(
+    diagnostics: &mut DiagnosticList,
+    schema: &Schema,
+    directive: &ast::Directive,
+    type_name: &Name,
+) {
+    // A missing or non-string argument is reported by directive validation
+    let Some(value) = directive.argument_by_name("fields") else {
+        return;
+    };
+    let Some(source_text) = value.as_str() else {
+        return;
+    };
+    // Some types are only defined in other subgraphs
+    if !schema.types.contains_key(type_name) {
+        return;
+    }
+    let sources = &schema.sources;
+    let schema = Valid::assume_valid_ref(schema);
+    let (field_set, mut errors) = Parser::new().parse_field_set_inner(
+        schema,
+        type_name.clone(),
+        source_text,
+        format!("@{}(fields:)", directive.name),
+    );
+    if !errors.has_errors() {
+        crate::executable::validation::validate_field_set(&mut errors, schema, &field_set);
+    }
+    check_disallowed_selections(&mut errors, &directive.name, &field_set.selection_set);
+
+    let string_start = string_contents_offset(sources, value, source_text);
+    for mut error in errors.diagnostics_data {
+        error.location = match (error.location, value.location(), string_start) {
+            (Some(location), Some(string), Some(start)) => Some(SourceSpan {
+                file_id: string.file_id,
+                text_range: location.text_range + TextSize::from(start),
+            }),
+            // Escape sequences and block strings make offsets in the value
+            // differ from offsets in the source, so point to the whole string instead
+            _ => value.location(),
+        };
+        diagnostics.diagnostics_data.push(error);
+    }
+}
+
+fn check_disallowed_selections(
+    diagnostics: &mut DiagnosticList,
+    directive_name: &Name,
+    selection_set: &executable::SelectionSet,
+) {
+    for selection in &selection_set.selections {
+        let executable::Selection::Field(field) = selection else {
+            // Fragments are reported by field set parsing
+            continue;
+        };
+        let mut disallowed = |location, describe| {
+            diagnostics.push(
+                location,
+                DiagnosticData::DisallowedInFieldSet {
+                    directive: directive_name.clone(),
+                    describe,
+                },
+            )
+        };
+        if let Some(alias) = &field.alias {
+            disallowed(alias.location(), "aliases");
+        }
+        for directive in &field.directives {
+            disallowed(directive.location(), "directives");
+        }
+        if directive_name == "key" {
+            for argument in &field.arguments {
+                disallowed(argument.location(), "arguments");
+            }
+        }
+        check_disallowed_selections(diagnostics, directive_name, &field.selection_set);
+    }
+}
+
+/// Returns the offset in the schema source of the first character inside the quotes
+/// of a string value, if the contents of the string appear there unchanged.
+fn string_contents_offset(
+    sources: &SourceMap,
+    value: &Node<ast::Value>,
+    contents: &str,
+) -> Option<u32> {
+    let location = value.location()?;
+    let source = sources.get(&location.file_id)?;
+    let raw = source
+        .source_text()
+        .get(location.offset()..location.end_offset())?;
+    let quoted = raw.strip_prefix('"')?.strip_suffix('"')?;
+    (quoted == contents && !raw.starts_with("\"\"\"")).then(|| location.offset() as u32 + 1)
+}
@@ -1,4 +1,5 @@
 use crate::ast;
+use crate::collections::HashSet;
 use crate::collections::IndexMap;
 use crate::coordinate::FieldArgumentCoordinate;
 use crate::coordinate::TypeAttributeCoordinate;
@@ -6,6 +7,8 @@ use crate::executable;
 use crate::schema;
 use crate::schema::Component;
 use crate::validation::diagnostics::DiagnosticData;
+use crate::validation::suggestion::suggestions;
+use crate::validation::suggestion::type_suggestions;
 use crate::validation::DiagnosticList;
 use crate::validation::OperationValidationContext;
 use crate::ExecutableDocument;
@@ -32,6 +35,15 @@ pub(crate) fn validate_field(
 
     super::argument::validate_arguments(diagnostics, &field.arguments);
 
+    if context.forbids_introspection() && matches!(field.name.as_str(), "__schema" | "__type") {
+        diagnostics.push(
+            field.location(),
+            DiagnosticData::IntrospectionDisabled {
+                field: field.name.clone(),
+            },
+        );
+    }
+
     // Return early if we don't know the type--this can happen if we are nested deeply
     // inside a selection set that has a wrong field, or if we are validating a standalone
     // operation without a schema.
@@ -75,6 +87,10 @@ pub(crate) fn validate_field(
                         }
                         .into(),
                         definition_location: loc,
+                        suggestions: suggestions(
+                            &argument.name,
+                            field_definition.arguments.iter().map(|arg| &arg.name),
+                        ),
                     },
                 );
             }
@@ -110,6 +126,48 @@ pub(crate) fn validate_field(
             }
         }
 
+        if context.deprecation_warnings() {
+            if let Some(reason) = deprecation_reason(&field_definition.directives) {
+                diagnostics.push(
+                    field.name.location(),
+                    DiagnosticData::DeprecatedUsage {
+                        coordinate: TypeAttributeCoordinate {
+                            ty: against_type.clone(),
+                            attribute: field.name.clone(),
+                        }
+                        .into(),
+                        reason,
+                        definition_location: field_definition.location(),
+                    },
+                );
+            }
+            for argument in &field.arguments {
+                if let Some(arg_definition) = field_definition.argument_by_name(&argument.name) {
+                    if let Some(reason) = deprecation_reason(&arg_definition.directives) {
+                        diagnostics.push(
+                            argument.name.location(),
+                            DiagnosticData::DeprecatedUsage {
+                                coordinate: FieldArgumentCoordinate {
+                                    ty: against_type.clone(),
+                                    field: field.name.clone(),
+                                    argument: argument.name.clone(),
+                                }
+                                .into(),
+                                reason,
+                                definition_location: arg_definition.location(),
+                            },
+                        );
+                    }
+                    validate_deprecated_values(
+                        diagnostics,
+                        schema,
+                        &arg_definition.ty,
+                        &argument.value,
+                    );
+                }
+            }
+        }
+
         if validate_leaf_field_selection(
             diagnostics,
             schema,
@@ -119,10 +177,21 @@ pub(crate) fn validate_field(
         )
         .is_ok()
         {
+            let field_type = field_definition.ty.inner_named_type();
+            if context.requires_typename(field_type) {
+                validate_typename_selected(
+                    diagnostics,
+                    document,
+                    schema,
+                    against_type,
+                    field,
+                    context,
+                );
+            }
             super::selection::validate_selection_set(
                 diagnostics,
                 document,
-                Some((schema, field_definition.ty.inner_named_type())),
+                Some((schema, field_type)),
                 &field.selection_set,
                 context,
             )
@@ -130,6 +199,191 @@ pub(crate) fn validate_field(
     }
 }
 
+/// Check that a field of union or interface type selects `__typename`,
+/// or has fragments that together cover all of the possible object types.
+fn validate_typename_selected(
+    diagnostics: &mut DiagnosticList,
+    document: &ExecutableDocument,
+    schema: &crate::Schema,
+    against_type: &ast::NamedType,
+    field: &Node<executable::Field>,
+    context: OperationValidationContext<'_>,
+) {
+    let abstract_type = &field.selection_set.ty;
+    let Some(type_definition) = schema.types.get(abstract_type) else {
+        return;
+    };
+    if !type_definition.is_interface() && !type_definition.is_union() {
+        return;
+    }
+
+    let mut selects_typename = false;
+    let mut covered = HashSet::default();
+    let mut seen_fragments = HashSet::default();
+    collect_typename_coverage(
+        document,
+        schema,
+        context,
+        &field.selection_set,
+        &mut selects_typename,
+        &mut covered,
+        &mut seen_fragments,
+    );
+    if selects_typename {
+        return;
+    }
+
+    let possible_types = super::fragment::get_possible_types(type_definition, &context);
+    let uncovered_types: Vec<_> = possible_types
+        .iter()
+        .filter(|ty| !covered.contains(*ty))
+        .cloned()
+        .collect();
+    if !uncovered_types.is_empty() {
+        diagnostics.push(
+            field.location(),
+            DiagnosticData::MissingTypename {
+                coordinate: TypeAttributeCoordinate {
+                    ty: against_type.clone(),
+                    attribute: field.name.clone(),
+                },
+                ty: abstract_type.clone(),
+                uncovered_types,
+            },
+        );
+    }
+}
+
+/// Walk the selections directly on the abstract type of `selection_set`,
+/// including those in fragments on that same type, looking for `__typename`.
+/// Fragments on other types mark their possible types as covered.
+fn collect_typename_coverage<'doc>(
+    document: &'doc ExecutableDocument,
+    schema: &crate::Schema,
+    context: OperationValidationContext<'_>,
+    selection_set: &'doc executable::SelectionSet,
+    selects_typename: &mut bool,
+    covered: &mut HashSet<ast::NamedType>,
+    seen_fragments: &mut HashSet<&'doc Name>,
+) {
+    for selection in &selection_set.selections {
+        let (type_condition, inner_set) = match selection {
+            executable::Selection::Field(field) => {
+                if field.name == "__typename" {
+                    *selects_typename = true;
+                }
+                continue;
+            }
+            executable::Selection::FragmentSpread(spread) => {
+                // Cycles and undefined fragments are reported by other rules
+                let Some(fragment) = document.fragments.get(&spread.fragment_name) else {
+                    continue;
+                };
+                if !seen_fragments.insert(&fragment.name) {
+                    continue;
+                }
+                (fragment.type_condition(), &fragment.selection_set)
+            }
+            executable::Selection::InlineFragment(inline) => (
+                inline.type_condition.as_ref().unwrap_or(&selection_set.ty),
+                &inline.selection_set,
+            ),
+        };
+        if *type_condition == selection_set.ty {
+            collect_typename_coverage(
+                document,
+                schema,
+                context,
+                inner_set,
+                selects_typename,
+                covered,
+                seen_fragments,
+            );
+        } else if let Some(definition) = schema.types.get(type_condition) {
+            covered.extend(
+                super::fragment::get_possible_types(definition, &context)
+                    .iter()
+                    .cloned(),
+            );
+        }
+    }
+}
+
+/// Returns the reason given by a `@deprecated` directive in `directives`, if there is one.
+fn deprecation_reason(directives: &ast::DirectiveList) -> Option<String> {
+    let directive = directives.get("deprecated")?;
+    let reason = directive
+        .argument_by_name("reason")
+        .and_then(|value| value.as_str())
+        // The default value of the `reason` argument
+        .unwrap_or("No longer supported");
+    Some(reason.to_owned())
+}
+
+/// Report enum values and input object fields marked `@deprecated`,
+/// possibly nested in lists and input objects.
+fn validate_deprecated_values(
+    diagnostics: &mut DiagnosticList,
+    schema: &crate::Schema,
+    ty: &ast::Type,
+    value: &Node<ast::Value>,
+) {
+    match value.as_ref() {
+        ast::Value::Enum(name) => {
+            let Some(enum_) = schema.get_enum(ty.inner_named_type()) else {
+                return;
+            };
+            let Some(definition) = enum_.values.get(name) else {
+                return;
+            };
+            if let Some(reason) = deprecation_reason(&definition.directives) {
+                diagnostics.push(
+                    value.location(),
+                    DiagnosticData::DeprecatedUsage {
+                        coordinate: TypeAttributeCoordinate {
+                            ty: enum_.name.clone(),
+                            attribute: name.clone(),
+                        }
+                        .into(),
+                        reason,
+                        definition_location: definition.location(),
+                    },
+                );
+            }
+        }
+        ast::Value::List(items) => {
+            for item in items {
+                validate_deprecated_values(diagnostics, schema, ty.item_type(), item);
+            }
+        }
+        ast::Value::Object(fields) => {
+            let Some(input_object) = schema.get_input_object(ty.inner_named_type()) else {
+                return;
+            };
+            for (name, value) in fields {
+                if let Some(field_definition) = input_object.fields.get(name) {
+                    if let Some(reason) = deprecation_reason(&field_definition.directives) {
+                        diagnostics.push(
+                            name.location(),
+                            DiagnosticData::DeprecatedUsage {
+                                coordinate: TypeAttributeCoordinate {
+                                    ty: input_object.name.clone(),
+                                    attribute: name.clone(),
+                                }
+                                .into(),
+                                reason,
+                                definition_location: field_definition.location(),
+                            },
+                        );
+                    }
+                    validate_deprecated_values(diagnostics, schema, &field_definition.ty, value);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
 pub(crate) fn validate_field_definition(
     diagnostics: &mut DiagnosticList,
     schema: &crate::Schema,
@@ -180,6 +434,11 @@ pub(crate) fn validate_field_definitions(
                 type_location,
                 DiagnosticData::UndefinedDefinition {
                     name: field.ty.inner_named_type().clone(),
+                    suggestions: type_suggestions(
+                        schema,
+                        field.ty.inner_named_type(),
+                        schema::ExtendedType::is_output_type,
+                    ),
                 },
             );
         }
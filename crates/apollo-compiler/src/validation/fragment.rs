@@ -1,13 +1,14 @@
 use crate::ast;
 use crate::ast::NamedType;
-use crate::collections::HashMap;
+use crate::collections::HashSet;
 use crate::collections::IndexSet;
 use crate::executable;
 use crate::schema;
-use crate::schema::Implementers;
 use crate::validation::diagnostics::DiagnosticData;
 use crate::validation::CycleError;
+use crate::validation::Details;
 use crate::validation::DiagnosticList;
+use crate::validation::ExecutableValidationContext;
 use crate::validation::OperationValidationContext;
 use crate::validation::RecursionGuard;
 use crate::validation::RecursionStack;
@@ -19,10 +20,13 @@ use std::borrow::Cow;
 
 /// Given a type definition, find all the type names that can be used for fragment spreading.
 ///
+/// Sets for abstract types are borrowed from maps cached in the validation context or the schema,
+/// so that large unions and interfaces are not collected again for every fragment.
+///
 /// Spec: https://spec.graphql.org/October2021/#GetPossibleTypes()
-fn get_possible_types<'a>(
+pub(crate) fn get_possible_types<'a>(
     type_definition: &schema::ExtendedType,
-    implementers_map: &'a HashMap<Name, Implementers>,
+    context: &'a OperationValidationContext<'_>,
 ) -> Cow<'a, IndexSet<NamedType>> {
     match type_definition {
         // 1. If `type` is an object type, return a set containing `type`.
@@ -32,18 +36,16 @@ fn get_possible_types<'a>(
             Cow::Owned(set)
         }
         // 2. If `type` is an interface type, return the set of object types implementing `type`.
-        schema::ExtendedType::Interface(interface) => implementers_map
+        schema::ExtendedType::Interface(interface) => context
+            .implementers_map()
             .get(&interface.name)
             .map(|implementers| Cow::Borrowed(&implementers.objects))
             .unwrap_or_default(),
         // 3. If `type` is a union type, return the set of possible types of `type`.
-        schema::ExtendedType::Union(union_) => Cow::Owned(
-            union_
-                .members
-                .iter()
-                .map(|component| component.name.clone())
-                .collect(),
-        ),
+        schema::ExtendedType::Union(union_) => context
+            .union_members(&union_.name)
+            .map(Cow::Borrowed)
+            .unwrap_or_default(),
         _ => Default::default(),
     }
 }
@@ -75,12 +77,17 @@ fn validate_fragment_spread_type(
         return;
     };
 
-    let implementers_map = context.implementers_map();
-    let concrete_parent_types = get_possible_types(against_type_definition, implementers_map);
-    let concrete_condition_types = get_possible_types(type_condition_definition, implementers_map);
+    let concrete_parent_types = get_possible_types(against_type_definition, &context);
+    let concrete_condition_types = get_possible_types(type_condition_definition, &context);
 
-    let mut applicable_types = concrete_parent_types.intersection(&concrete_condition_types);
-    if applicable_types.next().is_none() {
+    // Look up the elements of the smaller set in the larger one,
+    // so that a fragment on one member of a large union is checked in constant time
+    let (smaller, larger) = if concrete_parent_types.len() <= concrete_condition_types.len() {
+        (&concrete_parent_types, &concrete_condition_types)
+    } else {
+        (&concrete_condition_types, &concrete_parent_types)
+    };
+    if !smaller.iter().any(|ty| larger.contains(ty)) {
         // Report specific errors for the different kinds of fragments.
         match selection {
             executable::Selection::Field(_) => unreachable!(),
@@ -194,7 +201,9 @@ pub(crate) fn validate_fragment_spread(
                     context,
                 );
             }
-            validate_fragment_definition(diagnostics, document, def, context);
+            if !context.is_prevalidated(&def.name) {
+                validate_fragment_definition(diagnostics, document, def, context);
+            }
         }
         None => {
             diagnostics.push(
@@ -234,7 +243,12 @@ pub(crate) fn validate_fragment_definition(
     let has_type_error = diagnostics.len() > previous;
 
     let previous = diagnostics.len();
-    validate_fragment_cycles(diagnostics, document, fragment);
+    validate_fragment_cycles(
+        diagnostics,
+        document,
+        fragment,
+        context.fragment_nesting_limit(),
+    );
     let has_cycles = diagnostics.len() > previous;
 
     if !has_type_error && !has_cycles {
@@ -258,10 +272,49 @@ pub(crate) fn validate_fragment_definition(
     }
 }
 
+/// Validate every fragment of a library on its own, without operations.
+///
+/// Each definition is validated exactly once: spreads between library fragments are checked
+/// for applicability, but do not revisit the definition they point to.
+pub(crate) fn validate_fragment_library(
+    diagnostics: &mut DiagnosticList,
+    schema: &crate::Schema,
+    document: &ExecutableDocument,
+) {
+    let names: HashSet<Name> = document.fragments.keys().cloned().collect();
+    let context =
+        ExecutableValidationContext::new(Some(schema)).with_prevalidated_fragments(&names);
+    let mut library_diagnostics = DiagnosticList::new(diagnostics.sources.clone());
+    for def in document.fragments.values() {
+        validate_fragment_definition(
+            &mut library_diagnostics,
+            document,
+            def,
+            context.operation_context(&[]),
+        );
+    }
+    // Variables are defined by the operations that use a fragment, so whether they exist
+    // can only be checked once the library is linked into an executable document.
+    diagnostics
+        .diagnostics_data
+        .extend(
+            library_diagnostics
+                .diagnostics_data
+                .into_iter()
+                .filter(|diagnostic| {
+                    !matches!(
+                        diagnostic.details,
+                        Details::CompilerDiagnostic(DiagnosticData::UndefinedVariable { .. })
+                    )
+                }),
+        );
+}
+
 pub(crate) fn validate_fragment_cycles(
     diagnostics: &mut DiagnosticList,
     document: &ExecutableDocument,
     def: &Node<executable::Fragment>,
+    nesting_limit: usize,
 ) {
     /// If a fragment spread is recursive, returns a vec containing the spread that refers back to
     /// the original fragment, and a trace of each fragment spread back to the original fragment.
@@ -301,7 +354,7 @@ pub(crate) fn validate_fragment_cycles(
         Ok(())
     }
 
-    let mut visited = RecursionStack::with_root(def.name.clone()).with_limit(100);
+    let mut visited = RecursionStack::with_root(def.name.clone()).with_limit(nesting_limit);
 
     match detect_fragment_cycles(document, &def.selection_set, &mut visited.guard()) {
         Ok(_) => {}
@@ -341,8 +394,8 @@ pub(crate) fn validate_fragment_type_condition(
     let type_def = schema.types.get(type_cond);
     let is_composite = type_def
         .as_ref()
-        // .map_or(false, |ty| ty.is_composite_definition());
-        .map_or(false, |ty| {
+        // .is_some_and(|ty| ty.is_composite_definition());
+        .is_some_and(|ty| {
             matches!(
                 ty,
                 schema::ExtendedType::Object(_)
@@ -362,14 +415,14 @@ pub(crate) fn validate_fragment_type_condition(
     }
 }
 
-pub(crate) fn validate_fragment_used(
+pub(crate) fn validate_fragments_used(
     diagnostics: &mut DiagnosticList,
     document: &ExecutableDocument,
-    fragment: &Node<executable::Fragment>,
 ) {
-    let fragment_name = &fragment.name;
-
-    let mut all_selections = document
+    // Collect spreads of the whole document once,
+    // rather than walking it again for each fragment
+    let mut used = HashSet::default();
+    let all_selection_sets = document
         .operations
         .iter()
         .map(|operation| &operation.selection_set)
@@ -378,33 +431,41 @@ pub(crate) fn validate_fragment_used(
                 .fragments
                 .values()
                 .map(|fragment| &fragment.selection_set),
-        )
-        .flat_map(|set| &set.selections);
-
-    let is_used = all_selections.any(|sel| selection_uses_fragment(sel, fragment_name));
+        );
+    for selection_set in all_selection_sets {
+        collect_used_fragments(selection_set, &mut used);
+    }
 
     // Fragments must be used within the schema
     //
     // Returns Unused Fragment error.
-    if !is_used {
-        diagnostics.push(
-            fragment.location(),
-            DiagnosticData::UnusedFragment {
-                name: fragment_name.clone(),
-            },
-        )
+    for fragment in document.fragments.values() {
+        if !used.contains(&fragment.name) {
+            diagnostics.push(
+                fragment.location(),
+                DiagnosticData::UnusedFragment {
+                    name: fragment.name.clone(),
+                },
+            )
+        }
     }
 }
 
-fn selection_uses_fragment(sel: &executable::Selection, name: &str) -> bool {
-    let sub_selections = match sel {
-        executable::Selection::FragmentSpread(fragment) => return fragment.fragment_name == name,
-        executable::Selection::Field(field) => &field.selection_set,
-        executable::Selection::InlineFragment(inline) => &inline.selection_set,
-    };
-
-    sub_selections
-        .selections
-        .iter()
-        .any(|sel| selection_uses_fragment(sel, name))
+fn collect_used_fragments<'doc>(
+    selection_set: &'doc executable::SelectionSet,
+    used: &mut HashSet<&'doc Name>,
+) {
+    for selection in &selection_set.selections {
+        match selection {
+            executable::Selection::FragmentSpread(fragment) => {
+                used.insert(&fragment.fragment_name);
+            }
+            executable::Selection::Field(field) => {
+                collect_used_fragments(&field.selection_set, used)
+            }
+            executable::Selection::InlineFragment(inline) => {
+                collect_used_fragments(&inline.selection_set, used)
+            }
+        }
+    }
 }
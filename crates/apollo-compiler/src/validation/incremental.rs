@@ -0,0 +1,183 @@
+use crate::ast;
+use crate::collections::HashMap;
+use crate::collections::HashSet;
+use crate::executable::DirectiveList;
+use crate::executable::Selection;
+use crate::executable::SelectionSet;
+use crate::parser::SourceSpan;
+use crate::validation::diagnostics::DiagnosticData;
+use crate::validation::DiagnosticList;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+
+/// Validate uses of the `@defer` and `@stream` directives for incremental delivery,
+/// beyond the locations and argument types checked for all directives.
+///
+/// Spec: https://github.com/graphql/graphql-spec/pull/742
+pub(crate) fn validate_incremental_directives(
+    diagnostics: &mut DiagnosticList,
+    schema: &Schema,
+    document: &ExecutableDocument,
+) {
+    // If the schema does not support incremental delivery,
+    // uses of these directives are already reported as undefined.
+    let defer = schema.directive_definitions.contains_key("defer");
+    let stream = schema.directive_definitions.contains_key("stream");
+    if !defer && !stream {
+        return;
+    }
+
+    let mut labels = HashMap::default();
+    for operation in document.operations.iter() {
+        validate_selection_set(diagnostics, &operation.selection_set, &mut labels);
+        if defer && operation.is_subscription() {
+            let mut seen_fragments = HashSet::default();
+            validate_subscription_root(
+                diagnostics,
+                document,
+                &operation.selection_set,
+                &mut seen_fragments,
+            );
+        }
+    }
+    for fragment in document.fragments.values() {
+        validate_selection_set(diagnostics, &fragment.selection_set, &mut labels);
+    }
+}
+
+fn validate_selection_set(
+    diagnostics: &mut DiagnosticList,
+    selection_set: &SelectionSet,
+    labels: &mut HashMap<String, Option<SourceSpan>>,
+) {
+    for selection in &selection_set.selections {
+        match selection {
+            Selection::Field(field) => {
+                if let Some(stream) = field.directives.get("stream") {
+                    validate_label(diagnostics, stream, labels);
+                    validate_initial_count(diagnostics, stream);
+                    if !field.definition.ty.is_list() {
+                        diagnostics.push(
+                            stream.location(),
+                            DiagnosticData::StreamOnNonListField {
+                                field: field.name.clone(),
+                                ty: field.definition.ty.clone(),
+                            },
+                        );
+                    }
+                }
+                validate_selection_set(diagnostics, &field.selection_set, labels);
+            }
+            Selection::FragmentSpread(spread) => {
+                if let Some(defer) = spread.directives.get("defer") {
+                    validate_label(diagnostics, defer, labels);
+                }
+            }
+            Selection::InlineFragment(inline) => {
+                if let Some(defer) = inline.directives.get("defer") {
+                    validate_label(diagnostics, defer, labels);
+                }
+                validate_selection_set(diagnostics, &inline.selection_set, labels);
+            }
+        }
+    }
+}
+
+/// Labels must be string literals, and unique across the document.
+fn validate_label(
+    diagnostics: &mut DiagnosticList,
+    directive: &Node<ast::Directive>,
+    labels: &mut HashMap<String, Option<SourceSpan>>,
+) {
+    let Some(label) = directive.argument_by_name("label") else {
+        return;
+    };
+    match label.as_ref() {
+        ast::Value::String(value) => {
+            if let Some(&original_definition) = labels.get(value.as_str()) {
+                diagnostics.push(
+                    label.location(),
+                    DiagnosticData::UniqueIncrementalLabel {
+                        label: value.clone(),
+                        original_definition,
+                        redefined_definition: label.location(),
+                    },
+                );
+            } else {
+                labels.insert(value.clone(), label.location());
+            }
+        }
+        ast::Value::Variable(_) => diagnostics.push(
+            label.location(),
+            DiagnosticData::VariableIncrementalLabel {
+                directive: directive.name.clone(),
+            },
+        ),
+        // Other kinds of values are reported by value validation
+        _ => {}
+    }
+}
+
+fn validate_initial_count(diagnostics: &mut DiagnosticList, stream: &Node<ast::Directive>) {
+    let Some(initial_count) = stream.argument_by_name("initialCount") else {
+        return;
+    };
+    if initial_count.to_i32().is_some_and(|count| count < 0) {
+        diagnostics.push(
+            initial_count.location(),
+            DiagnosticData::NegativeInitialCount {
+                value: initial_count.clone(),
+            },
+        );
+    }
+}
+
+/// `@defer` cannot be used on the root selection set of a subscription,
+/// unless disabled with `if: false`.
+fn validate_subscription_root<'doc>(
+    diagnostics: &mut DiagnosticList,
+    document: &'doc ExecutableDocument,
+    selection_set: &'doc SelectionSet,
+    seen_fragments: &mut HashSet<&'doc Name>,
+) {
+    let check = |diagnostics: &mut DiagnosticList, directives: &DirectiveList| {
+        if let Some(defer) = directives.get("defer") {
+            let disabled = defer
+                .argument_by_name("if")
+                .is_some_and(|value| matches!(value.as_ref(), ast::Value::Boolean(false)));
+            if !disabled {
+                diagnostics.push(defer.location(), DiagnosticData::DeferOnSubscriptionRoot);
+            }
+        }
+    };
+    for selection in &selection_set.selections {
+        match selection {
+            Selection::Field(_) => {}
+            Selection::FragmentSpread(spread) => {
+                check(diagnostics, &spread.directives);
+                // Cycles are reported by fragment cycle validation
+                if let Some(fragment) = document.fragments.get(&spread.fragment_name) {
+                    if seen_fragments.insert(&fragment.name) {
+                        validate_subscription_root(
+                            diagnostics,
+                            document,
+                            &fragment.selection_set,
+                            seen_fragments,
+                        );
+                    }
+                }
+            }
+            Selection::InlineFragment(inline) => {
+                check(diagnostics, &inline.directives);
+                validate_subscription_root(
+                    diagnostics,
+                    document,
+                    &inline.selection_set,
+                    seen_fragments,
+                );
+            }
+        }
+    }
+}
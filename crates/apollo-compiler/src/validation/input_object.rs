@@ -1,12 +1,14 @@
 use crate::ast;
-use crate::collections::HashMap;
+use crate::collections::IndexMap;
 use crate::schema::ExtendedType;
 use crate::schema::InputObjectType;
 use crate::validation::diagnostics::DiagnosticData;
+use crate::validation::suggestion::type_suggestions;
 use crate::validation::CycleError;
 use crate::validation::DiagnosticList;
 use crate::validation::RecursionGuard;
 use crate::validation::RecursionStack;
+use crate::validation::SourceSpan;
 use crate::Name;
 use crate::Node;
 
@@ -58,8 +60,10 @@ impl FindRecursiveInputValue<'_> {
     fn check(
         schema: &crate::Schema,
         input_object: &InputObjectType,
+        limit: usize,
     ) -> Result<(), CycleError<ast::InputValueDefinition>> {
-        let mut recursion_stack = RecursionStack::with_root(input_object.name.clone());
+        let mut recursion_stack =
+            RecursionStack::with_root(input_object.name.clone()).with_limit(limit);
         FindRecursiveInputValue { schema }
             .input_object_definition(recursion_stack.guard(), input_object)
     }
@@ -68,18 +72,26 @@ impl FindRecursiveInputValue<'_> {
 pub(crate) fn validate_input_object_definitions(
     diagnostics: &mut DiagnosticList,
     schema: &crate::Schema,
+    nesting_limit: usize,
 ) {
-    for ty in schema.types.values() {
-        if let ExtendedType::InputObject(input_object) = ty {
-            validate_input_object_definition(diagnostics, schema, input_object);
-        }
-    }
+    let definitions: Vec<_> = schema
+        .types
+        .values()
+        .filter_map(|ty| match ty {
+            ExtendedType::InputObject(input_object) => Some(input_object),
+            _ => None,
+        })
+        .collect();
+    diagnostics.validate_each(&definitions, |diagnostics, input_object| {
+        validate_input_object_definition(diagnostics, schema, input_object, nesting_limit)
+    });
 }
 
 pub(crate) fn validate_input_object_definition(
     diagnostics: &mut DiagnosticList,
     schema: &crate::Schema,
     input_object: &Node<InputObjectType>,
+    nesting_limit: usize,
 ) {
     super::directive::validate_directives(
         diagnostics,
@@ -90,7 +102,7 @@ pub(crate) fn validate_input_object_definition(
         Default::default(),
     );
 
-    match FindRecursiveInputValue::check(schema, input_object) {
+    match FindRecursiveInputValue::check(schema, input_object, nesting_limit) {
         Ok(_) => {}
         Err(CycleError::Recursed(trace)) => diagnostics.push(
             input_object.location(),
@@ -151,24 +163,25 @@ pub(crate) fn validate_argument_definitions(
 ) {
     validate_input_value_definitions(diagnostics, schema, input_values, directive_location);
 
-    let mut seen: HashMap<Name, &Node<ast::InputValueDefinition>> = HashMap::default();
+    let mut seen = IndexMap::<&Name, Vec<Option<SourceSpan>>>::default();
     for input_value in input_values {
-        let name = &input_value.name;
-        if let Some(prev_value) = seen.get(name) {
-            let (original_definition, redefined_definition) =
-                (prev_value.location(), input_value.location());
-
-            diagnostics.push(
-                original_definition,
-                DiagnosticData::UniqueInputValue {
-                    name: name.clone(),
-                    original_definition,
-                    redefined_definition,
-                },
-            );
-        } else {
-            seen.insert(name.clone(), input_value);
+        seen.entry(&input_value.name)
+            .or_default()
+            .push(input_value.location());
+    }
+    for (name, mut locations) in seen {
+        if locations.len() < 2 {
+            continue;
         }
+        let original_definition = locations.remove(0);
+        diagnostics.push(
+            original_definition,
+            DiagnosticData::UniqueInputValue {
+                name: name.clone(),
+                original_definition,
+                redefined_definitions: locations,
+            },
+        );
     }
 }
 
@@ -206,6 +219,7 @@ pub(crate) fn validate_input_value_definitions(
                 loc,
                 DiagnosticData::UndefinedDefinition {
                     name: named_type.clone(),
+                    suggestions: type_suggestions(schema, named_type, ExtendedType::is_input_type),
                 },
             );
         }
@@ -7,6 +7,7 @@ use crate::schema::InterfaceType;
 use crate::schema::Name;
 use crate::validation::diagnostics::DiagnosticData;
 use crate::validation::field::validate_field_definitions;
+use crate::validation::suggestion::type_suggestions;
 use crate::validation::DiagnosticList;
 use crate::Node;
 
@@ -14,11 +15,17 @@ pub(crate) fn validate_interface_definitions(
     diagnostics: &mut DiagnosticList,
     schema: &crate::Schema,
 ) {
-    for ty in schema.types.values() {
-        if let ExtendedType::Interface(interface) = ty {
-            validate_interface_definition(diagnostics, schema, interface);
-        }
-    }
+    let definitions: Vec<_> = schema
+        .types
+        .values()
+        .filter_map(|ty| match ty {
+            ExtendedType::Interface(interface) => Some(interface),
+            _ => None,
+        })
+        .collect();
+    diagnostics.validate_each(&definitions, |diagnostics, interface| {
+        validate_interface_definition(diagnostics, schema, interface)
+    });
 }
 
 pub(crate) fn validate_interface_definition(
@@ -146,6 +153,7 @@ pub(crate) fn validate_implements_interfaces(
             loc,
             DiagnosticData::UndefinedDefinition {
                 name: interface_name.name.clone(),
+                suggestions: type_suggestions(schema, interface_name, ExtendedType::is_interface),
             },
         );
     }
@@ -0,0 +1,495 @@
+//! Opt-in style checks for schemas and executable documents, run with
+//! [`Schema::lint`][crate::Schema::lint] and [`ExecutableDocument::lint`][crate::ExecutableDocument::lint].
+//!
+//! Unlike validation rules, lints do not come from the GraphQL specification:
+//! a schema or document that has lints is still valid.
+//! They are reported with [`Severity::Lint`][crate::validation::Severity::Lint].
+
+use crate::ast;
+use crate::collections::HashMap;
+use crate::collections::HashSet;
+use crate::parser::FileId;
+use crate::schema::ExtendedType;
+use crate::validation::diagnostics::DiagnosticData;
+use crate::validation::directive::walk_document_directives;
+use crate::validation::DiagnosticList;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Node;
+use crate::Schema;
+
+/// A style rule checked by [`Schema::lint`][crate::Schema::lint]
+/// or [`ExecutableDocument::lint`][crate::ExecutableDocument::lint]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LintRule {
+    /// Type names are `PascalCase`, like `UserProfile`
+    TypeNamePascalCase,
+    /// Fields of object types, interfaces, and input objects are `camelCase`, like `createdAt`
+    FieldNameCamelCase,
+    /// Enum values are `SCREAMING_SNAKE_CASE`, like `IN_PROGRESS`
+    EnumValueScreamingSnakeCase,
+    /// Types defined in the schema have a description
+    DescriptionRequired,
+    /// Names in the same scope do not differ only by case, like `userId` and `userID`.
+    /// Off by default.
+    ///
+    /// Scopes are: type names, directive names, fields of a type, values of an enum,
+    /// and arguments of a field or directive.
+    CaseInsensitiveNameCollision,
+    /// In executable documents, `@include` and `@skip` can change whether a selection is included:
+    /// not `@include(if: true)` nor `@skip(if: false)`, which have no effect,
+    /// nor `@include(if: $x) @skip(if: $x)` on the same selection, which always excludes it.
+    RedundantConditionalDirective,
+    /// In executable documents, every operation has a name.
+    /// Off by default.
+    OperationNameRequired,
+    /// Operation names end with their operation type,
+    /// like `GetUserQuery`, `UpdateUserMutation`, or `MessagesSubscription`.
+    /// Off by default.
+    OperationTypeSuffix,
+    /// Operation names match the regular expression set with
+    /// [`LintConfig::operation_name_pattern`], which requires the `regex` feature.
+    /// Only checked when a pattern is set.
+    OperationNamePattern,
+}
+
+impl LintRule {
+    /// Returns the identifier of this rule, as returned by
+    /// [`DiagnosticData::rule`][crate::validation::DiagnosticData::rule]
+    pub const fn name(self) -> &'static str {
+        match self {
+            LintRule::TypeNamePascalCase => "TypeNamePascalCase",
+            LintRule::FieldNameCamelCase => "FieldNameCamelCase",
+            LintRule::EnumValueScreamingSnakeCase => "EnumValueScreamingSnakeCase",
+            LintRule::DescriptionRequired => "DescriptionRequired",
+            LintRule::CaseInsensitiveNameCollision => "CaseInsensitiveNameCollision",
+            LintRule::RedundantConditionalDirective => "RedundantConditionalDirective",
+            LintRule::OperationNameRequired => "OperationNameRequired",
+            LintRule::OperationTypeSuffix => "OperationTypeSuffix",
+            LintRule::OperationNamePattern => "OperationNamePattern",
+        }
+    }
+
+    fn is_enabled_by_default(self) -> bool {
+        !matches!(
+            self,
+            LintRule::CaseInsensitiveNameCollision
+                | LintRule::OperationNameRequired
+                | LintRule::OperationTypeSuffix
+        )
+    }
+}
+
+/// Which rules [`Schema::lint`][crate::Schema::lint]
+/// and [`ExecutableDocument::lint`][crate::ExecutableDocument::lint] check.
+/// All rules are enabled by default, except [`LintRule::CaseInsensitiveNameCollision`],
+/// [`LintRule::OperationNameRequired`], and [`LintRule::OperationTypeSuffix`].
+///
+/// ```rust
+/// use apollo_compiler::validation::LintConfig;
+/// use apollo_compiler::validation::LintRule;
+///
+/// let config = LintConfig::new().disable_rule(LintRule::DescriptionRequired);
+/// assert!(!config.is_rule_enabled(LintRule::DescriptionRequired));
+/// assert!(config.is_rule_enabled(LintRule::TypeNamePascalCase));
+/// assert!(!config.is_rule_enabled(LintRule::CaseInsensitiveNameCollision));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintConfig {
+    disabled_rules: HashSet<LintRule>,
+    enabled_rules: HashSet<LintRule>,
+    #[cfg(feature = "regex")]
+    operation_name_pattern: Option<NamePattern>,
+}
+
+/// A regular expression, compared by its source text
+#[cfg(feature = "regex")]
+#[derive(Debug, Clone)]
+struct NamePattern(regex::Regex);
+
+#[cfg(feature = "regex")]
+impl PartialEq for NamePattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+#[cfg(feature = "regex")]
+impl Eq for NamePattern {}
+
+impl LintConfig {
+    /// Returns a configuration with the default lint rules enabled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Do not check the given rule
+    pub fn disable_rule(mut self, rule: LintRule) -> Self {
+        self.enabled_rules.remove(&rule);
+        self.disabled_rules.insert(rule);
+        self
+    }
+
+    /// Check the given rule, if it is off by default
+    /// or after [`disable_rule`][Self::disable_rule]
+    pub fn enable_rule(mut self, rule: LintRule) -> Self {
+        self.disabled_rules.remove(&rule);
+        self.enabled_rules.insert(rule);
+        self
+    }
+
+    /// Returns whether the given rule is checked
+    pub fn is_rule_enabled(&self, rule: LintRule) -> bool {
+        if rule.is_enabled_by_default() {
+            !self.disabled_rules.contains(&rule)
+        } else {
+            self.enabled_rules.contains(&rule)
+        }
+    }
+
+    /// Require operation names to match `pattern`, as checked by [`LintRule::OperationNamePattern`].
+    /// The pattern is not anchored: use `^` and `$` to match entire names.
+    ///
+    /// ```rust
+    /// use apollo_compiler::validation::LintConfig;
+    /// use apollo_compiler::ExecutableDocument;
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    /// let pattern = regex::Regex::new("^[A-Z][A-Za-z0-9]*$").unwrap();
+    /// let config = LintConfig::new().operation_name_pattern(pattern);
+    /// let document = ExecutableDocument::parse(&schema, "query get_a { a }", "query.graphql").unwrap();
+    /// let lints = document.lint(&config);
+    /// assert_eq!(
+    ///     lints.iter().next().unwrap().error.to_string(),
+    ///     "operation name `get_a` does not match the pattern `^[A-Z][A-Za-z0-9]*$`"
+    /// );
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn operation_name_pattern(mut self, pattern: regex::Regex) -> Self {
+        self.operation_name_pattern = Some(NamePattern(pattern));
+        self
+    }
+}
+
+pub(crate) fn lint_schema(diagnostics: &mut DiagnosticList, schema: &Schema, config: &LintConfig) {
+    for ty in schema.types.values() {
+        if ty.is_built_in() {
+            continue;
+        }
+        if config.is_rule_enabled(LintRule::TypeNamePascalCase) {
+            check_name(diagnostics, LintRule::TypeNamePascalCase, ty.name());
+        }
+        if config.is_rule_enabled(LintRule::DescriptionRequired) && ty.description().is_none() {
+            diagnostics.push(
+                ty.name().location(),
+                DiagnosticData::MissingDescription {
+                    describe_type: ty.describe(),
+                    name: ty.name().clone(),
+                },
+            );
+        }
+        match ty {
+            ExtendedType::Object(object) => {
+                check_field_names(diagnostics, config, object.fields.keys());
+            }
+            ExtendedType::Interface(interface) => {
+                check_field_names(diagnostics, config, interface.fields.keys());
+            }
+            ExtendedType::InputObject(input_object) => {
+                check_field_names(diagnostics, config, input_object.fields.keys());
+            }
+            ExtendedType::Enum(enum_) => {
+                if config.is_rule_enabled(LintRule::EnumValueScreamingSnakeCase) {
+                    for value in enum_.values.keys() {
+                        check_name(diagnostics, LintRule::EnumValueScreamingSnakeCase, value);
+                    }
+                }
+            }
+            ExtendedType::Scalar(_) | ExtendedType::Union(_) => {}
+        }
+    }
+    if config.is_rule_enabled(LintRule::CaseInsensitiveNameCollision) {
+        check_case_collisions_in_schema(diagnostics, schema);
+    }
+    diagnostics.sort()
+}
+
+pub(crate) fn lint_executable_document(
+    diagnostics: &mut DiagnosticList,
+    document: &ExecutableDocument,
+    config: &LintConfig,
+) {
+    if config.is_rule_enabled(LintRule::RedundantConditionalDirective) {
+        walk_document_directives(document, |directives| {
+            check_conditional_directives(diagnostics, directives)
+        });
+    }
+    check_operation_names(diagnostics, document, config);
+    diagnostics.sort()
+}
+
+fn check_operation_names(
+    diagnostics: &mut DiagnosticList,
+    document: &ExecutableDocument,
+    config: &LintConfig,
+) {
+    for operation in document.operations.iter() {
+        let Some(name) = &operation.name else {
+            if config.is_rule_enabled(LintRule::OperationNameRequired) {
+                diagnostics.push(
+                    operation.location(),
+                    DiagnosticData::UnnamedOperation {
+                        operation_type: operation.operation_type,
+                    },
+                );
+            }
+            continue;
+        };
+        if config.is_rule_enabled(LintRule::OperationTypeSuffix) {
+            let suffix = operation.operation_type.default_type_name();
+            if !name.ends_with(suffix.as_str()) {
+                diagnostics.push(
+                    name.location(),
+                    DiagnosticData::NamingConvention {
+                        rule: LintRule::OperationTypeSuffix,
+                        name: name.clone(),
+                        expected: format!("{name}{suffix}"),
+                    },
+                );
+            }
+        }
+        #[cfg(feature = "regex")]
+        if let Some(NamePattern(pattern)) = &config.operation_name_pattern {
+            if config.is_rule_enabled(LintRule::OperationNamePattern) && !pattern.is_match(name) {
+                diagnostics.push(
+                    name.location(),
+                    DiagnosticData::OperationNamePattern {
+                        name: name.clone(),
+                        pattern: pattern.as_str().to_owned(),
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// Check the `@include` and `@skip` directives applied to one selection
+fn check_conditional_directives<'doc>(
+    diagnostics: &mut DiagnosticList,
+    directives: impl Iterator<Item = &'doc Node<ast::Directive>>,
+) {
+    let mut included_by = Vec::new();
+    let mut skipped_by = Vec::new();
+    for directive in directives {
+        // The value of `if` for which the directive has no effect
+        let (variables, redundant_if) = match directive.name.as_str() {
+            "include" => (&mut included_by, true),
+            "skip" => (&mut skipped_by, false),
+            _ => continue,
+        };
+        match directive.argument_by_name("if").map(|value| &**value) {
+            Some(ast::Value::Boolean(value)) if *value == redundant_if => diagnostics.push(
+                directive.location(),
+                DiagnosticData::RedundantConditionalDirective {
+                    directive: directive.name.clone(),
+                    value: *value,
+                },
+            ),
+            Some(ast::Value::Variable(variable)) => variables.push((variable, directive)),
+            _ => {}
+        }
+    }
+    for (variable, skip) in skipped_by {
+        if let Some((_, include)) = included_by.iter().find(|(other, _)| *other == variable) {
+            diagnostics.push(
+                skip.location(),
+                DiagnosticData::ContradictoryConditionalDirectives {
+                    variable: variable.clone(),
+                    include: include.location(),
+                },
+            );
+        }
+    }
+}
+
+fn check_case_collisions_in_schema(diagnostics: &mut DiagnosticList, schema: &Schema) {
+    // Built-in names are included so that a type like `string` is reported,
+    // but collisions only between built-in names are not
+    check_case_collisions(diagnostics, "type", schema.types.keys());
+    check_case_collisions(
+        diagnostics,
+        "directive",
+        schema.directive_definitions.keys(),
+    );
+    for directive in schema.directive_definitions.values() {
+        if !directive.is_built_in() {
+            let names = directive.arguments.iter().map(|arg| &arg.name);
+            check_case_collisions(diagnostics, "argument", names);
+        }
+    }
+    for ty in schema.types.values() {
+        if ty.is_built_in() {
+            continue;
+        }
+        let fields = match ty {
+            ExtendedType::Object(object) => &object.fields,
+            ExtendedType::Interface(interface) => &interface.fields,
+            ExtendedType::InputObject(input_object) => {
+                check_case_collisions(diagnostics, "field", input_object.fields.keys());
+                continue;
+            }
+            ExtendedType::Enum(enum_) => {
+                check_case_collisions(diagnostics, "enum value", enum_.values.keys());
+                continue;
+            }
+            ExtendedType::Scalar(_) | ExtendedType::Union(_) => continue,
+        };
+        check_case_collisions(diagnostics, "field", fields.keys());
+        for field in fields.values() {
+            let names = field.arguments.iter().map(|arg| &arg.name);
+            check_case_collisions(diagnostics, "argument", names);
+        }
+    }
+}
+
+/// Report each name that is equal to an earlier one when ignoring ASCII case.
+/// Names within a scope are already unique, so any match differs only by case.
+fn check_case_collisions<'a>(
+    diagnostics: &mut DiagnosticList,
+    describe: &'static str,
+    names: impl Iterator<Item = &'a Name>,
+) {
+    let is_built_in = |name: &Name| {
+        name.location()
+            .is_some_and(|location| location.file_id() == FileId::BUILT_IN)
+    };
+    let mut seen = HashMap::<String, &Name>::default();
+    for name in names {
+        let folded = name.to_ascii_lowercase();
+        if let Some(&other) = seen.get(&folded) {
+            if is_built_in(name) && is_built_in(other) {
+                continue;
+            }
+            diagnostics.push(
+                name.location(),
+                DiagnosticData::CaseInsensitiveNameCollision {
+                    describe,
+                    name: name.clone(),
+                    other: other.clone(),
+                },
+            );
+        } else {
+            seen.insert(folded, name);
+        }
+    }
+}
+
+fn check_field_names<'a>(
+    diagnostics: &mut DiagnosticList,
+    config: &LintConfig,
+    names: impl Iterator<Item = &'a Name>,
+) {
+    if !config.is_rule_enabled(LintRule::FieldNameCamelCase) {
+        return;
+    }
+    for name in names {
+        // Introspection fields are named by the specification
+        if !name.starts_with("__") {
+            check_name(diagnostics, LintRule::FieldNameCamelCase, name);
+        }
+    }
+}
+
+fn check_name(diagnostics: &mut DiagnosticList, rule: LintRule, name: &Name) {
+    // Leading underscores mark names that are not meant for clients,
+    // such as `_entities` in Apollo Federation, and are not part of the convention.
+    let prefix_len = name.len() - name.trim_start_matches('_').len();
+    let (prefix, rest) = name.split_at(prefix_len);
+    if rest.is_empty() {
+        return;
+    }
+    let follows_convention = match rule {
+        LintRule::TypeNamePascalCase => {
+            rest.starts_with(|c: char| c.is_ascii_uppercase()) && !rest.contains('_')
+        }
+        LintRule::FieldNameCamelCase => {
+            rest.starts_with(|c: char| c.is_ascii_lowercase()) && !rest.contains('_')
+        }
+        LintRule::EnumValueScreamingSnakeCase => !rest.contains(|c: char| c.is_ascii_lowercase()),
+        LintRule::DescriptionRequired
+        | LintRule::CaseInsensitiveNameCollision
+        | LintRule::RedundantConditionalDirective
+        | LintRule::OperationNameRequired
+        | LintRule::OperationTypeSuffix
+        | LintRule::OperationNamePattern => true,
+    };
+    if follows_convention {
+        return;
+    }
+    let words = split_words(rest);
+    let expected: String = match rule {
+        LintRule::TypeNamePascalCase => words.iter().map(|word| capitalize(word)).collect(),
+        LintRule::FieldNameCamelCase => words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| {
+                if i == 0 {
+                    word.clone()
+                } else {
+                    capitalize(word)
+                }
+            })
+            .collect(),
+        _ => words.join("_").to_ascii_uppercase(),
+    };
+    diagnostics.push(
+        name.location(),
+        DiagnosticData::NamingConvention {
+            rule,
+            name: name.clone(),
+            expected: format!("{prefix}{expected}"),
+        },
+    );
+}
+
+/// Split an identifier into lowercase words at underscores and case changes:
+/// `HTTPServer_v2` becomes `http`, `server`, `v2`.
+fn split_words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+        if c.is_ascii_uppercase() && !word.is_empty() {
+            let previous = chars[i - 1];
+            let next_is_lowercase = chars.get(i + 1).is_some_and(|c| c.is_ascii_lowercase());
+            // `userId` splits before `I`, `HTTPServer` splits before `S`
+            if previous.is_ascii_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_ascii_uppercase() && next_is_lowercase)
+            {
+                words.push(std::mem::take(&mut word));
+            }
+        }
+        word.push(c.to_ascii_lowercase());
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,76 @@
+use crate::diagnostic::Color;
+use crate::diagnostic::Diagnostic;
+use crate::parser::LineColumn;
+use crate::parser::PositionEncoding;
+use crate::parser::SourceFile;
+use crate::parser::SourceMap;
+use crate::parser::SourceSpan;
+use crate::validation::DiagnosticData;
+use crate::validation::Severity;
+
+impl Diagnostic<'_, DiagnosticData> {
+    /// Convert to a [Language Server Protocol](https://microsoft.github.io/language-server-protocol/)
+    /// diagnostic, with columns counted in the position encoding negotiated with the client.
+    ///
+    /// Labels at other locations than the main one become related information.
+    /// `uri` returns the URI of the document for a source file,
+    /// and labels in files it returns `None` for are left out.
+    pub fn to_lsp(
+        &self,
+        encoding: PositionEncoding,
+        mut uri: impl FnMut(&SourceFile) -> Option<lsp_types::Url>,
+    ) -> lsp_types::Diagnostic {
+        let report = self.to_report(Color::Never);
+        let main_location = self.error.location;
+        let related_information: Vec<_> = report
+            .labels
+            .iter()
+            .filter(|(location, _)| Some(*location) != main_location)
+            .filter_map(|(location, message)| {
+                let file = self.sources.get(&location.file_id())?;
+                Some(lsp_types::DiagnosticRelatedInformation {
+                    location: lsp_types::Location {
+                        uri: uri(file)?,
+                        range: to_lsp_range(*location, self.sources, encoding)?,
+                    },
+                    message: message.clone(),
+                })
+            })
+            .collect();
+        lsp_types::Diagnostic {
+            range: main_location
+                .and_then(|location| to_lsp_range(location, self.sources, encoding))
+                .unwrap_or_default(),
+            severity: Some(match self.error.severity() {
+                Severity::Error => lsp_types::DiagnosticSeverity::ERROR,
+                Severity::Warning => lsp_types::DiagnosticSeverity::WARNING,
+                Severity::Lint => lsp_types::DiagnosticSeverity::INFORMATION,
+            }),
+            code: self
+                .error
+                .rule()
+                .map(|rule| lsp_types::NumberOrString::String(rule.to_owned())),
+            source: Some("apollo-compiler".to_owned()),
+            message: self.error.to_string(),
+            related_information: (!related_information.is_empty()).then_some(related_information),
+            ..Default::default()
+        }
+    }
+}
+
+/// LSP positions are zero-based, unlike [`LineColumn`]
+fn to_lsp_range(
+    location: SourceSpan,
+    sources: &SourceMap,
+    encoding: PositionEncoding,
+) -> Option<lsp_types::Range> {
+    let range = location.line_column_range_with_encoding(sources, encoding)?;
+    let position = |line_column: LineColumn| lsp_types::Position {
+        line: (line_column.line - 1) as u32,
+        character: (line_column.column - 1) as u32,
+    };
+    Some(lsp_types::Range {
+        start: position(range.start),
+        end: position(range.end),
+    })
+}
@@ -1,31 +1,132 @@
 //! Supporting APIs for [GraphQL validation](https://spec.graphql.org/October2021/#sec-Validation)
 //! and other kinds of errors.
-
+//!
+//! ## Validating values built programmatically
+//!
+//! Validation works on the in-memory [`Schema`] and [`ExecutableDocument`] types,
+//! whether they were parsed or built in code without any source file.
+//! [`Schema::validation_diagnostics`] and [`ExecutableDocument::validation_diagnostics`]
+//! borrow their input and return every diagnostic without consuming it.
+//!
+//! Nodes created in code have no [location][crate::Node::location],
+//! so diagnostics about them have none either:
+//! they are still reported and formatted, without a source snippet,
+//! and [`Diagnostic::line_column_range`][crate::diagnostic::Diagnostic::line_column_range]
+//! returns `None` for them.
+//!
+//! ```rust
+//! use apollo_compiler::ast;
+//! use apollo_compiler::executable;
+//! use apollo_compiler::name;
+//! use apollo_compiler::ty;
+//! use apollo_compiler::validation::ValidationConfig;
+//! use apollo_compiler::ExecutableDocument;
+//! use apollo_compiler::Node;
+//! use apollo_compiler::Schema;
+//!
+//! let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+//! let mut selection_set = executable::SelectionSet::new(name!("Query"));
+//! selection_set.push(selection_set.new_field(&schema, name!("a")).unwrap());
+//! let mut document = ExecutableDocument::new();
+//! document.operations.insert(executable::Operation {
+//!     operation_type: ast::OperationType::Query,
+//!     name: None,
+//!     variables: vec![Node::new(ast::VariableDefinition {
+//!         name: name!("unused"),
+//!         ty: Node::new(ty!(Int)),
+//!         default_value: None,
+//!         directives: Default::default(),
+//!     })],
+//!     directives: Default::default(),
+//!     selection_set,
+//! });
+//!
+//! let diagnostics = document.validation_diagnostics(&schema, &ValidationConfig::new());
+//! let diagnostic = diagnostics.iter().next().unwrap();
+//! assert_eq!(diagnostic.error.to_string(), "unused variable: `$unused`");
+//! assert_eq!(diagnostic.line_column_range(), None);
+//! ```
+
+use crate::ast;
 use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::TypeAttributeCoordinate;
 #[cfg(doc)]
 use crate::ExecutableDocument;
 use crate::Schema;
 
+pub(crate) mod allowlist;
 pub(crate) mod argument;
+pub(crate) mod batch;
+pub(crate) mod cache;
+pub(crate) mod custom_directive;
+pub(crate) mod custom_scalar;
+pub(crate) mod deprecation;
+pub(crate) mod depth;
 pub(crate) mod diagnostics;
 pub(crate) mod directive;
+pub(crate) mod document_set;
 pub(crate) mod enum_;
+pub(crate) mod error_kind;
+pub(crate) mod federation;
 pub(crate) mod field;
 pub(crate) mod fragment;
+pub(crate) mod incremental;
 pub(crate) mod input_object;
 pub(crate) mod interface;
+pub(crate) mod lint;
+#[cfg(feature = "lsp")]
+pub(crate) mod lsp;
 pub(crate) mod object;
+pub(crate) mod one_of;
 pub(crate) mod operation;
+pub(crate) mod progress;
+pub(crate) mod registry;
+pub(crate) mod rule;
+#[cfg(feature = "sarif")]
+pub(crate) mod sarif;
 pub(crate) mod scalar;
 pub(crate) mod schema;
 pub(crate) mod selection;
+pub(crate) mod spec_reference;
+pub(crate) mod suggestion;
 pub(crate) mod union_;
+pub(crate) mod unreachable;
 pub(crate) mod value;
 pub(crate) mod variable;
 
+pub use self::allowlist::OperationAllowlist;
+pub use self::batch::BatchResult;
+pub use self::batch::BatchValidator;
+pub use self::cache::ContentHash;
+pub use self::cache::InMemoryValidationCacheStore;
+pub use self::cache::ValidationCache;
+pub use self::cache::ValidationCacheKey;
+pub use self::cache::ValidationCacheStore;
+pub use self::custom_directive::DirectiveApplication;
+pub use self::document_set::DocumentSet;
+pub use self::error_kind::ValidationErrorKind;
+pub use self::lint::LintConfig;
+pub use self::lint::LintRule;
+pub use self::progress::ValidationPhase;
+pub use self::progress::ValidationProgress;
+pub use self::registry::rule_info;
+pub use self::registry::rules;
+pub use self::registry::RuleInfo;
+pub use self::rule::ValidationRule;
+#[cfg(feature = "sarif")]
+pub use self::sarif::SarifLog;
+pub use self::spec_reference::SpecReference;
+pub use self::value::coerce_const_value;
+pub use self::value::effective_default_value;
+pub use self::variable::analyze_variable_usages;
+pub use self::variable::VariableUsage;
+pub use self::variable::VariableUsageReport;
+pub use self::variable::VariableUsages;
 use crate::collections::HashMap;
+use crate::collections::HashSet;
 use crate::collections::IndexSet;
 use crate::diagnostic::CliReport;
+use crate::diagnostic::Color;
 use crate::diagnostic::Diagnostic;
 use crate::diagnostic::ToCliReport;
 use crate::executable::BuildError as ExecutableBuildError;
@@ -35,13 +136,29 @@ use crate::executable::ConflictingFieldType;
 use crate::executable::VariableDefinition;
 use crate::execution::GraphQLError;
 use crate::execution::Response;
+use crate::parser::LineColumn;
+use crate::parser::SourceFile;
 use crate::parser::SourceMap;
 use crate::parser::SourceSpan;
 use crate::schema::BuildError as SchemaBuildError;
+use crate::schema::CompositionError;
 use crate::schema::Implementers;
+#[cfg(feature = "serde_json")]
+use crate::schema::IntrospectionResponseError;
+use crate::schema::LinkError;
+use crate::schema::ReverseIndexes;
+use crate::schema::SupergraphError;
 use crate::Name;
 use crate::Node;
+use serde::Deserialize;
+use serde::Serialize;
 use std::fmt;
+use std::hash::Hash;
+use std::ops::ControlFlow;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::OnceLock;
 
@@ -129,6 +246,18 @@ pub(crate) struct ExecutableValidationContext<'a> {
     schema: Option<&'a Schema>,
     /// `schema.implementers_map()` is expensive to compute. This caches it for reuse.
     implementers_map: OnceLock<HashMap<Name, Implementers>>,
+    /// `schema.reverse_indexes()`, which are cached in the schema
+    /// and shared with other documents validated against it.
+    reverse_indexes: OnceLock<Option<Arc<ReverseIndexes>>>,
+    /// Fragments whose definitions were validated separately, as part of a fragment library.
+    /// Spreads of these fragments are still checked, but their definitions are not revisited.
+    prevalidated_fragments: Option<&'a HashSet<Name>>,
+    /// Opt-in rules. When None, only rules from the specification are run.
+    config: Option<&'a ValidationConfig>,
+    /// Selections visited so far, counted against `ValidationLimits::validation_steps`
+    steps: AtomicUsize,
+    /// Set when `steps` went over the budget, so that it is reported only once
+    budget_exceeded: AtomicBool,
 }
 
 impl<'a> ExecutableValidationContext<'a> {
@@ -136,14 +265,92 @@ impl<'a> ExecutableValidationContext<'a> {
         Self {
             schema,
             implementers_map: Default::default(),
+            reverse_indexes: Default::default(),
+            prevalidated_fragments: None,
+            config: None,
+            steps: AtomicUsize::new(0),
+            budget_exceeded: AtomicBool::new(false),
         }
     }
 
+    pub fn with_config(mut self, config: &'a ValidationConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    pub fn with_prevalidated_fragments(mut self, names: &'a HashSet<Name>) -> Self {
+        self.prevalidated_fragments = Some(names);
+        self
+    }
+
     /// Returns the schema to validate against, if any.
     pub fn schema(&self) -> Option<&'a Schema> {
         self.schema
     }
 
+    /// Returns the configured limit of how deeply fields can be nested, if any.
+    pub fn max_depth(&self) -> Option<usize> {
+        self.config.and_then(|config| config.max_depth_limit())
+    }
+
+    /// Returns the configured limits of depth and field count of introspection fields, if any.
+    pub fn introspection_limits(&self) -> (Option<usize>, Option<usize>) {
+        match self.config {
+            Some(config) => (
+                config.max_introspection_depth_limit(),
+                config.max_introspection_fields_limit(),
+            ),
+            None => (None, None),
+        }
+    }
+
+    /// Returns the configured limits on recursion and size, or the defaults.
+    pub fn limits(&self) -> ValidationLimits {
+        self.config
+            .map(|config| config.validation_limits().clone())
+            .unwrap_or_default()
+    }
+
+    /// Count `count` steps against the configured budget.
+    ///
+    /// Returns false if validation should stop. A diagnostic is pushed
+    /// the first time the budget is exceeded.
+    pub fn take_steps(
+        &self,
+        diagnostics: &mut DiagnosticList,
+        count: usize,
+        location: Option<SourceSpan>,
+    ) -> bool {
+        let Some(limit) = self
+            .config
+            .and_then(|config| config.validation_limits().validation_steps)
+        else {
+            return true;
+        };
+        let steps = self.steps.fetch_add(count, Ordering::Relaxed) + count;
+        if steps <= limit {
+            return true;
+        }
+        if !self.budget_exceeded.swap(true, Ordering::Relaxed) {
+            diagnostics.push(
+                location,
+                diagnostics::DiagnosticData::ValidationBudgetExceeded { limit },
+            );
+        }
+        false
+    }
+
+    /// Returns whether `rule` is checked, unless turned off with [`ValidationConfig::disable_rule`].
+    pub fn is_rule_enabled(&self, rule: ValidationRule) -> bool {
+        self.config
+            .is_none_or(|config| config.is_rule_enabled(rule.name()))
+    }
+
+    /// Returns whether validation stopped because it went over the step budget.
+    pub fn budget_exceeded(&self) -> bool {
+        self.budget_exceeded.load(Ordering::Relaxed)
+    }
+
     /// Returns a cached reference to the implementers map.
     pub fn implementers_map(&self) -> &HashMap<Name, Implementers> {
         self.implementers_map.get_or_init(|| {
@@ -153,6 +360,14 @@ impl<'a> ExecutableValidationContext<'a> {
         })
     }
 
+    /// Returns the members of the given union type, from indexes cached in the schema.
+    pub(crate) fn union_members(&self, union_: &str) -> Option<&IndexSet<Name>> {
+        self.reverse_indexes
+            .get_or_init(|| self.schema.map(Schema::reverse_indexes))
+            .as_deref()?
+            .union_members(union_)
+    }
+
     /// Returns a context for operation validation.
     pub fn operation_context<'o>(
         &'o self,
@@ -184,6 +399,880 @@ impl<'a> OperationValidationContext<'a> {
     pub fn implementers_map(&self) -> &HashMap<Name, Implementers> {
         self.executable.implementers_map()
     }
+
+    /// Returns the members of the given union type, from indexes cached in the schema.
+    pub(crate) fn union_members(&self, union_: &str) -> Option<&'a IndexSet<Name>> {
+        self.executable.union_members(union_)
+    }
+
+    /// Count `count` steps against the configured budget.
+    /// Returns false if validation should stop.
+    pub fn take_steps(
+        &self,
+        diagnostics: &mut DiagnosticList,
+        count: usize,
+        location: Option<SourceSpan>,
+    ) -> bool {
+        self.executable.take_steps(diagnostics, count, location)
+    }
+
+    /// Returns whether usages of deprecated fields, arguments, input fields, and enum values
+    /// should be reported.
+    pub fn deprecation_warnings(&self) -> bool {
+        self.executable
+            .config
+            .is_some_and(|config| config.deprecation_warnings_enabled())
+    }
+
+    /// Returns whether selections of `__schema` and `__type` should be reported.
+    pub fn forbids_introspection(&self) -> bool {
+        self.executable
+            .config
+            .is_some_and(|config| config.introspection_forbidden())
+    }
+
+    /// Returns whether selections on this abstract type must select `__typename`
+    /// or have a fragment for each possible type.
+    pub fn requires_typename(&self, abstract_type: &str) -> bool {
+        self.executable
+            .config
+            .is_some_and(|config| config.requires_typename(abstract_type))
+    }
+
+    /// Returns the configured limits of aliased and repeated fields in a selection set, if any.
+    pub fn selection_set_limits(&self) -> (Option<usize>, Option<usize>) {
+        match self.executable.config {
+            Some(config) => (
+                config.max_aliases_limit(),
+                config.max_repeated_fields_limit(),
+            ),
+            None => (None, None),
+        }
+    }
+
+    /// Returns how many fragment spreads deep the document can be walked.
+    pub fn fragment_nesting_limit(&self) -> usize {
+        self.executable.config.map_or_else(
+            || ValidationLimits::default().fragment_nesting,
+            |config| config.validation_limits().fragment_nesting,
+        )
+    }
+
+    /// Returns whether the definition of this fragment was already validated on its own.
+    pub fn is_prevalidated(&self, fragment_name: &Name) -> bool {
+        self.executable
+            .prevalidated_fragments
+            .is_some_and(|names| names.contains(fragment_name))
+    }
+}
+
+/// Configuration for [`Schema::validate_with_config`]
+/// and [`ExecutableDocument::validate_with_config`].
+///
+/// All rules are enabled by default.
+/// Rules are identified by the stable names of [`ValidationRule`] and [`LintRule`],
+/// as returned by [`DiagnosticData::rule`], such as `"UnusedFragment"` or `"UnusedVariable"`.
+///
+/// ```rust
+/// use apollo_compiler::validation::ValidationConfig;
+/// use apollo_compiler::validation::ValidationRule;
+///
+/// let config = ValidationConfig::new().disable_rule(ValidationRule::UnusedFragment.name());
+/// assert!(!config.is_rule_enabled("UnusedFragment"));
+/// assert!(config.is_rule_enabled(ValidationRule::UnusedVariable.name()));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationConfig {
+    disabled_rules: HashSet<&'static str>,
+    warning_rules: HashSet<&'static str>,
+    deprecation_warnings: bool,
+    introspection_forbidden: bool,
+    operation_allowlist: Option<Arc<OperationAllowlist>>,
+    allowed_unknown_directives: HashSet<String>,
+    unknown_directive_warnings: bool,
+    typename_required_on: HashSet<String>,
+    typename_required_everywhere: bool,
+    max_depth: Option<usize>,
+    max_aliases: Option<usize>,
+    max_repeated_fields: Option<usize>,
+    max_introspection_depth: Option<usize>,
+    max_introspection_fields: Option<usize>,
+    suppressions: Vec<Suppression>,
+    inline_suppressions: bool,
+    limits: ValidationLimits,
+    scalar_validators: HashMap<String, custom_scalar::ScalarValidator>,
+    directive_validators: HashMap<String, custom_directive::DirectiveValidator>,
+    progress_callback: Option<progress::ProgressCallback>,
+    spec_version: SpecVersion,
+    specified_by_urls_checked: bool,
+    repeated_directive_warnings: bool,
+    unreachable_selection_warnings: bool,
+    federation_field_sets: bool,
+    lenient_specified_by_urls: bool,
+}
+
+/// The edition of the GraphQL specification that validation follows,
+/// set with [`ValidationConfig::spec_version`].
+///
+/// Rules added to the specification after October 2021 only apply with [`SpecVersion::Draft`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum SpecVersion {
+    /// The [October 2021](https://spec.graphql.org/October2021/) edition
+    #[default]
+    October2021,
+    /// The [working draft](https://spec.graphql.org/draft/). This currently adds:
+    ///
+    /// * [OneOf input objects](https://spec.graphql.org/draft/#sec-OneOf-Input-Objects):
+    ///   fields of input objects with a `@oneOf` directive must be nullable without a default,
+    ///   and their values must have exactly one non-null field.
+    ///   The schema must define `directive @oneOf on INPUT_OBJECT`.
+    /// * [`@deprecated`](https://spec.graphql.org/draft/#sec--deprecated)
+    ///   must not be applied to required arguments and input fields.
+    Draft,
+}
+
+/// Limits on how deep or how much validation walks, set with [`ValidationConfig::limits`].
+///
+/// When a limit is reached, validation reports a diagnostic instead of going further.
+/// Servers can tighten these to bound the work done for untrusted documents,
+/// and IDEs or build tools can relax them for large trusted ones.
+///
+/// ```rust
+/// use apollo_compiler::validation::ValidationConfig;
+/// use apollo_compiler::validation::ValidationLimits;
+///
+/// let mut limits = ValidationLimits::default();
+/// limits.fragment_nesting = 20;
+/// limits.field_merging_budget = Some(10_000);
+/// let config = ValidationConfig::new().limits(limits);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ValidationLimits {
+    /// How many input object or directive definitions deep references are followed
+    /// when looking for cycles in a schema. Defaults to 32.
+    pub type_nesting: usize,
+    /// How many fragment spreads deep an executable document is walked,
+    /// such as when looking for fragment cycles or used variables. Defaults to 100.
+    pub fragment_nesting: usize,
+    /// How many levels of nested fields are compared to check that fields can be merged.
+    /// Defaults to 128.
+    pub field_merging_depth: usize,
+    /// How many fields in total can be expanded from selection sets and fragment spreads
+    /// to check that the fields of one operation or fragment can be merged.
+    /// Unlimited by default.
+    pub field_merging_budget: Option<usize>,
+    /// How many selections in total are visited while validating an executable document.
+    /// Fragment definitions are revisited for each of their spreads,
+    /// so a small document with many nested spreads can take many steps.
+    /// When the budget is exceeded, validation stops and reports a diagnostic.
+    /// Unlimited by default.
+    ///
+    /// Unlike a time limit, the number of steps does not depend on the machine or its load,
+    /// so a document is either always or never over the budget.
+    pub validation_steps: Option<usize>,
+}
+
+impl Default for ValidationLimits {
+    fn default() -> Self {
+        Self {
+            type_nesting: DEFAULT_RECURSION_LIMIT,
+            fragment_nesting: 100,
+            field_merging_depth: 128,
+            field_merging_budget: None,
+            validation_steps: None,
+        }
+    }
+}
+
+/// A diagnostic suppressed with [`ValidationConfig::suppress`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Suppression {
+    path: PathBuf,
+    rule: String,
+    coordinate: Option<SchemaCoordinate>,
+}
+
+impl ValidationConfig {
+    /// Returns a configuration with all rules enabled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Turn off the rule with the given identifier, such as the [name][ValidationRule::name]
+    /// of a [`ValidationRule`] or of a [`LintRule`].
+    ///
+    /// Rules checked by a pass of their own, such as `UnusedFragment`, field merging,
+    /// `@defer` and `@stream` rules, and opt-in rules, are not checked at all
+    /// when all of the rules of that pass are off.
+    /// Other rules are checked in the same walk as the rest of validation,
+    /// and their diagnostics are dropped.
+    ///
+    /// Identifiers that are not in [`rules`] are ignored, including those of limits
+    /// such as `ValidationBudgetExceeded` that are not rules and cannot be turned off.
+    /// Configuration files can check identifiers with [`rule_info`] to report typos.
+    pub fn disable_rule(mut self, rule: &str) -> Self {
+        if let Some(info) = rule_info(rule) {
+            self.disabled_rules.insert(info.id);
+        }
+        self
+    }
+
+    /// Turn back on a rule previously turned off with [`disable_rule`][Self::disable_rule]
+    pub fn enable_rule(mut self, rule: &str) -> Self {
+        self.disabled_rules.remove(rule);
+        self
+    }
+
+    /// Returns whether diagnostics from the rule with the given identifier are reported
+    pub fn is_rule_enabled(&self, rule: &str) -> bool {
+        !self.disabled_rules.contains(rule)
+    }
+
+    /// Returns whether any of `rules` is enabled.
+    /// Otherwise, a pass that only checks those rules can be skipped.
+    pub(crate) fn any_rule_enabled(&self, rules: &[ValidationRule]) -> bool {
+        rules.iter().any(|rule| self.is_rule_enabled(rule.name()))
+    }
+
+    /// Set how fragment definitions that no operation uses are reported:
+    /// as errors (the default), as warnings, or not at all with `None`.
+    ///
+    /// This is useful for documents that are later concatenated with other files
+    /// that use their fragments.
+    /// As with other warnings, a document with only warnings passes validation,
+    /// and the warnings are in [`WithWarnings::warnings`].
+    ///
+    /// ```rust
+    /// use apollo_compiler::validation::Severity;
+    /// use apollo_compiler::validation::ValidationConfig;
+    ///
+    /// let config = ValidationConfig::new()
+    ///     .unused_fragment_severity(Some(Severity::Warning))
+    ///     .unused_variable_severity(None);
+    /// ```
+    pub fn unused_fragment_severity(self, severity: Option<Severity>) -> Self {
+        self.rule_severity(ValidationRule::UnusedFragment, severity)
+    }
+
+    /// Set how variables that their operation does not use are reported:
+    /// as errors (the default), as warnings, or not at all with `None`.
+    /// See [`unused_fragment_severity`][Self::unused_fragment_severity].
+    pub fn unused_variable_severity(self, severity: Option<Severity>) -> Self {
+        self.rule_severity(ValidationRule::UnusedVariable, severity)
+    }
+
+    fn rule_severity(mut self, rule: ValidationRule, severity: Option<Severity>) -> Self {
+        let rule = rule.name();
+        self.disabled_rules.remove(rule);
+        self.warning_rules.remove(rule);
+        match severity {
+            None => {
+                self.disabled_rules.insert(rule);
+            }
+            // There is no separate lint class for validation rules
+            Some(Severity::Warning | Severity::Lint) => {
+                self.warning_rules.insert(rule);
+            }
+            Some(Severity::Error) => {}
+        }
+        self
+    }
+
+    /// Report a [warning][Severity::Warning] when an executable document selects a field,
+    /// passes a field argument or input object field, or uses an enum value
+    /// marked `@deprecated` in the schema. Off by default.
+    /// The diagnostic includes the deprecation reason.
+    ///
+    /// Warnings do not fail validation:
+    /// a document that only has warnings returns `Ok`, with them in [`WithWarnings::warnings`].
+    pub fn deprecation_warnings(mut self, enable: bool) -> Self {
+        self.deprecation_warnings = enable;
+        self
+    }
+
+    /// Returns whether usages of deprecated fields, arguments, input fields, and enum values
+    /// are reported
+    pub fn deprecation_warnings_enabled(&self) -> bool {
+        self.deprecation_warnings && self.is_rule_enabled(ValidationRule::DeprecatedUsage.name())
+    }
+
+    /// Report an error when an executable document selects the `__schema` or `__type`
+    /// introspection fields. Off by default. `__typename` is still allowed.
+    ///
+    /// Servers that disable introspection in production can use this
+    /// to reject such operations during validation instead of during execution.
+    pub fn forbid_introspection(mut self, enable: bool) -> Self {
+        self.introspection_forbidden = enable;
+        self
+    }
+
+    /// Returns whether selections of `__schema` and `__type` are reported
+    pub fn introspection_forbidden(&self) -> bool {
+        self.introspection_forbidden
+            && self.is_rule_enabled(ValidationRule::IntrospectionDisabled.name())
+    }
+
+    /// Report an `OperationNotAllowed` error when an executable document
+    /// is not in `allowlist`, for servers that only execute persisted operations.
+    /// Other rules still apply to allowed documents.
+    ///
+    /// An `Arc` can be passed to share a large allowlist between configurations.
+    pub fn operation_allowlist(mut self, allowlist: impl Into<Arc<OperationAllowlist>>) -> Self {
+        self.operation_allowlist = Some(allowlist.into());
+        self
+    }
+
+    /// Returns the allowlist that executable documents are checked against, if any
+    pub fn checked_operation_allowlist(&self) -> Option<&OperationAllowlist> {
+        self.operation_allowlist
+            .as_deref()
+            .filter(|_| self.is_rule_enabled(ValidationRule::OperationNotAllowed.name()))
+    }
+
+    /// Do not report usages of the directive with the given name (without `@`)
+    /// in executable documents if the schema does not define it.
+    ///
+    /// This is for client-only directives like `@client`,
+    /// which are removed from operations before they are sent to a server.
+    ///
+    /// ```rust
+    /// use apollo_compiler::validation::ValidationConfig;
+    ///
+    /// let config = ValidationConfig::new()
+    ///     .allow_unknown_directive("client")
+    ///     .allow_unknown_directive("connection");
+    /// ```
+    pub fn allow_unknown_directive(mut self, name: impl Into<String>) -> Self {
+        self.allowed_unknown_directives.insert(name.into());
+        self
+    }
+
+    /// Report usages of directives that the schema does not define in executable documents
+    /// as [warnings][Severity::Warning] instead of errors.
+    /// Directives allowed with [`allow_unknown_directive`][Self::allow_unknown_directive]
+    /// are not reported at all.
+    ///
+    /// Undefined directives in schemas are still errors.
+    pub fn unknown_directive_warnings(mut self, enable: bool) -> Self {
+        self.unknown_directive_warnings = enable;
+        self
+    }
+
+    /// Require fields of the given union or interface type to select `__typename`,
+    /// or to have a fragment for each of its possible object types.
+    ///
+    /// Client-side caches that normalize responses need to know the concrete type
+    /// of every object, which is otherwise missing from responses to such selections.
+    pub fn require_typename_on(mut self, abstract_type: impl Into<String>) -> Self {
+        self.typename_required_on.insert(abstract_type.into());
+        self
+    }
+
+    /// Like [`require_typename_on`][Self::require_typename_on], for every union and interface type.
+    pub fn require_typename_on_all_abstract_types(mut self, enable: bool) -> Self {
+        self.typename_required_everywhere = enable;
+        self
+    }
+
+    /// Returns whether selections on the given union or interface type must select `__typename`
+    /// or have a fragment for each possible type
+    pub fn requires_typename(&self, abstract_type: &str) -> bool {
+        (self.typename_required_everywhere || self.typename_required_on.contains(abstract_type))
+            && self.is_rule_enabled(ValidationRule::MissingTypename.name())
+    }
+
+    /// Limit how deeply fields can be nested in an operation, including through fragments.
+    /// The fields of the root selection set are at depth 1. Unlimited by default.
+    ///
+    /// Deeply nested operations can be expensive to execute,
+    /// so servers can use this to reject them before execution starts.
+    pub fn max_depth(mut self, limit: usize) -> Self {
+        self.max_depth = Some(limit);
+        self
+    }
+
+    /// Returns the limit set by [`max_depth`][Self::max_depth], if any
+    pub fn max_depth_limit(&self) -> Option<usize> {
+        self.max_depth
+            .filter(|_| self.is_rule_enabled(ValidationRule::MaxDepth.name()))
+    }
+
+    /// Limit how many aliased fields a single selection set can have. Unlimited by default.
+    ///
+    /// Aliases let an operation request the same expensive field many times in one request.
+    pub fn max_aliases(mut self, limit: usize) -> Self {
+        self.max_aliases = Some(limit);
+        self
+    }
+
+    /// Returns the limit set by [`max_aliases`][Self::max_aliases], if any
+    pub fn max_aliases_limit(&self) -> Option<usize> {
+        self.max_aliases
+            .filter(|_| self.is_rule_enabled(ValidationRule::TooManyAliases.name()))
+    }
+
+    /// Limit how many times a single selection set can select a field
+    /// with the same response key, as in `{ user user user }`. Unlimited by default.
+    pub fn max_repeated_fields(mut self, limit: usize) -> Self {
+        self.max_repeated_fields = Some(limit);
+        self
+    }
+
+    /// Returns the limit set by [`max_repeated_fields`][Self::max_repeated_fields], if any
+    pub fn max_repeated_fields_limit(&self) -> Option<usize> {
+        self.max_repeated_fields
+            .filter(|_| self.is_rule_enabled(ValidationRule::TooManyRepeatedFields.name()))
+    }
+
+    /// Limit how deeply fields can be nested in a `__schema` or `__type` introspection field,
+    /// including through fragments. The introspection field itself is at depth 1.
+    /// Unlimited by default.
+    ///
+    /// Introspection types refer to each other, so an introspection selection can be
+    /// nested arbitrarily deep, such as in `__schema { types { fields { type { fields … } } } }`.
+    /// Common introspection queries nest `ofType` several levels to unwrap list
+    /// and non-null types, so leave room for those.
+    pub fn max_introspection_depth(mut self, limit: usize) -> Self {
+        self.max_introspection_depth = Some(limit);
+        self
+    }
+
+    /// Returns the limit set by [`max_introspection_depth`][Self::max_introspection_depth], if any
+    pub fn max_introspection_depth_limit(&self) -> Option<usize> {
+        self.max_introspection_depth
+            .filter(|_| self.is_rule_enabled(ValidationRule::IntrospectionDepth.name()))
+    }
+
+    /// Limit how many fields a `__schema` or `__type` introspection field can select in total,
+    /// including nested fields and fields of fragments for each spread. Unlimited by default.
+    pub fn max_introspection_fields(mut self, limit: usize) -> Self {
+        self.max_introspection_fields = Some(limit);
+        self
+    }
+
+    /// Returns the limit set by [`max_introspection_fields`][Self::max_introspection_fields],
+    /// if any
+    pub fn max_introspection_fields_limit(&self) -> Option<usize> {
+        self.max_introspection_fields
+            .filter(|_| self.is_rule_enabled(ValidationRule::IntrospectionFieldCount.name()))
+    }
+
+    /// Suppress diagnostics of the given rule in the source file with the given path,
+    /// either all of them or only those about the schema element at `coordinate`.
+    ///
+    /// Unlike with [`disable_rule`][Self::disable_rule], suppressed diagnostics are still
+    /// recorded and available from [`DiagnosticList::suppressed`].
+    pub fn suppress(
+        mut self,
+        path: impl Into<PathBuf>,
+        rule: impl Into<String>,
+        coordinate: Option<SchemaCoordinate>,
+    ) -> Self {
+        self.suppressions.push(Suppression {
+            path: path.into(),
+            rule: rule.into(),
+            coordinate,
+        });
+        self
+    }
+
+    /// Suppress diagnostics that start on the line after an `# apollo-disable-next-line` comment.
+    /// The comment can list rules separated by commas or spaces,
+    /// otherwise it suppresses diagnostics of every rule. Off by default.
+    ///
+    /// ```graphql
+    /// # apollo-disable-next-line UnusedFragment
+    /// fragment Unused on Query { field }
+    /// ```
+    ///
+    /// Only enable this for trusted source text:
+    /// clients could otherwise send documents that opt out of limits such as
+    /// [`max_depth`][Self::max_depth].
+    pub fn inline_suppressions(mut self, enable: bool) -> Self {
+        self.inline_suppressions = enable;
+        self
+    }
+
+    /// Replace the default [limits][ValidationLimits] on recursion and size during validation
+    pub fn limits(mut self, limits: ValidationLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Returns the limits set by [`limits`][Self::limits], or the defaults
+    pub fn validation_limits(&self) -> &ValidationLimits {
+        &self.limits
+    }
+
+    /// Check literal values of the custom scalar `scalar` in executable documents
+    /// with `validator`, which returns an error message for invalid values.
+    ///
+    /// The validator is called for values of arguments and of variable default values,
+    /// including those nested in lists and input objects.
+    /// Variables and `null` are not passed to it.
+    /// An error is reported as an `InvalidScalarValue` diagnostic.
+    ///
+    /// ```rust
+    /// use apollo_compiler::ast::Value;
+    /// use apollo_compiler::validation::ValidationConfig;
+    ///
+    /// let config = ValidationConfig::new().validate_scalar("DateTime", |value| match value {
+    ///     Value::String(s) if s.contains('T') => Ok(()),
+    ///     _ => Err("expected an ISO 8601 date and time".to_owned()),
+    /// });
+    /// ```
+    pub fn validate_scalar(
+        mut self,
+        scalar: impl Into<String>,
+        validator: impl Fn(&ast::Value) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.scalar_validators.insert(
+            scalar.into(),
+            custom_scalar::ScalarValidator(Arc::new(validator)),
+        );
+        self
+    }
+
+    /// Returns the validator registered with [`validate_scalar`][Self::validate_scalar]
+    /// for `scalar`, if any
+    #[allow(clippy::type_complexity)]
+    pub fn scalar_validator(
+        &self,
+        scalar: &str,
+    ) -> Option<&(dyn Fn(&ast::Value) -> Result<(), String> + Send + Sync)> {
+        self.scalar_validators
+            .get(scalar)
+            .map(|validator| &*validator.0)
+    }
+
+    pub(crate) fn has_scalar_validators(&self) -> bool {
+        !self.scalar_validators.is_empty()
+    }
+
+    /// Check applications of the directive `directive` in schemas and executable documents
+    /// with `validator`, after the checks of the specification.
+    ///
+    /// The validator receives each application with its arguments coerced
+    /// to the types of the directive definition, including default values of arguments
+    /// that are not provided. Arguments that are variables are passed as written.
+    /// Each [error][DirectiveApplication::error] it reports
+    /// is an `InvalidDirectiveApplication` diagnostic.
+    ///
+    /// ```rust
+    /// use apollo_compiler::validation::ValidationConfig;
+    ///
+    /// let config = ValidationConfig::new().validate_directive("constraint", |application| {
+    ///     let Some(pattern) = application.argument("pattern").and_then(|v| v.as_str()) else {
+    ///         return;
+    ///     };
+    ///     if pattern.is_empty() {
+    ///         let location = application.argument("pattern").unwrap().location();
+    ///         application.error(location, "`pattern` must not be empty");
+    ///     }
+    /// });
+    /// ```
+    pub fn validate_directive(
+        mut self,
+        directive: impl Into<String>,
+        validator: impl Fn(&mut DirectiveApplication<'_>) + Send + Sync + 'static,
+    ) -> Self {
+        self.directive_validators.insert(
+            directive.into(),
+            custom_directive::DirectiveValidator(Arc::new(validator)),
+        );
+        self
+    }
+
+    pub(crate) fn directive_validator(
+        &self,
+        directive: &str,
+    ) -> Option<&custom_directive::DirectiveValidator> {
+        self.directive_validators.get(directive)
+    }
+
+    pub(crate) fn has_directive_validators(&self) -> bool {
+        !self.directive_validators.is_empty()
+    }
+
+    /// Call `callback` before each phase of validation, such as to show progress
+    /// when validating a large schema in an interactive tool.
+    ///
+    /// Returning [`ControlFlow::Break`] cancels validation:
+    /// later phases do not run, and a `ValidationCancelled` error is reported
+    /// so that the result is never considered valid.
+    ///
+    /// ```rust
+    /// use apollo_compiler::validation::ValidationConfig;
+    /// use apollo_compiler::Schema;
+    /// use std::ops::ControlFlow;
+    /// use std::sync::atomic::AtomicBool;
+    /// use std::sync::atomic::Ordering;
+    /// use std::sync::Arc;
+    ///
+    /// let cancelled = Arc::new(AtomicBool::new(false));
+    /// let config = ValidationConfig::new().on_progress({
+    ///     let cancelled = cancelled.clone();
+    ///     move |progress| {
+    ///         println!("{:?}: {:.0}%", progress.phase, progress.fraction() * 100.0);
+    ///         if cancelled.load(Ordering::Relaxed) {
+    ///             ControlFlow::Break(())
+    ///         } else {
+    ///             ControlFlow::Continue(())
+    ///         }
+    ///     }
+    /// });
+    /// let schema = Schema::parse("type Query { a: Int }", "schema.graphql").unwrap();
+    /// assert!(schema.clone().validate_with_config(&config).is_ok());
+    /// cancelled.store(true, Ordering::Relaxed);
+    /// assert!(schema.validate_with_config(&config).is_err());
+    /// ```
+    pub fn on_progress(
+        mut self,
+        callback: impl Fn(&ValidationProgress) -> ControlFlow<()> + Send + Sync + 'static,
+    ) -> Self {
+        self.progress_callback = Some(progress::ProgressCallback(Arc::new(callback)));
+        self
+    }
+
+    pub(crate) fn progress_callback(&self) -> Option<&progress::ProgressCallback> {
+        self.progress_callback.as_ref()
+    }
+
+    /// Select the edition of the specification to validate against.
+    /// Defaults to [`SpecVersion::October2021`].
+    ///
+    /// ```rust
+    /// use apollo_compiler::validation::SpecVersion;
+    /// use apollo_compiler::validation::ValidationConfig;
+    ///
+    /// let config = ValidationConfig::new().spec_version(SpecVersion::Draft);
+    /// assert_eq!(config.validation_spec_version(), SpecVersion::Draft);
+    /// ```
+    pub fn spec_version(mut self, version: SpecVersion) -> Self {
+        self.spec_version = version;
+        self
+    }
+
+    /// Returns the version set by [`spec_version`][Self::spec_version]
+    pub fn validation_spec_version(&self) -> SpecVersion {
+        self.spec_version
+    }
+
+    /// Report an error when the `url` argument of a `@specifiedBy` directive in a schema
+    /// is not an absolute URI as defined by [RFC 3986](https://www.rfc-editor.org/rfc/rfc3986),
+    /// such as `"example.com"` without a scheme. Off by default.
+    pub fn check_specified_by_urls(mut self, enable: bool) -> Self {
+        self.specified_by_urls_checked = enable;
+        self
+    }
+
+    /// With [`check_specified_by_urls`][Self::check_specified_by_urls],
+    /// only require a scheme and no whitespace or control characters.
+    /// Other characters outside of RFC 3986, such as non-ASCII letters
+    /// or a `%` not followed by two hexadecimal digits, are then accepted.
+    pub fn lenient_specified_by_urls(mut self, enable: bool) -> Self {
+        self.lenient_specified_by_urls = enable;
+        self
+    }
+
+    /// Report a [warning][Severity::Warning] when a repeatable directive is applied
+    /// to the same schema or document element more than once with the same arguments,
+    /// such as `@tag(name: "x") @tag(name: "x")`. Off by default.
+    ///
+    /// Argument order is ignored. This is valid GraphQL, but almost always a copy-paste mistake.
+    pub fn repeated_directive_warnings(mut self, enable: bool) -> Self {
+        self.repeated_directive_warnings = enable;
+        self
+    }
+
+    /// Returns whether repeated directive applications with identical arguments are reported
+    pub fn repeated_directive_warnings_enabled(&self) -> bool {
+        self.repeated_directive_warnings
+            && self.is_rule_enabled(ValidationRule::RepeatedDirectiveArguments.name())
+    }
+
+    /// Report a [warning][Severity::Warning] when the type condition of a fragment
+    /// cannot match any type allowed by the fragments enclosing it. Off by default.
+    ///
+    /// For example with `Cat` and `Dog` implementing `Pet`, `... on Dog` is unreachable
+    /// in `... on Cat { ... on Pet { ... on Dog { name } } }`, including through named fragments.
+    /// Each fragment is valid where it is spread, but the selection can never produce data.
+    pub fn unreachable_selection_warnings(mut self, enable: bool) -> Self {
+        self.unreachable_selection_warnings = enable;
+        self
+    }
+
+    /// Returns whether fragments that can never match are reported
+    pub fn unreachable_selection_warnings_enabled(&self) -> bool {
+        self.unreachable_selection_warnings
+            && self.is_rule_enabled(ValidationRule::UnreachableSelection.name())
+    }
+
+    /// Validate the `fields` arguments of Apollo Federation `@key`, `@requires`,
+    /// and `@provides` directives in a subgraph schema. Off by default.
+    ///
+    /// Each argument is parsed as a selection set on the relevant type,
+    /// and diagnostics point inside the string literal when possible.
+    /// Aliases and directives are rejected in all three, and arguments in `@key`.
+    pub fn federation_field_sets(mut self, enable: bool) -> Self {
+        self.federation_field_sets = enable;
+        self
+    }
+
+    /// Returns whether federation `fields` arguments are validated
+    pub fn federation_field_sets_enabled(&self) -> bool {
+        self.federation_field_sets
+    }
+
+    /// Returns whether `@specifiedBy` URLs are checked, and whether leniently
+    pub(crate) fn specified_by_url_check(&self) -> Option<scalar::UrlCheck> {
+        if !self.specified_by_urls_checked
+            || !self.is_rule_enabled(ValidationRule::InvalidSpecifiedByUrl.name())
+        {
+            None
+        } else if self.lenient_specified_by_urls {
+            Some(scalar::UrlCheck::Lenient)
+        } else {
+            Some(scalar::UrlCheck::Strict)
+        }
+    }
+
+    /// Returns whether a diagnostic of `rule` at `location` is suppressed
+    fn is_suppressed(
+        &self,
+        sources: &SourceMap,
+        rule: &str,
+        location: Option<SourceSpan>,
+        coordinate: impl FnOnce() -> Option<SchemaCoordinate>,
+    ) -> bool {
+        let Some(location) = location else {
+            return false;
+        };
+        let Some(file) = sources.get(&location.file_id()) else {
+            return false;
+        };
+        if self.inline_suppressions && is_disabled_by_comment(file, location.offset(), rule) {
+            return true;
+        }
+        let mut suppressions = self
+            .suppressions
+            .iter()
+            .filter(|suppression| suppression.rule == rule && suppression.path == file.path())
+            .peekable();
+        if suppressions.peek().is_none() {
+            return false;
+        }
+        let coordinate = coordinate();
+        suppressions.any(|suppression| {
+            suppression.coordinate.is_none() || suppression.coordinate == coordinate
+        })
+    }
+}
+
+const DISABLE_NEXT_LINE: &str = "apollo-disable-next-line";
+
+/// Returns whether the line before `offset` is an `# apollo-disable-next-line` comment
+/// that lists `rule` or no rule at all
+fn is_disabled_by_comment(file: &SourceFile, offset: usize, rule: &str) -> bool {
+    let line_index = file.line_index();
+    let Some(line) = line_index.line_of(offset) else {
+        return false;
+    };
+    let (Some(start), Some(end)) = (line_index.line_start(line - 1), line_index.line_start(line))
+    else {
+        return false;
+    };
+    let Some(comment) = file
+        .source_text()
+        .get(start..end)
+        .and_then(|previous_line| previous_line.trim().strip_prefix('#'))
+        .and_then(|comment| comment.trim_start().strip_prefix(DISABLE_NEXT_LINE))
+    else {
+        return false;
+    };
+    if comment.starts_with(|c: char| !c.is_whitespace()) {
+        // Some other word that starts with the same prefix
+        return false;
+    }
+    let mut rules = comment
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|rule| !rule.is_empty())
+        .peekable();
+    rules.peek().is_none() || rules.any(|listed| listed == rule)
+}
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The schema or document is invalid
+    Error,
+    /// The schema or document is valid, but something in it may need attention
+    Warning,
+    /// A style convention is not followed, as checked by [`Schema::lint`]
+    Lint,
+}
+
+/// A [`serde`]-serializable diagnostic with source locations resolved to file paths
+/// and line/column numbers, for tools that consume validation results.
+///
+/// A [`DiagnosticList`] serializes as a sequence of these.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SerializedDiagnostic {
+    /// The rule that emitted this diagnostic, as returned by [`DiagnosticData::rule`]
+    pub code: Option<String>,
+    pub message: String,
+    pub severity: Severity,
+    /// The main location of this diagnostic, if known
+    pub location: Option<SerializedLocation>,
+    /// Messages about the main location and related locations
+    #[serde(default)]
+    pub labels: Vec<SerializedLabel>,
+    /// A suggestion on how to fix this diagnostic
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help: Option<String>,
+    /// Names that may have been meant instead of an undefined name,
+    /// as returned by [`DiagnosticData::suggestions`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<Name>,
+}
+
+/// A source location in a [`SerializedDiagnostic`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SerializedLocation {
+    /// The path of the source file, as given to the parser
+    pub file: PathBuf,
+    /// Byte offset of the start of the location in the source file
+    pub start_offset: usize,
+    /// Byte offset of the end of the location in the source file
+    pub end_offset: usize,
+    pub start: LineColumn,
+    pub end: LineColumn,
+}
+
+/// A labeled location in a [`SerializedDiagnostic`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializedLabel {
+    pub location: SerializedLocation,
+    pub message: String,
+}
+
+impl SerializedLocation {
+    fn new(location: SourceSpan, sources: &SourceMap) -> Option<Self> {
+        let file = sources.get(&location.file_id())?;
+        let range = location.line_column_range(sources)?;
+        Some(Self {
+            file: file.path().to_owned(),
+            start_offset: location.offset(),
+            end_offset: location.end_offset(),
+            start: range.start,
+            end: range.end,
+        })
+    }
 }
 
 /// A conversion failed with some errors, but also resulted in a partial document.
@@ -215,6 +1304,143 @@ impl<T> fmt::Display for WithErrors<T> {
     }
 }
 
+/// Validation passed, but reported some [warnings][Severity::Warning]
+/// (or lints), such as usages of deprecated fields.
+///
+/// Dereferences to the [`Valid`] schema or document, so it can be used where one is expected.
+/// The [`Debug`][fmt::Debug] trait is implemented by forwarding to [`Self::warnings`].
+pub struct WithWarnings<T> {
+    /// The validated schema or document
+    pub valid: Valid<T>,
+
+    /// Diagnostics that do not fail validation. Often empty.
+    pub warnings: DiagnosticList,
+}
+
+impl<T> WithWarnings<T> {
+    /// Discard warnings and return the validated schema or document
+    pub fn into_valid(self) -> Valid<T> {
+        self.valid
+    }
+}
+
+impl<T> std::ops::Deref for WithWarnings<T> {
+    type Target = Valid<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.valid
+    }
+}
+
+impl<T> fmt::Debug for WithWarnings<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.warnings.fmt(f)
+    }
+}
+
+/// The diagnostics of a schema or document that failed validation, as an owned error.
+///
+/// Unlike [`WithErrors`], this keeps neither the partial schema or document nor its source files.
+/// It is `'static` and implements [`std::error::Error`], so services can propagate it with `?`
+/// instead of unwrapping, then log each [`ValidationError`] as structured data.
+///
+/// ```rust
+/// use apollo_compiler::validation::Valid;
+/// use apollo_compiler::validation::ValidationErrorKind;
+/// use apollo_compiler::validation::ValidationErrors;
+/// use apollo_compiler::Schema;
+///
+/// fn load(sdl: &str) -> Result<Valid<Schema>, ValidationErrors> {
+///     Ok(Schema::parse_and_validate(sdl, "schema.graphql")?)
+/// }
+///
+/// let errors = load("type Query { a: Missing }").unwrap_err();
+/// let error = &errors.errors[0];
+/// assert_eq!(error.kind, ValidationErrorKind::UndefinedType);
+/// assert_eq!(error.rule.as_deref(), Some("UndefinedDefinition"));
+/// let location = error.location.as_ref().unwrap();
+/// assert_eq!((location.start.line, location.start.column), (1, 17));
+/// assert_eq!(
+///     error.to_string(),
+///     "schema.graphql:1:17: [undefinedType] cannot find type `Missing` in this document"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationErrors {
+    /// Every diagnostic, sorted by location
+    pub errors: Vec<ValidationError>,
+}
+
+/// A diagnostic in [`ValidationErrors`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationError {
+    /// The invariant that is broken, as returned by [`DiagnosticData::kind`]
+    pub kind: ValidationErrorKind,
+    /// The rule that emitted this diagnostic, as returned by [`DiagnosticData::rule`]
+    pub rule: Option<String>,
+    pub message: String,
+    pub severity: Severity,
+    /// The main location of this diagnostic, if known
+    pub location: Option<SerializedLocation>,
+}
+
+impl From<DiagnosticList> for ValidationErrors {
+    fn from(diagnostics: DiagnosticList) -> Self {
+        let errors = diagnostics
+            .iter()
+            .map(|diagnostic| ValidationError {
+                kind: diagnostic.error.kind(),
+                rule: diagnostic.error.rule().map(ToOwned::to_owned),
+                message: diagnostic.error.to_string(),
+                severity: diagnostic.error.severity(),
+                location: diagnostic
+                    .error
+                    .location
+                    .and_then(|location| SerializedLocation::new(location, diagnostic.sources)),
+            })
+            .collect();
+        Self { errors }
+    }
+}
+
+impl<T> From<WithErrors<T>> for ValidationErrors {
+    fn from(with_errors: WithErrors<T>) -> Self {
+        with_errors.errors.into()
+    }
+}
+
+/// Formats one diagnostic per line, prefixed with its location and kind
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, error) in self.errors.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+/// Formats as `path:line:column: [kind] message`, or `[kind] message` without a location
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(location) = &self.location {
+            write!(
+                f,
+                "{}:{}:{}: ",
+                location.file.display(),
+                location.start.line,
+                location.start.column
+            )?;
+        }
+        write!(f, "[{}] {}", self.kind, self.message)
+    }
+}
+
 /// Returned as an error for situtations that should not happen with a valid schema or document.
 ///
 /// Since the relevant APIs take [`Valid<_>`][crate::validation::Valid] parameters,
@@ -254,6 +1480,7 @@ impl SuspectedValidationBug {
 pub struct DiagnosticList {
     pub(crate) sources: SourceMap,
     diagnostics_data: Vec<DiagnosticData>,
+    suppressed: Vec<DiagnosticData>,
 }
 
 // TODO(@goto-bus-stop) Can/should this be non-pub?
@@ -262,6 +1489,8 @@ pub struct DiagnosticList {
 pub struct DiagnosticData {
     location: Option<SourceSpan>,
     details: Details,
+    /// Downgraded to a warning by [`ValidationConfig`]
+    warning: bool,
 }
 
 #[derive(thiserror::Error, Debug, Clone)]
@@ -270,10 +1499,22 @@ pub(crate) enum Details {
     ParserLimit { message: String },
     #[error("syntax error: {message}")]
     SyntaxError { message: String },
+    /// A deviation from the grammar accepted by [`Parser::lenient`][crate::parser::Parser::lenient]
+    #[error("{message}")]
+    SyntaxWarning { message: String },
     #[error("{0}")]
     SchemaBuildError(SchemaBuildError),
     #[error("{0}")]
     ExecutableBuildError(ExecutableBuildError),
+    #[error("{0}")]
+    SupergraphError(SupergraphError),
+    #[error("{0}")]
+    CompositionError(CompositionError),
+    #[error("{0}")]
+    LinkError(LinkError),
+    #[cfg(feature = "serde_json")]
+    #[error("{0}")]
+    IntrospectionResponseError(IntrospectionResponseError),
     // TODO: Merge ValidationError into this enum
     #[error(transparent)]
     CompilerDiagnostic(diagnostics::DiagnosticData),
@@ -281,85 +1522,345 @@ pub(crate) enum Details {
     RecursionLimitError,
 }
 
+/// Path to a nested value within an argument or variable default value,
+/// such as `where.filters[2].range.min`.
+///
+/// Included in value validation diagnostics
+/// so that the offending branch of a large input literal can be identified.
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq)]
+pub struct ValuePath(Vec<ValuePathElement>);
+
+/// An element of a [`ValuePath`]
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub enum ValuePathElement {
+    /// The argument whose value contains the path
+    Argument(Name),
+    /// The variable whose default value contains the path
+    Variable(Name),
+    /// A field of an input object value
+    Field(Name),
+    /// An item of a list value
+    Index(usize),
+}
+
+impl ValuePath {
+    /// Returns the elements of this path, starting with the argument or variable.
+    pub fn elements(&self) -> &[ValuePathElement] {
+        &self.0
+    }
+
+    /// Returns whether this path points inside of a list or input object value,
+    /// rather than at the whole value of an argument or variable.
+    pub fn is_nested(&self) -> bool {
+        self.0.len() > 1
+    }
+}
+
+impl fmt::Display for ValuePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for element in &self.0 {
+            match element {
+                ValuePathElement::Argument(name) => write!(f, "{name}")?,
+                ValuePathElement::Variable(name) => write!(f, "${name}")?,
+                ValuePathElement::Field(name) => write!(f, ".{name}")?,
+                ValuePathElement::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromIterator<ValuePathElement> for ValuePath {
+    fn from_iter<I: IntoIterator<Item = ValuePathElement>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
 impl DiagnosticData {
-    /// Returns the internal error name for an (operation) validation error.
-    /// This is meant for debugging apollo-rs, not for public consumption.
-    #[doc(hidden)]
-    pub fn unstable_error_name(&self) -> Option<&'static str> {
+    /// If this diagnostic is about an invalid (possibly nested) input value,
+    /// returns the path to that value from its argument or variable.
+    pub fn value_path(&self) -> Option<&ValuePath> {
         match &self.details {
-            Details::CompilerDiagnostic(diagnostic) => {
-                use diagnostics::DiagnosticData::*;
-                Some(match diagnostic {
-                    RecursionError { .. } => "RecursionError",
-                    UniqueVariable { .. } => "UniqueVariable",
-                    UniqueArgument { .. } => "UniqueArgument",
-                    UniqueInputValue { .. } => "UniqueInputValue",
-                    UndefinedArgument { .. } => "UndefinedArgument",
-                    UndefinedDefinition { .. } => "UndefinedDefinition",
-                    UndefinedDirective { .. } => "UndefinedDirective",
-                    UndefinedVariable { .. } => "UndefinedVariable",
-                    UndefinedFragment { .. } => "UndefinedFragment",
-                    UndefinedEnumValue { .. } => "UndefinedEnumValue",
-                    UndefinedInputValue { .. } => "UndefinedInputValue",
-                    MissingInterfaceField { .. } => "MissingInterfaceField",
-                    RequiredArgument { .. } => "RequiredArgument",
-                    RequiredField { .. } => "RequiredField",
-                    TransitiveImplementedInterfaces { .. } => "TransitiveImplementedInterfaces",
-                    OutputType { .. } => "OutputType",
-                    InputType { .. } => "InputType",
-                    VariableInputType { .. } => "VariableInputType",
-                    QueryRootOperationType { .. } => "QueryRootOperationType",
-                    UnusedVariable { .. } => "UnusedVariable",
-                    RootOperationObjectType { .. } => "RootOperationObjectType",
-                    UnionMemberObjectType { .. } => "UnionMemberObjectType",
-                    UnsupportedLocation { .. } => "UnsupportedLocation",
-                    UnsupportedValueType { .. } => "UnsupportedValueType",
-                    IntCoercionError { .. } => "IntCoercionError",
-                    FloatCoercionError { .. } => "FloatCoercionError",
-                    UniqueDirective { .. } => "UniqueDirective",
-                    MissingSubselection { .. } => "MissingSubselection",
-                    InvalidFragmentTarget { .. } => "InvalidFragmentTarget",
-                    InvalidFragmentSpread { .. } => "InvalidFragmentSpread",
-                    UnusedFragment { .. } => "UnusedFragment",
-                    DisallowedVariableUsage { .. } => "DisallowedVariableUsage",
-                    RecursiveDirectiveDefinition { .. } => "RecursiveDirectiveDefinition",
-                    RecursiveInterfaceDefinition { .. } => "RecursiveInterfaceDefinition",
-                    RecursiveInputObjectDefinition { .. } => "RecursiveInputObjectDefinition",
-                    RecursiveFragmentDefinition { .. } => "RecursiveFragmentDefinition",
-                    DeeplyNestedType { .. } => "DeeplyNestedType",
-                    EmptyFieldSet { .. } => "EmptyFieldSet",
-                    EmptyValueSet { .. } => "EmptyValueSet",
-                    EmptyMemberSet { .. } => "EmptyMemberSet",
-                    EmptyInputValueSet { .. } => "EmptyInputValueSet",
-                })
-            }
+            Details::CompilerDiagnostic(diagnostic) => diagnostic.value_path(),
+            _ => None,
+        }
+    }
+
+    /// Returns the schema element this diagnostic is about, if it is tied to one,
+    /// for use with [`ValidationConfig::suppress`].
+    pub fn coordinate(&self) -> Option<SchemaCoordinate> {
+        match &self.details {
+            Details::CompilerDiagnostic(diagnostic) => diagnostic.coordinate(),
+            Details::ExecutableBuildError(ExecutableBuildError::UndefinedField {
+                type_name,
+                field_name,
+                ..
+            }) => Some(
+                TypeAttributeCoordinate {
+                    ty: type_name.clone(),
+                    attribute: field_name.clone(),
+                }
+                .into(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Returns names that exist in the schema and are similar to an undefined name
+    /// this diagnostic is about, most similar first.
+    ///
+    /// This is set for undefined fields, arguments, types, and directives,
+    /// so that editors can offer to replace the name.
+    pub fn suggestions(&self) -> &[Name] {
+        match &self.details {
+            Details::CompilerDiagnostic(diagnostic) => diagnostic.suggestions(),
+            Details::ExecutableBuildError(ExecutableBuildError::UndefinedField {
+                suggestions,
+                ..
+            }) => suggestions,
+            _ => &[],
+        }
+    }
+
+    /// Returns whether this diagnostic makes the schema or document invalid
+    pub fn severity(&self) -> Severity {
+        match &self.details {
+            Details::CompilerDiagnostic(diagnostic) if diagnostic.is_lint() => Severity::Lint,
+            _ if self.warning => Severity::Warning,
+            Details::CompilerDiagnostic(diagnostic) if diagnostic.is_warning() => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+
+    /// Returns the identifier of the validation or lint rule that emitted this diagnostic,
+    /// for use with [`ValidationConfig::disable_rule`].
+    ///
+    /// This is the [name][ValidationRule::name] of [`validation_rule`][Self::validation_rule]
+    /// or of [`lint_rule`][Self::lint_rule], and is stable across releases.
+    /// Returns `None` for syntax errors, limits, and cancellation, which cannot be turned off.
+    pub fn rule(&self) -> Option<&'static str> {
+        match self.validation_rule() {
+            Some(rule) => Some(rule.name()),
+            None => self.lint_rule().map(LintRule::name),
+        }
+    }
+
+    /// Returns the validation rule that emitted this diagnostic, if any
+    pub fn validation_rule(&self) -> Option<ValidationRule> {
+        use diagnostics::DiagnosticData::*;
+        match &self.details {
+            Details::CompilerDiagnostic(diagnostic) => Some(match diagnostic {
+                UniqueVariable { .. } => ValidationRule::UniqueVariable,
+                UniqueArgument { .. } => ValidationRule::UniqueArgument,
+                UniqueInputValue { .. } => ValidationRule::UniqueInputValue,
+                UndefinedArgument { .. } => ValidationRule::UndefinedArgument,
+                UndefinedDefinition { .. } => ValidationRule::UndefinedDefinition,
+                UndefinedDirective { .. } => ValidationRule::UndefinedDirective,
+                UndefinedVariable { .. } => ValidationRule::UndefinedVariable,
+                UndefinedFragment { .. } => ValidationRule::UndefinedFragment,
+                UndefinedEnumValue { .. } => ValidationRule::UndefinedEnumValue,
+                UndefinedInputValue { .. } => ValidationRule::UndefinedInputValue,
+                MissingInterfaceField { .. } => ValidationRule::MissingInterfaceField,
+                RequiredArgument { .. } => ValidationRule::RequiredArgument,
+                RequiredField { .. } => ValidationRule::RequiredField,
+                TransitiveImplementedInterfaces { .. } => {
+                    ValidationRule::TransitiveImplementedInterfaces
+                }
+                OutputType { .. } => ValidationRule::OutputType,
+                InputType { .. } => ValidationRule::InputType,
+                VariableInputType { .. } => ValidationRule::VariableInputType,
+                QueryRootOperationType => ValidationRule::QueryRootOperationType,
+                UnusedVariable { .. } => ValidationRule::UnusedVariable,
+                RootOperationObjectType { .. } => ValidationRule::RootOperationObjectType,
+                UnionMemberObjectType { .. } => ValidationRule::UnionMemberObjectType,
+                UnsupportedLocation { .. } => ValidationRule::UnsupportedLocation,
+                UnsupportedValueType { .. } => ValidationRule::UnsupportedValueType,
+                IntCoercionError { .. } => ValidationRule::IntCoercionError,
+                FloatCoercionError { .. } => ValidationRule::FloatCoercionError,
+                UniqueDirective { .. } => ValidationRule::UniqueDirective,
+                MissingSubselection { .. } => ValidationRule::MissingSubselection,
+                InvalidFragmentTarget { .. } => ValidationRule::InvalidFragmentTarget,
+                InvalidFragmentSpread { .. } => ValidationRule::InvalidFragmentSpread,
+                UnusedFragment { .. } => ValidationRule::UnusedFragment,
+                DisallowedVariableUsage { .. } => ValidationRule::DisallowedVariableUsage,
+                RecursiveDirectiveDefinition { .. } => ValidationRule::RecursiveDirectiveDefinition,
+                RecursiveInterfaceDefinition { .. } => ValidationRule::RecursiveInterfaceDefinition,
+                RecursiveInputObjectDefinition { .. } => {
+                    ValidationRule::RecursiveInputObjectDefinition
+                }
+                RecursiveFragmentDefinition { .. } => ValidationRule::RecursiveFragmentDefinition,
+                EmptyFieldSet { .. } => ValidationRule::EmptyFieldSet,
+                EmptyValueSet { .. } => ValidationRule::EmptyValueSet,
+                EmptyMemberSet { .. } => ValidationRule::EmptyMemberSet,
+                EmptyInputValueSet { .. } => ValidationRule::EmptyInputValueSet,
+                DeprecatedUsage { .. } => ValidationRule::DeprecatedUsage,
+                MissingTypename { .. } => ValidationRule::MissingTypename,
+                MaxDepth { .. } => ValidationRule::MaxDepth,
+                IntrospectionDepth { .. } => ValidationRule::IntrospectionDepth,
+                IntrospectionFieldCount { .. } => ValidationRule::IntrospectionFieldCount,
+                TooManyAliases { .. } => ValidationRule::TooManyAliases,
+                TooManyRepeatedFields { .. } => ValidationRule::TooManyRepeatedFields,
+                UniqueIncrementalLabel { .. } => ValidationRule::UniqueIncrementalLabel,
+                VariableIncrementalLabel { .. } => ValidationRule::VariableIncrementalLabel,
+                NegativeInitialCount { .. } => ValidationRule::NegativeInitialCount,
+                DeferOnSubscriptionRoot => ValidationRule::DeferOnSubscriptionRoot,
+                StreamOnNonListField { .. } => ValidationRule::StreamOnNonListField,
+                InvalidScalarValue { .. } => ValidationRule::InvalidScalarValue,
+                InvalidDirectiveApplication { .. } => ValidationRule::InvalidDirectiveApplication,
+                NonNullOneOfField { .. } => ValidationRule::NonNullOneOfField,
+                OneOfFieldDefault { .. } => ValidationRule::OneOfFieldDefault,
+                DeprecatedRequiredInput { .. } => ValidationRule::DeprecatedRequiredInput,
+                OneOfFieldCount { .. } => ValidationRule::OneOfFieldCount,
+                NullOneOfField { .. } => ValidationRule::NullOneOfField,
+                NullableVariableInOneOf { .. } => ValidationRule::NullableVariableInOneOf,
+                IntrospectionDisabled { .. } => ValidationRule::IntrospectionDisabled,
+                OperationNotAllowed { .. } => ValidationRule::OperationNotAllowed,
+                UnreachableSelection { .. } => ValidationRule::UnreachableSelection,
+                OperationNotFound { .. } => ValidationRule::OperationNotFound,
+                RepeatedDirectiveArguments { .. } => ValidationRule::RepeatedDirectiveArguments,
+                NullPropagation { .. } => ValidationRule::NullPropagation,
+                DisallowedInFieldSet { .. } => ValidationRule::DisallowedInFieldSet,
+                InvalidSpecifiedByUrl { .. } => ValidationRule::InvalidSpecifiedByUrl,
+                // Limits and cancellation are not rules, so they cannot be disabled or suppressed
+                RecursionError { .. }
+                | DeeplyNestedType { .. }
+                | FieldMergingBudget { .. }
+                | ValidationBudgetExceeded { .. }
+                | ValidationCancelled => return None,
+                NamingConvention { .. }
+                | MissingDescription { .. }
+                | RedundantConditionalDirective { .. }
+                | ContradictoryConditionalDirectives { .. }
+                | CaseInsensitiveNameCollision { .. }
+                | UnnamedOperation { .. }
+                | OperationNamePattern { .. } => return None,
+            }),
             Details::ExecutableBuildError(error) => Some(match error {
-                ExecutableBuildError::UndefinedField { .. } => "UndefinedField",
-                ExecutableBuildError::TypeSystemDefinition { .. } => "TypeSystemDefinition",
-                ExecutableBuildError::AmbiguousAnonymousOperation { .. } => {
-                    "AmbiguousAnonymousOperation"
+                ExecutableBuildError::UndefinedField { .. } => ValidationRule::UndefinedField,
+                ExecutableBuildError::TypeSystemDefinition { .. } => {
+                    ValidationRule::TypeSystemDefinition
+                }
+                ExecutableBuildError::OperationInFragmentLibrary { .. } => {
+                    ValidationRule::OperationInFragmentLibrary
+                }
+                ExecutableBuildError::AmbiguousAnonymousOperation => {
+                    ValidationRule::AmbiguousAnonymousOperation
+                }
+                ExecutableBuildError::OperationNameCollision { .. } => {
+                    ValidationRule::OperationNameCollision
+                }
+                ExecutableBuildError::FragmentNameCollision { .. } => {
+                    ValidationRule::FragmentNameCollision
+                }
+                ExecutableBuildError::UndefinedRootOperation { .. } => {
+                    ValidationRule::UndefinedRootOperation
                 }
-                ExecutableBuildError::OperationNameCollision { .. } => "OperationNameCollision",
-                ExecutableBuildError::FragmentNameCollision { .. } => "FragmentNameCollision",
-                ExecutableBuildError::UndefinedRootOperation { .. } => "UndefinedRootOperation",
                 ExecutableBuildError::UndefinedTypeInNamedFragmentTypeCondition { .. } => {
-                    "UndefinedTypeInNamedFragmentTypeCondition"
+                    ValidationRule::UndefinedTypeInNamedFragmentTypeCondition
                 }
                 ExecutableBuildError::UndefinedTypeInInlineFragmentTypeCondition { .. } => {
-                    "UndefinedTypeInInlineFragmentTypeCondition"
+                    ValidationRule::UndefinedTypeInInlineFragmentTypeCondition
+                }
+                ExecutableBuildError::SubselectionOnScalarType { .. } => {
+                    ValidationRule::SubselectionOnScalarType
+                }
+                ExecutableBuildError::SubselectionOnEnumType { .. } => {
+                    ValidationRule::SubselectionOnEnumType
                 }
-                ExecutableBuildError::SubselectionOnScalarType { .. } => "SubselectionOnScalarType",
-                ExecutableBuildError::SubselectionOnEnumType { .. } => "SubselectionOnEnumType",
                 ExecutableBuildError::SubscriptionUsesMultipleFields { .. } => {
-                    "SubscriptionUsesMultipleFields"
+                    ValidationRule::SubscriptionUsesMultipleFields
                 }
                 ExecutableBuildError::SubscriptionUsesIntrospection { .. } => {
-                    "SubscriptionUsesIntrospection"
+                    ValidationRule::SubscriptionUsesIntrospection
+                }
+                ExecutableBuildError::ConflictingFieldType(_) => {
+                    ValidationRule::ConflictingFieldType
+                }
+                ExecutableBuildError::ConflictingFieldName(_) => {
+                    ValidationRule::ConflictingFieldName
+                }
+                ExecutableBuildError::ConflictingFieldArgument(_) => {
+                    ValidationRule::ConflictingFieldArgument
                 }
-                ExecutableBuildError::ConflictingFieldType(_) => "ConflictingFieldType",
-                ExecutableBuildError::ConflictingFieldName(_) => "ConflictingFieldName",
-                ExecutableBuildError::ConflictingFieldArgument(_) => "ConflictingFieldArgument",
             }),
+            _ => None,
+        }
+    }
+
+    /// Returns the lint rule that emitted this diagnostic, if any
+    pub fn lint_rule(&self) -> Option<LintRule> {
+        use diagnostics::DiagnosticData::*;
+        let Details::CompilerDiagnostic(diagnostic) = &self.details else {
+            return None;
+        };
+        Some(match diagnostic {
+            NamingConvention { rule, .. } => *rule,
+            MissingDescription { .. } => LintRule::DescriptionRequired,
+            RedundantConditionalDirective { .. } | ContradictoryConditionalDirectives { .. } => {
+                LintRule::RedundantConditionalDirective
+            }
+            CaseInsensitiveNameCollision { .. } => LintRule::CaseInsensitiveNameCollision,
+            UnnamedOperation { .. } => LintRule::OperationNameRequired,
+            OperationNamePattern { .. } => LintRule::OperationNamePattern,
+            _ => return None,
+        })
+    }
+
+    /// Returns which invariant of a valid schema or document this diagnostic reports as broken
+    pub fn kind(&self) -> ValidationErrorKind {
+        match &self.details {
+            Details::SyntaxError { .. } | Details::SyntaxWarning { .. } => {
+                ValidationErrorKind::Syntax
+            }
+            Details::ParserLimit { .. } => ValidationErrorKind::Limit,
+            Details::SchemaBuildError(error) => error_kind::schema_build_error_kind(error),
+            Details::SupergraphError(_) => ValidationErrorKind::Supergraph,
+            Details::CompositionError(_) => ValidationErrorKind::Composition,
+            Details::LinkError(_) => ValidationErrorKind::InvalidDirectiveUsage,
+            #[cfg(feature = "serde_json")]
+            Details::IntrospectionResponseError(_) => ValidationErrorKind::Syntax,
+            Details::CompilerDiagnostic(diagnostic) if diagnostic.is_lint() => {
+                ValidationErrorKind::Lint
+            }
+            _ => match self.validation_rule() {
+                Some(rule) => error_kind::rule_kind(rule),
+                None if self.limit_name().is_some() => ValidationErrorKind::Limit,
+                None => ValidationErrorKind::Other,
+            },
+        }
+    }
+
+    /// Returns the section of the GraphQL specification that the [rule][Self::rule]
+    /// which emitted this diagnostic enforces, with a link to it.
+    ///
+    /// Returns `None` for diagnostics that do not come from the specification,
+    /// such as syntax errors, limits, lints, and opt-in warnings.
+    pub fn spec_reference(&self) -> Option<SpecReference> {
+        self.validation_rule()
+            .and_then(spec_reference::spec_reference)
+    }
+
+    /// Returns the internal error name for an (operation) validation error.
+    /// This is meant for debugging apollo-rs, not for public consumption.
+    /// Use [`rule`][Self::rule] for a stable identifier.
+    #[doc(hidden)]
+    pub fn unstable_error_name(&self) -> Option<&'static str> {
+        self.limit_name().or_else(|| self.rule())
+    }
+
+    /// Returns the name of a diagnostic that stopped validation early:
+    /// a recursion or budget limit, or cancellation.
+    fn limit_name(&self) -> Option<&'static str> {
+        use diagnostics::DiagnosticData::*;
+        match &self.details {
+            Details::CompilerDiagnostic(diagnostic) => match diagnostic {
+                RecursionError { .. } => Some("RecursionError"),
+                DeeplyNestedType { .. } => Some("DeeplyNestedType"),
+                FieldMergingBudget { .. } => Some("FieldMergingBudget"),
+                ValidationBudgetExceeded { .. } => Some("ValidationBudgetExceeded"),
+                ValidationCancelled => Some("ValidationCancelled"),
+                _ => None,
+            },
             Details::RecursionLimitError => Some("RecursionLimitError"),
             _ => None,
         }
@@ -388,8 +1889,10 @@ impl DiagnosticData {
                     } => Some(format!(
                         r#"Unknown argument "{name}" on field "{coordinate}"."#
                     )),
-                    UndefinedDefinition { name } => Some(format!(r#"Unknown type "{name}"."#)),
-                    UndefinedDirective { name } => Some(format!(r#"Unknown directive "@{name}"."#)),
+                    UndefinedDefinition { name, .. } => Some(format!(r#"Unknown type "{name}"."#)),
+                    UndefinedDirective { name, .. } => {
+                        Some(format!(r#"Unknown directive "@{name}"."#))
+                    }
                     UndefinedVariable { name } => {
                         Some(format!(r#"Variable "${name}" is not defined."#))
                     }
@@ -448,7 +1951,7 @@ impl DiagnosticData {
                         r#"{} cannot represent value: {value}"#,
                         ty.inner_named_type()
                     )),
-                    IntCoercionError { value } => {
+                    IntCoercionError { value, .. } => {
                         let is_integer = value
                             .chars()
                             // The possible characters in "-1e+100"
@@ -463,7 +1966,7 @@ impl DiagnosticData {
                             ))
                         }
                     }
-                    FloatCoercionError { value } => Some(format!(
+                    FloatCoercionError { value, .. } => Some(format!(
                         r#"Float cannot represent non numeric value: {value}"#
                     )),
                     UniqueDirective { name, .. } => Some(format!(
@@ -527,6 +2030,44 @@ impl DiagnosticData {
                     EmptyValueSet { .. } => None,
                     EmptyMemberSet { .. } => None,
                     EmptyInputValueSet { .. } => None,
+                    DeprecatedUsage { .. } => None,
+                    MissingTypename { .. } => None,
+                    MaxDepth { .. } => None,
+                    IntrospectionDepth { .. } => None,
+                    IntrospectionFieldCount { .. } => None,
+                    TooManyAliases { .. } => None,
+                    TooManyRepeatedFields { .. } => None,
+                    FieldMergingBudget { .. } => None,
+                    ValidationBudgetExceeded { .. } => None,
+                    ValidationCancelled => None,
+                    UniqueIncrementalLabel { .. } => None,
+                    VariableIncrementalLabel { .. } => None,
+                    NegativeInitialCount { .. } => None,
+                    DeferOnSubscriptionRoot => None,
+                    StreamOnNonListField { .. } => None,
+                    InvalidScalarValue { .. } => None,
+                    InvalidDirectiveApplication { .. } => None,
+                    NonNullOneOfField { .. } => None,
+                    OneOfFieldDefault { .. } => None,
+                    DeprecatedRequiredInput { .. } => None,
+                    OneOfFieldCount { .. } => None,
+                    NullOneOfField { .. } => None,
+                    NullableVariableInOneOf { .. } => None,
+                    IntrospectionDisabled { .. } => None,
+                    OperationNotAllowed { .. } => None,
+                    UnreachableSelection { .. } => None,
+                    OperationNotFound { .. } => None,
+                    NamingConvention { .. } => None,
+                    MissingDescription { .. } => None,
+                    CaseInsensitiveNameCollision { .. } => None,
+                    RedundantConditionalDirective { .. } => None,
+                    ContradictoryConditionalDirectives { .. } => None,
+                    UnnamedOperation { .. } => None,
+                    OperationNamePattern { .. } => None,
+                    RepeatedDirectiveArguments { .. } => None,
+                    NullPropagation { .. } => None,
+                    DisallowedInFieldSet { .. } => None,
+                    InvalidSpecifiedByUrl { .. } => None,
                 }
             }
             Details::ExecutableBuildError(error) => match error {
@@ -545,6 +2086,18 @@ impl DiagnosticData {
                         Some("The schema definition is not executable.".to_string())
                     }
                 }
+                ExecutableBuildError::OperationInFragmentLibrary { name } => {
+                    if let Some(name) = name {
+                        Some(format!(
+                            r#"The "{name}" operation is not allowed in a fragment library."#
+                        ))
+                    } else {
+                        Some(
+                            "Anonymous operations are not allowed in a fragment library."
+                                .to_string(),
+                        )
+                    }
+                }
                 ExecutableBuildError::AmbiguousAnonymousOperation => {
                     Some("This anonymous operation must be the only defined operation.".to_string())
                 }
@@ -643,6 +2196,17 @@ impl ToCliReport for DiagnosticData {
         self.location
     }
 
+    fn to_report<'s>(&self, sources: &'s SourceMap, color: Color) -> CliReport<'s> {
+        let mut report = match self.severity() {
+            Severity::Error => CliReport::builder(sources, self.location, color),
+            Severity::Warning => CliReport::warning_builder(sources, self.location, color),
+            Severity::Lint => CliReport::advice_builder(sources, self.location, color),
+        };
+        report.with_message(self);
+        self.report(&mut report);
+        report
+    }
+
     fn report(&self, report: &mut CliReport) {
         if let Details::CompilerDiagnostic(diagnostic) = &self.details {
             diagnostic.report(self.location, report);
@@ -658,6 +2222,9 @@ impl ToCliReport for DiagnosticData {
             Details::CompilerDiagnostic(_) => unreachable!(),
             Details::ParserLimit { message, .. } => report.with_label_opt(self.location, message),
             Details::SyntaxError { message, .. } => report.with_label_opt(self.location, message),
+            Details::SyntaxWarning { .. } => {
+                report.with_label_opt(self.location, "accepted in lenient mode")
+            }
             Details::SchemaBuildError(err) => match err {
                 SchemaBuildError::ExecutableDefinition { .. } => report.with_label_opt(
                     self.location,
@@ -696,6 +2263,17 @@ impl ToCliReport for DiagnosticData {
                     report.with_label_opt(self.location, format_args!("`{name}` redefined here"));
                     report.with_help("remove or rename one of the definitions, or use `extend`");
                 }
+                SchemaBuildError::DuplicateDefinitionResolved {
+                    previous_location,
+                    kept,
+                    ..
+                } => {
+                    report.with_label_opt(*previous_location, "previous definition here");
+                    report.with_label_opt(self.location, "defined again here");
+                    report.with_note(format_args!(
+                        "the {kept} definition is used, as configured on the schema builder"
+                    ));
+                }
                 SchemaBuildError::BuiltInScalarTypeRedefinition { .. } => {
                     report.with_label_opt(self.location, "remove this scalar definition");
                 }
@@ -744,21 +2322,37 @@ impl ToCliReport for DiagnosticData {
                 }
                 SchemaBuildError::ObjectFieldNameCollision {
                     name_at_previous_location,
+                    redefined_definitions,
                     ..
                 }
                 | SchemaBuildError::InterfaceFieldNameCollision {
                     name_at_previous_location,
+                    redefined_definitions,
                     ..
                 }
                 | SchemaBuildError::EnumValueNameCollision {
                     name_at_previous_location,
+                    redefined_definitions,
                     ..
                 }
-                | SchemaBuildError::UnionMemberNameCollision {
+                | SchemaBuildError::InputFieldNameCollision {
                     name_at_previous_location,
+                    redefined_definitions,
                     ..
+                } => {
+                    let name = name_at_previous_location;
+                    report.with_label_opt(
+                        name.location(),
+                        format_args!("previous definition of `{name}` here"),
+                    );
+                    for redefined_definition in redefined_definitions {
+                        report.with_label_opt(
+                            *redefined_definition,
+                            format_args!("`{name}` redefined here"),
+                        );
+                    }
                 }
-                | SchemaBuildError::InputFieldNameCollision {
+                SchemaBuildError::UnionMemberNameCollision {
                     name_at_previous_location,
                     ..
                 } => {
@@ -776,6 +2370,10 @@ impl ToCliReport for DiagnosticData {
                     self.location,
                     "remove this definition, or use `parse_mixed()`",
                 ),
+                ExecutableBuildError::OperationInFragmentLibrary { .. } => report.with_label_opt(
+                    self.location,
+                    "move this operation to an executable document",
+                ),
                 ExecutableBuildError::AmbiguousAnonymousOperation { .. } => {
                     report.with_label_opt(self.location, "provide a name for this definition");
                     report.with_help(
@@ -827,7 +2425,7 @@ impl ToCliReport for DiagnosticData {
                     field_name,
                     type_name,
                     path,
-                    ..
+                    suggestions,
                 } => {
                     report.with_label_opt(
                         self.location,
@@ -837,7 +2435,8 @@ impl ToCliReport for DiagnosticData {
                         type_name.location(),
                         format_args!("type `{type_name}` defined here"),
                     );
-                    report.with_note(format_args!("path to the field: `{path}`"))
+                    report.with_note(format_args!("path to the field: `{path}`"));
+                    suggestion::report_suggestions(report, "", suggestions);
                 }
                 ExecutableBuildError::SubscriptionUsesMultipleFields { fields, .. } => {
                     report.with_label_opt(
@@ -945,6 +2544,54 @@ impl ToCliReport for DiagnosticData {
                     report.with_help("Both fields may be present on the schema type, so it's not clear which one should be used to fill the response");
                 }
             },
+            Details::SupergraphError(err) => match err {
+                SupergraphError::MissingGraphEnum => {}
+                SupergraphError::MissingGraphDirective { .. } => {
+                    report.with_label_opt(self.location, "add `@join__graph` here")
+                }
+                SupergraphError::UndefinedGraph { .. } => {
+                    report.with_label_opt(self.location, "subgraph not found")
+                }
+                SupergraphError::GraphDoesNotOwnType { type_name, .. } => report.with_label_opt(
+                    self.location,
+                    format_args!("this subgraph does not define `{type_name}`"),
+                ),
+            },
+            Details::CompositionError(err) => match err {
+                CompositionError::TypeKindMismatch { other_kind, .. } => report
+                    .with_label_opt(self.location, format_args!("defined as {other_kind} here")),
+                CompositionError::FieldTypeMismatch { other_ty, .. }
+                | CompositionError::ArgumentTypeMismatch { other_ty, .. } => report
+                    .with_label_opt(self.location, format_args!("defined as `{other_ty}` here")),
+                CompositionError::NonShareableField { .. } => {
+                    report.with_label_opt(self.location, "not marked `@shareable` here");
+                    report
+                        .with_help("mark the field `@shareable` in every subgraph that resolves it")
+                }
+                CompositionError::UnresolvableField { coordinate, .. } => {
+                    report.with_label_opt(self.location, "field defined here");
+                    report.with_help(format_args!(
+                        "add a resolvable `@key` to `{}` in a subgraph that resolves this field",
+                        coordinate.ty
+                    ))
+                }
+            },
+            Details::LinkError(err) => match err {
+                LinkError::MissingUrl { .. } | LinkError::InvalidUrl { .. } => {
+                    report.with_label_opt(self.location, "invalid link here")
+                }
+                LinkError::InvalidNamespace { .. } => {
+                    report.with_label_opt(self.location, "invalid `as` argument here")
+                }
+                LinkError::InvalidImport { .. } => {
+                    report.with_label_opt(self.location, "invalid import here")
+                }
+                LinkError::NameCollision { .. } => {
+                    report.with_label_opt(self.location, "this link reuses the name")
+                }
+            },
+            #[cfg(feature = "serde_json")]
+            Details::IntrospectionResponseError(_) => {}
             Details::RecursionLimitError => {}
         }
     }
@@ -965,6 +2612,34 @@ impl Diagnostic<'_, DiagnosticData> {
             self.sources,
         )
     }
+
+    /// Returns a [`serde`]-serializable version of this diagnostic,
+    /// including its labels and their locations.
+    pub fn to_serialized(&self) -> SerializedDiagnostic {
+        let report = self.to_report(Color::Never);
+        let labels = report
+            .labels
+            .iter()
+            .filter_map(|(location, message)| {
+                Some(SerializedLabel {
+                    location: SerializedLocation::new(*location, self.sources)?,
+                    message: message.clone(),
+                })
+            })
+            .collect();
+        SerializedDiagnostic {
+            code: self.error.rule().map(ToOwned::to_owned),
+            message: self.error.to_string(),
+            severity: self.error.severity(),
+            location: self
+                .error
+                .location
+                .and_then(|location| SerializedLocation::new(location, self.sources)),
+            labels,
+            help: report.help.clone(),
+            suggestions: self.error.suggestions().to_vec(),
+        }
+    }
 }
 
 impl DiagnosticList {
@@ -973,6 +2648,7 @@ impl DiagnosticList {
         Self {
             sources,
             diagnostics_data: Vec::new(),
+            suppressed: Vec::new(),
         }
     }
 
@@ -980,6 +2656,14 @@ impl DiagnosticList {
         self.diagnostics_data.is_empty()
     }
 
+    /// Returns whether this list has any diagnostic with [`Severity::Error`],
+    /// as opposed to only warnings.
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics_data
+            .iter()
+            .any(|diagnostic| diagnostic.severity() == Severity::Error)
+    }
+
     pub fn len(&self) -> usize {
         self.diagnostics_data.len()
     }
@@ -996,9 +2680,149 @@ impl DiagnosticList {
         self.diagnostics_data.push(DiagnosticData {
             location,
             details: details.into(),
+            warning: false,
+        })
+    }
+
+    /// Push an error for a field or enum value name defined more than once in a type,
+    /// or add its later definitions to the error for an earlier extension of the same type
+    pub(crate) fn push_name_collision(&mut self, mut error: SchemaBuildError) {
+        let Some((name, type_name, redefined_definitions)) = error.name_collision_mut() else {
+            return self.push(None, error);
+        };
+        let location = redefined_definitions.first().copied().flatten();
+        let earlier = self
+            .diagnostics_data
+            .iter_mut()
+            .rev()
+            .find_map(|diagnostic| {
+                let Details::SchemaBuildError(earlier) = &mut diagnostic.details else {
+                    return None;
+                };
+                let (earlier_name, earlier_type_name, earlier_definitions) =
+                    earlier.name_collision_mut()?;
+                (earlier_type_name == type_name
+                    && earlier_name == name
+                    && earlier_name.location() == name.location())
+                .then_some(earlier_definitions)
+            });
+        match earlier {
+            Some(earlier_definitions) => earlier_definitions.append(redefined_definitions),
+            None => self.push(location, error),
+        }
+    }
+
+    /// Like [`push`][Self::push], but with [`Severity::Warning`]
+    pub(crate) fn push_warning(
+        &mut self,
+        location: Option<SourceSpan>,
+        details: impl Into<Details>,
+    ) {
+        self.diagnostics_data.push(DiagnosticData {
+            location,
+            details: details.into(),
+            warning: true,
         })
     }
 
+    /// Call `validate` for each of `items`, which must be independent of each other.
+    ///
+    /// With the `rayon` feature, items are validated concurrently into separate lists
+    /// that are then appended in the order of `items`,
+    /// so the result is the same as validating them one after the other.
+    pub(crate) fn validate_each<T: Sync>(
+        &mut self,
+        items: &[T],
+        validate: impl Fn(&mut DiagnosticList, &T) + Sync,
+    ) {
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+
+            let sources = &self.sources;
+            let lists: Vec<DiagnosticList> = items
+                .par_iter()
+                .map(|item| {
+                    let mut diagnostics = DiagnosticList::new(sources.clone());
+                    validate(&mut diagnostics, item);
+                    diagnostics
+                })
+                .collect();
+            for list in lists {
+                self.diagnostics_data.extend(list.diagnostics_data);
+                self.suppressed.extend(list.suppressed);
+            }
+        }
+        #[cfg(not(feature = "rayon"))]
+        for item in items {
+            validate(self, item)
+        }
+    }
+
+    /// Returns diagnostics that were suppressed by [`ValidationConfig::suppress`]
+    /// or by [inline comments][ValidationConfig::inline_suppressions].
+    ///
+    /// These are not included in [`iter`][Self::iter] or [`len`][Self::len],
+    /// and do not make validation fail.
+    pub fn suppressed(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = Diagnostic<'_, DiagnosticData>> + ExactSizeIterator {
+        self.suppressed
+            .iter()
+            .map(|data| data.to_diagnostic(&self.sources))
+    }
+
+    /// Returns the number of [suppressed][Self::suppressed] diagnostics
+    pub fn suppressed_len(&self) -> usize {
+        self.suppressed.len()
+    }
+
+    /// Drop diagnostics emitted by rules that `config` turns off,
+    /// downgrade those it reports as warnings, and set aside those that it suppresses.
+    pub(crate) fn apply_config(&mut self, config: &ValidationConfig) {
+        for mut diagnostic in std::mem::take(&mut self.diagnostics_data) {
+            // Syntax errors, limits, and cancellation are not rules and are always reported
+            let Some(rule) = diagnostic.rule() else {
+                self.diagnostics_data.push(diagnostic);
+                continue;
+            };
+            if !config.is_rule_enabled(rule) {
+                continue;
+            }
+            diagnostic.warning |= config.warning_rules.contains(rule);
+            if config.is_suppressed(&self.sources, rule, diagnostic.location, || {
+                diagnostic.coordinate()
+            }) {
+                self.suppressed.push(diagnostic)
+            } else {
+                self.diagnostics_data.push(diagnostic)
+            }
+        }
+    }
+
+    /// Drop or downgrade `UndefinedDirective` diagnostics of an executable document
+    /// as configured by [`ValidationConfig::allow_unknown_directive`]
+    /// and [`ValidationConfig::unknown_directive_warnings`].
+    pub(crate) fn apply_unknown_directive_config(&mut self, config: &ValidationConfig) {
+        if config.allowed_unknown_directives.is_empty() && !config.unknown_directive_warnings {
+            return;
+        }
+        self.diagnostics_data.retain_mut(|diagnostic| {
+            let Details::CompilerDiagnostic(diagnostics::DiagnosticData::UndefinedDirective {
+                name,
+                ..
+            }) = &diagnostic.details
+            else {
+                return true;
+            };
+            if config.allowed_unknown_directives.contains(name.as_str()) {
+                return false;
+            }
+            diagnostic.warning |= config.unknown_directive_warnings;
+            true
+        });
+    }
+
     /// Concatenate an `other` list of diagnostics into `self`, and sort them together.
     pub fn merge(&mut self, other: Self) {
         if !Arc::ptr_eq(&self.sources, &other.sources) {
@@ -1008,19 +2832,22 @@ impl DiagnosticList {
             }
         }
         self.diagnostics_data.extend(other.diagnostics_data);
+        self.suppressed.extend(other.suppressed);
         self.sort()
     }
 
     fn sort(&mut self) {
         self.diagnostics_data
             .sort_by_key(|err| err.location.map(|loc| (loc.file_id(), loc.offset())));
+        self.suppressed
+            .sort_by_key(|err| err.location.map(|loc| (loc.file_id(), loc.offset())));
     }
 
     pub(crate) fn into_result(mut self) -> Result<(), Self> {
+        self.sort();
         if self.diagnostics_data.is_empty() {
             Ok(())
         } else {
-            self.sort();
             Err(self)
         }
     }
@@ -1035,17 +2862,37 @@ impl DiagnosticList {
         }
     }
 
+    /// Fails only if there is an [error][Self::has_errors]. Warnings are discarded.
     pub(crate) fn into_valid_result<T>(self, value: T) -> Result<Valid<T>, WithErrors<T>> {
-        match self.into_result() {
-            Ok(()) => Ok(Valid(value)),
-            Err(errors) => Err(WithErrors {
+        self.into_valid_result_with_warnings(value)
+            .map(WithWarnings::into_valid)
+    }
+
+    pub(crate) fn into_valid_result_with_warnings<T>(
+        mut self,
+        value: T,
+    ) -> Result<WithWarnings<T>, WithErrors<T>> {
+        self.sort();
+        if self.has_errors() {
+            Err(WithErrors {
                 partial: value,
-                errors,
-            }),
+                errors: self,
+            })
+        } else {
+            Ok(WithWarnings {
+                valid: Valid(value),
+                warnings: self,
+            })
         }
     }
 }
 
+impl Serialize for DiagnosticList {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().map(|diagnostic| diagnostic.to_serialized()))
+    }
+}
+
 /// Use Debug formatting to output with colors: `format!("{diagnostics:?}")`
 impl fmt::Display for DiagnosticList {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -1078,6 +2925,31 @@ impl From<ExecutableBuildError> for Details {
     }
 }
 
+impl From<SupergraphError> for Details {
+    fn from(value: SupergraphError) -> Self {
+        Details::SupergraphError(value)
+    }
+}
+
+impl From<CompositionError> for Details {
+    fn from(value: CompositionError) -> Self {
+        Details::CompositionError(value)
+    }
+}
+
+impl From<LinkError> for Details {
+    fn from(value: LinkError) -> Self {
+        Details::LinkError(value)
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl From<IntrospectionResponseError> for Details {
+    fn from(value: IntrospectionResponseError) -> Self {
+        Details::IntrospectionResponseError(value)
+    }
+}
+
 impl From<diagnostics::DiagnosticData> for Details {
     fn from(value: diagnostics::DiagnosticData) -> Self {
         Details::CompilerDiagnostic(value)
@@ -1086,21 +2958,24 @@ impl From<diagnostics::DiagnosticData> for Details {
 
 const DEFAULT_RECURSION_LIMIT: usize = 32;
 
+/// Returned when a recursive walk goes deeper than its configured limit.
 #[derive(Debug, Clone, thiserror::Error)]
 #[error("Recursion limit reached")]
 #[non_exhaustive]
-struct RecursionLimitError {}
+pub struct RecursionLimitError {}
 
 /// Track used names in a recursive function.
+///
+/// Keys are usually type or fragment names, but any hashable identity works.
 #[derive(Debug)]
-struct RecursionStack {
-    seen: IndexSet<Name>,
+pub(crate) struct RecursionStack<K: Hash + Eq = Name> {
+    seen: IndexSet<K>,
     high: usize,
     limit: usize,
 }
 
-impl RecursionStack {
-    fn new() -> Self {
+impl<K: Hash + Eq> RecursionStack<K> {
+    pub(crate) fn new() -> Self {
         Self {
             seen: IndexSet::with_hasher(Default::default()),
             high: 0,
@@ -1108,19 +2983,19 @@ impl RecursionStack {
         }
     }
 
-    fn with_root(root: Name) -> Self {
+    pub(crate) fn with_root(root: K) -> Self {
         let mut stack = Self::new();
         stack.seen.insert(root);
         stack
     }
 
-    fn with_limit(mut self, limit: usize) -> Self {
+    pub(crate) fn with_limit(mut self, limit: usize) -> Self {
         self.limit = limit;
         self
     }
 
     /// Return the actual API for tracking recursive uses.
-    pub(crate) fn guard(&mut self) -> RecursionGuard<'_> {
+    pub(crate) fn guard(&mut self) -> RecursionGuard<'_, K> {
         RecursionGuard(self)
     }
 }
@@ -1130,11 +3005,11 @@ impl RecursionStack {
 /// Pass the result of `guard.push(name)` to recursive calls. Use `guard.contains(name)` to check
 /// if the name was used somewhere up the call stack. When a guard is dropped, its name is removed
 /// from the list.
-struct RecursionGuard<'a>(&'a mut RecursionStack);
+pub(crate) struct RecursionGuard<'a, K: Hash + Eq = Name>(&'a mut RecursionStack<K>);
 
-impl RecursionGuard<'_> {
+impl<K: Hash + Eq + Clone> RecursionGuard<'_, K> {
     /// Mark that we saw a name. If there are too many names, return an error.
-    fn push(&mut self, name: &Name) -> Result<RecursionGuard<'_>, RecursionLimitError> {
+    pub(crate) fn push(&mut self, name: &K) -> Result<RecursionGuard<'_, K>, RecursionLimitError> {
         let new = self.0.seen.insert(name.clone());
         debug_assert!(
             new,
@@ -1149,17 +3024,17 @@ impl RecursionGuard<'_> {
     }
 
     /// Check if we saw a name somewhere up the call stack.
-    fn contains(&self, name: &Name) -> bool {
+    pub(crate) fn contains(&self, name: &K) -> bool {
         self.0.seen.contains(name)
     }
 
     /// Return the name where we started.
-    fn first(&self) -> Option<&Name> {
+    pub(crate) fn first(&self) -> Option<&K> {
         self.0.seen.first()
     }
 }
 
-impl Drop for RecursionGuard<'_> {
+impl<K: Hash + Eq> Drop for RecursionGuard<'_, K> {
     fn drop(&mut self) {
         // This may already be empty if it's the original `stack.guard()` result, but that's fine
         let _ = self.0.seen.pop();
@@ -1168,7 +3043,7 @@ impl Drop for RecursionGuard<'_> {
 
 /// Errors that can happen when chasing potentially cyclical references.
 #[derive(Debug, Clone, thiserror::Error)]
-enum CycleError<T> {
+pub enum CycleError<T> {
     /// Detected a cycle, value contains the path from the offending node back to the node where we
     /// started.
     #[error("Cycle detected")]
@@ -1179,7 +3054,7 @@ enum CycleError<T> {
 }
 
 impl<T> CycleError<T> {
-    fn trace(mut self, node: &Node<T>) -> Self {
+    pub(crate) fn trace(mut self, node: &Node<T>) -> Self {
         if let Self::Recursed(trace) = &mut self {
             trace.push(node.clone());
         }
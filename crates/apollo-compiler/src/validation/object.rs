@@ -10,11 +10,17 @@ pub(crate) fn validate_object_type_definitions(
     diagnostics: &mut DiagnosticList,
     schema: &crate::Schema,
 ) {
-    for ty in schema.types.values() {
-        if let ExtendedType::Object(object) = ty {
-            validate_object_type_definition(diagnostics, schema, object)
-        }
-    }
+    let definitions: Vec<_> = schema
+        .types
+        .values()
+        .filter_map(|ty| match ty {
+            ExtendedType::Object(object) => Some(object),
+            _ => None,
+        })
+        .collect();
+    diagnostics.validate_each(&definitions, |diagnostics, object| {
+        validate_object_type_definition(diagnostics, schema, object)
+    });
 }
 
 pub(crate) fn validate_object_type_definition(
@@ -0,0 +1,117 @@
+//! Rules for [OneOf input objects](https://spec.graphql.org/draft/#sec-OneOf-Input-Objects),
+//! which are only in the draft specification.
+
+use crate::ast;
+use crate::coordinate::TypeAttributeCoordinate;
+use crate::schema::ExtendedType;
+use crate::schema::InputObjectType;
+use crate::validation::diagnostics::DiagnosticData;
+use crate::validation::value::walk_literal_values;
+use crate::validation::DiagnosticList;
+use crate::ExecutableDocument;
+use crate::Schema;
+
+const ONE_OF: &str = "oneOf";
+
+fn is_one_of(input_object: &InputObjectType) -> bool {
+    input_object.directives.has(ONE_OF)
+}
+
+/// Fields of a OneOf input object must be nullable and must not have a default value
+pub(crate) fn validate_one_of_definitions(diagnostics: &mut DiagnosticList, schema: &Schema) {
+    for ty in schema.types.values() {
+        let ExtendedType::InputObject(input_object) = ty else {
+            continue;
+        };
+        if !is_one_of(input_object) {
+            continue;
+        }
+        for (name, field) in &input_object.fields {
+            let coordinate = TypeAttributeCoordinate {
+                ty: input_object.name.clone(),
+                attribute: name.clone(),
+            };
+            if field.ty.is_non_null() {
+                diagnostics.push(
+                    field.location(),
+                    DiagnosticData::NonNullOneOfField {
+                        coordinate: coordinate.clone(),
+                        ty: field.ty.clone(),
+                    },
+                );
+            }
+            if let Some(default) = &field.default_value {
+                diagnostics.push(
+                    default.location(),
+                    DiagnosticData::OneOfFieldDefault { coordinate },
+                );
+            }
+        }
+    }
+}
+
+/// A literal value of a OneOf input object must have exactly one field, which is not `null`.
+/// A variable used for that field must have a non-null type.
+pub(crate) fn validate_one_of_values(
+    diagnostics: &mut DiagnosticList,
+    schema: &Schema,
+    document: &ExecutableDocument,
+) {
+    walk_literal_values(schema, document, &mut |ty, value, path, variables| {
+        let ExtendedType::InputObject(input_object) = ty else {
+            return;
+        };
+        if !is_one_of(input_object) {
+            return;
+        }
+        let Some(fields) = value.as_object() else {
+            return;
+        };
+        let value_path = || path.iter().cloned().collect();
+        let [(name, field_value)] = fields else {
+            diagnostics.push(
+                value.location(),
+                DiagnosticData::OneOfFieldCount {
+                    ty: input_object.name.clone(),
+                    count: fields.len(),
+                    path: value_path(),
+                },
+            );
+            return;
+        };
+        match field_value.as_ref() {
+            ast::Value::Null => diagnostics.push(
+                field_value.location(),
+                DiagnosticData::NullOneOfField {
+                    coordinate: TypeAttributeCoordinate {
+                        ty: input_object.name.clone(),
+                        attribute: name.clone(),
+                    },
+                    path: value_path(),
+                },
+            ),
+            ast::Value::Variable(variable) => {
+                // Variables of fragments are defined by the operations using them:
+                // `variables` is empty there and those usages are not checked.
+                let Some(definition) = variables.iter().find(|def| def.name == *variable) else {
+                    return;
+                };
+                if !definition.ty.is_non_null() {
+                    diagnostics.push(
+                        field_value.location(),
+                        DiagnosticData::NullableVariableInOneOf {
+                            variable: variable.clone(),
+                            variable_type: (*definition.ty).clone(),
+                            variable_location: definition.location(),
+                            coordinate: TypeAttributeCoordinate {
+                                ty: input_object.name.clone(),
+                                attribute: name.clone(),
+                            },
+                        },
+                    )
+                }
+            }
+            _ => {}
+        }
+    });
+}
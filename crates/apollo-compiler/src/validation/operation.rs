@@ -52,7 +52,7 @@ pub(crate) fn validate_subscription(
 pub(crate) fn validate_operation(
     diagnostics: &mut DiagnosticList,
     document: &ExecutableDocument,
-    operation: &executable::Operation,
+    operation: &Node<executable::Operation>,
     context: &ExecutableValidationContext<'_>,
 ) {
     let against_type = if let Some(schema) = context.schema() {
@@ -76,7 +76,21 @@ pub(crate) fn validate_operation(
         &operation.variables,
     );
 
-    super::variable::validate_unused_variables(diagnostics, document, operation);
+    super::variable::validate_unused_variables(
+        diagnostics,
+        document,
+        operation,
+        context.limits().fragment_nesting,
+    );
+    if context.schema().is_none() {
+        super::variable::validate_undefined_variables_without_schema(
+            diagnostics,
+            document,
+            operation,
+            context.limits().fragment_nesting,
+        );
+    }
+    validate_subscription(document, operation, diagnostics);
     super::selection::validate_selection_set(
         diagnostics,
         document,
@@ -84,6 +98,24 @@ pub(crate) fn validate_operation(
         &operation.selection_set,
         context.operation_context(&operation.variables),
     );
+
+    if let Some(limit) = context.max_depth() {
+        super::depth::validate_operation_depth(
+            diagnostics,
+            document,
+            operation,
+            limit,
+            context.limits().fragment_nesting,
+        );
+    }
+
+    super::depth::validate_introspection_size(
+        diagnostics,
+        document,
+        operation,
+        context.introspection_limits(),
+        context.limits().fragment_nesting,
+    );
 }
 
 pub(crate) fn validate_operation_definitions(
@@ -91,7 +123,8 @@ pub(crate) fn validate_operation_definitions(
     document: &ExecutableDocument,
     context: &ExecutableValidationContext<'_>,
 ) {
-    for operation in document.operations.iter() {
-        validate_operation(diagnostics, document, operation, context);
-    }
+    let operations: Vec<_> = document.operations.iter().collect();
+    diagnostics.validate_each(&operations, |diagnostics, operation| {
+        validate_operation(diagnostics, document, operation, context)
+    });
 }
@@ -0,0 +1,144 @@
+use crate::validation::diagnostics::DiagnosticData;
+use crate::validation::DiagnosticList;
+use crate::validation::ValidationConfig;
+use std::fmt;
+use std::ops::ControlFlow;
+use std::sync::Arc;
+
+/// A phase of validation, reported to the callback registered with
+/// [`ValidationConfig::on_progress`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ValidationPhase {
+    /// The schema definition and root operation types
+    SchemaDefinition,
+    /// Scalar type definitions
+    ScalarTypes,
+    /// Enum type definitions
+    EnumTypes,
+    /// Union type definitions
+    UnionTypes,
+    /// Interface type definitions
+    InterfaceTypes,
+    /// Directive definitions
+    DirectiveDefinitions,
+    /// Input object type definitions
+    InputObjectTypes,
+    /// Object type definitions
+    ObjectTypes,
+    /// Operations and fragment definitions of an executable document,
+    /// including their selections, arguments, and variables
+    Operations,
+    /// Whether fields with the same response key in each operation can merge
+    FieldMerging,
+    /// Rules enabled by [`ValidationConfig`] beyond the October 2021 specification,
+    /// such as custom scalar and directive validators
+    AdditionalRules,
+}
+
+/// Progress of validation, passed to the callback registered with
+/// [`ValidationConfig::on_progress`] before each phase starts.
+///
+/// Progress is counted in steps, which estimate work by the number of definitions
+/// that each phase validates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidationProgress {
+    /// The phase about to start
+    pub phase: ValidationPhase,
+    /// Steps of earlier phases
+    pub completed: usize,
+    /// Steps of all phases. Does not change during one validation.
+    pub total: usize,
+}
+
+impl ValidationProgress {
+    /// Returns the completed fraction of validation, between 0.0 and 1.0
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.completed as f64 / self.total as f64
+        }
+    }
+}
+
+type OnProgress = dyn Fn(&ValidationProgress) -> ControlFlow<()> + Send + Sync;
+
+/// A callback registered with [`ValidationConfig::on_progress`]
+#[derive(Clone)]
+pub(crate) struct ProgressCallback(pub(crate) Arc<OnProgress>);
+
+impl fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressCallback")
+    }
+}
+
+/// Callbacks are closures, so compare by identity
+impl PartialEq for ProgressCallback {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for ProgressCallback {}
+
+/// Tracks steps through the phases of one validation
+/// and calls the configured callback, if any
+pub(crate) struct Progress<'a> {
+    callback: Option<&'a ProgressCallback>,
+    completed: usize,
+    total: usize,
+    cancelled: bool,
+}
+
+impl<'a> Progress<'a> {
+    pub(crate) fn new(config: &'a ValidationConfig, total: usize) -> Self {
+        Self {
+            callback: config.progress_callback(),
+            completed: 0,
+            total,
+            cancelled: false,
+        }
+    }
+
+    /// Progress that is not reported
+    pub(crate) fn none() -> Self {
+        Self {
+            callback: None,
+            completed: 0,
+            total: 0,
+            cancelled: false,
+        }
+    }
+
+    /// Report that `phase` is about to start and will take `steps`.
+    ///
+    /// Returns whether to run the phase.
+    /// If the callback breaks, reports a `ValidationCancelled` error
+    /// and returns `false` for this and every later phase.
+    pub(crate) fn start(
+        &mut self,
+        diagnostics: &mut DiagnosticList,
+        phase: ValidationPhase,
+        steps: usize,
+    ) -> bool {
+        if self.cancelled {
+            return false;
+        }
+        if let Some(callback) = self.callback {
+            let progress = ValidationProgress {
+                phase,
+                completed: self.completed,
+                total: self.total,
+            };
+            if (callback.0)(&progress).is_break() {
+                self.cancelled = true;
+                diagnostics.push(None, DiagnosticData::ValidationCancelled);
+                return false;
+            }
+        }
+        self.completed += steps;
+        true
+    }
+}
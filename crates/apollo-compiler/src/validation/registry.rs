@@ -0,0 +1,574 @@
+use crate::validation::error_kind::rule_kind;
+use crate::validation::spec_reference::spec_reference;
+use crate::validation::LintRule;
+use crate::validation::Severity;
+use crate::validation::SpecReference;
+use crate::validation::ValidationErrorKind;
+use crate::validation::ValidationRule;
+use serde::Serialize;
+use std::sync::OnceLock;
+
+/// A built-in validation or lint rule, as listed by [`rules`].
+///
+/// Rules are identified by the names returned by
+/// [`DiagnosticData::rule`][crate::validation::DiagnosticData::rule]
+/// and accepted by [`ValidationConfig::disable_rule`][crate::validation::ValidationConfig::disable_rule].
+/// Syntax errors, errors found while building a schema, limits that stop validation early,
+/// and cancellation are not rules and cannot be turned off, so they are not listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct RuleInfo {
+    /// The stable identifier of the rule, like `UnusedVariable`
+    pub id: &'static str,
+    /// What the rule checks, as one sentence
+    pub description: &'static str,
+    /// Which invariant diagnostics of this rule report as broken
+    pub kind: ValidationErrorKind,
+    /// The section of the GraphQL specification that the rule enforces, if any
+    pub spec_reference: Option<SpecReference>,
+    /// The severity of diagnostics of this rule, unless configured otherwise
+    pub default_severity: Severity,
+    /// Whether the rule is checked without opting in.
+    /// Opt-in rules are enabled by a method of
+    /// [`ValidationConfig`][crate::validation::ValidationConfig] or
+    /// [`LintConfig`][crate::validation::LintConfig], as described by the rule.
+    pub enabled_by_default: bool,
+}
+
+/// Returns every built-in validation and lint rule of this version of the compiler,
+/// sorted by [`id`][RuleInfo::id].
+///
+/// ```rust
+/// use apollo_compiler::validation::rules;
+/// use apollo_compiler::validation::Severity;
+///
+/// let unused = rules().iter().find(|rule| rule.id == "UnusedVariable").unwrap();
+/// assert_eq!(unused.default_severity, Severity::Error);
+/// assert_eq!(
+///     unused.spec_reference.unwrap().to_string(),
+///     "5.8.4 All Variables Used"
+/// );
+/// ```
+pub fn rules() -> &'static [RuleInfo] {
+    static RULES: OnceLock<Vec<RuleInfo>> = OnceLock::new();
+    RULES.get_or_init(|| {
+        let mut rules: Vec<_> = ENTRIES
+            .iter()
+            .map(|entry| RuleInfo {
+                id: entry.id,
+                description: entry.description,
+                kind: match entry.rule {
+                    Some(rule) => rule_kind(rule),
+                    None => ValidationErrorKind::Lint,
+                },
+                spec_reference: match entry.rule {
+                    Some(rule) => spec_reference(rule),
+                    None => None,
+                },
+                default_severity: entry.severity,
+                enabled_by_default: entry.enabled_by_default,
+            })
+            .collect();
+        rules.sort_by_key(|rule| rule.id);
+        rules
+    })
+}
+
+/// Returns the built-in rule with the given [`id`][RuleInfo::id], if any.
+///
+/// Configuration files can use this to reject unknown rule names.
+pub fn rule_info(id: &str) -> Option<&'static RuleInfo> {
+    let rules = rules();
+    rules
+        .binary_search_by_key(&id, |rule| rule.id)
+        .ok()
+        .map(|index| &rules[index])
+}
+
+struct Entry {
+    id: &'static str,
+    /// `None` for lints
+    rule: Option<ValidationRule>,
+    severity: Severity,
+    enabled_by_default: bool,
+    description: &'static str,
+}
+
+const fn error(rule: ValidationRule, description: &'static str) -> Entry {
+    Entry {
+        id: rule.name(),
+        rule: Some(rule),
+        severity: Severity::Error,
+        enabled_by_default: true,
+        description,
+    }
+}
+
+const fn warning(rule: ValidationRule, description: &'static str) -> Entry {
+    Entry {
+        id: rule.name(),
+        rule: Some(rule),
+        severity: Severity::Warning,
+        enabled_by_default: true,
+        description,
+    }
+}
+
+const fn lint(rule: LintRule, description: &'static str) -> Entry {
+    Entry {
+        id: rule.name(),
+        rule: None,
+        severity: Severity::Lint,
+        enabled_by_default: true,
+        description,
+    }
+}
+
+impl Entry {
+    const fn opt_in(self) -> Self {
+        Self {
+            enabled_by_default: false,
+            ..self
+        }
+    }
+}
+
+const ENTRIES: &[Entry] = &[
+    // Type system definitions
+    error(
+        ValidationRule::QueryRootOperationType,
+        "A schema defines a query root operation type.",
+    ),
+    error(
+        ValidationRule::RootOperationObjectType,
+        "Root operation types are object types.",
+    ),
+    error(
+        ValidationRule::UndefinedDefinition,
+        "Types referenced by definitions and extensions are defined.",
+    ),
+    error(
+        ValidationRule::UndefinedDirective,
+        "Applied directives are defined.",
+    ),
+    error(
+        ValidationRule::UndefinedArgument,
+        "Arguments are defined by their field or directive.",
+    ),
+    error(
+        ValidationRule::UndefinedEnumValue,
+        "Enum values are defined by their enum type.",
+    ),
+    error(
+        ValidationRule::UndefinedInputValue,
+        "Fields of input object values are defined by their input object type.",
+    ),
+    error(
+        ValidationRule::UniqueArgument,
+        "An argument is provided at most once.",
+    ),
+    error(
+        ValidationRule::UniqueInputValue,
+        "Arguments and input fields are defined at most once.",
+    ),
+    error(
+        ValidationRule::UniqueDirective,
+        "Non-repeatable directives are applied at most once per location.",
+    ),
+    error(
+        ValidationRule::UnsupportedLocation,
+        "Directives are applied in locations their definition allows.",
+    ),
+    error(
+        ValidationRule::OutputType,
+        "Fields of object types and interfaces have output types.",
+    ),
+    error(
+        ValidationRule::InputType,
+        "Arguments and input object fields have input types.",
+    ),
+    error(
+        ValidationRule::UnionMemberObjectType,
+        "Union members are object types.",
+    ),
+    error(
+        ValidationRule::MissingInterfaceField,
+        "Object types and interfaces define every field of the interfaces they implement.",
+    ),
+    error(
+        ValidationRule::TransitiveImplementedInterfaces,
+        "Types also implement the interfaces implemented by their interfaces.",
+    ),
+    error(
+        ValidationRule::RecursiveDirectiveDefinition,
+        "Directive definitions do not apply themselves, directly or indirectly.",
+    ),
+    error(
+        ValidationRule::RecursiveInterfaceDefinition,
+        "Interfaces do not implement themselves.",
+    ),
+    error(
+        ValidationRule::RecursiveInputObjectDefinition,
+        "Input objects do not require values of their own type, directly or indirectly.",
+    ),
+    error(
+        ValidationRule::EmptyFieldSet,
+        "Object types, interfaces, and their extensions define at least one field.",
+    ),
+    error(
+        ValidationRule::EmptyValueSet,
+        "Enum types define at least one value.",
+    ),
+    error(
+        ValidationRule::EmptyMemberSet,
+        "Union types have at least one member.",
+    ),
+    error(
+        ValidationRule::EmptyInputValueSet,
+        "Input object types define at least one field.",
+    ),
+    error(
+        ValidationRule::NonNullOneOfField,
+        "Fields of `@oneOf` input objects are nullable. Only with `SpecVersion::Draft`.",
+    )
+    .opt_in(),
+    error(
+        ValidationRule::OneOfFieldDefault,
+        "Fields of `@oneOf` input objects have no default value. Only with `SpecVersion::Draft`.",
+    )
+    .opt_in(),
+    error(
+        ValidationRule::DeprecatedRequiredInput,
+        "Required arguments and input fields are not deprecated. Only with `SpecVersion::Draft`.",
+    )
+    .opt_in(),
+    error(
+        ValidationRule::InvalidSpecifiedByUrl,
+        "`@specifiedBy` URLs are absolute URIs. \
+         Enabled by `ValidationConfig::check_specified_by_urls`.",
+    )
+    .opt_in(),
+    error(
+        ValidationRule::DisallowedInFieldSet,
+        "Federation field sets, like the `fields` argument of `@key`, \
+         select only fields without arguments or aliases. \
+         Enabled by `ValidationConfig::federation_field_sets`.",
+    )
+    .opt_in(),
+    warning(
+        ValidationRule::RepeatedDirectiveArguments,
+        "Repeatable directives are not applied twice with the same arguments. \
+         Enabled by `ValidationConfig::repeated_directive_warnings`.",
+    )
+    .opt_in(),
+    // Executable documents
+    error(
+        ValidationRule::TypeSystemDefinition,
+        "Executable documents only contain operations and fragments.",
+    ),
+    error(
+        ValidationRule::OperationNameCollision,
+        "Operation names are unique in a document.",
+    ),
+    error(
+        ValidationRule::AmbiguousAnonymousOperation,
+        "An anonymous operation is the only operation in its document.",
+    ),
+    error(
+        ValidationRule::UndefinedRootOperation,
+        "The schema defines a root operation type for each operation type used.",
+    ),
+    error(
+        ValidationRule::SubscriptionUsesMultipleFields,
+        "Subscriptions select exactly one root field.",
+    ),
+    error(
+        ValidationRule::SubscriptionUsesIntrospection,
+        "Subscriptions do not select introspection root fields.",
+    ),
+    error(
+        ValidationRule::UndefinedField,
+        "Selected fields are defined on their parent type.",
+    ),
+    error(
+        ValidationRule::ConflictingFieldType,
+        "Fields with the same response key have compatible types.",
+    ),
+    error(
+        ValidationRule::ConflictingFieldName,
+        "Fields with the same response key select the same field.",
+    ),
+    error(
+        ValidationRule::ConflictingFieldArgument,
+        "Fields with the same response key have the same arguments.",
+    ),
+    error(
+        ValidationRule::MissingSubselection,
+        "Fields of object, interface, and union types have a selection set.",
+    ),
+    error(
+        ValidationRule::SubselectionOnScalarType,
+        "Fields of scalar types have no selection set.",
+    ),
+    error(
+        ValidationRule::SubselectionOnEnumType,
+        "Fields of enum types have no selection set.",
+    ),
+    error(
+        ValidationRule::RequiredArgument,
+        "Required arguments are provided.",
+    ),
+    error(
+        ValidationRule::RequiredField,
+        "Required fields of input object values are provided.",
+    ),
+    error(
+        ValidationRule::FragmentNameCollision,
+        "Fragment names are unique in a document.",
+    ),
+    error(
+        ValidationRule::OperationInFragmentLibrary,
+        "Fragment libraries only contain fragment definitions.",
+    ),
+    error(
+        ValidationRule::UndefinedTypeInNamedFragmentTypeCondition,
+        "Type conditions of fragment definitions are defined types.",
+    ),
+    error(
+        ValidationRule::UndefinedTypeInInlineFragmentTypeCondition,
+        "Type conditions of inline fragments are defined types.",
+    ),
+    error(
+        ValidationRule::InvalidFragmentTarget,
+        "Type conditions are object, interface, or union types.",
+    ),
+    error(
+        ValidationRule::UnusedFragment,
+        "Fragment definitions are used by an operation. \
+         The severity can be changed with `ValidationConfig::unused_fragment_severity`.",
+    ),
+    error(
+        ValidationRule::UndefinedFragment,
+        "Spread fragments are defined.",
+    ),
+    error(
+        ValidationRule::RecursiveFragmentDefinition,
+        "Fragments do not spread themselves, directly or indirectly.",
+    ),
+    error(
+        ValidationRule::InvalidFragmentSpread,
+        "Fragments are spread where their type condition can apply.",
+    ),
+    error(
+        ValidationRule::UnsupportedValueType,
+        "Values have the type expected where they are used.",
+    ),
+    error(
+        ValidationRule::IntCoercionError,
+        "`Int` values fit in a 32-bit signed integer.",
+    ),
+    error(
+        ValidationRule::FloatCoercionError,
+        "`Float` values are finite.",
+    ),
+    error(
+        ValidationRule::UniqueVariable,
+        "Variable names are unique in an operation.",
+    ),
+    error(
+        ValidationRule::VariableInputType,
+        "Variables have input types.",
+    ),
+    error(
+        ValidationRule::UndefinedVariable,
+        "Used variables are defined by the operation.",
+    ),
+    error(
+        ValidationRule::UnusedVariable,
+        "Variables defined by an operation are used. \
+         The severity can be changed with `ValidationConfig::unused_variable_severity`.",
+    ),
+    error(
+        ValidationRule::DisallowedVariableUsage,
+        "Variables are used where their type is allowed.",
+    ),
+    error(
+        ValidationRule::UniqueIncrementalLabel,
+        "Labels of `@defer` and `@stream` are unique in a document.",
+    ),
+    error(
+        ValidationRule::VariableIncrementalLabel,
+        "Labels of `@defer` and `@stream` are not variables.",
+    ),
+    error(
+        ValidationRule::NegativeInitialCount,
+        "The `initialCount` argument of `@stream` is not negative.",
+    ),
+    error(
+        ValidationRule::DeferOnSubscriptionRoot,
+        "`@defer` is not used on the root selection set of a subscription.",
+    ),
+    error(
+        ValidationRule::StreamOnNonListField,
+        "`@stream` is only used on fields of list types.",
+    ),
+    error(
+        ValidationRule::OneOfFieldCount,
+        "Values of `@oneOf` input objects have exactly one field. \
+         Only with `SpecVersion::Draft`.",
+    )
+    .opt_in(),
+    error(
+        ValidationRule::NullOneOfField,
+        "Fields of `@oneOf` input object values are not null. Only with `SpecVersion::Draft`.",
+    )
+    .opt_in(),
+    error(
+        ValidationRule::NullableVariableInOneOf,
+        "Variables used for fields of `@oneOf` input objects are non-null. \
+         Only with `SpecVersion::Draft`.",
+    )
+    .opt_in(),
+    error(
+        ValidationRule::OperationNotFound,
+        "The operation selected by name exists in the document. \
+         Checked by `ExecutableDocument::validate_operation`.",
+    ),
+    error(
+        ValidationRule::MissingTypename,
+        "Fields of abstract types select `__typename`. \
+         Enabled by `ValidationConfig::require_typename_on`.",
+    )
+    .opt_in(),
+    error(
+        ValidationRule::IntrospectionDisabled,
+        "Operations do not select `__schema` or `__type`. \
+         Enabled by `ValidationConfig::forbid_introspection`.",
+    )
+    .opt_in(),
+    error(
+        ValidationRule::OperationNotAllowed,
+        "Documents are in the operation allowlist. \
+         Enabled by `ValidationConfig::operation_allowlist`.",
+    )
+    .opt_in(),
+    error(
+        ValidationRule::InvalidScalarValue,
+        "Values of custom scalars are accepted by their validator. \
+         Enabled by `ValidationConfig::validate_scalar`.",
+    )
+    .opt_in(),
+    error(
+        ValidationRule::InvalidDirectiveApplication,
+        "Applications of a directive are accepted by its validator. \
+         Enabled by `ValidationConfig::validate_directive`.",
+    )
+    .opt_in(),
+    warning(
+        ValidationRule::DeprecatedUsage,
+        "Deprecated fields, arguments, input fields, and enum values are not used. \
+         Enabled by `ValidationConfig::deprecation_warnings`.",
+    )
+    .opt_in(),
+    warning(
+        ValidationRule::UnreachableSelection,
+        "Nested type conditions can match at least one type. \
+         Enabled by `ValidationConfig::unreachable_selection_warnings`.",
+    )
+    .opt_in(),
+    warning(
+        ValidationRule::NullPropagation,
+        "Errors in non-null fields do not propagate past their parent object. \
+         Reported by `NullPropagationReport::warnings`.",
+    )
+    .opt_in(),
+    // Limits
+    error(
+        ValidationRule::MaxDepth,
+        "Fields do not nest deeper than the limit set with `ValidationConfig::max_depth`.",
+    )
+    .opt_in(),
+    error(
+        ValidationRule::IntrospectionDepth,
+        "Introspection fields do not nest deeper than the limit set with \
+         `ValidationConfig::max_introspection_depth`.",
+    )
+    .opt_in(),
+    error(
+        ValidationRule::IntrospectionFieldCount,
+        "Introspection fields select no more fields than the limit set with \
+         `ValidationConfig::max_introspection_fields`.",
+    )
+    .opt_in(),
+    error(
+        ValidationRule::TooManyAliases,
+        "Selection sets have no more aliases than the limit set with \
+         `ValidationConfig::max_aliases`.",
+    )
+    .opt_in(),
+    error(
+        ValidationRule::TooManyRepeatedFields,
+        "Response keys are not selected more times than the limit set with \
+         `ValidationConfig::max_repeated_fields`.",
+    )
+    .opt_in(),
+    // Lints
+    lint(
+        LintRule::TypeNamePascalCase,
+        "Type names are `PascalCase`, like `UserProfile`.",
+    ),
+    lint(
+        LintRule::FieldNameCamelCase,
+        "Fields of object types, interfaces, and input objects are `camelCase`, \
+         like `createdAt`.",
+    ),
+    lint(
+        LintRule::EnumValueScreamingSnakeCase,
+        "Enum values are `SCREAMING_SNAKE_CASE`, like `IN_PROGRESS`.",
+    ),
+    lint(
+        LintRule::DescriptionRequired,
+        "Types defined in the schema have a description.",
+    ),
+    lint(
+        LintRule::CaseInsensitiveNameCollision,
+        "Names in the same scope do not differ only by case, like `userId` and `userID`.",
+    )
+    .opt_in(),
+    lint(
+        LintRule::RedundantConditionalDirective,
+        "`@include` and `@skip` can change whether a selection is included.",
+    ),
+    lint(
+        LintRule::OperationNameRequired,
+        "Operations in executable documents have a name.",
+    )
+    .opt_in(),
+    lint(
+        LintRule::OperationTypeSuffix,
+        "Operation names end with their operation type, like `GetUserQuery`.",
+    )
+    .opt_in(),
+    lint(
+        LintRule::OperationNamePattern,
+        "Operation names match the pattern set with `LintConfig::operation_name_pattern`.",
+    )
+    .opt_in(),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_validation_rule_has_one_entry() {
+        for &rule in ValidationRule::ALL {
+            let count = ENTRIES
+                .iter()
+                .filter(|entry| entry.rule == Some(rule))
+                .count();
+            assert_eq!(count, 1, "{} has {count} registry entries", rule.name());
+        }
+    }
+}
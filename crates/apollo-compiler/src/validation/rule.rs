@@ -0,0 +1,234 @@
+/// Defines [`ValidationRule`] together with the list of all its variants and their names,
+/// so that tables keyed by rule can be checked against every variant.
+macro_rules! validation_rules {
+    (
+        $(#[$meta:meta])*
+    pub enum ValidationRule {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident,
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+    pub enum ValidationRule {
+            $(
+                $(#[$variant_meta])*
+                $variant,
+            )*
+        }
+
+        impl ValidationRule {
+            /// Every rule, in declaration order
+            #[cfg(test)]
+            pub(crate) const ALL: &'static [Self] = &[$(Self::$variant),*];
+
+            /// Returns the stable identifier of this rule, like `"UnusedVariable"`
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $(Self::$variant => stringify!($variant),)*
+                }
+            }
+        }
+    };
+}
+
+validation_rules! {
+    /// A rule of validation, as opposed to a [`LintRule`][crate::validation::LintRule].
+    ///
+    /// The [`name`][Self::name] of a rule is its stable identifier,
+    /// returned by [`DiagnosticData::rule`][crate::validation::DiagnosticData::rule]
+    /// and accepted by [`ValidationConfig::disable_rule`][crate::validation::ValidationConfig::disable_rule].
+    /// Names do not change between releases, even if the diagnostics of a rule do.
+    /// Syntax errors, errors found while building a schema, and cancellation are not rules.
+    /// Neither are recursion and budget limits: validation stops early when it reaches one,
+    /// so turning off its diagnostic would let an incompletely validated document pass.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[non_exhaustive]
+    pub enum ValidationRule {
+        /// A schema defines a query root operation type.
+        QueryRootOperationType,
+        /// Root operation types are object types.
+        RootOperationObjectType,
+        /// Types referenced by definitions and extensions are defined.
+        UndefinedDefinition,
+        /// Applied directives are defined.
+        UndefinedDirective,
+        /// Arguments are defined by their field or directive.
+        UndefinedArgument,
+        /// Enum values are defined by their enum type.
+        UndefinedEnumValue,
+        /// Fields of input object values are defined by their input object type.
+        UndefinedInputValue,
+        /// An argument is provided at most once.
+        UniqueArgument,
+        /// Arguments and input fields are defined at most once.
+        UniqueInputValue,
+        /// Non-repeatable directives are applied at most once per location.
+        UniqueDirective,
+        /// Directives are applied in locations their definition allows.
+        UnsupportedLocation,
+        /// Fields of object types and interfaces have output types.
+        OutputType,
+        /// Arguments and input object fields have input types.
+        InputType,
+        /// Union members are object types.
+        UnionMemberObjectType,
+        /// Object types and interfaces define every field of the interfaces they implement.
+        MissingInterfaceField,
+        /// Types also implement the interfaces implemented by their interfaces.
+        TransitiveImplementedInterfaces,
+        /// Directive definitions do not apply themselves, directly or indirectly.
+        RecursiveDirectiveDefinition,
+        /// Interfaces do not implement themselves.
+        RecursiveInterfaceDefinition,
+        /// Input objects do not require values of their own type, directly or indirectly.
+        RecursiveInputObjectDefinition,
+        /// Object types, interfaces, and their extensions define at least one field.
+        EmptyFieldSet,
+        /// Enum types define at least one value.
+        EmptyValueSet,
+        /// Union types have at least one member.
+        EmptyMemberSet,
+        /// Input object types define at least one field.
+        EmptyInputValueSet,
+        /// Fields of `@oneOf` input objects are nullable. Only with `SpecVersion::Draft`.
+        NonNullOneOfField,
+        /// Fields of `@oneOf` input objects have no default value. Only with `SpecVersion::Draft`.
+        OneOfFieldDefault,
+        /// Required arguments and input fields are not deprecated. Only with `SpecVersion::Draft`.
+        DeprecatedRequiredInput,
+        /// `@specifiedBy` URLs are absolute URIs. Enabled by
+        /// `ValidationConfig::check_specified_by_urls`.
+        InvalidSpecifiedByUrl,
+        /// Federation field sets, like the `fields` argument of `@key`, select only fields without
+        /// arguments or aliases. Enabled by `ValidationConfig::federation_field_sets`.
+        DisallowedInFieldSet,
+        /// Repeatable directives are not applied twice with the same arguments. Enabled by
+        /// `ValidationConfig::repeated_directive_warnings`.
+        RepeatedDirectiveArguments,
+        /// Executable documents only contain operations and fragments.
+        TypeSystemDefinition,
+        /// Operation names are unique in a document.
+        OperationNameCollision,
+        /// An anonymous operation is the only operation in its document.
+        AmbiguousAnonymousOperation,
+        /// The schema defines a root operation type for each operation type used.
+        UndefinedRootOperation,
+        /// Subscriptions select exactly one root field.
+        SubscriptionUsesMultipleFields,
+        /// Subscriptions do not select introspection root fields.
+        SubscriptionUsesIntrospection,
+        /// Selected fields are defined on their parent type.
+        UndefinedField,
+        /// Fields with the same response key have compatible types.
+        ConflictingFieldType,
+        /// Fields with the same response key select the same field.
+        ConflictingFieldName,
+        /// Fields with the same response key have the same arguments.
+        ConflictingFieldArgument,
+        /// Fields of object, interface, and union types have a selection set.
+        MissingSubselection,
+        /// Fields of scalar types have no selection set.
+        SubselectionOnScalarType,
+        /// Fields of enum types have no selection set.
+        SubselectionOnEnumType,
+        /// Required arguments are provided.
+        RequiredArgument,
+        /// Required fields of input object values are provided.
+        RequiredField,
+        /// Fragment names are unique in a document.
+        FragmentNameCollision,
+        /// Fragment libraries only contain fragment definitions.
+        OperationInFragmentLibrary,
+        /// Type conditions of fragment definitions are defined types.
+        UndefinedTypeInNamedFragmentTypeCondition,
+        /// Type conditions of inline fragments are defined types.
+        UndefinedTypeInInlineFragmentTypeCondition,
+        /// Type conditions are object, interface, or union types.
+        InvalidFragmentTarget,
+        /// Fragment definitions are used by an operation. The severity can be changed with
+        /// `ValidationConfig::unused_fragment_severity`.
+        UnusedFragment,
+        /// Spread fragments are defined.
+        UndefinedFragment,
+        /// Fragments do not spread themselves, directly or indirectly.
+        RecursiveFragmentDefinition,
+        /// Fragments are spread where their type condition can apply.
+        InvalidFragmentSpread,
+        /// Values have the type expected where they are used.
+        UnsupportedValueType,
+        /// `Int` values fit in a 32-bit signed integer.
+        IntCoercionError,
+        /// `Float` values are finite.
+        FloatCoercionError,
+        /// Variable names are unique in an operation.
+        UniqueVariable,
+        /// Variables have input types.
+        VariableInputType,
+        /// Used variables are defined by the operation.
+        UndefinedVariable,
+        /// Variables defined by an operation are used. The severity can be changed with
+        /// `ValidationConfig::unused_variable_severity`.
+        UnusedVariable,
+        /// Variables are used where their type is allowed.
+        DisallowedVariableUsage,
+        /// Labels of `@defer` and `@stream` are unique in a document.
+        UniqueIncrementalLabel,
+        /// Labels of `@defer` and `@stream` are not variables.
+        VariableIncrementalLabel,
+        /// The `initialCount` argument of `@stream` is not negative.
+        NegativeInitialCount,
+        /// `@defer` is not used on the root selection set of a subscription.
+        DeferOnSubscriptionRoot,
+        /// `@stream` is only used on fields of list types.
+        StreamOnNonListField,
+        /// Values of `@oneOf` input objects have exactly one field. Only with `SpecVersion::Draft`.
+        OneOfFieldCount,
+        /// Fields of `@oneOf` input object values are not null. Only with `SpecVersion::Draft`.
+        NullOneOfField,
+        /// Variables used for fields of `@oneOf` input objects are non-null. Only with
+        /// `SpecVersion::Draft`.
+        NullableVariableInOneOf,
+        /// The operation selected by name exists in the document. Checked by
+        /// `ExecutableDocument::validate_operation`.
+        OperationNotFound,
+        /// Fields of abstract types select `__typename`. Enabled by
+        /// `ValidationConfig::require_typename_on`.
+        MissingTypename,
+        /// Operations do not select `__schema` or `__type`. Enabled by
+        /// `ValidationConfig::forbid_introspection`.
+        IntrospectionDisabled,
+        /// Documents are in the operation allowlist. Enabled by
+        /// `ValidationConfig::operation_allowlist`.
+        OperationNotAllowed,
+        /// Values of custom scalars are accepted by their validator. Enabled by
+        /// `ValidationConfig::validate_scalar`.
+        InvalidScalarValue,
+        /// Applications of a directive are accepted by its validator. Enabled by
+        /// `ValidationConfig::validate_directive`.
+        InvalidDirectiveApplication,
+        /// Deprecated fields, arguments, input fields, and enum values are not used. Enabled by
+        /// `ValidationConfig::deprecation_warnings`.
+        DeprecatedUsage,
+        /// Nested type conditions can match at least one type. Enabled by
+        /// `ValidationConfig::unreachable_selection_warnings`.
+        UnreachableSelection,
+        /// Errors in non-null fields do not propagate past their parent object. Reported by
+        /// `NullPropagationReport::warnings`.
+        NullPropagation,
+        /// Fields do not nest deeper than the limit set with `ValidationConfig::max_depth`.
+        MaxDepth,
+        /// Introspection fields do not nest deeper than the limit set with
+        /// `ValidationConfig::max_introspection_depth`.
+        IntrospectionDepth,
+        /// Introspection fields select no more fields than the limit set with
+        /// `ValidationConfig::max_introspection_fields`.
+        IntrospectionFieldCount,
+        /// Selection sets have no more aliases than the limit set with `ValidationConfig::max_aliases`.
+        TooManyAliases,
+        /// Response keys are not selected more times than the limit set with
+        /// `ValidationConfig::max_repeated_fields`.
+        TooManyRepeatedFields,
+    }
+}
@@ -0,0 +1,188 @@
+use crate::collections::IndexSet;
+use crate::validation::DiagnosticList;
+use crate::validation::SerializedDiagnostic;
+use crate::validation::SerializedLocation;
+use crate::validation::Severity;
+use serde::Serialize;
+
+/// A [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html) log
+/// with a single run, as returned by [`DiagnosticList::to_sarif`].
+///
+/// Serialize it to JSON with `serde_json` to upload it to GitHub code scanning
+/// or other tools that ingest SARIF.
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: [Run; 1],
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Run {
+    tool: Tool,
+    /// Columns of [`LineColumn`][crate::parser::LineColumn] count Unicode scalar values
+    column_kind: &'static str,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Tool {
+    driver: Driver,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Rule {
+    id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_index: Option<usize>,
+    level: &'static str,
+    message: Message,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related_locations: Vec<Location>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<usize>,
+    physical_location: PhysicalLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<Message>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    region: Region,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: usize,
+    start_column: usize,
+    end_line: usize,
+    end_column: usize,
+    byte_offset: usize,
+    byte_length: usize,
+}
+
+impl DiagnosticList {
+    /// Convert to a [SARIF](https://sarifweb.azurewebsites.net/) log,
+    /// with one result per diagnostic and one rule per [`DiagnosticData::rule`][super::DiagnosticData::rule].
+    ///
+    /// Labels of each diagnostic become related locations of its result.
+    /// File paths are used as-is for artifact URIs,
+    /// so they should be relative to the root of the repository being scanned.
+    pub fn to_sarif(&self) -> SarifLog {
+        let mut rules = IndexSet::default();
+        let results = self
+            .iter()
+            .map(|diagnostic| {
+                let SerializedDiagnostic {
+                    code,
+                    message,
+                    severity,
+                    location,
+                    labels,
+                    help: _,
+                    suggestions: _,
+                } = diagnostic.to_serialized();
+                let rule_index = code.clone().map(|code| rules.insert_full(code).0);
+                SarifResult {
+                    rule_id: code,
+                    rule_index,
+                    level: match severity {
+                        Severity::Error => "error",
+                        Severity::Warning => "warning",
+                        Severity::Lint => "note",
+                    },
+                    message: Message { text: message },
+                    locations: location
+                        .map(|location| to_location(location, None, None))
+                        .into_iter()
+                        .collect(),
+                    related_locations: labels
+                        .into_iter()
+                        .enumerate()
+                        .map(|(id, label)| {
+                            to_location(label.location, Some(id), Some(label.message))
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+        SarifLog {
+            schema: "https://json.schemastore.org/sarif-2.1.0.json",
+            version: "2.1.0",
+            runs: [Run {
+                tool: Tool {
+                    driver: Driver {
+                        name: "apollo-compiler",
+                        version: env!("CARGO_PKG_VERSION"),
+                        information_uri: env!("CARGO_PKG_REPOSITORY"),
+                        rules: rules.into_iter().map(|id| Rule { id }).collect(),
+                    },
+                },
+                column_kind: "unicodeCodePoints",
+                results,
+            }],
+        }
+    }
+}
+
+fn to_location(
+    location: SerializedLocation,
+    id: Option<usize>,
+    message: Option<String>,
+) -> Location {
+    Location {
+        id,
+        physical_location: PhysicalLocation {
+            artifact_location: ArtifactLocation {
+                uri: location.file.to_string_lossy().replace('\\', "/"),
+            },
+            region: Region {
+                start_line: location.start.line,
+                start_column: location.start.column,
+                end_line: location.end.line,
+                end_column: location.end.column,
+                byte_offset: location.start_offset,
+                byte_length: location.end_offset - location.start_offset,
+            },
+        },
+        message: message.map(|text| Message { text }),
+    }
+}
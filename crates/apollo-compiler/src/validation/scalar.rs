@@ -1,5 +1,6 @@
 use crate::ast;
 use crate::schema;
+use crate::validation::diagnostics::DiagnosticData;
 use crate::validation::DiagnosticList;
 use crate::Node;
 
@@ -7,11 +8,17 @@ pub(crate) fn validate_scalar_definitions(
     diagnostics: &mut DiagnosticList,
     schema: &crate::Schema,
 ) {
-    for def in schema.types.values() {
-        if let schema::ExtendedType::Scalar(scalar) = def {
-            validate_scalar_definition(diagnostics, schema, scalar);
-        }
-    }
+    let definitions: Vec<_> = schema
+        .types
+        .values()
+        .filter_map(|def| match def {
+            schema::ExtendedType::Scalar(scalar) => Some(scalar),
+            _ => None,
+        })
+        .collect();
+    diagnostics.validate_each(&definitions, |diagnostics, scalar| {
+        validate_scalar_definition(diagnostics, schema, scalar)
+    });
 }
 
 pub(crate) fn validate_scalar_definition(
@@ -34,3 +41,92 @@ pub(crate) fn validate_scalar_definition(
         );
     }
 }
+
+/// How strictly [`validate_specified_by_urls`] checks URLs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UrlCheck {
+    /// Only a scheme is required, and whitespace and control characters are rejected
+    Lenient,
+    /// The URL must be an RFC 3986 URI with a scheme
+    Strict,
+}
+
+/// The `url` argument of `@specifiedBy` must be an absolute URI.
+///
+/// Only run when enabled with [`ValidationConfig::check_specified_by_urls`][crate::validation::ValidationConfig::check_specified_by_urls].
+pub(crate) fn validate_specified_by_urls(
+    diagnostics: &mut DiagnosticList,
+    schema: &crate::Schema,
+    check: UrlCheck,
+) {
+    for def in schema.types.values() {
+        let schema::ExtendedType::Scalar(scalar) = def else {
+            continue;
+        };
+        for directive in scalar.directives.get_all("specifiedBy") {
+            let Some(value) = directive.argument_by_name("url") else {
+                continue;
+            };
+            // A URL that is not a string is reported by argument validation
+            let Some(url) = value.as_str() else {
+                continue;
+            };
+            if let Err(reason) = check_url(url, check) {
+                diagnostics.push(
+                    value.location(),
+                    DiagnosticData::InvalidSpecifiedByUrl {
+                        scalar: scalar.name.clone(),
+                        url: url.to_owned(),
+                        reason,
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// Check the syntax of a `URI` (RFC 3986 section 3): `scheme ":" hier-part [ "?" query ] [ "#" fragment ]`
+fn check_url(url: &str, check: UrlCheck) -> Result<(), String> {
+    let Some((scheme, rest)) = url.split_once(':') else {
+        return Err("missing a scheme such as `https:`".to_owned());
+    };
+    let mut scheme_chars = scheme.chars();
+    let scheme_is_valid = scheme_chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic())
+        && scheme_chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    if !scheme_is_valid {
+        return Err(format!("invalid scheme `{scheme}`"));
+    }
+    if let Some(c) = rest.chars().find(|c| c.is_whitespace() || c.is_control()) {
+        return Err(format!("invalid character {c:?}"));
+    }
+    if check == UrlCheck::Lenient {
+        return Ok(());
+    }
+    let mut fragments = 0;
+    let mut chars = rest.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '%' => {
+                let is_hex_pair = chars.next().is_some_and(|c| c.is_ascii_hexdigit())
+                    && chars.next().is_some_and(|c| c.is_ascii_hexdigit());
+                if !is_hex_pair {
+                    return Err("`%` must be followed by two hexadecimal digits".to_owned());
+                }
+            }
+            '#' => {
+                fragments += 1;
+                if fragments > 1 {
+                    return Err("more than one `#`".to_owned());
+                }
+            }
+            // unreserved, gen-delims other than `#`, and sub-delims
+            'A'..='Z' | 'a'..='z' | '0'..='9' | '-' | '.' | '_' | '~' => {}
+            ':' | '/' | '?' | '[' | ']' | '@' => {}
+            '!' | '$' | '&' | '\'' | '(' | ')' | '*' | '+' | ',' | ';' | '=' => {}
+            _ => return Err(format!("invalid character {c:?}")),
+        }
+    }
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use crate::ast;
 use crate::schema;
 use crate::validation::diagnostics::DiagnosticData;
+use crate::validation::suggestion::type_suggestions;
 use crate::validation::DiagnosticList;
 
 pub(crate) fn validate_schema_definition(diagnostics: &mut DiagnosticList, schema: &crate::Schema) {
@@ -54,6 +55,7 @@ pub(crate) fn validate_root_operation_definitions(
                 name.location(),
                 DiagnosticData::UndefinedDefinition {
                     name: name.name.clone(),
+                    suggestions: type_suggestions(schema, name, schema::ExtendedType::is_object),
                 },
             );
         }
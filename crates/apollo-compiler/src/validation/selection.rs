@@ -10,9 +10,12 @@ use crate::executable::ConflictingFieldArgument;
 use crate::executable::ConflictingFieldName;
 use crate::executable::ConflictingFieldType;
 use crate::executable::SelectionSet;
+use crate::parser::SourceSpan;
 use crate::schema;
+use crate::validation::diagnostics::DiagnosticData;
 use crate::validation::DiagnosticList;
 use crate::validation::OperationValidationContext;
+use crate::validation::ValidationLimits;
 use crate::ExecutableDocument;
 use crate::Name;
 use crate::Node;
@@ -474,10 +477,6 @@ impl<'alloc, 'doc> MergedFieldSet<'alloc, 'doc> {
     }
 }
 
-// The field depth in the field merging validation matches the nesting level in the resulting
-// data. It makes sense to use the same limit as serde_json.
-const FIELD_DEPTH_LIMIT: usize = 128;
-
 /// Implements the `FieldsInSetCanMerge()` validation.
 /// https://spec.graphql.org/draft/#sec-Field-Selection-Merging
 ///
@@ -499,6 +498,10 @@ pub(crate) struct FieldsInSetCanMerge<'alloc, 's, 'doc> {
     // The recursion limit is used for two separate recursions, but they are not interleaved,
     // so the effective limit does apply to field nesting levels in both cases.
     recursion_limit: LimitTracker,
+    /// How many fields can be expanded for one operation or fragment, if limited.
+    budget: Option<usize>,
+    /// How many fields were expanded so far for the current operation or fragment.
+    expanded_count: usize,
 }
 
 impl<'alloc, 's, 'doc> FieldsInSetCanMerge<'alloc, 's, 'doc> {
@@ -512,16 +515,36 @@ impl<'alloc, 's, 'doc> FieldsInSetCanMerge<'alloc, 's, 'doc> {
             schema,
             document,
             cache: Default::default(),
-            recursion_limit: LimitTracker::new(FIELD_DEPTH_LIMIT),
+            recursion_limit: LimitTracker::new(ValidationLimits::default().field_merging_depth),
+            budget: None,
+            expanded_count: 0,
         }
     }
 
+    pub(crate) fn with_limits(mut self, limits: &ValidationLimits) -> Self {
+        self.recursion_limit = LimitTracker::new(limits.field_merging_depth);
+        self.budget = limits.field_merging_budget;
+        self
+    }
+
+    fn over_budget(&self) -> bool {
+        self.budget
+            .is_some_and(|budget| self.expanded_count > budget)
+    }
+
     fn expand_selections(
-        &self,
+        &mut self,
         selection_sets: impl Iterator<Item = &'doc executable::SelectionSet>,
     ) -> &'alloc [FieldSelection<'doc>] {
-        self.alloc
-            .alloc(expand_selections(&self.document.fragments, selection_sets))
+        if self.over_budget() {
+            // Stop checking nested fields, the diagnostic is added by `validate_root`
+            return &[];
+        }
+        let expanded = self
+            .alloc
+            .alloc(expand_selections(&self.document.fragments, selection_sets));
+        self.expanded_count += expanded.len();
+        expanded
     }
 
     pub(crate) fn validate_operation(
@@ -529,13 +552,34 @@ impl<'alloc, 's, 'doc> FieldsInSetCanMerge<'alloc, 's, 'doc> {
         operation: &'doc Node<executable::Operation>,
         diagnostics: &mut DiagnosticList,
     ) {
-        let fields = self.expand_selections(std::iter::once(&operation.selection_set));
+        self.validate_root(&operation.selection_set, operation.location(), diagnostics);
+    }
+
+    pub(crate) fn validate_fragment(
+        &mut self,
+        fragment: &'doc Node<executable::Fragment>,
+        diagnostics: &mut DiagnosticList,
+    ) {
+        self.validate_root(&fragment.selection_set, fragment.location(), diagnostics);
+    }
+
+    fn validate_root(
+        &mut self,
+        selection_set: &'doc SelectionSet,
+        location: Option<SourceSpan>,
+        diagnostics: &mut DiagnosticList,
+    ) {
+        self.expanded_count = 0;
+        let fields = self.expand_selections(std::iter::once(selection_set));
         let set = self.lookup(fields);
         set.same_response_shape_by_name(self, diagnostics);
         set.same_for_common_parents_by_name(self, diagnostics);
 
         if self.recursion_limit.high > self.recursion_limit.limit {
-            diagnostics.push(operation.location(), super::Details::RecursionLimitError);
+            diagnostics.push(location, super::Details::RecursionLimitError);
+        }
+        if let Some(limit) = self.budget.filter(|_| self.over_budget()) {
+            diagnostics.push(location, DiagnosticData::FieldMergingBudget { limit });
         }
     }
 
@@ -583,7 +627,16 @@ pub(crate) fn validate_selection_set(
     selection_set: &SelectionSet,
     context: OperationValidationContext<'_>,
 ) {
+    validate_selection_set_limits(diagnostics, selection_set, context);
     for selection in &selection_set.selections {
+        let location = match selection {
+            executable::Selection::Field(field) => field.location(),
+            executable::Selection::FragmentSpread(spread) => spread.location(),
+            executable::Selection::InlineFragment(inline) => inline.location(),
+        };
+        if !context.take_steps(diagnostics, 1, location) {
+            return;
+        }
         match selection {
             executable::Selection::Field(field) => {
                 super::field::validate_field(diagnostics, document, against_type, field, context)
@@ -609,3 +662,67 @@ pub(crate) fn validate_selection_set(
         }
     }
 }
+
+/// Check the opt-in limits on aliased and repeated fields.
+///
+/// Only fields directly in `selection_set` are counted.
+/// Fields in fragments are counted in the selection set of that fragment.
+fn validate_selection_set_limits(
+    diagnostics: &mut DiagnosticList,
+    selection_set: &SelectionSet,
+    context: OperationValidationContext<'_>,
+) {
+    let (max_aliases, max_repeated_fields) = context.selection_set_limits();
+    let fields = || {
+        selection_set
+            .selections
+            .iter()
+            .filter_map(|selection| selection.as_field())
+    };
+
+    if let Some(limit) = max_aliases {
+        let aliased: Vec<_> = fields().filter(|field| field.alias.is_some()).collect();
+        if aliased.len() > limit {
+            let excess_locations: Vec<_> = aliased[limit..]
+                .iter()
+                .map(|field| field.location())
+                .collect();
+            diagnostics.push(
+                excess_locations[0],
+                DiagnosticData::TooManyAliases {
+                    count: aliased.len(),
+                    limit,
+                    excess_locations,
+                },
+            );
+        }
+    }
+
+    if let Some(limit) = max_repeated_fields {
+        let mut by_response_key: IndexMap<&Name, Vec<&Node<executable::Field>>> =
+            IndexMap::default();
+        for field in fields() {
+            by_response_key
+                .entry(field.response_key())
+                .or_default()
+                .push(field);
+        }
+        for (response_key, repeated) in by_response_key {
+            if repeated.len() > limit {
+                let excess_locations: Vec<_> = repeated[limit..]
+                    .iter()
+                    .map(|field| field.location())
+                    .collect();
+                diagnostics.push(
+                    excess_locations[0],
+                    DiagnosticData::TooManyRepeatedFields {
+                        response_key: response_key.clone(),
+                        count: repeated.len(),
+                        limit,
+                        excess_locations,
+                    },
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,280 @@
+use crate::validation::ValidationRule;
+use serde::Serialize;
+use std::fmt;
+
+/// A section of the GraphQL specification that a validation rule enforces,
+/// as returned by [`DiagnosticData::spec_reference`][crate::validation::DiagnosticData::spec_reference].
+///
+/// ```rust
+/// use apollo_compiler::ExecutableDocument;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+/// let errors = ExecutableDocument::parse_and_validate(&schema, "query($v: Int) { a }", "q")
+///     .unwrap_err()
+///     .errors;
+/// let reference = errors.iter().next().unwrap().error.spec_reference().unwrap();
+/// assert_eq!(reference.to_string(), "5.8.4 All Variables Used");
+/// assert_eq!(
+///     reference.url,
+///     "https://spec.graphql.org/October2021/#sec-All-Variables-Used"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub struct SpecReference {
+    /// The section number, like `5.3.2`
+    pub section: &'static str,
+    /// The section title, like `Field Selection Merging`
+    pub title: &'static str,
+    /// A link to the section in the edition of the specification that defines the rule
+    pub url: &'static str,
+}
+
+/// Formats as the section number followed by its title
+impl fmt::Display for SpecReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.section, self.title)
+    }
+}
+
+macro_rules! october_2021 {
+    ($section: literal, $title: literal, $anchor: literal) => {
+        SpecReference {
+            section: $section,
+            title: $title,
+            url: concat!("https://spec.graphql.org/October2021/#", $anchor),
+        }
+    };
+}
+
+macro_rules! draft {
+    ($section: literal, $title: literal, $anchor: literal) => {
+        SpecReference {
+            section: $section,
+            title: $title,
+            url: concat!("https://spec.graphql.org/draft/#", $anchor),
+        }
+    };
+}
+
+/// Returns the section of the specification enforced by `rule`.
+///
+/// Rules that do not come from the specification, such as limits, lints,
+/// and opt-in warnings, return `None`.
+pub(crate) const fn spec_reference(rule: ValidationRule) -> Option<SpecReference> {
+    Some(match rule {
+        // Type system
+        ValidationRule::QueryRootOperationType
+        | ValidationRule::RootOperationObjectType
+        | ValidationRule::UndefinedRootOperation => {
+            october_2021!("3.3.1", "Root Operation Types", "sec-Root-Operation-Types")
+        }
+        ValidationRule::OutputType | ValidationRule::InputType => october_2021!(
+            "3.4.2",
+            "Input and Output Types",
+            "sec-Input-and-Output-Types"
+        ),
+        ValidationRule::EmptyFieldSet
+        | ValidationRule::MissingInterfaceField
+        | ValidationRule::TransitiveImplementedInterfaces => {
+            october_2021!("3.6", "Objects", "sec-Objects")
+        }
+        ValidationRule::RecursiveInterfaceDefinition => {
+            october_2021!("3.7", "Interfaces", "sec-Interfaces")
+        }
+        ValidationRule::EmptyMemberSet | ValidationRule::UnionMemberObjectType => {
+            october_2021!("3.8", "Unions", "sec-Unions")
+        }
+        ValidationRule::EmptyValueSet => october_2021!("3.9", "Enums", "sec-Enums"),
+        ValidationRule::EmptyInputValueSet
+        | ValidationRule::UniqueInputValue
+        | ValidationRule::RecursiveInputObjectDefinition => {
+            october_2021!("3.10", "Input Objects", "sec-Input-Objects")
+        }
+        ValidationRule::RecursiveDirectiveDefinition => {
+            october_2021!("3.13", "Directives", "sec-Type-System.Directives")
+        }
+        ValidationRule::InvalidSpecifiedByUrl => {
+            october_2021!("3.13.4", "@specifiedBy", "sec--specifiedBy")
+        }
+        ValidationRule::DeprecatedRequiredInput => {
+            draft!("3.13.3", "@deprecated", "sec--deprecated")
+        }
+        ValidationRule::NonNullOneOfField | ValidationRule::OneOfFieldDefault => {
+            draft!("3.10.1", "OneOf Input Objects", "sec-OneOf-Input-Objects")
+        }
+        // Validation of executable documents
+        ValidationRule::TypeSystemDefinition => october_2021!(
+            "5.1.1",
+            "Executable Definitions",
+            "sec-Executable-Definitions"
+        ),
+        ValidationRule::OperationNameCollision => october_2021!(
+            "5.2.1.1",
+            "Operation Name Uniqueness",
+            "sec-Operation-Name-Uniqueness"
+        ),
+        ValidationRule::AmbiguousAnonymousOperation => october_2021!(
+            "5.2.2.1",
+            "Lone Anonymous Operation",
+            "sec-Lone-Anonymous-Operation"
+        ),
+        ValidationRule::SubscriptionUsesMultipleFields
+        | ValidationRule::SubscriptionUsesIntrospection => {
+            october_2021!("5.2.3.1", "Single root field", "sec-Single-root-field")
+        }
+        ValidationRule::UndefinedField => {
+            october_2021!("5.3.1", "Field Selections", "sec-Field-Selections")
+        }
+        ValidationRule::ConflictingFieldType
+        | ValidationRule::ConflictingFieldName
+        | ValidationRule::ConflictingFieldArgument => {
+            october_2021!(
+                "5.3.2",
+                "Field Selection Merging",
+                "sec-Field-Selection-Merging"
+            )
+        }
+        ValidationRule::MissingSubselection
+        | ValidationRule::SubselectionOnScalarType
+        | ValidationRule::SubselectionOnEnumType => {
+            october_2021!(
+                "5.3.3",
+                "Leaf Field Selections",
+                "sec-Leaf-Field-Selections"
+            )
+        }
+        ValidationRule::UndefinedArgument => {
+            october_2021!("5.4.1", "Argument Names", "sec-Argument-Names")
+        }
+        ValidationRule::UniqueArgument => {
+            october_2021!("5.4.2", "Argument Uniqueness", "sec-Argument-Uniqueness")
+        }
+        ValidationRule::RequiredArgument => {
+            october_2021!("5.4.2.1", "Required Arguments", "sec-Required-Arguments")
+        }
+        ValidationRule::FragmentNameCollision => october_2021!(
+            "5.5.1.1",
+            "Fragment Name Uniqueness",
+            "sec-Fragment-Name-Uniqueness"
+        ),
+        ValidationRule::UndefinedTypeInNamedFragmentTypeCondition
+        | ValidationRule::UndefinedTypeInInlineFragmentTypeCondition => october_2021!(
+            "5.5.1.2",
+            "Fragment Spread Type Existence",
+            "sec-Fragment-Spread-Type-Existence"
+        ),
+        ValidationRule::InvalidFragmentTarget => october_2021!(
+            "5.5.1.3",
+            "Fragments On Composite Types",
+            "sec-Fragments-On-Composite-Types"
+        ),
+        ValidationRule::UnusedFragment => october_2021!(
+            "5.5.1.4",
+            "Fragments Must Be Used",
+            "sec-Fragments-Must-Be-Used"
+        ),
+        ValidationRule::UndefinedFragment => october_2021!(
+            "5.5.2.1",
+            "Fragment spread target defined",
+            "sec-Fragment-spread-target-defined"
+        ),
+        ValidationRule::RecursiveFragmentDefinition => october_2021!(
+            "5.5.2.2",
+            "Fragment spreads must not form cycles",
+            "sec-Fragment-spreads-must-not-form-cycles"
+        ),
+        ValidationRule::InvalidFragmentSpread => october_2021!(
+            "5.5.2.3",
+            "Fragment spread is possible",
+            "sec-Fragment-spread-is-possible"
+        ),
+        ValidationRule::UnsupportedValueType
+        | ValidationRule::IntCoercionError
+        | ValidationRule::FloatCoercionError
+        | ValidationRule::UndefinedEnumValue => october_2021!(
+            "5.6.1",
+            "Values of Correct Type",
+            "sec-Values-of-Correct-Type"
+        ),
+        ValidationRule::UndefinedInputValue => october_2021!(
+            "5.6.2",
+            "Input Object Field Names",
+            "sec-Input-Object-Field-Names"
+        ),
+        ValidationRule::RequiredField => october_2021!(
+            "5.6.4",
+            "Input Object Required Fields",
+            "sec-Input-Object-Required-Fields"
+        ),
+        ValidationRule::OneOfFieldCount | ValidationRule::NullOneOfField => draft!(
+            "5.6.1",
+            "Values of Correct Type",
+            "sec-Values-of-Correct-Type"
+        ),
+        ValidationRule::UndefinedDirective => october_2021!(
+            "5.7.1",
+            "Directives Are Defined",
+            "sec-Directives-Are-Defined"
+        ),
+        ValidationRule::UnsupportedLocation => october_2021!(
+            "5.7.2",
+            "Directives Are In Valid Locations",
+            "sec-Directives-Are-In-Valid-Locations"
+        ),
+        ValidationRule::UniqueDirective => october_2021!(
+            "5.7.3",
+            "Directives Are Unique Per Location",
+            "sec-Directives-Are-Unique-Per-Location"
+        ),
+        ValidationRule::UniqueVariable => {
+            october_2021!("5.8.1", "Variable Uniqueness", "sec-Variable-Uniqueness")
+        }
+        ValidationRule::VariableInputType => october_2021!(
+            "5.8.2",
+            "Variables Are Input Types",
+            "sec-Variables-Are-Input-Types"
+        ),
+        ValidationRule::UndefinedVariable => october_2021!(
+            "5.8.3",
+            "All Variable Uses Defined",
+            "sec-All-Variable-Uses-Defined"
+        ),
+        ValidationRule::UnusedVariable => {
+            october_2021!("5.8.4", "All Variables Used", "sec-All-Variables-Used")
+        }
+        ValidationRule::DisallowedVariableUsage => october_2021!(
+            "5.8.5",
+            "All Variable Usages are Allowed",
+            "sec-All-Variable-Usages-are-Allowed"
+        ),
+        ValidationRule::NullableVariableInOneOf => draft!(
+            "5.8.5",
+            "All Variable Usages Are Allowed",
+            "sec-All-Variable-Usages-Are-Allowed"
+        ),
+        ValidationRule::UndefinedDefinition
+        | ValidationRule::DisallowedInFieldSet
+        | ValidationRule::RepeatedDirectiveArguments
+        | ValidationRule::OperationInFragmentLibrary
+        | ValidationRule::UniqueIncrementalLabel
+        | ValidationRule::VariableIncrementalLabel
+        | ValidationRule::NegativeInitialCount
+        | ValidationRule::DeferOnSubscriptionRoot
+        | ValidationRule::StreamOnNonListField
+        | ValidationRule::OperationNotFound
+        | ValidationRule::MissingTypename
+        | ValidationRule::IntrospectionDisabled
+        | ValidationRule::OperationNotAllowed
+        | ValidationRule::InvalidScalarValue
+        | ValidationRule::InvalidDirectiveApplication
+        | ValidationRule::DeprecatedUsage
+        | ValidationRule::UnreachableSelection
+        | ValidationRule::NullPropagation
+        | ValidationRule::MaxDepth
+        | ValidationRule::IntrospectionDepth
+        | ValidationRule::IntrospectionFieldCount
+        | ValidationRule::TooManyAliases
+        | ValidationRule::TooManyRepeatedFields => return None,
+    })
+}
@@ -0,0 +1,100 @@
+use crate::diagnostic::CliReport;
+use crate::schema::ExtendedType;
+use crate::Name;
+use crate::Schema;
+
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Returns the names among `candidates` that are similar enough to `input`
+/// to likely be what was meant, most similar first.
+///
+/// Uses the same thresholds as `suggestionList` in graphql-js:
+/// names within an edit distance of 40% of the length of `input`,
+/// where names that only differ by case are always suggested.
+pub(crate) fn suggestions<'a>(
+    input: &str,
+    candidates: impl IntoIterator<Item = &'a Name>,
+) -> Vec<Name> {
+    let threshold = input.chars().count() * 2 / 5 + 1;
+    let mut similar: Vec<_> = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let distance = lexical_distance(input, candidate);
+            (distance <= threshold).then_some((distance, candidate))
+        })
+        .collect();
+    similar.sort_by(|(distance_a, name_a), (distance_b, name_b)| {
+        distance_a
+            .cmp(distance_b)
+            .then_with(|| name_a.as_str().cmp(name_b.as_str()))
+    });
+    similar
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, name)| name.clone())
+        .collect()
+}
+
+/// Returns the names of types in `schema` that are similar to `input` and match `filter`
+pub(crate) fn type_suggestions(
+    schema: &Schema,
+    input: &str,
+    filter: impl Fn(&ExtendedType) -> bool,
+) -> Vec<Name> {
+    suggestions(
+        input,
+        schema
+            .types
+            .iter()
+            .filter(|(name, ty)| !name.starts_with("__") && filter(ty))
+            .map(|(name, _)| name),
+    )
+}
+
+/// Add a "did you mean" help message if there are any suggestions.
+/// `prefix` is prepended to each name, such as `@` for directives.
+pub(crate) fn report_suggestions(report: &mut CliReport, prefix: &str, suggestions: &[Name]) {
+    let quoted = |name: &Name| format!("`{prefix}{name}`");
+    let list = match suggestions {
+        [] => return,
+        [name] => quoted(name),
+        [init @ .., last] => {
+            let init: Vec<_> = init.iter().map(quoted).collect();
+            format!("{} or {}", init.join(", "), quoted(last))
+        }
+    };
+    report.with_help(format_args!("did you mean {list}?"));
+}
+
+/// The [optimal string alignment distance](https://en.wikipedia.org/wiki/Damerau%E2%80%93Levenshtein_distance#Optimal_string_alignment_distance)
+/// between two strings, ignoring case except that a difference in case only counts as 1
+fn lexical_distance(a: &str, b: &str) -> usize {
+    if a == b {
+        return 0;
+    }
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    if a == b {
+        return 1;
+    }
+    let mut previous_previous = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                // Transposition
+                distance = distance.min(previous_previous[j - 2] + 1);
+            }
+            current[j] = distance;
+        }
+        std::mem::swap(&mut previous_previous, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
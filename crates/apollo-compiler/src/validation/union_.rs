@@ -3,15 +3,22 @@ use crate::schema;
 use crate::schema::ExtendedType;
 use crate::schema::UnionType;
 use crate::validation::diagnostics::DiagnosticData;
+use crate::validation::suggestion::type_suggestions;
 use crate::validation::DiagnosticList;
 use crate::Node;
 
 pub(crate) fn validate_union_definitions(diagnostics: &mut DiagnosticList, schema: &crate::Schema) {
-    for ty in schema.types.values() {
-        if let ExtendedType::Union(def) = ty {
-            validate_union_definition(diagnostics, schema, def);
-        }
-    }
+    let definitions: Vec<_> = schema
+        .types
+        .values()
+        .filter_map(|ty| match ty {
+            ExtendedType::Union(def) => Some(def),
+            _ => None,
+        })
+        .collect();
+    diagnostics.validate_each(&definitions, |diagnostics, def| {
+        validate_union_definition(diagnostics, schema, def)
+    });
 }
 
 pub(crate) fn validate_union_definition(
@@ -39,6 +46,11 @@ pub(crate) fn validate_union_definition(
                     member_location,
                     DiagnosticData::UndefinedDefinition {
                         name: union_member.name.clone(),
+                        suggestions: type_suggestions(
+                            schema,
+                            &union_member.name,
+                            ExtendedType::is_object,
+                        ),
                     },
                 );
             }
@@ -0,0 +1,192 @@
+use crate::ast::NamedType;
+use crate::collections::HashMap;
+use crate::collections::HashSet;
+use crate::executable::Selection;
+use crate::executable::SelectionSet;
+use crate::parser::SourceSpan;
+use crate::schema::ExtendedType;
+use crate::schema::Implementers;
+use crate::validation::diagnostics::DiagnosticData;
+use crate::validation::DiagnosticList;
+use crate::ExecutableDocument;
+use crate::Name;
+use crate::Schema;
+use std::sync::Arc;
+
+/// Report fragments whose type condition cannot match any type allowed by the type conditions
+/// enclosing it since the nearest field, such as `... on Dog` in `... on Cat { ... on Pet { } }`.
+///
+/// Each fragment spread can be possible by itself, as checked by `validate_fragment_spread_type`,
+/// while their combination can never produce data.
+pub(crate) fn validate_unreachable_selections(
+    diagnostics: &mut DiagnosticList,
+    schema: &Schema,
+    document: &ExecutableDocument,
+) {
+    let mut walker = Walker {
+        diagnostics,
+        schema,
+        document,
+        implementers: schema.implementers_map(),
+        possible_types: HashMap::default(),
+    };
+    for operation in document.operations.iter() {
+        walker.walk_definition(&operation.selection_set);
+    }
+    for fragment in document.fragments.values() {
+        walker.walk_definition(&fragment.selection_set);
+    }
+}
+
+struct Walker<'a> {
+    diagnostics: &'a mut DiagnosticList,
+    schema: &'a Schema,
+    document: &'a ExecutableDocument,
+    implementers: HashMap<Name, Implementers>,
+    possible_types: HashMap<NamedType, Arc<HashSet<Name>>>,
+}
+
+/// A type condition enclosing the selections being walked
+struct Condition<'doc> {
+    type_condition: &'doc NamedType,
+    location: Option<SourceSpan>,
+}
+
+impl<'doc> Walker<'doc> {
+    /// Walk the selection set of an operation, fragment definition, or field,
+    /// where any type of `selection_set.ty` is possible
+    fn walk_definition(&mut self, selection_set: &'doc SelectionSet) {
+        let possible = self.possible_types(&selection_set.ty);
+        self.walk_selection_set(
+            selection_set,
+            &possible,
+            &mut Vec::new(),
+            &mut HashSet::default(),
+        );
+    }
+
+    /// `spreads` are the names of fragments being walked through spreads.
+    /// Their fields were already walked from their own definition.
+    fn walk_selection_set(
+        &mut self,
+        selection_set: &'doc SelectionSet,
+        possible: &HashSet<Name>,
+        conditions: &mut Vec<Condition<'doc>>,
+        spreads: &mut HashSet<&'doc Name>,
+    ) {
+        for selection in &selection_set.selections {
+            match selection {
+                Selection::Field(field) => {
+                    if spreads.is_empty() {
+                        self.walk_definition(&field.selection_set);
+                    }
+                }
+                Selection::InlineFragment(inline) => {
+                    if inline.type_condition.is_none() {
+                        self.walk_selection_set(
+                            &inline.selection_set,
+                            possible,
+                            conditions,
+                            spreads,
+                        );
+                    } else {
+                        self.walk_type_condition(
+                            &selection_set.ty,
+                            &inline.selection_set,
+                            inline.location(),
+                            possible,
+                            conditions,
+                            spreads,
+                        );
+                    }
+                }
+                Selection::FragmentSpread(spread) => {
+                    // Undefined fragments and cycles are reported by other rules
+                    let document = self.document;
+                    let Some(fragment) = document.fragments.get(&spread.fragment_name) else {
+                        continue;
+                    };
+                    if !spreads.insert(&spread.fragment_name) {
+                        continue;
+                    }
+                    self.walk_type_condition(
+                        &selection_set.ty,
+                        &fragment.selection_set,
+                        spread.location(),
+                        possible,
+                        conditions,
+                        spreads,
+                    );
+                    spreads.remove(&spread.fragment_name);
+                }
+            }
+        }
+    }
+
+    fn walk_type_condition(
+        &mut self,
+        parent_type: &NamedType,
+        selection_set: &'doc SelectionSet,
+        location: Option<SourceSpan>,
+        possible: &HashSet<Name>,
+        conditions: &mut Vec<Condition<'doc>>,
+        spreads: &mut HashSet<&'doc Name>,
+    ) {
+        let type_condition = &selection_set.ty;
+        let condition_types = self.possible_types(type_condition);
+        let narrowed: HashSet<Name> = possible.intersection(&condition_types).cloned().collect();
+        if narrowed.is_empty() {
+            // If the type condition cannot match the parent type by itself,
+            // or is not a composite type, the fragment is already invalid
+            let parent_types = self.possible_types(parent_type);
+            if !parent_types.is_disjoint(&condition_types) {
+                self.diagnostics.push(
+                    location,
+                    DiagnosticData::UnreachableSelection {
+                        type_condition: type_condition.clone(),
+                        enclosing_conditions: conditions
+                            .iter()
+                            .map(|condition| (condition.type_condition.clone(), condition.location))
+                            .collect(),
+                    },
+                );
+            }
+            return;
+        }
+        // A fragment that is not narrowed more than by its own type condition
+        // has the same results as when walked from its definition
+        if !spreads.is_empty() && narrowed.len() == condition_types.len() {
+            return;
+        }
+        conditions.push(Condition {
+            type_condition,
+            location,
+        });
+        self.walk_selection_set(selection_set, &narrowed, conditions, spreads);
+        conditions.pop();
+    }
+
+    /// Object types that can be the runtime type of a value of type `name`
+    fn possible_types(&mut self, name: &NamedType) -> Arc<HashSet<Name>> {
+        if let Some(types) = self.possible_types.get(name) {
+            return types.clone();
+        }
+        let types: HashSet<Name> = match self.schema.types.get(name) {
+            Some(ExtendedType::Object(object)) => [object.name.clone()].into_iter().collect(),
+            Some(ExtendedType::Interface(_)) => self
+                .implementers
+                .get(name)
+                .map(|implementers| implementers.objects.iter().cloned().collect())
+                .unwrap_or_default(),
+            Some(ExtendedType::Union(union_)) => union_
+                .members
+                .iter()
+                .map(|member| member.name.clone())
+                .collect(),
+            _ => HashSet::default(),
+        };
+        let types = Arc::new(types);
+        self.possible_types.insert(name.clone(), types.clone());
+        types
+    }
+}
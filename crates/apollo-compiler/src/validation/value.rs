@@ -1,14 +1,40 @@
 use crate::ast;
 use crate::coordinate::TypeAttributeCoordinate;
+use crate::executable;
 use crate::schema;
 use crate::validation::diagnostics::DiagnosticData;
+use crate::validation::suggestion::type_suggestions;
 use crate::validation::DiagnosticList;
+use crate::validation::Valid;
+use crate::validation::ValuePath;
+use crate::validation::ValuePathElement;
+use crate::ExecutableDocument;
 use crate::Node;
+use crate::Schema;
+
+/// Linked-list version of `ValuePath`, taking advantage of the call stack
+type LinkedPath<'a> = Option<&'a LinkedPathElement<'a>>;
+
+struct LinkedPathElement<'a> {
+    element: ValuePathElement,
+    next: LinkedPath<'a>,
+}
+
+fn to_value_path(path: LinkedPath<'_>) -> ValuePath {
+    let mut elements = Vec::new();
+    let mut next = path;
+    while let Some(node) = next {
+        elements.push(node.element.clone());
+        next = node.next;
+    }
+    elements.into_iter().rev().collect()
+}
 
 fn unsupported_type(
     diagnostics: &mut DiagnosticList,
     value: &Node<ast::Value>,
     declared_type: &Node<ast::Type>,
+    path: LinkedPath<'_>,
 ) {
     diagnostics.push(
         value.location(),
@@ -16,6 +42,7 @@ fn unsupported_type(
             ty: declared_type.clone(),
             value: value.clone(),
             definition_location: declared_type.location(),
+            path: to_value_path(path),
         },
     )
 }
@@ -27,15 +54,208 @@ pub(crate) fn validate_values(
     argument: &Node<ast::Argument>,
     var_defs: &[Node<ast::VariableDefinition>],
 ) {
-    value_of_correct_type(diagnostics, schema, ty, &argument.value, var_defs);
+    let path = LinkedPathElement {
+        element: ValuePathElement::Argument(argument.name.clone()),
+        next: None,
+    };
+    value_of_correct_type(
+        diagnostics,
+        schema,
+        ty,
+        &argument.value,
+        var_defs,
+        Some(&path),
+    );
+}
+
+/// Check the default value of an argument definition against the argument's type,
+/// including items of lists and fields of nested input objects.
+///
+/// Diagnostics have a path starting with the argument, as for arguments in a document.
+pub(crate) fn validate_argument_default_value(
+    diagnostics: &mut DiagnosticList,
+    schema: &crate::Schema,
+    argument: &Node<ast::InputValueDefinition>,
+) {
+    let Some(default_value) = &argument.default_value else {
+        return;
+    };
+    let is_input_type = schema
+        .types
+        .get(argument.ty.inner_named_type())
+        .is_some_and(|ty| ty.is_input_type());
+    if !is_input_type {
+        // Reported by `validate_input_value_definitions`
+        return;
+    }
+    let path = LinkedPathElement {
+        element: ValuePathElement::Argument(argument.name.clone()),
+        next: None,
+    };
+    value_of_correct_type(
+        diagnostics,
+        schema,
+        &argument.ty,
+        default_value,
+        &[],
+        Some(&path),
+    );
+}
+
+/// Coerce a constant `value` to the input type `ty` of `schema`,
+/// as for a literal argument or default value.
+///
+/// Returns the value as it would be seen during execution:
+/// defaults of missing input object fields are filled in (in field definition order),
+/// single values are wrapped in lists as needed,
+/// `Int` values are converted to `Float` or `ID` (as a string) where those are expected,
+/// and variables are rejected.
+///
+/// Diagnostics are the same as for validation of arguments in a document.
+///
+/// ```
+/// use apollo_compiler::ast::Value;
+/// use apollo_compiler::validation::coerce_const_value;
+/// use apollo_compiler::name;
+/// use apollo_compiler::ty;
+/// use apollo_compiler::Node;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(
+///     "type Query { a: Int } input Range { min: Float = 0, max: Float }",
+///     "schema.graphql",
+/// )
+/// .unwrap();
+/// let value = Node::new(Value::Object(vec![(name!("max"), Node::new(Value::from(5)))]));
+/// let coerced = coerce_const_value(&schema, &Node::new(ty!([Range])), &value).unwrap();
+/// assert_eq!(
+///     coerced.serialize().no_indent().to_string(),
+///     "[{min: 0.0, max: 5.0}]"
+/// );
+/// ```
+pub fn coerce_const_value(
+    schema: &Valid<Schema>,
+    ty: &Node<ast::Type>,
+    value: &Node<ast::Value>,
+) -> Result<Node<ast::Value>, DiagnosticList> {
+    let mut diagnostics = DiagnosticList::new(schema.sources.clone());
+    let type_name = ty.inner_named_type();
+    if !schema.types.contains_key(type_name) {
+        diagnostics.push(
+            type_name.location(),
+            DiagnosticData::UndefinedDefinition {
+                name: type_name.clone(),
+                suggestions: type_suggestions(schema, type_name, |ty| ty.is_input_type()),
+            },
+        );
+    } else {
+        value_of_correct_type(&mut diagnostics, schema, ty, value, &[], None);
+    }
+    diagnostics.into_result()?;
+    Ok(coerce_valid_value(schema, ty, value))
+}
+
+/// Returns the value that an argument or input field has when none is given,
+/// or `None` if it has no default value.
+///
+/// This is its default value as coerced by [`coerce_const_value`]:
+/// defaults of input object fields that are missing from it are filled in, recursively,
+/// and single values are wrapped in lists as needed.
+/// Default values were checked when validating `schema`.
+///
+/// ```
+/// use apollo_compiler::validation::effective_default_value;
+/// use apollo_compiler::Schema;
+///
+/// let schema = Schema::parse_and_validate(
+///     r#"
+///     type Query { search(page: Page = { size: 20 }): [String] }
+///     input Page { size: Int!, sort: Sort = { by: "name" } }
+///     input Sort { by: String!, descending: Boolean = false }
+///     "#,
+///     "schema.graphql",
+/// )
+/// .unwrap();
+/// let argument = schema.type_field("Query", "search").unwrap().argument_by_name("page");
+/// let default = effective_default_value(&schema, argument.unwrap()).unwrap();
+/// assert_eq!(
+///     default.serialize().no_indent().to_string(),
+///     r#"{size: 20, sort: {by: "name", descending: false}}"#
+/// );
+/// ```
+pub fn effective_default_value(
+    schema: &Valid<Schema>,
+    definition: &ast::InputValueDefinition,
+) -> Option<Node<ast::Value>> {
+    let default_value = definition.default_value.as_ref()?;
+    Some(coerce_valid_value(schema, &definition.ty, default_value))
+}
+
+/// Apply input coercion to a value that passed `value_of_correct_type`
+pub(crate) fn coerce_valid_value(
+    schema: &Schema,
+    ty: &ast::Type,
+    value: &Node<ast::Value>,
+) -> Node<ast::Value> {
+    if value.is_null() {
+        return value.clone();
+    }
+    if ty.is_list() {
+        let item_type = ty.item_type();
+        let items = match value.as_list() {
+            Some(items) => items
+                .iter()
+                .map(|item| coerce_valid_value(schema, item_type, item))
+                .collect(),
+            None => vec![coerce_valid_value(schema, item_type, value)],
+        };
+        return value.same_location(ast::Value::List(items));
+    }
+    let coerced = match (schema.types.get(ty.inner_named_type()), value.as_ref()) {
+        (Some(schema::ExtendedType::Scalar(scalar)), ast::Value::Int(int))
+            if scalar.name == "Float" =>
+        {
+            match int.try_to_f64() {
+                Ok(float) => ast::Value::from(float),
+                Err(_) => return value.clone(),
+            }
+        }
+        (Some(schema::ExtendedType::Scalar(scalar)), ast::Value::Int(int))
+            if scalar.name == "ID" =>
+        {
+            ast::Value::String(int.as_str().to_owned())
+        }
+        (Some(schema::ExtendedType::InputObject(input_object)), ast::Value::Object(fields)) => {
+            ast::Value::Object(
+                input_object
+                    .fields
+                    .iter()
+                    .filter_map(|(name, field_def)| {
+                        let field_value = fields
+                            .iter()
+                            .find(|(field_name, _)| field_name == name)
+                            .map(|(_, field_value)| field_value)
+                            .or(field_def.default_value.as_ref())?;
+                        Some((
+                            name.clone(),
+                            coerce_valid_value(schema, &field_def.ty, field_value),
+                        ))
+                    })
+                    .collect(),
+            )
+        }
+        _ => return value.clone(),
+    };
+    value.same_location(coerced)
 }
 
-pub(crate) fn value_of_correct_type(
+fn value_of_correct_type(
     diagnostics: &mut DiagnosticList,
     schema: &crate::Schema,
     ty: &Node<ast::Type>,
     arg_value: &Node<ast::Value>,
     var_defs: &[Node<ast::VariableDefinition>],
+    path: LinkedPath<'_>,
 ) {
     let Some(type_definition) = schema.types.get(ty.inner_named_type()) else {
         return;
@@ -62,6 +282,7 @@ pub(crate) fn value_of_correct_type(
                             arg_value.location(),
                             DiagnosticData::IntCoercionError {
                                 value: int.as_str().to_owned(),
+                                path: to_value_path(path),
                             },
                         )
                     }
@@ -72,13 +293,14 @@ pub(crate) fn value_of_correct_type(
                             arg_value.location(),
                             DiagnosticData::FloatCoercionError {
                                 value: int.as_str().to_owned(),
+                                path: to_value_path(path),
                             },
                         )
                     }
                 }
-                _ => unsupported_type(diagnostics, arg_value, ty),
+                _ => unsupported_type(diagnostics, arg_value, ty, path),
             },
-            _ => unsupported_type(diagnostics, arg_value, ty),
+            _ => unsupported_type(diagnostics, arg_value, ty, path),
         },
         // When expected as an input type, both integer and float input
         // values are accepted. All other input values, including strings
@@ -93,11 +315,12 @@ pub(crate) fn value_of_correct_type(
                         arg_value.location(),
                         DiagnosticData::FloatCoercionError {
                             value: float.as_str().to_owned(),
+                            path: to_value_path(path),
                         },
                     )
                 }
             }
-            _ => unsupported_type(diagnostics, arg_value, ty),
+            _ => unsupported_type(diagnostics, arg_value, ty, path),
         },
         // When expected as an input type, only valid Unicode string input
         // values are accepted. All other input values must raise a request
@@ -111,10 +334,10 @@ pub(crate) fn value_of_correct_type(
                 // string, ids and custom scalars are ok, and
                 // don't need a diagnostic.
                 if scalar.is_built_in() && !matches!(scalar.name.as_str(), "String" | "ID") {
-                    unsupported_type(diagnostics, arg_value, ty);
+                    unsupported_type(diagnostics, arg_value, ty, path);
                 }
             }
-            _ => unsupported_type(diagnostics, arg_value, ty),
+            _ => unsupported_type(diagnostics, arg_value, ty, path),
         },
         // When expected as an input type, only boolean input values are
         // accepted. All other input values must raise a request error
@@ -122,14 +345,14 @@ pub(crate) fn value_of_correct_type(
         ast::Value::Boolean(_) => match &type_definition {
             schema::ExtendedType::Scalar(scalar) => {
                 if scalar.is_built_in() && scalar.name.as_str() != "Boolean" {
-                    unsupported_type(diagnostics, arg_value, ty);
+                    unsupported_type(diagnostics, arg_value, ty, path);
                 }
             }
-            _ => unsupported_type(diagnostics, arg_value, ty),
+            _ => unsupported_type(diagnostics, arg_value, ty, path),
         },
         ast::Value::Null => {
             if ty.is_non_null() {
-                unsupported_type(diagnostics, arg_value, ty);
+                unsupported_type(diagnostics, arg_value, ty, path);
             }
         }
         ast::Value::Variable(var_name) => {
@@ -142,10 +365,19 @@ pub(crate) fn value_of_correct_type(
                         // compare if two Types are the same
                         // TODO(@goto-bus-stop) This should use the is_assignable_to check
                         if var_def.ty.inner_named_type() != ty.inner_named_type() {
-                            unsupported_type(diagnostics, arg_value, ty);
+                            unsupported_type(diagnostics, arg_value, ty, path);
                         } else if let Some(default_value) = &var_def.default_value {
+                            let default_value_path = LinkedPathElement {
+                                element: ValuePathElement::Variable(var_def.name.clone()),
+                                next: None,
+                            };
                             if var_def.ty.is_non_null() && default_value.is_null() {
-                                unsupported_type(diagnostics, default_value, &var_def.ty)
+                                unsupported_type(
+                                    diagnostics,
+                                    default_value,
+                                    &var_def.ty,
+                                    Some(&default_value_path),
+                                )
                             } else {
                                 value_of_correct_type(
                                     diagnostics,
@@ -153,11 +385,12 @@ pub(crate) fn value_of_correct_type(
                                     &var_def.ty,
                                     default_value,
                                     var_defs,
+                                    Some(&default_value_path),
                                 )
                             }
                         }
                     }
-                    _ => unsupported_type(diagnostics, arg_value, ty),
+                    _ => unsupported_type(diagnostics, arg_value, ty, path),
                 }
             } else {
                 diagnostics.push(
@@ -180,11 +413,12 @@ pub(crate) fn value_of_correct_type(
                             value: value.clone(),
                             definition: enum_.name.clone(),
                             definition_location: enum_.location(),
+                            path: to_value_path(path),
                         },
                     );
                 }
             }
-            _ => unsupported_type(diagnostics, arg_value, ty),
+            _ => unsupported_type(diagnostics, arg_value, ty, path),
         },
         // When expected as an input, list values are accepted only when
         // each item in the list can be accepted by the list’s item type.
@@ -199,15 +433,26 @@ pub(crate) fn value_of_correct_type(
                 // A named type can still accept a list if it is a custom scalar.
                 || matches!(type_definition, schema::ExtendedType::Scalar(scalar) if !scalar.is_built_in());
             if !accepts_list {
-                unsupported_type(diagnostics, arg_value, ty)
+                unsupported_type(diagnostics, arg_value, ty, path)
             } else {
                 let item_type = ty.same_location(ty.item_type().clone());
                 if type_definition.is_input_type() {
-                    for v in li {
-                        value_of_correct_type(diagnostics, schema, &item_type, v, var_defs);
+                    for (index, v) in li.iter().enumerate() {
+                        let item_path = LinkedPathElement {
+                            element: ValuePathElement::Index(index),
+                            next: path,
+                        };
+                        value_of_correct_type(
+                            diagnostics,
+                            schema,
+                            &item_type,
+                            v,
+                            var_defs,
+                            Some(&item_path),
+                        );
                     }
                 } else {
-                    unsupported_type(diagnostics, arg_value, &item_type);
+                    unsupported_type(diagnostics, arg_value, &item_type, path);
                 }
             }
         }
@@ -221,12 +466,17 @@ pub(crate) fn value_of_correct_type(
                 // Add a diagnostic if a value does not exist on the input
                 // object type
                 if let Some((name, value)) = undefined_field {
+                    let field_path = LinkedPathElement {
+                        element: ValuePathElement::Field(name.clone()),
+                        next: path,
+                    };
                     diagnostics.push(
                         value.location(),
                         DiagnosticData::UndefinedInputValue {
                             value: name.clone(),
                             definition: input_obj.name.clone(),
                             definition_location: input_obj.location(),
+                            path: to_value_path(Some(&field_path)),
                         },
                     );
                 }
@@ -253,6 +503,7 @@ pub(crate) fn value_of_correct_type(
                                 },
                                 expected_type: ty.clone(),
                                 definition_location: f.location(),
+                                path: to_value_path(path),
                             },
                         );
                     }
@@ -260,11 +511,158 @@ pub(crate) fn value_of_correct_type(
                     let used_val = obj.iter().find(|(obj_name, ..)| obj_name == input_name);
 
                     if let Some((_, v)) = used_val {
-                        value_of_correct_type(diagnostics, schema, ty, v, var_defs);
+                        let field_path = LinkedPathElement {
+                            element: ValuePathElement::Field(input_name.clone()),
+                            next: path,
+                        };
+                        value_of_correct_type(
+                            diagnostics,
+                            schema,
+                            ty,
+                            v,
+                            var_defs,
+                            Some(&field_path),
+                        );
                     }
                 })
             }
-            _ => unsupported_type(diagnostics, arg_value, ty),
+            _ => unsupported_type(diagnostics, arg_value, ty, path),
         },
     }
 }
+
+/// Callback of [`walk_literal_values`]
+type VisitLiteral<'a> = dyn FnMut(
+        &schema::ExtendedType,
+        &Node<ast::Value>,
+        &[ValuePathElement],
+        &[Node<ast::VariableDefinition>],
+    ) + 'a;
+
+/// Call `visit` for every literal value in `document` that has a named input type,
+/// including values nested in lists and input objects: in arguments of fields and directives,
+/// and in default values of variables.
+/// `null` values and variables are skipped.
+///
+/// `visit` receives the type and the value, the path to the value, and the variables
+/// of the operation where the value is found. Those are empty for values in fragments,
+/// since they may be used from multiple operations.
+pub(crate) fn walk_literal_values(
+    schema: &Schema,
+    document: &ExecutableDocument,
+    visit: &mut VisitLiteral<'_>,
+) {
+    let mut walker = LiteralWalker {
+        schema,
+        visit,
+        path: Vec::new(),
+        variables: &[],
+    };
+    for operation in document.operations.iter() {
+        walker.variables = &operation.variables;
+        for variable in &operation.variables {
+            if let Some(default) = &variable.default_value {
+                walker
+                    .path
+                    .push(ValuePathElement::Variable(variable.name.clone()));
+                walker.value(&variable.ty, default);
+                walker.path.pop();
+            }
+            walker.directives(&variable.directives);
+        }
+        walker.directives(&operation.directives);
+        walker.selection_set(&operation.selection_set);
+    }
+    walker.variables = &[];
+    for fragment in document.fragments.values() {
+        walker.directives(&fragment.directives);
+        walker.selection_set(&fragment.selection_set);
+    }
+}
+
+struct LiteralWalker<'a> {
+    schema: &'a Schema,
+    visit: &'a mut VisitLiteral<'a>,
+    /// Path from the argument or variable to the value being visited
+    path: Vec<ValuePathElement>,
+    variables: &'a [Node<ast::VariableDefinition>],
+}
+
+impl LiteralWalker<'_> {
+    fn selection_set(&mut self, selection_set: &executable::SelectionSet) {
+        for selection in &selection_set.selections {
+            match selection {
+                executable::Selection::Field(field) => {
+                    self.arguments(&field.definition.arguments, &field.arguments);
+                    self.directives(&field.directives);
+                    self.selection_set(&field.selection_set);
+                }
+                executable::Selection::FragmentSpread(spread) => {
+                    self.directives(&spread.directives);
+                }
+                executable::Selection::InlineFragment(inline) => {
+                    self.directives(&inline.directives);
+                    self.selection_set(&inline.selection_set);
+                }
+            }
+        }
+    }
+
+    fn directives(&mut self, directives: &ast::DirectiveList) {
+        for directive in directives {
+            if let Some(definition) = self.schema.directive_definitions.get(&directive.name) {
+                self.arguments(&definition.arguments, &directive.arguments);
+            }
+        }
+    }
+
+    fn arguments(
+        &mut self,
+        definitions: &[Node<ast::InputValueDefinition>],
+        arguments: &[Node<ast::Argument>],
+    ) {
+        for argument in arguments {
+            if let Some(definition) = definitions.iter().find(|def| def.name == argument.name) {
+                self.path
+                    .push(ValuePathElement::Argument(argument.name.clone()));
+                self.value(&definition.ty, &argument.value);
+                self.path.pop();
+            }
+        }
+    }
+
+    fn value(&mut self, ty: &ast::Type, value: &Node<ast::Value>) {
+        if matches!(value.as_ref(), ast::Value::Null | ast::Value::Variable(_)) {
+            return;
+        }
+        if ty.is_list() {
+            match value.as_list() {
+                Some(items) => {
+                    for (index, item) in items.iter().enumerate() {
+                        self.path.push(ValuePathElement::Index(index));
+                        self.value(ty.item_type(), item);
+                        self.path.pop();
+                    }
+                }
+                // Input coercion turns a single value into a list of one item
+                None => self.value(ty.item_type(), value),
+            }
+            return;
+        }
+        let Some(type_definition) = self.schema.types.get(ty.inner_named_type()) else {
+            return;
+        };
+        (self.visit)(type_definition, value, &self.path, self.variables);
+        if let (schema::ExtendedType::InputObject(input_object), Some(fields)) =
+            (type_definition, value.as_object())
+        {
+            for (name, field_value) in fields {
+                if let Some(definition) = input_object.fields.get(name) {
+                    self.path.push(ValuePathElement::Field(name.clone()));
+                    self.value(&definition.ty, field_value);
+                    self.path.pop();
+                }
+            }
+        }
+    }
+}
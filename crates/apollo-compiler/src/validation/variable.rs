@@ -1,23 +1,33 @@
 use crate::ast;
 use crate::collections::HashMap;
+use crate::collections::HashSet;
+use crate::collections::IndexMap;
+use crate::coordinate::DirectiveArgumentCoordinate;
+use crate::coordinate::FieldArgumentCoordinate;
+use crate::coordinate::SchemaCoordinate;
+use crate::coordinate::TypeAttributeCoordinate;
 use crate::executable;
+use crate::schema::ExtendedType;
 use crate::validation::diagnostics::DiagnosticData;
+use crate::validation::suggestion::type_suggestions;
 use crate::validation::DiagnosticList;
 use crate::validation::RecursionGuard;
 use crate::validation::RecursionLimitError;
 use crate::validation::RecursionStack;
 use crate::validation::SourceSpan;
+use crate::validation::Valid;
+use crate::validation::ValidationLimits;
 use crate::ExecutableDocument;
 use crate::Name;
 use crate::Node;
-use std::collections::hash_map::Entry;
+use crate::Schema;
 
 pub(crate) fn validate_variable_definitions(
     diagnostics: &mut DiagnosticList,
     schema: Option<&crate::Schema>,
     variables: &[Node<ast::VariableDefinition>],
 ) {
-    let mut seen: HashMap<Name, &Node<ast::VariableDefinition>> = HashMap::default();
+    let mut seen = IndexMap::<_, Vec<Option<SourceSpan>>>::default();
     for variable in variables.iter() {
         super::directive::validate_directives(
             diagnostics,
@@ -51,34 +61,40 @@ pub(crate) fn validate_variable_definitions(
                     variable.location(),
                     DiagnosticData::UndefinedDefinition {
                         name: ty.inner_named_type().clone(),
+                        suggestions: type_suggestions(
+                            schema,
+                            ty.inner_named_type(),
+                            ExtendedType::is_input_type,
+                        ),
                     },
                 ),
             }
         }
 
-        match seen.entry(variable.name.clone()) {
-            Entry::Occupied(original) => {
-                let original_definition = original.get().location();
-                let redefined_definition = variable.location();
-                diagnostics.push(
-                    redefined_definition,
-                    DiagnosticData::UniqueVariable {
-                        name: variable.name.clone(),
-                        original_definition,
-                        redefined_definition,
-                    },
-                );
-            }
-            Entry::Vacant(entry) => {
-                entry.insert(variable);
-            }
+        seen.entry(&variable.name)
+            .or_default()
+            .push(variable.location());
+    }
+    for (name, mut locations) in seen {
+        if locations.len() < 2 {
+            continue;
         }
+        let original_definition = locations.remove(0);
+        diagnostics.push(
+            locations[0],
+            DiagnosticData::UniqueVariable {
+                name: name.clone(),
+                original_definition,
+                redefined_definitions: locations,
+            },
+        );
     }
 }
 
 fn walk_selections<'doc>(
     document: &'doc ExecutableDocument,
     selections: &'doc executable::SelectionSet,
+    fragment_nesting_limit: usize,
     mut f: impl FnMut(&'doc executable::Selection),
 ) -> Result<(), RecursionLimitError> {
     fn walk_selections_inner<'doc, 'guard>(
@@ -121,7 +137,7 @@ fn walk_selections<'doc>(
         Ok(())
     }
 
-    let mut stack = RecursionStack::new().with_limit(100);
+    let mut stack = RecursionStack::new().with_limit(fragment_nesting_limit);
     let result = walk_selections_inner(document, selections, &mut stack.guard(), &mut f);
     result
 }
@@ -155,6 +171,50 @@ fn variables_in_directives(
         .flat_map(|directive| variables_in_arguments(&directive.arguments))
 }
 
+/// Returns whether a fragment refers to any variable, directly or through nested spreads.
+///
+/// Fragments that do not can be validated once, independently of the operations using them.
+pub(crate) fn fragment_uses_variables(
+    document: &ExecutableDocument,
+    fragment: &executable::Fragment,
+) -> bool {
+    if variables_in_directives(&fragment.directives)
+        .next()
+        .is_some()
+    {
+        return true;
+    }
+    let mut uses_variables = false;
+    let limit = ValidationLimits::default().fragment_nesting;
+    let walked = walk_selections(document, &fragment.selection_set, limit, |selection| {
+        let directives = match selection {
+            executable::Selection::Field(field) => {
+                if variables_in_arguments(&field.arguments).next().is_some() {
+                    uses_variables = true;
+                }
+                &field.directives
+            }
+            executable::Selection::FragmentSpread(spread) => {
+                if let Some(fragment_def) = document.fragments.get(&spread.fragment_name) {
+                    if variables_in_directives(&fragment_def.directives)
+                        .next()
+                        .is_some()
+                    {
+                        uses_variables = true;
+                    }
+                }
+                &spread.directives
+            }
+            executable::Selection::InlineFragment(inline) => &inline.directives,
+        };
+        if variables_in_directives(directives).next().is_some() {
+            uses_variables = true;
+        }
+    });
+    // Err on the side of validating too much if the fragment could not be fully walked.
+    uses_variables || walked.is_err()
+}
+
 // TODO add test:
 // should NOT report a unused variable warning
 // query ($var1: Boolean!, $var2: Boolean!) {
@@ -165,6 +225,7 @@ pub(crate) fn validate_unused_variables(
     diagnostics: &mut DiagnosticList,
     document: &ExecutableDocument,
     operation: &executable::Operation,
+    fragment_nesting_limit: usize,
 ) {
     // Start off by considering all variables unused: names are removed from this as we find them.
     let mut unused_vars: HashMap<_, _> = operation
@@ -186,6 +247,7 @@ pub(crate) fn validate_unused_variables(
     let walked = walk_selections(
         document,
         &operation.selection_set,
+        fragment_nesting_limit,
         |selection| match selection {
             executable::Selection::Field(field) => {
                 for used in variables_in_directives(&field.directives) {
@@ -227,6 +289,78 @@ pub(crate) fn validate_unused_variables(
     }
 }
 
+/// Report variables that `operation` uses but does not define,
+/// for validation without a schema where argument values are not otherwise validated.
+pub(crate) fn validate_undefined_variables_without_schema(
+    diagnostics: &mut DiagnosticList,
+    document: &ExecutableDocument,
+    operation: &executable::Operation,
+    fragment_nesting_limit: usize,
+) {
+    let variables = &operation.variables;
+    undefined_variables_in_directives(diagnostics, variables, &operation.directives);
+    // A recursion error is reported by `validate_unused_variables`
+    let _ = walk_selections(
+        document,
+        &operation.selection_set,
+        fragment_nesting_limit,
+        |selection| match selection {
+            executable::Selection::Field(field) => {
+                undefined_variables_in_arguments(diagnostics, variables, &field.arguments);
+                undefined_variables_in_directives(diagnostics, variables, &field.directives);
+            }
+            executable::Selection::FragmentSpread(spread) => {
+                if let Some(fragment_def) = document.fragments.get(&spread.fragment_name) {
+                    undefined_variables_in_directives(
+                        diagnostics,
+                        variables,
+                        &fragment_def.directives,
+                    );
+                }
+                undefined_variables_in_directives(diagnostics, variables, &spread.directives);
+            }
+            executable::Selection::InlineFragment(inline) => {
+                undefined_variables_in_directives(diagnostics, variables, &inline.directives);
+            }
+        },
+    );
+}
+
+fn undefined_variables_in_directives(
+    diagnostics: &mut DiagnosticList,
+    variables: &[Node<ast::VariableDefinition>],
+    directives: &ast::DirectiveList,
+) {
+    for directive in directives {
+        // Arguments of built-in directives are validated like with a schema
+        if !super::directive::is_built_in_directive(&directive.name) {
+            undefined_variables_in_arguments(diagnostics, variables, &directive.arguments);
+        }
+    }
+}
+
+fn undefined_variables_in_arguments(
+    diagnostics: &mut DiagnosticList,
+    variables: &[Node<ast::VariableDefinition>],
+    arguments: &[Node<ast::Argument>],
+) {
+    let mut values: Vec<&Node<ast::Value>> =
+        arguments.iter().map(|argument| &argument.value).collect();
+    while let Some(value) = values.pop() {
+        match value.as_ref() {
+            ast::Value::Variable(name) if !variables.iter().any(|def| def.name == *name) => {
+                diagnostics.push(
+                    value.location(),
+                    DiagnosticData::UndefinedVariable { name: name.clone() },
+                );
+            }
+            ast::Value::List(items) => values.extend(items),
+            ast::Value::Object(fields) => values.extend(fields.iter().map(|(_, value)| value)),
+            _ => {}
+        }
+    }
+}
+
 pub(crate) fn validate_variable_usage(
     diagnostics: &mut DiagnosticList,
     var_usage: &Node<ast::InputValueDefinition>,
@@ -238,7 +372,11 @@ pub(crate) fn validate_variable_usage(
         // variable_name defined within operation.
         let var_def = var_defs.iter().find(|v| v.name == *var_name);
         if let Some(var_def) = var_def {
-            let is_allowed = is_variable_usage_allowed(var_def, var_usage);
+            let is_allowed = is_variable_usage_allowed(
+                var_def,
+                &var_usage.ty,
+                var_usage.default_value.is_some(),
+            );
             if !is_allowed {
                 diagnostics.push(
                     argument.location(),
@@ -263,14 +401,14 @@ pub(crate) fn validate_variable_usage(
 
 fn is_variable_usage_allowed(
     variable_def: &ast::VariableDefinition,
-    variable_usage: &ast::InputValueDefinition,
+    location_ty: &ast::Type,
+    has_location_default_value: bool,
 ) -> bool {
     // 1. Let variable_ty be the expected type of variable_def.
     let variable_ty = &variable_def.ty;
     // 2. Let location_ty be the expected type of the Argument,
     // ObjectField, or ListValue entry where variableUsage is
     // located.
-    let location_ty = &variable_usage.ty;
     // 3. if location_ty is a non-null type AND variable_ty is
     // NOT a non-null type:
     if location_ty.is_non_null() && !variable_ty.is_non_null() {
@@ -281,7 +419,6 @@ fn is_variable_usage_allowed(
         // 3.b. Let hasLocationDefaultValue be true if a default
         // value exists for the Argument or ObjectField where
         // variableUsage is located.
-        let has_location_default_value = variable_usage.default_value.is_some();
         // 3.c. If hasNonNullVariableDefaultValue is NOT true
         // AND hasLocationDefaultValue is NOT true, return
         // false.
@@ -291,8 +428,230 @@ fn is_variable_usage_allowed(
 
         // 3.d. Let nullable_location_ty be the unwrapped
         // nullable type of location_ty.
-        return variable_ty.is_assignable_to(&location_ty.as_ref().clone().nullable());
+        return variable_ty.is_assignable_to(&location_ty.clone().nullable());
     }
 
     variable_ty.is_assignable_to(location_ty)
 }
+
+/// The result of [`analyze_variable_usages`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariableUsageReport {
+    /// Each variable defined by the operation, in definition order
+    pub variables: Vec<VariableUsages>,
+}
+
+/// A variable defined by an operation and where it is used, in a [`VariableUsageReport`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariableUsages {
+    /// The definition of this variable in the operation
+    pub definition: Node<ast::VariableDefinition>,
+    /// Each place where the operation or fragments it spreads use this variable,
+    /// in document order. Empty if the variable is unused.
+    pub usages: Vec<VariableUsage>,
+}
+
+/// A use of a variable as an argument, input object field, or list item value
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariableUsage {
+    /// The location of the variable reference `$name`
+    pub location: Option<SourceSpan>,
+    /// The field argument, directive argument, or input object field
+    /// where the variable is used, possibly through list items
+    pub coordinate: SchemaCoordinate,
+    /// The type expected at this location.
+    /// For a list item, this is the item type of the argument or input field.
+    pub expected_type: ast::Type,
+    /// Whether the argument or input field has a default value
+    pub has_location_default: bool,
+    /// Whether the variable type is [allowed][spec] at this location,
+    /// taking nullability and default values into account
+    ///
+    /// [spec]: https://spec.graphql.org/October2021/#IsVariableUsageAllowed()
+    pub is_allowed: bool,
+}
+
+impl VariableUsages {
+    /// Returns whether a nullable variable is used where a non-null value is expected.
+    /// Such a usage is only allowed thanks to a default value,
+    /// and an explicit `null` from the client is rejected during execution.
+    pub fn relies_on_default_value(&self) -> bool {
+        !self.definition.ty.is_non_null()
+            && self
+                .usages
+                .iter()
+                .any(|usage| usage.expected_type.is_non_null())
+    }
+}
+
+/// Find every variable of an operation and where it is used, with the type expected there.
+///
+/// This is the analysis behind the validation of [variable usages][spec]:
+/// usages that are not [allowed][VariableUsage::is_allowed] are
+/// reported by validation as `DisallowedVariableUsage` errors.
+/// The document does not need to be valid. Undefined fields, arguments,
+/// and directives are skipped, as are usages of variables that the operation does not define.
+///
+/// Fragments spread more than once are only walked the first time.
+///
+/// [spec]: https://spec.graphql.org/October2021/#sec-All-Variable-Usages-are-Allowed
+pub fn analyze_variable_usages(
+    schema: &Valid<Schema>,
+    document: &ExecutableDocument,
+    operation: &executable::Operation,
+) -> VariableUsageReport {
+    let mut analyzer = UsageAnalyzer {
+        schema,
+        document,
+        seen_fragments: HashSet::default(),
+        variables: operation
+            .variables
+            .iter()
+            .map(|definition| VariableUsages {
+                definition: definition.clone(),
+                usages: Vec::new(),
+            })
+            .collect(),
+    };
+    analyzer.directives(&operation.directives);
+    analyzer.selection_set(&operation.selection_set);
+    VariableUsageReport {
+        variables: analyzer.variables,
+    }
+}
+
+struct UsageAnalyzer<'a> {
+    schema: &'a Schema,
+    document: &'a ExecutableDocument,
+    seen_fragments: HashSet<&'a Name>,
+    variables: Vec<VariableUsages>,
+}
+
+impl<'a> UsageAnalyzer<'a> {
+    fn selection_set(&mut self, selection_set: &'a executable::SelectionSet) {
+        for selection in &selection_set.selections {
+            match selection {
+                executable::Selection::Field(field) => {
+                    self.directives(&field.directives);
+                    for argument in &field.arguments {
+                        let Some(definition) = field.definition.argument_by_name(&argument.name)
+                        else {
+                            continue;
+                        };
+                        let coordinate = SchemaCoordinate::FieldArgument(FieldArgumentCoordinate {
+                            ty: selection_set.ty.clone(),
+                            field: field.name.clone(),
+                            argument: argument.name.clone(),
+                        });
+                        self.value(
+                            &argument.value,
+                            &definition.ty,
+                            definition.default_value.is_some(),
+                            &coordinate,
+                        );
+                    }
+                    self.selection_set(&field.selection_set);
+                }
+                executable::Selection::FragmentSpread(spread) => {
+                    self.directives(&spread.directives);
+                    // Also prevents following fragment cycles
+                    if !self.seen_fragments.insert(&spread.fragment_name) {
+                        continue;
+                    }
+                    if let Some(fragment) = self.document.fragments.get(&spread.fragment_name) {
+                        self.directives(&fragment.directives);
+                        self.selection_set(&fragment.selection_set);
+                    }
+                }
+                executable::Selection::InlineFragment(inline) => {
+                    self.directives(&inline.directives);
+                    self.selection_set(&inline.selection_set);
+                }
+            }
+        }
+    }
+
+    fn directives(&mut self, directives: &ast::DirectiveList) {
+        for directive in directives {
+            let Some(definition) = self.schema.directive_definitions.get(&directive.name) else {
+                continue;
+            };
+            for argument in &directive.arguments {
+                let Some(argument_def) = definition.argument_by_name(&argument.name) else {
+                    continue;
+                };
+                let coordinate = SchemaCoordinate::DirectiveArgument(DirectiveArgumentCoordinate {
+                    directive: directive.name.clone(),
+                    argument: argument.name.clone(),
+                });
+                self.value(
+                    &argument.value,
+                    &argument_def.ty,
+                    argument_def.default_value.is_some(),
+                    &coordinate,
+                );
+            }
+        }
+    }
+
+    fn value(
+        &mut self,
+        value: &Node<ast::Value>,
+        expected_type: &ast::Type,
+        has_location_default: bool,
+        coordinate: &SchemaCoordinate,
+    ) {
+        match value.as_ref() {
+            ast::Value::Variable(name) => {
+                let Some(variable) = self
+                    .variables
+                    .iter_mut()
+                    .find(|variable| variable.definition.name == *name)
+                else {
+                    return;
+                };
+                let is_allowed = is_variable_usage_allowed(
+                    &variable.definition,
+                    expected_type,
+                    has_location_default,
+                );
+                variable.usages.push(VariableUsage {
+                    location: value.location(),
+                    coordinate: coordinate.clone(),
+                    expected_type: expected_type.clone(),
+                    has_location_default,
+                    is_allowed,
+                });
+            }
+            ast::Value::List(items) => {
+                for item in items {
+                    self.value(item, expected_type.item_type(), false, coordinate);
+                }
+            }
+            ast::Value::Object(fields) => {
+                let Some(input_object) = self
+                    .schema
+                    .get_input_object(expected_type.inner_named_type())
+                else {
+                    return;
+                };
+                for (name, field_value) in fields {
+                    let Some(field_def) = input_object.fields.get(name) else {
+                        continue;
+                    };
+                    let coordinate = SchemaCoordinate::TypeAttribute(TypeAttributeCoordinate {
+                        ty: input_object.name.clone(),
+                        attribute: name.clone(),
+                    });
+                    self.value(
+                        field_value,
+                        &field_def.ty,
+                        field_def.default_value.is_some(),
+                        &coordinate,
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use apollo_compiler::schema;
+use apollo_compiler::validation::Valid;
+use apollo_compiler::validation::ValidationErrorKind;
+use apollo_compiler::Schema;
+use expect_test::expect;
+
+const FEDERATION_2: &str = r#"
+extend schema @link(url: "https://specs.apollo.dev/federation/v2.3", import: ["@key", "@shareable", "@external", "@inaccessible"])
+directive @link(url: String!, import: [String]) repeatable on SCHEMA
+directive @key(fields: String!, resolvable: Boolean = true) repeatable on OBJECT | INTERFACE
+directive @shareable repeatable on OBJECT | FIELD_DEFINITION
+directive @external on OBJECT | FIELD_DEFINITION
+directive @inaccessible on FIELD_DEFINITION | OBJECT | INTERFACE | UNION | ARGUMENT_DEFINITION | SCALAR | ENUM | ENUM_VALUE | INPUT_OBJECT | INPUT_FIELD_DEFINITION
+"#;
+
+fn subgraph(name: &str, sdl: &str) -> Valid<Schema> {
+    Schema::parse_and_validate(format!("{FEDERATION_2}{sdl}"), format!("{name}.graphql")).unwrap()
+}
+
+#[test]
+fn compose_subgraphs() {
+    let products = subgraph(
+        "products",
+        r#"
+        type Query { products: [Product!]! }
+        type Product @key(fields: "upc") {
+          upc: String!
+          "The product name"
+          name: String! @shareable
+          price: Int
+          internal: String @inaccessible
+        }
+        "#,
+    );
+    let reviews = subgraph(
+        "reviews",
+        r#"
+        type Query { reviews(first: Int = 10): [Review] }
+        type Review { body: String! product: Product }
+        type Product @key(fields: "upc") {
+          upc: String!
+          name: String @shareable @deprecated
+          price: Int @external
+          reviews: [Review!]
+        }
+        "#,
+    );
+    let composed = schema::compose([("products", &products), ("reviews", &reviews)]).unwrap();
+    let expected = expect![[r#"
+        type Query {
+          products: [Product!]!
+          reviews(first: Int = 10): [Review]
+        }
+
+        type Product {
+          upc: String!
+          """The product name"""
+          name: String @deprecated
+          price: Int
+          reviews: [Review!]
+        }
+
+        type Review {
+          body: String!
+          product: Product
+        }
+    "#]];
+    expected.assert_eq(&composed.to_string());
+}
+
+#[test]
+fn api_schema_and_root_types() {
+    let a = Schema::parse_and_validate(
+        r#"
+        schema { query: RootQuery }
+        directive @inaccessible on OBJECT | ENUM_VALUE | ARGUMENT_DEFINITION
+        directive @tracked on FIELD
+        directive @private on FIELD
+        type RootQuery { a(hidden: Int @inaccessible): Int e: E _service: _Service! }
+        type _Service { sdl: String }
+        type Hidden @inaccessible { a: Int }
+        enum E { A B @inaccessible }
+        "#,
+        "a.graphql",
+    )
+    .unwrap();
+    let b = Schema::parse_and_validate(
+        r#"
+        directive @tracked on FIELD
+        type Query { b(e: E): Int }
+        enum E { A C }
+        "#,
+        "b.graphql",
+    )
+    .unwrap();
+    let composed = schema::compose([("a", &a), ("b", &b)]).unwrap();
+    let expected = expect![[r#"
+        directive @tracked on FIELD
+
+        type Query {
+          a: Int
+          e: E
+          b(e: E): Int
+        }
+
+        enum E {
+          A
+          C
+        }
+    "#]];
+    expected.assert_eq(&composed.to_string());
+}
+
+#[test]
+fn composition_errors() {
+    let products = subgraph(
+        "products",
+        r#"
+        type Query { products: [Product!]! }
+        type Product @key(fields: "upc") { upc: String! name: String price: Int }
+        interface Node { id: ID! }
+        "#,
+    );
+    let reviews = subgraph(
+        "reviews",
+        r#"
+        type Query { reviews: [Review] }
+        type Review { body: String }
+        type Product @key(fields: "upc", resolvable: false) {
+          upc: String!
+          name: String @shareable
+          price: String
+          reviews: [Review]
+        }
+        type Node { id: ID! }
+        "#,
+    );
+    let errors = schema::compose([("products", &products), ("reviews", &reviews)])
+        .unwrap_err()
+        .errors;
+    let messages: Vec<_> = errors.iter().map(|error| error.error.to_string()).collect();
+    for expected in [
+        "`Node` is an interface type in subgraph `products` but an object type in subgraph `reviews`",
+        "field `Product.price` has type `Int` in subgraph `products` but `String` in subgraph `reviews`",
+        "field `Product.name` is resolved by subgraphs `products`, `reviews`, \
+         but is not `@shareable` in subgraph `products`",
+        "field `Product.price` is resolved by subgraphs `products`, `reviews`, \
+         but is not `@shareable` in subgraph `reviews`",
+        "field `Product.reviews` can only be resolved by subgraphs `reviews`, \
+         which have no resolvable `@key` on `Product`",
+    ] {
+        assert!(messages.iter().any(|message| message == expected), "{errors}");
+    }
+    assert_eq!(messages.len(), 6, "{errors}");
+    assert!(errors
+        .iter()
+        .all(|error| error.error.kind() == ValidationErrorKind::Composition));
+}
@@ -0,0 +1,76 @@
+use apollo_compiler::execution::estimate_cost;
+use apollo_compiler::execution::CostConfig;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+
+const SCHEMA: &str = r#"
+  directive @cost(weight: String!) on FIELD_DEFINITION | OBJECT | SCALAR
+  directive @listSize(assumedSize: Int, slicingArguments: [String!]) on FIELD_DEFINITION
+
+  type Query {
+    me: User
+    users(first: Int): [User] @listSize(slicingArguments: ["first"])
+  }
+
+  type User {
+    name: String
+    avatar: String @cost(weight: "3")
+    friends: [User] @listSize(assumedSize: 5)
+  }
+"#;
+
+const QUERY: &str = r#"
+  {
+    me { name avatar }
+    users(first: 2) { ...Friends }
+  }
+  fragment Friends on User { friends { name } }
+"#;
+
+fn estimate(config: &CostConfig) -> (f64, Vec<(String, f64)>) {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let doc = ExecutableDocument::parse_and_validate(&schema, QUERY, "query.graphql").unwrap();
+    let operation = doc.operations.get(None).unwrap();
+    let estimate = estimate_cost(&schema, &doc, operation, config);
+    let fields = estimate
+        .fields
+        .iter()
+        .map(|field| {
+            let path: Vec<_> = field.path.iter().map(|key| key.as_str()).collect();
+            (path.join("."), field.cost)
+        })
+        .collect();
+    (estimate.total, fields)
+}
+
+#[test]
+fn default_weights() {
+    let (total, fields) = estimate(&CostConfig::new());
+    // Lists are assumed to have 10 items, objects weigh 1 and scalars 0
+    assert_eq!(total, 1.0 + (1.0 + 10.0) * 10.0);
+    assert_eq!(
+        fields,
+        [
+            ("me".to_owned(), 1.0),
+            ("me.name".to_owned(), 0.0),
+            ("me.avatar".to_owned(), 0.0),
+            ("users".to_owned(), 110.0),
+            ("users.friends".to_owned(), 10.0),
+            ("users.friends.name".to_owned(), 0.0),
+        ]
+    );
+}
+
+#[test]
+fn cost_directives() {
+    let config = CostConfig::new().cost_directives(true);
+    let (total, fields) = estimate(&config);
+    // `users` is sliced to 2 items, each with 5 friends
+    assert_eq!(total, (1.0 + 3.0) + (1.0 + 5.0) * 2.0);
+    assert_eq!(fields[2], ("me.avatar".to_owned(), 3.0));
+    assert_eq!(fields[4], ("users.friends".to_owned(), 5.0));
+
+    let config = config.field_weight("Query.me".parse().unwrap(), 0.0);
+    let (total, _) = estimate(&config);
+    assert_eq!(total, 3.0 + 12.0);
+}
@@ -1,5 +1,13 @@
+use apollo_compiler::ast::Argument;
+use apollo_compiler::ast::Directive;
+use apollo_compiler::ast::OperationType;
+use apollo_compiler::ast::Value;
+use apollo_compiler::executable;
+use apollo_compiler::name;
 use apollo_compiler::parse_mixed_validate;
+use apollo_compiler::ty;
 use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Node;
 use apollo_compiler::Schema;
 
 #[test]
@@ -287,3 +295,173 @@ fn iter_all_fields() {
         ["f1", "inner", "f2", "f3", "f3"]
     );
 }
+
+#[test]
+fn field_usage_report() {
+    let schema = Schema::parse_and_validate(
+        r#"
+        directive @cached(scope: Scope) on FIELD
+        enum Scope { PUBLIC PRIVATE }
+        type Query {
+          node(id: ID!): Node
+          search(filter: Filter, first: Int): [Node]
+        }
+        interface Node { id: ID! }
+        type User implements Node { id: ID! name: String email: String }
+        type Post implements Node { id: ID! title: String }
+        input Filter { kind: Kind tags: [String] }
+        enum Kind { USER POST }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let web = ExecutableDocument::parse_and_validate(
+        &schema,
+        r#"
+        query Profile($id: ID!) {
+          node(id: $id) { ...NodeFields ... on User { name ...NodeFields } }
+        }
+        query Search {
+          search(filter: { kind: USER }) @cached(scope: PUBLIC) { ...NodeFields }
+        }
+        fragment NodeFields on Node { id }
+        "#,
+        "web.graphql",
+    )
+    .unwrap();
+    let mobile =
+        ExecutableDocument::parse_and_validate(&schema, "{ node(id: 1) { id } }", "mobile.graphql")
+            .unwrap();
+    let report = executable::field_usage(&schema, [("web", &web), ("mobile", &mobile)]);
+
+    let fields: Vec<_> = report
+        .fields
+        .iter()
+        .map(|(coordinate, usage)| {
+            let operations: Vec<_> = usage.operations.iter().map(|op| op.to_string()).collect();
+            format!("{coordinate} x{}: {}", usage.count, operations.join(", "))
+        })
+        .collect();
+    assert_eq!(
+        fields,
+        [
+            "Query.node x2: web#Profile, mobile",
+            "Node.id x3: web#Profile, web#Search, mobile",
+            "User.id x3: web#Profile, web#Search, mobile",
+            "Post.id x3: web#Profile, web#Search, mobile",
+            "User.name x1: web#Profile",
+            "Query.search x1: web#Search",
+            "Filter.kind x1: web#Search",
+        ]
+    );
+    let arguments: Vec<_> = report.arguments.keys().map(|c| c.to_string()).collect();
+    assert_eq!(
+        arguments,
+        [
+            "Query.node(id:)",
+            "Query.search(filter:)",
+            "@cached(scope:)"
+        ]
+    );
+    let enum_values: Vec<_> = report.enum_values.keys().map(|c| c.to_string()).collect();
+    assert_eq!(enum_values, ["Kind.USER", "Scope.PUBLIC"]);
+
+    let unused: Vec<_> = report
+        .unused_fields(&schema)
+        .map(|c| c.to_string())
+        .collect();
+    assert_eq!(unused, ["User.email", "Post.title", "Filter.tags"]);
+}
+
+#[test]
+fn build_operations_fluently() {
+    let schema = Schema::parse_and_validate(
+        r#"
+        directive @include(if: Boolean!) on FIELD
+        type Query { search(term: String!, first: Int = 10): [Result] }
+        type Mutation { like(id: ID!): Int }
+        union Result = Book | Author
+        type Book { id: ID! title: String }
+        type Author { id: ID! name: String }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let builder = ExecutableDocument::builder()
+        .operation(OperationType::Query, Some(name!("Search")), |op| {
+            op.variable(name!("term"), ty!(String!))
+                .variable_with_default(name!("withNames"), ty!(Boolean!), true)
+                .field_with(name!("search"), |field| {
+                    field
+                        .alias(name!("results"))
+                        .argument(name!("term"), Value::Variable(name!("term")))
+                        .argument(name!("first"), 5)
+                        .inline_fragment(Some(name!("Book")), |fragment| {
+                            fragment.field(name!("id")).field(name!("title"))
+                        })
+                        .inline_fragment(Some(name!("Author")), |fragment| {
+                            fragment.fragment_spread(name!("AuthorName"))
+                        })
+                })
+        })
+        .operation(OperationType::Mutation, Some(name!("Like")), |op| {
+            op.field_with(name!("like"), |field| field.argument(name!("id"), "1"))
+        })
+        .fragment(name!("AuthorName"), name!("Author"), |fragment| {
+            fragment.field_with(name!("name"), |field| {
+                field.directive(Directive {
+                    name: name!("include"),
+                    arguments: vec![Node::new(Argument {
+                        name: name!("if"),
+                        value: Value::Variable(name!("withNames")).into(),
+                    })],
+                })
+            })
+        });
+    let expected = expect_test::expect![[r#"
+        query Search($term: String!, $withNames: Boolean! = true) {
+          results: search(term: $term, first: 5) {
+            ... on Book {
+              id
+              title
+            }
+            ... on Author {
+              ...AuthorName
+            }
+          }
+        }
+
+        mutation Like {
+          like(id: "1")
+        }
+
+        fragment AuthorName on Author {
+          name @include(if: $withNames)
+        }
+    "#]];
+    expected.assert_eq(&builder.to_ast().to_string());
+    let document = builder.build(&schema).unwrap();
+    expected.assert_eq(&document.to_string());
+
+    // Fields are resolved against the schema when building
+    let err = ExecutableDocument::builder()
+        .operation(OperationType::Query, None, |op| op.field(name!("serach")))
+        .build(&schema)
+        .unwrap_err();
+    let err = err.errors.to_string();
+    assert!(
+        err.contains("type `Query` does not have a field `serach`"),
+        "{err}"
+    );
+
+    // And the document is validated
+    let err = ExecutableDocument::builder()
+        .operation(OperationType::Query, None, |op| op.field(name!("search")))
+        .build(&schema)
+        .unwrap_err();
+    let err = err.errors.to_string();
+    assert!(
+        err.contains("the required argument `Query.search(term:)` is not provided"),
+        "{err}"
+    );
+}
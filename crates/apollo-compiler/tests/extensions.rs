@@ -1,3 +1,5 @@
+use apollo_compiler::schema::ExtendedType;
+use apollo_compiler::Node;
 use apollo_compiler::Schema;
 
 #[test]
@@ -164,3 +166,66 @@ fn test_extend_implicit_schema() {
     let schema = Schema::parse_and_validate(input, "schema.graphql").unwrap();
     assert!(schema.schema_definition.directives.has("dir"));
 }
+
+#[test]
+fn test_to_document_preserves_source_order() {
+    let first = r#"extend type Query {
+  b: Date @tag(name: "b")
+}
+
+type Query {
+  a: Int
+}
+
+extend schema @tag(name: "first")
+
+scalar Date
+
+directive @tag(name: String) repeatable on SCHEMA | OBJECT | FIELD_DEFINITION
+"#;
+    let second = r#"extend type Query @tag(name: "second") {
+  c: Int
+}
+
+extend scalar Date @tag(name: "date")
+"#;
+    let schema = Schema::builder()
+        .adopt_orphan_extensions()
+        .parse(first, "first.graphql")
+        .parse(second, "second.graphql")
+        .build()
+        .unwrap();
+    let document = schema.to_document();
+    // Sources are those of the schema, so that every location in the document resolves
+    let paths: Vec<_> = document
+        .sources
+        .values()
+        .map(|source| source.path().to_str().unwrap())
+        .collect();
+    assert_eq!(
+        paths,
+        ["built_in.graphql", "first.graphql", "second.graphql"]
+    );
+    assert_eq!(document.to_string(), format!("{first}\n{second}"));
+
+    // Parsing the output again gives the same document
+    let reparsed = Schema::builder()
+        .adopt_orphan_extensions()
+        .parse(document.to_string(), "formatted.graphql")
+        .build()
+        .unwrap();
+    assert_eq!(reparsed.to_document().to_string(), document.to_string());
+
+    // Programmatic definitions keep their position in schema order
+    let mut schema = schema;
+    let url = apollo_compiler::schema::ScalarType {
+        description: None,
+        name: apollo_compiler::name!("Url"),
+        directives: Default::default(),
+    };
+    schema
+        .types
+        .insert(url.name.clone(), ExtendedType::Scalar(Node::new(url)));
+    let printed = schema.to_document().to_string();
+    assert!(printed.ends_with("extend scalar Date @tag(name: \"date\")\n\nscalar Url\n"));
+}
@@ -0,0 +1,217 @@
+use apollo_compiler::ast::Type;
+use apollo_compiler::execution::coerce_variable_values;
+use apollo_compiler::execution::serde_json_bytes::json;
+use apollo_compiler::execution::JsonValue;
+use apollo_compiler::ty;
+use apollo_compiler::validation::coerce_const_value;
+use apollo_compiler::validation::effective_default_value;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Node;
+use apollo_compiler::Schema;
+
+const SCHEMA: &str = r#"
+  type Query {
+    search(filter: Filter, ids: [ID!], scale: Float): [String]
+  }
+
+  input Filter {
+    text: String!
+    limit: Int = 10
+    order: Order = ASC
+  }
+
+  enum Order { ASC DESC }
+"#;
+
+fn coerce(query: &str, variables: JsonValue) -> Result<JsonValue, Vec<String>> {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let document = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let JsonValue::Object(variables) = variables else {
+        panic!("expected an object")
+    };
+    coerce_variable_values(&schema, operation, &variables)
+        .map(|values| JsonValue::Object(values.into_inner()))
+        .map_err(|errors| {
+            errors
+                .into_graphql_errors(&document.sources)
+                .into_iter()
+                .map(|error| {
+                    let locations: Vec<_> = error
+                        .locations
+                        .iter()
+                        .map(|loc| format!("{}:{}", loc.line, loc.column))
+                        .collect();
+                    format!("{} at {}", error.message, locations.join(", "))
+                })
+                .collect()
+        })
+}
+
+#[test]
+fn applies_defaults_and_list_coercion() {
+    let query = r#"
+      query($filter: Filter = { text: "a" }, $ids: [ID!], $scale: Float) {
+        search(filter: $filter, ids: $ids, scale: $scale)
+      }
+    "#;
+    assert_eq!(
+        coerce(query, json!({ "ids": 4, "scale": 2 })).unwrap(),
+        json!({
+            "ids": ["4"],
+            "scale": 2,
+            "filter": { "text": "a", "limit": 10, "order": "ASC" },
+        })
+    );
+    assert_eq!(
+        coerce(query, json!({ "filter": { "text": "b", "order": "DESC" } })).unwrap(),
+        json!({ "filter": { "text": "b", "order": "DESC", "limit": 10 } })
+    );
+}
+
+#[test]
+fn errors_point_to_the_variable_definition() {
+    let query = "query($ids: [ID!], $filter: Filter) { search(ids: $ids, filter: $filter) }";
+    assert_eq!(
+        coerce(query, json!({ "ids": [1, null] })).unwrap_err(),
+        ["null value for variable ids of non-null type ID! (at $ids[1]) at 1:7"]
+    );
+    assert_eq!(
+        coerce(query, json!({ "filter": { "limit": 1 } })).unwrap_err(),
+        ["Missing value for non-null input object field Filter.text (at $filter.text) at 1:20"]
+    );
+}
+
+#[test]
+fn reports_every_error() {
+    let query = "query($ids: [ID!], $filter: Filter!, $scale: Float) {
+      search(ids: $ids, filter: $filter, scale: $scale)
+    }";
+    assert_eq!(
+        coerce(
+            query,
+            json!({
+                "ids": [null, "a", true],
+                "filter": { "text": 1, "order": "UP", "extra": 0 },
+                "scale": "big",
+            })
+        )
+        .unwrap_err(),
+        [
+            "null value for variable ids of non-null type ID! (at $ids[0]) at 1:7",
+            "Could not coerce variable ids: true to type ID (at $ids[2]) at 1:7",
+            "Input object has key extra not in type Filter at 1:20",
+            "Could not coerce input field Filter.text: 1 to type String (at $filter.text) at 1:20",
+            "Could not coerce input field Filter.order: \"UP\" to type Order (at $filter.order) at 1:20",
+            "Could not coerce variable scale: \"big\" to type Float at 1:38",
+        ]
+    );
+    assert_eq!(
+        coerce(query, json!({})).unwrap_err(),
+        ["missing value for non-null variable 'filter' at 1:20"]
+    );
+}
+
+/// Behavior change: integer values of `ID` variables used to be passed through as numbers
+#[test]
+fn integer_ids_are_coerced_to_strings() {
+    let query = "query($ids: [ID!]) { search(ids: $ids) }";
+    assert_eq!(
+        coerce(query, json!({ "ids": [7, -7, i64::MAX, u64::MAX, "8"] })).unwrap(),
+        json!({
+            "ids": ["7", "-7", "9223372036854775807", "18446744073709551615", "8"]
+        })
+    );
+    assert_eq!(
+        coerce(query, json!({ "ids": [1.5] })).unwrap_err(),
+        ["Could not coerce variable ids: 1.5 to type ID (at $ids[0]) at 1:7"]
+    );
+}
+
+#[test]
+fn coerce_const_values() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let coerce = |ty: Type, value: &str| {
+        let document = ExecutableDocument::parse(
+            &schema,
+            format!("{{ search(scale: {value}) }}"),
+            "value.graphql",
+        )
+        .unwrap();
+        let operation = document.operations.get(None).unwrap();
+        let field = operation.selection_set.fields().next().unwrap();
+        coerce_const_value(&schema, &Node::new(ty), &field.arguments[0].value)
+            .map(|value| value.serialize().no_indent().to_string())
+            .map_err(|errors| {
+                errors
+                    .iter()
+                    .map(|d| d.error.to_string())
+                    .collect::<Vec<_>>()
+            })
+    };
+    assert_eq!(
+        coerce(ty!([Filter!]), r#"{ text: "a", order: DESC }"#).unwrap(),
+        r#"[{text: "a", limit: 10, order: DESC}]"#
+    );
+    assert_eq!(coerce(ty!(Float), "1").unwrap(), "1.0");
+    assert_eq!(coerce(ty!([ID]), "[1, \"2\"]").unwrap(), r#"["1", "2"]"#);
+    assert_eq!(
+        coerce(ty!(Filter), "{ order: UP }").unwrap_err(),
+        [
+            "the required field `Filter.text` is not provided",
+            "value `UP` does not exist on `Order`",
+        ]
+    );
+    assert_eq!(
+        coerce(ty!(Nope), "1").unwrap_err(),
+        ["cannot find type `Nope` in this document"]
+    );
+}
+
+#[test]
+fn nested_defaults() {
+    let schema = Schema::parse_and_validate(
+        r#"
+        type Query { list(page: Page = { size: 20 }, ids: [ID] = 1): [String] }
+        input Page { size: Int!, sort: Sort = {} }
+        input Sort { by: String = "name", descending: Boolean = false }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let field = schema.type_field("Query", "list").unwrap();
+    let default = |name: &str| {
+        let argument = field.argument_by_name(name).unwrap();
+        effective_default_value(&schema, argument)
+            .unwrap()
+            .serialize()
+            .no_indent()
+            .to_string()
+    };
+    assert_eq!(
+        default("page"),
+        r#"{size: 20, sort: {by: "name", descending: false}}"#
+    );
+    assert_eq!(default("ids"), r#"["1"]"#);
+    let sort = &schema.get_input_object("Page").unwrap().fields["sort"];
+    let sort = effective_default_value(&schema, sort).unwrap();
+    assert_eq!(
+        sort.serialize().no_indent().to_string(),
+        r#"{by: "name", descending: false}"#
+    );
+    let size = &schema.get_input_object("Page").unwrap().fields["size"];
+    assert!(effective_default_value(&schema, size).is_none());
+
+    let document =
+        ExecutableDocument::parse_and_validate(&schema, "query($p: Page) { list(page: $p) }", "")
+            .unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let JsonValue::Object(variables) = json!({ "p": { "size": 5 } }) else {
+        unreachable!()
+    };
+    let coerced = coerce_variable_values(&schema, operation, &variables).unwrap();
+    assert_eq!(
+        JsonValue::Object(coerced.into_inner()),
+        json!({ "p": { "size": 5, "sort": { "by": "name", "descending": false } } })
+    );
+}
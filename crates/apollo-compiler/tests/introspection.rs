@@ -1,4 +1,5 @@
 use apollo_compiler::execution::coerce_variable_values;
+use apollo_compiler::execution::execute_introspection_request;
 use apollo_compiler::execution::JsonMap;
 use apollo_compiler::execution::Response;
 use apollo_compiler::execution::SchemaIntrospectionQuery;
@@ -134,3 +135,273 @@ fn test() {
     );
     expect_file!("../test_data/introspection/response_full.json").assert_eq(&response);
 }
+
+#[test]
+fn introspection_request() {
+    let schema = r#"
+        type Query {
+            id: ID!
+            int: Int! @deprecated(reason: "…")
+            url: String
+        }
+    "#;
+    let schema = Schema::parse_and_validate(schema, "schema.graphql").unwrap();
+    let query = r#"
+        query Q($deprecated: Boolean!) {
+            __typename
+            root: __schema { queryType { name } }
+            ...F
+        }
+        fragment F on Query {
+            query: __type(name: "Query") {
+                fields(includeDeprecated: $deprecated) { name }
+            }
+        }
+    "#;
+    let variables = [("deprecated".into(), false.into())].into_iter().collect();
+    let response = execute_introspection_request(&schema, query, None, &variables);
+    let expected = expect!([r#"
+        {
+          "data": {
+            "__typename": "Query",
+            "root": {
+              "queryType": {
+                "name": "Query"
+              }
+            },
+            "query": {
+              "fields": [
+                {
+                  "name": "id"
+                },
+                {
+                  "name": "url"
+                }
+              ]
+            }
+          }
+        }"#]);
+    expected.assert_eq(&serde_json::to_string_pretty(&response).unwrap());
+
+    let variables = [("deprecated".into(), true.into())].into_iter().collect();
+    let response = execute_introspection_request(&schema, query, Some("Q"), &variables);
+    let response = serde_json::to_value(&response).unwrap();
+    assert_eq!(response["data"]["query"]["fields"][1]["name"], "int");
+
+    let request_error = |query, operation_name| {
+        let response =
+            execute_introspection_request(&schema, query, operation_name, &JsonMap::new());
+        assert!(response.data.is_absent());
+        response
+            .errors
+            .iter()
+            .map(|error| error.message.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        request_error("{ __typename id }", None),
+        ["field `id` cannot be answered from the schema alone"]
+    );
+    assert_eq!(
+        request_error("query A { __typename } query B { __typename }", None),
+        ["an operation name is required when a document has multiple operations"]
+    );
+    assert_eq!(
+        request_error("{ __typename }", Some("C")),
+        ["unknown operation named `C`"]
+    );
+    // Missing variable
+    assert_eq!(request_error(query, None).len(), 1);
+    // Validation error
+    assert!(!request_error("{ __type { name } }", None).is_empty());
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn from_introspection_response() {
+    let sdl = r#"
+        "The root query"
+        type Query implements Node {
+          id: ID!
+          search(
+            "What to look for"
+            text: String! = "*"
+            limit: Int = 10
+            kinds: [Kind!] = [BOOK]
+          ): [Result!]!
+          old: Int @deprecated(reason: "use id")
+        }
+
+        type Mutation {
+          add(input: AddInput!): Book
+        }
+
+        interface Node {
+          id: ID!
+        }
+
+        interface Item implements Node {
+          id: ID!
+        }
+
+        type Book implements Item & Node {
+          id: ID!
+          title: String
+        }
+
+        union Result = Book | Query
+
+        enum Kind {
+          BOOK
+          "Discontinued"
+          MAGAZINE @deprecated(reason: "not sold anymore")
+        }
+
+        input AddInput {
+          title: String!
+          tags: [String] = []
+        }
+
+        scalar Date
+
+        directive @cached(ttl: Int = 60) on FIELD | QUERY
+    "#;
+    let schema = Schema::parse_and_validate(sdl, "schema.graphql").unwrap();
+    let query = include_str!("../test_data/introspection/introspect_full_schema.graphql");
+    let document = ExecutableDocument::parse_and_validate(&schema, query, "query.graphql").unwrap();
+    let operation = document.operations.get(None).unwrap();
+    let variables = coerce_variable_values(&schema, operation, &JsonMap::new()).unwrap();
+    let response = SchemaIntrospectionQuery::split_and_execute(
+        &schema,
+        &document,
+        operation,
+        &variables,
+        |_| unreachable!(),
+    );
+    let response = serde_json::to_value(&response).unwrap();
+    let rebuilt = Schema::from_introspection_response(&response).unwrap();
+    assert_eq!(rebuilt.to_string(), schema.to_string());
+
+    // The `data` object is accepted too
+    let rebuilt = Schema::from_introspection_response(&response["data"]).unwrap();
+    assert_eq!(rebuilt.to_string(), schema.to_string());
+
+    let errors = Schema::from_introspection_response(&serde_json::json!({ "data": null }))
+        .unwrap_err()
+        .errors;
+    assert!(
+        errors
+            .iter()
+            .any(|error| error.error.to_string()
+                == "introspection response has no `__schema` object"),
+        "{errors}"
+    );
+
+    let response = serde_json::json!({
+        "__schema": {
+            "queryType": { "name": "Query" },
+            "types": [
+                {
+                    "kind": "OBJECT",
+                    "name": "Query",
+                    "fields": [
+                        { "name": "a", "args": [], "type": { "kind": "SCALAR", "name": "Int" } },
+                        { "name": "b", "args": [], "type": { "kind": "NON_NULL" } }
+                    ]
+                },
+                { "kind": "THING", "name": "Thing" }
+            ]
+        }
+    });
+    let result = Schema::from_introspection_response(&response).unwrap_err();
+    let messages: Vec<_> = result
+        .errors
+        .iter()
+        .map(|error| error.error.to_string())
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "expected a type reference at `types[0].fields[1].type.ofType` in introspection response",
+            "unknown type kind `THING` at `types[1].kind` in introspection response",
+        ]
+    );
+    assert!(result
+        .partial
+        .get_object("Query")
+        .unwrap()
+        .fields
+        .contains_key("a"));
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn to_introspection_response() {
+    use apollo_compiler::schema::IntrospectionOptions;
+
+    /// Remove fields not selected by `introspect_full_schema.graphql`
+    fn remove_unselected(value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(object) => {
+                for key in ["specifiedByURL", "isRepeatable", "isOneOf"] {
+                    object.remove(key);
+                }
+                object.values_mut().for_each(remove_unselected)
+            }
+            serde_json::Value::Array(array) => array.iter_mut().for_each(remove_unselected),
+            _ => {}
+        }
+    }
+
+    // Same schema as `test()`, which produced `response_full.json` by execution
+    let schema = r#"
+        type Query implements I {
+            id: ID!
+            int: Int! @deprecated(reason: "…")
+            url: Url
+        }
+
+        interface I {
+            id: ID!
+        }
+
+        scalar Url @specifiedBy(url: "https://url.spec.whatwg.org/")
+    "#;
+    let schema = Schema::parse_and_validate(schema, "schema.graphql").unwrap();
+    let mut response = schema.to_introspection_response(&IntrospectionOptions::new());
+    let introspection = &response["data"]["__schema"];
+    let url = introspection["types"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|ty| ty["name"] == "Url")
+        .unwrap();
+    assert_eq!(url["specifiedByURL"], "https://url.spec.whatwg.org/");
+    assert_eq!(introspection["description"], serde_json::Value::Null);
+
+    response["data"]["__schema"]
+        .as_object_mut()
+        .unwrap()
+        .remove("description");
+    remove_unselected(&mut response);
+    let executed: serde_json::Value = serde_json::from_str(include_str!(
+        "../test_data/introspection/response_full.json"
+    ))
+    .unwrap();
+    assert_eq!(response, executed);
+
+    let options = IntrospectionOptions::new()
+        .descriptions(false)
+        .include_deprecated(false);
+    let response = schema.to_introspection_response(&options);
+    let types = response["data"]["__schema"]["types"].as_array().unwrap();
+    assert!(types.iter().all(|ty| ty["description"].is_null()));
+    let query = types.iter().find(|ty| ty["name"] == "Query").unwrap();
+    let fields: Vec<_> = query["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|field| field["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(fields, ["id", "url"]);
+}
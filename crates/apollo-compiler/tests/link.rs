@@ -0,0 +1,151 @@
+use apollo_compiler::schema::Links;
+use apollo_compiler::Schema;
+
+fn links(schema_extension: &str) -> Result<Links, String> {
+    let sdl = format!("{schema_extension}\ntype Query {{ a: Int }}");
+    let schema = Schema::parse(sdl, "schema.graphql").unwrap();
+    Links::from_schema(&schema).map_err(|e| e.errors.to_string())
+}
+
+#[test]
+fn namespaces_and_imports() {
+    let links = links(
+        r#"
+        extend schema
+          @link(url: "https://specs.apollo.dev/link/v1.0")
+          @link(url: "https://specs.apollo.dev/federation/v2.5", as: "fed", import: ["@key", "FieldSet"])
+          @link(url: "https://example.com/tools/cache/v0.1?draft#top", import: [{ name: "@cache", as: "@cached" }], for: EXECUTION)
+        "#,
+    )
+    .unwrap();
+    let summary: Vec<_> = links
+        .iter()
+        .map(|link| {
+            format!(
+                "{} {} {:?}",
+                link.identity,
+                link.version.unwrap(),
+                link.namespace.as_deref()
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            "https://specs.apollo.dev/link v1.0 Some(\"link\")",
+            "https://specs.apollo.dev/federation v2.5 Some(\"fed\")",
+            "https://example.com/tools/cache v0.1 Some(\"cache\")",
+        ]
+    );
+
+    let resolve = |name: &str| {
+        links
+            .resolve_directive(name)
+            .map(|(link, element)| format!("{}#@{element}", link.identity))
+    };
+    assert_eq!(
+        resolve("link").unwrap(),
+        "https://specs.apollo.dev/link#@link"
+    );
+    assert_eq!(
+        resolve("key").unwrap(),
+        "https://specs.apollo.dev/federation#@key"
+    );
+    assert_eq!(
+        resolve("fed__requires").unwrap(),
+        "https://specs.apollo.dev/federation#@requires"
+    );
+    assert_eq!(resolve("federation__requires"), None);
+    assert_eq!(
+        resolve("cached").unwrap(),
+        "https://example.com/tools/cache#@cache"
+    );
+    assert_eq!(resolve("deprecated"), None);
+
+    let (link, element) = links.resolve_type("FieldSet").unwrap();
+    assert_eq!(
+        (link.name.as_ref().unwrap().as_str(), element.as_str()),
+        ("federation", "FieldSet")
+    );
+    assert_eq!(links.resolve_type("link__Import").unwrap().1, "Import");
+    // Imported directives are not types
+    assert!(links.resolve_type("key").is_none());
+
+    let federation = links
+        .by_identity("https://specs.apollo.dev/federation")
+        .unwrap();
+    assert_eq!(federation.directive_name_in_schema("key").unwrap(), "key");
+    assert_eq!(
+        federation.directive_name_in_schema("shareable").unwrap(),
+        "fed__shareable"
+    );
+    assert_eq!(
+        federation.type_name_in_schema("FieldSet").unwrap(),
+        "FieldSet"
+    );
+    assert_eq!(
+        federation.type_name_in_schema("Scope").unwrap(),
+        "fed__Scope"
+    );
+    let cache = links
+        .by_identity("https://example.com/tools/cache")
+        .unwrap();
+    assert_eq!(cache.purpose.as_ref().unwrap(), "EXECUTION");
+    assert_eq!(cache.directive_name_in_schema("cache").unwrap(), "cached");
+}
+
+#[test]
+fn renamed_link_directive() {
+    let links = links(
+        r#"
+        extend schema
+          @core(url: "https://specs.apollo.dev/link/v1.0", as: "core")
+          @core(url: "https://specs.apollo.dev/federation/v2.0")
+          @link(url: "https://specs.apollo.dev/ignored/v1.0")
+        "#,
+    )
+    .unwrap();
+    let identities: Vec<_> = links.iter().map(|link| link.identity.as_str()).collect();
+    assert_eq!(
+        identities,
+        [
+            "https://specs.apollo.dev/link",
+            "https://specs.apollo.dev/federation"
+        ]
+    );
+    assert_eq!(links.resolve_directive("core").unwrap().1, "link");
+}
+
+#[test]
+fn malformed_links() {
+    let errors = links(
+        r#"
+        extend schema
+          @link(url: "specs.apollo.dev/federation/v2.0")
+          @link(url: "https:///federation/v2.0")
+          @link(url: "https://specs.apollo.dev/federation/v2")
+          @link(as: "nope")
+          @link(url: "https://specs.apollo.dev/a/v1.0", as: "not-a-name")
+          @link(url: "https://specs.apollo.dev/b/v1.0", import: ["@ok", 3, { as: "@x" }, { name: "@y", as: "z" }, "@bad name"])
+          @link(url: "https://specs.apollo.dev/c/v1.0", import: ["@ok"])
+          @link(url: "https://example.com/b/v2.0")
+        "#,
+    )
+    .unwrap_err();
+    let expected = [
+        "`specs.apollo.dev/federation/v2.0` is not a valid link URL: expected an absolute URL",
+        "`https:///federation/v2.0` is not a valid link URL: missing host",
+        "`https://specs.apollo.dev/federation/v2` is not a valid link URL: invalid version",
+        "`@link` must have a `url` string argument",
+        "`not-a-name` is not a valid namespace for the link to `https://specs.apollo.dev/a/v1.0`",
+        "invalid import in the link to `https://specs.apollo.dev/b/v1.0`: expected a string or an object, found `3`",
+        "invalid import in the link to `https://specs.apollo.dev/b/v1.0`: missing `name`",
+        "invalid import in the link to `https://specs.apollo.dev/b/v1.0`: `z`, the alias of `@y`, must start with `@`",
+        "invalid import in the link to `https://specs.apollo.dev/b/v1.0`: `@bad name` is not a valid name",
+        "`@ok` refers to elements of multiple linked specifications",
+        "`b__` refers to elements of multiple linked specifications",
+    ];
+    for message in expected {
+        assert!(errors.contains(message), "{message}\n\n{errors}");
+    }
+}
@@ -1,18 +1,26 @@
+mod composition;
+mod cost;
 mod executable;
 mod extensions;
 mod field_set;
 mod field_type;
+mod input_coercion;
 mod introspection;
 mod introspection_max_depth;
 mod introspection_split;
+mod link;
 mod merge_schemas;
 /// Formerly in src/lib.rs
 mod misc;
 mod name;
+mod null_propagation;
 mod parser;
 mod schema;
+mod schema_diff;
 mod serde;
+mod supergraph;
 mod validation;
+mod variable_usage;
 
 #[path = "../examples/rename.rs"]
 mod rename;
@@ -280,3 +280,122 @@ enum E {
     ];
     expected.assert_eq(&merge_schemas(&inputs).unwrap());
 }
+
+#[test]
+fn test_schema_merge() {
+    let first = Schema::parse(
+        r#"
+            type Query {
+                t: T
+            }
+
+            "A T"
+            type T @key(fields: "k") {
+                k: ID
+            }
+
+            type S {
+                x: Int
+            }
+
+            union U = S | T
+        "#,
+        "first.graphql",
+    )
+    .unwrap();
+    let second = Schema::parse(
+        r#"
+            type T @key(fields: "k") @tag(name: "t") {
+                k: ID
+                a(limit: Int = 5): Int
+                b: String
+            }
+
+            union U = T | V
+
+            type V {
+                y: Int
+            }
+
+            enum E {
+                V1
+                V2
+            }
+
+            directive @tag(name: String!) repeatable on OBJECT
+        "#,
+        "second.graphql",
+    )
+    .unwrap();
+    let merged = first.merge(&second).unwrap();
+    let expected = expect_test::expect![[r#"
+        directive @tag(name: String!) repeatable on OBJECT
+
+        type Query {
+          t: T
+        }
+
+        """A T"""
+        type T @key(fields: "k") @tag(name: "t") {
+          k: ID
+          a(limit: Int = 5): Int
+          b: String
+        }
+
+        type S {
+          x: Int
+        }
+
+        union U = S | T | V
+
+        type V {
+          y: Int
+        }
+
+        enum E {
+          V1
+          V2
+        }
+    "#]];
+    expected.assert_eq(&merged.to_string());
+    let paths: Vec<_> = merged
+        .sources
+        .values()
+        .map(|file| file.path().to_str().unwrap())
+        .collect();
+    assert!(paths.contains(&"first.graphql") && paths.contains(&"second.graphql"));
+
+    let conflicting = Schema::parse(
+        r#"
+            schema { query: Root }
+            type Root { t: T }
+            interface S { x: Int }
+            type T { k: String a(limit: Int): Int }
+            directive @tag(name: String) repeatable on OBJECT
+        "#,
+        "conflicting.graphql",
+    )
+    .unwrap();
+    let merged = merged.merge(&conflicting).unwrap_err();
+    let conflicts: Vec<_> = merged
+        .conflicts
+        .iter()
+        .map(|conflict| conflict.to_string())
+        .collect();
+    assert_eq!(
+        conflicts,
+        [
+            "the query root operation type is `Query` in the first schema \
+             but `Root` in the second schema",
+            "directive `@tag` is defined differently in the two schemas",
+            "`S` is an object type in the first schema but an interface type in the second schema",
+            "field `T.k` has type `ID` in the first schema but `String` in the second schema",
+            "`T.a(limit:)` is not defined the same way in the two schemas",
+        ]
+    );
+    // The partial schema keeps the first definition of conflicting names
+    let partial = &merged.partial;
+    assert!(partial.get_object("S").is_some());
+    assert_eq!(partial.type_field("T", "k").unwrap().ty.to_string(), "ID");
+    assert!(partial.get_object("Root").is_some());
+}
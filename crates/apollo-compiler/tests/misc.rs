@@ -1,9 +1,13 @@
+use apollo_compiler::ast;
 use apollo_compiler::ast::Document;
 use apollo_compiler::executable::Selection;
 use apollo_compiler::name;
 use apollo_compiler::parse_mixed_validate;
 use apollo_compiler::schema::ExtendedType;
+use apollo_compiler::validation::CycleError;
 use apollo_compiler::ExecutableDocument;
+use apollo_compiler::MapRecursive;
+use apollo_compiler::Node;
 use apollo_compiler::Schema;
 use std::collections::HashMap;
 
@@ -849,3 +853,67 @@ fn initial_indent() {
     "#]];
     expected.assert_eq(&formatted);
 }
+
+#[test]
+fn map_recursive_respects_limit() {
+    fn nested_list(depth: usize) -> Node<ast::Value> {
+        let mut value = Node::new(ast::Value::Int(1.into()));
+        for _ in 0..depth {
+            value = Node::new(ast::Value::List(vec![value]));
+        }
+        value
+    }
+
+    fn double_ints(
+        map: &mut MapRecursive<'_, ast::Value, ast::Value>,
+        node: &Node<ast::Value>,
+    ) -> Result<ast::Value, CycleError<ast::Value>> {
+        Ok(match &**node {
+            ast::Value::Int(i) => ast::Value::Int((i.try_to_i32().unwrap() * 2).into()),
+            ast::Value::List(items) => ast::Value::List(
+                items
+                    .iter()
+                    .map(|item| map.map(item))
+                    .collect::<Result<_, _>>()?,
+            ),
+            other => other.clone(),
+        })
+    }
+
+    let shallow = nested_list(3);
+    let doubled = shallow.map_recursive(10, double_ints).unwrap();
+    assert_eq!(doubled.serialize().no_indent().to_string(), "[[[2]]]");
+
+    let deep = nested_list(1_000);
+    let result = deep.map_recursive(100, double_ints);
+    assert!(matches!(result, Err(CycleError::Limit(_))));
+}
+
+#[test]
+fn int_literals_larger_than_int_keep_their_value() {
+    let input = r#"
+        scalar BigInt
+        type Query { field(small: BigInt, large: BigInt, huge: BigInt): Int }
+        query { field(small: 3000000000, large: -170141183460469231731687303715884105728, huge: 1234567890123456789012345678901234567890) }
+    "#;
+    let (_schema, document) = parse_mixed_validate(input, "doc.graphql").unwrap();
+    let operation = document.operations.anonymous.as_ref().unwrap();
+    let Selection::Field(field) = &operation.selection_set.selections[0] else {
+        panic!("expected a field")
+    };
+    let ints: Vec<_> = field
+        .arguments
+        .iter()
+        .map(|arg| match &*arg.value {
+            ast::Value::Int(int) => int,
+            _ => panic!("expected an int"),
+        })
+        .collect();
+
+    assert!(ints[0].try_to_i32().is_err());
+    assert_eq!(ints[0].try_to_i64(), Ok(3_000_000_000));
+    assert!(ints[1].try_to_i64().is_err());
+    assert_eq!(ints[1].try_to_i128(), Ok(i128::MIN));
+    assert!(ints[2].try_to_i128().is_err());
+    assert_eq!(ints[2].as_str(), "1234567890123456789012345678901234567890");
+}
@@ -0,0 +1,98 @@
+use apollo_compiler::execution::analyze_null_propagation;
+use apollo_compiler::validation::Severity;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+
+const SCHEMA: &str = r#"
+  type Query {
+    me: User!
+    users: [User!]
+    node: Node
+  }
+
+  interface Node { id: ID! }
+
+  type User implements Node {
+    id: ID!
+    name: String
+    profile: Profile!
+  }
+
+  type Profile {
+    avatar: String!
+    bio: String
+  }
+"#;
+
+const QUERY: &str = r#"
+  {
+    me { name profile { avatar } }
+    users { ...Profile }
+    node { id ... on User { profile { bio } } }
+  }
+  fragment Profile on User { profile { avatar } }
+"#;
+
+#[test]
+fn reports_where_nulls_propagate() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let doc = ExecutableDocument::parse_and_validate(&schema, QUERY, "query.graphql").unwrap();
+    let operation = doc.operations.get(None).unwrap();
+    let report = analyze_null_propagation(&doc, operation);
+    let fields: Vec<_> = report
+        .fields
+        .iter()
+        .map(|field| {
+            let join = |path: &[_]| {
+                path.iter()
+                    .map(|key: &apollo_compiler::Name| key.as_str())
+                    .collect::<Vec<_>>()
+                    .join(".")
+            };
+            (
+                join(&field.path),
+                field.nulled.as_deref().map(join),
+                field.is_fragile(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        fields,
+        [
+            ("me".to_owned(), None, true),
+            ("me.profile".to_owned(), None, true),
+            ("me.profile.avatar".to_owned(), None, true),
+            // A null `User!` item nulls the whole `users` list
+            ("users.profile".to_owned(), Some("users".to_owned()), false),
+            (
+                "users.profile.avatar".to_owned(),
+                Some("users".to_owned()),
+                true
+            ),
+            ("node.id".to_owned(), Some("node".to_owned()), false),
+            ("node.profile".to_owned(), Some("node".to_owned()), false),
+        ]
+    );
+    assert_eq!(report.fields[1].coordinate.to_string(), "User.profile");
+
+    let warnings = report.warnings(&doc);
+    assert_eq!(warnings.len(), 4);
+    assert!(warnings
+        .iter()
+        .all(|warning| warning.error.severity() == Severity::Warning));
+    let warnings = warnings.to_string();
+    assert!(
+        warnings.contains(
+            "an error in non-null field `users.profile.avatar` propagates past its parent object"
+        ),
+        "{warnings}"
+    );
+    assert!(
+        warnings.contains("an error here sets `users` to null"),
+        "{warnings}"
+    );
+    assert!(
+        warnings.contains("an error here sets the entire response `data` to null"),
+        "{warnings}"
+    );
+}
@@ -1,4 +1,9 @@
+use apollo_compiler::ast;
+use apollo_compiler::parser::LineColumn;
+use apollo_compiler::parser::LineTerminators;
 use apollo_compiler::parser::Parser;
+use apollo_compiler::parser::PositionEncoding;
+use apollo_compiler::parser::SourceAnchor;
 
 #[test]
 fn it_errors_when_selection_set_recursion_limit_exceeded() {
@@ -98,3 +103,240 @@ fn it_errors_with_multiple_limits() {
     );
     assert!(errors.contains("doc.graphql:6:25"), "{errors}");
 }
+
+#[test]
+fn it_maps_locations_into_the_host_file() {
+    let host = "import { gql } from 'graphql-tag';\nconst query = gql`{\n  hello\n  world\n}`;\n";
+    let start = host.find('{').unwrap();
+    let start = host[start + 1..].find('{').unwrap() + start + 1;
+    let end = host.rfind('`').unwrap();
+    let anchor = SourceAnchor {
+        offset: start,
+        line_column: LineColumn {
+            line: 2,
+            column: 19,
+        },
+    };
+
+    let document = Parser::new()
+        .anchor(anchor)
+        .parse_ast(&host[start..end], "query.js")
+        .unwrap();
+    let ast::Definition::OperationDefinition(operation) = &document.definitions[0] else {
+        panic!("expected an operation")
+    };
+    let world = operation.selection_set[1].location().unwrap();
+    assert_eq!(&host[world.host_range(&document.sources)], "world");
+    assert_eq!(
+        world.line_column(&document.sources),
+        Some(LineColumn { line: 4, column: 3 })
+    );
+    let root = operation.location().unwrap();
+    assert_eq!(
+        root.line_column(&document.sources),
+        Some(LineColumn {
+            line: 2,
+            column: 19
+        })
+    );
+
+    let errors = Parser::new()
+        .anchor(anchor)
+        .parse_ast("{\n  hello(\n}", "query.js")
+        .unwrap_err()
+        .errors;
+    let error = errors.iter().next().unwrap();
+    assert_eq!(error.line_column_range().unwrap().start.line, 4);
+    assert!(errors.to_string().contains("query.js:4:"), "{errors}");
+}
+
+#[test]
+fn it_counts_lines_with_mixed_line_terminators() {
+    // U+2028 LINE SEPARATOR is not a line terminator in GraphQL
+    let input = "query {\r\n  a\r  b\n  c # \u{2028} still a comment\r\n  d\n}";
+    let document = Parser::new().parse_ast(input, "doc.graphql").unwrap();
+    let source = document.sources.values().next().unwrap();
+    let index = source.line_index();
+    assert_eq!(index.line_count(), 6);
+    assert_eq!(index.line_start(2), Some(input.find('a').unwrap() - 2));
+    // The middle of a CRLF belongs to the line it terminates
+    assert_eq!(index.line_of(input.find('\n').unwrap()), Some(1));
+    assert_eq!(index.line_of(input.len()), Some(6));
+    assert_eq!(index.line_of(input.len() + 1), None);
+
+    let ast::Definition::OperationDefinition(operation) = &document.definitions[0] else {
+        panic!("expected an operation")
+    };
+    let positions: Vec<_> = operation
+        .selection_set
+        .iter()
+        .map(|selection| selection.location().unwrap().line_column(&document.sources))
+        .collect();
+    assert_eq!(
+        positions,
+        [
+            Some(LineColumn { line: 2, column: 3 }),
+            Some(LineColumn { line: 3, column: 3 }),
+            Some(LineColumn { line: 4, column: 3 }),
+            Some(LineColumn { line: 5, column: 3 }),
+        ]
+    );
+}
+
+#[test]
+fn it_reports_lines_with_configured_line_terminators() {
+    let input = "# \u{2028} comment\nquery {\r  a(\n}";
+
+    let errors = Parser::new()
+        .parse_ast(input, "doc.graphql")
+        .unwrap_err()
+        .errors;
+    let error = errors.iter().next().unwrap();
+    assert_eq!(error.line_column_range().unwrap().start.line, 4);
+    // CLI reports count lines the same way
+    assert!(errors.to_string().contains("doc.graphql:4:"), "{errors}");
+
+    let errors = Parser::new()
+        .line_terminators(LineTerminators::LineFeed)
+        .parse_ast(input, "doc.graphql")
+        .unwrap_err()
+        .errors;
+    let error = errors.iter().next().unwrap();
+    assert_eq!(error.line_column_range().unwrap().start.line, 3);
+    assert!(errors.to_string().contains("doc.graphql:3:"), "{errors}");
+}
+
+#[test]
+fn it_counts_columns_in_different_encodings() {
+    // "é" is 2 UTF-8 bytes and 1 UTF-16 unit, "🚀" is 4 UTF-8 bytes and 2 UTF-16 units
+    let input = "query { a(s: \"é🚀\") b }";
+    let document = Parser::new().parse_ast(input, "doc.graphql").unwrap();
+    let ast::Definition::OperationDefinition(operation) = &document.definitions[0] else {
+        panic!("expected an operation")
+    };
+    let b = operation.selection_set[1].location().unwrap();
+    let column = |encoding| {
+        b.line_column_range_with_encoding(&document.sources, encoding)
+            .unwrap()
+            .start
+            .column
+    };
+    assert_eq!(column(PositionEncoding::Utf8), input.find('b').unwrap() + 1);
+    assert_eq!(column(PositionEncoding::Utf16), 21);
+    assert_eq!(column(PositionEncoding::Scalar), 20);
+    assert_eq!(
+        b.line_column(&document.sources).unwrap().column,
+        column(PositionEncoding::Scalar)
+    );
+}
+
+#[test]
+fn it_returns_byte_ranges_of_nodes_and_arguments() {
+    let input = "query { alias: field (x: 1, # comment\n y: 2 ,) @dir( z: 3 ) other }";
+    let document = Parser::new().parse_ast(input, "doc.graphql").unwrap();
+    let sources = &document.sources;
+    let ast::Definition::OperationDefinition(operation) = &document.definitions[0] else {
+        panic!("expected an operation")
+    };
+    let ast::Selection::Field(field) = &operation.selection_set[0] else {
+        panic!("expected a field")
+    };
+    let location = field.location().unwrap();
+    assert_eq!(
+        &input[location.range()],
+        "alias: field (x: 1, # comment\n y: 2 ,) @dir( z: 3 )"
+    );
+    let arguments = field.arguments_location(sources).unwrap();
+    assert_eq!(arguments.file_id(), location.file_id());
+    assert_eq!(&input[arguments.range()], "(x: 1, # comment\n y: 2 ,)");
+    let directive_arguments = field.directives[0].arguments_location(sources).unwrap();
+    assert_eq!(&input[directive_arguments.range()], "( z: 3 )");
+
+    let ast::Selection::Field(other) = &operation.selection_set[1] else {
+        panic!("expected a field")
+    };
+    assert_eq!(other.arguments_location(sources), None);
+}
+
+#[test]
+fn it_builds_the_same_ast_without_a_syntax_tree() {
+    let input = r#"
+        "Schema description"
+        schema @dir(list: [1, [2.5]], obj: { a: { b: "c" } }) { query: Query }
+        extend schema { mutation: Mutation }
+        directive @dir(list: [Float], obj: In) repeatable on | SCHEMA | FIELD_DEFINITION
+        """
+        Block string
+          description
+        """
+        type Query implements & A & B @dir {
+          "field" field(arg: [[Int!]]! = [[1]] @dir): String!
+        }
+        extend type Query { other: Int }
+        interface A { id: ID } interface B implements A { id: ID }
+        union U = | Query | Mutation
+        enum E { ONE, "two" TWO @dir }
+        input In { a: In = { a: null, b: ENUM } }
+        scalar S @dir
+        extend scalar S @dir
+
+        query Q($v: [Int] = [1, 2], $w: In! @dir) @dir {
+          alias: field(arg: $v) { ... on Query { x } ...F ... @dir { y } }
+        }
+        { field(a: "esc\"apedé", b: true, c: -1.5e3) }
+        fragment F on Query { z }
+    "#;
+    let cst_document = Parser::new().parse_ast(input, "doc.graphql").unwrap();
+    let mut parser = Parser::new().skip_syntax_tree(true);
+    let direct_document = parser.parse_ast(input, "doc.graphql").unwrap();
+    assert_eq!(direct_document, cst_document);
+
+    // Compare source locations too, which are ignored by `==`
+    let debug_without_file_id = |document: &ast::Document| {
+        let file_id = document.sources.keys().next().unwrap();
+        format!("{:?}", document.definitions).replace(&format!("@{file_id:?}"), "@")
+    };
+    assert_eq!(
+        debug_without_file_id(&direct_document),
+        debug_without_file_id(&cst_document)
+    );
+
+    let mut cst_parser = Parser::new();
+    cst_parser.parse_ast(input, "doc.graphql").unwrap();
+    assert_eq!(parser.recursion_reached(), cst_parser.recursion_reached());
+    assert_eq!(parser.tokens_reached(), cst_parser.tokens_reached());
+}
+
+#[test]
+fn it_reports_the_same_errors_without_a_syntax_tree() {
+    for input in [
+        "{ field(arg: ) }",
+        "type Query {}",
+        "query { a { b { c } } }",
+        "\"description\" query { a }",
+    ] {
+        let mut cst_parser = Parser::new().recursion_limit(2);
+        let mut direct_parser = Parser::new().recursion_limit(2).skip_syntax_tree(true);
+        let expected = cst_parser.parse_ast(input, "doc.graphql").unwrap_err();
+        let actual = direct_parser.parse_ast(input, "doc.graphql").unwrap_err();
+        assert_eq!(actual.errors.to_string(), expected.errors.to_string());
+        assert_eq!(actual.partial, expected.partial);
+    }
+}
+
+#[test]
+fn it_accepts_empty_bodies_in_lenient_mode() {
+    let input = "type Query {} input I {} enum E {} type T { f(): Int }";
+
+    let mut strict = Parser::new();
+    assert!(strict.parse_ast(input, "schema.graphql").is_err());
+    assert!(strict.warnings().is_empty());
+
+    let mut lenient = Parser::new().lenient(true);
+    let doc = lenient.parse_ast(input, "schema.graphql").unwrap();
+    assert_eq!(doc.definitions.len(), 4);
+    let warnings = lenient.warnings().to_string();
+    assert_eq!(lenient.warnings().len(), 4, "{warnings}");
+    assert!(warnings.contains("empty fields definition is not allowed"));
+    assert!(warnings.contains("empty arguments definition is not allowed"));
+}
@@ -0,0 +1,233 @@
+use apollo_compiler::ast;
+use apollo_compiler::schema::BuiltInScalarRedefinitions;
+use apollo_compiler::schema::DuplicateDefinitions;
+use apollo_compiler::validation::DiagnosticList;
+use apollo_compiler::validation::Severity;
+use apollo_compiler::Schema;
+
+#[test]
+fn duplicate_definitions_policy() {
+    let base = r#"
+        type Query { a: Int }
+        directive @tag(name: String!) on FIELD_DEFINITION
+        enum Color { RED }
+    "#;
+    let layer = r#"
+        type Query { b: Int }
+        directive @tag(name: String!) on FIELD_DEFINITION
+        enum Color { RED }
+    "#;
+    let build = |policy| {
+        Schema::builder()
+            .duplicate_definitions(policy)
+            .parse(base, "base.graphql")
+            .parse(layer, "layer.graphql")
+            .build()
+            .unwrap_err()
+    };
+    let messages = |errors: &DiagnosticList| -> Vec<String> {
+        errors.iter().map(|d| d.error.to_string()).collect()
+    };
+
+    let result = build(DuplicateDefinitions::Error);
+    assert!(result.errors.has_errors());
+    assert_eq!(result.errors.len(), 3);
+
+    let result = build(DuplicateDefinitions::FirstWins);
+    assert!(!result.errors.has_errors());
+    assert_eq!(
+        messages(&result.errors),
+        [
+            "the type `Query` is defined multiple times in the schema, \
+             keeping the first definition",
+            "the directive `@tag` is defined multiple times in the schema, \
+             keeping the first definition",
+            "the type `Color` is defined multiple times in the schema, \
+             keeping the first definition",
+        ]
+    );
+    let query = result.partial.get_object("Query").unwrap();
+    assert!(query.fields.contains_key("a"));
+    assert!(!query.fields.contains_key("b"));
+
+    let result = build(DuplicateDefinitions::LastWins);
+    assert!(!result.errors.has_errors());
+    assert_eq!(result.errors.len(), 3);
+    let query = result.partial.get_object("Query").unwrap();
+    assert!(!query.fields.contains_key("a"));
+    assert!(query.fields.contains_key("b"));
+    result.partial.validate().unwrap();
+
+    // Only `Query` differs
+    let result = build(DuplicateDefinitions::MergeIfIdentical);
+    assert_eq!(
+        messages(&result.errors),
+        [
+            "the type `Query` is defined multiple times in the schema",
+            "the directive `@tag` is defined multiple times in the schema, \
+             keeping the first definition",
+            "the type `Color` is defined multiple times in the schema, \
+             keeping the first definition",
+        ]
+    );
+    let severities: Vec<_> = result.errors.iter().map(|d| d.error.severity()).collect();
+    assert_eq!(
+        severities,
+        [Severity::Error, Severity::Warning, Severity::Warning]
+    );
+}
+
+#[test]
+fn built_in_scalar_redefinitions_policy() {
+    let input = r#"
+        "A custom description is ignored"
+        scalar String
+        scalar Boolean @specifiedBy(url: "https://example.com")
+        type Query { a: String b: Boolean }
+    "#;
+    let build = |policy| {
+        Schema::builder()
+            .built_in_scalar_redefinitions(policy)
+            .parse(input, "schema.graphql")
+            .build()
+            .unwrap_err()
+            .errors
+    };
+    let severities = |errors: &DiagnosticList| -> Vec<Severity> {
+        errors.iter().map(|d| d.error.severity()).collect()
+    };
+
+    let errors = build(BuiltInScalarRedefinitions::Error);
+    assert_eq!(severities(&errors), [Severity::Error, Severity::Error]);
+
+    // The redefinition with a directive is not identical
+    let errors = build(BuiltInScalarRedefinitions::WarnIfIdentical);
+    assert_eq!(severities(&errors), [Severity::Warning, Severity::Error]);
+    let errors = build(BuiltInScalarRedefinitions::AllowIfIdentical);
+    assert_eq!(severities(&errors), [Severity::Error]);
+    assert_eq!(
+        errors.iter().next().unwrap().error.to_string(),
+        "built-in scalar definitions must be omitted"
+    );
+
+    let schema = Schema::builder()
+        .built_in_scalar_redefinitions(BuiltInScalarRedefinitions::AllowIfIdentical)
+        .parse(
+            "scalar String scalar Boolean type Query { a: String }",
+            "schema.graphql",
+        )
+        .build()
+        .unwrap();
+    assert!(schema.types["String"].is_built_in());
+    schema.validate().unwrap();
+}
+
+#[test]
+fn validate_added_definitions() {
+    let schema = Schema::parse_and_validate(
+        r#"
+            type Query { node: Node }
+            interface Node { id: ID! }
+            type User implements Node { id: ID! }
+            type Unrelated { a: Int }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let validate = |sdl: &str| {
+        let document = ast::Document::parse(sdl, "added.graphql").unwrap();
+        Schema::validate_added_definitions(&schema, &document)
+    };
+    let messages = |errors: &DiagnosticList| -> Vec<String> {
+        errors.iter().map(|d| d.error.to_string()).collect()
+    };
+
+    let extended = validate("type Post implements Node { id: ID! title: String }").unwrap();
+    assert!(extended.get_object("Post").is_some());
+    // The original schema is unchanged
+    assert!(schema.get_object("Post").is_none());
+
+    let result = validate("extend interface Node { createdAt: String }").unwrap_err();
+    assert_eq!(
+        messages(&result.errors),
+        ["type `User` does not satisfy interface `Node`: missing field `createdAt`"]
+    );
+
+    let result = validate("type User { name: String } directive @tag(name: Strin) on FIELD");
+    assert_eq!(
+        messages(&result.unwrap_err().errors),
+        [
+            "the type `User` is defined multiple times in the schema",
+            "cannot find type `Strin` in this document",
+        ]
+    );
+}
+
+#[test]
+fn fluent_builder() {
+    let schema = Schema::builder()
+        .object("Query", |ty| {
+            ty.description("The \"root\" query type")
+                .field_with("search", "[SearchResult!]!", |field| {
+                    field
+                        .argument("text", "String!")
+                        .argument_with("filter", "Filter", |arg| {
+                            arg.default_value("{ color: RED }")
+                        })
+                })
+                .field_with("legacy", "Int", |field| field.deprecated("Use `search`"))
+        })
+        .interface("Node", |ty| ty.field("id", "ID!"))
+        .object("Product", |ty| ty.implements("Node").field("id", "ID!"))
+        .union("SearchResult", ["Product"])
+        .input_object("Filter", |ty| {
+            ty.field("color", "Color").field("size", "Size")
+        })
+        .enum_type("Color", |ty| {
+            ty.value("RED")
+                .value_with("BLUE", |value| value.description("Not red"))
+        })
+        .scalar("Size")
+        .build()
+        .unwrap()
+        .validate()
+        .unwrap();
+
+    let query = schema.get_object("Query").unwrap();
+    assert_eq!(
+        query.description.as_deref(),
+        Some("The \"root\" query type")
+    );
+    let search = &query.fields["search"];
+    assert_eq!(search.ty.to_string(), "[SearchResult!]!");
+    assert_eq!(search.arguments.len(), 2);
+    assert_eq!(
+        search.arguments[1]
+            .default_value
+            .as_ref()
+            .unwrap()
+            .serialize()
+            .no_indent()
+            .to_string(),
+        "{color: RED}"
+    );
+    assert!(query.fields["legacy"].directives.has("deprecated"));
+    let product = schema.get_object("Product").unwrap();
+    assert!(product
+        .implements_interfaces
+        .iter()
+        .any(|i| i.name == "Node"));
+    assert_eq!(schema.get_enum("Color").unwrap().values.len(), 2);
+    assert!(schema.get_scalar("Size").is_some());
+    // Definitions have synthetic source files named after them
+    let location = query.location().unwrap();
+    let path = schema.sources[&location.file_id()].path();
+    assert_eq!(path.to_str(), Some("builder:Query"));
+
+    let errors = Schema::builder()
+        .object("Query", |ty| ty.field("a", "Undefined").field("b c", "Int"))
+        .build()
+        .unwrap_err()
+        .errors;
+    assert!(errors.to_string().contains("builder:Query"), "{errors}");
+}
@@ -0,0 +1,446 @@
+use apollo_compiler::ast;
+use apollo_compiler::coordinate::SchemaCoordinate;
+use apollo_compiler::coordinate::SchemaCoordinateLookup;
+use apollo_compiler::name;
+use apollo_compiler::schema;
+use apollo_compiler::schema::ExtendedType;
+use apollo_compiler::schema::TypeReferenceKind;
+use apollo_compiler::ty;
+use apollo_compiler::Schema;
+use std::sync::Arc;
+
+#[test]
+fn type_reference_graph() {
+    let schema = Schema::parse_and_validate(
+        r#"
+        directive @cost(weight: Money) on FIELD_DEFINITION
+        type Query { search(filter: Filter): [Result!]! }
+        interface Priced { price: Money }
+        type Product implements Priced { price: Money @cost }
+        type Service implements Priced { price: Money }
+        union Result = Product | Service
+        input Filter { maxPrice: Money }
+        scalar Money
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let graph = schema.type_references();
+    let to = |ty: &str| -> Vec<String> {
+        graph
+            .references_to(ty)
+            .map(|reference| reference.source.to_string())
+            .collect()
+    };
+    assert_eq!(
+        to("Money"),
+        [
+            "Priced.price",
+            "Product.price",
+            "Service.price",
+            "Filter.maxPrice",
+            "@cost(weight:)",
+        ]
+    );
+    assert_eq!(to("Priced"), ["Product", "Service"]);
+    assert_eq!(to("Product"), ["Result"]);
+    assert_eq!(to("Filter"), ["Query.search(filter:)"]);
+    assert!(to("Query").is_empty());
+    assert!(!graph.is_referenced("Query"));
+
+    let from_query: Vec<_> = graph
+        .references_from_type("Query")
+        .map(|reference| (reference.target.as_str(), reference.kind))
+        .collect();
+    assert_eq!(
+        from_query,
+        [
+            ("Result", TypeReferenceKind::FieldType),
+            ("Filter", TypeReferenceKind::ArgumentType),
+        ]
+    );
+    let from_product: Vec<_> = graph
+        .references_from_type("Product")
+        .map(|reference| reference.to_string())
+        .collect();
+    assert_eq!(
+        from_product,
+        ["Product -> Priced", "Product.price -> Money"]
+    );
+    let from_cost: Vec<_> = graph.references_from_directive("cost").collect();
+    assert_eq!(from_cost.len(), 1);
+    assert_eq!(from_cost[0].kind, TypeReferenceKind::DirectiveArgumentType);
+    // References from built-in definitions are not included
+    assert!(graph
+        .references_from_directive("deprecated")
+        .next()
+        .is_none());
+    assert!(graph.references_to("Boolean").next().is_none());
+}
+
+#[test]
+fn element_at_coordinate() {
+    let schema = Schema::parse_and_validate(
+        r#"
+        directive @auth(role: Role) on FIELD_DEFINITION
+        type Query { user(id: ID!): User }
+        type User { name: String @auth(role: ADMIN) }
+        input UserFilter { name: String }
+        enum Role { ADMIN }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let lookup = |coordinate: &str| {
+        let coordinate: SchemaCoordinate = coordinate.parse().unwrap();
+        match schema.element_at(&coordinate) {
+            Ok(SchemaCoordinateLookup::Type(ty)) => format!("type {}", ty.name()),
+            Ok(SchemaCoordinateLookup::Directive(def)) => format!("directive @{}", def.name),
+            Ok(SchemaCoordinateLookup::Field(def)) => format!("field {}: {}", def.name, def.ty),
+            Ok(SchemaCoordinateLookup::InputField(def)) => {
+                format!("input field {}: {}", def.name, def.ty)
+            }
+            Ok(SchemaCoordinateLookup::EnumValue(def)) => format!("enum value {}", def.value),
+            Ok(SchemaCoordinateLookup::Argument(def)) => {
+                format!("argument {}: {}", def.name, def.ty)
+            }
+            Ok(_) => unreachable!(),
+            Err(error) => format!("error: {error}"),
+        }
+    };
+    assert_eq!(lookup("User"), "type User");
+    assert_eq!(lookup("User.name"), "field name: String");
+    assert_eq!(lookup("Query.user(id:)"), "argument id: ID!");
+    assert_eq!(lookup("UserFilter.name"), "input field name: String");
+    assert_eq!(lookup("Role.ADMIN"), "enum value ADMIN");
+    assert_eq!(lookup("@auth"), "directive @auth");
+    assert_eq!(lookup("@auth(role:)"), "argument role: Role");
+
+    assert_eq!(lookup("Account"), "error: type `Account` does not exist");
+    assert_eq!(
+        lookup("User.email"),
+        "error: type does not have attribute `email`"
+    );
+    assert_eq!(
+        lookup("Query.user(first:)"),
+        "error: field or directive does not have argument `first`"
+    );
+    assert_eq!(
+        lookup("Role.ADMIN(level:)"),
+        "error: type attribute `ADMIN` is not a field and can not have arguments"
+    );
+    assert_eq!(
+        lookup("@cached"),
+        "error: directive `@cached` is not defined"
+    );
+    assert_eq!(
+        lookup("@cached(ttl:)"),
+        "error: directive `@cached` is not defined"
+    );
+}
+
+#[test]
+fn reverse_indexes_are_cached_until_invalidated() {
+    let mut schema = Schema::parse(
+        r#"
+        type Query { pets: [Pet] }
+        interface Pet { name: String }
+        type Cat implements Pet { name: String }
+        union Animal = Cat
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let indexes = schema.reverse_indexes();
+    assert!(Arc::ptr_eq(&indexes, &schema.reverse_indexes()));
+    assert!(Arc::ptr_eq(&indexes, &schema.clone().reverse_indexes()));
+    assert_eq!(indexes.implementers_of("Pet").unwrap().objects.len(), 1);
+    assert!(indexes.implementers_of("Cat").is_none());
+    assert_eq!(indexes.unions_containing("Cat").count(), 1);
+    assert_eq!(indexes.fields_returning("Pet")[0].to_string(), "Query.pets");
+    assert!(indexes.fields_returning("Cat").is_empty());
+
+    // Mutating a type keeps the cached indexes until they are invalidated
+    let ExtendedType::Object(query) = &mut schema.types["Query"] else {
+        panic!()
+    };
+    query.make_mut().fields.insert(
+        name!("cat"),
+        ast::FieldDefinition {
+            description: None,
+            name: name!("cat"),
+            arguments: Vec::new(),
+            ty: ty!(Cat),
+            directives: Default::default(),
+        }
+        .into(),
+    );
+    assert!(Arc::ptr_eq(&indexes, &schema.reverse_indexes()));
+    schema.invalidate_reverse_indexes();
+    let updated = schema.reverse_indexes();
+    assert!(!Arc::ptr_eq(&indexes, &updated));
+    assert_eq!(updated.fields_returning("Cat")[0].to_string(), "Query.cat");
+    // The previous indexes are unchanged
+    assert!(indexes.fields_returning("Cat").is_empty());
+
+    // Validation recomputes them
+    let mut dog = schema.get_object("Cat").unwrap().as_ref().clone();
+    dog.name = name!("Dog");
+    schema.types.insert(name!("Dog"), dog.into());
+    let schema = schema.validate().unwrap();
+    let updated_again = schema.reverse_indexes();
+    assert!(!Arc::ptr_eq(&updated, &updated_again));
+    assert_eq!(
+        updated_again.implementers_of("Pet").unwrap().objects.len(),
+        2
+    );
+}
+
+#[test]
+fn possible_types() {
+    // Not valid, since `Video` does not also implement `Node`,
+    // but possible types still include transitive implementations.
+    let schema = Schema::parse(
+        r#"
+        type Query { node: Node }
+        interface Node { id: ID! }
+        interface Media implements Node { id: ID! }
+        type Video implements Media { id: ID! }
+        type User implements Node { id: ID! }
+        union Result = User | Video
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let possible = |ty: &str| -> Vec<String> {
+        schema
+            .possible_types(ty)
+            .iter()
+            .map(|name| name.to_string())
+            .collect()
+    };
+    assert_eq!(possible("Node"), ["User", "Video"]);
+    assert_eq!(possible("Media"), ["Video"]);
+    assert_eq!(possible("Result"), ["User", "Video"]);
+    assert_eq!(possible("User"), ["User"]);
+    assert!(possible("ID").is_empty());
+    assert!(possible("Unknown").is_empty());
+    assert!(Arc::ptr_eq(
+        &schema.possible_types("Node"),
+        &schema.possible_types("Node")
+    ));
+}
+
+#[test]
+fn deprecations() {
+    let schema = Schema::parse_and_validate(
+        r#"
+        directive @cache(ttl: Int, maxAge: Int @deprecated(reason: "Use `ttl`")) on FIELD
+        type Query {
+          user(id: ID!, legacyId: Int @deprecated): User
+          users: [User] @deprecated(reason: "Use `search`")
+          search(filter: Filter): [User]
+        }
+        interface Node { legacyId: Int @deprecated }
+        type User implements Node {
+          legacyId: Int @deprecated
+          role: Role
+        }
+        enum Role { ADMIN SUPERUSER @deprecated(reason: "Merged into ADMIN") }
+        input Filter { role: Role name: String @deprecated(reason: "") }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let deprecations: Vec<_> = schema
+        .deprecations()
+        .into_iter()
+        .map(|deprecation| {
+            let line = deprecation
+                .location
+                .unwrap()
+                .line_column(&schema.sources)
+                .unwrap()
+                .line;
+            format!(
+                "{:?} {} at line {line}: {:?}",
+                deprecation.kind, deprecation.coordinate, deprecation.reason
+            )
+        })
+        .collect();
+    assert_eq!(
+        deprecations,
+        [
+            "Argument Query.user(legacyId:) at line 4: \"No longer supported\"",
+            "Field Query.users at line 5: \"Use `search`\"",
+            "Field Node.legacyId at line 8: \"No longer supported\"",
+            "Field User.legacyId at line 10: \"No longer supported\"",
+            "EnumValue Role.SUPERUSER at line 13: \"Merged into ADMIN\"",
+            "InputField Filter.name at line 14: \"\"",
+            "Argument @cache(maxAge:) at line 2: \"Use `ttl`\"",
+        ]
+    );
+}
+
+#[test]
+fn directive_applications() {
+    let schema = Schema::parse_and_validate(
+        r#"
+        schema @tag(name: "schema") { query: Query }
+        directive @tag(name: String!, level: Int = 1) repeatable on
+          | SCHEMA | OBJECT | FIELD_DEFINITION | ARGUMENT_DEFINITION
+          | ENUM | ENUM_VALUE | INPUT_OBJECT | INPUT_FIELD_DEFINITION
+        directive @cache(ttl: Int @tag(name: "ttl", level: 2)) on FIELD
+        type Query @tag(name: "query") {
+          search(filter: Filter @tag(name: "filter")): [Role] @tag(name: "search")
+        }
+        enum Role @tag(name: "role") { ADMIN @tag(name: "admin") USER }
+        input Filter { role: Role @tag(name: "input") @deprecated }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let tags: Vec<_> = schema
+        .directive_applications("tag")
+        .iter()
+        .map(|application| {
+            let coordinate = match &application.coordinate {
+                Some(coordinate) => coordinate.to_string(),
+                None => "schema".to_owned(),
+            };
+            let arguments: Vec<_> = application
+                .arguments
+                .iter()
+                .map(|(name, value)| format!("{name}: {value}"))
+                .collect();
+            format!("{coordinate} ({})", arguments.join(", "))
+        })
+        .collect();
+    assert_eq!(
+        tags,
+        [
+            r#"schema (name: "schema", level: 1)"#,
+            r#"Query (name: "query", level: 1)"#,
+            r#"Query.search (name: "search", level: 1)"#,
+            r#"Query.search(filter:) (name: "filter", level: 1)"#,
+            r#"Role (name: "role", level: 1)"#,
+            r#"Role.ADMIN (name: "admin", level: 1)"#,
+            r#"Filter.role (name: "input", level: 1)"#,
+            r#"@cache(ttl:) (name: "ttl", level: 2)"#,
+        ]
+    );
+
+    let deprecated = schema.directive_applications("deprecated");
+    assert_eq!(deprecated.len(), 1);
+    assert_eq!(
+        deprecated[0].coordinate.as_ref().unwrap().to_string(),
+        "Filter.role"
+    );
+    assert_eq!(
+        deprecated[0].argument("reason").unwrap().as_str(),
+        Some("No longer supported")
+    );
+    assert!(schema.directive_applications("specifiedBy").is_empty());
+}
+
+#[test]
+fn visit_schema() {
+    struct CountElements(Vec<String>);
+
+    impl schema::Visitor for CountElements {
+        fn visit_type(&mut self, ty: &ExtendedType) {
+            self.0.push(format!("type {}", ty.name()))
+        }
+        fn visit_field(
+            &mut self,
+            coordinate: &apollo_compiler::coordinate::TypeAttributeCoordinate,
+            _field: &schema::FieldDefinition,
+        ) {
+            self.0.push(format!("field {coordinate}"))
+        }
+        fn visit_argument(
+            &mut self,
+            coordinate: &SchemaCoordinate,
+            _: &schema::InputValueDefinition,
+        ) {
+            self.0.push(format!("argument {coordinate}"))
+        }
+        fn visit_directive(&mut self, directive: &schema::Directive) {
+            self.0.push(format!("directive @{}", directive.name))
+        }
+    }
+
+    struct DropInternal;
+
+    impl schema::VisitorMut for DropInternal {
+        fn visit_field(
+            &mut self,
+            _coordinate: &apollo_compiler::coordinate::TypeAttributeCoordinate,
+            field: &mut schema::FieldDefinition,
+        ) {
+            field.description = None
+        }
+        fn visit_directive(&mut self, directive: &mut schema::Directive) -> bool {
+            directive.name != "internal"
+        }
+    }
+
+    let input = r#"
+        schema @internal { query: Query }
+        directive @internal on SCHEMA | OBJECT | FIELD_DEFINITION | ARGUMENT_DEFINITION | ENUM_VALUE
+        directive @tag(name: String @internal) repeatable on OBJECT | FIELD_DEFINITION
+        type Query @internal @tag(name: "root") {
+          "The current user"
+          me(token: String @internal): User @internal
+        }
+        type User { name: String @tag(name: "pii") }
+        enum Role { ADMIN @internal }
+    "#;
+    let schema = Schema::parse_and_validate(input, "schema.graphql").unwrap();
+    let mut visitor = CountElements(Vec::new());
+    schema.visit(&mut visitor);
+    assert_eq!(
+        visitor.0,
+        [
+            "directive @internal",
+            "type Query",
+            "directive @internal",
+            "directive @tag",
+            "field Query.me",
+            "directive @internal",
+            "argument Query.me(token:)",
+            "directive @internal",
+            "type User",
+            "field User.name",
+            "directive @tag",
+            "type Role",
+            "directive @internal",
+            "argument @tag(name:)",
+            "directive @internal",
+        ]
+    );
+
+    let mut transformed = schema.clone().into_inner();
+    transformed.visit_mut(&mut DropInternal);
+    // Without its directive, the `schema` definition matches the implicit one
+    let expected = expect_test::expect![[r#"
+        directive @internal on SCHEMA | OBJECT | FIELD_DEFINITION | ARGUMENT_DEFINITION | ENUM_VALUE
+
+        directive @tag(name: String) repeatable on OBJECT | FIELD_DEFINITION
+
+        type Query @tag(name: "root") {
+          me(token: String): User
+        }
+
+        type User {
+          name: String @tag(name: "pii")
+        }
+
+        enum Role {
+          ADMIN
+        }
+    "#]];
+    expected.assert_eq(&transformed.to_string());
+    // The original schema is unchanged
+    assert!(schema.to_string().contains("\"The current user\""));
+}
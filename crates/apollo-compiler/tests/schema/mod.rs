@@ -1,3 +1,8 @@
+mod builder;
+mod lookup;
+mod serialize;
+mod transform;
+
 use apollo_compiler::Schema;
 
 #[test]
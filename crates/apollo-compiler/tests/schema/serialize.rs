@@ -0,0 +1,338 @@
+use apollo_compiler::schema;
+use apollo_compiler::schema::FingerprintOptions;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+
+#[test]
+fn serialize_with_options() {
+    let schema = Schema::parse_and_validate(
+        r#"
+        "The root"
+        type Query {
+          zebra(b: Int, a: Int): Animal
+          apple: Fruit
+        }
+        enum Fruit { PEAR APPLE }
+        directive @z on FIELD
+        directive @a on FIELD
+        type Animal { name: String }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let expected = r#"directive @a on FIELD
+
+directive @z on FIELD
+
+type Animal {
+  name: String
+}
+
+enum Fruit {
+  APPLE
+  PEAR
+}
+
+type Query {
+  apple: Fruit
+  zebra(a: Int, b: Int): Animal
+}
+"#;
+    assert_eq!(
+        schema.serialize().sorted().no_descriptions().to_string(),
+        expected
+    );
+    // The input order is kept by default, and sorting does not mutate the schema
+    let printed = schema.to_string();
+    assert!(printed.contains("The root"));
+    assert!(printed.contains("type Query {\n  zebra(b: Int, a: Int): Animal\n  apple: Fruit\n}"));
+
+    let with_built_ins = schema.serialize().include_built_ins().to_string();
+    assert!(with_built_ins.contains("directive @skip("));
+    assert!(with_built_ins.contains("scalar String"));
+    assert!(with_built_ins.contains("type __Schema {"));
+    assert!(!schema.to_string().contains("scalar String"));
+
+    let indented = schema.serialize().indent_prefix("\t").to_string();
+    assert!(indented.contains("enum Fruit {\n\tPEAR\n\tAPPLE\n}"));
+}
+
+#[test]
+fn print_subgraph_faithfully() {
+    let input = r#"
+        extend schema
+          @link(url: "https://specs.apollo.dev/link/v1.0")
+          @link(url: "https://specs.apollo.dev/federation/v2.3", import: [{ name: "@key", as: "@id" }])
+
+        directive @link(url: String!, import: [link__Import]) repeatable on SCHEMA
+        directive @id(fields: String!) repeatable on OBJECT
+        directive @federation__requires(fields: String!) on FIELD_DEFINITION
+        scalar link__Import
+
+        type Query { products: [Product] }
+
+        extend type Product @id(fields: "upc") {
+          upc: String!
+          weight: Int
+          shippingCost: Int @federation__requires(fields: "weight")
+        }
+    "#;
+    let parse = |sdl: &str| {
+        Schema::builder()
+            .adopt_orphan_extensions()
+            .parse(sdl, "subgraph.graphql")
+            .build()
+            .unwrap()
+    };
+    let schema = parse(input);
+    let printed = schema.to_string();
+    // No `schema` definition or empty `type Product` definition is made up
+    assert!(printed.starts_with("extend schema @link("), "{printed}");
+    assert!(!printed.contains("schema {"), "{printed}");
+    assert!(!printed.contains("\ntype Product"), "{printed}");
+    assert!(printed.contains("extend type Product @id("), "{printed}");
+    // Imports and namespaced directives are printed as written
+    assert!(printed.contains(r#"as: "@id""#), "{printed}");
+    assert!(
+        printed.contains(r#"@federation__requires(fields: "weight")"#),
+        "{printed}"
+    );
+
+    let reparsed = parse(&printed);
+    assert_eq!(reparsed.to_string(), printed);
+}
+
+#[test]
+fn fingerprint() {
+    let schema = Schema::parse_and_validate(
+        r#"
+        "The root"
+        type Query { b(y: Int, x: Int): Int a: Color }
+        enum Color { RED GREEN }
+        "#,
+        "a.graphql",
+    )
+    .unwrap();
+    let equivalent = Schema::parse_and_validate(
+        r#"
+        schema { query: Query }
+        enum Color { GREEN RED }
+        "The root" type Query { a: Color }
+        extend type Query { b(x: Int, y: Int): Int }
+        "#,
+        "b.graphql",
+    )
+    .unwrap();
+    assert_eq!(schema.fingerprint(), equivalent.fingerprint());
+    assert_eq!(schema.fingerprint().to_string().len(), 64);
+
+    let described = Schema::parse_and_validate(
+        r#"
+        "A different description"
+        type Query { b(y: Int, x: Int): Int a: Color }
+        enum Color { RED GREEN }
+        "#,
+        "c.graphql",
+    )
+    .unwrap();
+    assert_ne!(schema.fingerprint(), described.fingerprint());
+    let options = FingerprintOptions::new().descriptions(false);
+    assert_eq!(
+        schema.fingerprint_with_options(&options),
+        described.fingerprint_with_options(&options)
+    );
+
+    let changed = Schema::parse_and_validate(
+        "type Query { b(y: Int, x: Int): Int a: Color } enum Color { RED BLUE }",
+        "d.graphql",
+    )
+    .unwrap();
+    assert_ne!(
+        schema.fingerprint_with_options(&options),
+        changed.fingerprint_with_options(&options)
+    );
+}
+
+#[cfg(feature = "snapshot")]
+#[test]
+fn snapshot_round_trip() {
+    use apollo_compiler::schema::SnapshotError;
+
+    let schema = Schema::builder()
+        .parse(
+            r#"
+            "The root query"
+            type Query {
+              search(term: String! = "*", limit: Int = 10, ratio: Float = 0.5): [Result!]!
+              node(id: ID!): Node @deprecated(reason: "use search")
+            }
+            interface Node { id: ID! }
+            type Book implements Node { id: ID! title: String }
+            union Result = Book
+            enum Kind { BOOK MAGAZINE @deprecated }
+            input Filter { kinds: [Kind!] = [BOOK] where: Filter }
+            directive @cache(maxAge: Int, scope: Scope = PUBLIC) repeatable on OBJECT | FIELD_DEFINITION
+            enum Scope { PUBLIC PRIVATE }
+            "#,
+            "schema.graphql",
+        )
+        .parse(
+            r#"
+            extend type Query @cache(maxAge: 60) { book(filter: Filter = {kinds: [BOOK], where: null}): Book }
+            extend type Book @cache(maxAge: 30)
+            extend enum Kind { COMIC }
+            "#,
+            "extensions.graphql",
+        )
+        .build()
+        .unwrap()
+        .validate()
+        .unwrap();
+
+    let snapshot = schema.to_snapshot();
+    let loaded = Schema::from_snapshot(&snapshot).unwrap();
+    assert_eq!(loaded, schema);
+    assert_eq!(loaded.to_string(), schema.to_string());
+    for name in ["Query", "Book"] {
+        assert_eq!(
+            loaded.get_object(name).unwrap().extensions().len(),
+            schema.get_object(name).unwrap().extensions().len(),
+        );
+    }
+    assert_eq!(
+        loaded.get_enum("Kind").unwrap().extensions().len(),
+        schema.get_enum("Kind").unwrap().extensions().len(),
+    );
+    assert_eq!(loaded.sources.len(), schema.sources.len());
+
+    // Locations still point into the original sources
+    let book = &loaded.get_object("Query").unwrap().fields["book"];
+    let location = book.location().unwrap();
+    assert_eq!(
+        loaded.sources[&location.file_id()].path(),
+        std::path::Path::new("extensions.graphql")
+    );
+    let doc = ExecutableDocument::parse_and_validate(&loaded, "{ book { nope } }", "query.graphql");
+    let errors = doc.unwrap_err().errors.to_string();
+    assert!(
+        errors.contains("type `Book` does not have a field `nope`"),
+        "{errors}"
+    );
+
+    assert_eq!(
+        Schema::from_snapshot(&snapshot[..snapshot.len() - 1]).unwrap_err(),
+        SnapshotError::Malformed
+    );
+    assert_eq!(
+        Schema::from_snapshot(b"type Query { a: Int }").unwrap_err(),
+        SnapshotError::NotASnapshot
+    );
+}
+
+#[test]
+fn semantic_eq() {
+    let schema = Schema::parse_and_validate(
+        r#"
+        "Entry points"
+        type Query {
+          search(term: String!, limit: Int = 10): [Result!]! @cost(weight: 2)
+          node(id: ID!): Node
+        }
+        interface Node { id: ID! }
+        type Book implements Node { id: ID! title: String }
+        union Result = Book
+        input Filter { kinds: [Kind!] = [BOOK], range: Range = {min: 0, max: 10} }
+        input Range { min: Int max: Int }
+        enum Kind { BOOK MAGAZINE @deprecated }
+        directive @cost(weight: Int) on FIELD_DEFINITION | OBJECT
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let equivalent = Schema::parse_and_validate(
+        r#"
+        directive @cost(weight: Int) on OBJECT | FIELD_DEFINITION
+        enum Kind { MAGAZINE @deprecated }
+        extend enum Kind { BOOK }
+        input Range { max: Int, min: Int }
+        input Filter { range: Range = {max: 10, min: 0}, kinds: [Kind!] = [BOOK] }
+        union Result = Book
+        type Book implements Node { title: String, id: ID! }
+        interface Node { id: ID! }
+        "Entry points"
+        type Query { node(id: ID!): Node }
+        extend type Query {
+          search(limit: Int = 10, term: String!): [Result!]! @cost(weight: 2)
+        }
+        "#,
+        "equivalent.graphql",
+    )
+    .unwrap();
+    assert!(schema.semantic_eq(&equivalent));
+    assert!(equivalent.semantic_eq(&schema));
+    assert!(schema
+        .semantic_differences(&equivalent, &schema::SemanticEqOptions::new())
+        .is_empty());
+
+    let changed = Schema::parse_and_validate(
+        r#"
+        "The entry points"
+        type Query {
+          search(term: String!, limit: Int = 20): [Result] @cost(weight: 3)
+          node(id: ID!): Node
+        }
+        interface Node { id: ID! }
+        type Book implements Node { id: ID! title: String }
+        type Magazine { title: String }
+        union Result = Book | Magazine
+        input Filter { kinds: [Kind!] = [BOOK], range: Range = {min: 0, max: 10} }
+        input Range { min: Int max: Int }
+        enum Kind { BOOK MAGAZINE }
+        directive @cost(weight: Int) repeatable on FIELD_DEFINITION | OBJECT
+        "#,
+        "changed.graphql",
+    )
+    .unwrap();
+    let differences = |options: &schema::SemanticEqOptions| {
+        schema
+            .semantic_differences(&changed, options)
+            .iter()
+            .map(|difference| difference.to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        differences(&schema::SemanticEqOptions::new()),
+        [
+            "`@cost` is repeatable in only one schema",
+            "`Query` has a different description",
+            "`Query.search` has a different type",
+            "`Query.search(limit:)` has a different default value",
+            "`Query.search` has different directive applications",
+            "`Result` has different members",
+            "`Kind.MAGAZINE` has different directive applications",
+            "`Magazine` is only in the second schema",
+        ]
+    );
+    let options = schema::SemanticEqOptions::new()
+        .descriptions(false)
+        .max_differences(2);
+    assert_eq!(
+        differences(&options),
+        [
+            "`@cost` is repeatable in only one schema",
+            "`Query.search` has a different type",
+        ]
+    );
+    assert!(!schema.semantic_eq_with_options(&changed, &options));
+
+    let described = Schema::parse_and_validate(
+        schema
+            .to_string()
+            .replace("\"Entry points\"", "\"Queries\""),
+        "described.graphql",
+    )
+    .unwrap();
+    assert!(!schema.semantic_eq(&described));
+    let options = schema::SemanticEqOptions::new().descriptions(false);
+    assert!(schema.semantic_eq_with_options(&described, &options));
+}
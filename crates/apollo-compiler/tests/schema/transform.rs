@@ -0,0 +1,350 @@
+use apollo_compiler::schema;
+use apollo_compiler::schema::ExtendedType;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Name;
+use apollo_compiler::Schema;
+
+#[test]
+fn prune_unreachable_types() {
+    let schema = Schema::parse_and_validate(
+        r#"
+        directive @auth(role: Role) on FIELD_DEFINITION
+        directive @internal on OBJECT | FIELD_DEFINITION
+        directive @trace(level: Level) on QUERY
+        type Query { node(id: ID!, filter: Filter): Node @auth search: [Result] }
+        interface Node { id: ID! }
+        type User implements Node { id: ID! }
+        union Result = Post
+        type Post { title: String }
+        input Filter { tag: Tag }
+        enum Tag { A B }
+        enum Role { ADMIN }
+        enum Level { DEBUG }
+        type Orphan { audit: Audit }
+        type Audit @internal { id: ID! }
+        type Unused { id: ID! @internal }
+        scalar Unreferenced
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+
+    let (pruned, report) = schema::prune(&schema, []);
+    assert_eq!(
+        report.removed_types,
+        ["Orphan", "Audit", "Unused", "Unreferenced"]
+    );
+    assert_eq!(report.removed_directives, ["internal"]);
+    for kept in ["User", "Post", "Filter", "Tag", "Role", "Level"] {
+        assert!(pruned.types.contains_key(kept), "{kept}");
+    }
+    assert!(pruned.clone().into_inner().validate().is_ok());
+
+    let (pruned, report) = schema::prune(&schema, ["Orphan", "NotAType"]);
+    assert_eq!(report.removed_types, ["Unused", "Unreferenced"]);
+    assert!(report.removed_directives.is_empty());
+    assert!(pruned.types.contains_key("Audit"));
+
+    let (_, report) = schema::prune(&pruned, ["Orphan"]);
+    assert!(report.is_empty());
+}
+
+#[test]
+fn slice_for_operations() {
+    let schema = Schema::parse_and_validate(
+        r#"
+        directive @auth(role: Role) on FIELD_DEFINITION
+        directive @cached(ttl: Int) on QUERY | FIELD
+        directive @internal on OBJECT
+        type Query { node(id: ID!): Node @auth search(filter: Filter): [Result] stats: Stats }
+        type Mutation { like(id: ID!): Post }
+        type Subscription { updates: Post }
+        interface Node { id: ID! }
+        type User implements Node { id: ID! name: String friends: [User] }
+        type Post implements Node { id: ID! title: String author: User }
+        type Article { author: User headline: String }
+        union Result = Post | User | Article
+        type Stats @internal { count: Int }
+        input Filter { tag: Tag }
+        enum Tag { A B }
+        enum Role { ADMIN }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let document = ExecutableDocument::parse_and_validate(
+        &schema,
+        r#"
+        query Search($filter: Filter) @cached(ttl: 60) {
+          node(id: "1") { id ... on User { name } }
+          search(filter: $filter) { ... on Article { __typename } }
+        }
+        mutation Like { like(id: "1") { ...PostTitle } }
+        fragment PostTitle on Post { title }
+        "#,
+        "operations.graphql",
+    )
+    .unwrap();
+    let sliced = schema::slice_for_operations(&schema, [&document]);
+    let field_names = |name: &str| -> Vec<String> {
+        match &sliced.types[name] {
+            ExtendedType::Object(def) => def.fields.keys().map(|k| k.to_string()).collect(),
+            ExtendedType::Interface(def) => def.fields.keys().map(|k| k.to_string()).collect(),
+            _ => panic!("expected an object or interface type"),
+        }
+    };
+    assert_eq!(field_names("Query"), ["node", "search"]);
+    assert_eq!(field_names("Mutation"), ["like"]);
+    assert_eq!(field_names("Node"), ["id"]);
+    // `id` is kept in implementations of the kept `Node.id`
+    assert_eq!(field_names("User"), ["id", "name"]);
+    assert_eq!(field_names("Post"), ["id", "title"]);
+    // Only named in a type condition: the first field of a leaf type is kept
+    assert_eq!(field_names("Article"), ["headline"]);
+    for kept in ["Result", "Filter", "Tag", "Role"] {
+        assert!(sliced.types.contains_key(kept), "{kept}");
+    }
+    for removed in ["Subscription", "Stats"] {
+        assert!(!sliced.types.contains_key(removed), "{removed}");
+    }
+    assert!(sliced.schema_definition.mutation.is_some());
+    assert!(sliced.schema_definition.subscription.is_none());
+    assert!(sliced.directive_definitions.contains_key("auth"));
+    assert!(sliced.directive_definitions.contains_key("cached"));
+    assert!(!sliced.directive_definitions.contains_key("internal"));
+    assert!(sliced.clone().into_inner().validate().is_ok());
+}
+
+#[test]
+fn remove_annotated() {
+    let schema = Schema::parse_and_validate(
+        r#"
+        directive @inaccessible on OBJECT | FIELD_DEFINITION | INPUT_FIELD_DEFINITION | ENUM_VALUE
+        directive @tag(name: String!, scope: Scope) repeatable on FIELD_DEFINITION
+        type Query {
+          node(id: ID!): Node
+          search(filter: Filter, sort: Sort = SECRET): [Result]
+          secret: Secret
+          byKey(key: Key!): String
+        }
+        interface Node { id: ID! internalId: ID }
+        type User implements Node {
+          id: ID!
+          internalId: ID @inaccessible
+          name: String @tag(name: "profile", scope: PRIVATE)
+        }
+        type Secret @inaccessible { value: String }
+        type Admin { secret: Secret }
+        union Result = User | Admin
+        input Filter { name: String key: Key }
+        input Key { id: ID! @inaccessible }
+        enum Sort { NAME SECRET @inaccessible }
+        enum Scope { PUBLIC PRIVATE @inaccessible }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let (filtered, report) = schema::remove_annotated(&schema, "inaccessible");
+    let annotated: Vec<_> = report.annotated.iter().map(|c| c.to_string()).collect();
+    assert_eq!(
+        annotated,
+        [
+            "User.internalId",
+            "Secret",
+            "Key.id",
+            "Sort.SECRET",
+            "Scope.PRIVATE"
+        ]
+    );
+    let cascaded: Vec<_> = report
+        .cascaded
+        .iter()
+        .map(|removal| format!("{} <- {}", removal.coordinate, removal.cause))
+        .collect();
+    assert_eq!(
+        cascaded,
+        [
+            "Node.internalId <- User.internalId",
+            "Admin.secret <- Secret",
+            "Admin <- Admin.secret",
+            "Key <- Key.id",
+            "Query.search(sort:) <- Sort.SECRET",
+            "Query.secret <- Secret",
+            "Query.byKey(key:) <- Key",
+            "Query.byKey <- Query.byKey(key:)",
+            "Filter.key <- Key",
+        ]
+    );
+
+    for removed in ["Secret", "Admin", "Key"] {
+        assert!(!filtered.types.contains_key(removed), "{removed}");
+    }
+    let ExtendedType::Union(result) = &filtered.types["Result"] else {
+        panic!("expected a union type")
+    };
+    assert_eq!(result.members.len(), 1);
+    let name = filtered.type_field("User", "name").unwrap();
+    assert_eq!(name.to_string(), r#"name: String @tag(name: "profile")"#);
+    let search = filtered.type_field("Query", "search").unwrap();
+    assert_eq!(search.to_string(), "search(filter: Filter): [Result]");
+    assert!(filtered.clone().validate().is_ok());
+
+    let (unchanged, report) = schema::remove_annotated(&schema, "notApplied");
+    assert!(report.is_empty());
+    assert_eq!(unchanged, *schema);
+}
+
+#[test]
+fn fold_extensions() {
+    let input = r#"
+        schema { query: Query }
+        extend schema @tag { mutation: Mutation }
+        directive @tag on SCHEMA | SCALAR | OBJECT | ENUM
+        type Query { a: Int }
+        type Mutation { b: Int }
+        interface Node { id: ID! }
+        extend type Query implements Node @tag { id: ID! }
+        enum Color { RED }
+        extend enum Color { GREEN }
+        union Result = Query
+        extend union Result = Mutation
+        extend scalar String @tag
+    "#;
+    let schema = Schema::parse_and_validate(input, "schema.graphql").unwrap();
+    let (folded, provenance) = schema.fold_extensions();
+    let printed = folded.to_string();
+    assert!(!printed.contains("extend type"), "{printed}");
+    assert!(!printed.contains("extend schema"), "{printed}");
+    assert!(
+        printed.contains("type Query implements Node @tag {"),
+        "{printed}"
+    );
+    assert!(
+        printed.contains("union Result = Query | Mutation"),
+        "{printed}"
+    );
+    // Built-in scalars have no printed definition to fold into
+    assert!(printed.contains("extend scalar String"), "{printed}");
+    // Folding does not change the meaning of the schema
+    let reparsed = Schema::parse_and_validate(&printed, "folded.graphql").unwrap();
+    assert_eq!(reparsed.to_string(), printed);
+    assert_eq!(schema.get_object("Query").unwrap().extensions().len(), 1);
+    assert!(folded.get_object("Query").unwrap().extensions().is_empty());
+
+    let components: Vec<_> = provenance
+        .components
+        .iter()
+        .map(|(component, extension)| {
+            let line = extension
+                .location()
+                .unwrap()
+                .line_column(&schema.sources)
+                .unwrap()
+                .line;
+            format!("{component:?} on line {line}")
+        })
+        .collect();
+    assert_eq!(
+        components,
+        [
+            r#"SchemaDirective("tag") on line 3"#,
+            "RootOperation(Mutation) on line 3",
+            r#"TypeDirective { ty: "Query", directive: "tag" } on line 8"#,
+            r#"ImplementedInterface { ty: "Query", interface: "Node" } on line 8"#,
+            r#"TypeAttribute(TypeAttributeCoordinate { ty: "Query", attribute: "id" }) on line 8"#,
+            r#"TypeAttribute(TypeAttributeCoordinate { ty: "Color", attribute: "GREEN" }) on line 10"#,
+            r#"UnionMember { ty: "Result", member: "Mutation" } on line 12"#,
+        ]
+    );
+}
+
+#[test]
+fn rename_types() {
+    let schema = Schema::parse_and_validate(
+        r#"
+        directive @filter(by: Criteria) on FIELD
+        type Query { search(criteria: [Criteria!]): [Result] node: Node }
+        interface Node { id: ID! }
+        type Invoice implements Node { id: ID! status: Status }
+        type Payment implements Node { id: ID! }
+        union Result = Invoice | Payment
+        input Criteria { status: Status, nested: Criteria }
+        enum Status { OPEN PAID }
+        scalar Date
+        "#,
+        "billing.graphql",
+    )
+    .unwrap();
+    let (renamed, renames) = schema::rename_types(&schema, |name| {
+        (name != "Query").then(|| Name::new(&format!("Billing_{name}")).unwrap())
+    })
+    .unwrap();
+    let renames: Vec<_> = renames
+        .iter()
+        .map(|(old, new)| format!("{old} -> {new}"))
+        .collect();
+    assert_eq!(
+        renames,
+        [
+            "Node -> Billing_Node",
+            "Invoice -> Billing_Invoice",
+            "Payment -> Billing_Payment",
+            "Result -> Billing_Result",
+            "Criteria -> Billing_Criteria",
+            "Status -> Billing_Status",
+            "Date -> Billing_Date",
+        ]
+    );
+    let expected = expect_test::expect![[r#"
+        directive @filter(by: Billing_Criteria) on FIELD
+
+        type Query {
+          search(criteria: [Billing_Criteria!]): [Billing_Result]
+          node: Billing_Node
+        }
+
+        interface Billing_Node {
+          id: ID!
+        }
+
+        type Billing_Invoice implements Billing_Node {
+          id: ID!
+          status: Billing_Status
+        }
+
+        type Billing_Payment implements Billing_Node {
+          id: ID!
+        }
+
+        union Billing_Result = Billing_Invoice | Billing_Payment
+
+        input Billing_Criteria {
+          status: Billing_Status
+          nested: Billing_Criteria
+        }
+
+        enum Billing_Status {
+          OPEN
+          PAID
+        }
+
+        scalar Billing_Date
+    "#]];
+    expected.assert_eq(&renamed.to_string());
+    renamed.into_inner().validate().unwrap();
+
+    let conflict = schema::rename_types(&schema, |name| {
+        (name == "Payment").then(|| Name::new("Invoice").unwrap())
+    })
+    .unwrap_err();
+    assert_eq!(
+        conflict.to_string(),
+        "cannot rename `Payment` to `Invoice`: type `Invoice` would have the same name"
+    );
+    let reserved =
+        schema::rename_types(&schema, |_| Some(Name::new("__Type").unwrap())).unwrap_err();
+    assert!(matches!(
+        reserved,
+        schema::RenameTypesError::ReservedName { .. }
+    ));
+}
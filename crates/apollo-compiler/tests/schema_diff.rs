@@ -0,0 +1,267 @@
+use apollo_compiler::schema;
+use apollo_compiler::schema::Criticality;
+use apollo_compiler::schema::SchemaChangeKind;
+use apollo_compiler::Schema;
+
+fn diff(old: &str, new: &str) -> Vec<(Criticality, String)> {
+    let old = Schema::parse_and_validate(old, "old.graphql").unwrap();
+    let new = Schema::parse_and_validate(new, "new.graphql").unwrap();
+    schema::diff(&old, &new)
+        .changes
+        .iter()
+        .map(|change| (change.criticality, change.to_string()))
+        .collect()
+}
+
+#[test]
+fn identical_schemas() {
+    let sdl = r#"
+        type Query { a(x: Int = 1): [String!] @deprecated }
+        enum E { A B }
+        directive @d(x: Int) on FIELD
+    "#;
+    assert_eq!(diff(sdl, sdl), []);
+}
+
+#[test]
+fn types() {
+    let old = r#"
+        type Query { a: Int }
+        type Removed { a: Int }
+        type Changed { a: Int }
+    "#;
+    let new = r#"
+        type Query { a: Int }
+        interface Changed { a: Int }
+        scalar Added
+    "#;
+    assert_eq!(
+        diff(old, new),
+        [
+            (
+                Criticality::Breaking,
+                "type `Removed` was removed".to_owned()
+            ),
+            (
+                Criticality::Breaking,
+                "type `Changed` changed from an object type to an interface type".to_owned()
+            ),
+            (Criticality::Safe, "type `Added` was added".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn output_fields() {
+    let old = r#"
+        type Query {
+          nonNull: Int
+          list: [Int]
+          nullable: Int!
+          renamed: String
+          args(a: Int, b: Int!, c: Int = 1, d: Int): Int
+          deprecated: Int
+        }
+    "#;
+    let new = r#"
+        type Query {
+          nonNull: Int!
+          list: [Int!]!
+          nullable: Int
+          renamed: ID
+          args(a: Int!, b: Int, c: Int = 2, e: Int, f: Int!, g: Int! = 0): Int
+          deprecated: Int @deprecated(reason: "use nonNull")
+        }
+    "#;
+    assert_eq!(
+        diff(old, new),
+        [
+            (
+                Criticality::Safe,
+                "field `Query.nonNull` changed type from `Int` to `Int!`".to_owned()
+            ),
+            (
+                Criticality::Safe,
+                "field `Query.list` changed type from `[Int]` to `[Int!]!`".to_owned()
+            ),
+            (
+                Criticality::Breaking,
+                "field `Query.nullable` changed type from `Int!` to `Int`".to_owned()
+            ),
+            (
+                Criticality::Breaking,
+                "field `Query.renamed` changed type from `String` to `ID`".to_owned()
+            ),
+            (
+                Criticality::Breaking,
+                "argument `Query.args(a:)` changed type from `Int` to `Int!`".to_owned()
+            ),
+            (
+                Criticality::Safe,
+                "argument `Query.args(b:)` changed type from `Int!` to `Int`".to_owned()
+            ),
+            (
+                Criticality::Dangerous,
+                "default value of `Query.args(c:)` changed from `1` to `2`".to_owned()
+            ),
+            (
+                Criticality::Breaking,
+                "argument `Query.args(d:)` was removed".to_owned()
+            ),
+            (
+                Criticality::Safe,
+                "argument `Query.args(e:)` was added".to_owned()
+            ),
+            (
+                Criticality::Breaking,
+                "argument `Query.args(f:)` was added".to_owned()
+            ),
+            (
+                Criticality::Safe,
+                "argument `Query.args(g:)` was added".to_owned()
+            ),
+            (
+                Criticality::Safe,
+                "`Query.deprecated` was deprecated".to_owned()
+            ),
+        ]
+    );
+
+    let old = Schema::parse_and_validate(old, "old.graphql").unwrap();
+    let new = Schema::parse_and_validate(new, "new.graphql").unwrap();
+    let diff = schema::diff(&old, &new);
+    let deprecation = diff.changes.last().unwrap();
+    assert!(matches!(
+        &deprecation.kind,
+        SchemaChangeKind::DeprecationAdded { reason: Some(reason), .. } if reason == "use nonNull"
+    ));
+    assert_eq!(diff.with_criticality(Criticality::Breaking).count(), 5);
+    assert!(diff.has_breaking_changes());
+}
+
+#[test]
+fn input_objects_enums_and_unions() {
+    let old = r#"
+        type Query { a(input: In): E u: U }
+        input In { optional: Int required: Int! removed: Int defaulted: Int! = 1 }
+        enum E { A B }
+        union U = X | Y
+        type X { a: Int }
+        type Y { a: Int }
+        interface I { a: Int }
+    "#;
+    let new = r#"
+        type Query { a(input: In): E u: U }
+        input In { optional: Int! required: Int defaulted: Int! added: Int addedRequired: Int! }
+        enum E { A C }
+        union U = X | Z
+        type X implements I { a: Int }
+        type Y { a: Int }
+        type Z { a: Int }
+        interface I { a: Int }
+    "#;
+    assert_eq!(
+        diff(old, new),
+        [
+            (
+                Criticality::Breaking,
+                "field `In.optional` changed type from `Int` to `Int!`".to_owned()
+            ),
+            (
+                Criticality::Safe,
+                "field `In.required` changed type from `Int!` to `Int`".to_owned()
+            ),
+            (
+                Criticality::Breaking,
+                "field `In.removed` was removed".to_owned()
+            ),
+            (
+                Criticality::Breaking,
+                "default value `1` was removed from `In.defaulted`".to_owned()
+            ),
+            (Criticality::Safe, "field `In.added` was added".to_owned()),
+            (
+                Criticality::Breaking,
+                "field `In.addedRequired` was added".to_owned()
+            ),
+            (
+                Criticality::Breaking,
+                "enum value `E.B` was removed".to_owned()
+            ),
+            (
+                Criticality::Dangerous,
+                "enum value `E.C` was added".to_owned()
+            ),
+            (
+                Criticality::Breaking,
+                "`Y` was removed from union `U`".to_owned()
+            ),
+            (
+                Criticality::Dangerous,
+                "`Z` was added to union `U`".to_owned()
+            ),
+            (Criticality::Dangerous, "`X` now implements `I`".to_owned()),
+            (Criticality::Safe, "type `Z` was added".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn root_operations_and_directives() {
+    let old = r#"
+        type Query { a: Int }
+        type Mutation { a: Int }
+        directive @a(x: Int) on FIELD | QUERY
+        directive @b repeatable on FIELD
+        directive @removed on FIELD
+    "#;
+    let new = r#"
+        type Query { a: Int }
+        type Subscription { a: Int }
+        directive @a(x: Int, y: Int!) on FIELD | MUTATION
+        directive @b on FIELD
+        directive @added repeatable on FIELD
+    "#;
+    assert_eq!(
+        diff(old, new),
+        [
+            (
+                Criticality::Breaking,
+                "mutation root operation type `Mutation` was removed".to_owned()
+            ),
+            (
+                Criticality::Safe,
+                "subscription root operation type `Subscription` was added".to_owned()
+            ),
+            (
+                Criticality::Breaking,
+                "type `Mutation` was removed".to_owned()
+            ),
+            (
+                Criticality::Safe,
+                "type `Subscription` was added".to_owned()
+            ),
+            (
+                Criticality::Breaking,
+                "location QUERY was removed from directive `@a`".to_owned()
+            ),
+            (
+                Criticality::Safe,
+                "location MUTATION was added to directive `@a`".to_owned()
+            ),
+            (
+                Criticality::Breaking,
+                "argument `@a(y:)` was added".to_owned()
+            ),
+            (
+                Criticality::Breaking,
+                "directive `@b` is no longer repeatable".to_owned()
+            ),
+            (
+                Criticality::Breaking,
+                "directive `@removed` was removed".to_owned()
+            ),
+            (Criticality::Safe, "directive `@added` was added".to_owned()),
+        ]
+    );
+}
@@ -11,9 +11,11 @@ use apollo_compiler::ast;
 use apollo_compiler::collections::IndexMap;
 use apollo_compiler::name;
 use apollo_compiler::parser::FileId;
+use apollo_compiler::parser::Parser;
 use apollo_compiler::schema;
 use apollo_compiler::ty;
 use apollo_compiler::validation::DiagnosticList;
+use apollo_compiler::validation::WithErrors;
 use apollo_compiler::Schema;
 use expect_test::expect_file;
 use serial_test::serial;
@@ -127,6 +129,71 @@ fn serialize_and_reparse_ast() {
     }
 }
 
+/// Checks that [`Parser::skip_syntax_tree`] gives the same documents, diagnostics,
+/// and limit usage as parsing through the syntax tree, for every test input of both crates
+/// and with every recursion limit these inputs reach.
+#[test]
+#[serial]
+fn parse_without_syntax_tree() {
+    let mut inputs = collect_graphql_files(&test_data_dir(), &["ok", "diagnostics"]);
+    for dir in ["parser", "lexer"] {
+        let dir = project_root().join("apollo-parser/test_data").join(dir);
+        inputs.extend(collect_graphql_files(&dir, &["ok", "err"]));
+    }
+    for (path, input) in &inputs {
+        let compare = |configure: &dyn Fn(Parser) -> Parser| {
+            let mut with_tree = configure(Parser::new());
+            let mut from_tokens = configure(Parser::new().skip_syntax_tree(true));
+            let expected = with_tree.parse_ast(input, "input.graphql");
+            let actual = from_tokens.parse_ast(input, "input.graphql");
+            assert_eq!(
+                parse_result_debug(&actual),
+                parse_result_debug(&expected),
+                "{path:?}"
+            );
+            let reached = (with_tree.recursion_reached(), with_tree.tokens_reached());
+            let reached_from_tokens = (
+                from_tokens.recursion_reached(),
+                from_tokens.tokens_reached(),
+            );
+            assert_eq!(reached_from_tokens, reached, "{path:?}");
+            reached
+        };
+        let (recursion, tokens) = compare(&|parser| parser);
+        for limit in 0..=recursion {
+            compare(&|parser| parser.recursion_limit(limit));
+        }
+        for limit in [tokens / 2, tokens.saturating_sub(1)] {
+            compare(&|parser| parser.token_limit(limit));
+        }
+    }
+}
+
+/// Debug output of a parse result, including source locations which `==` ignores,
+/// without the file ID that differs between two parses
+fn parse_result_debug(result: &Result<ast::Document, WithErrors<ast::Document>>) -> String {
+    let (document, errors) = match result {
+        Ok(document) => (document, String::new()),
+        // Not rendered with ariadne, which can panic on limit errors inside a multi-byte character
+        Err(invalid) => (
+            &invalid.partial,
+            invalid
+                .errors
+                .iter()
+                .map(|diagnostic| {
+                    format!(
+                        "{:?} {}\n",
+                        diagnostic.line_column_range(),
+                        diagnostic.error
+                    )
+                })
+                .collect(),
+        ),
+    };
+    let file_id = document.sources.keys().next().unwrap();
+    format!("{:?}\n{errors}", document.definitions).replace(&format!("@{file_id:?}"), "@")
+}
+
 /// Compares input code taken from a `.graphql` file in test_fixtures and its
 /// expected output in the corresponding `.txt` file.
 ///
@@ -0,0 +1,128 @@
+use apollo_compiler::schema::Supergraph;
+use apollo_compiler::Schema;
+
+const DEFINITIONS: &str = r#"
+  directive @join__graph(name: String!, url: String!) on ENUM_VALUE
+  directive @join__type(graph: join__Graph!, key: String) repeatable on OBJECT | INTERFACE | UNION | ENUM | INPUT_OBJECT | SCALAR
+  directive @join__field(graph: join__Graph, external: Boolean) repeatable on FIELD_DEFINITION | INPUT_FIELD_DEFINITION
+  directive @join__implements(graph: join__Graph!, interface: String!) repeatable on OBJECT | INTERFACE
+  directive @join__unionMember(graph: join__Graph!, member: String!) repeatable on UNION
+  directive @join__enumValue(graph: join__Graph!) repeatable on ENUM_VALUE
+"#;
+
+fn supergraph(sdl: &str) -> Result<Supergraph, String> {
+    let schema =
+        Schema::parse_and_validate(format!("{DEFINITIONS}{sdl}"), "supergraph.graphql").unwrap();
+    Supergraph::from_schema(&schema).map_err(|e| e.errors.to_string())
+}
+
+fn names<'a>(
+    subgraphs: impl Iterator<Item = &'a apollo_compiler::schema::Subgraph>,
+) -> Vec<&'a str> {
+    subgraphs.map(|subgraph| subgraph.name.as_str()).collect()
+}
+
+#[test]
+fn field_ownership() {
+    let supergraph = supergraph(
+        r#"
+          enum join__Graph {
+            ACCOUNTS @join__graph(name: "accounts", url: "http://accounts")
+            REVIEWS @join__graph(name: "reviews", url: "http://reviews")
+          }
+          type Query @join__type(graph: ACCOUNTS) @join__type(graph: REVIEWS) {
+            me: User @join__field(graph: ACCOUNTS)
+          }
+          type User @join__type(graph: ACCOUNTS, key: "id") @join__type(graph: REVIEWS, key: "id") {
+            id: ID!
+            name: String @join__field(graph: ACCOUNTS) @join__field(graph: REVIEWS, external: true)
+            reviews: [String] @join__field(graph: REVIEWS)
+          }
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(names(supergraph.subgraphs()), ["accounts", "reviews"]);
+    assert_eq!(
+        supergraph.subgraph("REVIEWS").unwrap().url,
+        "http://reviews"
+    );
+    assert_eq!(
+        names(supergraph.type_subgraphs("User")),
+        ["accounts", "reviews"]
+    );
+    // Fields without `@join__field` belong to every subgraph of their type
+    assert_eq!(
+        names(supergraph.field_subgraphs("User", "id")),
+        ["accounts", "reviews"]
+    );
+    // External fields are not resolved by that subgraph
+    assert_eq!(
+        names(supergraph.field_subgraphs("User", "name")),
+        ["accounts"]
+    );
+    assert_eq!(
+        names(supergraph.field_subgraphs("User", "reviews")),
+        ["reviews"]
+    );
+    assert!(names(supergraph.type_subgraphs("join__Graph")).is_empty());
+}
+
+#[test]
+fn inconsistent_metadata() {
+    let errors = supergraph(
+        r#"
+          enum join__Graph {
+            ACCOUNTS @join__graph(name: "accounts", url: "http://accounts")
+            REVIEWS
+          }
+          type Query @join__type(graph: ACCOUNTS) {
+            reviews: [String] @join__field(graph: REVIEWS)
+            me: String @join__field(graph: ACCOUNTS)
+          }
+          enum Status @join__type(graph: ACCOUNTS) {
+            ACTIVE @join__enumValue(graph: ACCOUNTS)
+          }
+        "#,
+    )
+    .unwrap_err();
+    assert!(
+        errors.contains("`join__Graph.REVIEWS` must have a `@join__graph(name:, url:)` directive"),
+        "{errors}"
+    );
+    assert!(
+        errors
+            .contains("`@join__field` refers to `REVIEWS`, which is not a value of `join__Graph`"),
+        "{errors}"
+    );
+
+    let errors = supergraph(
+        r#"
+          enum join__Graph {
+            ACCOUNTS @join__graph(name: "accounts", url: "http://accounts")
+            REVIEWS @join__graph(name: "reviews", url: "http://reviews")
+          }
+          type Query @join__type(graph: ACCOUNTS) {
+            reviews: [String] @join__field(graph: REVIEWS)
+          }
+        "#,
+    )
+    .unwrap_err();
+    assert!(
+        errors.contains(
+            "`@join__field(graph: REVIEWS)` is used in `Query`, \
+             which has no `@join__type(graph: REVIEWS)`"
+        ),
+        "{errors}"
+    );
+
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let errors = Supergraph::from_schema(&schema)
+        .unwrap_err()
+        .errors
+        .to_string();
+    assert!(
+        errors.contains("supergraph schema must define a `join__Graph` enum"),
+        "{errors}"
+    );
+}
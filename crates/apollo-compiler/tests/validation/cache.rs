@@ -0,0 +1,88 @@
+use apollo_compiler::validation::DocumentSet;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+
+#[test]
+fn validation_cache() {
+    use apollo_compiler::validation::ContentHash;
+    use apollo_compiler::validation::InMemoryValidationCacheStore;
+    use apollo_compiler::validation::ValidationCache;
+    use apollo_compiler::validation::ValidationCacheKey;
+    use apollo_compiler::validation::ValidationCacheStore;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct CountingStore {
+        inner: InMemoryValidationCacheStore,
+        hits: Mutex<usize>,
+    }
+
+    impl ValidationCacheStore for CountingStore {
+        fn contains(&self, key: &ValidationCacheKey) -> bool {
+            let hit = self.inner.contains(key);
+            *self.hits.lock().unwrap() += usize::from(hit);
+            hit
+        }
+
+        fn insert(&self, key: ValidationCacheKey) {
+            self.inner.insert(key)
+        }
+    }
+
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let cache = ValidationCache::new(&schema, CountingStore::default());
+    let parse = |source: &str| ExecutableDocument::parse(&schema, source, "query.graphql").unwrap();
+
+    cache.validate(parse("{ a }")).unwrap();
+    // Formatting and file names do not matter
+    cache.validate(parse("query {\n  a\n}")).unwrap();
+    assert_eq!(*cache.store().hits.lock().unwrap(), 1);
+
+    // Invalid documents are not recorded
+    cache.validate(parse("query($v: Int) { a }")).unwrap_err();
+    cache.validate(parse("query($v: Int) { a }")).unwrap_err();
+    assert_eq!(*cache.store().hits.lock().unwrap(), 1);
+
+    let key = cache.key(&parse("{ a }"));
+    assert_eq!(key.document, ContentHash::of_document(&parse("{a}")));
+    assert_eq!(key.document.to_string().len(), 64);
+    assert_eq!(
+        ContentHash::from_bytes(*key.schema.as_bytes()),
+        ContentHash::of_schema(&schema)
+    );
+
+    let other_schema =
+        Schema::parse_and_validate("type Query { a: Int b: Int }", "schema.graphql").unwrap();
+    assert_ne!(ContentHash::of_schema(&other_schema), key.schema);
+}
+
+#[test]
+fn document_set_revalidates_changed_documents() {
+    let schema =
+        Schema::parse_and_validate("type Query { a: Int b: Int }", "schema.graphql").unwrap();
+    let parse =
+        |source_text| ExecutableDocument::parse(&schema, source_text, "doc.graphql").unwrap();
+    let mut documents = DocumentSet::new(schema.clone());
+    documents.insert(1, parse("{ a }"));
+    documents.insert(2, parse("query($unused: Int) { a }"));
+    let counts: Vec<_> = documents
+        .all_diagnostics()
+        .map(|(key, diagnostics)| (*key, diagnostics.len()))
+        .collect();
+    assert_eq!(counts, [(1, 0), (2, 1)]);
+
+    documents.insert(2, parse("{ b }"));
+    assert!(documents.is_validated(&1));
+    assert!(!documents.is_validated(&2));
+    assert!(documents.diagnostics(&2).unwrap().is_empty());
+
+    documents.remove(&2);
+    assert!(documents.is_validated(&1));
+    assert!(documents.diagnostics(&2).is_none());
+
+    let schema = Schema::parse_and_validate("type Query { a: [Int] }", "schema.graphql").unwrap();
+    documents.set_schema(schema);
+    assert!(!documents.is_validated(&1));
+    assert!(documents.diagnostics(&1).unwrap().is_empty());
+    assert!(documents.is_validated(&1));
+}
@@ -0,0 +1,347 @@
+use super::validate_executable;
+use apollo_compiler::coord;
+use apollo_compiler::validation::Severity;
+use apollo_compiler::validation::Valid;
+use apollo_compiler::validation::ValidationConfig;
+use apollo_compiler::validation::ValidationErrorKind;
+use apollo_compiler::validation::ValidationLimits;
+use apollo_compiler::validation::ValidationRule;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+
+#[test]
+fn validation_config_disables_rules() {
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let input = r#"
+        query($unused: Int) { a }
+        fragment Unused on Query { a }
+    "#;
+
+    let errors = ExecutableDocument::parse(&schema, input, "query.graphql")
+        .unwrap()
+        .validate(&schema)
+        .unwrap_err()
+        .errors;
+    let rules: Vec<_> = errors.iter().map(|d| d.error.rule().unwrap()).collect();
+    assert_eq!(rules, ["UnusedVariable", "UnusedFragment"]);
+
+    let config = ValidationConfig::new().disable_rule("UnusedFragment");
+    let errors = validate_executable(&schema, input, &config).unwrap_err();
+    let rules: Vec<_> = errors.iter().map(|d| d.error.rule().unwrap()).collect();
+    assert_eq!(rules, ["UnusedVariable"]);
+
+    let config = config.disable_rule("UnusedVariable");
+    validate_executable(&schema, input, &config).unwrap();
+}
+
+#[test]
+fn validation_rules_have_stable_names() {
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let errors = ExecutableDocument::parse(&schema, "query($unused: Int) { a }", "query.graphql")
+        .unwrap()
+        .validate(&schema)
+        .unwrap_err()
+        .errors;
+    let error = &errors.iter().next().unwrap().error;
+    assert_eq!(
+        error.validation_rule(),
+        Some(ValidationRule::UnusedVariable)
+    );
+    assert_eq!(error.lint_rule(), None);
+    assert_eq!(error.rule(), Some("UnusedVariable"));
+    assert_eq!(ValidationRule::UnusedVariable.name(), "UnusedVariable");
+}
+
+#[test]
+fn disable_rule_ignores_unknown_identifiers() {
+    let config = ValidationConfig::new()
+        .disable_rule("UnusedFragmnet")
+        .disable_rule("ValidationBudgetExceeded")
+        .disable_rule("ValidationCancelled");
+    assert_eq!(config, ValidationConfig::new());
+    assert!(config.is_rule_enabled("ValidationBudgetExceeded"));
+
+    let config = config.disable_rule("UnusedFragment");
+    assert!(!config.is_rule_enabled("UnusedFragment"));
+    assert_ne!(config, ValidationConfig::new());
+}
+
+#[test]
+fn validation_config_skips_disabled_passes() {
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let input = "{ a a a a a a }";
+    let mut limits = ValidationLimits::default();
+    limits.field_merging_budget = Some(2);
+    let config = ValidationConfig::new().limits(limits);
+    let errors = validate_executable(&schema, input, &config).unwrap_err();
+    let messages: Vec<_> = errors.iter().map(|d| d.error.to_string()).collect();
+    assert_eq!(
+        messages,
+        ["too many fields to check for merging, more than the limit of 2"]
+    );
+
+    // Field merging is not checked at all, so it does not go over its budget
+    let config = config
+        .disable_rule(ValidationRule::ConflictingFieldType.name())
+        .disable_rule(ValidationRule::ConflictingFieldName.name())
+        .disable_rule(ValidationRule::ConflictingFieldArgument.name());
+    validate_executable(&schema, input, &config).unwrap();
+}
+
+#[test]
+fn unused_definitions_severity() {
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let input = r#"
+        query($unused: Int) { a }
+        fragment Unused on Query { a }
+    "#;
+
+    let config = ValidationConfig::new().unused_fragment_severity(Some(Severity::Warning));
+    let errors = validate_executable(&schema, input, &config).unwrap_err();
+    let severities: Vec<_> = errors
+        .iter()
+        .map(|d| (d.error.rule().unwrap(), d.error.severity()))
+        .collect();
+    assert_eq!(
+        severities,
+        [
+            ("UnusedVariable", Severity::Error),
+            ("UnusedFragment", Severity::Warning)
+        ]
+    );
+
+    let config = config.unused_variable_severity(Some(Severity::Warning));
+    let warnings = validate_executable(&schema, input, &config).unwrap();
+    assert_eq!(warnings.len(), 2);
+    assert!(!warnings.has_errors());
+
+    let config = config
+        .unused_fragment_severity(None)
+        .unused_variable_severity(None);
+    validate_executable(&schema, input, &config).unwrap();
+
+    // Back to the default
+    let config = config.unused_fragment_severity(Some(Severity::Error));
+    let errors = validate_executable(&schema, input, &config).unwrap_err();
+    assert!(errors.has_errors());
+}
+
+#[test]
+fn rule_registry() {
+    use apollo_compiler::validation::rule_info;
+    use apollo_compiler::validation::rules;
+    use apollo_compiler::validation::LintRule;
+
+    let ids: Vec<_> = rules().iter().map(|rule| rule.id).collect();
+    assert!(ids.windows(2).all(|pair| pair[0] < pair[1]), "{ids:?}");
+    assert!(rules().iter().all(|rule| !rule.description.is_empty()));
+
+    let rule = rule_info("ConflictingFieldType").unwrap();
+    assert_eq!(rule.default_severity, Severity::Error);
+    assert_eq!(rule.kind, ValidationErrorKind::InvalidSelection);
+    assert_eq!(rule.spec_reference.unwrap().section, "5.3.2");
+    assert!(rule.enabled_by_default);
+
+    let rule = rule_info("DeprecatedUsage").unwrap();
+    assert_eq!(rule.default_severity, Severity::Warning);
+    assert_eq!(rule.spec_reference, None);
+    assert!(!rule.enabled_by_default);
+
+    let rule = rule_info(LintRule::OperationNameRequired.name()).unwrap();
+    assert_eq!(rule.default_severity, Severity::Lint);
+    assert_eq!(rule.kind, ValidationErrorKind::Lint);
+    assert!(!rule.enabled_by_default);
+    assert!(rule_info("NotARule").is_none());
+
+    // Every reported diagnostic comes from a listed rule, with its default severity
+    let schema = Schema::parse(
+        "type Query { a: Missing b(x: Int): Int } enum empty",
+        "schema.graphql",
+    )
+    .unwrap();
+    let input = "query($v: Int) { b b(x: 1) c ...F } fragment G on Query { a }";
+    let valid_schema = Valid::assume_valid_ref(&schema);
+    let diagnostics = [
+        schema.validation_diagnostics(&ValidationConfig::new()),
+        ExecutableDocument::parse_and_validate(valid_schema, input, "query.graphql")
+            .unwrap_err()
+            .errors,
+        schema.lint(&Default::default()),
+    ];
+    let mut checked = 0;
+    for diagnostic in diagnostics.iter().flat_map(|list| list.iter()) {
+        let id = diagnostic.error.rule().unwrap();
+        let rule = rule_info(id).unwrap_or_else(|| panic!("{id} is not listed"));
+        assert_eq!(rule.default_severity, diagnostic.error.severity(), "{id}");
+        assert_eq!(rule.kind, diagnostic.error.kind(), "{id}");
+        checked += 1;
+    }
+    assert!(checked >= 6, "{checked}");
+}
+
+#[test]
+fn suppressed_diagnostics() {
+    let schema = Schema::parse_and_validate(
+        "type Query { a: Int b(x: Int!): Int c(y: Int!): Int }",
+        "schema.graphql",
+    )
+    .unwrap();
+    let input = r#"
+        query {
+          a
+          # apollo-disable-next-line RequiredArgument
+          b
+          c
+        }
+        fragment Unused on Query { a }
+    "#;
+    let document = ExecutableDocument::parse(&schema, input, "query.graphql").unwrap();
+
+    // Comments are ignored unless enabled
+    let rules: Vec<_> = document
+        .validation_diagnostics(&schema, &ValidationConfig::new())
+        .iter()
+        .map(|d| d.error.rule().unwrap())
+        .collect();
+    assert_eq!(
+        rules,
+        ["RequiredArgument", "RequiredArgument", "UnusedFragment"]
+    );
+
+    let config = ValidationConfig::new()
+        .inline_suppressions(true)
+        .suppress("query.graphql", "UnusedFragment", None)
+        .suppress(
+            "query.graphql",
+            "RequiredArgument",
+            Some(coord!(Query.c(y:)).into()),
+        );
+    let diagnostics = document.validation_diagnostics(&schema, &config);
+    assert!(diagnostics.is_empty(), "{diagnostics}");
+    let suppressed: Vec<_> = diagnostics
+        .suppressed()
+        .map(|d| d.error.coordinate().map(|c| c.to_string()))
+        .collect();
+    assert_eq!(
+        suppressed,
+        [
+            Some("Query.b(x:)".to_owned()),
+            Some("Query.c(y:)".to_owned()),
+            None
+        ]
+    );
+    document
+        .clone()
+        .validate_with_config(&schema, &config)
+        .unwrap();
+
+    // Suppressions only apply to the given file and coordinate
+    let config = ValidationConfig::new()
+        .suppress("other.graphql", "UnusedFragment", None)
+        .suppress(
+            "query.graphql",
+            "RequiredArgument",
+            Some(coord!(Query.b(x:)).into()),
+        );
+    let diagnostics = document.validation_diagnostics(&schema, &config);
+    let rules: Vec<_> = diagnostics
+        .iter()
+        .map(|d| d.error.coordinate().map(|c| c.to_string()))
+        .collect();
+    assert_eq!(rules, [Some("Query.c(y:)".to_owned()), None]);
+    assert_eq!(diagnostics.suppressed_len(), 1);
+}
+
+#[test]
+fn validation_progress() {
+    use apollo_compiler::validation::ValidationPhase;
+    use std::ops::ControlFlow;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let cancel_at = Arc::new(Mutex::new(None));
+    let config = ValidationConfig::new().on_progress({
+        let reported = reported.clone();
+        let cancel_at = cancel_at.clone();
+        move |progress| {
+            reported.lock().unwrap().push(*progress);
+            if *cancel_at.lock().unwrap() == Some(progress.phase) {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        }
+    });
+
+    let sdl = "type Query { a: Int b: Pet } interface Pet { name: String }";
+    let schema = Schema::parse(sdl, "schema.graphql").unwrap();
+    let schema = schema.validate_with_config(&config).unwrap();
+    let phases: Vec<_> = reported.lock().unwrap().iter().map(|p| p.phase).collect();
+    assert_eq!(
+        phases,
+        [
+            ValidationPhase::SchemaDefinition,
+            ValidationPhase::ScalarTypes,
+            ValidationPhase::EnumTypes,
+            ValidationPhase::UnionTypes,
+            ValidationPhase::InterfaceTypes,
+            ValidationPhase::DirectiveDefinitions,
+            ValidationPhase::InputObjectTypes,
+            ValidationPhase::ObjectTypes,
+        ]
+    );
+    let reported_steps: Vec<_> = std::mem::take(&mut *reported.lock().unwrap());
+    let total = reported_steps[0].total;
+    assert!(total > 0);
+    assert!(reported_steps.iter().all(|p| p.total == total));
+    assert!(reported_steps
+        .windows(2)
+        .all(|pair| pair[0].completed <= pair[1].completed));
+
+    let input = "query GetA { a } fragment F on Pet { name }";
+    let document = ExecutableDocument::parse(&schema, input, "query.graphql").unwrap();
+    *cancel_at.lock().unwrap() = Some(ValidationPhase::FieldMerging);
+    let errors = document
+        .validate_with_config(&schema, &config)
+        .unwrap_err()
+        .errors;
+    let phases: Vec<_> = reported.lock().unwrap().iter().map(|p| p.phase).collect();
+    assert_eq!(
+        phases,
+        [ValidationPhase::Operations, ValidationPhase::FieldMerging]
+    );
+    // Rules that ran before cancellation still report errors.
+    // The cancellation has no location, so it is not ordered relative to other diagnostics.
+    let mut messages: Vec<_> = errors.iter().map(|d| d.error.to_string()).collect();
+    messages.sort();
+    assert_eq!(
+        messages,
+        [
+            "fragment `F` must be used in an operation",
+            "validation was cancelled before it completed",
+        ]
+    );
+    let cancelled = errors
+        .iter()
+        .find(|d| d.error.to_string().contains("cancelled"))
+        .unwrap();
+    assert_eq!(cancelled.error.rule(), None);
+    assert_eq!(cancelled.error.kind(), ValidationErrorKind::Limit);
+}
+
+#[test]
+fn diagnostics_order_does_not_depend_on_scheduling() {
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let input: String = (0..50)
+        .map(|i| format!("query Q{i}($v{i}: Int) {{ a }}\n"))
+        .collect();
+    let errors = ExecutableDocument::parse_and_validate(&schema, input, "query.graphql")
+        .unwrap_err()
+        .errors;
+    let messages: Vec<_> = errors.iter().map(|d| d.error.to_string()).collect();
+    let expected: Vec<_> = (0..50)
+        .map(|i| format!("unused variable: `$v{i}`"))
+        .collect();
+    assert_eq!(messages, expected);
+}
@@ -0,0 +1,101 @@
+use super::validate_executable;
+use super::validate_schema;
+use apollo_compiler::validation::ValidationConfig;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+
+#[test]
+fn custom_scalar_validators() {
+    use apollo_compiler::ast::Value;
+
+    let schema = Schema::parse_and_validate(
+        r#"
+            scalar DateTime
+            input Range { from: DateTime, to: DateTime }
+            type Query { events(after: DateTime, ranges: [Range]): [String] }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let config = ValidationConfig::new().validate_scalar("DateTime", |value| match value {
+        Value::String(s) if s.contains('T') => Ok(()),
+        _ => Err("expected an ISO 8601 date and time".to_owned()),
+    });
+
+    validate_executable(
+        &schema,
+        r#"query($d: DateTime = "2024-01-01T00:00Z") { events(after: $d) }"#,
+        &config,
+    )
+    .unwrap();
+    // Without the config, any value of the right kind is accepted
+    ExecutableDocument::parse_and_validate(&schema, r#"{ events(after: "yesterday") }"#, "q")
+        .unwrap();
+
+    let errors = validate_executable(
+        &schema,
+        r#"query($d: DateTime = "tomorrow") {
+            events(after: "yesterday", ranges: [{ from: "2024-01-01T00:00Z", to: 5 }])
+            other: events(after: $d)
+        }"#,
+        &config,
+    )
+    .unwrap_err();
+    let paths: Vec<_> = errors
+        .iter()
+        .map(|d| d.error.value_path().unwrap().to_string())
+        .collect();
+    assert_eq!(paths, ["$d", "after", "ranges[0].to"]);
+    assert!(errors
+        .to_string()
+        .contains("invalid value for scalar `DateTime`: expected an ISO 8601 date and time"));
+}
+
+#[test]
+fn custom_directive_validators() {
+    let config = ValidationConfig::new().validate_directive("constraint", |application| {
+        let max = application.argument("max").and_then(|v| v.to_i32());
+        let min = application.argument("min").and_then(|v| v.to_i32());
+        if let (Some(min), Some(max)) = (min, max) {
+            if min > max {
+                let location = application.argument("min").unwrap().location();
+                application.error(
+                    location,
+                    format!("`min` ({min}) is greater than `max` ({max})"),
+                );
+            }
+        }
+    });
+    let sdl = r#"
+        directive @constraint(min: Int = 0, max: Int) on ARGUMENT_DEFINITION | FIELD
+        type Query {
+          a(x: Int @constraint(max: 10)): Int
+          b(x: Int @constraint(min: 5, max: 1)): Int
+          c(x: Int @constraint(max: -1)): Int
+        }
+    "#;
+    let errors = validate_schema(sdl, &config).unwrap_err();
+    let messages: Vec<_> = errors.iter().map(|d| d.error.to_string()).collect();
+    assert_eq!(
+        messages,
+        [
+            "invalid `@constraint` directive: `min` (5) is greater than `max` (1)",
+            "invalid `@constraint` directive: `min` (0) is greater than `max` (-1)",
+        ]
+    );
+    assert!(errors
+        .iter()
+        .all(|d| d.error.rule() == Some("InvalidDirectiveApplication")));
+    // Without the config, the schema is valid
+    let schema = Schema::parse_and_validate(sdl, "schema.graphql").unwrap();
+
+    let errors = ExecutableDocument::parse(&schema, "{ a @constraint(min: 3, max: 2) }", "q")
+        .unwrap()
+        .validate_with_config(&schema, &config)
+        .unwrap_err()
+        .errors;
+    assert_eq!(errors.len(), 1);
+    assert!(errors
+        .to_string()
+        .contains("invalid `@constraint` directive: `min` (3) is greater than `max` (2)"));
+}
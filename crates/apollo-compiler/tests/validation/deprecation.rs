@@ -0,0 +1,83 @@
+use super::validate_executable;
+use apollo_compiler::parser::LineColumn;
+use apollo_compiler::validation::Severity;
+use apollo_compiler::validation::ValidationConfig;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+
+#[test]
+fn deprecation_warnings() {
+    let schema = Schema::parse_and_validate(
+        r#"
+            type Query {
+              old: Int @deprecated(reason: "Use `new`")
+              new(sort: [Sort]): Int
+            }
+            enum Sort { ASC DESC @deprecated }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let input = "{ old new(sort: [ASC, DESC]) }";
+
+    // Off by default
+    ExecutableDocument::parse_and_validate(&schema, input, "query.graphql").unwrap();
+
+    let config = ValidationConfig::new().deprecation_warnings(true);
+    let warnings = validate_executable(&schema, input, &config).unwrap();
+    assert!(!warnings.has_errors());
+    let messages: Vec<_> = warnings
+        .iter()
+        .map(|d| {
+            assert_eq!(d.error.severity(), Severity::Warning);
+            d.error.to_string()
+        })
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "`Query.old` is deprecated: Use `new`",
+            "`Sort.DESC` is deprecated: No longer supported",
+        ]
+    );
+    assert!(warnings.to_string().contains("Warning"), "{warnings}");
+}
+
+#[test]
+fn deprecated_arguments_and_input_fields() {
+    let schema = Schema::parse_and_validate(
+        r#"
+            type Query {
+              search(
+                text: String
+                limit: Int @deprecated(reason: "Use `page`")
+                page: Page
+              ): [String]
+            }
+            input Page { size: Int, first: Int @deprecated }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let input = "{ search(text: \"a\", limit: 10, page: { size: 10, first: 0 }) }";
+
+    let config = ValidationConfig::new().deprecation_warnings(true);
+    let warnings = validate_executable(&schema, input, &config).unwrap();
+    assert!(!warnings.has_errors());
+    let messages: Vec<_> = warnings.iter().map(|d| d.error.to_string()).collect();
+    assert_eq!(
+        messages,
+        [
+            "`Query.search(limit:)` is deprecated: Use `page`",
+            "`Page.first` is deprecated: No longer supported",
+        ]
+    );
+    let json = warnings.iter().next().unwrap().to_json();
+    assert_eq!(
+        json.locations,
+        [LineColumn {
+            line: 1,
+            column: 21
+        }]
+    );
+}
@@ -0,0 +1,403 @@
+use apollo_compiler::name;
+use apollo_compiler::parser::LineColumn;
+use apollo_compiler::validation::SerializedDiagnostic;
+use apollo_compiler::validation::Severity;
+use apollo_compiler::validation::Valid;
+use apollo_compiler::validation::ValidationErrorKind;
+use apollo_compiler::validation::ValidationErrors;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+
+#[test]
+fn spec_references() {
+    let schema = Schema::parse_and_validate(
+        "type Query { a(x: Int): Int b: String c: String }",
+        "schema.graphql",
+    )
+    .unwrap();
+    let input = "query($v: Int) { a(x: 1, x: 2) ...F ab: b ab: c }";
+    let errors = ExecutableDocument::parse_and_validate(&schema, input, "query.graphql")
+        .unwrap_err()
+        .errors;
+    let references: Vec<_> = errors
+        .iter()
+        .map(|d| {
+            let reference = d.error.spec_reference().unwrap();
+            (reference.to_string(), reference.url)
+        })
+        .collect();
+    assert_eq!(
+        references,
+        [
+            (
+                "5.8.4 All Variables Used".to_owned(),
+                "https://spec.graphql.org/October2021/#sec-All-Variables-Used"
+            ),
+            (
+                "5.4.2 Argument Uniqueness".to_owned(),
+                "https://spec.graphql.org/October2021/#sec-Argument-Uniqueness"
+            ),
+            (
+                "5.5.2.1 Fragment spread target defined".to_owned(),
+                "https://spec.graphql.org/October2021/#sec-Fragment-spread-target-defined"
+            ),
+            (
+                "5.3.2 Field Selection Merging".to_owned(),
+                "https://spec.graphql.org/October2021/#sec-Field-Selection-Merging"
+            ),
+        ],
+        "{errors}"
+    );
+
+    // Lints are not from the specification
+    let lints = schema.lint(&Default::default());
+    assert!(!lints.is_empty());
+    assert!(lints.iter().all(|d| d.error.spec_reference().is_none()));
+}
+
+#[test]
+fn validation_error_kinds() {
+    let schema = Schema::parse_and_validate(
+        "type Query { a(x: Int): Int b: String c: String }",
+        "schema.graphql",
+    )
+    .unwrap();
+    let input = "query($v: Int) { a(x: 1, x: 2) ...F ab: b ab: c }";
+    let errors: ValidationErrors =
+        ExecutableDocument::parse_and_validate(&schema, input, "query.graphql")
+            .unwrap_err()
+            .into();
+    let kinds: Vec<_> = errors.errors.iter().map(|error| error.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            ValidationErrorKind::Unused,
+            ValidationErrorKind::NameCollision,
+            ValidationErrorKind::UndefinedFragment,
+            ValidationErrorKind::InvalidSelection,
+        ],
+        "{errors}"
+    );
+    let error = &errors.errors[1];
+    assert_eq!(error.rule.as_deref(), Some("UniqueArgument"));
+    assert_eq!(error.severity, Severity::Error);
+    let location = error.location.as_ref().unwrap();
+    assert_eq!(location.file, std::path::Path::new("query.graphql"));
+    assert_eq!(
+        location.start,
+        LineColumn {
+            line: 1,
+            column: 26
+        }
+    );
+
+    fn load(sdl: &str) -> Result<Valid<Schema>, ValidationErrors> {
+        Ok(Schema::parse_and_validate(sdl, "schema.graphql")?)
+    }
+    let sdl = r#"
+        schema { query: Query query: Query }
+        type Query { a: Missing b: Int b: Int }
+        type Query { c: Int }
+        type Query {
+    "#;
+    let errors = load(sdl).unwrap_err();
+    let kinds: Vec<_> = errors.errors.iter().map(|error| error.kind).collect();
+    assert!(kinds.contains(&ValidationErrorKind::Syntax), "{errors}");
+    assert!(
+        kinds.contains(&ValidationErrorKind::RootOperationType),
+        "{errors}"
+    );
+    assert!(
+        kinds.contains(&ValidationErrorKind::NameCollision),
+        "{errors}"
+    );
+    assert!(
+        kinds.contains(&ValidationErrorKind::UndefinedType),
+        "{errors}"
+    );
+
+    let json = serde_json::to_value(&errors.errors[0]).unwrap();
+    assert!(json["kind"].is_string());
+    let round_trip: ValidationErrors =
+        serde_json::from_str(&serde_json::to_string(&errors).unwrap()).unwrap();
+    assert_eq!(round_trip, errors);
+}
+
+#[test]
+fn serialize_diagnostics() {
+    let input = "type Query { a: Int }\ntype Query { b: Int }\n";
+    let errors = Schema::parse_and_validate(input, "schema.graphql")
+        .unwrap_err()
+        .errors;
+    let json = serde_json::to_value(&errors).unwrap();
+    let expected = serde_json::json!([{
+        "code": null,
+        "message": "the type `Query` is defined multiple times in the schema",
+        "severity": "error",
+        "location": {
+            "file": "schema.graphql",
+            "startOffset": 27,
+            "endOffset": 32,
+            "start": { "line": 2, "column": 6 },
+            "end": { "line": 2, "column": 11 },
+        },
+        "labels": [
+            {
+                "location": {
+                    "file": "schema.graphql",
+                    "startOffset": 5,
+                    "endOffset": 10,
+                    "start": { "line": 1, "column": 6 },
+                    "end": { "line": 1, "column": 11 },
+                },
+                "message": "previous definition of `Query` here",
+            },
+            {
+                "location": {
+                    "file": "schema.graphql",
+                    "startOffset": 27,
+                    "endOffset": 32,
+                    "start": { "line": 2, "column": 6 },
+                    "end": { "line": 2, "column": 11 },
+                },
+                "message": "`Query` redefined here",
+            },
+        ],
+        "help": "remove or rename one of the definitions, or use `extend`",
+    }]);
+    assert_eq!(json, expected);
+
+    let diagnostics: Vec<SerializedDiagnostic> = serde_json::from_value(json).unwrap();
+    assert_eq!(
+        diagnostics[0],
+        errors.iter().next().unwrap().to_serialized()
+    );
+}
+
+#[cfg(feature = "sarif")]
+#[test]
+fn sarif_output() {
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let input = "query($unused: Int) { a }\nfragment Unused on Query { a }";
+    let errors = ExecutableDocument::parse(&schema, input, "query.graphql")
+        .unwrap()
+        .validate(&schema)
+        .unwrap_err()
+        .errors;
+    let sarif = serde_json::to_value(errors.to_sarif()).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+    let run = &sarif["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "apollo-compiler");
+    assert_eq!(
+        run["tool"]["driver"]["rules"],
+        serde_json::json!([{ "id": "UnusedVariable" }, { "id": "UnusedFragment" }])
+    );
+    let result = &run["results"][1];
+    assert_eq!(result["ruleId"], "UnusedFragment");
+    assert_eq!(result["ruleIndex"], 1);
+    assert_eq!(result["level"], "error");
+    assert_eq!(
+        result["locations"][0]["physicalLocation"],
+        serde_json::json!({
+            "artifactLocation": { "uri": "query.graphql" },
+            "region": {
+                "startLine": 2,
+                "startColumn": 1,
+                "endLine": 2,
+                "endColumn": 31,
+                "byteOffset": 26,
+                "byteLength": 30,
+            },
+        })
+    );
+}
+
+#[cfg(feature = "lsp")]
+#[test]
+fn lsp_diagnostics() {
+    use apollo_compiler::parser::PositionEncoding;
+
+    let schema =
+        Schema::parse_and_validate("type Query { a(s: String): Int }", "schema.graphql").unwrap();
+    // A non-ASCII string value before the error shifts UTF-16 columns from byte offsets
+    let input = r#"query($v: Int, $v: Int) { a(s: "é", b: $v) }"#;
+    let errors = ExecutableDocument::parse(&schema, input, "query.graphql")
+        .unwrap()
+        .validate(&schema)
+        .unwrap_err()
+        .errors;
+    let uri = |file: &apollo_compiler::parser::SourceFile| {
+        lsp_types::Url::parse(&format!("file:///{}", file.path().display())).ok()
+    };
+    let diagnostics: Vec<_> = errors
+        .iter()
+        .map(|d| d.to_lsp(PositionEncoding::Utf16, uri))
+        .collect();
+
+    let unique = &diagnostics[0];
+    assert_eq!(
+        unique.code,
+        Some(lsp_types::NumberOrString::String("UniqueVariable".into()))
+    );
+    assert_eq!(unique.severity, Some(lsp_types::DiagnosticSeverity::ERROR));
+    let related = unique.related_information.as_ref().unwrap();
+    assert_eq!(related.len(), 1);
+    assert_eq!(related[0].message, "previous definition of `$v` here");
+    assert_eq!(related[0].location.uri.as_str(), "file:///query.graphql");
+    assert_eq!(related[0].location.range.start.character, 6);
+
+    let undefined = &diagnostics[1];
+    assert_eq!(
+        undefined.code,
+        Some(lsp_types::NumberOrString::String(
+            "UndefinedArgument".into()
+        ))
+    );
+    assert_eq!(
+        undefined.range,
+        lsp_types::Range {
+            start: lsp_types::Position {
+                line: 0,
+                character: 36
+            },
+            end: lsp_types::Position {
+                line: 0,
+                character: 41
+            },
+        }
+    );
+}
+
+#[test]
+fn did_you_mean_suggestions() {
+    let schema = Schema::parse_and_validate(
+        r#"
+            directive @cacheControl(maxAge: Int) on FIELD
+            type Query { user(id: ID): User }
+            type User { name: String nickname: String }
+            input UserFilter { name: String }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let input = r#"
+        query($filter: UserFiltr) {
+          user(ID: 1) @cachecontrol(maxAge: 60) {
+            nam
+            nickname @cacheControl(maxAg: 5)
+          }
+        }
+    "#;
+    let errors = ExecutableDocument::parse_and_validate(&schema, input, "query.graphql")
+        .unwrap_err()
+        .errors;
+    let suggestions: Vec<_> = errors
+        .iter()
+        .filter(|d| !d.error.suggestions().is_empty())
+        .map(|d| (d.error.rule().unwrap(), d.error.suggestions().to_vec()))
+        .collect();
+    assert_eq!(
+        suggestions,
+        [
+            ("UndefinedDefinition", vec![name!("UserFilter")]),
+            ("UndefinedArgument", vec![name!("id")]),
+            ("UndefinedDirective", vec![name!("cacheControl")]),
+            ("UndefinedField", vec![name!("name")]),
+            ("UndefinedArgument", vec![name!("maxAge")]),
+        ]
+    );
+    let report = errors.to_string();
+    assert!(
+        report.contains("Help: did you mean `@cacheControl`?"),
+        "{report}"
+    );
+}
+
+#[test]
+fn grouped_duplicate_arguments() {
+    let sdl = r#"
+        type Query {
+          field(arg: Int, other: Int, arg: Int, arg: Int): Int
+        }
+    "#;
+    let errors = Schema::parse(sdl, "schema.graphql")
+        .unwrap()
+        .validate()
+        .unwrap_err()
+        .errors;
+    assert_eq!(errors.len(), 1, "{errors}");
+    let report = errors.to_string();
+    assert!(report.contains("the value `arg` is defined multiple times"));
+    assert_eq!(
+        report.matches("previous definition of `arg` here").count(),
+        1
+    );
+    assert_eq!(report.matches("`arg` defined again here").count(), 2);
+
+    let schema = Schema::parse("type Query { field(arg: Int): Int }", "schema.graphql")
+        .unwrap()
+        .validate()
+        .unwrap();
+    let input = "{ field(arg: 1, arg: 2, arg: 3) }";
+    let errors = ExecutableDocument::parse(&schema, input, "query.graphql")
+        .unwrap()
+        .validate(&schema)
+        .unwrap_err()
+        .errors;
+    assert_eq!(errors.len(), 1, "{errors}");
+    let report = errors.to_string();
+    assert!(report.contains("the argument `arg` is provided multiple times"));
+    assert_eq!(report.matches("previously provided `arg` here").count(), 1);
+    assert_eq!(report.matches("`arg` provided again here").count(), 2);
+}
+
+#[test]
+fn grouped_duplicate_definitions() {
+    let sdl = r#"
+        type Query { a: Int a: Int a: Int }
+        extend type Query { a: Int }
+        interface I { b: Int b: Int b: Int }
+        input In { c: Int c: Int c: Int }
+        enum E { D D D }
+    "#;
+    let errors = Schema::parse_and_validate(sdl, "schema.graphql")
+        .unwrap_err()
+        .errors;
+    let messages: Vec<_> = errors.iter().map(|d| d.error.to_string()).collect();
+    assert_eq!(
+        messages,
+        [
+            "duplicate definitions for the `a` field of object type `Query`",
+            "duplicate definitions for the `b` field of interface type `I`",
+            "duplicate definitions for the `c` field of input object type `In`",
+            "duplicate definitions for the `D` value of enum type `E`",
+        ]
+    );
+    let report = errors.to_string();
+    assert_eq!(report.matches("previous definition of `a` here").count(), 1);
+    assert_eq!(report.matches("`a` redefined here").count(), 3);
+    for name in ["b", "c", "D"] {
+        assert_eq!(
+            report.matches(&format!("`{name}` redefined here")).count(),
+            2
+        );
+    }
+
+    let schema =
+        Schema::parse_and_validate("type Query { field(arg: Int): Int }", "schema.graphql")
+            .unwrap();
+    let input = "query($v: Int, $v: Int, $v: Int) { field(arg: $v) }";
+    let errors = ExecutableDocument::parse(&schema, input, "query.graphql")
+        .unwrap()
+        .validate(&schema)
+        .unwrap_err()
+        .errors;
+    assert_eq!(errors.len(), 1, "{errors}");
+    let report = errors.to_string();
+    assert!(report.contains("the variable `$v` is declared multiple times"));
+    assert_eq!(
+        report.matches("previous definition of `$v` here").count(),
+        1
+    );
+    assert_eq!(report.matches("`$v` defined again here").count(), 2);
+}
@@ -0,0 +1,258 @@
+use super::validate_executable;
+use super::validate_schema;
+use apollo_compiler::parser::LineColumn;
+use apollo_compiler::validation::Severity;
+use apollo_compiler::validation::ValidationConfig;
+use apollo_compiler::Schema;
+
+#[test]
+fn unknown_directives() {
+    let schema =
+        Schema::parse_and_validate("type Query { a: Int b: Int }", "schema.graphql").unwrap();
+    let input = "{ a @client b @export(as: \"b\") }";
+    let severities = |errors: &apollo_compiler::validation::DiagnosticList| -> Vec<_> {
+        errors
+            .iter()
+            .map(|d| (d.error.to_string(), d.error.severity()))
+            .collect()
+    };
+
+    let errors = validate_executable(&schema, input, &ValidationConfig::new()).unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert!(errors.has_errors());
+
+    let config = ValidationConfig::new().allow_unknown_directive("client");
+    let errors = validate_executable(&schema, input, &config).unwrap_err();
+    assert_eq!(
+        severities(&errors),
+        [(
+            "cannot find directive `@export` in this document".to_owned(),
+            Severity::Error
+        )]
+    );
+
+    let warnings = validate_executable(
+        &schema,
+        input,
+        &config.clone().unknown_directive_warnings(true),
+    )
+    .unwrap();
+    assert_eq!(
+        severities(&warnings),
+        [(
+            "cannot find directive `@export` in this document".to_owned(),
+            Severity::Warning
+        )]
+    );
+
+    let warnings =
+        validate_executable(&schema, input, &config.allow_unknown_directive("export")).unwrap();
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn directive_argument_default_values() {
+    let sdl = r#"
+        directive @cache(
+          scope: Scope = PUBLIC
+          options: CacheOptions = { maxAge: 60, sort: [ASC, UP], nested: { maxAge: "1" } }
+          labels: [String!] = ["a", null]
+        ) on FIELD_DEFINITION
+        type Query { a: Int @cache }
+        enum Scope { PUBLIC PRIVATE }
+        enum Sort { ASC DESC }
+        input CacheOptions { maxAge: Int, sort: [Sort], nested: CacheOptions }
+    "#;
+    let errors = Schema::parse(sdl, "schema.graphql")
+        .unwrap()
+        .validate()
+        .unwrap_err()
+        .errors;
+    let paths: Vec<_> = errors
+        .iter()
+        .map(|d| d.error.value_path().unwrap().to_string())
+        .collect();
+    assert_eq!(
+        paths,
+        ["options.sort[1]", "options.nested.maxAge", "labels[1]"],
+        "{errors}"
+    );
+    assert!(errors.iter().next().unwrap().to_string().contains("UP"));
+
+    let valid = sdl
+        .replace("[ASC, UP]", "[ASC, DESC]")
+        .replace("maxAge: \"1\"", "maxAge: 1")
+        .replace("[\"a\", null]", "[\"a\"]");
+    Schema::parse_and_validate(valid, "schema.graphql").unwrap();
+}
+
+#[test]
+fn repeated_directive_arguments() {
+    let sdl = r#"
+        directive @tag(name: String!, note: String) repeatable on FIELD_DEFINITION | FIELD
+        type Query {
+          a: Int @tag(name: "x") @tag(name: "y") @tag(name: "x")
+          b: Int @tag(name: "x", note: "n") @tag(note: "n", name: "x")
+        }
+    "#;
+    let validate = |config: &ValidationConfig| {
+        Schema::parse(sdl, "schema.graphql")
+            .unwrap()
+            .validate_with_config(config)
+            .map_err(|e| e.errors)
+    };
+
+    // Off by default
+    let schema = validate(&ValidationConfig::new()).unwrap();
+    assert!(schema.warnings.is_empty());
+
+    let config = ValidationConfig::new().repeated_directive_warnings(true);
+    let warnings = validate(&config).unwrap().warnings;
+    assert!(!warnings.has_errors());
+    let messages: Vec<_> = warnings.iter().map(|d| d.error.to_string()).collect();
+    assert_eq!(
+        messages,
+        [
+            "directive `@tag` is applied twice with the same arguments",
+            "directive `@tag` is applied twice with the same arguments",
+        ]
+    );
+    let range = warnings.iter().next().unwrap().line_column_range().unwrap();
+    assert_eq!(
+        range.start,
+        LineColumn {
+            line: 4,
+            column: 50
+        }
+    );
+
+    let input = r#"{ a @tag(name: "x") @tag(name: "x") b @tag(name: "x") }"#;
+    let warnings = validate_executable(&schema, input, &config).unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(
+        warnings.iter().next().unwrap().error.rule(),
+        Some("RepeatedDirectiveArguments")
+    );
+}
+
+#[test]
+fn specified_by_urls() {
+    let sdl = r#"
+        type Query { a: Int }
+        scalar Date @specifiedBy(url: "https://tools.ietf.org/html/rfc3339#section-5.6")
+        scalar Uuid @specifiedBy(url: "urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6")
+        scalar NoScheme @specifiedBy(url: "example.com/spec")
+        scalar Unicode @specifiedBy(url: "https://example.com/spécification")
+    "#;
+
+    // Not checked by default
+    validate_schema(sdl, &ValidationConfig::new()).unwrap();
+
+    let errors =
+        validate_schema(sdl, &ValidationConfig::new().check_specified_by_urls(true)).unwrap_err();
+    let messages: Vec<_> = errors.iter().map(|d| d.error.to_string()).collect();
+    assert_eq!(
+        messages,
+        [
+            "`@specifiedBy` URL of scalar `NoScheme` is not an absolute URI: \
+             missing a scheme such as `https:`",
+            "`@specifiedBy` URL of scalar `Unicode` is not an absolute URI: invalid character 'é'",
+        ]
+    );
+    // The span is the string value
+    let range = errors.iter().next().unwrap().line_column_range().unwrap();
+    assert_eq!(
+        range.start,
+        LineColumn {
+            line: 5,
+            column: 43
+        }
+    );
+    assert_eq!(
+        range.end,
+        LineColumn {
+            line: 5,
+            column: 61
+        }
+    );
+
+    let errors = validate_schema(
+        sdl,
+        &ValidationConfig::new()
+            .check_specified_by_urls(true)
+            .lenient_specified_by_urls(true),
+    )
+    .unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors.iter().next().unwrap().error.rule(),
+        Some("InvalidSpecifiedByUrl")
+    );
+}
+
+#[test]
+fn federation_field_sets() {
+    let sdl = r#"
+        scalar FieldSet
+        directive @key(fields: FieldSet!) repeatable on OBJECT | INTERFACE
+        directive @requires(fields: FieldSet!) on FIELD_DEFINITION
+        directive @provides(fields: FieldSet!) on FIELD_DEFINITION
+        directive @tag(name: String!) repeatable on FIELD_DEFINITION | FIELD
+
+        type Query {
+          product: Product @provides(fields: "name vendor { id }")
+          other: Product @provides(fields: "nope")
+        }
+        type Product @key(fields: "id") @key(fields: "sku(format: 1) vendor { idd }") {
+          id: ID!
+          sku(format: Int): String
+          name: String
+          vendor: Vendor
+          price: Int @requires(fields: "n: name vendor { id @tag(name: \"x\") }")
+        }
+        type Vendor @key(fields: """id""") {
+          id: ID!
+        }
+    "#;
+
+    // Off by default
+    validate_schema(sdl, &ValidationConfig::new()).unwrap();
+
+    let errors =
+        validate_schema(sdl, &ValidationConfig::new().federation_field_sets(true)).unwrap_err();
+    let messages: Vec<_> = errors.iter().map(|d| d.error.to_string()).collect();
+    assert_eq!(
+        messages,
+        [
+            "type `Product` does not have a field `nope`",
+            "arguments are not allowed in the `fields` argument of `@key`",
+            "type `Vendor` does not have a field `idd`",
+            "aliases are not allowed in the `fields` argument of `@requires`",
+            "directives are not allowed in the `fields` argument of `@requires`",
+        ],
+        "{errors}"
+    );
+
+    // Locations point inside the string literal
+    let ranges: Vec<_> = errors
+        .iter()
+        .map(|d| d.line_column_range().unwrap().start)
+        .collect();
+    assert_eq!(
+        ranges[..3],
+        [
+            LineColumn {
+                line: 10,
+                column: 45
+            },
+            LineColumn {
+                line: 12,
+                column: 59
+            },
+            LineColumn {
+                line: 12,
+                column: 79
+            },
+        ]
+    );
+}
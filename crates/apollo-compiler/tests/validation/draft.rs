@@ -0,0 +1,98 @@
+use super::validate_executable;
+use super::validate_schema;
+use apollo_compiler::validation::ValidationConfig;
+use apollo_compiler::Schema;
+
+#[test]
+fn draft_spec_one_of_input_objects() {
+    use apollo_compiler::validation::DiagnosticList;
+    use apollo_compiler::validation::SpecVersion;
+
+    let draft = ValidationConfig::new().spec_version(SpecVersion::Draft);
+    let sdl = r#"
+        directive @oneOf on INPUT_OBJECT
+        input UserBy @oneOf { id: ID, email: String }
+        input Invalid @oneOf { id: ID!, email: String = "a@example.com" }
+        type Query { user(by: UserBy): String }
+    "#;
+    let rules = |errors: &DiagnosticList| -> Vec<_> {
+        errors.iter().map(|d| d.error.rule().unwrap()).collect()
+    };
+
+    // The October 2021 edition has no OneOf rules
+    Schema::parse(sdl, "schema.graphql")
+        .unwrap()
+        .validate_with_config(&ValidationConfig::new())
+        .unwrap();
+    let errors = validate_schema(sdl, &draft).unwrap_err();
+    assert_eq!(rules(&errors), ["NonNullOneOfField", "OneOfFieldDefault"]);
+
+    let schema = Schema::parse_and_validate(
+        sdl.replace(
+            "input Invalid @oneOf { id: ID!, email: String = \"a@example.com\" }",
+            "",
+        ),
+        "schema.graphql",
+    )
+    .unwrap();
+    let input = r#"
+        query($id: ID, $email: String!) {
+            a: user(by: { id: "1" })
+            b: user(by: { email: $email })
+            c: user(by: { id: "1", email: "a@example.com" })
+            d: user(by: {})
+            e: user(by: { id: null })
+            f: user(by: { id: $id })
+        }
+    "#;
+    validate_executable(&schema, input, &ValidationConfig::new()).unwrap();
+    let errors = validate_executable(&schema, input, &draft).unwrap_err();
+    assert_eq!(
+        rules(&errors),
+        [
+            "OneOfFieldCount",
+            "OneOfFieldCount",
+            "NullOneOfField",
+            "NullableVariableInOneOf"
+        ]
+    );
+    assert!(errors
+        .to_string()
+        .contains("a value of OneOf input object `UserBy` must have exactly one field, found 2"));
+}
+
+#[test]
+fn draft_spec_deprecated_required_inputs() {
+    use apollo_compiler::validation::SpecVersion;
+
+    let sdl = r#"
+        directive @limit(max: Int! @deprecated, min: Int! = 0 @deprecated) on FIELD
+        type Query {
+          a(x: Int! @deprecated(reason: "use y"), y: Int @deprecated): Int
+        }
+        input Filter { id: ID! @deprecated, name: String @deprecated }
+    "#;
+
+    // The October 2021 edition has no such rule
+    validate_schema(sdl, &ValidationConfig::new()).unwrap();
+    let errors = validate_schema(
+        sdl,
+        &ValidationConfig::new().spec_version(SpecVersion::Draft),
+    )
+    .unwrap_err();
+    let messages: Vec<_> = errors.iter().map(|d| d.error.to_string()).collect();
+    assert_eq!(
+        messages,
+        [
+            "required argument `@limit(max:)` cannot be deprecated",
+            "required argument `Query.a(x:)` cannot be deprecated",
+            "required input field `Filter.id` cannot be deprecated",
+        ],
+        "{errors}"
+    );
+    assert!(errors
+        .iter()
+        .all(|d| d.error.rule() == Some("DeprecatedRequiredInput")));
+    let report = errors.to_string();
+    assert!(report.contains("`Int!` is required"), "{report}");
+}
@@ -0,0 +1,215 @@
+use apollo_compiler::executable::FragmentLibrary;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+
+const SCHEMA: &str = r#"
+type Query {
+    user(id: ID!): User
+    viewer: User
+}
+
+type User {
+    id: ID!
+    name: String
+    avatar(size: Int): String
+    friends: [User]
+}
+"#;
+
+#[test]
+fn library_fragments_do_not_need_to_be_used() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let library = r#"
+fragment UserName on User { name }
+fragment UserCard on User { id ...UserName friends { ...UserName } }
+fragment UserAvatar on User { avatar(size: $size) }
+"#;
+    FragmentLibrary::parse_and_validate(&schema, library, "library.graphql").unwrap();
+}
+
+#[test]
+fn library_fragments_are_validated_against_the_schema() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let library = r#"
+fragment UserName on User { nickname }
+fragment UserLink on User { ...UserName ...Missing }
+fragment Self on User { ...Self }
+"#;
+    let errors = FragmentLibrary::parse_and_validate(&schema, library, "library.graphql")
+        .unwrap_err()
+        .errors
+        .to_string();
+    assert!(
+        errors.contains("type `User` does not have a field `nickname`"),
+        "{errors}"
+    );
+    assert!(
+        errors.contains("cannot find fragment `Missing` in this document"),
+        "{errors}"
+    );
+    assert!(
+        errors.contains("`Self` fragment cannot reference itself"),
+        "{errors}"
+    );
+    assert!(!errors.contains("must be used in an operation"), "{errors}");
+}
+
+#[test]
+fn library_must_not_contain_operations() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let library = r#"
+fragment UserName on User { name }
+query GetViewer { viewer { ...UserName } }
+"#;
+    let errors = FragmentLibrary::parse(&schema, library, "library.graphql")
+        .unwrap_err()
+        .errors
+        .to_string();
+    assert!(
+        errors.contains("a fragment library must not contain operations"),
+        "{errors}"
+    );
+}
+
+#[test]
+fn documents_link_used_library_fragments() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let library = r#"
+fragment UserName on User { name }
+fragment UserCard on User { id ...UserName }
+fragment UserAvatar on User { avatar }
+"#;
+    let library = FragmentLibrary::parse_and_validate(&schema, library, "library.graphql").unwrap();
+
+    let query = "query { viewer { ...UserCard } }";
+    let document = ExecutableDocument::parse(&schema, query, "query.graphql").unwrap();
+    let document = document.validate_with_library(&schema, &library).unwrap();
+    let linked: Vec<_> = document
+        .fragments
+        .keys()
+        .map(|name| name.as_str())
+        .collect();
+    assert_eq!(linked, ["UserCard", "UserName"]);
+}
+
+#[test]
+fn linked_fragments_use_operation_variables() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let library = "fragment UserAvatar on User { avatar(size: $size) }";
+    let library = FragmentLibrary::parse_and_validate(&schema, library, "library.graphql").unwrap();
+
+    let query = "query($size: Int) { viewer { ...UserAvatar } }";
+    let document = ExecutableDocument::parse(&schema, query, "query.graphql").unwrap();
+    document.validate_with_library(&schema, &library).unwrap();
+
+    let query = "query { viewer { ...UserAvatar } }";
+    let document = ExecutableDocument::parse(&schema, query, "query.graphql").unwrap();
+    let errors = document
+        .validate_with_library(&schema, &library)
+        .unwrap_err()
+        .errors
+        .to_string();
+    assert!(
+        errors.contains("variable `$size` is not defined"),
+        "{errors}"
+    );
+}
+
+#[test]
+fn document_fragments_must_not_shadow_library_fragments() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let library = "fragment UserName on User { name }";
+    let library = FragmentLibrary::parse_and_validate(&schema, library, "library.graphql").unwrap();
+
+    let query = "query { viewer { ...UserName } } fragment UserName on User { id }";
+    let document = ExecutableDocument::parse(&schema, query, "query.graphql").unwrap();
+    let errors = document
+        .validate_with_library(&schema, &library)
+        .unwrap_err()
+        .errors
+        .to_string();
+    assert!(
+        errors.contains("the fragment `UserName` is defined multiple times in the document"),
+        "{errors}"
+    );
+}
+
+#[test]
+fn library_fragments_spread_across_files() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let mut library = FragmentLibrary::parse(
+        &schema,
+        "fragment UserCard on User { id ...UserName }",
+        "card.graphql",
+    )
+    .unwrap();
+    library
+        .parse_into(
+            &schema,
+            "fragment UserName on User { name }",
+            "name.graphql",
+        )
+        .unwrap();
+    let errors = library
+        .parse_into(
+            &schema,
+            "fragment UserName on User { id }",
+            "duplicate.graphql",
+        )
+        .unwrap_err()
+        .to_string();
+    assert!(
+        errors.contains("the fragment `UserName` is defined multiple times"),
+        "{errors}"
+    );
+    assert!(errors.contains("name.graphql"), "{errors}");
+    let library = library.validate(&schema).unwrap();
+
+    let query = "query { viewer { ...UserCard } }";
+    let document = ExecutableDocument::parse(&schema, query, "query.graphql").unwrap();
+    let document = document.validate_with_library(&schema, &library).unwrap();
+    let linked: Vec<_> = document
+        .fragments
+        .keys()
+        .map(|name| name.as_str())
+        .collect();
+    assert_eq!(linked, ["UserCard", "UserName"]);
+}
+
+#[test]
+fn batch_validation_of_a_manifest() {
+    use apollo_compiler::validation::BatchValidator;
+
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let library = r#"
+fragment UserName on User { name }
+fragment UserAvatar on User { avatar(size: $size) }
+"#;
+    let library = FragmentLibrary::parse_and_validate(&schema, library, "library.graphql").unwrap();
+    let manifest = vec![
+        ("op1".to_owned(), "{ viewer { ...UserName } }"),
+        (
+            "op2".to_owned(),
+            "query($size: Int) { viewer { ...UserAvatar } }",
+        ),
+        ("op3".to_owned(), "{ viewer { ...UserAvatar } }"),
+        ("op4".to_owned(), "{ viewer { nickname } }"),
+        ("op5".to_owned(), "{ viewer {"),
+    ];
+    let results = BatchValidator::new(&schema)
+        .library(&library)
+        .validate(manifest);
+    let ids: Vec<_> = results.keys().map(String::as_str).collect();
+    assert_eq!(ids, ["op1", "op2", "op3", "op4", "op5"]);
+    assert!(results["op1"].is_ok());
+    assert!(results["op2"].is_ok());
+    let errors = |id: &str| results[id].as_ref().unwrap_err().errors.to_string();
+    assert!(
+        errors("op3").contains("variable `$size` is not defined"),
+        "{}",
+        errors("op3")
+    );
+    assert!(errors("op4").contains("does not have a field `nickname`"));
+    // Diagnostics use the manifest id as the file name
+    assert!(errors("op5").contains("op5"), "{}", errors("op5"));
+}
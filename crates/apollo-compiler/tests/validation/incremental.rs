@@ -0,0 +1,81 @@
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+
+const SCHEMA: &str = r#"
+  directive @defer(if: Boolean! = true, label: String) on FRAGMENT_SPREAD | INLINE_FRAGMENT
+  directive @stream(if: Boolean! = true, label: String, initialCount: Int = 0) on FIELD
+
+  type Query { user: User users: [User] }
+  type Subscription { user: User }
+  type User { name: String friends: [User] }
+"#;
+
+fn errors(query: &str) -> String {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    match ExecutableDocument::parse_and_validate(&schema, query, "query.graphql") {
+        Ok(_) => String::new(),
+        Err(err) => err.errors.to_string(),
+    }
+}
+
+#[test]
+fn valid_incremental_directives() {
+    let errors = errors(
+        r#"
+          query Q($skip: Boolean!) {
+            users @stream(label: "users", initialCount: 1) { name }
+            ... @defer(label: "user", if: $skip) { user { friends @stream { name } } }
+          }
+          subscription S { ... @defer(if: false) { user { ... @defer { name } } } }
+        "#,
+    );
+    assert_eq!(errors, "");
+}
+
+#[test]
+fn labels() {
+    let errors = errors(
+        r#"
+          query($label: String) {
+            users @stream(label: "a") { name }
+            ... @defer(label: "a") { user { name } }
+            ... @defer(label: $label) { user { name } }
+          }
+        "#,
+    );
+    assert!(
+        errors.contains("the label `a` is used by multiple `@defer` or `@stream` directives"),
+        "{errors}"
+    );
+    assert!(
+        errors.contains("the `label` argument of `@defer` must not be a variable"),
+        "{errors}"
+    );
+}
+
+#[test]
+fn stream() {
+    let errors = errors("{ users @stream(initialCount: -1) { name } user @stream { name } }");
+    assert!(
+        errors.contains("`initialCount` must not be negative, got -1"),
+        "{errors}"
+    );
+    assert!(
+        errors.contains("`@stream` cannot be used on field `user` of non-list type `User`"),
+        "{errors}"
+    );
+}
+
+#[test]
+fn defer_on_subscription_root() {
+    let errors = errors(
+        r#"
+          subscription { ...Root }
+          fragment Root on Subscription { ... @defer { user { name } } }
+        "#,
+    );
+    assert!(
+        errors.contains("`@defer` cannot be used on the root selection set of a subscription"),
+        "{errors}"
+    );
+}
@@ -0,0 +1,140 @@
+use super::validate_executable;
+use apollo_compiler::validation::ValidationConfig;
+use apollo_compiler::Schema;
+
+#[test]
+fn max_depth() {
+    let schema = Schema::parse_and_validate(
+        r#"
+            type Query { user: User }
+            type User { name: String friends: [User] }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let input = r#"
+        query { user { ...Friends } }
+        fragment Friends on User { friends { friends { name } } }
+    "#;
+
+    validate_executable(&schema, input, &ValidationConfig::new()).unwrap();
+    validate_executable(&schema, input, &ValidationConfig::new().max_depth(4)).unwrap();
+    let errors = validate_executable(&schema, input, &ValidationConfig::new().max_depth(3))
+        .unwrap_err()
+        .to_string();
+    assert!(
+        errors.contains("fields are nested 4 levels deep, more than the limit of 3"),
+        "{errors}"
+    );
+    assert!(
+        errors.contains("`user.friends.friends.name` is at depth 4"),
+        "{errors}"
+    );
+}
+
+#[test]
+fn introspection_limits() {
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let input = r#"
+        query {
+          a
+          __schema { types { ...TypeRef fields { type { ...TypeRef } } } }
+        }
+        fragment TypeRef on __Type { name ofType { name ofType { name } } }
+    "#;
+
+    validate_executable(&schema, input, &ValidationConfig::new()).unwrap();
+    validate_executable(
+        &schema,
+        input,
+        &ValidationConfig::new().max_introspection_depth(7),
+    )
+    .unwrap();
+    let errors = validate_executable(
+        &schema,
+        input,
+        &ValidationConfig::new().max_introspection_depth(6),
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(
+        errors.contains("introspection field `__schema` is nested 7 levels deep"),
+        "{errors}"
+    );
+    assert!(
+        errors.contains("`__schema.types.fields.type.ofType.ofType.name` is at depth 7"),
+        "{errors}"
+    );
+
+    // `TypeRef` has 5 fields and is spread twice
+    validate_executable(
+        &schema,
+        input,
+        &ValidationConfig::new().max_introspection_fields(13),
+    )
+    .unwrap();
+    let errors = validate_executable(
+        &schema,
+        input,
+        &ValidationConfig::new().max_introspection_fields(12),
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(
+        errors.contains("introspection field `__schema` selects 13 fields"),
+        "{errors}"
+    );
+
+    // Fields outside of `__schema` and `__type` do not count
+    let config = ValidationConfig::new()
+        .max_introspection_depth(7)
+        .max_introspection_fields(13)
+        .max_depth(7);
+    validate_executable(&schema, input, &config).unwrap();
+}
+
+#[test]
+fn alias_and_repeated_field_limits() {
+    let schema = Schema::parse_and_validate(
+        "type Query { user(id: ID): User } type User { name: String }",
+        "schema.graphql",
+    )
+    .unwrap();
+    let input = r#"
+        {
+          a: user(id: 1) { name name name }
+          b: user(id: 2) { name }
+          c: user(id: 3) { name }
+        }
+    "#;
+
+    validate_executable(&schema, input, &ValidationConfig::new()).unwrap();
+    validate_executable(
+        &schema,
+        input,
+        &ValidationConfig::new()
+            .max_aliases(3)
+            .max_repeated_fields(3),
+    )
+    .unwrap();
+
+    let errors = validate_executable(&schema, input, &ValidationConfig::new().max_aliases(2))
+        .unwrap_err()
+        .to_string();
+    assert!(
+        errors.contains("selection set has 3 aliased fields, more than the limit of 2"),
+        "{errors}"
+    );
+
+    let errors = validate_executable(
+        &schema,
+        input,
+        &ValidationConfig::new().max_repeated_fields(2),
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(
+        errors.contains("`name` is selected 3 times, more than the limit of 2"),
+        "{errors}"
+    );
+}
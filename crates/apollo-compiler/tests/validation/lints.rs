@@ -0,0 +1,250 @@
+use apollo_compiler::validation::Severity;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+
+#[test]
+fn schema_lints() {
+    use apollo_compiler::validation::LintConfig;
+    use apollo_compiler::validation::LintRule;
+
+    let schema = Schema::parse(
+        r#"
+            "The root query type"
+            type Query {
+              user_name: String
+              _service: String
+              userID: ID
+            }
+            "An order"
+            type order_item { status: Status }
+            "Order status"
+            enum Status { IN_PROGRESS done }
+            input Filter { Max_Age: Int }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let lints = schema.lint(&LintConfig::new());
+    let results: Vec<_> = lints
+        .iter()
+        .map(|d| {
+            assert_eq!(d.error.severity(), Severity::Lint);
+            (d.error.rule().unwrap(), d.error.to_string())
+        })
+        .collect();
+    assert_eq!(
+        results,
+        [
+            (
+                "FieldNameCamelCase",
+                "field name `user_name` should be camelCase".to_owned()
+            ),
+            (
+                "TypeNamePascalCase",
+                "type name `order_item` should be PascalCase".to_owned()
+            ),
+            (
+                "EnumValueScreamingSnakeCase",
+                "enum value `done` should be SCREAMING_SNAKE_CASE".to_owned()
+            ),
+            (
+                "DescriptionRequired",
+                "an input object type `Filter` is missing a description".to_owned()
+            ),
+            (
+                "FieldNameCamelCase",
+                "field name `Max_Age` should be camelCase".to_owned()
+            ),
+        ]
+    );
+    assert!(
+        lints.to_string().contains("rename to `OrderItem`"),
+        "{lints}"
+    );
+    assert!(lints.to_string().contains("rename to `maxAge`"), "{lints}");
+    // Lints are not validation errors
+    assert!(!lints.has_errors());
+    schema.clone().validate().unwrap();
+
+    let config = LintConfig::new()
+        .disable_rule(LintRule::FieldNameCamelCase)
+        .disable_rule(LintRule::DescriptionRequired);
+    assert_eq!(schema.lint(&config).len(), 2);
+}
+
+#[test]
+fn case_insensitive_name_collision_lint() {
+    use apollo_compiler::validation::LintConfig;
+    use apollo_compiler::validation::LintRule;
+
+    let schema = Schema::parse(
+        r#"
+            type Query {
+              userId: ID
+              userID: ID
+              search(first: Int, First: Int): [Result]
+            }
+            type Result { status: Status }
+            type result { id: ID }
+            enum Status { Active ACTIVE INACTIVE }
+            scalar string
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    // Off by default
+    let rule = LintRule::CaseInsensitiveNameCollision;
+    let default_lints = schema.lint(&LintConfig::new());
+    assert!(default_lints
+        .iter()
+        .all(|d| d.error.rule() != Some(rule.name())));
+
+    let config = LintConfig::new()
+        .disable_rule(LintRule::TypeNamePascalCase)
+        .disable_rule(LintRule::EnumValueScreamingSnakeCase)
+        .disable_rule(LintRule::DescriptionRequired)
+        .enable_rule(rule);
+    let lints = schema.lint(&config);
+    let messages: Vec<_> = lints.iter().map(|d| d.error.to_string()).collect();
+    assert_eq!(
+        messages,
+        [
+            "field `userID` differs only by case from `userId`",
+            "argument `First` differs only by case from `first`",
+            "type `result` differs only by case from `Result`",
+            "enum value `ACTIVE` differs only by case from `Active`",
+            "type `string` differs only by case from `String`",
+        ],
+        "{lints}"
+    );
+    let report = lints.to_string();
+    assert!(report.contains("`userId` defined here"), "{report}");
+    assert!(!lints.has_errors());
+}
+
+#[test]
+fn redundant_conditional_directive_lint() {
+    use apollo_compiler::validation::LintConfig;
+    use apollo_compiler::validation::LintRule;
+
+    let schema = Schema::parse_and_validate(
+        "type Query { a: Int b: Int c: Int d: Int }",
+        "schema.graphql",
+    )
+    .unwrap();
+    let input = r#"
+        query($x: Boolean!, $y: Boolean!) {
+          a @include(if: true)
+          b @skip(if: false)
+          ... @include(if: $x) @skip(if: $x) { c }
+          d @include(if: $x) @skip(if: $y)
+          e: d @include(if: false)
+        }
+    "#;
+    let document = ExecutableDocument::parse_and_validate(&schema, input, "query.graphql").unwrap();
+    let lints = document.lint(&LintConfig::new());
+    let messages: Vec<_> = lints.iter().map(|d| d.error.to_string()).collect();
+    assert_eq!(
+        messages,
+        [
+            "`@include(if: true)` has no effect",
+            "`@skip(if: false)` has no effect",
+            "`@include(if: $x)` and `@skip(if: $x)` always exclude this selection",
+        ],
+        "{lints}"
+    );
+    let report = lints.to_string();
+    assert!(report.contains("included when `$x` is true"), "{report}");
+    assert!(report.contains("skipped when `$x` is true"), "{report}");
+    assert!(lints
+        .iter()
+        .all(|d| d.error.rule() == Some("RedundantConditionalDirective")));
+
+    let config = LintConfig::new().disable_rule(LintRule::RedundantConditionalDirective);
+    assert!(document.lint(&config).is_empty());
+}
+
+#[test]
+fn operation_naming_lints() {
+    use apollo_compiler::validation::LintConfig;
+    use apollo_compiler::validation::LintRule;
+
+    let schema = Schema::parse_and_validate(
+        "type Query { a: Int } type Mutation { b: Int }",
+        "schema.graphql",
+    )
+    .unwrap();
+    let input = r#"
+        query GetA { a }
+        query AQuery { a }
+        mutation SetB { b }
+    "#;
+    let document = ExecutableDocument::parse(&schema, input, "query.graphql").unwrap();
+
+    // Off by default
+    assert!(document.lint(&LintConfig::new()).is_empty());
+
+    let config = LintConfig::new()
+        .enable_rule(LintRule::OperationNameRequired)
+        .enable_rule(LintRule::OperationTypeSuffix);
+    let lints = document.lint(&config);
+    let messages: Vec<_> = lints
+        .iter()
+        .map(|d| (d.error.rule().unwrap(), d.error.to_string()))
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            (
+                "OperationTypeSuffix",
+                "operation name `GetA` should be suffixed with its operation type".to_owned()
+            ),
+            (
+                "OperationTypeSuffix",
+                "operation name `SetB` should be suffixed with its operation type".to_owned()
+            ),
+        ]
+    );
+    assert!(
+        lints.to_string().contains("rename to `GetAQuery`"),
+        "{lints}"
+    );
+    assert!(lints.iter().all(|d| d.error.severity() == Severity::Lint));
+
+    // An anonymous operation must be the only one in its document
+    let anonymous = ExecutableDocument::parse(&schema, "{ a }", "anonymous.graphql").unwrap();
+    let lints = anonymous.lint(&config);
+    let messages: Vec<_> = lints
+        .iter()
+        .map(|d| (d.error.rule().unwrap(), d.error.to_string()))
+        .collect();
+    assert_eq!(
+        messages,
+        [(
+            "OperationNameRequired",
+            "anonymous query should have a name".to_owned()
+        )]
+    );
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn operation_name_pattern_lint() {
+    use apollo_compiler::validation::LintConfig;
+    use apollo_compiler::validation::LintRule;
+
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let input = "query GetA { a } query get_a { a }";
+    let document = ExecutableDocument::parse(&schema, input, "query.graphql").unwrap();
+    let pattern = regex::Regex::new("^[A-Z][A-Za-z0-9]*$").unwrap();
+    let config = LintConfig::new().operation_name_pattern(pattern);
+    let lints = document.lint(&config);
+    assert_eq!(lints.len(), 1, "{lints}");
+    assert_eq!(
+        lints.iter().next().unwrap().error.rule(),
+        Some("OperationNamePattern")
+    );
+
+    let config = config.disable_rule(LintRule::OperationNamePattern);
+    assert!(document.lint(&config).is_empty());
+}
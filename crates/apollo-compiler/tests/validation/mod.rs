@@ -1,5 +1,16 @@
+mod cache;
+mod config;
+mod custom_rules;
+mod deprecation;
+mod diagnostics;
+mod directive;
+mod draft;
 mod field_merging;
+mod fragment_library;
+mod incremental;
 mod interface;
+mod limits;
+mod lints;
 mod object;
 mod operation;
 mod recursion;
@@ -7,11 +18,37 @@ mod types;
 mod variable;
 
 use apollo_compiler::ast;
+use apollo_compiler::name;
 use apollo_compiler::parser::LineColumn;
+use apollo_compiler::validation::DiagnosticList;
+use apollo_compiler::validation::Valid;
+use apollo_compiler::validation::ValidationConfig;
 use apollo_compiler::ExecutableDocument;
 use apollo_compiler::Schema;
 use std::ops::Range;
 
+/// Validates `input` against `schema`, returning the warnings or the errors
+fn validate_executable(
+    schema: &Valid<Schema>,
+    input: &str,
+    config: &ValidationConfig,
+) -> Result<DiagnosticList, DiagnosticList> {
+    ExecutableDocument::parse(schema, input, "query.graphql")
+        .unwrap()
+        .validate_with_config(schema, config)
+        .map(|valid| valid.warnings)
+        .map_err(|invalid| invalid.errors)
+}
+
+/// Validates the schema in `sdl`, returning the warnings or the errors
+fn validate_schema(sdl: &str, config: &ValidationConfig) -> Result<DiagnosticList, DiagnosticList> {
+    Schema::parse(sdl, "schema.graphql")
+        .unwrap()
+        .validate_with_config(config)
+        .map(|valid| valid.warnings)
+        .map_err(|invalid| invalid.errors)
+}
+
 #[test]
 fn executable_and_type_system_definitions() {
     let input_type_system = r#"
@@ -301,6 +338,146 @@ fn validate_variable_usage_without_type_system() {
     doc.validate_standalone_executable().unwrap()
 }
 
+#[test]
+fn structural_validation_without_type_system() {
+    let rules = |input: &str| -> Vec<&'static str> {
+        ast::Document::parse(input, "query.graphql")
+            .unwrap()
+            .validate_standalone_executable()
+            .err()
+            .map(|errors| errors.iter().map(|d| d.error.rule().unwrap()).collect())
+            .unwrap_or_default()
+    };
+
+    // Unknown directives are assumed to be defined and repeatable
+    assert!(rules("query($v: Boolean!) { a @skip(if: $v) @client @client }").is_empty());
+    assert!(rules("query($v: Boolean!) { ...F } fragment F on T { a(x: $v) }").is_empty());
+
+    assert_eq!(rules("{ a(x: $undefined) }"), ["UndefinedVariable"]);
+    assert_eq!(
+        rules("{ ...F } fragment F on T { a(x: { y: [$undefined] }) }"),
+        ["UndefinedVariable"]
+    );
+    assert_eq!(
+        rules("{ a @include(if: $undefined) }"),
+        ["UndefinedVariable"]
+    );
+    assert_eq!(
+        rules("query($v: String) { a @include(if: $v) }"),
+        ["DisallowedVariableUsage"]
+    );
+    assert_eq!(
+        rules("{ a @skip(if: true) @skip(if: false) }"),
+        ["UniqueDirective"]
+    );
+    assert_eq!(rules("{ a @include }"), ["RequiredArgument"]);
+    assert_eq!(
+        rules("query @skip(if: true) { a }"),
+        ["UnsupportedLocation"]
+    );
+    assert!(
+        rules("query { ...A } fragment A on T { ...B } fragment B on T { ...A }")
+            .contains(&"RecursiveFragmentDefinition")
+    );
+    assert_eq!(
+        rules("subscription { a b }"),
+        ["SubscriptionUsesMultipleFields"]
+    );
+}
+
+#[test]
+fn validate_programmatic_values_without_locations() {
+    use apollo_compiler::executable;
+    use apollo_compiler::schema;
+
+    let mut schema = Schema::new();
+    schema.types.insert(
+        name!("Query"),
+        schema::ObjectType {
+            description: None,
+            name: name!("Query"),
+            implements_interfaces: Default::default(),
+            directives: Default::default(),
+            fields: Default::default(),
+        }
+        .into(),
+    );
+    schema.schema_definition.make_mut().query = Some(name!("Query").into());
+    // An object type must have fields
+    let errors = schema.validation_diagnostics(&ValidationConfig::new());
+    assert!(!errors.is_empty());
+    for diagnostic in errors.iter() {
+        assert_eq!(diagnostic.line_column_range(), None);
+        assert!(diagnostic.to_json().locations.is_empty());
+    }
+    assert!(
+        errors.to_string().contains("`Query` has no fields"),
+        "{errors}"
+    );
+
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let mut document = ExecutableDocument::new();
+    let mut selection_set = executable::SelectionSet::new(name!("Query"));
+    selection_set.push(executable::FragmentSpread::new(name!("Missing")));
+    document.operations.insert(executable::Operation {
+        operation_type: ast::OperationType::Query,
+        name: None,
+        variables: Vec::new(),
+        directives: Default::default(),
+        selection_set,
+    });
+    let errors = document.validation_diagnostics(&schema, &ValidationConfig::new());
+    assert_eq!(errors.len(), 1);
+    assert!(
+        errors
+            .to_string()
+            .contains("cannot find fragment `Missing` in this document"),
+        "{errors}"
+    );
+    assert_eq!(errors.iter().next().unwrap().line_column_range(), None);
+}
+
+#[test]
+fn validate_against_schema_variants() {
+    let staging = Schema::parse_and_validate(
+        "type Query { user(id: ID!): User } type User { name: String, email: String }",
+        "staging.graphql",
+    )
+    .unwrap();
+    let prod = Schema::parse_and_validate(
+        "type Query { user(id: ID): User } type User { name: String }",
+        "prod.graphql",
+    )
+    .unwrap();
+    let document = ast::Document::parse(
+        r#"
+            query User($id: ID) { user(id: $id) { name email } }
+            query Name { user(id: "1") { name } }
+        "#,
+        "query.graphql",
+    )
+    .unwrap();
+    let results =
+        document.validate_executable_against(&[&staging, &prod], &ValidationConfig::new());
+    assert_eq!(results.len(), 2);
+    let rules = |errors: &apollo_compiler::validation::DiagnosticList| -> Vec<_> {
+        errors.iter().map(|d| d.error.rule().unwrap()).collect()
+    };
+    // `$id` is nullable but `id` is required in staging
+    assert_eq!(
+        rules(&results[0]),
+        ["DisallowedVariableUsage"],
+        "{}",
+        results[0]
+    );
+    // `email` does not exist in prod
+    assert_eq!(rules(&results[1]), ["UndefinedField"], "{}", results[1]);
+
+    let config = ValidationConfig::new().disable_rule("UndefinedField");
+    let results = document.validate_executable_against(&[&prod], &config);
+    assert!(results[0].is_empty(), "{}", results[0]);
+}
+
 #[test]
 fn json_location_with_multibyte() {
     let input_type_system = r#"
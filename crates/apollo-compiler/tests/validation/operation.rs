@@ -1,4 +1,8 @@
+use super::validate_executable;
 use apollo_compiler::parse_mixed_validate;
+use apollo_compiler::validation::ValidationConfig;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
 
 #[test]
 fn it_fails_validation_with_missing_ident() {
@@ -212,3 +216,220 @@ type Product {
         "{errors}"
     );
 }
+
+#[test]
+fn forbid_introspection() {
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let config = ValidationConfig::new().forbid_introspection(true);
+    let input = r#"
+        query Fields { a __typename ...Types }
+        fragment Types on Query { __type(name: "Query") { name } }
+        query Schema { __schema { queryType { name } } }
+    "#;
+
+    validate_executable(&schema, input, &ValidationConfig::new()).unwrap();
+    validate_executable(&schema, "{ a __typename }", &config).unwrap();
+    let errors = validate_executable(&schema, input, &config).unwrap_err();
+    let messages: Vec<_> = errors.iter().map(|d| d.error.to_string()).collect();
+    assert_eq!(
+        messages,
+        [
+            "introspection is disabled, but `__type` is selected",
+            "introspection is disabled, but `__schema` is selected",
+        ]
+    );
+}
+
+#[test]
+fn operation_allowlist() {
+    use apollo_compiler::validation::ContentHash;
+    use apollo_compiler::validation::OperationAllowlist;
+
+    let schema =
+        Schema::parse_and_validate("type Query { a: Int b: Int }", "schema.graphql").unwrap();
+    let parse = |input: &str| ExecutableDocument::parse(&schema, input, "query.graphql").unwrap();
+    let mut allowlist = OperationAllowlist::new();
+    allowlist.insert_document(&parse("query A { a }"));
+    allowlist.insert_hash(ContentHash::of_document(&parse("{ b }")));
+    let config = ValidationConfig::new().operation_allowlist(allowlist);
+    let validate = |input: &str, config: &ValidationConfig| {
+        parse(input)
+            .validate_with_config(&schema, config)
+            .map(|_| ())
+            .map_err(|e| e.errors)
+    };
+
+    // Formatting does not matter
+    validate("query A {\n  a,\n}  # comment", &config).unwrap();
+    validate("{ b }", &config).unwrap();
+
+    let errors = validate("query B { a }", &config).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    let error = errors.iter().next().unwrap();
+    assert_eq!(error.error.rule(), Some("OperationNotAllowed"));
+    let hash = ContentHash::of_document(&parse("query B { a }"));
+    assert_eq!(
+        error.error.to_string(),
+        format!("document with hash `{hash}` is not in the operation allowlist")
+    );
+
+    // Other rules still apply
+    let errors = validate("query B { a } fragment F on Query { b }", &config).unwrap_err();
+    assert_eq!(errors.len(), 2);
+
+    validate("query B { a }", &config.disable_rule("OperationNotAllowed")).unwrap();
+}
+
+#[test]
+fn require_typename_on_abstract_types() {
+    let schema = Schema::parse_and_validate(
+        r#"
+            type Query { pets: [Pet] search: [Result] }
+            interface Pet { name: String }
+            type Dog implements Pet { name: String }
+            type Cat implements Pet { name: String }
+            union Result = Dog | Cat
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+
+    let input = "{ pets { name } search { ... on Dog { name } } }";
+    // Off by default
+    validate_executable(&schema, input, &ValidationConfig::new()).unwrap();
+
+    let config = ValidationConfig::new().require_typename_on("Pet");
+    let errors = validate_executable(&schema, input, &config)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        errors.contains("field `Query.pets` of abstract type `Pet` must select `__typename`"),
+        "{errors}"
+    );
+    assert!(!errors.contains("Query.search"), "{errors}");
+
+    let config = ValidationConfig::new().require_typename_on_all_abstract_types(true);
+    let errors = validate_executable(&schema, input, &config)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        errors.contains("no fragment selects from `Cat`"),
+        "{errors}"
+    );
+
+    validate_executable(
+        &schema,
+        r#"
+            { pets { ...PetName } search { ... on Dog { name } ... on Cat { name } } }
+            fragment PetName on Pet { __typename name }
+        "#,
+        &config,
+    )
+    .unwrap();
+}
+
+#[test]
+fn validate_single_operation() {
+    let schema =
+        Schema::parse_and_validate("type Query { a: Int b(x: Int!): Int }", "schema.graphql")
+            .unwrap();
+    let input = r#"
+        query A { ...F1 }
+        query B { b }
+        fragment F1 on Query { ...F2 }
+        fragment F2 on Query { a }
+        fragment Broken on Query { b }
+    "#;
+    let document = ExecutableDocument::parse(&schema, input, "query.graphql").unwrap();
+    document.clone().validate(&schema).unwrap_err();
+
+    let valid = document.validate_operation(&schema, Some("A")).unwrap();
+    assert_eq!(valid.operations.named.keys().collect::<Vec<_>>(), ["A"]);
+    assert_eq!(valid.fragments.keys().collect::<Vec<_>>(), ["F1", "F2"]);
+
+    let errors = document
+        .validate_operation(&schema, Some("B"))
+        .unwrap_err()
+        .errors;
+    assert_eq!(errors.len(), 1);
+    assert!(errors.to_string().contains("required argument"), "{errors}");
+
+    let errors = document
+        .validate_operation(&schema, Some("C"))
+        .unwrap_err()
+        .errors;
+    assert!(errors
+        .to_string()
+        .contains("the document does not contain an operation named `C`"));
+    let errors = document
+        .validate_operation(&schema, None)
+        .unwrap_err()
+        .errors;
+    assert!(errors
+        .to_string()
+        .contains("an operation name is required, the document contains 2 operations"));
+}
+
+#[test]
+fn unreachable_selection_warnings() {
+    let schema = Schema::parse_and_validate(
+        r#"
+            interface Pet { name: String }
+            type Cat implements Pet { name: String meows: Boolean }
+            type Dog implements Pet { name: String barks: Boolean }
+            type Fish { fins: Int }
+            union CatOrFish = Cat | Fish
+            type Query { pet: Pet }
+        "#,
+        "schema.graphql",
+    )
+    .unwrap();
+    let input = r#"
+        {
+          pet {
+            ... on Cat {
+              ... on Pet {
+                ... on Dog { barks }
+                ... on Cat { meows }
+              }
+              ...DogFields
+              ...PetFields
+            }
+            ... on CatOrFish { ... on Pet { name } }
+          }
+        }
+        fragment DogFields on Pet { ... on Dog { barks } }
+        fragment PetFields on Pet { name }
+    "#;
+
+    // Off by default
+    assert!(
+        validate_executable(&schema, input, &ValidationConfig::new())
+            .unwrap()
+            .is_empty()
+    );
+
+    let config = ValidationConfig::new().unreachable_selection_warnings(true);
+    let warnings = validate_executable(&schema, input, &config).unwrap();
+    assert!(!warnings.has_errors());
+    let messages: Vec<_> = warnings.iter().map(|d| d.error.to_string()).collect();
+    assert_eq!(
+        messages,
+        [
+            "selection can never be reached: no type matches both `Dog` \
+             and the enclosing type conditions `Cat`, `Pet`",
+            "selection can never be reached: no type matches both `Dog` \
+             and the enclosing type conditions `Cat`, `Pet`",
+        ],
+        "{warnings}"
+    );
+    assert!(warnings
+        .iter()
+        .all(|d| d.error.rule() == Some("UnreachableSelection")));
+    let report = warnings.to_string();
+    assert!(
+        report.contains("only matches types that are `Cat`"),
+        "{report}"
+    );
+    assert!(report.contains("no such type is `Dog`"), "{report}");
+}
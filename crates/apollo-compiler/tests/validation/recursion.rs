@@ -1,3 +1,9 @@
+use apollo_compiler::validation::DiagnosticList;
+use apollo_compiler::validation::ValidationConfig;
+use apollo_compiler::validation::ValidationErrorKind;
+use apollo_compiler::validation::ValidationLimits;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
 use expect_test::expect;
 
 fn build_fragment_chain(size: usize) -> String {
@@ -223,3 +229,224 @@ type Query {
     "#]]
     .assert_eq(&errors.to_string());
 }
+
+#[test]
+fn configured_type_nesting_limit() {
+    let validate = |size, type_nesting| {
+        let mut limits = ValidationLimits::default();
+        limits.type_nesting = type_nesting;
+        let config = ValidationConfig::new().limits(limits);
+        Schema::parse(build_input_object_chain(size), "input_objects.graphql")
+            .unwrap()
+            .validate_with_config(&config)
+    };
+    let errors = validate(31, 10)
+        .expect_err("must have recursion errors")
+        .errors;
+    assert!(errors.iter().all(|diagnostic| diagnostic
+        .error
+        .to_string()
+        .ends_with("contains too much nesting")));
+    validate(100, 200).expect("must not have recursion errors");
+}
+
+#[test]
+fn configured_fragment_nesting_limit() {
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let validate = |size, fragment_nesting| {
+        let mut limits = ValidationLimits::default();
+        limits.fragment_nesting = fragment_nesting;
+        let config = ValidationConfig::new().limits(limits);
+        ExecutableDocument::parse(&schema, build_fragment_chain(size), "fragments.graphql")
+            .unwrap()
+            .validate_with_config(&schema, &config)
+    };
+    let errors = validate(50, 20)
+        .expect_err("must have recursion errors")
+        .errors;
+    assert!(errors.iter().any(|diagnostic| diagnostic
+        .error
+        .to_string()
+        .ends_with("contains too much nesting")));
+    validate(50, 60).expect("must not have recursion errors");
+}
+
+#[test]
+fn field_merging_budget() {
+    let schema = Schema::parse_and_validate(
+        "type Query { user: User } type User { id: ID name: String friends: [User] }",
+        "schema.graphql",
+    )
+    .unwrap();
+    let query = format!(
+        "{{ user {{ {} }} }} fragment Friend on User {{ id name }}",
+        "friends { ...Friend } ".repeat(50)
+    );
+    let validate = |budget| {
+        let mut limits = ValidationLimits::default();
+        limits.field_merging_budget = Some(budget);
+        let config = ValidationConfig::new().limits(limits);
+        ExecutableDocument::parse(&schema, &query, "query.graphql")
+            .unwrap()
+            .validate_with_config(&schema, &config)
+    };
+    let errors = validate(20).expect_err("must be over budget").errors;
+    let messages: Vec<_> = errors.iter().map(|d| d.error.to_string()).collect();
+    assert_eq!(
+        messages,
+        ["too many fields to check for merging, more than the limit of 20"]
+    );
+    validate(1_000).expect("must be within budget");
+}
+
+#[test]
+fn validation_step_budget() {
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    // Each fragment spreads the next one twice, so validation visits about 2^12 selections
+    let mut query = "{ ...F0 }".to_owned();
+    for i in 0..10 {
+        query.push_str(&format!(
+            " fragment F{i} on Query {{ ...F{next} ...F{next} }}",
+            next = i + 1
+        ));
+    }
+    query.push_str(" fragment F10 on Query { a }");
+    let validate = |steps| {
+        let mut limits = ValidationLimits::default();
+        limits.validation_steps = Some(steps);
+        let config = ValidationConfig::new().limits(limits);
+        ExecutableDocument::parse(&schema, &query, "query.graphql")
+            .unwrap()
+            .validate_with_config(&schema, &config)
+    };
+    let errors = validate(100).expect_err("must be over budget").errors;
+    let messages: Vec<_> = errors.iter().map(|d| d.error.to_string()).collect();
+    assert_eq!(
+        messages,
+        ["validation budget exceeded: stopped after 100 steps"]
+    );
+    validate(10_000).expect("must be within budget");
+}
+
+/// Validation stops early when it reaches a limit, so the limit must be reported
+/// even with a config that tries to turn off the diagnostic named `name`.
+/// Returns the messages of diagnostics, which are the same with every config.
+fn limit_messages(
+    name: &str,
+    path: &str,
+    validate: impl Fn(ValidationConfig) -> Result<(), DiagnosticList>,
+) -> Vec<String> {
+    let messages = |config| {
+        let errors = validate(config).expect_err("must reach the limit");
+        assert_eq!(errors.suppressed_len(), 0);
+        assert!(errors
+            .iter()
+            .any(|d| d.error.kind() == ValidationErrorKind::Limit && d.error.rule().is_none()));
+        errors
+            .iter()
+            .map(|d| d.error.to_string())
+            .collect::<Vec<_>>()
+    };
+    let expected = messages(ValidationConfig::new());
+    assert_eq!(
+        messages(ValidationConfig::new().disable_rule(name)),
+        expected
+    );
+    assert_eq!(
+        messages(ValidationConfig::new().suppress(path, name, None)),
+        expected
+    );
+    expected
+}
+
+#[test]
+fn recursion_error_cannot_be_turned_off() {
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let messages = limit_messages("RecursionError", "fragments.graphql", |config| {
+        ExecutableDocument::parse(&schema, build_fragment_chain(1_000), "fragments.graphql")
+            .unwrap()
+            .validate_with_config(&schema, &config)
+            .map(|_| ())
+            .map_err(|e| e.errors)
+    });
+    assert!(messages
+        .iter()
+        .any(|message| message == "too much recursion"));
+}
+
+#[test]
+fn recursion_limit_error_cannot_be_turned_off() {
+    let schema = Schema::parse_and_validate(
+        "type Recur { recur: Recur leaf(arg: Boolean): Int } type Query { recur: Recur }",
+        "schema.graphql",
+    )
+    .unwrap();
+    let messages = limit_messages("RecursionLimitError", "selection.graphql", |config| {
+        ExecutableDocument::parse(&schema, build_nested_selection(400), "selection.graphql")
+            .unwrap()
+            .validate_with_config(&schema, &config)
+            .map(|_| ())
+            .map_err(|e| e.errors)
+    });
+    assert_eq!(messages, ["too much recursion"]);
+}
+
+#[test]
+fn deeply_nested_type_cannot_be_turned_off() {
+    let messages = limit_messages("DeeplyNestedType", "input_objects.graphql", |config| {
+        let mut limits = ValidationLimits::default();
+        limits.type_nesting = 10;
+        Schema::parse(build_input_object_chain(31), "input_objects.graphql")
+            .unwrap()
+            .validate_with_config(&config.limits(limits))
+            .map(|_| ())
+            .map_err(|e| e.errors)
+    });
+    assert!(messages
+        .iter()
+        .all(|message| message.ends_with("contains too much nesting")));
+}
+
+#[test]
+fn field_merging_budget_cannot_be_turned_off() {
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let messages = limit_messages("FieldMergingBudget", "query.graphql", |config| {
+        let mut limits = ValidationLimits::default();
+        limits.field_merging_budget = Some(2);
+        ExecutableDocument::parse(&schema, "{ a a a a a a }", "query.graphql")
+            .unwrap()
+            .validate_with_config(&schema, &config.limits(limits))
+            .map(|_| ())
+            .map_err(|e| e.errors)
+    });
+    assert_eq!(
+        messages,
+        ["too many fields to check for merging, more than the limit of 2"]
+    );
+}
+
+#[test]
+fn validation_budget_exceeded_cannot_be_turned_off() {
+    let schema = Schema::parse_and_validate("type Query { a: Int }", "schema.graphql").unwrap();
+    let mut query = "{ ...F0 }".to_owned();
+    for i in 0..10 {
+        query.push_str(&format!(
+            " fragment F{i} on Query {{ ...F{next} ...F{next} }}",
+            next = i + 1
+        ));
+    }
+    query.push_str(" fragment F10 on Query { a }");
+    let messages = limit_messages("ValidationBudgetExceeded", "query.graphql", |config| {
+        let mut limits = ValidationLimits::default();
+        limits.validation_steps = Some(100);
+        ExecutableDocument::parse(&schema, &query, "query.graphql")
+            .unwrap()
+            .validate_with_config(&schema, &config.limits(limits))
+            .map(|_| ())
+            .map_err(|e| e.errors)
+    });
+    assert_eq!(
+        messages,
+        ["validation budget exceeded: stopped after 100 steps"]
+    );
+}
@@ -1137,6 +1137,8 @@ mod invalid_list_values {
                  85 │   stringListArgField(stringListArg: [String]): String
                     │                                     ────┬───  
                     │                                         ╰───── expected type declared here as String
+                    │ 
+                    │ Note: input path `stringListArg[1]`
                 ────╯
             "#]],
         );
@@ -1598,6 +1600,8 @@ mod invalid_input_object_values {
                  65 │   stringListField: [String]
                     │                    ────┬───  
                     │                        ╰───── expected type declared here as String
+                    │ 
+                    │ Note: input path `complexArg.stringListField[1]`
                 ────╯
             "#]],
         );
@@ -1629,6 +1633,8 @@ mod invalid_input_object_values {
                  61 │   nonNullField: Boolean! = false
                     │                 ────┬───  
                     │                     ╰───── expected type declared here as Boolean!
+                    │ 
+                    │ Note: input path `complexArg.nonNullField`
                 ────╯
             "#]],
         );
@@ -1662,6 +1668,8 @@ mod invalid_input_object_values {
                  66 │ ├─▶ }
                     │ │       
                     │ ╰─────── input object defined here
+                    │     
+                    │     Note: input path `complexArg.invalidField`
                 ────╯
             "#]],
         );
@@ -1870,6 +1878,8 @@ mod variable_default_values {
                  60 │   requiredField: Boolean!
                     │                  ────┬───  
                     │                      ╰───── expected type declared here as Boolean!
+                    │ 
+                    │ Note: input path `$c.requiredField`
                 ────╯
             "#]],
         );
@@ -1948,6 +1958,8 @@ mod variable_default_values {
                  60 │   requiredField: Boolean!
                     │                  ────┬───  
                     │                      ╰───── expected type declared here as Boolean!
+                    │ 
+                    │ Note: input path `$a.requiredField`
                 ────╯
                 Error: expected value of type Int, found a string
                     ╭─[query.graphql:2:44]
@@ -1961,6 +1973,8 @@ mod variable_default_values {
                  62 │   intField: Int
                     │             ─┬─  
                     │              ╰─── expected type declared here as Int
+                    │ 
+                    │ Note: input path `$a.intField`
                 ────╯
             "#]],
         );
@@ -2011,6 +2025,8 @@ mod variable_default_values {
                    │                           ╰───────────────── expected type declared here as String
                    │                                          │  
                    │                                          ╰── provided value is an integer
+                   │ 
+                   │ Note: input path `$a[1]`
                 ───╯
             "#]],
         );
@@ -0,0 +1,81 @@
+use apollo_compiler::validation::analyze_variable_usages;
+use apollo_compiler::ExecutableDocument;
+use apollo_compiler::Schema;
+
+const SCHEMA: &str = r#"
+  type Query {
+    user(id: ID!): User
+    search(filter: Filter, first: Int! = 10): [User]
+  }
+
+  type User {
+    name(upper: Boolean): String
+  }
+
+  input Filter {
+    names: [String!]
+    active: Boolean!
+  }
+"#;
+
+#[test]
+fn reports_variable_usages() {
+    let schema = Schema::parse_and_validate(SCHEMA, "schema.graphql").unwrap();
+    let query = r#"
+      query($id: ID!, $name: String, $active: Boolean = true, $first: Int, $upper: Boolean!, $unused: Int) {
+        user(id: $id) { ...Name }
+        search(filter: { names: [$name], active: $active }, first: $first) {
+          ...Name
+          name @include(if: $upper)
+        }
+      }
+      fragment Name on User { name(upper: $upper) }
+    "#;
+    let doc = ExecutableDocument::parse(&schema, query, "query.graphql").unwrap();
+    let operation = doc.operations.get(None).unwrap();
+    let report = analyze_variable_usages(&schema, &doc, operation);
+    let variables: Vec<_> = report
+        .variables
+        .iter()
+        .map(|variable| {
+            let usages: Vec<_> = variable
+                .usages
+                .iter()
+                .map(|usage| {
+                    (
+                        usage.coordinate.to_string(),
+                        usage.expected_type.to_string(),
+                        usage.is_allowed,
+                    )
+                })
+                .collect();
+            (variable.definition.name.as_str(), usages)
+        })
+        .collect();
+    let usage =
+        |coordinate: &str, ty: &str, is_allowed| (coordinate.to_owned(), ty.to_owned(), is_allowed);
+    assert_eq!(
+        variables,
+        [
+            ("id", vec![usage("Query.user(id:)", "ID!", true)]),
+            ("name", vec![usage("Filter.names", "String!", false)]),
+            ("active", vec![usage("Filter.active", "Boolean!", true)]),
+            ("first", vec![usage("Query.search(first:)", "Int!", true)]),
+            (
+                "upper",
+                vec![
+                    usage("User.name(upper:)", "Boolean", true),
+                    usage("@include(if:)", "Boolean!", true),
+                ]
+            ),
+            ("unused", vec![]),
+        ]
+    );
+
+    let active = &report.variables[2];
+    assert!(active.relies_on_default_value());
+    assert!(!report.variables[0].relies_on_default_value());
+    let first = &report.variables[3].usages[0];
+    assert!(first.has_location_default);
+    assert!(first.location.is_some());
+}
@@ -111,6 +111,7 @@ pub use crate::SyntaxNode;
 use crate::SyntaxNodeChildren;
 use crate::SyntaxToken;
 pub use generated::nodes::*;
+pub(crate) use node_ext::string_value;
 use std::marker::PhantomData;
 
 /// The main trait to go from untyped `SyntaxNode`  to a typed CST. The
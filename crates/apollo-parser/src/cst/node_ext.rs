@@ -359,14 +359,19 @@ fn unescape_block_string(raw_value: &str) -> String {
 // TODO(@goto-bus-stop) As this handles escaping, which can fail in theory, it should be TryFrom
 impl From<&'_ cst::StringValue> for String {
     fn from(val: &'_ cst::StringValue) -> Self {
-        let text = text_of_first_token(val.syntax());
-        // These slices would panic if the contents are invalid, but the lexer already guarantees that the
-        // string is valid.
-        if is_block_string(&text) {
-            unescape_block_string(&text[3..text.len() - 3])
-        } else {
-            unescape_string(&text[1..text.len() - 1])
-        }
+        string_value(&text_of_first_token(val.syntax()))
+    }
+}
+
+/// Returns the value of a string token, with quotes removed and escape sequences
+/// or block string indentation processed.
+pub(crate) fn string_value(text: &str) -> String {
+    // These slices would panic if the contents are invalid, but the lexer already guarantees that the
+    // string is valid.
+    if is_block_string(text) {
+        unescape_block_string(&text[3..text.len() - 3])
+    } else {
+        unescape_string(&text[1..text.len() - 1])
     }
 }
 
@@ -24,6 +24,24 @@ impl<'a> Token<'a> {
     pub fn index(&self) -> usize {
         self.index
     }
+
+    /// For a [`TokenKind::StringValue`] token, returns the string it represents,
+    /// with escape sequences and block string indentation processed.
+    /// Returns `None` for other kinds of tokens.
+    ///
+    /// ```rust
+    /// use apollo_parser::Lexer;
+    ///
+    /// let (tokens, _) = Lexer::new(r#""tab\tseparated""#).lex();
+    /// assert_eq!(tokens[0].string_value().as_deref(), Some("tab\tseparated"));
+    /// ```
+    pub fn string_value(&self) -> Option<String> {
+        if self.kind == TokenKind::StringValue {
+            Some(crate::cst::string_value(self.data))
+        } else {
+            None
+        }
+    }
 }
 
 impl<'a> fmt::Debug for Token<'a> {
@@ -14,6 +14,8 @@ pub use crate::lexer::Lexer;
 pub use crate::lexer::Token;
 pub use crate::lexer::TokenKind;
 pub use crate::limit::LimitTracker;
+pub use crate::parser::DecodeError;
+pub use crate::parser::ParseSummary;
 pub use crate::parser::Parser;
 pub use crate::parser::SyntaxElement;
 pub use crate::parser::SyntaxKind;
@@ -22,4 +24,5 @@ pub(crate) use crate::parser::SyntaxNodeChildren;
 pub use crate::parser::SyntaxToken;
 pub use crate::parser::SyntaxTree;
 pub(crate) use crate::parser::TokenText;
+pub use crate::parser::Visitor;
 pub use rowan::TextRange;
@@ -0,0 +1,310 @@
+//! A compact binary encoding of [`SyntaxTree`], for caching parse results on disk.
+
+use crate::cst::CstNode;
+use crate::error::ErrorData;
+use crate::Error;
+use crate::LimitTracker;
+use crate::SyntaxKind;
+use crate::SyntaxTree;
+use rowan::GreenNodeBuilder;
+use rowan::NodeOrToken;
+use rowan::WalkEvent;
+use std::marker::PhantomData;
+
+/// Identifies the encoding. Bump the trailing version byte when changing the format.
+const MAGIC: &[u8] = b"GQLCST\x01";
+
+const START_NODE: u8 = 0;
+const TOKEN: u8 = 1;
+const FINISH_NODE: u8 = 2;
+
+const ERROR_EOF: u8 = 0;
+const ERROR_LIMIT: u8 = 1;
+const ERROR_TEXT: u8 = 2;
+
+/// An error returned by [`SyntaxTree::from_bytes`] when the input was not produced by
+/// [`SyntaxTree::to_bytes`] with the same version of `apollo-parser`.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid serialized syntax tree: {message}")]
+pub struct DecodeError {
+    message: &'static str,
+}
+
+impl DecodeError {
+    fn new(message: &'static str) -> Self {
+        Self { message }
+    }
+}
+
+impl<T: CstNode> SyntaxTree<T> {
+    /// Encode this syntax tree, including its errors, warnings, and limits, into bytes
+    /// that can be stored and later turned back into a tree with [`SyntaxTree::from_bytes`].
+    ///
+    /// The encoding is specific to the version of `apollo-parser` that produced it,
+    /// and is rejected by other versions. This makes it suitable for caches
+    /// that are invalidated when dependencies change, but not as an interchange format.
+    ///
+    /// ```rust
+    /// use apollo_parser::Parser;
+    /// use apollo_parser::SyntaxTree;
+    ///
+    /// let cst = Parser::new("type Query { hello: String }").parse();
+    /// let bytes = cst.to_bytes();
+    /// let cached = SyntaxTree::from_bytes(&bytes).unwrap();
+    /// assert_eq!(cst, cached);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(MAGIC);
+        write_str(&mut out, env!("CARGO_PKG_VERSION"));
+
+        for event in self.syntax_node().preorder_with_tokens() {
+            match event {
+                WalkEvent::Enter(NodeOrToken::Node(node)) => {
+                    out.push(START_NODE);
+                    out.extend_from_slice(&u16::from(node.kind()).to_le_bytes());
+                }
+                WalkEvent::Enter(NodeOrToken::Token(token)) => {
+                    out.push(TOKEN);
+                    out.extend_from_slice(&u16::from(token.kind()).to_le_bytes());
+                    write_str(&mut out, token.text());
+                }
+                WalkEvent::Leave(NodeOrToken::Node(_)) => out.push(FINISH_NODE),
+                WalkEvent::Leave(NodeOrToken::Token(_)) => {}
+            }
+        }
+
+        write_errors(&mut out, &self.errors);
+        write_errors(&mut out, &self.warnings);
+        write_limit(&mut out, self.recursion_limit);
+        write_limit(&mut out, self.token_limit);
+        out
+    }
+
+    /// Decode a syntax tree previously encoded with [`SyntaxTree::to_bytes`].
+    ///
+    /// Returns an error if the bytes are malformed, were produced by a different version
+    /// of `apollo-parser`, or encode a tree of a different kind than `T`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut reader = Reader { bytes };
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(DecodeError::new("unknown format"));
+        }
+        if reader.str()? != env!("CARGO_PKG_VERSION") {
+            return Err(DecodeError::new(
+                "encoded by a different version of apollo-parser",
+            ));
+        }
+
+        // Rebuild the tree with an explicit depth counter rather than recursion,
+        // so that a deeply nested input cannot overflow the stack.
+        let mut builder = GreenNodeBuilder::new();
+        if reader.u8()? != START_NODE {
+            return Err(DecodeError::new("expected a root node"));
+        }
+        let root_kind = reader.kind()?;
+        if !T::can_cast(root_kind) {
+            return Err(DecodeError::new("unexpected root node kind"));
+        }
+        builder.start_node(rowan::SyntaxKind(root_kind.into()));
+        let mut depth = 1_usize;
+        while depth > 0 {
+            match reader.u8()? {
+                START_NODE => {
+                    builder.start_node(rowan::SyntaxKind(reader.kind()?.into()));
+                    depth += 1;
+                }
+                TOKEN => {
+                    let kind = reader.kind()?;
+                    builder.token(rowan::SyntaxKind(kind.into()), reader.str()?);
+                }
+                FINISH_NODE => {
+                    builder.finish_node();
+                    depth -= 1;
+                }
+                _ => return Err(DecodeError::new("unknown tree event")),
+            }
+        }
+        let green = builder.finish();
+
+        let errors = reader.errors()?;
+        let warnings = reader.errors()?;
+        let recursion_limit = reader.limit()?;
+        let token_limit = reader.limit()?;
+        if !reader.bytes.is_empty() {
+            return Err(DecodeError::new("unexpected trailing bytes"));
+        }
+        Ok(SyntaxTree {
+            green,
+            errors,
+            warnings,
+            recursion_limit,
+            token_limit,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+fn write_u64(out: &mut Vec<u8>, value: usize) {
+    out.extend_from_slice(&(value as u64).to_le_bytes());
+}
+
+fn write_str(out: &mut Vec<u8>, value: &str) {
+    write_u64(out, value.len());
+    out.extend_from_slice(value.as_bytes());
+}
+
+fn write_errors(out: &mut Vec<u8>, errors: &[Error]) {
+    write_u64(out, errors.len());
+    for error in errors {
+        write_str(out, &error.message);
+        match &error.data {
+            ErrorData::Eof => out.push(ERROR_EOF),
+            ErrorData::LimitExceeded => out.push(ERROR_LIMIT),
+            ErrorData::Text(text) => {
+                out.push(ERROR_TEXT);
+                write_str(out, text);
+            }
+        }
+        write_u64(out, error.index);
+    }
+}
+
+fn write_limit(out: &mut Vec<u8>, limit: LimitTracker) {
+    write_u64(out, limit.current);
+    write_u64(out, limit.high);
+    write_u64(out, limit.limit);
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        if self.bytes.len() < len {
+            return Err(DecodeError::new("unexpected end of input"));
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn usize(&mut self) -> Result<usize, DecodeError> {
+        let bytes = self.take(8)?.try_into().unwrap();
+        usize::try_from(u64::from_le_bytes(bytes))
+            .map_err(|_| DecodeError::new("integer out of range"))
+    }
+
+    fn str(&mut self) -> Result<&'a str, DecodeError> {
+        let len = self.usize()?;
+        std::str::from_utf8(self.take(len)?).map_err(|_| DecodeError::new("invalid UTF-8"))
+    }
+
+    fn kind(&mut self) -> Result<SyntaxKind, DecodeError> {
+        let raw = u16::from_le_bytes(self.take(2)?.try_into().unwrap());
+        // `__LAST` is a marker, not a kind that can appear in a tree
+        if raw >= SyntaxKind::__LAST as u16 {
+            return Err(DecodeError::new("unknown syntax kind"));
+        }
+        // SAFETY: `SyntaxKind` is `repr(u16)` with contiguous discriminants up to `__LAST`,
+        // the same conversion as `GraphQLLanguage::kind_from_raw`.
+        Ok(unsafe { std::mem::transmute::<u16, SyntaxKind>(raw) })
+    }
+
+    fn errors(&mut self) -> Result<Vec<Error>, DecodeError> {
+        let len = self.usize()?;
+        let mut errors = Vec::new();
+        for _ in 0..len {
+            let message = self.str()?.to_owned();
+            let data = match self.u8()? {
+                ERROR_EOF => ErrorData::Eof,
+                ERROR_LIMIT => ErrorData::LimitExceeded,
+                ERROR_TEXT => ErrorData::Text(self.str()?.to_owned()),
+                _ => return Err(DecodeError::new("unknown error kind")),
+            };
+            let index = self.usize()?;
+            errors.push(Error {
+                message,
+                data,
+                index,
+            });
+        }
+        Ok(errors)
+    }
+
+    fn limit(&mut self) -> Result<LimitTracker, DecodeError> {
+        Ok(LimitTracker {
+            current: self.usize()?,
+            high: self.usize()?,
+            limit: self.usize()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DecodeError;
+    use crate::cst;
+    use crate::cst::CstNode;
+    use crate::Parser;
+    use crate::SyntaxTree;
+
+    #[test]
+    fn round_trip() {
+        let source = r#"
+            # comments and whitespace are kept
+            type Query { field(arg: Int = 1): [String!] @deprecated }
+            query { field(arg: ) }
+        "#;
+        let cst = Parser::new(source).parse();
+        assert_eq!(cst.errors().len(), 1);
+
+        let decoded = SyntaxTree::<cst::Document>::from_bytes(&cst.to_bytes()).unwrap();
+        assert_eq!(decoded, cst);
+        assert_eq!(decoded.document().syntax().to_string(), source);
+        assert_eq!(
+            decoded.errors().collect::<Vec<_>>(),
+            cst.errors().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn rejects_mismatched_root_kind() {
+        let bytes = Parser::new("Int!").parse_type().to_bytes();
+        let error = SyntaxTree::<cst::Document>::from_bytes(&bytes).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid serialized syntax tree: unexpected root node kind"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_kind() {
+        let mut bytes = Parser::new("{ a }").parse().to_bytes();
+        // The root node kind follows the magic bytes, the version string, and the event byte
+        let offset = super::MAGIC.len() + 8 + env!("CARGO_PKG_VERSION").len() + 1;
+        let last = crate::SyntaxKind::__LAST as u16;
+        for raw in [last, last + 1, u16::MAX] {
+            bytes[offset..offset + 2].copy_from_slice(&raw.to_le_bytes());
+            let error = SyntaxTree::<cst::Document>::from_bytes(&bytes).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "invalid serialized syntax tree: unknown syntax kind"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_truncated_input() {
+        let bytes = Parser::new("{ a }").parse().to_bytes();
+        for len in 0..bytes.len() {
+            let result = SyntaxTree::<cst::Document>::from_bytes(&bytes[..len]);
+            assert!(matches!(result, Err(DecodeError { .. })));
+        }
+    }
+}
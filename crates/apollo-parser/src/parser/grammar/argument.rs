@@ -29,10 +29,10 @@ pub(crate) fn argument(p: &mut Parser, constness: Constness) {
 pub(crate) fn arguments(p: &mut Parser, constness: Constness) {
     let _g = p.start_node(SyntaxKind::ARGUMENTS);
     p.bump(S!['(']);
-    if let Some(TokenKind::Name) = p.peek() {
-        argument(p, constness);
-    } else {
-        p.err("expected an Argument");
+    match p.peek() {
+        Some(TokenKind::Name) => argument(p, constness),
+        Some(T![')']) => p.err_unless_lenient("expected an Argument", "arguments list"),
+        _ => p.err("expected an Argument"),
     }
     p.peek_while_kind(TokenKind::Name, |p| {
         argument(p, constness);
@@ -47,10 +47,12 @@ pub(crate) fn arguments(p: &mut Parser, constness: Constness) {
 pub(crate) fn arguments_definition(p: &mut Parser) {
     let _g = p.start_node(SyntaxKind::ARGUMENTS_DEFINITION);
     p.bump(S!['(']);
-    if let Some(TokenKind::Name | TokenKind::StringValue) = p.peek() {
-        input::input_value_definition(p);
-    } else {
-        p.err("expected an Argument Definition");
+    match p.peek() {
+        Some(TokenKind::Name | TokenKind::StringValue) => input::input_value_definition(p),
+        Some(T![')']) => {
+            p.err_unless_lenient("expected an Argument Definition", "arguments definition")
+        }
+        _ => p.err("expected an Argument Definition"),
     }
     p.peek_while(|p, kind| match kind {
         TokenKind::Name | TokenKind::StringValue => {
@@ -1,5 +1,8 @@
 use crate::Parser;
 use crate::SyntaxKind;
+use crate::TokenKind;
+
+const MISPLACED_DESCRIPTION: &str = "description not allowed here";
 
 /// See: https://spec.graphql.org/October2021/#Description
 ///
@@ -11,6 +14,36 @@ pub(crate) fn description(p: &mut Parser) {
     p.bump(SyntaxKind::STRING)
 }
 
+/// Recover from a description written after a definition keyword, like `type "Description" Name`,
+/// by reporting an error and keeping it as the description of the definition.
+pub(crate) fn description_after_keyword(p: &mut Parser) {
+    if let Some(TokenKind::StringValue) = p.peek() {
+        p.err(MISPLACED_DESCRIPTION);
+        description(p);
+    }
+}
+
+/// Recover from a description written where nothing can have one, by reporting an error
+/// and discarding it. For example after `extend type`, or before an operation.
+pub(crate) fn discard_description(p: &mut Parser) {
+    if let Some(TokenKind::StringValue) = p.peek() {
+        p.err_and_pop(MISPLACED_DESCRIPTION);
+    }
+}
+
+/// Recover from a description that is not followed by the name of what it describes,
+/// such as a stray string at the end of a fields definition, by discarding it with an error.
+///
+/// Returns whether a description was discarded.
+pub(crate) fn discard_stray_description(p: &mut Parser) -> bool {
+    if p.peek() == Some(TokenKind::StringValue) && p.peek_n(2) != Some(TokenKind::Name) {
+        p.err_and_pop(MISPLACED_DESCRIPTION);
+        true
+    } else {
+        false
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -45,4 +78,81 @@ type Query {
         }
         panic!("object type definition has not been catched");
     }
+
+    fn error_messages(input: &str) -> Vec<String> {
+        let cst = Parser::new(input).parse();
+        cst.errors()
+            .map(|error| format!("{} {}", error.message(), error.data()))
+            .collect()
+    }
+
+    #[test]
+    fn it_keeps_a_description_after_the_definition_keyword() {
+        let input = r#"type "The query type" Query { a: Int }"#;
+        assert_eq!(
+            error_messages(input),
+            [r#"description not allowed here "The query type""#]
+        );
+
+        let cst = Parser::new(input).parse();
+        let Some(cst::Definition::ObjectTypeDefinition(def)) = cst.document().definitions().next()
+        else {
+            panic!("expected an object type definition")
+        };
+        let description: String = def.description().unwrap().string_value().unwrap().into();
+        assert_eq!(description, "The query type");
+        assert_eq!(def.name().unwrap().text(), "Query");
+        assert_eq!(
+            def.fields_definition().unwrap().field_definitions().count(),
+            1
+        );
+    }
+
+    #[test]
+    fn it_discards_stray_descriptions() {
+        assert_eq!(
+            error_messages(r#"type Query { a: Int "stray" }"#),
+            [r#"description not allowed here "stray""#]
+        );
+        assert_eq!(
+            error_messages(r#"enum E { A "stray" "B value" B }"#),
+            [r#"description not allowed here "stray""#]
+        );
+        assert_eq!(
+            error_messages(r#"input I { a: Int, "stray", }"#),
+            [r#"description not allowed here "stray""#]
+        );
+        assert_eq!(
+            error_messages(r#"type Query { a("stray"): Int }"#),
+            [r#"description not allowed here "stray""#]
+        );
+        assert_eq!(
+            error_messages(r#"extend type "stray" Query @dir"#),
+            [r#"description not allowed here "stray""#]
+        );
+        assert_eq!(
+            error_messages(r#""stray" fragment F on Query { a }"#),
+            [r#"description not allowed here "stray""#]
+        );
+
+        let cst = Parser::new(r#"type Query { a: Int "stray" "b field" b: Int }"#).parse();
+        let Some(cst::Definition::ObjectTypeDefinition(def)) = cst.document().definitions().next()
+        else {
+            panic!("expected an object type definition")
+        };
+        let fields: Vec<_> = def
+            .fields_definition()
+            .unwrap()
+            .field_definitions()
+            .collect();
+        assert_eq!(fields.len(), 2);
+        assert!(fields[0].description().is_none());
+        let description: String = fields[1]
+            .description()
+            .unwrap()
+            .string_value()
+            .unwrap()
+            .into();
+        assert_eq!(description, "b field");
+    }
 }
@@ -24,6 +24,7 @@ pub(crate) fn directive_definition(p: &mut Parser) {
     if let Some("directive") = p.peek_data() {
         p.bump(SyntaxKind::directive_KW);
     }
+    description::description_after_keyword(p);
 
     match p.peek() {
         Some(T![@]) => p.bump(S![@]),
@@ -34,10 +35,12 @@ pub(crate) fn directive_definition(p: &mut Parser) {
     if let Some(T!['(']) = p.peek() {
         let _g = p.start_node(SyntaxKind::ARGUMENTS_DEFINITION);
         p.bump(S!['(']);
-        if let Some(TokenKind::Name | TokenKind::StringValue) = p.peek() {
-            input::input_value_definition(p);
-        } else {
-            p.err("expected an Argument Definition");
+        match p.peek() {
+            Some(TokenKind::Name | TokenKind::StringValue) => input::input_value_definition(p),
+            Some(T![')']) => {
+                p.err_unless_lenient("expected an Argument Definition", "arguments definition")
+            }
+            _ => p.err("expected an Argument Definition"),
         }
         p.peek_while(|p, kind| match kind {
             TokenKind::Name | TokenKind::StringValue => {
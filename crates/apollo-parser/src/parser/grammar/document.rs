@@ -1,3 +1,4 @@
+use crate::parser::grammar::description;
 use crate::parser::grammar::directive;
 use crate::parser::grammar::enum_;
 use crate::parser::grammar::extensions;
@@ -11,6 +12,7 @@ use crate::parser::grammar::schema;
 use crate::parser::grammar::union_;
 use crate::Parser;
 use crate::SyntaxKind;
+use crate::SyntaxNode;
 use crate::TokenKind;
 use std::ops::ControlFlow;
 
@@ -21,40 +23,66 @@ use std::ops::ControlFlow;
 pub(crate) fn document(p: &mut Parser) {
     let doc = p.start_node(SyntaxKind::DOCUMENT);
 
-    p.peek_while(|p, kind| {
-        assert_eq!(
-            p.recursion_limit.current, 0,
-            "unbalanced limit increment / decrement"
-        );
-
-        match kind {
-            TokenKind::StringValue => {
-                if let Some(def) = p.peek_data_n(2) {
-                    select_definition(def, p);
-                } else {
-                    p.err_and_pop("expected a definition after this StringValue");
-                }
-            }
-            TokenKind::Name => {
-                let def = p.peek_data().unwrap();
-                select_definition(def, p);
-            }
-            TokenKind::LCurly => {
-                let def = p.peek_data().unwrap();
-                select_definition(def, p);
-            }
-            TokenKind::Eof => return ControlFlow::Break(()),
-            _ => p.err_and_pop("expected a StringValue, Name or OperationDefinition"),
-        }
-
-        ControlFlow::Continue(())
-    });
+    p.peek_while(definition);
 
     p.push_ignored();
 
     doc.finish_node();
 }
 
+/// Parse a document one definition at a time, each into its own syntax tree
+/// that is passed to `each` and then dropped.
+///
+/// Every tree has a DOCUMENT root node, containing one definition
+/// or the tokens that were skipped because of a syntax error.
+pub(crate) fn streaming_document(p: &mut Parser, mut each: impl FnMut(SyntaxNode)) {
+    loop {
+        let doc = p.start_root_node(SyntaxKind::DOCUMENT);
+        let done = match p.peek() {
+            Some(kind) => definition(p, kind).is_break(),
+            None => true,
+        };
+        if done {
+            p.push_ignored();
+        }
+        doc.finish_node();
+        each(SyntaxNode::new_root(p.take_green_node()));
+        if done {
+            break;
+        }
+    }
+}
+
+fn definition(p: &mut Parser, kind: TokenKind) -> ControlFlow<()> {
+    assert_eq!(
+        p.recursion_limit.current, 0,
+        "unbalanced limit increment / decrement"
+    );
+
+    match kind {
+        TokenKind::StringValue => match p.peek_data_n(2) {
+            // Executable definitions and extensions cannot have a description
+            Some("query" | "mutation" | "subscription" | "fragment" | "extend" | "{") => {
+                description::discard_description(p)
+            }
+            Some(def) => select_definition(def, p),
+            None => p.err_and_pop("expected a definition after this StringValue"),
+        },
+        TokenKind::Name => {
+            let def = p.peek_data().unwrap();
+            select_definition(def, p);
+        }
+        TokenKind::LCurly => {
+            let def = p.peek_data().unwrap();
+            select_definition(def, p);
+        }
+        TokenKind::Eof => return ControlFlow::Break(()),
+        _ => p.err_and_pop("expected a StringValue, Name or OperationDefinition"),
+    }
+
+    ControlFlow::Continue(())
+}
+
 fn select_definition(def: &str, p: &mut Parser) {
     match def {
         "directive" => directive::directive_definition(p),
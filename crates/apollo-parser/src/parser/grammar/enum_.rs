@@ -26,6 +26,7 @@ pub(crate) fn enum_type_definition(p: &mut Parser) {
     if let Some("enum") = p.peek_data() {
         p.bump(SyntaxKind::enum_KW);
     }
+    description::description_after_keyword(p);
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
@@ -50,6 +51,7 @@ pub(crate) fn enum_type_extension(p: &mut Parser) {
     let _g = p.start_node(SyntaxKind::ENUM_TYPE_EXTENSION);
     p.bump(SyntaxKind::extend_KW);
     p.bump(SyntaxKind::enum_KW);
+    description::discard_description(p);
 
     let mut meets_requirements = false;
 
@@ -83,6 +85,9 @@ pub(crate) fn enum_values_definition(p: &mut Parser) {
 
     match p.peek() {
         Some(TokenKind::Name | TokenKind::StringValue) => enum_value_definition(p),
+        Some(T!['}']) => {
+            p.err_unless_lenient("expected Enum Value Definition", "enum values definition")
+        }
         _ => p.err("expected Enum Value Definition"),
     }
 
@@ -102,6 +107,10 @@ pub(crate) fn enum_values_definition(p: &mut Parser) {
 /// *EnumValueDefinition*:
 ///     Description? EnumValue Directives[Const]?
 pub(crate) fn enum_value_definition(p: &mut Parser) {
+    if description::discard_stray_description(p) {
+        return;
+    }
+
     if let Some(TokenKind::Name | TokenKind::StringValue) = p.peek() {
         let _guard = p.start_node(SyntaxKind::ENUM_VALUE_DEFINITION);
 
@@ -53,6 +53,7 @@ pub(crate) fn fields_definition(p: &mut Parser) {
 
     match p.peek() {
         Some(TokenKind::Name | TokenKind::StringValue) => field_definition(p),
+        Some(T!['}']) => p.err_unless_lenient("expected Field Definition", "fields definition"),
         _ => p.err("expected Field Definition"),
     }
 
@@ -71,6 +72,10 @@ pub(crate) fn fields_definition(p: &mut Parser) {
 /// *FieldDefinition*:
 ///     Description? Name ArgumentsDefinition? **:** Type Directives[Const]?
 pub(crate) fn field_definition(p: &mut Parser) {
+    if description::discard_stray_description(p) {
+        return;
+    }
+
     let _guard = p.start_node(SyntaxKind::FIELD_DEFINITION);
 
     if let Some(TokenKind::StringValue) = p.peek() {
@@ -26,6 +26,7 @@ pub(crate) fn input_object_type_definition(p: &mut Parser) {
     if let Some("input") = p.peek_data() {
         p.bump(SyntaxKind::input_KW);
     }
+    description::description_after_keyword(p);
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
@@ -50,6 +51,7 @@ pub(crate) fn input_object_type_extension(p: &mut Parser) {
     let _g = p.start_node(SyntaxKind::INPUT_OBJECT_TYPE_EXTENSION);
     p.bump(SyntaxKind::extend_KW);
     p.bump(SyntaxKind::input_KW);
+    description::discard_description(p);
 
     let mut meets_requirements = false;
 
@@ -80,10 +82,13 @@ pub(crate) fn input_object_type_extension(p: &mut Parser) {
 pub(crate) fn input_fields_definition(p: &mut Parser) {
     let _g = p.start_node(SyntaxKind::INPUT_FIELDS_DEFINITION);
     p.bump(S!['{']);
-    if let Some(TokenKind::Name | TokenKind::StringValue) = p.peek() {
-        input_value_definition(p);
-    } else {
-        p.err("expected an Input Value Definition");
+    match p.peek() {
+        Some(TokenKind::Name | TokenKind::StringValue) => input_value_definition(p),
+        Some(T!['}']) => p.err_unless_lenient(
+            "expected an Input Value Definition",
+            "input fields definition",
+        ),
+        _ => p.err("expected an Input Value Definition"),
     }
     p.peek_while(|p, kind| {
         if matches!(kind, TokenKind::Name | TokenKind::StringValue) {
@@ -102,6 +107,10 @@ pub(crate) fn input_fields_definition(p: &mut Parser) {
 /// *InputValueDefinition*:
 ///     Description? Name **:** Type DefaultValue? Directives[Const]?
 pub(crate) fn input_value_definition(p: &mut Parser) {
+    if description::discard_stray_description(p) {
+        return;
+    }
+
     let _guard = p.start_node(SyntaxKind::INPUT_VALUE_DEFINITION);
 
     if let Some(TokenKind::StringValue) = p.peek() {
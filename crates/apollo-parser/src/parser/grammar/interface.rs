@@ -23,6 +23,7 @@ pub(crate) fn interface_type_definition(p: &mut Parser) {
     if let Some("interface") = p.peek_data() {
         p.bump(SyntaxKind::interface_KW);
     }
+    description::description_after_keyword(p);
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
@@ -52,6 +53,7 @@ pub(crate) fn interface_type_extension(p: &mut Parser) {
     let _g = p.start_node(SyntaxKind::INTERFACE_TYPE_EXTENSION);
     p.bump(SyntaxKind::extend_KW);
     p.bump(SyntaxKind::interface_KW);
+    description::discard_description(p);
 
     let mut meets_requirements = false;
 
@@ -26,6 +26,7 @@ pub(crate) fn object_type_definition(p: &mut Parser) {
     if let Some("type") = p.peek_data() {
         p.bump(SyntaxKind::type_KW);
     }
+    description::description_after_keyword(p);
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
@@ -57,6 +58,7 @@ pub(crate) fn object_type_extension(p: &mut Parser) {
     let _g = p.start_node(SyntaxKind::OBJECT_TYPE_EXTENSION);
     p.bump(SyntaxKind::extend_KW);
     p.bump(SyntaxKind::type_KW);
+    description::discard_description(p);
 
     // Use this variable to see if any of ImplementsInterfacs, Directives or
     // FieldsDefinitions is provided. If none are present, we push an error.
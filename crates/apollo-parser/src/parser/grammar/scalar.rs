@@ -21,6 +21,7 @@ pub(crate) fn scalar_type_definition(p: &mut Parser) {
     if let Some("scalar") = p.peek_data() {
         p.bump(SyntaxKind::scalar_KW);
     }
+    description::description_after_keyword(p);
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
@@ -40,6 +41,7 @@ pub(crate) fn scalar_type_extension(p: &mut Parser) {
     let _g = p.start_node(SyntaxKind::SCALAR_TYPE_EXTENSION);
     p.bump(SyntaxKind::extend_KW);
     p.bump(SyntaxKind::scalar_KW);
+    description::discard_description(p);
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
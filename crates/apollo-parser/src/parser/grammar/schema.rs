@@ -41,6 +41,7 @@ pub(crate) fn schema_definition(p: &mut Parser) {
     if let Some("schema") = p.peek_data() {
         p.bump(SyntaxKind::schema_KW);
     }
+    description::description_after_keyword(p);
 
     if let Some(T![@]) = p.peek() {
         directive::directives(p, Constness::Const);
@@ -71,6 +72,7 @@ pub(crate) fn schema_extension(p: &mut Parser) {
     let _g = p.start_node(SyntaxKind::SCHEMA_EXTENSION);
     p.bump(SyntaxKind::extend_KW);
     p.bump(SyntaxKind::schema_KW);
+    description::discard_description(p);
 
     let mut meets_requirements = false;
 
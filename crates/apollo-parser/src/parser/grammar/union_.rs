@@ -25,6 +25,7 @@ pub(crate) fn union_type_definition(p: &mut Parser) {
     if let Some("union") = p.peek_data() {
         p.bump(SyntaxKind::union_KW);
     }
+    description::description_after_keyword(p);
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
@@ -49,6 +50,7 @@ pub(crate) fn union_type_extension(p: &mut Parser) {
     let _g = p.start_node(SyntaxKind::UNION_TYPE_EXTENSION);
     p.bump(SyntaxKind::extend_KW);
     p.bump(SyntaxKind::union_KW);
+    description::discard_description(p);
 
     let mut meets_requirements = false;
 
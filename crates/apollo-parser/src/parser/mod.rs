@@ -1,7 +1,9 @@
+mod binary;
 mod generated;
 mod language;
 mod syntax_tree;
 mod token_text;
+mod visitor;
 
 pub(crate) mod grammar;
 
@@ -13,6 +15,7 @@ use crate::Error;
 use crate::LimitTracker;
 use crate::Token;
 use crate::TokenKind;
+pub use binary::DecodeError;
 pub use generated::syntax_kind::SyntaxKind;
 pub use language::SyntaxElement;
 pub use language::SyntaxNode;
@@ -26,6 +29,8 @@ pub use syntax_tree::SyntaxTree;
 // pub(crate) use language::GraphQLLanguage;
 pub(crate) use syntax_tree::SyntaxTreeBuilder;
 pub(crate) use token_text::TokenText;
+pub use visitor::ParseSummary;
+pub use visitor::Visitor;
 
 /// Parse GraphQL schemas or queries into a typed CST.
 ///
@@ -90,6 +95,10 @@ pub struct Parser<'input> {
     ignored: Vec<Token<'input>>,
     /// The list of syntax errors we've accumulated so far.
     errors: Vec<crate::Error>,
+    /// Deviations from the spec that were accepted in lenient mode.
+    warnings: Vec<crate::Error>,
+    /// Accept known deviations from the spec with warnings?
+    lenient: bool,
     /// The limit to apply to parsing.
     recursion_limit: LimitTracker,
     /// Accept parsing errors?
@@ -110,6 +119,10 @@ pub struct Parser<'input> {
 const DEFAULT_RECURSION_LIMIT: usize = 500;
 
 impl<'input> Parser<'input> {
+    /// The recursion limit used unless another one is set with
+    /// [`recursion_limit`][Self::recursion_limit]
+    pub const DEFAULT_RECURSION_LIMIT: usize = DEFAULT_RECURSION_LIMIT;
+
     /// Create a new instance of a parser given an input string.
     pub fn new(input: &'input str) -> Self {
         let lexer = Lexer::new(input);
@@ -120,6 +133,8 @@ impl<'input> Parser<'input> {
             builder: Rc::new(RefCell::new(SyntaxTreeBuilder::new())),
             ignored: vec![],
             errors: Vec::new(),
+            warnings: Vec::new(),
+            lenient: false,
             recursion_limit: LimitTracker::new(DEFAULT_RECURSION_LIMIT),
             accept_errors: true,
         }
@@ -140,6 +155,34 @@ impl<'input> Parser<'input> {
         self
     }
 
+    /// Configure whether to accept known deviations from the GraphQL grammar.
+    ///
+    /// By default the parser is strict: anything outside the
+    /// [October 2021 grammar](https://spec.graphql.org/October2021/#sec-Appendix-Grammar-Summary)
+    /// is reported as an error.
+    /// In lenient mode, constructs that are commonly found in the wild
+    /// are parsed as if they were valid and reported in [`SyntaxTree::warnings`] instead.
+    /// Currently these are:
+    ///
+    /// * Empty bodies `{}` for object, interface, input object, and enum types
+    /// * Empty argument lists `()`, and empty argument definition lists
+    ///
+    /// ```rust
+    /// use apollo_parser::Parser;
+    ///
+    /// let input = "type Query {}";
+    /// let cst = Parser::new(input).parse();
+    /// assert_eq!(cst.errors().len(), 1);
+    ///
+    /// let cst = Parser::new(input).lenient(true).parse();
+    /// assert_eq!(cst.errors().len(), 0);
+    /// assert_eq!(cst.warnings().len(), 1);
+    /// ```
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
     /// Parse the current tokens.
     pub fn parse(mut self) -> SyntaxTree<Document> {
         grammar::document::document(&mut self);
@@ -147,8 +190,12 @@ impl<'input> Parser<'input> {
         let builder = Rc::try_unwrap(self.builder)
             .expect("More than one reference to builder left")
             .into_inner();
-        let builder =
-            builder.finish_document(self.errors, self.recursion_limit, self.lexer.limit_tracker);
+        let builder = builder.finish_document(
+            self.errors,
+            self.warnings,
+            self.recursion_limit,
+            self.lexer.limit_tracker,
+        );
 
         match builder {
             syntax_tree::SyntaxTreeWrapper::Document(tree) => tree,
@@ -159,6 +206,45 @@ impl<'input> Parser<'input> {
         }
     }
 
+    /// Parse the current tokens, calling `visitor` methods for each top-level definition
+    /// instead of returning a syntax tree for the whole document.
+    ///
+    /// This does not emit events while tokens are consumed. Each top-level definition
+    /// is first parsed into a complete syntax tree of its own, including whitespace and comments,
+    /// which is then visited in document order and dropped before parsing the next definition.
+    /// Besides the input string and the errors found so far, memory usage is therefore
+    /// bounded by the syntax tree of the largest definition rather than that of the whole document.
+    /// A document made of one very large definition uses about as much memory as [`Parser::parse`].
+    ///
+    /// ```rust
+    /// use apollo_parser::cst;
+    /// use apollo_parser::Parser;
+    /// use apollo_parser::Visitor;
+    ///
+    /// struct CountFields(usize);
+    ///
+    /// impl Visitor for CountFields {
+    ///     fn enter_field_definition(&mut self, _node: &cst::FieldDefinition) {
+    ///         self.0 += 1
+    ///     }
+    /// }
+    ///
+    /// let mut count = CountFields(0);
+    /// let summary = Parser::new("type Query { a: Int b: Int }").parse_with_visitor(&mut count);
+    /// assert_eq!(summary.errors().len(), 0);
+    /// assert_eq!(count.0, 2);
+    /// ```
+    pub fn parse_with_visitor(mut self, visitor: &mut (impl Visitor + ?Sized)) -> ParseSummary {
+        grammar::document::streaming_document(&mut self, |root| visitor::visit(visitor, root));
+
+        ParseSummary {
+            errors: self.errors,
+            warnings: self.warnings,
+            recursion_limit: self.recursion_limit,
+            token_limit: self.lexer.limit_tracker,
+        }
+    }
+
     /// Parse a selection set with optional outer braces.
     /// This is the expected format of the string value of the `fields` argument of some directives
     /// like [`@requires`](https://www.apollographql.com/docs/federation/federated-types/federated-directives/#requires).
@@ -170,6 +256,7 @@ impl<'input> Parser<'input> {
             .into_inner();
         let builder = builder.finish_selection_set(
             self.errors,
+            self.warnings,
             self.recursion_limit,
             self.lexer.limit_tracker,
         );
@@ -192,8 +279,12 @@ impl<'input> Parser<'input> {
         let builder = Rc::try_unwrap(self.builder)
             .expect("More than one reference to builder left")
             .into_inner();
-        let builder =
-            builder.finish_type(self.errors, self.recursion_limit, self.lexer.limit_tracker);
+        let builder = builder.finish_type(
+            self.errors,
+            self.warnings,
+            self.recursion_limit,
+            self.lexer.limit_tracker,
+        );
 
         match builder {
             syntax_tree::SyntaxTreeWrapper::Type(tree) => tree,
@@ -304,6 +395,28 @@ impl<'input> Parser<'input> {
         self.push_err(err);
     }
 
+    /// Report an `empty` list that the grammar requires to have at least one item,
+    /// at the current location.
+    ///
+    /// In lenient mode this is recorded as a warning, otherwise it is an error with `message`
+    /// like [`Parser::err`].
+    pub(crate) fn err_unless_lenient(&mut self, message: &str, empty: &str) {
+        if !self.lenient {
+            self.err(message);
+            return;
+        }
+        let Some(current) = self.current() else {
+            return;
+        };
+        let message = format!("empty {empty} is not allowed by the GraphQL specification");
+        let warning = if current.kind == TokenKind::Eof {
+            Error::eof(message, current.index())
+        } else {
+            Error::with_loc(message, current.data().to_string(), current.index())
+        };
+        self.warnings.push(warning);
+    }
+
     /// Create a parser error at the current location and eat the responsible token.
     pub(crate) fn err_and_pop(&mut self, message: &str) {
         self.push_ignored();
@@ -397,6 +510,18 @@ impl<'input> Parser<'input> {
             .expect("Could not pop a token from the lexer")
     }
 
+    /// Finish the tree built so far and start a new one.
+    pub(crate) fn take_green_node(&mut self) -> rowan::GreenNode {
+        let builder = std::mem::replace(
+            &mut self.builder,
+            Rc::new(RefCell::new(SyntaxTreeBuilder::new())),
+        );
+        Rc::try_unwrap(builder)
+            .expect("More than one reference to builder left")
+            .into_inner()
+            .finish()
+    }
+
     /// Insert a token into the syntax tree.
     pub(crate) fn push_token(&mut self, kind: SyntaxKind, token: Token) {
         self.builder.borrow_mut().token(kind, token.data())
@@ -418,6 +543,19 @@ impl<'input> Parser<'input> {
         guard
     }
 
+    /// Start the root node of a new tree.
+    ///
+    /// Unlike [`start_node`][Self::start_node], ignored tokens seen before this node
+    /// go inside it, since a tree can only have one root.
+    pub(crate) fn start_root_node(&mut self, kind: SyntaxKind) -> NodeGuard {
+        self.builder.borrow_mut().start_node(kind);
+        let guard = NodeGuard::new(self.builder.clone());
+        self.push_ignored();
+        self.skip_ignored();
+
+        guard
+    }
+
     /// Set a checkpoint for *maybe* wrapping the following parse tree in some
     /// other node.
     pub(crate) fn checkpoint_node(&mut self) -> Checkpoint {
@@ -516,7 +654,12 @@ impl<'input> Parser<'input> {
             .map(Result::Ok)
             .chain(self.lexer.clone())
             .filter_map(Result::ok)
-            .filter(|token| !matches!(token.kind(), TokenKind::Whitespace | TokenKind::Comment))
+            .filter(|token| {
+                !matches!(
+                    token.kind(),
+                    TokenKind::Whitespace | TokenKind::Comment | TokenKind::Comma
+                )
+            })
             .nth(n - 1)
     }
 
@@ -947,4 +1090,44 @@ mod tests {
         let parser = Parser::new(source).token_limit(3);
         let _cst = parser.parse();
     }
+
+    #[test]
+    fn lenient_empty_bodies() {
+        let source = r#"
+            type Query {}
+            interface Node {}
+            input Filter {}
+            enum Empty {}
+            directive @noArgs() on FIELD
+            type Mutation { run(): Boolean @tag() }
+        "#;
+
+        let cst = Parser::new(source).parse();
+        assert_eq!(cst.errors().len(), 7);
+        assert_eq!(cst.warnings().len(), 0);
+
+        let cst = Parser::new(source).lenient(true).parse();
+        assert_eq!(cst.errors().len(), 0);
+        let warnings = cst.warnings().map(|w| w.message()).collect::<Vec<_>>();
+        assert_eq!(
+            warnings,
+            [
+                "empty fields definition is not allowed by the GraphQL specification",
+                "empty fields definition is not allowed by the GraphQL specification",
+                "empty input fields definition is not allowed by the GraphQL specification",
+                "empty enum values definition is not allowed by the GraphQL specification",
+                "empty arguments definition is not allowed by the GraphQL specification",
+                "empty arguments definition is not allowed by the GraphQL specification",
+                "empty arguments list is not allowed by the GraphQL specification",
+            ]
+        );
+        assert_eq!(cst.document().definitions().count(), 6);
+    }
+
+    #[test]
+    fn lenient_still_rejects_invalid_syntax() {
+        let cst = Parser::new("type Query { : }").lenient(true).parse();
+        assert_eq!(cst.warnings().len(), 0);
+        assert_ne!(cst.errors().len(), 0);
+    }
 }
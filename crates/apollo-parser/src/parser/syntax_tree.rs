@@ -58,9 +58,10 @@ pub(crate) enum SyntaxTreeWrapper {
 pub struct SyntaxTree<T: CstNode = cst::Document> {
     pub(crate) green: GreenNode,
     pub(crate) errors: Vec<crate::Error>,
+    pub(crate) warnings: Vec<crate::Error>,
     pub(crate) recursion_limit: LimitTracker,
     pub(crate) token_limit: LimitTracker,
-    _phantom: PhantomData<fn() -> T>,
+    pub(crate) _phantom: PhantomData<fn() -> T>,
 }
 
 const _: () = {
@@ -76,6 +77,15 @@ impl<T: CstNode> SyntaxTree<T> {
         self.errors.iter()
     }
 
+    /// Get a reference to the syntax tree's warnings.
+    ///
+    /// These are deviations from the GraphQL grammar that were accepted
+    /// because the parser was configured with [`Parser::lenient`][crate::Parser::lenient].
+    /// Always empty in the default strict mode.
+    pub fn warnings(&self) -> Iter<'_, crate::Error> {
+        self.warnings.iter()
+    }
+
     /// Get the syntax tree's recursion limit.
     pub fn recursion_limit(&self) -> LimitTracker {
         self.recursion_limit
@@ -217,9 +227,14 @@ impl SyntaxTreeBuilder {
         self.builder.token(rowan::SyntaxKind(kind as u16), text);
     }
 
+    pub(crate) fn finish(self) -> GreenNode {
+        self.builder.finish()
+    }
+
     pub(crate) fn finish_document(
         self,
         errors: Vec<Error>,
+        warnings: Vec<Error>,
         recursion_limit: LimitTracker,
         token_limit: LimitTracker,
     ) -> SyntaxTreeWrapper {
@@ -227,6 +242,7 @@ impl SyntaxTreeBuilder {
             green: self.builder.finish(),
             // TODO: keep the errors in the builder rather than pass it in here?
             errors,
+            warnings,
             // TODO: keep the recursion and token limits in the builder rather than pass it in here?
             recursion_limit,
             token_limit,
@@ -237,6 +253,7 @@ impl SyntaxTreeBuilder {
     pub(crate) fn finish_selection_set(
         self,
         errors: Vec<Error>,
+        warnings: Vec<Error>,
         recursion_limit: LimitTracker,
         token_limit: LimitTracker,
     ) -> SyntaxTreeWrapper {
@@ -244,6 +261,7 @@ impl SyntaxTreeBuilder {
             green: self.builder.finish(),
             // TODO: keep the errors in the builder rather than pass it in here?
             errors,
+            warnings,
             // TODO: keep the recursion and token limits in the builder rather than pass it in here?
             recursion_limit,
             token_limit,
@@ -254,6 +272,7 @@ impl SyntaxTreeBuilder {
     pub(crate) fn finish_type(
         self,
         errors: Vec<Error>,
+        warnings: Vec<Error>,
         recursion_limit: LimitTracker,
         token_limit: LimitTracker,
     ) -> SyntaxTreeWrapper {
@@ -261,6 +280,7 @@ impl SyntaxTreeBuilder {
             green: self.builder.finish(),
             // TODO: keep the errors in the builder rather than pass it in here?
             errors,
+            warnings,
             // TODO: keep the recursion and token limits in the builder rather than pass it in here?
             recursion_limit,
             token_limit,
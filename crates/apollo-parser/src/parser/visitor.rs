@@ -0,0 +1,218 @@
+//! Push-based parsing, with callbacks instead of a syntax tree for the whole document.
+//!
+//! Callbacks run on a short-lived syntax tree for each top-level definition,
+//! once that definition is fully parsed.
+
+use crate::cst;
+use crate::cst::CstNode;
+use crate::Error;
+use crate::LimitTracker;
+use crate::SyntaxKind;
+use crate::SyntaxNode;
+use rowan::WalkEvent;
+use std::slice::Iter;
+
+/// Errors and limits from [`Parser::parse_with_visitor`][crate::Parser::parse_with_visitor],
+/// the parts of a [`SyntaxTree`][crate::SyntaxTree] other than the tree itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSummary {
+    pub(crate) errors: Vec<Error>,
+    pub(crate) warnings: Vec<Error>,
+    pub(crate) recursion_limit: LimitTracker,
+    pub(crate) token_limit: LimitTracker,
+}
+
+impl ParseSummary {
+    /// Get a reference to the syntax errors.
+    pub fn errors(&self) -> Iter<'_, Error> {
+        self.errors.iter()
+    }
+
+    /// Get a reference to the deviations from the grammar that were accepted in lenient mode.
+    pub fn warnings(&self) -> Iter<'_, Error> {
+        self.warnings.iter()
+    }
+
+    /// Get the recursion limit and high-water mark of recursion usage.
+    pub fn recursion_limit(&self) -> LimitTracker {
+        self.recursion_limit
+    }
+
+    /// Get the token limit and number of tokens lexed.
+    pub fn token_limit(&self) -> LimitTracker {
+        self.token_limit
+    }
+}
+
+macro_rules! visitor {
+    ($($kind: ident => $node: ident, $enter: ident, $leave: ident;)*) => {
+        /// Callbacks invoked by [`Parser::parse_with_visitor`][crate::Parser::parse_with_visitor]
+        /// for the nodes of each top-level definition, after that definition is parsed.
+        ///
+        /// For each node, `enter_*` is called before any of its descendants are visited
+        /// and `leave_*` after all of them. All methods do nothing by default,
+        /// so implementations only override those they need.
+        ///
+        /// Nodes are only valid until the top-level definition containing them is done,
+        /// after which its syntax tree is dropped.
+        /// They can be cloned to keep them for longer.
+        pub trait Visitor {
+            $(
+                #[doc = concat!("Called when starting to visit a [`cst::", stringify!($node), "`].")]
+                fn $enter(&mut self, _node: &cst::$node) {}
+
+                #[doc = concat!("Called when done visiting a [`cst::", stringify!($node), "`].")]
+                fn $leave(&mut self, _node: &cst::$node) {}
+            )*
+        }
+
+        fn enter(visitor: &mut (impl Visitor + ?Sized), node: SyntaxNode) {
+            match node.kind() {
+                $(
+                    SyntaxKind::$kind => {
+                        if let Some(node) = cst::$node::cast(node) {
+                            visitor.$enter(&node)
+                        }
+                    }
+                )*
+                _ => {}
+            }
+        }
+
+        fn leave(visitor: &mut (impl Visitor + ?Sized), node: SyntaxNode) {
+            match node.kind() {
+                $(
+                    SyntaxKind::$kind => {
+                        if let Some(node) = cst::$node::cast(node) {
+                            visitor.$leave(&node)
+                        }
+                    }
+                )*
+                _ => {}
+            }
+        }
+    };
+}
+
+visitor! {
+    OPERATION_DEFINITION => OperationDefinition, enter_operation_definition, leave_operation_definition;
+    FRAGMENT_DEFINITION => FragmentDefinition, enter_fragment_definition, leave_fragment_definition;
+    DIRECTIVE_DEFINITION => DirectiveDefinition, enter_directive_definition, leave_directive_definition;
+    SCHEMA_DEFINITION => SchemaDefinition, enter_schema_definition, leave_schema_definition;
+    SCALAR_TYPE_DEFINITION => ScalarTypeDefinition, enter_scalar_type_definition, leave_scalar_type_definition;
+    OBJECT_TYPE_DEFINITION => ObjectTypeDefinition, enter_object_type_definition, leave_object_type_definition;
+    INTERFACE_TYPE_DEFINITION => InterfaceTypeDefinition, enter_interface_type_definition, leave_interface_type_definition;
+    UNION_TYPE_DEFINITION => UnionTypeDefinition, enter_union_type_definition, leave_union_type_definition;
+    ENUM_TYPE_DEFINITION => EnumTypeDefinition, enter_enum_type_definition, leave_enum_type_definition;
+    INPUT_OBJECT_TYPE_DEFINITION => InputObjectTypeDefinition, enter_input_object_type_definition, leave_input_object_type_definition;
+    SCHEMA_EXTENSION => SchemaExtension, enter_schema_extension, leave_schema_extension;
+    SCALAR_TYPE_EXTENSION => ScalarTypeExtension, enter_scalar_type_extension, leave_scalar_type_extension;
+    OBJECT_TYPE_EXTENSION => ObjectTypeExtension, enter_object_type_extension, leave_object_type_extension;
+    INTERFACE_TYPE_EXTENSION => InterfaceTypeExtension, enter_interface_type_extension, leave_interface_type_extension;
+    UNION_TYPE_EXTENSION => UnionTypeExtension, enter_union_type_extension, leave_union_type_extension;
+    ENUM_TYPE_EXTENSION => EnumTypeExtension, enter_enum_type_extension, leave_enum_type_extension;
+    INPUT_OBJECT_TYPE_EXTENSION => InputObjectTypeExtension, enter_input_object_type_extension, leave_input_object_type_extension;
+    ROOT_OPERATION_TYPE_DEFINITION => RootOperationTypeDefinition, enter_root_operation_type_definition, leave_root_operation_type_definition;
+    FIELD_DEFINITION => FieldDefinition, enter_field_definition, leave_field_definition;
+    INPUT_VALUE_DEFINITION => InputValueDefinition, enter_input_value_definition, leave_input_value_definition;
+    ENUM_VALUE_DEFINITION => EnumValueDefinition, enter_enum_value_definition, leave_enum_value_definition;
+    VARIABLE_DEFINITION => VariableDefinition, enter_variable_definition, leave_variable_definition;
+    FIELD => Field, enter_field, leave_field;
+    FRAGMENT_SPREAD => FragmentSpread, enter_fragment_spread, leave_fragment_spread;
+    INLINE_FRAGMENT => InlineFragment, enter_inline_fragment, leave_inline_fragment;
+    DIRECTIVE => Directive, enter_directive, leave_directive;
+    ARGUMENT => Argument, enter_argument, leave_argument;
+}
+
+/// Visit the nodes of a syntax tree for one top-level definition.
+pub(crate) fn visit(visitor: &mut (impl Visitor + ?Sized), root: SyntaxNode) {
+    for event in root.preorder() {
+        match event {
+            WalkEvent::Enter(node) => enter(visitor, node),
+            WalkEvent::Leave(node) => leave(visitor, node),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Visitor;
+    use crate::cst;
+    use crate::Parser;
+
+    #[derive(Default)]
+    struct Outline {
+        events: Vec<String>,
+    }
+
+    impl Visitor for Outline {
+        fn enter_object_type_definition(&mut self, node: &cst::ObjectTypeDefinition) {
+            let name = node.name().unwrap().text();
+            self.events.push(format!("enter type {name}"));
+        }
+
+        fn leave_object_type_definition(&mut self, node: &cst::ObjectTypeDefinition) {
+            let name = node.name().unwrap().text();
+            self.events.push(format!("leave type {name}"));
+        }
+
+        fn enter_field_definition(&mut self, node: &cst::FieldDefinition) {
+            let name = node.name().unwrap().text();
+            self.events.push(format!("field {name}"));
+        }
+
+        fn enter_directive(&mut self, node: &cst::Directive) {
+            let name = node.name().unwrap().text();
+            self.events.push(format!("directive @{name}"));
+        }
+
+        fn enter_field(&mut self, node: &cst::Field) {
+            let name = node.name().unwrap().text();
+            self.events.push(format!("select {name}"));
+        }
+    }
+
+    #[test]
+    fn it_calls_visitor_methods_in_document_order() {
+        let input = r#"
+            type Query @key(fields: "id") {
+              id: ID! @deprecated
+              name: String
+            }
+            type Other { x: Int }
+            query { id }
+        "#;
+        let mut outline = Outline::default();
+        let summary = Parser::new(input).parse_with_visitor(&mut outline);
+        assert_eq!(summary.errors().len(), 0);
+        assert_eq!(
+            outline.events,
+            [
+                "enter type Query",
+                "directive @key",
+                "field id",
+                "directive @deprecated",
+                "field name",
+                "leave type Query",
+                "enter type Other",
+                "field x",
+                "leave type Other",
+                "select id",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_errors_and_keeps_visiting() {
+        let input = "type Query { a: } ! type Other { x: Int }";
+        let mut outline = Outline::default();
+        let summary = Parser::new(input).parse_with_visitor(&mut outline);
+        let cst = Parser::new(input).parse();
+        assert_eq!(
+            summary.errors().collect::<Vec<_>>(),
+            cst.errors().collect::<Vec<_>>()
+        );
+        assert_eq!(summary.token_limit(), cst.token_limit());
+        assert!(outline.events.contains(&"field x".to_owned()));
+    }
+}